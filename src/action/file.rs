@@ -1,6 +1,10 @@
-//! File operations (create, rename, delete, copy)
+//! File operations (create, rename, delete, copy, link)
 
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
 
 /// Create a new file
 pub fn create_file(parent: &Path, name: &str) -> anyhow::Result<PathBuf> {
@@ -21,6 +25,50 @@ pub fn create_dir(parent: &Path, name: &str) -> anyhow::Result<PathBuf> {
     Ok(path)
 }
 
+/// Create `name`, a path relative to `parent`, with any missing directories
+///
+/// Creates a directory when `as_dir` is set or `name` ends in `/`, a file
+/// otherwise. `parent` itself must exist, and `name` may not leave it
+/// (`..` or an absolute path). Returns the created paths, outermost first;
+/// the last one is `name`.
+pub fn create_nested(parent: &Path, name: &str, as_dir: bool) -> anyhow::Result<Vec<PathBuf>> {
+    use std::path::Component;
+
+    let relative = Path::new(name);
+    if !relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        anyhow::bail!("Invalid name '{}': must stay inside the directory", name);
+    }
+    let Some(file_name) = relative.file_name().and_then(|n| n.to_str()) else {
+        anyhow::bail!("Invalid name '{}'", name);
+    };
+    if !parent.is_dir() {
+        anyhow::bail!("Directory '{}' does not exist", parent.display());
+    }
+
+    let mut created = Vec::new();
+    let mut dir = parent.to_path_buf();
+    if let Some(dirs) = relative.parent() {
+        for component in dirs.components() {
+            dir.push(component);
+            match std::fs::create_dir(&dir) {
+                Ok(()) => created.push(dir.clone()),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && dir.is_dir() => {}
+                Err(e) => anyhow::bail!("Failed to create directory '{}': {}", dir.display(), e),
+            }
+        }
+    }
+    let path = if as_dir || name.ends_with('/') {
+        create_dir(&dir, file_name)?
+    } else {
+        create_file(&dir, file_name)?
+    };
+    created.push(path);
+    Ok(created)
+}
+
 /// Rename a file or directory
 pub fn rename(path: &Path, new_name: &str) -> anyhow::Result<PathBuf> {
     let parent = path
@@ -41,11 +89,287 @@ pub fn rename(path: &Path, new_name: &str) -> anyhow::Result<PathBuf> {
     Ok(new_path)
 }
 
-/// Delete a file or directory (move to trash)
-pub fn delete(path: &Path) -> anyhow::Result<()> {
+/// Existing entry that renaming `path` to `new_name` would replace
+///
+/// `rename` replaces an existing file silently, so callers check first.
+/// Returns None when the name is free or names `path` itself, which covers
+/// case-only renames (`readme.md` -> `README.md`) on case-insensitive
+/// filesystems where the new name already resolves to the same file.
+pub fn rename_conflict(path: &Path, new_name: &str) -> Option<PathBuf> {
+    let target = path.parent()?.join(new_name);
+    std::fs::symlink_metadata(&target).ok()?;
+    (!is_same_file(path, &target)).then_some(target)
+}
+
+#[cfg(unix)]
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (std::fs::symlink_metadata(a), std::fs::symlink_metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// What the rename prompt starts with selected
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenameSelect {
+    /// Cursor before the extension (default)
+    #[default]
+    Stem,
+    /// Cursor after the whole name
+    Name,
+}
+
+impl std::str::FromStr for RenameSelect {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "stem" => Ok(Self::Stem),
+            "name" | "all" => Ok(Self::Name),
+            _ => Err(()),
+        }
+    }
+}
+
+impl RenameSelect {
+    /// Initial cursor position (byte offset) in the rename buffer for `name`
+    ///
+    /// Only the last extension is excluded (`foo.tar|.gz`). Leading dots
+    /// belong to the name, so `.env` and `Makefile` put the cursor at the end.
+    pub fn cursor(self, name: &str, is_dir: bool) -> usize {
+        if self == Self::Name || is_dir {
+            return name.len();
+        }
+        let rest = name.trim_start_matches('.');
+        let lead = name.len() - rest.len();
+        rest.rfind('.').map_or(name.len(), |dot| lead + dot)
+    }
+}
+
+/// Move a file or directory to the system trash
+pub fn trash(path: &Path) -> anyhow::Result<()> {
     trash::delete(path).map_err(|e| anyhow::anyhow!("Failed to move to trash: {}", e))
 }
 
+/// Put trashed files and directories back where they were
+///
+/// Each path is matched with the most recently trashed item that came
+/// from it.
+#[cfg(any(
+    target_os = "windows",
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+))]
+pub fn restore_from_trash(paths: &[PathBuf]) -> anyhow::Result<()> {
+    let mut trashed = trash::os_limited::list()
+        .map_err(|e| anyhow::anyhow!("Failed to read the trash: {}", e))?;
+    let mut items = Vec::new();
+    for path in paths {
+        let newest = trashed
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.original_path() == *path)
+            .max_by_key(|(_, item)| item.time_deleted)
+            .map(|(i, _)| i)
+            .ok_or_else(|| anyhow::anyhow!("Not in the trash: {}", path.display()))?;
+        items.push(trashed.swap_remove(newest));
+    }
+    trash::os_limited::restore_all(items)
+        .map_err(|e| anyhow::anyhow!("Failed to restore from trash: {}", e))
+}
+
+/// Put trashed files and directories back where they were
+#[cfg(not(any(
+    target_os = "windows",
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+)))]
+pub fn restore_from_trash(_paths: &[PathBuf]) -> anyhow::Result<()> {
+    anyhow::bail!("Restoring from the trash is not supported on this platform")
+}
+
+/// Delete a file or directory permanently
+///
+/// Symlinks are removed themselves, never what they point to.
+pub fn delete(path: &Path) -> anyhow::Result<()> {
+    if std::fs::symlink_metadata(path)?.is_dir() {
+        std::fs::remove_dir_all(path)?;
+    } else {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Maximum number of entries visited when summarizing delete targets
+pub const DELETE_SUMMARY_LIMIT: usize = 100_000;
+
+/// Recursive totals of the paths about to be deleted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeleteSummary {
+    /// Number of top-level targets
+    pub items: usize,
+    /// Number of files (non-directories), including files inside directories
+    pub files: usize,
+    /// Total size of those files in bytes
+    pub bytes: u64,
+    /// The entry limit was hit, so the totals are lower bounds
+    pub truncated: bool,
+}
+
+/// When to ask before deleting
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConfirmDeleteMode {
+    /// Never ask
+    Off,
+    /// Always ask (default)
+    #[default]
+    Always,
+    /// Ask for directories and for deletes above the item or size limit
+    Threshold,
+}
+
+impl std::str::FromStr for ConfirmDeleteMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" | "never" => Ok(Self::Off),
+            "always" => Ok(Self::Always),
+            "threshold" => Ok(Self::Threshold),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Delete confirmation policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeleteConfirmPolicy {
+    pub mode: ConfirmDeleteMode,
+    /// Threshold mode: ask when deleting more items than this
+    pub max_items: usize,
+    /// Threshold mode: ask when deleting more bytes than this
+    pub max_bytes: u64,
+}
+
+impl Default for DeleteConfirmPolicy {
+    fn default() -> Self {
+        Self {
+            mode: ConfirmDeleteMode::Always,
+            max_items: 10,
+            max_bytes: 10 * 1024 * 1024,
+        }
+    }
+}
+
+impl DeleteConfirmPolicy {
+    /// Check if deleting `targets` (totals in `summary`) needs a confirmation
+    ///
+    /// In threshold mode directories always ask, as do totals that were
+    /// truncated and so may exceed the limits.
+    pub fn needs_confirm(&self, targets: &[PathBuf], summary: &DeleteSummary) -> bool {
+        match self.mode {
+            ConfirmDeleteMode::Off => false,
+            ConfirmDeleteMode::Always => true,
+            ConfirmDeleteMode::Threshold => {
+                summary.truncated
+                    || targets.len() > self.max_items
+                    || summary.bytes > self.max_bytes
+                    || targets
+                        .iter()
+                        .any(|path| std::fs::symlink_metadata(path).is_ok_and(|meta| meta.is_dir()))
+            }
+        }
+    }
+}
+
+/// Count files and bytes under `paths`, visiting at most `limit` entries
+///
+/// Symlinks are counted but not followed, matching what the trash moves.
+pub fn summarize_delete(paths: &[PathBuf], limit: usize) -> DeleteSummary {
+    summarize_until(paths, limit, &AtomicBool::new(false))
+}
+
+/// [`summarize_delete`] that stops early (marked truncated) once `cancel` is set
+fn summarize_until(paths: &[PathBuf], limit: usize, cancel: &AtomicBool) -> DeleteSummary {
+    let mut summary = DeleteSummary {
+        items: paths.len(),
+        ..Default::default()
+    };
+    let mut visited = 0;
+    let mut stack: Vec<PathBuf> = paths.to_vec();
+
+    while let Some(path) = stack.pop() {
+        if visited >= limit || cancel.load(Ordering::Relaxed) {
+            summary.truncated = true;
+            break;
+        }
+        visited += 1;
+
+        let Ok(meta) = std::fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if meta.is_dir() {
+            if let Ok(entries) = std::fs::read_dir(&path) {
+                stack.extend(entries.flatten().map(|e| e.path()));
+            }
+        } else {
+            summary.files += 1;
+            summary.bytes += meta.len();
+        }
+    }
+
+    summary
+}
+
+/// A [`summarize_delete`] running on a worker thread (cancelled when dropped)
+#[derive(Debug)]
+pub struct DeleteSummaryJob {
+    cancel: Arc<AtomicBool>,
+    result: Receiver<DeleteSummary>,
+}
+
+impl DeleteSummaryJob {
+    /// Start counting `paths` on a new worker thread
+    pub fn start(paths: &[PathBuf], limit: usize) -> Self {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (tx, result) = mpsc::channel();
+        let worker_paths = paths.to_vec();
+        let worker_cancel = Arc::clone(&cancel);
+        thread::spawn(move || {
+            let _ = tx.send(summarize_until(&worker_paths, limit, &worker_cancel));
+        });
+        Self { cancel, result }
+    }
+
+    /// The totals once counted (None while still counting)
+    pub fn poll(&self) -> Option<DeleteSummary> {
+        self.result.try_recv().ok()
+    }
+}
+
+impl Drop for DeleteSummaryJob {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
 /// Copy a file to a destination directory
 pub fn copy_to(src: &Path, dest_dir: &Path) -> anyhow::Result<PathBuf> {
     let file_name = src
@@ -61,10 +385,158 @@ pub fn copy_to(src: &Path, dest_dir: &Path) -> anyhow::Result<PathBuf> {
     Ok(dest)
 }
 
+/// Kind of link to create
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkKind {
+    /// Symbolic link, pointing at a relative or absolute target
+    Symlink { relative: bool },
+    /// Hard link (files only)
+    Hardlink,
+}
+
+/// Create a link named `name` in `dest_dir` pointing at `src`
+pub fn create_link(
+    src: &Path,
+    dest_dir: &Path,
+    name: &str,
+    kind: LinkKind,
+) -> anyhow::Result<PathBuf> {
+    match kind {
+        LinkKind::Symlink { relative } => create_symlink(src, dest_dir, name, relative),
+        LinkKind::Hardlink => create_hardlink(src, dest_dir, name),
+    }
+}
+
+/// Create a symbolic link named `name` in `dest_dir` pointing at `src`
+///
+/// With `relative`, the link stores the path from `dest_dir` to `src`
+/// (e.g. `../lib/a.txt`) so it survives moving the tree as a whole.
+pub fn create_symlink(
+    src: &Path,
+    dest_dir: &Path,
+    name: &str,
+    relative: bool,
+) -> anyhow::Result<PathBuf> {
+    let link = dest_dir.join(name);
+    let absolute = absolute_link_target(src)?;
+    let target = if relative {
+        let dir = dest_dir
+            .canonicalize()
+            .map_err(|e| anyhow::anyhow!("Cannot resolve '{}': {}", dest_dir.display(), e))?;
+        relative_path(&dir, &absolute)
+    } else {
+        absolute.clone()
+    };
+
+    symlink(&target, &link, absolute.is_dir())
+        .map_err(|e| anyhow::anyhow!("Failed to create symlink '{}': {}", link.display(), e))?;
+    Ok(link)
+}
+
+/// Create a hard link named `name` in `dest_dir` to the file `src`
+pub fn create_hardlink(src: &Path, dest_dir: &Path, name: &str) -> anyhow::Result<PathBuf> {
+    if src.is_dir() {
+        anyhow::bail!("Cannot hard link a directory: '{}'", src.display());
+    }
+    let link = dest_dir.join(name);
+    std::fs::hard_link(src, &link)
+        .map_err(|e| anyhow::anyhow!("Failed to create hard link '{}': {}", link.display(), e))?;
+    Ok(link)
+}
+
+/// Absolute path of a link source without resolving the source itself
+///
+/// Only the parent is canonicalized, so linking to a symlink points at the
+/// symlink rather than at what it resolves to.
+fn absolute_link_target(src: &Path) -> anyhow::Result<PathBuf> {
+    let file_name = src
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Cannot link '{}': no filename", src.display()))?;
+    let parent = src
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let parent = parent
+        .canonicalize()
+        .map_err(|e| anyhow::anyhow!("Cannot resolve '{}': {}", parent.display(), e))?;
+    Ok(parent.join(file_name))
+}
+
+/// Path from the directory `from` to `to` (both absolute)
+fn relative_path(from: &Path, to: &Path) -> PathBuf {
+    let from: Vec<_> = from.components().collect();
+    let to: Vec<_> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut path = PathBuf::new();
+    for _ in common..from.len() {
+        path.push("..");
+    }
+    for component in &to[common..] {
+        path.push(component);
+    }
+    path
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path, _is_dir: bool) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn symlink(target: &Path, link: &Path, is_dir: bool) -> std::io::Result<()> {
+    if is_dir {
+        std::os::windows::fs::symlink_dir(target, link)
+    } else {
+        std::os::windows::fs::symlink_file(target, link)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn symlink(_target: &Path, _link: &Path, _is_dir: bool) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "symlinks are not supported on this platform",
+    ))
+}
+
+/// Check whether `path` resolves to a location inside `root`
+///
+/// Symlinks and `..` are resolved through the deepest existing ancestor, so
+/// a link pointing outside the tree counts as outside. Paths that do not
+/// exist yet (rename or paste destinations) are checked by where they would
+/// be created.
+pub fn is_within_root(path: &Path, root: &Path) -> bool {
+    let Ok(root) = root.canonicalize() else {
+        return false;
+    };
+    let resolved = path.ancestors().find_map(|ancestor| {
+        let canonical = ancestor.canonicalize().ok()?;
+        let rest = path.strip_prefix(ancestor).ok()?;
+        Some(canonical.join(rest))
+    });
+    let Some(resolved) = resolved else {
+        return false;
+    };
+
+    // Resolve any `..` left in the part that does not exist yet
+    let mut normalized = PathBuf::new();
+    for component in resolved.components() {
+        match component {
+            std::path::Component::ParentDir => {
+                normalized.pop();
+            }
+            std::path::Component::CurDir => {}
+            _ => normalized.push(component),
+        }
+    }
+    normalized.starts_with(&root)
+}
+
 /// Get a unique path by appending _1, _2, etc. if needed
 ///
 /// Uses a bounded counter with timestamp fallback to mitigate TOCTOU race conditions.
-fn get_unique_path(path: &Path) -> PathBuf {
+pub(crate) fn get_unique_path(path: &Path) -> PathBuf {
     if !path.exists() {
         return path.to_path_buf();
     }
@@ -97,7 +569,7 @@ fn get_unique_path(path: &Path) -> PathBuf {
 }
 
 /// Copy directory recursively
-fn copy_dir_recursive(src: &Path, dest: &Path) -> anyhow::Result<()> {
+pub(crate) fn copy_dir_recursive(src: &Path, dest: &Path) -> anyhow::Result<()> {
     std::fs::create_dir_all(dest)?;
     for entry in std::fs::read_dir(src)? {
         let entry = entry?;
@@ -119,6 +591,95 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_summarize_delete_recursive() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("dir");
+        fs::create_dir_all(dir.join("nested/deeper")).unwrap();
+        fs::write(dir.join("a.txt"), "12345").unwrap();
+        fs::write(dir.join("nested/b.txt"), "123").unwrap();
+        fs::write(dir.join("nested/deeper/c.txt"), "12").unwrap();
+        let single = temp.path().join("single.txt");
+        fs::write(&single, "1234567890").unwrap();
+
+        let summary = summarize_delete(&[dir, single], DELETE_SUMMARY_LIMIT);
+        assert_eq!(
+            summary,
+            DeleteSummary {
+                items: 2,
+                files: 4,
+                bytes: 20,
+                truncated: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_summarize_delete_is_bounded() {
+        let temp = TempDir::new().unwrap();
+        for i in 0..10 {
+            fs::write(temp.path().join(format!("{}.txt", i)), "x").unwrap();
+        }
+
+        let summary = summarize_delete(&[temp.path().to_path_buf()], 5);
+        assert!(summary.truncated);
+        assert!(summary.files < 10);
+    }
+
+    #[test]
+    fn test_delete_confirm_threshold_matrix() {
+        let temp = TempDir::new().unwrap();
+        let small = temp.path().join("scratch.txt");
+        fs::write(&small, "tmp").unwrap();
+        let dir = temp.path().join("empty_dir");
+        fs::create_dir(&dir).unwrap();
+        let many: Vec<PathBuf> = (0..4)
+            .map(|i| {
+                let path = temp.path().join(format!("{}.log", i));
+                fs::write(&path, "x").unwrap();
+                path
+            })
+            .collect();
+
+        let policy = DeleteConfirmPolicy {
+            mode: ConfirmDeleteMode::Threshold,
+            max_items: 3,
+            max_bytes: 100,
+        };
+        let needs = |targets: &[PathBuf]| {
+            policy.needs_confirm(targets, &summarize_delete(targets, DELETE_SUMMARY_LIMIT))
+        };
+
+        // A single small file below the thresholds skips the confirmation
+        assert!(!needs(std::slice::from_ref(&small)));
+        // A directory always confirms, even an empty one
+        assert!(needs(std::slice::from_ref(&dir)));
+        // More items than the limit confirms
+        assert!(needs(&many));
+        // More bytes than the limit confirms
+        fs::write(&small, vec![b'x'; 101]).unwrap();
+        assert!(needs(std::slice::from_ref(&small)));
+    }
+
+    #[test]
+    fn test_delete_confirm_off_and_always() {
+        let summary = DeleteSummary {
+            items: 1,
+            files: 1,
+            bytes: 1,
+            truncated: false,
+        };
+        let targets = [PathBuf::from("/nonexistent/file")];
+        let policy = |mode| DeleteConfirmPolicy {
+            mode,
+            ..Default::default()
+        };
+        assert!(!policy(ConfirmDeleteMode::Off).needs_confirm(&targets, &summary));
+        assert!(policy(ConfirmDeleteMode::Always).needs_confirm(&targets, &summary));
+        assert_eq!("Threshold".parse(), Ok(ConfirmDeleteMode::Threshold));
+        assert_eq!("bogus".parse::<ConfirmDeleteMode>(), Err(()));
+    }
+
     #[test]
     fn test_create_file() {
         let temp = TempDir::new().unwrap();
@@ -148,6 +709,40 @@ mod tests {
         assert_eq!(result.file_name().unwrap(), "subdir");
     }
 
+    #[test]
+    fn test_create_nested_makes_parents() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join("a")).unwrap();
+
+        let created = create_nested(temp.path(), "a/b/c.txt", false).unwrap();
+        assert_eq!(
+            created,
+            vec![temp.path().join("a/b"), temp.path().join("a/b/c.txt")]
+        );
+        assert!(temp.path().join("a/b/c.txt").is_file());
+
+        // Existing directories are reused, an existing file is an error
+        assert_eq!(
+            create_nested(temp.path(), "a/b/d.txt", false).unwrap(),
+            vec![temp.path().join("a/b/d.txt")]
+        );
+        assert!(create_nested(temp.path(), "a/b/c.txt", false).is_err());
+        assert!(create_nested(temp.path(), "a/b/c.txt/x", false).is_err());
+    }
+
+    #[test]
+    fn test_create_nested_trailing_slash_creates_dir() {
+        let temp = TempDir::new().unwrap();
+        let created = create_nested(temp.path(), "x/y/", false).unwrap();
+        assert_eq!(created.last(), Some(&temp.path().join("x/y")));
+        assert!(temp.path().join("x/y").is_dir());
+
+        assert!(create_nested(temp.path(), "z", true).unwrap()[0].is_dir());
+        assert!(create_nested(temp.path(), "../escape.txt", false).is_err());
+        assert!(create_nested(temp.path(), "/abs.txt", false).is_err());
+        assert!(!temp.path().parent().unwrap().join("escape.txt").exists());
+    }
+
     #[test]
     fn test_rename_file() {
         let temp = TempDir::new().unwrap();
@@ -162,6 +757,33 @@ mod tests {
         assert_eq!(fs::read_to_string(&result).unwrap(), "content");
     }
 
+    #[test]
+    fn test_rename_conflict_with_existing_target() {
+        let temp = TempDir::new().unwrap();
+        let original = temp.path().join("draft.txt");
+        let existing = temp.path().join("final.txt");
+        fs::write(&original, "new").unwrap();
+        fs::write(&existing, "old").unwrap();
+
+        assert_eq!(rename_conflict(&original, "final.txt"), Some(existing));
+        assert_eq!(rename_conflict(&original, "other.txt"), None);
+        // Keeping the name is not a conflict
+        assert_eq!(rename_conflict(&original, "draft.txt"), None);
+    }
+
+    #[test]
+    fn test_rename_conflict_case_only_change() {
+        let temp = TempDir::new().unwrap();
+        let original = temp.path().join("readme.md");
+        fs::write(&original, "docs").unwrap();
+
+        // Free on case-sensitive filesystems, the same file on
+        // case-insensitive ones: never an overwrite
+        assert_eq!(rename_conflict(&original, "README.md"), None);
+        let renamed = rename(&original, "README.md").unwrap();
+        assert_eq!(fs::read_to_string(renamed).unwrap(), "docs");
+    }
+
     #[test]
     fn test_rename_dir() {
         let temp = TempDir::new().unwrap();
@@ -179,25 +801,68 @@ mod tests {
 
     #[test]
     #[ignore] // Requires Finder/trash permissions; run manually
-    fn test_delete_file() {
+    fn test_trash_file() {
         let temp = TempDir::new().unwrap();
         let file = temp.path().join("to_delete.txt");
         fs::write(&file, "content").unwrap();
 
-        delete(&file).unwrap();
+        trash(&file).unwrap();
         assert!(!file.exists());
     }
 
     #[test]
     #[ignore] // Requires Finder/trash permissions; run manually
-    fn test_delete_dir() {
+    fn test_trash_dir() {
         let temp = TempDir::new().unwrap();
         let dir = temp.path().join("to_delete");
         fs::create_dir(&dir).unwrap();
         fs::write(dir.join("file.txt"), "content").unwrap();
 
+        trash(&dir).unwrap();
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    #[ignore] // Touches the user's trash; run manually
+    fn test_restore_from_trash() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("restore_me.txt");
+        fs::write(&file, "content").unwrap();
+
+        trash(&file).unwrap();
+        assert!(!file.exists());
+        restore_from_trash(std::slice::from_ref(&file)).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "content");
+        assert!(restore_from_trash(&[temp.path().join("never_trashed")]).is_err());
+    }
+
+    #[test]
+    fn test_delete_permanently() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("to_delete.txt");
+        fs::write(&file, "content").unwrap();
+        let dir = temp.path().join("to_delete");
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("file.txt"), "content").unwrap();
+
+        delete(&file).unwrap();
         delete(&dir).unwrap();
+        assert!(!file.exists());
         assert!(!dir.exists());
+        assert!(delete(&file).is_err());
+
+        // A link to a directory goes, the directory stays
+        #[cfg(unix)]
+        {
+            let target = temp.path().join("target");
+            fs::create_dir(&target).unwrap();
+            fs::write(target.join("keep.txt"), "keep").unwrap();
+            let link = temp.path().join("link");
+            std::os::unix::fs::symlink(&target, &link).unwrap();
+            delete(&link).unwrap();
+            assert!(link.symlink_metadata().is_err());
+            assert!(target.join("keep.txt").exists());
+        }
     }
 
     #[test]
@@ -249,4 +914,117 @@ mod tests {
         assert_eq!(result.file_name().unwrap(), "file_1.txt");
         assert!(result.exists());
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_create_symlink_relative_and_absolute() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("lib")).unwrap();
+        fs::create_dir_all(root.join("bin")).unwrap();
+        let src = root.join("lib/tool.sh");
+        fs::write(&src, "echo hi").unwrap();
+
+        let relative = create_symlink(&src, &root.join("bin"), "tool", true).unwrap();
+        assert!(fs::symlink_metadata(&relative)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(
+            fs::read_link(&relative).unwrap(),
+            PathBuf::from("../lib/tool.sh")
+        );
+        assert_eq!(fs::read_to_string(&relative).unwrap(), "echo hi");
+
+        let absolute = create_symlink(&src, &root.join("bin"), "tool_abs", false).unwrap();
+        assert_eq!(fs::read_link(&absolute).unwrap(), src);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_create_hardlink() {
+        use std::os::unix::fs::MetadataExt;
+
+        let temp = TempDir::new().unwrap();
+        let src = temp.path().join("data.txt");
+        fs::write(&src, "shared").unwrap();
+
+        let link = create_hardlink(&src, temp.path(), "data_link.txt").unwrap();
+        let meta = fs::symlink_metadata(&link).unwrap();
+        assert!(meta.file_type().is_file());
+        assert_eq!(meta.ino(), fs::metadata(&src).unwrap().ino());
+        assert_eq!(meta.nlink(), 2);
+    }
+
+    #[test]
+    fn test_create_hardlink_rejects_directory() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("dir");
+        fs::create_dir(&dir).unwrap();
+
+        assert!(create_hardlink(&dir, temp.path(), "dir_link").is_err());
+    }
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(
+            relative_path(Path::new("/a/b/c"), Path::new("/a/d/e.txt")),
+            PathBuf::from("../../d/e.txt")
+        );
+        assert_eq!(
+            relative_path(Path::new("/a"), Path::new("/a/b.txt")),
+            PathBuf::from("b.txt")
+        );
+    }
+
+    #[test]
+    fn test_is_within_root_rejects_parent_escape() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("root");
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(temp.path().join("outside.txt"), "x").unwrap();
+
+        assert!(is_within_root(&root.join("src"), &root));
+        assert!(is_within_root(&root.join("src/new.rs"), &root));
+        assert!(is_within_root(&root.join("src/../new.rs"), &root));
+        assert!(!is_within_root(&root.join("../outside.txt"), &root));
+        assert!(!is_within_root(&root.join("src/../../outside.txt"), &root));
+        // Not created yet, but would land outside
+        assert!(!is_within_root(&root.join("missing/../../new.txt"), &root));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_is_within_root_rejects_symlink_outside() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("root");
+        let outside = temp.path().join("outside");
+        fs::create_dir_all(&root).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(root.join("inside.txt"), "x").unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("escape")).unwrap();
+        std::os::unix::fs::symlink(root.join("inside.txt"), root.join("alias")).unwrap();
+
+        assert!(!is_within_root(&root.join("escape"), &root));
+        assert!(!is_within_root(&root.join("escape/new.txt"), &root));
+        assert!(is_within_root(&root.join("alias"), &root));
+    }
+
+    #[test]
+    fn test_rename_cursor_before_extension() {
+        let stem = RenameSelect::Stem;
+        assert_eq!(stem.cursor("foo.tar.gz", false), "foo.tar".len());
+        assert_eq!(stem.cursor("report_final.pdf", false), "report_final".len());
+        assert_eq!(stem.cursor("Makefile", false), "Makefile".len());
+        assert_eq!(stem.cursor(".env", false), ".env".len());
+        assert_eq!(stem.cursor(".env.local", false), ".env".len());
+        assert_eq!(stem.cursor("tests.d", true), "tests.d".len());
+
+        assert_eq!(
+            RenameSelect::Name.cursor("foo.tar.gz", false),
+            "foo.tar.gz".len()
+        );
+        assert_eq!("all".parse(), Ok(RenameSelect::Name));
+        assert_eq!("stem".parse(), Ok(RenameSelect::Stem));
+    }
 }
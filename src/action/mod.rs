@@ -2,6 +2,15 @@
 
 pub mod clipboard;
 pub mod file;
+pub mod oplog;
+pub mod paste;
 
 pub use clipboard::{Clipboard, ClipboardContent};
-pub use file::{copy_to, create_dir, create_file, delete, rename};
+pub use file::{
+    copy_to, create_dir, create_file, create_hardlink, create_link, create_symlink, delete,
+    is_within_root, rename, rename_conflict, restore_from_trash, summarize_delete, trash,
+    ConfirmDeleteMode, DeleteConfirmPolicy, DeleteSummary, DeleteSummaryJob, LinkKind,
+    RenameSelect, DELETE_SUMMARY_LIMIT,
+};
+pub use oplog::{Operation, OperationKind, OperationLog, OPERATION_LOG_CAPACITY};
+pub use paste::{ConflictResolution, PasteItem, PastePlan, PasteSummary};
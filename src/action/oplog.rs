@@ -0,0 +1,315 @@
+//! Log of the file operations performed in a session
+//!
+//! Every create, rename, move, copy, link and delete is appended to an
+//! [`OperationLog`] with the time it ran, the paths it acted on and the
+//! paths it produced. The log is kept in memory for the session and shown
+//! in the operation history popup. The last rename, move or trash can be
+//! undone once.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::file::restore_from_trash;
+
+/// Default number of operations kept
+pub const OPERATION_LOG_CAPACITY: usize = 500;
+
+/// Kind of file operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    Create,
+    Rename,
+    Move,
+    /// Moved into an existing directory, merging with its contents
+    Merge,
+    Copy,
+    Link,
+    /// Moved to the system trash
+    Trash,
+    /// Deleted permanently
+    Delete,
+}
+
+impl OperationKind {
+    /// Label shown in the history
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Create => "Create",
+            Self::Rename => "Rename",
+            Self::Move => "Move",
+            Self::Merge => "Merge",
+            Self::Copy => "Copy",
+            Self::Link => "Link",
+            Self::Trash => "Trash",
+            Self::Delete => "Delete",
+        }
+    }
+
+    /// Check if operations of this kind can be reverted
+    pub fn is_undoable(self) -> bool {
+        matches!(self, Self::Rename | Self::Move | Self::Trash)
+    }
+}
+
+/// A performed file operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Operation {
+    pub kind: OperationKind,
+    /// When the operation ran
+    pub time: SystemTime,
+    /// Paths acted on (empty for creates)
+    pub sources: Vec<PathBuf>,
+    /// Paths produced (empty for deletes)
+    pub results: Vec<PathBuf>,
+    /// Whether the operation has been undone
+    pub undone: bool,
+}
+
+impl Operation {
+    /// Record an operation that ran just now
+    pub fn new(kind: OperationKind, sources: Vec<PathBuf>, results: Vec<PathBuf>) -> Self {
+        Self {
+            kind,
+            time: SystemTime::now(),
+            sources,
+            results,
+            undone: false,
+        }
+    }
+
+    /// Revert the operation on disk and mark it undone
+    ///
+    /// Renames and moves put every result back at its source, latest first,
+    /// and stop before replacing anything that took a source's place.
+    /// Trashed items are restored from the trash. An undo that fails before
+    /// changing anything leaves the operation undoable, so it can be retried.
+    pub fn undo(&mut self) -> anyhow::Result<()> {
+        match self.kind {
+            OperationKind::Rename | OperationKind::Move => {
+                let mut moved = false;
+                let mut outcome = Ok(());
+                for (source, result) in self.sources.iter().zip(&self.results).rev() {
+                    if source.symlink_metadata().is_ok() {
+                        outcome = Err(anyhow::anyhow!("{} already exists", source.display()));
+                        break;
+                    }
+                    if let Err(e) = std::fs::rename(result, source) {
+                        outcome = Err(anyhow::anyhow!(
+                            "Failed to move {} back: {}",
+                            result.display(),
+                            e
+                        ));
+                        break;
+                    }
+                    moved = true;
+                }
+                // A partial undo cannot be repeated either
+                self.undone = outcome.is_ok() || moved;
+                outcome
+            }
+            OperationKind::Trash => {
+                restore_from_trash(&self.sources)?;
+                self.undone = true;
+                Ok(())
+            }
+            kind => anyhow::bail!("{} cannot be undone", kind.label()),
+        }
+    }
+
+    /// Path to reveal for this entry (the first resulting path)
+    pub fn reveal_target(&self) -> Option<&Path> {
+        self.results.first().map(PathBuf::as_path)
+    }
+
+    /// One-line summary with paths relative to `root`
+    ///
+    /// Single items read `Rename a.txt -> b.txt`; batches show the first
+    /// path and a count, e.g. `Copy 3 items: a.txt, ... -> dest`.
+    pub fn summary(&self, root: &Path) -> String {
+        let rel = |path: &Path| {
+            path.strip_prefix(root)
+                .unwrap_or(path)
+                .display()
+                .to_string()
+        };
+        let list = |paths: &[PathBuf]| match paths {
+            [] => String::new(),
+            [only] => rel(only),
+            [first, ..] => format!("{} items: {}, ...", paths.len(), rel(first)),
+        };
+        let label = self.kind.label();
+        match (self.sources.as_slice(), self.results.as_slice()) {
+            ([], results) => format!("{} {}", label, list(results)),
+            (sources, []) => format!("{} {}", label, list(sources)),
+            ([source], [result]) => format!("{} {} -> {}", label, rel(source), rel(result)),
+            (sources, results) => {
+                // Batches usually land in one directory
+                let dest = results[0].parent().map(rel).unwrap_or_default();
+                let dest = if dest.is_empty() {
+                    ".".to_string()
+                } else {
+                    dest
+                };
+                format!("{} {} -> {}", label, list(sources), dest)
+            }
+        }
+    }
+}
+
+/// Operations in the order they ran
+#[derive(Debug, Clone)]
+pub struct OperationLog {
+    entries: VecDeque<Operation>,
+    capacity: usize,
+}
+
+impl Default for OperationLog {
+    fn default() -> Self {
+        Self::new(OPERATION_LOG_CAPACITY)
+    }
+}
+
+impl OperationLog {
+    /// Create an empty log keeping at most `capacity` operations
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Append an operation, dropping the oldest once full
+    pub fn record(&mut self, operation: Operation) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(operation);
+    }
+
+    /// Operations, oldest first
+    pub fn entries(&self) -> &VecDeque<Operation> {
+        &self.entries
+    }
+
+    /// The most recent operation
+    pub fn last_mut(&mut self) -> Option<&mut Operation> {
+        self.entries.back_mut()
+    }
+
+    /// Number of logged operations
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if nothing has been logged
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_keeps_order_and_capacity() {
+        let mut log = OperationLog::new(2);
+        log.record(Operation::new(
+            OperationKind::Create,
+            vec![],
+            vec!["/a".into()],
+        ));
+        log.record(Operation::new(
+            OperationKind::Delete,
+            vec!["/b".into()],
+            vec![],
+        ));
+        log.record(Operation::new(
+            OperationKind::Copy,
+            vec!["/c".into()],
+            vec!["/d".into()],
+        ));
+
+        let kinds: Vec<OperationKind> = log.entries().iter().map(|op| op.kind).collect();
+        assert_eq!(kinds, vec![OperationKind::Delete, OperationKind::Copy]);
+    }
+
+    #[test]
+    fn test_undo_rename_and_move() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let a = temp.path().join("a.txt");
+        let b = temp.path().join("b.txt");
+        let dest = temp.path().join("dest");
+        std::fs::create_dir(&dest).unwrap();
+        std::fs::write(&b, "b").unwrap();
+        std::fs::write(dest.join("c.txt"), "c").unwrap();
+
+        let mut rename = Operation::new(OperationKind::Rename, vec![a.clone()], vec![b.clone()]);
+        rename.undo().unwrap();
+        assert!(a.exists() && !b.exists());
+        assert!(rename.undone);
+
+        let mut moved = Operation::new(
+            OperationKind::Move,
+            vec![temp.path().join("c.txt")],
+            vec![dest.join("c.txt")],
+        );
+        moved.undo().unwrap();
+        assert!(temp.path().join("c.txt").exists());
+
+        // Never replaces what took the original's place
+        std::fs::write(&b, "new b").unwrap();
+        let mut rename = Operation::new(OperationKind::Rename, vec![a.clone()], vec![b.clone()]);
+        std::fs::rename(&a, temp.path().join("other.txt")).unwrap();
+        std::fs::write(&a, "new a").unwrap();
+        assert!(rename.undo().is_err());
+        assert_eq!(std::fs::read_to_string(&a).unwrap(), "new a");
+        // Nothing moved, so the undo can be retried once the way is clear
+        assert!(!rename.undone);
+        std::fs::remove_file(&a).unwrap();
+        rename.undo().unwrap();
+        assert_eq!(std::fs::read_to_string(&a).unwrap(), "new b");
+
+        let mut copy = Operation::new(OperationKind::Copy, vec![a.clone()], vec![b]);
+        assert!(!copy.kind.is_undoable());
+        assert!(copy.undo().is_err());
+    }
+
+    #[test]
+    fn test_summary() {
+        let root = Path::new("/root");
+        let create = Operation::new(
+            OperationKind::Create,
+            vec![],
+            vec!["/root/src/new.rs".into()],
+        );
+        assert_eq!(create.summary(root), "Create src/new.rs");
+
+        let rename = Operation::new(
+            OperationKind::Rename,
+            vec!["/root/a.txt".into()],
+            vec!["/root/b.txt".into()],
+        );
+        assert_eq!(rename.summary(root), "Rename a.txt -> b.txt");
+        assert_eq!(rename.reveal_target(), Some(Path::new("/root/b.txt")));
+
+        let delete = Operation::new(
+            OperationKind::Delete,
+            vec!["/root/a".into(), "/root/b".into()],
+            vec![],
+        );
+        assert_eq!(delete.summary(root), "Delete 2 items: a, ...");
+        assert_eq!(delete.reveal_target(), None);
+
+        let copy = Operation::new(
+            OperationKind::Copy,
+            vec!["/root/a".into(), "/root/b".into()],
+            vec!["/root/dest/a".into(), "/root/dest/b".into()],
+        );
+        assert_eq!(copy.summary(root), "Copy 2 items: a, ... -> dest");
+    }
+}
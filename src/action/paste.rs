@@ -0,0 +1,363 @@
+//! Paste planning with conflict resolution
+//!
+//! A paste is planned before anything touches the filesystem: every item whose
+//! destination already exists is a conflict that needs a resolution
+//! (overwrite, skip, or rename) before the plan can be executed.
+
+use std::path::{Path, PathBuf};
+
+use super::file::{copy_dir_recursive, get_unique_path};
+
+/// How to resolve a paste onto an existing destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+    /// Replace the destination (directories are merged recursively)
+    Overwrite,
+    /// Leave the destination untouched and skip the item
+    Skip,
+    /// Paste under a unique name (`name_1.ext`)
+    Rename,
+}
+
+/// A single item of a paste
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasteItem {
+    /// Source path
+    pub src: PathBuf,
+    /// Destination path (dest_dir + file name)
+    pub dest: PathBuf,
+    /// Destination already exists
+    pub conflict: bool,
+    /// Chosen resolution (None for non-conflicting items and undecided conflicts)
+    pub resolution: Option<ConflictResolution>,
+}
+
+/// Result counts of an executed paste
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PasteSummary {
+    pub pasted: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    /// Source and final destination of each pasted item
+    pub placed: Vec<(PathBuf, PathBuf)>,
+    /// Some directory was merged into an existing one
+    pub merged: bool,
+}
+
+/// Planned paste of clipboard paths into a directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PastePlan {
+    pub items: Vec<PasteItem>,
+    /// Move instead of copy
+    pub is_cut: bool,
+    /// Target directory
+    pub dest_dir: PathBuf,
+}
+
+impl PastePlan {
+    /// Plan a paste and detect conflicts
+    ///
+    /// Pasting an item onto itself is not a conflict: a copy is duplicated
+    /// under a unique name and a cut is a no-op. An item whose destination
+    /// contains it (`a/b/b` pasted into `a`) is always skipped, since
+    /// replacing the destination would delete the source.
+    pub fn new(paths: &[PathBuf], dest_dir: &Path, is_cut: bool) -> Self {
+        let items = paths
+            .iter()
+            .filter_map(|src| {
+                let dest = dest_dir.join(src.file_name()?);
+                let same = dest == *src;
+                let contains_src = !same && src.starts_with(&dest);
+                let resolution = match (same, is_cut) {
+                    _ if contains_src => Some(ConflictResolution::Skip),
+                    (true, false) => Some(ConflictResolution::Rename),
+                    (true, true) => Some(ConflictResolution::Skip),
+                    _ => None,
+                };
+                Some(PasteItem {
+                    conflict: !same && !contains_src && dest.symlink_metadata().is_ok(),
+                    src: src.clone(),
+                    dest,
+                    resolution,
+                })
+            })
+            .collect();
+
+        Self {
+            items,
+            is_cut,
+            dest_dir: dest_dir.to_path_buf(),
+        }
+    }
+
+    /// Index of the next conflict that still needs a decision
+    pub fn next_conflict(&self) -> Option<usize> {
+        self.items
+            .iter()
+            .position(|item| item.conflict && item.resolution.is_none())
+    }
+
+    /// Number of conflicting items
+    pub fn conflict_count(&self) -> usize {
+        self.items.iter().filter(|item| item.conflict).count()
+    }
+
+    /// Resolve the next conflict (or all remaining conflicts)
+    pub fn resolve(&mut self, resolution: ConflictResolution, apply_all: bool) {
+        for item in self
+            .items
+            .iter_mut()
+            .filter(|item| item.conflict && item.resolution.is_none())
+        {
+            item.resolution = Some(resolution);
+            if !apply_all {
+                break;
+            }
+        }
+    }
+
+    /// Execute the plan
+    ///
+    /// Undecided conflicts are skipped. Failures are counted rather than
+    /// aborting the remaining items.
+    pub fn execute(&self) -> PasteSummary {
+        let mut summary = PasteSummary::default();
+        for item in &self.items {
+            let dest = match item.resolution {
+                Some(ConflictResolution::Skip) => {
+                    summary.skipped += 1;
+                    continue;
+                }
+                None if item.conflict => {
+                    summary.skipped += 1;
+                    continue;
+                }
+                Some(ConflictResolution::Rename) => get_unique_path(&item.dest),
+                Some(ConflictResolution::Overwrite) | None => item.dest.clone(),
+            };
+            let merges = merges(&item.src, &dest);
+            match place(&item.src, &dest, self.is_cut) {
+                Ok(()) => {
+                    summary.merged |= merges;
+                    summary.pasted += 1;
+                    summary.placed.push((item.src.clone(), dest));
+                }
+                Err(_) => summary.failed += 1,
+            }
+        }
+        summary
+    }
+}
+
+/// Copy or move `src` to `dest`, replacing or merging into an existing destination
+///
+/// Refuses a `dest` that contains `src`, which replacing would delete.
+fn place(src: &Path, dest: &Path, is_cut: bool) -> anyhow::Result<()> {
+    if src != dest && src.starts_with(dest) {
+        anyhow::bail!("{} is inside {}", src.display(), dest.display());
+    }
+    if merges(src, dest) {
+        return merge_dir(src, dest, is_cut);
+    }
+    if dest.symlink_metadata().is_ok() {
+        remove_path(dest)?;
+    }
+    if is_cut {
+        std::fs::rename(src, dest)?;
+    } else if src.is_dir() {
+        copy_dir_recursive(src, dest)?;
+    } else {
+        std::fs::copy(src, dest)?;
+    }
+    Ok(())
+}
+
+/// Check if placing `src` at `dest` merges two directories
+///
+/// Symlinks are never merged through: a link to a directory on either side
+/// is replaced like a file.
+fn merges(src: &Path, dest: &Path) -> bool {
+    let is_real_dir = |path: &Path| path.symlink_metadata().is_ok_and(|meta| meta.is_dir());
+    is_real_dir(src) && is_real_dir(dest)
+}
+
+/// Merge the contents of `src` into the existing directory `dest`
+fn merge_dir(src: &Path, dest: &Path, is_cut: bool) -> anyhow::Result<()> {
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        place(&entry.path(), &dest.join(entry.file_name()), is_cut)?;
+    }
+    if is_cut {
+        std::fs::remove_dir(src)?;
+    }
+    Ok(())
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() && !path.is_symlink() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// src/{a.txt, b.txt, sub/inner.txt} and dest/{a.txt, sub/other.txt}
+    fn fixture() -> (TempDir, PathBuf, PathBuf) {
+        let temp = TempDir::new().unwrap();
+        let src = temp.path().join("src");
+        let dest = temp.path().join("dest");
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::create_dir_all(dest.join("sub")).unwrap();
+        fs::write(src.join("a.txt"), "new a").unwrap();
+        fs::write(src.join("b.txt"), "b").unwrap();
+        fs::write(src.join("sub/inner.txt"), "inner").unwrap();
+        fs::write(dest.join("a.txt"), "old a").unwrap();
+        fs::write(dest.join("sub/other.txt"), "other").unwrap();
+        (temp, src, dest)
+    }
+
+    fn sources(src: &Path) -> Vec<PathBuf> {
+        vec![src.join("a.txt"), src.join("b.txt"), src.join("sub")]
+    }
+
+    #[test]
+    fn test_plan_detects_conflicts() {
+        let (_temp, src, dest) = fixture();
+        let plan = PastePlan::new(&sources(&src), &dest, false);
+
+        let conflicts: Vec<bool> = plan.items.iter().map(|i| i.conflict).collect();
+        assert_eq!(conflicts, vec![true, false, true]);
+        assert_eq!(plan.conflict_count(), 2);
+        assert_eq!(plan.next_conflict(), Some(0));
+    }
+
+    #[test]
+    fn test_plan_same_directory_is_not_conflict() {
+        let (_temp, src, _dest) = fixture();
+        let copy = PastePlan::new(&[src.join("a.txt")], &src, false);
+        assert!(!copy.items[0].conflict);
+        assert_eq!(copy.items[0].resolution, Some(ConflictResolution::Rename));
+
+        let cut = PastePlan::new(&[src.join("a.txt")], &src, true);
+        assert_eq!(cut.items[0].resolution, Some(ConflictResolution::Skip));
+        assert_eq!(cut.next_conflict(), None);
+    }
+
+    #[test]
+    fn test_resolve_one_at_a_time() {
+        let (_temp, src, dest) = fixture();
+        let mut plan = PastePlan::new(&sources(&src), &dest, false);
+
+        plan.resolve(ConflictResolution::Skip, false);
+        assert_eq!(plan.items[0].resolution, Some(ConflictResolution::Skip));
+        assert_eq!(plan.next_conflict(), Some(2));
+
+        plan.resolve(ConflictResolution::Overwrite, false);
+        assert_eq!(
+            plan.items[2].resolution,
+            Some(ConflictResolution::Overwrite)
+        );
+        assert_eq!(plan.items[1].resolution, None);
+        assert_eq!(plan.next_conflict(), None);
+    }
+
+    #[test]
+    fn test_resolve_apply_all() {
+        let (_temp, src, dest) = fixture();
+        let mut plan = PastePlan::new(&sources(&src), &dest, false);
+
+        plan.resolve(ConflictResolution::Rename, true);
+        let resolutions: Vec<_> = plan.items.iter().map(|i| i.resolution).collect();
+        assert_eq!(
+            resolutions,
+            vec![
+                Some(ConflictResolution::Rename),
+                None,
+                Some(ConflictResolution::Rename)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_execute_overwrite_merges_directories() {
+        let (_temp, src, dest) = fixture();
+        let mut plan = PastePlan::new(&sources(&src), &dest, true);
+        plan.resolve(ConflictResolution::Overwrite, true);
+
+        let summary = plan.execute();
+        assert_eq!(summary.pasted, 3);
+        assert_eq!(fs::read_to_string(dest.join("a.txt")).unwrap(), "new a");
+        assert!(dest.join("b.txt").exists());
+        // Merged, not clobbered
+        assert!(dest.join("sub/inner.txt").exists());
+        assert!(dest.join("sub/other.txt").exists());
+        assert!(!src.join("sub").exists());
+        assert!(summary.merged);
+    }
+
+    #[test]
+    fn test_execute_skip_and_rename() {
+        let (_temp, src, dest) = fixture();
+        let mut plan = PastePlan::new(&sources(&src), &dest, false);
+        plan.resolve(ConflictResolution::Rename, false);
+        plan.resolve(ConflictResolution::Skip, false);
+
+        let summary = plan.execute();
+        assert_eq!(summary.pasted, 2);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(summary.placed[0], (src.join("a.txt"), dest.join("a_1.txt")));
+        assert_eq!(fs::read_to_string(dest.join("a.txt")).unwrap(), "old a");
+        assert_eq!(fs::read_to_string(dest.join("a_1.txt")).unwrap(), "new a");
+        assert!(!dest.join("sub/inner.txt").exists());
+        assert!(!summary.merged);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_overwrite_replaces_directory_symlink() {
+        let (temp, src, dest) = fixture();
+        let target = temp.path().join("target");
+        fs::create_dir(&target).unwrap();
+        fs::remove_dir_all(dest.join("sub")).unwrap();
+        std::os::unix::fs::symlink(&target, dest.join("sub")).unwrap();
+
+        let mut plan = PastePlan::new(&[src.join("sub")], &dest, false);
+        plan.resolve(ConflictResolution::Overwrite, true);
+
+        let summary = plan.execute();
+        assert_eq!(summary.pasted, 1);
+        assert!(!summary.merged);
+        // The link was replaced, not written through
+        assert!(!dest.join("sub").is_symlink());
+        assert!(dest.join("sub/inner.txt").exists());
+        assert_eq!(fs::read_dir(&target).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_paste_into_ancestor_keeps_source() {
+        let temp = TempDir::new().unwrap();
+        let proj = temp.path().join("proj");
+        let nested = proj.join("build/build");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("keep.txt"), "keep").unwrap();
+
+        // `proj/build` would replace the directory holding the source
+        let mut plan = PastePlan::new(std::slice::from_ref(&nested), &proj, true);
+        assert_eq!(plan.next_conflict(), None);
+        plan.resolve(ConflictResolution::Overwrite, true);
+        let summary = plan.execute();
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(summary.pasted, 0);
+        assert!(nested.join("keep.txt").exists());
+
+        // Also refused below the plan, e.g. while merging
+        assert!(place(&nested, &proj.join("build"), true).is_err());
+        assert!(nested.join("keep.txt").exists());
+    }
+}
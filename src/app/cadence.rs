@@ -0,0 +1,109 @@
+//! Event poll cadence and frame rate cap
+//!
+//! While input is arriving the loop polls every `tick` so key presses are
+//! handled promptly. Once input stops for [`IDLE_AFTER`], the poll timeout
+//! doubles every further [`IDLE_AFTER`] until it reaches `idle_tick`, so an
+//! idle window wakes up far less often. Redraws are limited to one per
+//! `min_frame`; a skipped redraw is made up as soon as the interval elapses.
+
+use std::time::Duration;
+
+/// Inactivity before the poll interval starts backing off
+pub const IDLE_AFTER: Duration = Duration::from_secs(2);
+
+/// Poll and redraw timing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cadence {
+    /// Poll timeout while input is arriving
+    pub tick: Duration,
+    /// Longest poll timeout when idle
+    pub idle_tick: Duration,
+    /// Minimum interval between redraws (zero = uncapped)
+    pub min_frame: Duration,
+}
+
+impl Default for Cadence {
+    fn default() -> Self {
+        Self::new(60, 500, 60)
+    }
+}
+
+impl Cadence {
+    /// Build from config values (`max_fps = 0` disables the frame cap)
+    ///
+    /// `tick_ms` is at least 1ms and `idle_tick_ms` never below `tick_ms`.
+    pub fn new(tick_ms: u64, idle_tick_ms: u64, max_fps: u32) -> Self {
+        let tick_ms = tick_ms.max(1);
+        let min_frame = if max_fps == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs(1) / max_fps
+        };
+        Self {
+            tick: Duration::from_millis(tick_ms),
+            idle_tick: Duration::from_millis(idle_tick_ms.max(tick_ms)),
+            min_frame,
+        }
+    }
+
+    /// Poll timeout after `idle` without input
+    pub fn poll_timeout(&self, idle: Duration) -> Duration {
+        if idle < IDLE_AFTER {
+            return self.tick;
+        }
+        let periods = (idle.as_millis() / IDLE_AFTER.as_millis()).min(u32::MAX as u128) as u32;
+        self.tick
+            .saturating_mul(2u32.saturating_pow(periods))
+            .min(self.idle_tick)
+    }
+
+    /// Time left before the next redraw is allowed
+    pub fn frame_wait(&self, since_last_frame: Duration) -> Duration {
+        self.min_frame.saturating_sub(since_last_frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_poll_timeout_backs_off_when_idle() {
+        let cadence = Cadence::new(50, 1000, 60);
+        assert_eq!(cadence.poll_timeout(Duration::ZERO), ms(50));
+        assert_eq!(cadence.poll_timeout(ms(1999)), ms(50));
+        assert_eq!(cadence.poll_timeout(ms(2000)), ms(100));
+        assert_eq!(cadence.poll_timeout(ms(4000)), ms(200));
+        assert_eq!(cadence.poll_timeout(ms(5999)), ms(200));
+        assert_eq!(cadence.poll_timeout(ms(6000)), ms(400));
+        assert_eq!(cadence.poll_timeout(ms(8000)), ms(800));
+        // Capped at the idle tick
+        assert_eq!(cadence.poll_timeout(ms(10_000)), ms(1000));
+        assert_eq!(cadence.poll_timeout(Duration::from_secs(86_400)), ms(1000));
+    }
+
+    #[test]
+    fn test_new_clamps_values() {
+        let cadence = Cadence::new(0, 0, 0);
+        assert_eq!(cadence.tick, ms(1));
+        assert_eq!(cadence.idle_tick, ms(1));
+        assert_eq!(cadence.min_frame, Duration::ZERO);
+
+        let cadence = Cadence::new(100, 20, 50);
+        assert_eq!(cadence.idle_tick, ms(100));
+        assert_eq!(cadence.min_frame, ms(20));
+    }
+
+    #[test]
+    fn test_frame_wait() {
+        let cadence = Cadence::new(60, 500, 50);
+        assert_eq!(cadence.frame_wait(ms(5)), ms(15));
+        assert_eq!(cadence.frame_wait(ms(20)), Duration::ZERO);
+        assert_eq!(cadence.frame_wait(ms(300)), Duration::ZERO);
+        assert_eq!(Cadence::new(60, 500, 0).frame_wait(ms(0)), Duration::ZERO);
+    }
+}
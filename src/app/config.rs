@@ -1,15 +1,22 @@
 //! Application configuration from CLI arguments
 
+use std::collections::HashSet;
 use std::env;
-use std::io::{self, BufRead, IsTerminal};
-use std::path::PathBuf;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::Duration;
 
+use super::cadence::Cadence;
 use super::config_file::{CommandsConfig, ConfigFile, PreviewConfig};
+use crate::action::{ConfirmDeleteMode, DeleteConfirmPolicy, RenameSelect};
+use crate::core::{
+    ClickAction, CollapseFallback, FileEnterAction, HiddenScope, IconSet, NameTruncation, PickDirs,
+    PreviewPosition, TreeGuideStyle, PREVIEW_SPLIT_MAX, PREVIEW_SPLIT_MIN,
+};
 use crate::integrate::{
-    exit_code, Callback, ContextAgent, ContextPackFormat, ContextPackOptions, ContextPackPreset,
-    OutputFormat,
+    exit_code, Callback, ConcatOptions, ContextAgent, ContextPackFormat, ContextPackOptions,
+    ContextPackPreset, OutputFormat, OutputOptions,
 };
 
 /// Session action (save, restore, clear)
@@ -23,6 +30,15 @@ pub enum SessionAction {
     Clear,
 }
 
+/// State file action (--export-state, --import-state)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateAction {
+    /// Write recent files and sessions to a file
+    Export(PathBuf),
+    /// Restore them from a file, merging unless `replace` is set
+    Import { path: PathBuf, replace: bool },
+}
+
 /// Plugin command action
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PluginAction {
@@ -42,8 +58,20 @@ pub enum InitAction {
 /// Application configuration from CLI args and config file
 pub struct Config {
     pub root: PathBuf,
+    /// Further directories named on the command line (extra root sections)
+    pub extra_roots: Vec<PathBuf>,
+    /// File named on the command line (revealed and focused at startup)
+    pub focus_path: Option<PathBuf>,
+    /// Line of `focus_path` to show, 1-based (`fv file.rs:42`)
+    pub focus_line: Option<usize>,
+    /// Open the preview panel at startup (--preview, with a file argument)
+    pub open_preview: bool,
     pub pick_mode: bool,
+    /// How pick mode treats directories (--allow-dirs, --expand-dirs, --dirs-only)
+    pub pick_dirs: PickDirs,
     pub output_format: OutputFormat,
+    /// Type indicators for pick output (--dir-slash, --with-types)
+    pub output_options: OutputOptions,
     pub callback: Option<Callback>,
     pub icons_enabled: Option<bool>,
     /// Shell integration: output directory path on exit (for cd)
@@ -58,20 +86,106 @@ pub struct Config {
     pub show_hidden: bool,
     /// Enable mouse support (from config file)
     pub mouse_enabled: bool,
+    /// What a single click on an entry does (from config file)
+    pub mouse_single_click: ClickAction,
+    /// What a double click on an entry does (from config file)
+    pub mouse_double_click: ClickAction,
+    /// Create relative symlinks by default (from config file)
+    pub relative_symlinks: bool,
+    /// Refuse mutating operations outside the root (from config file)
+    pub confine_to_root: bool,
+    /// Directory levels expanded at startup (from config file)
+    pub startup_expand_depth: usize,
+    /// Initial cursor of the rename prompt (from config file)
+    pub rename_select: RenameSelect,
+    /// What Enter does on a file (from config file)
+    pub file_enter_action: FileEnterAction,
+    /// What `h` does on a collapsed directory or a file (from config file)
+    pub collapse_fallback: CollapseFallback,
+    /// Program that opens files (from config file)
+    pub opener: String,
+    /// When to confirm deletes (from config file)
+    pub delete_confirm: DeleteConfirmPolicy,
+    /// Deletes go to the system trash (from config file)
+    pub use_trash: bool,
+    /// Ask before a rename replaces an existing file (from config file)
+    pub confirm_rename_overwrite: bool,
+    /// Create missing directories for nested new names (from config file)
+    pub create_parents: bool,
+    /// Where the hidden files toggle applies (from config file)
+    pub hidden_scope: HiddenScope,
+    /// Cap on matching lines per content search (from config file)
+    pub content_search_max_results: usize,
+    /// Results listed by the fuzzy finder (from config file)
+    pub fuzzy_max_results: usize,
+    /// Directory names the fuzzy finder skips (from config file)
+    pub fuzzy_exclude: Vec<String>,
+    /// The fuzzy finder skips gitignored paths (from config file)
+    pub fuzzy_gitignore: bool,
+    /// `c` copies relative paths (from config file)
+    pub copy_relative_path: bool,
+    /// Directories above files at startup (from config file)
+    pub dirs_first: bool,
+    /// Watch collapsed directories too (from config file, read at startup)
+    pub watch_recursive: bool,
     /// Maximum bytes for hex preview (from config file)
     pub hex_max_bytes: usize,
     /// Maximum entries for archive preview (from config file)
     pub max_archive_entries: usize,
     /// Image protocol setting (from config file)
     pub image_protocol: String,
+    /// Syntax highlighting theme name (from config file)
+    pub syntax_theme: String,
+    /// Tab width for text previews (from config file)
+    pub tab_width: usize,
+    /// Columns a text preview line is cut at, 0 = no limit (from config file)
+    pub max_line_width: usize,
+    /// Soft-wrap text previews by default (from config file)
+    pub preview_wrap: bool,
+    /// Show line numbers in text previews by default (from config file)
+    pub preview_line_numbers: bool,
+    /// Frame previews with a border and title (from config file)
+    pub preview_borders: bool,
+    /// Classify unknown file types with `file --mime-type` (from config file)
+    pub use_file_command: bool,
+    /// Preview position (from config file)
+    pub preview_position: PreviewPosition,
+    /// Preview share of the split, 0 = by terminal width (from config file)
+    pub preview_split: u16,
+    /// Delay before the focused entry is previewed (from config file)
+    pub preview_debounce: Duration,
+    /// Thumbnail cache bound in bytes, 0 = full images only (from config file)
+    pub thumbnail_cache_bytes: u64,
+    /// Rows parsed for CSV/TSV table previews (from config file)
+    pub csv_max_rows: usize,
     /// Git poll interval (from config file)
     pub git_poll_interval: Duration,
+    /// Event poll cadence and frame cap (from config file)
+    pub cadence: Cadence,
     /// Show file size in tree (from config file)
     pub show_size: bool,
     /// Show file permissions in tree (from config file)
     pub show_permissions: bool,
     /// Date format string (from config file)
     pub date_format: String,
+    /// Tree indentation guide style (from config file, degraded by ascii_only)
+    pub tree_guides: TreeGuideStyle,
+    /// Draw tree guides dimmed (from config file)
+    pub dim_tree_guides: bool,
+    /// Where long tree names are cut (from config file)
+    pub name_truncation: NameTruncation,
+    /// Status bar template (from config file, None = built-in layout)
+    pub status_format: Option<String>,
+    /// Ring the terminal bell on errors (from config file)
+    pub bell_on_error: bool,
+    /// Flash the status bar on errors (from config file)
+    pub flash_on_error: bool,
+    /// Relative-number gutter in the tree (from config file)
+    pub relative_numbers: bool,
+    /// Avoid Unicode drawing characters (from config file or FILEVIEW_ASCII)
+    pub ascii_only: bool,
+    /// Tree icon glyph set (from config file, degraded by ascii_only)
+    pub icon_set: IconSet,
     /// Custom commands configuration
     pub commands: CommandsConfig,
     /// Custom preview configuration
@@ -80,6 +194,8 @@ pub struct Config {
     pub tree_mode: bool,
     /// Maximum depth for tree output (None = unlimited)
     pub tree_depth: Option<usize>,
+    /// Re-emit the tree or context output whenever the directory changes
+    pub watch_output: bool,
     /// Include file content with pick output
     pub with_content: bool,
     /// Select mode (simpler interactive selection)
@@ -100,12 +216,20 @@ pub struct Config {
     pub context_pack: Option<ContextPackPreset>,
     /// Context pack options
     pub context_pack_options: ContextPackOptions,
+    /// Concatenated text dump mode (non-interactive)
+    pub concat_mode: bool,
+    /// Limits for the concatenated dump
+    pub concat_options: ConcatOptions,
+    /// Workspace symbol index output mode (non-interactive)
+    pub symbols_mode: bool,
     /// Related file selection output mode (non-interactive)
     pub select_related_path: Option<PathBuf>,
     /// Explain related-file selection scoring
     pub explain_selection: bool,
     /// Session action (save/restore/clear) - non-interactive
     pub session_action: Option<SessionAction>,
+    /// State file export/import - non-interactive
+    pub state_action: Option<StateAction>,
     /// Plugin command action
     pub plugin_action: Option<PluginAction>,
     /// Plugin path for plugin commands
@@ -118,6 +242,10 @@ pub struct Config {
     pub init_force: bool,
     /// Resume AI session by name (default: ai)
     pub resume_ai_session: Option<String>,
+    /// Serve the tree state over HTTP on this localhost port (`serve` feature)
+    pub serve_port: Option<u16>,
+    /// Bearer token required by the HTTP API (--serve-token or FILEVIEW_SERVE_TOKEN)
+    pub serve_token: Option<String>,
 }
 
 impl Config {
@@ -127,17 +255,26 @@ impl Config {
 
         let mut args = env::args().skip(1).peekable();
         let mut root = env::current_dir()?;
+        let mut root_given = false;
+        let mut extra_roots: Vec<PathBuf> = Vec::new();
+        let mut focus_path: Option<PathBuf> = None;
+        let mut focus_line: Option<usize> = None;
+        let mut open_preview = false;
         let mut pick_mode = false;
+        let mut pick_dirs = PickDirs::default();
         let mut output_format = OutputFormat::default();
+        let mut output_options = OutputOptions::default();
         let mut callback: Option<Callback> = None;
         let mut icons_enabled: Option<bool> = None;
         let mut choosedir_mode = false;
         let mut choosedir_file: Option<PathBuf> = None;
         let mut selection_path_file: Option<PathBuf> = None;
         let mut stdin_mode = false;
+        let mut null_separated = false;
         let mut show_hidden: Option<bool> = None;
         let mut tree_mode = false;
         let mut tree_depth: Option<usize> = None;
+        let mut watch_output = false;
         let mut with_content = false;
         let mut select_mode = false;
         let mut multi_select = false;
@@ -149,19 +286,30 @@ impl Config {
         let mut context_pack: Option<ContextPackPreset> = None;
         let mut context_pack_format = ContextPackFormat::AiMarkdown;
         let mut context_pack_options = ContextPackOptions::default();
+        let mut concat_mode = false;
+        let mut concat_options = ConcatOptions::default();
+        let mut symbols_mode = false;
         let mut select_related_path: Option<PathBuf> = None;
         let mut explain_selection = false;
         let mut session_action: Option<SessionAction> = None;
+        let mut state_action: Option<StateAction> = None;
+        let mut replace_state = false;
         let mut plugin_action: Option<PluginAction> = None;
         let mut plugin_path: Option<PathBuf> = None;
         let mut init_action: Option<InitAction> = None;
         let mut init_path: Option<PathBuf> = None;
         let mut init_force = false;
         let mut resume_ai_session: Option<String> = None;
+        let mut serve_port: Option<u16> = None;
+        let mut serve_token: Option<String> = None;
 
         while let Some(arg) = args.next() {
             match arg.as_str() {
                 "--pick" | "-p" => pick_mode = true,
+                "--allow-dirs" => pick_dirs = PickDirs::Allow,
+                "--expand-dirs" => pick_dirs = PickDirs::Expand,
+                "--dirs-only" => pick_dirs = PickDirs::Only,
+                "--preview" => open_preview = true,
                 "--choosedir" => {
                     choosedir_mode = true;
                     // Check if next arg is a file path (not starting with -)
@@ -178,20 +326,23 @@ impl Config {
                         anyhow::bail!("--selection-path requires a file path");
                     }
                 }
-                "--stdin" => stdin_mode = true,
+                "--stdin" | "--from-stdin" => stdin_mode = true,
+                "--null" | "-0" => null_separated = true,
                 "--tree" | "-t" => tree_mode = true,
-                "--depth" => {
+                "--depth" | "--max-depth" => {
                     if let Some(depth_str) = args.next() {
                         tree_depth = Some(depth_str.parse().map_err(|_| {
                             anyhow::anyhow!(
-                                "--depth requires a positive integer, got '{}'",
+                                "{} requires a positive integer, got '{}'",
+                                arg,
                                 depth_str
                             )
                         })?);
                     } else {
-                        anyhow::bail!("--depth requires a value");
+                        anyhow::bail!("{} requires a value", arg);
                     }
                 }
+                "--watch" => watch_output = true,
                 "--with-content" => with_content = true,
                 "--select-mode" => select_mode = true,
                 "--multi" => multi_select = true,
@@ -288,6 +439,26 @@ impl Config {
                         anyhow::bail!("--context-depth requires a value");
                     }
                 }
+                "--concat" => concat_mode = true,
+                "--symbols" => symbols_mode = true,
+                "--concat-bytes" => {
+                    if let Some(value) = args.next() {
+                        concat_options.max_bytes = value.parse().map_err(|_| {
+                            anyhow::anyhow!("--concat-bytes requires a positive integer")
+                        })?;
+                    } else {
+                        anyhow::bail!("--concat-bytes requires a value");
+                    }
+                }
+                "--concat-files" => {
+                    if let Some(value) = args.next() {
+                        concat_options.max_files = value.parse().map_err(|_| {
+                            anyhow::anyhow!("--concat-files requires a positive integer")
+                        })?;
+                    } else {
+                        anyhow::bail!("--concat-files requires a value");
+                    }
+                }
                 "--select-related" => {
                     if let Some(path) = args.next() {
                         select_related_path = Some(PathBuf::from(path));
@@ -313,6 +484,40 @@ impl Config {
                         anyhow::bail!("--session requires 'save', 'restore', or 'clear'");
                     }
                 }
+                "--export-state" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow::anyhow!("--export-state requires a file path"))?;
+                    state_action = Some(StateAction::Export(PathBuf::from(path)));
+                }
+                "--import-state" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow::anyhow!("--import-state requires a file path"))?;
+                    state_action = Some(StateAction::Import {
+                        path: PathBuf::from(path),
+                        replace: false,
+                    });
+                }
+                "--replace" => replace_state = true,
+                "--serve" => {
+                    if !cfg!(feature = "serve") {
+                        anyhow::bail!("--serve requires fv built with the serve feature");
+                    }
+                    let port = args
+                        .next()
+                        .ok_or_else(|| anyhow::anyhow!("--serve requires a port"))?;
+                    serve_port = Some(
+                        port.parse()
+                            .map_err(|_| anyhow::anyhow!("--serve requires a port number"))?,
+                    );
+                }
+                "--serve-token" => {
+                    serve_token = Some(
+                        args.next()
+                            .ok_or_else(|| anyhow::anyhow!("--serve-token requires a value"))?,
+                    );
+                }
                 "--resume-ai-session" => {
                     let name = match args.peek() {
                         Some(next) if !next.starts_with('-') => args.next().unwrap(),
@@ -377,6 +582,8 @@ impl Config {
                 "--no-icons" => icons_enabled = Some(false),
                 "--hidden" | "-a" => show_hidden = Some(true),
                 "--no-hidden" => show_hidden = Some(false),
+                "--dir-slash" => output_options.trailing_slash = true,
+                "--with-types" => output_options.types = true,
                 "--format" | "-f" => {
                     if let Some(fmt) = args.next() {
                         output_format = OutputFormat::from_str(&fmt).map_err(|_| {
@@ -407,13 +614,28 @@ impl Config {
                 path if !path.starts_with('-') => {
                     let p = PathBuf::from(path);
                     if p.is_dir() {
-                        root = p.canonicalize()?;
-                    } else if p.is_file() {
-                        if let Some(parent) = p.parent() {
-                            root = parent.canonicalize()?;
+                        // The first directory is the root; later ones join the workspace
+                        let dir = p.canonicalize()?;
+                        if !root_given {
+                            root = dir;
+                            root_given = true;
+                        } else if dir != root && !extra_roots.contains(&dir) {
+                            extra_roots.push(dir);
                         }
                     } else {
-                        anyhow::bail!("Path does not exist: {}", path);
+                        // A `file:line[:col]` location names a file too
+                        let (p, line) = match split_location(path) {
+                            _ if p.is_file() => (p, None),
+                            (file, line @ Some(_), _) if Path::new(file).is_file() => {
+                                (PathBuf::from(file), line)
+                            }
+                            _ => anyhow::bail!("Path does not exist: {}", path),
+                        };
+                        let file = p.canonicalize()?;
+                        root = launch_root(&p, &file, &env::current_dir()?.canonicalize()?);
+                        root_given = true;
+                        focus_path = Some(file);
+                        focus_line = line;
                     }
                 }
                 unknown => {
@@ -427,7 +649,7 @@ impl Config {
 
         // Handle stdin mode
         let stdin_paths = if stdin_mode {
-            Some(read_stdin_paths()?)
+            Some(read_stdin_paths(null_separated)?)
         } else {
             None
         };
@@ -441,12 +663,35 @@ impl Config {
 
         context_pack_options.format = context_pack_format;
 
+        match state_action {
+            Some(StateAction::Import {
+                ref mut replace, ..
+            }) => *replace = replace_state,
+            _ if replace_state => anyhow::bail!("--replace requires --import-state"),
+            _ => {}
+        }
+        if watch_output && !tree_mode && !context_mode {
+            anyhow::bail!("--watch requires --tree or --context");
+        }
+
+        // --preview only applies when a file was named (a line implies it)
+        let open_preview = (open_preview || focus_line.is_some()) && focus_path.is_some();
+
+        let (ascii_only, tree_guides) = resolve_tree_guides(&config_file);
+        let icon_set = icon_set_from(&config_file, ascii_only);
+
         // Merge config file settings with CLI overrides
         // CLI arguments take precedence over config file
         Ok(Self {
             root,
+            extra_roots,
+            focus_path,
+            focus_line,
+            open_preview,
             pick_mode,
+            pick_dirs,
             output_format,
+            output_options,
             callback,
             icons_enabled,
             choosedir_mode,
@@ -456,17 +701,70 @@ impl Config {
             // Settings from config file (CLI can override some)
             show_hidden: show_hidden.unwrap_or(config_file.general.show_hidden),
             mouse_enabled: config_file.general.mouse_enabled,
+            mouse_single_click: ClickAction::from_str(&config_file.general.mouse_single_click)
+                .unwrap_or_default(),
+            mouse_double_click: ClickAction::from_str(&config_file.general.mouse_double_click)
+                .unwrap_or(ClickAction::Expand),
+            relative_symlinks: config_file.general.relative_symlinks,
+            confine_to_root: config_file.general.confine_to_root,
+            startup_expand_depth: config_file.general.startup_expand_depth,
+            rename_select: RenameSelect::from_str(&config_file.general.rename_select)
+                .unwrap_or_default(),
+            collapse_fallback: CollapseFallback::from_str(&config_file.general.collapse_fallback)
+                .unwrap_or_default(),
+            file_enter_action: FileEnterAction::from_str(&config_file.general.file_enter_action)
+                .unwrap_or_default(),
+            opener: config_file.general.opener.clone(),
+            delete_confirm: delete_confirm_from(&config_file),
+            use_trash: config_file.general.use_trash,
+            confirm_rename_overwrite: config_file.general.confirm_rename_overwrite,
+            create_parents: config_file.general.create_parents,
+            hidden_scope: HiddenScope::from_str(&config_file.general.hidden_toggle_scope)
+                .unwrap_or_default(),
+            content_search_max_results: config_file.general.content_search_max_results,
+            fuzzy_max_results: config_file.general.fuzzy_max_results.max(1),
+            fuzzy_exclude: config_file.general.fuzzy_exclude.clone(),
+            fuzzy_gitignore: config_file.general.fuzzy_gitignore,
+            copy_relative_path: config_file.general.copy_relative_path,
+            dirs_first: config_file.general.dirs_first,
+            watch_recursive: config_file.general.watch_recursive,
             hex_max_bytes: config_file.preview.hex_max_bytes,
             max_archive_entries: config_file.preview.max_archive_entries,
             image_protocol: config_file.preview.image_protocol.clone(),
+            syntax_theme: config_file.preview.syntax_theme.clone(),
+            tab_width: config_file.preview.tab_width,
+            max_line_width: config_file.preview.max_line_width,
+            preview_wrap: config_file.preview.wrap,
+            preview_line_numbers: config_file.preview.line_numbers,
+            preview_borders: config_file.preview.borders,
+            use_file_command: config_file.preview.use_file_command,
+            preview_position: PreviewPosition::from_str(&config_file.preview.position)
+                .unwrap_or_default(),
+            preview_split: preview_split_from(&config_file),
+            preview_debounce: Duration::from_millis(config_file.preview.preview_debounce_ms),
+            thumbnail_cache_bytes: config_file.preview.thumbnail_cache_mb * 1024 * 1024,
+            csv_max_rows: config_file.preview.csv_max_rows,
             git_poll_interval: Duration::from_secs(config_file.performance.git_poll_interval_secs),
+            cadence: cadence_from(&config_file),
             show_size: config_file.ui.show_size,
             show_permissions: config_file.ui.show_permissions,
             date_format: config_file.ui.date_format,
+            tree_guides,
+            dim_tree_guides: config_file.ui.dim_tree_guides,
+            name_truncation: NameTruncation::from_str(&config_file.ui.name_truncation)
+                .unwrap_or_default(),
+            status_format: Some(config_file.ui.status_format.clone())
+                .filter(|format| !format.trim().is_empty()),
+            bell_on_error: config_file.ui.bell_on_error,
+            flash_on_error: config_file.ui.flash_on_error,
+            relative_numbers: config_file.ui.relative_numbers,
+            ascii_only,
+            icon_set,
             commands: config_file.commands,
             preview_custom: config_file.preview,
             tree_mode,
             tree_depth,
+            watch_output,
             with_content,
             select_mode,
             multi_select,
@@ -477,47 +775,156 @@ impl Config {
             benchmark_iterations,
             context_pack,
             context_pack_options,
+            concat_mode,
+            concat_options,
+            symbols_mode,
             select_related_path,
             explain_selection,
             session_action,
+            state_action,
             plugin_action,
             plugin_path,
             init_action,
             init_path,
             init_force,
             resume_ai_session,
+            serve_port,
+            serve_token: serve_token.or_else(|| {
+                env::var("FILEVIEW_SERVE_TOKEN")
+                    .ok()
+                    .filter(|token| !token.is_empty())
+            }),
         })
     }
 }
 
-/// Read paths from stdin (one path per line)
-fn read_stdin_paths() -> anyhow::Result<Vec<PathBuf>> {
-    let stdin = io::stdin();
+impl Config {
+    /// Apply settings from a reloaded config file
+    ///
+    /// Only settings that can change while running are updated. CLI overrides
+    /// and startup-only settings (mouse, image protocol) are left alone.
+    pub fn apply_file(&mut self, config_file: &ConfigFile) {
+        let (ascii_only, tree_guides) = resolve_tree_guides(config_file);
+        self.relative_symlinks = config_file.general.relative_symlinks;
+        self.confine_to_root = config_file.general.confine_to_root;
+        self.rename_select =
+            RenameSelect::from_str(&config_file.general.rename_select).unwrap_or_default();
+        self.file_enter_action =
+            FileEnterAction::from_str(&config_file.general.file_enter_action).unwrap_or_default();
+        self.collapse_fallback =
+            CollapseFallback::from_str(&config_file.general.collapse_fallback).unwrap_or_default();
+        self.mouse_single_click =
+            ClickAction::from_str(&config_file.general.mouse_single_click).unwrap_or_default();
+        self.mouse_double_click = ClickAction::from_str(&config_file.general.mouse_double_click)
+            .unwrap_or(ClickAction::Expand);
+        self.opener = config_file.general.opener.clone();
+        self.delete_confirm = delete_confirm_from(config_file);
+        self.use_trash = config_file.general.use_trash;
+        self.confirm_rename_overwrite = config_file.general.confirm_rename_overwrite;
+        self.create_parents = config_file.general.create_parents;
+        self.hidden_scope =
+            HiddenScope::from_str(&config_file.general.hidden_toggle_scope).unwrap_or_default();
+        self.content_search_max_results = config_file.general.content_search_max_results;
+        self.fuzzy_max_results = config_file.general.fuzzy_max_results.max(1);
+        self.fuzzy_exclude = config_file.general.fuzzy_exclude.clone();
+        self.fuzzy_gitignore = config_file.general.fuzzy_gitignore;
+        self.copy_relative_path = config_file.general.copy_relative_path;
+        self.dirs_first = config_file.general.dirs_first;
+        self.watch_recursive = config_file.general.watch_recursive;
+        self.hex_max_bytes = config_file.preview.hex_max_bytes;
+        self.max_archive_entries = config_file.preview.max_archive_entries;
+        self.syntax_theme = config_file.preview.syntax_theme.clone();
+        self.tab_width = config_file.preview.tab_width;
+        self.max_line_width = config_file.preview.max_line_width;
+        self.preview_wrap = config_file.preview.wrap;
+        self.preview_line_numbers = config_file.preview.line_numbers;
+        self.preview_borders = config_file.preview.borders;
+        self.use_file_command = config_file.preview.use_file_command;
+        self.preview_position =
+            PreviewPosition::from_str(&config_file.preview.position).unwrap_or_default();
+        self.preview_split = preview_split_from(config_file);
+        self.preview_debounce = Duration::from_millis(config_file.preview.preview_debounce_ms);
+        self.thumbnail_cache_bytes = config_file.preview.thumbnail_cache_mb * 1024 * 1024;
+        self.csv_max_rows = config_file.preview.csv_max_rows;
+        self.git_poll_interval =
+            Duration::from_secs(config_file.performance.git_poll_interval_secs);
+        self.cadence = cadence_from(config_file);
+        self.show_size = config_file.ui.show_size;
+        self.show_permissions = config_file.ui.show_permissions;
+        self.date_format = config_file.ui.date_format.clone();
+        self.tree_guides = tree_guides;
+        self.dim_tree_guides = config_file.ui.dim_tree_guides;
+        self.name_truncation =
+            NameTruncation::from_str(&config_file.ui.name_truncation).unwrap_or_default();
+        self.status_format =
+            Some(config_file.ui.status_format.clone()).filter(|format| !format.trim().is_empty());
+        self.bell_on_error = config_file.ui.bell_on_error;
+        self.flash_on_error = config_file.ui.flash_on_error;
+        self.relative_numbers = config_file.ui.relative_numbers;
+        self.ascii_only = ascii_only;
+        self.icon_set = icon_set_from(config_file, ascii_only);
+        self.commands = config_file.commands.clone();
+        self.preview_custom = config_file.preview.clone();
+    }
+}
+
+/// Delete confirmation policy from the `[general]` section
+///
+/// Unknown modes fall back to always confirming.
+fn delete_confirm_from(config_file: &ConfigFile) -> DeleteConfirmPolicy {
+    let general = &config_file.general;
+    DeleteConfirmPolicy {
+        mode: ConfirmDeleteMode::from_str(&general.confirm_delete).unwrap_or_default(),
+        max_items: general.confirm_delete_max_items,
+        max_bytes: general.confirm_delete_max_mb.saturating_mul(1024 * 1024),
+    }
+}
+
+/// Poll cadence and frame cap from the `[performance]` section
+fn cadence_from(config_file: &ConfigFile) -> Cadence {
+    let perf = &config_file.performance;
+    Cadence::new(perf.tick_ms, perf.idle_tick_ms, perf.max_fps)
+}
+
+/// Preview split from `preview.split_percent`, kept within the resizable range (0 = auto)
+fn preview_split_from(config_file: &ConfigFile) -> u16 {
+    match config_file.preview.split_percent {
+        0 => 0,
+        pct => pct.clamp(PREVIEW_SPLIT_MIN, PREVIEW_SPLIT_MAX),
+    }
+}
+
+/// Resolve `ascii_only` (config file or FILEVIEW_ASCII) and the effective tree guide style
+fn resolve_tree_guides(config_file: &ConfigFile) -> (bool, TreeGuideStyle) {
+    let ascii_only = config_file.ui.ascii_only
+        || std::env::var("FILEVIEW_ASCII")
+            .map(|v| v != "0" && v.to_lowercase() != "false")
+            .unwrap_or(false);
+    let tree_guides = TreeGuideStyle::from_str(&config_file.ui.tree_guides)
+        .unwrap_or_default()
+        .resolve(ascii_only);
+    (ascii_only, tree_guides)
+}
+
+/// Effective icon set: the `icons` setting, degraded to ASCII by `ascii_only`
+fn icon_set_from(config_file: &ConfigFile, ascii_only: bool) -> IconSet {
+    IconSet::from_str(&config_file.ui.icons)
+        .unwrap_or_default()
+        .resolve(ascii_only)
+}
+
+/// Read paths from stdin (one per line, or NUL-separated with `--null`)
+fn read_stdin_paths(null_separated: bool) -> anyhow::Result<Vec<PathBuf>> {
+    let mut stdin = io::stdin();
 
     // Check if stdin is a TTY (not piped)
     if stdin.is_terminal() {
         anyhow::bail!("--stdin requires piped input");
     }
 
-    let cwd = env::current_dir()?;
-    let paths: Vec<PathBuf> = stdin
-        .lock()
-        .lines()
-        .map_while(Result::ok)
-        .filter(|line| !line.trim().is_empty())
-        .filter_map(|line| {
-            let path = PathBuf::from(line.trim());
-            let resolved = if path.is_absolute() {
-                path
-            } else {
-                cwd.join(&path)
-            };
-
-            // canonicalize() resolves ".." components and verifies path exists
-            // This prevents path traversal attacks like "../../../etc/passwd"
-            resolved.canonicalize().ok()
-        })
-        .collect();
+    let mut input = Vec::new();
+    stdin.read_to_end(&mut input)?;
+    let paths = parse_path_list(&input, null_separated, &env::current_dir()?);
 
     if paths.is_empty() {
         anyhow::bail!("No valid paths provided via stdin");
@@ -526,6 +933,102 @@ fn read_stdin_paths() -> anyhow::Result<Vec<PathBuf>> {
     Ok(paths)
 }
 
+/// Parse a piped list of paths into existing, canonical paths
+///
+/// Entries are separated by newlines, or by NUL bytes when `null_separated`
+/// is set or the input contains any (`fd -0`, `find -print0`), so names
+/// with newlines survive. Names are taken as raw bytes on unix, so ones that
+/// are not UTF-8 still resolve. Relative entries resolve against `cwd`;
+/// entries that do not exist and repeated entries are dropped.
+pub fn parse_path_list(input: &[u8], null_separated: bool, cwd: &Path) -> Vec<PathBuf> {
+    let separator = if null_separated || input.contains(&b'\0') {
+        b'\0'
+    } else {
+        b'\n'
+    };
+
+    let mut seen = HashSet::new();
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for entry in input.split(|&byte| byte == separator) {
+        // Newline lists are trimmed; NUL-separated names are taken verbatim
+        let entry = if separator == b'\n' {
+            entry.trim_ascii()
+        } else {
+            entry
+        };
+        if entry.is_empty() {
+            continue;
+        }
+        let path = path_from_bytes(entry);
+        let resolved = if path.is_absolute() {
+            path
+        } else {
+            cwd.join(&path)
+        };
+
+        // canonicalize() resolves ".." components and verifies path exists
+        // This prevents path traversal attacks like "../../../etc/passwd"
+        if let Ok(path) = resolved.canonicalize() {
+            if seen.insert(path.clone()) {
+                paths.push(path);
+            }
+        }
+    }
+    paths
+}
+
+/// Split a `path:line[:col]` location (`foo.rs:42:7`) into its parts
+///
+/// Only numeric suffixes are split off; lines and columns are 1-based as
+/// printed by compilers and `grep -n`.
+pub fn split_location(arg: &str) -> (&str, Option<usize>, Option<usize>) {
+    // Too large to parse still counts as a (nonexistent) line
+    let number = |s: &str| {
+        (!s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()))
+            .then(|| s.parse().unwrap_or(usize::MAX))
+    };
+    let Some((rest, last)) = arg.rsplit_once(':') else {
+        return (arg, None, None);
+    };
+    let Some(last) = number(last) else {
+        return (arg, None, None);
+    };
+    match rest.rsplit_once(':') {
+        Some((path, line)) if !path.is_empty() => match number(line) {
+            Some(line) => (path, Some(line), Some(last)),
+            None => (rest, Some(last), None),
+        },
+        _ => (rest, Some(last), None),
+    }
+}
+
+/// Path from the raw bytes of a name (lossy where paths are not bytes)
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        PathBuf::from(std::ffi::OsStr::from_bytes(bytes))
+    }
+    #[cfg(not(unix))]
+    {
+        PathBuf::from(String::from_utf8_lossy(bytes).into_owned())
+    }
+}
+
+/// Root directory when launched with a file argument
+///
+/// A relative path keeps the current directory as root when the file is
+/// inside it (`fv src/app/main.rs` from the project root); otherwise the
+/// file's directory is used.
+fn launch_root(arg: &Path, file: &Path, cwd: &Path) -> PathBuf {
+    if arg.is_relative() && file.starts_with(cwd) {
+        return cwd.to_path_buf();
+    }
+    file.parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| cwd.to_path_buf())
+}
+
 /// Find the common ancestor directory of all paths
 fn find_common_ancestor(paths: &[PathBuf]) -> anyhow::Result<PathBuf> {
     if paths.is_empty() {
@@ -572,14 +1075,23 @@ fn print_help() {
         r#"fv - FileView: A minimal file tree UI
 
 USAGE:
-    fv [OPTIONS] [PATH]
+    fv [OPTIONS] [PATH]...
     command | fv --stdin [OPTIONS]
     fv benchmark ai [--scenario NAME] [--iterations N] [PATH]
 
 OPTIONS:
     -p, --pick          Pick mode: output selected path(s) to stdout
+    --allow-dirs        Pick mode: Enter on a directory picks it (default)
+    --expand-dirs       Pick mode: Enter on a directory expands it instead of picking it
+    --dirs-only         Pick mode: show and pick directories only
+    --preview           Open the preview panel for the file given as PATH
+                        (PATH:LINE[:COL] also opens it centered on LINE)
     -f, --format FMT    Output format for pick mode: lines, null, json
-    --stdin             Read paths from stdin (one per line)
+    --dir-slash         Append / to picked directory paths
+    --with-types        With --format json, output {{"path", "type"}} objects
+    --stdin, --from-stdin
+                        Browse only the paths read from stdin (one per line)
+    -0, --null          With --stdin, paths are NUL-separated (fd -0, find -print0)
     --on-select CMD     Run command when file is selected (use {{path}}, {{name}}, etc.)
     --choosedir [FILE]  Write directory path to FILE on exit (for shell cd integration)
     --selection-path F  Write selected file paths to FILE on exit
@@ -592,7 +1104,10 @@ OPTIONS:
 
 CLAUDE CODE INTEGRATION:
     -t, --tree          Output directory tree to stdout (non-interactive)
-    --depth N           Limit tree depth to N levels
+    --depth N           Limit tree depth to N levels (alias: --max-depth)
+    --tree --format json
+                        Output the tree as nested JSON (name, path, type, size, git)
+    --watch             With --tree or --context, print again whenever files change
     --with-content      Include file contents in pick output (Claude format)
     --select-mode       Simple selection mode: Enter to select, output to stdout
     --multi             Allow multiple selection in select mode
@@ -605,9 +1120,18 @@ CLAUDE CODE INTEGRATION:
     --include-git-diff  Force include git diff summary in context pack
     --include-tests     Include inferred test files in context pack
     --context-depth N   Max recursive scan depth for fallback file discovery
+    --concat            Output all text files under PATH with `=== path ===` headers
+    --concat-bytes N    Total content limit for --concat (default: 1048576)
+    --concat-files N    File count limit for --concat (default: 500)
+    --symbols           Output top-level code symbols under PATH as JSON (--depth caps the scan, default: 16)
     --select-related F  Output related file paths for file F
     --explain-selection Include score/reasons for --select-related output
     --session ACTION    Session management: save, restore, or clear
+    --export-state FILE Write recent files, bookmarks and the sessions under PATH to FILE (JSON)
+    --import-state FILE Merge recent files, bookmarks and sessions from FILE
+    --replace           With --import-state, overwrite instead of merging
+    --serve PORT        Serve the tree state as JSON on 127.0.0.1:PORT (serve feature)
+    --serve-token TOKEN Require this bearer token from API clients
     --resume-ai-session [NAME]
                         Resume named AI session non-interactively (default name: ai)
     benchmark ai        Run AI benchmark scenarios (context-pack/review-pack/related/all)
@@ -623,8 +1147,11 @@ CONFIG FILE:
 
 ENVIRONMENT:
     FILEVIEW_ICONS=0            Disable icons
-    FILEVIEW_IMAGE_PROTOCOL     Force image protocol: auto, halfblocks, chafa, sixel, kitty, iterm2
+    FILEVIEW_ASCII=1            Avoid Unicode drawing characters (tree guides degrade to ASCII)
+    FILEVIEW_IMAGE_PROTOCOL     Force image protocol: auto, halfblocks, chafa, sixel, kitty, iterm2, command
+    FILEVIEW_IMAGE_COMMAND      External image renderer for the command protocol ($f, $w, $h)
     FILEVIEW_HELP_KEY_STYLE     Help key style: solid (default), outline, plain
+    FILEVIEW_SERVE_TOKEN        Bearer token for --serve when --serve-token is not given
 
 KEYBINDINGS:
     j/↓         Move down
@@ -5,8 +5,10 @@
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use crate::core::CSV_MAX_ROWS;
+use crate::core::EXCLUDED_DIRS;
 pub use crate::handler::HooksConfig;
 
 /// Main configuration file structure
@@ -37,6 +39,53 @@ pub struct GeneralConfig {
     pub enable_icons: bool,
     /// Enable mouse support
     pub mouse_enabled: bool,
+    /// Single click on an entry: "focus", "expand", "open" or "preview"
+    pub mouse_single_click: String,
+    /// Double click on an entry: "focus", "expand", "open" or "preview"
+    pub mouse_double_click: String,
+    /// Create symlinks with relative targets by default
+    pub relative_symlinks: bool,
+    /// Refuse delete, rename, move and paste outside the root
+    pub confine_to_root: bool,
+    /// Directory levels expanded at startup (1 = only the root's children)
+    pub startup_expand_depth: usize,
+    /// Rename prompt cursor: "stem" (before the extension) or "name" (at the end)
+    pub rename_select: String,
+    /// Enter on a file: "preview", "open" (system opener) or "select"
+    pub file_enter_action: String,
+    /// `h` on a collapsed directory or a file: "none", "parent" (focus it)
+    /// or "root_up" (also move the root up from a top-level entry)
+    pub collapse_fallback: String,
+    /// Program used to open files (empty = xdg-open / open / start)
+    pub opener: String,
+    /// Delete confirmation: "always", "off", or "threshold"
+    pub confirm_delete: String,
+    /// Threshold mode: confirm deletes of more items than this
+    pub confirm_delete_max_items: usize,
+    /// Threshold mode: confirm deletes larger than this many MiB
+    pub confirm_delete_max_mb: u64,
+    /// `D` moves to the system trash (off = deletes permanently)
+    pub use_trash: bool,
+    /// Ask before a rename replaces an existing file (off = overwrite)
+    pub confirm_rename_overwrite: bool,
+    /// New file/dir names containing `/` create the missing directories
+    pub create_parents: bool,
+    /// What `.` toggles hidden files in: "global" or "directory" (the one at the cursor)
+    pub hidden_toggle_scope: String,
+    /// Stop a content search after this many matching lines
+    pub content_search_max_results: usize,
+    /// Results listed by the fuzzy finder
+    pub fuzzy_max_results: usize,
+    /// Directory names the fuzzy finder skips (default: the watcher's list)
+    pub fuzzy_exclude: Vec<String>,
+    /// Skip paths git ignores in the fuzzy finder
+    pub fuzzy_gitignore: bool,
+    /// `c` copies the path relative to the root instead of the absolute path
+    pub copy_relative_path: bool,
+    /// List directories above files (off = intermixed by the sort key)
+    pub dirs_first: bool,
+    /// Watch the whole tree for changes, not just the expanded directories
+    pub watch_recursive: bool,
 }
 
 impl Default for GeneralConfig {
@@ -45,6 +94,29 @@ impl Default for GeneralConfig {
             show_hidden: false,
             enable_icons: true,
             mouse_enabled: true,
+            mouse_single_click: "focus".to_string(),
+            mouse_double_click: "expand".to_string(),
+            relative_symlinks: true,
+            confine_to_root: false,
+            startup_expand_depth: 1,
+            rename_select: "stem".to_string(),
+            file_enter_action: "preview".to_string(),
+            collapse_fallback: "none".to_string(),
+            opener: String::new(),
+            confirm_delete: "always".to_string(),
+            confirm_delete_max_items: 10,
+            confirm_delete_max_mb: 10,
+            use_trash: true,
+            confirm_rename_overwrite: true,
+            create_parents: true,
+            hidden_toggle_scope: "global".to_string(),
+            content_search_max_results: 1000,
+            fuzzy_max_results: 15,
+            fuzzy_exclude: EXCLUDED_DIRS.iter().map(|d| d.to_string()).collect(),
+            fuzzy_gitignore: false,
+            copy_relative_path: false,
+            dirs_first: true,
+            watch_recursive: false,
         }
     }
 }
@@ -59,9 +131,39 @@ pub struct PreviewConfig {
     pub max_archive_entries: usize,
     /// Image protocol: "auto", "sixel", "kitty", "iterm2", "halfblocks"
     pub image_protocol: String,
+    /// External image renderer for `FILEVIEW_IMAGE_PROTOCOL=command`
+    /// ($f = file path, $w/$h = preview size in cells)
+    pub image_command: String,
     /// Custom preview scripts: extension -> command
     /// The command can use $f for the file path
     pub custom: HashMap<String, String>,
+    /// Colored preview commands: extension or file name glob -> command
+    /// ($f = file path), tried before the built-in previews
+    pub commands: HashMap<String, String>,
+    /// Syntax highlighting theme (bundled syntect theme name)
+    pub syntax_theme: String,
+    /// Number of columns a tab expands to in text previews (0 = keep tabs)
+    pub tab_width: usize,
+    /// Columns a text preview line is cut at (0 = no limit)
+    pub max_line_width: usize,
+    /// Soft-wrap long lines in text previews
+    pub wrap: bool,
+    /// Show line numbers in text previews
+    pub line_numbers: bool,
+    /// Frame previews with a border and title (false = content only)
+    pub borders: bool,
+    /// Classify files of unknown type with `file --mime-type`
+    pub use_file_command: bool,
+    /// Preview position: "right", "left", "bottom", "top"
+    pub position: String,
+    /// Preview share of the split in percent (0 = by terminal width)
+    pub split_percent: u16,
+    /// Milliseconds focus rests on an entry before it is previewed (0 = at once)
+    pub preview_debounce_ms: u64,
+    /// Megabytes of image thumbnails kept in the cache directory (0 = no thumbnails)
+    pub thumbnail_cache_mb: u64,
+    /// Rows of a CSV/TSV file parsed for the table preview
+    pub csv_max_rows: usize,
 }
 
 impl Default for PreviewConfig {
@@ -70,7 +172,21 @@ impl Default for PreviewConfig {
             hex_max_bytes: 4096,
             max_archive_entries: 500,
             image_protocol: "auto".to_string(),
+            image_command: String::new(),
             custom: HashMap::new(),
+            commands: HashMap::new(),
+            syntax_theme: "base16-ocean.dark".to_string(),
+            tab_width: 4,
+            max_line_width: 1000,
+            wrap: false,
+            line_numbers: true,
+            borders: true,
+            use_file_command: false,
+            position: "right".to_string(),
+            split_percent: 0,
+            preview_debounce_ms: 80,
+            thumbnail_cache_mb: 256,
+            csv_max_rows: CSV_MAX_ROWS,
         }
     }
 }
@@ -81,12 +197,21 @@ impl Default for PreviewConfig {
 pub struct PerformanceConfig {
     /// Git status polling interval in seconds
     pub git_poll_interval_secs: u64,
+    /// Event poll interval in milliseconds while input is arriving
+    pub tick_ms: u64,
+    /// Longest event poll interval in milliseconds when idle
+    pub idle_tick_ms: u64,
+    /// Maximum redraws per second (0 = uncapped)
+    pub max_fps: u32,
 }
 
 impl Default for PerformanceConfig {
     fn default() -> Self {
         Self {
             git_poll_interval_secs: 5,
+            tick_ms: 60,
+            idle_tick_ms: 500,
+            max_fps: 60,
         }
     }
 }
@@ -101,6 +226,24 @@ pub struct UiConfig {
     pub show_permissions: bool,
     /// Date format string (strftime-style)
     pub date_format: String,
+    /// Tree indentation guides: "spaces", "ascii", "unicode"
+    pub tree_guides: String,
+    /// Draw tree guides in a dimmed color
+    pub dim_tree_guides: bool,
+    /// Where long names are cut: "tail", "middle" or "smart" (keep the extension)
+    pub name_truncation: String,
+    /// Status bar template, e.g. "{mode} {path} {git}" (empty = built-in layout)
+    pub status_format: String,
+    /// Ring the terminal bell when an error is shown
+    pub bell_on_error: bool,
+    /// Invert the status bar briefly when an error is shown
+    pub flash_on_error: bool,
+    /// Show each tree row's distance from the cursor in a left gutter
+    pub relative_numbers: bool,
+    /// Avoid Unicode drawing characters (terminal lacks Unicode support)
+    pub ascii_only: bool,
+    /// Icon glyphs: "nerdfont", "emoji", "ascii", "none", or "auto"
+    pub icons: String,
 }
 
 impl Default for UiConfig {
@@ -109,6 +252,15 @@ impl Default for UiConfig {
             show_size: true,
             show_permissions: false,
             date_format: "%Y-%m-%d %H:%M".to_string(),
+            tree_guides: "spaces".to_string(),
+            dim_tree_guides: true,
+            name_truncation: "tail".to_string(),
+            status_format: String::new(),
+            bell_on_error: false,
+            flash_on_error: false,
+            relative_numbers: false,
+            ascii_only: false,
+            icons: "nerdfont".to_string(),
         }
     }
 }
@@ -232,6 +384,19 @@ impl ConfigFile {
         let config: ConfigFile = toml::from_str(&content)?;
         Ok(config)
     }
+
+    /// Re-read the configuration from `path` (live reload)
+    ///
+    /// A missing file resets to defaults. On a parse error the current
+    /// settings are kept and the error is returned.
+    pub fn reload(&mut self, path: &Path) -> anyhow::Result<()> {
+        *self = if path.exists() {
+            Self::load_from(&path.to_path_buf())?
+        } else {
+            Self::default()
+        };
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -246,13 +411,25 @@ mod tests {
         assert!(!config.general.show_hidden);
         assert!(config.general.enable_icons);
         assert!(config.general.mouse_enabled);
+        assert_eq!(config.general.confirm_delete, "always");
+        assert_eq!(config.general.fuzzy_max_results, 15);
         assert_eq!(config.preview.hex_max_bytes, 4096);
+        assert_eq!(config.preview.syntax_theme, "base16-ocean.dark");
+        assert_eq!(config.preview.tab_width, 4);
+        assert_eq!(config.preview.max_line_width, 1000);
         assert_eq!(config.preview.max_archive_entries, 500);
         assert_eq!(config.preview.image_protocol, "auto");
         assert_eq!(config.performance.git_poll_interval_secs, 5);
+        assert_eq!(config.performance.tick_ms, 60);
+        assert_eq!(config.performance.idle_tick_ms, 500);
+        assert_eq!(config.performance.max_fps, 60);
         assert!(config.ui.show_size);
         assert!(!config.ui.show_permissions);
         assert_eq!(config.ui.date_format, "%Y-%m-%d %H:%M");
+        assert_eq!(config.ui.tree_guides, "spaces");
+        assert!(config.ui.dim_tree_guides);
+        assert_eq!(config.ui.name_truncation, "tail");
+        assert!(!config.ui.ascii_only);
     }
 
     #[test]
@@ -278,14 +455,22 @@ hex_max_bytes = 8192
 show_hidden = true
 enable_icons = false
 mouse_enabled = false
+confirm_delete = "threshold"
+confirm_delete_max_items = 5
+use_trash = false
+fuzzy_max_results = 40
 
 [preview]
 hex_max_bytes = 8192
 max_archive_entries = 1000
 image_protocol = "kitty"
+position = "bottom"
+split_percent = 30
 
 [performance]
 git_poll_interval_secs = 10
+tick_ms = 30
+max_fps = 120
 
 [ui]
 show_size = false
@@ -296,10 +481,20 @@ date_format = "%d/%m/%Y"
         assert!(config.general.show_hidden);
         assert!(!config.general.enable_icons);
         assert!(!config.general.mouse_enabled);
+        assert_eq!(config.general.confirm_delete, "threshold");
+        assert_eq!(config.general.confirm_delete_max_items, 5);
+        assert_eq!(config.general.confirm_delete_max_mb, 10); // default
+        assert!(!config.general.use_trash);
+        assert_eq!(config.general.fuzzy_max_results, 40);
         assert_eq!(config.preview.hex_max_bytes, 8192);
         assert_eq!(config.preview.max_archive_entries, 1000);
         assert_eq!(config.preview.image_protocol, "kitty");
+        assert_eq!(config.preview.position, "bottom");
+        assert_eq!(config.preview.split_percent, 30);
         assert_eq!(config.performance.git_poll_interval_secs, 10);
+        assert_eq!(config.performance.tick_ms, 30);
+        assert_eq!(config.performance.idle_tick_ms, 500); // default
+        assert_eq!(config.performance.max_fps, 120);
         assert!(!config.ui.show_size);
         assert!(config.ui.show_permissions);
         assert_eq!(config.ui.date_format, "%d/%m/%Y");
@@ -330,6 +525,40 @@ show_hidden = true
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_reload_replaces_settings() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[preview]\ntab_width = 2\n").unwrap();
+        let mut config = ConfigFile::load_from(&path).unwrap();
+
+        fs::write(
+            &path,
+            "[preview]\ntab_width = 8\nsyntax_theme = \"InspiredGitHub\"\n",
+        )
+        .unwrap();
+        config.reload(&path).unwrap();
+        assert_eq!(config.preview.tab_width, 8);
+        assert_eq!(config.preview.syntax_theme, "InspiredGitHub");
+
+        // Removing the file resets to defaults
+        fs::remove_file(&path).unwrap();
+        config.reload(&path).unwrap();
+        assert_eq!(config.preview.tab_width, 4);
+    }
+
+    #[test]
+    fn test_reload_invalid_keeps_previous() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[general]\nshow_hidden = true\n").unwrap();
+        let mut config = ConfigFile::load_from(&path).unwrap();
+
+        fs::write(&path, "[general]\nshow_hidden = \"yes\n").unwrap();
+        assert!(config.reload(&path).is_err());
+        assert!(config.general.show_hidden);
+    }
+
     #[test]
     #[cfg(unix)]
     fn test_expand_shell_escaped_unix_style() {
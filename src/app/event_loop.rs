@@ -1,29 +1,54 @@
 //! Main event loop for the application
 
-use std::io::Stdout;
+use std::io::{Stdout, Write};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
-use crossterm::event::{self, Event};
+use crossterm::event::{
+    self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+    Event,
+};
+use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, execute};
 use ratatui::prelude::*;
 
 use crate::action::file as file_ops;
-use crate::app::{Config, PreviewState};
-use crate::core::{AppState, FocusTarget, TabManager, ViewMode};
+use crate::action::OperationKind;
+use crate::app::reload::{reload_keymap, ConfigChanges, ConfigReloader};
+use crate::app::{Config, FuzzyPreview, PreviewState, FUZZY_PREVIEW_DELAY};
+use crate::core::{
+    AppState, DiskUsageJob, FocusTarget, TabManager, ViewMode, DISK_USAGE_INTERVAL,
+    PREVIEW_SPLIT_MAX, PREVIEW_SPLIT_MIN,
+};
+use crate::git::GitStatus;
 use crate::handler::{
     action::{
-        get_target_directory, handle_action, reload_tree, update_bulk_rename_buffer, ActionContext,
-        ActionResult, EntrySnapshot,
+        displayed_entries, editor_rename, focus_best_live_match, get_filename_str,
+        get_target_directory, handle_action, poll_preview_search, refresh_selection_summary,
+        reload_changed, reload_tree, reveal_and_focus, update_bulk_rename_buffer,
+        update_search_matches, ActionContext, ActionResult, EntrySnapshot,
     },
-    key::{handle_key_event, update_input_buffer, KeyAction},
-    mouse::{handle_mouse_event, ClickDetector, MouseAction, PathBuffer},
+    key::{handle_key_event, handle_key_event_with_registry, update_input_buffer, KeyAction},
+    mouse::{
+        click_effect, handle_mouse_event, ClickDetector, ClickEffect, MouseAction, PathBuffer,
+    },
+    KeyBindingRegistry, KeymapFile,
 };
+use crate::integrate::{BookmarkStore, RecentStore, UiState};
+#[cfg(feature = "serve")]
+use crate::integrate::{ServeCommand, StateServer, TreeSnapshot};
 use crate::plugin::{PluginAction, PluginEvent, PluginManager};
-use crate::render::{collect_paths, fuzzy_match, visible_height, FuzzyMatch, Picker};
-use crate::tree::TreeNavigator;
-use crate::watcher::FileWatcher;
+use crate::render::preview::text::available_syntax_themes;
+use crate::render::preview::{
+    configure_image_command, configure_text_preview, resolve_image_command,
+};
+use crate::render::{
+    collect_paths_with, fuzzy_match_with_case, reload_theme, visible_height, FuzzyMatch, Picker,
+};
+use crate::tree::{TreeNavigator, STARTUP_EXPAND_ENTRY_LIMIT};
+use crate::watcher::{FileWatcher, WatchPoll};
 
-use super::render::{render_frame, RenderContext};
+use super::render::{render_frame, split_main_area, RenderContext};
 
 /// Result of running the app
 pub struct AppResult {
@@ -53,12 +78,18 @@ fn handle_file_drop(
     let dest = get_target_directory(focused_path, root);
     let mut success_count = 0;
     let mut fail_count = 0;
+    let (mut sources, mut results) = (Vec::new(), Vec::new());
     for src in paths {
         match file_ops::copy_to(src, &dest) {
-            Ok(_) => success_count += 1,
+            Ok(copied) => {
+                success_count += 1;
+                sources.push(src.clone());
+                results.push(copied);
+            }
             Err(_) => fail_count += 1,
         }
     }
+    state.log_operation(OperationKind::Copy, sources, results);
     reload_tree(navigator, state)?;
 
     let message = if fail_count == 0 {
@@ -66,23 +97,330 @@ fn handle_file_drop(
     } else {
         format!("Dropped {} file(s), {} failed", success_count, fail_count)
     };
-    state.set_message(message);
+    if fail_count == 0 {
+        state.set_message(message);
+    } else {
+        state.set_warning(message);
+    }
     Ok(success_count)
 }
 
+/// Apply the configured click action to the clicked entry
+fn apply_click(
+    entry: &EntrySnapshot,
+    is_double: bool,
+    navigator: &mut TreeNavigator,
+    state: &mut AppState,
+) {
+    let effect = click_effect(
+        state.mouse_single_click,
+        state.mouse_double_click,
+        is_double,
+        entry.is_dir,
+    );
+    match effect {
+        ClickEffect::None => {}
+        ClickEffect::ToggleDir => {
+            let _ = navigator.toggle_expand(&entry.path);
+            if navigator.is_denied(&entry.path) {
+                state.set_error(format!("Permission denied: {}", entry.path.display()));
+            }
+        }
+        ClickEffect::OpenFile => {
+            match crate::handler::action::command::open_with(&entry.path, &state.opener) {
+                Ok(()) => state.set_message(format!("Opened {}", entry.name)),
+                Err(e) => state.set_error(format!("Failed: open - {}", e)),
+            }
+        }
+        ClickEffect::PreviewFile => state.mode = ViewMode::Preview { scroll: 0 },
+    }
+}
+
+/// Ring the terminal bell (a failed write is not worth reporting)
+fn ring_bell(terminal: &mut Terminal<CrosstermBackend<Stdout>>) {
+    let backend = terminal.backend_mut();
+    let _ = backend
+        .write_all(b"\x07")
+        .and_then(|_| Write::flush(backend));
+}
+
+/// Temporarily leave the TUI to run an interactive program (e.g. `$EDITOR`)
+fn with_suspended_terminal<T>(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    f: impl FnOnce() -> T,
+) -> anyhow::Result<T> {
+    terminal::disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        cursor::Show
+    )?;
+
+    let result = f();
+
+    terminal::enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
+    terminal.clear()?;
+    Ok(result)
+}
+
+/// Rename entries by editing their names in `$EDITOR`
+fn run_editor_rename(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    state: &mut AppState,
+    navigator: &mut TreeNavigator,
+    entries: &[EntrySnapshot],
+) -> anyhow::Result<()> {
+    let targets = editor_rename::collect_targets(state, entries);
+    if targets.is_empty() {
+        state.set_message("Nothing to rename");
+        return Ok(());
+    }
+
+    let names: Vec<String> = targets.iter().map(|p| get_filename_str(Some(p))).collect();
+    let edited = with_suspended_terminal(terminal, || editor_rename::edit_names_in_editor(&names))?;
+
+    match edited.and_then(|content| editor_rename::plan_editor_rename(&targets, &content)) {
+        Ok(plan) => editor_rename::apply_plan(plan, state, navigator)?,
+        Err(e) => state.set_error(format!("Editor rename aborted: {}", e)),
+    }
+    Ok(())
+}
+
+/// Start watching `root` for changes
+///
+/// Without a working watcher fv carries on without auto-refresh.
+fn start_watcher(root: &Path, recursive: bool, state: &mut AppState) -> Option<FileWatcher> {
+    let watcher = if recursive {
+        FileWatcher::new_recursive(root)
+    } else {
+        FileWatcher::new(root)
+    };
+    state.watch_enabled = watcher.is_ok();
+    watcher.ok()
+}
+
+/// Apply a command received by the HTTP API
+#[cfg(feature = "serve")]
+fn apply_serve_command(
+    command: &ServeCommand,
+    navigator: &mut TreeNavigator,
+    state: &mut AppState,
+) -> anyhow::Result<()> {
+    let Some(target) = command.target(&state.root) else {
+        anyhow::bail!("path is outside {}", state.root.display());
+    };
+    match command {
+        ServeCommand::Focus { .. } => {
+            let index = displayed_entries(navigator, state)
+                .iter()
+                .position(|e| e.path == target);
+            match index {
+                Some(index) => state.focus_index = index,
+                None => anyhow::bail!("{} is not displayed", target.display()),
+            }
+        }
+        ServeCommand::Expand { .. } => {
+            navigator.reveal_path(&target)?;
+            navigator.expand(&target)?;
+        }
+        ServeCommand::Reveal { .. } => {
+            if !reveal_and_focus(navigator, state, &target)? {
+                anyhow::bail!("{} is hidden by a filter", target.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Apply edited config, keymap, and theme files
+///
+/// Each file is reloaded independently; a file that fails to parse keeps its
+/// previous settings and the error is shown instead.
+fn apply_config_changes(
+    changes: ConfigChanges,
+    reloader: &mut ConfigReloader,
+    config: &mut Config,
+    state: &mut AppState,
+    navigator: &mut TreeNavigator,
+    action_context: &mut ActionContext,
+    registry: &mut Option<KeyBindingRegistry>,
+) {
+    let mut errors = Vec::new();
+    let mut conflicts = Vec::new();
+
+    if changes.config {
+        match reloader.reload_config() {
+            Ok(config_file) => {
+                config.apply_file(config_file);
+                state.tree_guides = config.tree_guides;
+                state.icon_set = config.icon_set;
+                state.dim_tree_guides = config.dim_tree_guides;
+                state.name_truncation = config.name_truncation;
+                state.status_format = config.status_format.clone();
+                state.bell_on_error = config.bell_on_error;
+                state.flash_on_error = config.flash_on_error;
+                state.relative_numbers = config.relative_numbers;
+                state.preview_wrap = config.preview_wrap;
+                state.preview_line_numbers = config.preview_line_numbers;
+                state.preview_borders = config.preview_borders;
+                state.use_file_command = config.use_file_command;
+                state.preview_position = config.preview_position;
+                state.preview_split = config.preview_split;
+                state.preview_debounce = config.preview_debounce;
+                state.thumbnail_cache_bytes = config.thumbnail_cache_bytes;
+                state.csv_max_rows = config.csv_max_rows;
+                state.relative_symlinks = config.relative_symlinks;
+                state.confine_to_root = config.confine_to_root;
+                state.rename_select = config.rename_select;
+                state.file_enter_action = config.file_enter_action;
+                state.mouse_single_click = config.mouse_single_click;
+                state.mouse_double_click = config.mouse_double_click;
+                state.collapse_fallback = config.collapse_fallback;
+                state.opener = config.opener.clone();
+                state.preview_commands = config.preview_custom.commands.clone();
+                state.delete_confirm = config.delete_confirm;
+                state.use_trash = config.use_trash;
+                state.confirm_rename_overwrite = config.confirm_rename_overwrite;
+                state.create_parents = config.create_parents;
+                state.hidden_scope = config.hidden_scope;
+                state.content_search_max_results = config.content_search_max_results;
+                state.copy_relative_path = config.copy_relative_path;
+                if state.dirs_first != config.dirs_first {
+                    state.dirs_first = config.dirs_first;
+                    if let Err(e) = navigator.set_dirs_first(config.dirs_first) {
+                        errors.push(format!("re-sorting the tree: {}", e));
+                    }
+                }
+                action_context.commands = config.commands.clone();
+                configure_image_command(resolve_image_command(
+                    &config.preview_custom.image_command,
+                ));
+                if !configure_text_preview(
+                    &config.syntax_theme,
+                    config.tab_width,
+                    config.max_line_width,
+                ) {
+                    errors.push(format!(
+                        "unknown syntax theme '{}', using default (available: {})",
+                        config.syntax_theme,
+                        available_syntax_themes().join(", ")
+                    ));
+                }
+            }
+            Err(e) => errors.push(format!("config.toml: {}", e)),
+        }
+    }
+    if changes.keymap {
+        match reload_keymap(registry) {
+            Ok(found) => conflicts = found.unwrap_or_default(),
+            Err(e) => errors.push(format!("keymap.toml: {}", e)),
+        }
+    }
+    if changes.theme {
+        if let Err(e) = reload_theme() {
+            errors.push(format!("theme.toml: {}", e));
+        }
+    }
+
+    match errors.first() {
+        None if conflicts.is_empty() => state.set_message("Config reloaded"),
+        None => state.set_warning(format!(
+            "Config reloaded, conflicting bindings: {}",
+            conflicts.join("; ")
+        )),
+        Some(e) => state.set_error(format!("Config error: {}", e)),
+    }
+}
+
+/// Re-read keymap.toml on request, keeping the current bindings on error
+fn reload_key_registry(registry: &mut Option<KeyBindingRegistry>, state: &mut AppState) {
+    match reload_keymap(registry) {
+        Ok(None) => state.set_message("Keymap: no keymap.toml, using defaults"),
+        Ok(Some(conflicts)) if conflicts.is_empty() => state.set_message("Keymap reloaded"),
+        Ok(Some(conflicts)) => state.set_warning(format!(
+            "Keymap reloaded, conflicting bindings: {}",
+            conflicts.join("; ")
+        )),
+        Err(e) => state.set_error(format!("Keymap error: {}", e)),
+    }
+}
+
 /// Main event loop
 pub fn run_app(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
-    config: Config,
+    mut config: Config,
     image_picker: &mut Option<Picker>,
 ) -> anyhow::Result<AppResult> {
     let mut state = AppState::new(config.root.clone());
     state.pick_mode = config.pick_mode;
+    state.pick_dirs = config.pick_dirs;
     state.select_mode = config.select_mode;
     state.multi_select = config.multi_select;
 
     // Apply config file settings
     state.show_hidden = config.show_hidden;
+    state.tree_guides = config.tree_guides;
+    state.icon_set = config.icon_set;
+    state.dim_tree_guides = config.dim_tree_guides;
+    state.name_truncation = config.name_truncation;
+    state.status_format = config.status_format.clone();
+    state.bell_on_error = config.bell_on_error;
+    state.flash_on_error = config.flash_on_error;
+    state.relative_numbers = config.relative_numbers;
+    state.preview_wrap = config.preview_wrap;
+    state.preview_line_numbers = config.preview_line_numbers;
+    state.preview_borders = config.preview_borders;
+    state.use_file_command = config.use_file_command;
+    state.preview_position = config.preview_position;
+    state.preview_split = config.preview_split;
+    state.preview_debounce = config.preview_debounce;
+    state.thumbnail_cache_bytes = config.thumbnail_cache_bytes;
+    state.csv_max_rows = config.csv_max_rows;
+    state.relative_symlinks = config.relative_symlinks;
+    state.confine_to_root = config.confine_to_root;
+    state.rename_select = config.rename_select;
+    state.file_enter_action = config.file_enter_action;
+    state.mouse_single_click = config.mouse_single_click;
+    state.mouse_double_click = config.mouse_double_click;
+    state.collapse_fallback = config.collapse_fallback;
+    state.opener = config.opener.clone();
+    state.preview_commands = config.preview_custom.commands.clone();
+    state.delete_confirm = config.delete_confirm;
+    state.use_trash = config.use_trash;
+    state.confirm_rename_overwrite = config.confirm_rename_overwrite;
+    state.create_parents = config.create_parents;
+    state.hidden_scope = config.hidden_scope;
+    state.content_search_max_results = config.content_search_max_results;
+    state.copy_relative_path = config.copy_relative_path;
+    state.recent_files = RecentStore::load();
+    state.bookmarks = BookmarkStore::load();
+    state.ui_state = UiState::load();
+    if let Some(split) = state.ui_state.layout.preview_split {
+        state.preview_split = split.clamp(PREVIEW_SPLIT_MIN, PREVIEW_SPLIT_MAX);
+    }
+    if let Some(borders) = state.ui_state.layout.preview_borders {
+        state.preview_borders = borders;
+    }
+    configure_image_command(resolve_image_command(&config.preview_custom.image_command));
+    if !configure_text_preview(
+        &config.syntax_theme,
+        config.tab_width,
+        config.max_line_width,
+    ) {
+        state.set_message(format!(
+            "Unknown syntax theme '{}', using default (available: {})",
+            config.syntax_theme,
+            available_syntax_themes().join(", ")
+        ));
+    }
     if let Some(icons) = config.icons_enabled {
         state.icons_enabled = icons;
     } else {
@@ -104,15 +442,46 @@ pub fn run_app(
         state.stdin_mode = true;
         TreeNavigator::from_paths(&config.root, paths, state.show_hidden)?
     } else {
-        TreeNavigator::new(&config.root, state.show_hidden)?
+        let mut navigator = TreeNavigator::new(&config.root, state.show_hidden)?;
+        for root in &config.extra_roots {
+            match navigator.add_root(root) {
+                Ok(()) => {
+                    let git = GitStatus::detect(root);
+                    state.workspace_git.insert(root.clone(), git);
+                }
+                Err(e) => state.set_warning(format!("Skipped root: {}", e)),
+            }
+        }
+        navigator.expand_to_depth(config.startup_expand_depth, STARTUP_EXPAND_ENTRY_LIMIT)?;
+        navigator
     };
+    state.dirs_first = config.dirs_first;
+    if !state.dirs_first {
+        navigator.set_dirs_first(false)?;
+    }
+
+    // Land on the file named on the command line
+    if let Some(ref file) = config.focus_path {
+        if !reveal_and_focus(&mut navigator, &mut state, file)? {
+            state.set_message(format!("Not shown (hidden file?): {}", file.display()));
+        }
+        if config.open_preview {
+            state.preview_visible = true;
+        }
+        // Line numbers start at 1; 0 opens at the top like a missing line
+        if let Some(line) = config.focus_line.and_then(|line| line.checked_sub(1)) {
+            state.preview_anchor = Some((file.clone(), line));
+        }
+    }
+
     let mut click_detector = ClickDetector::new();
     let mut path_buffer = PathBuffer::new();
 
     // Create action context from config
-    let action_context = ActionContext {
+    let mut action_context = ActionContext {
         callback: config.callback.clone(),
         output_format: config.output_format,
+        output_options: config.output_options,
         commands: config.commands.clone(),
     };
 
@@ -122,6 +491,7 @@ pub fn run_app(
     // Fuzzy finder state
     let mut fuzzy_paths: Vec<PathBuf> = Vec::new();
     let mut fuzzy_results: Vec<FuzzyMatch> = Vec::new();
+    let mut fuzzy_preview = FuzzyPreview::new();
 
     // Lazy initialization: defer Git detection until after the first frame
     // to improve perceived startup time (first frame renders faster)
@@ -129,33 +499,43 @@ pub fn run_app(
 
     // Initialize file watcher (disabled in stdin mode)
     let mut file_watcher = if !state.stdin_mode {
-        match FileWatcher::new(&config.root) {
-            Ok(watcher) => {
-                state.watch_enabled = true;
-                Some(watcher)
-            }
-            Err(_) => {
-                // Watcher initialization failed, continue without watching
-                None
-            }
-        }
+        start_watcher(&config.root, config.watch_recursive, &mut state)
     } else {
         None
     };
+    // Root the watcher was started for (followed on reroot)
+    let mut watch_root = navigator.root().path.clone();
+
+    // Custom key bindings (only when a keymap file exists)
+    let mut key_registry = KeymapFile::keymap_path()
+        .filter(|path| path.exists())
+        .map(|_| KeyBindingRegistry::from_file());
+
+    // Watch config, keymap, and theme files for live reload
+    let mut config_reloader = ConfigReloader::new();
 
     // Git status polling timer (configurable, default 5 seconds)
     let mut last_git_poll = Instant::now();
-    let git_poll_interval = config.git_poll_interval;
+
+    // Free space of the root's filesystem, refreshed on its own timer
+    let mut disk_job = Some(DiskUsageJob::start(&state.root));
+    let mut last_disk_poll = Instant::now();
+
+    // Poll backoff and frame cap timers
+    let mut last_input = Instant::now();
+    let mut last_frame: Option<Instant> = None;
 
     // Track previous expanded paths for watcher sync
     let mut prev_expanded: Vec<PathBuf> = Vec::new();
+    // Lost watcher events are reported once per session
+    let mut watch_loss_reported = false;
 
     // Initialize plugin manager
     let mut plugin_manager = PluginManager::new().ok();
     if let Some(ref mut pm) = plugin_manager {
         // Load plugins from ~/.config/fileview/plugins/init.lua
         if let Err(e) = pm.load_plugins() {
-            state.set_message(format!("Plugin error: {}", e));
+            state.set_error(format!("Plugin error: {}", e));
         } else {
             // Update context with initial state
             let selected: Vec<PathBuf> = state.selected_paths.iter().cloned().collect();
@@ -171,6 +551,24 @@ pub fn run_app(
         }
     }
 
+    // Local HTTP/JSON API (--serve)
+    #[cfg(feature = "serve")]
+    let server = config.serve_port.and_then(|port| {
+        match StateServer::start(port, config.serve_token.clone()) {
+            Ok(server) => {
+                state.set_message(format!("Serving tree state on http://{}", server.addr()));
+                Some(server)
+            }
+            Err(e) => {
+                state.set_error(format!("Failed: serve - {:#}", e));
+                None
+            }
+        }
+    });
+    // Digest of the last published snapshot
+    #[cfg(feature = "serve")]
+    let mut published: Option<u64> = None;
+
     // Track previous state for plugin events
     let mut prev_focused_path: Option<PathBuf> = None;
     let mut prev_root = config.root.clone();
@@ -185,19 +583,19 @@ pub fn run_app(
         } else if state.git_status.is_none() {
             state.init_git_status();
         }
+
+        // Apply commands received by the HTTP API
+        #[cfg(feature = "serve")]
+        if let Some(server) = &server {
+            for command in server.take_commands() {
+                if let Err(e) = apply_serve_command(&command, &mut navigator, &mut state) {
+                    state.set_error(format!("Failed: serve command - {}", e));
+                }
+            }
+        }
+
         // Get visible entries and apply filter if set
-        let all_entries = navigator.visible_entries();
-        let entries: Vec<_> = if let Some(ref pattern) = state.filter_pattern {
-            all_entries
-                .into_iter()
-                .filter(|e| {
-                    // Always show directories for navigation
-                    e.is_dir || crate::handler::action::matches_filter(&e.name, pattern)
-                })
-                .collect()
-        } else {
-            all_entries
-        };
+        let entries = displayed_entries(&navigator, &state);
         let total_entries = entries.len();
         let snapshots: Vec<EntrySnapshot> = entries
             .iter()
@@ -214,9 +612,41 @@ pub fn run_app(
             state.focus_index = total_entries - 1;
         }
 
+        // Publish what is displayed to the HTTP API when it changed
+        #[cfg(feature = "serve")]
+        if let Some(server) = &server {
+            let digest = TreeSnapshot::digest(
+                &state.root,
+                &entries,
+                state.focus_index,
+                &state.selected_paths,
+                state.git_status.as_ref(),
+            );
+            if published != Some(digest) {
+                published = Some(digest);
+                server.publish(&TreeSnapshot::build(
+                    &state.root,
+                    &entries,
+                    state.focus_index,
+                    &state.selected_paths,
+                    state.git_status.as_ref(),
+                ));
+            }
+        }
+
         // Get focused entry path
         let focused_path = snapshots.get(state.focus_index).map(|e| e.path.clone());
 
+        // Operations act on the workspace root holding the focused entry
+        if let Some(root) = focused_path
+            .as_deref()
+            .and_then(|path| navigator.root_containing(path))
+        {
+            if root != state.root {
+                state.set_active_root(root.to_path_buf());
+            }
+        }
+
         // Update preview if needed (side panel or fullscreen mode)
         let needs_preview = state.preview_visible || matches!(state.mode, ViewMode::Preview { .. });
         if needs_preview {
@@ -225,37 +655,69 @@ pub fn run_app(
                 image_picker,
                 &mut state,
                 &config.preview_custom.custom,
+                plugin_manager.as_mut(),
             );
         }
 
+        // Preview the highlighted fuzzy finder entry once the highlight settles
+        let fuzzy_selected = match &state.mode {
+            ViewMode::FuzzyFinder { selected, .. } => fuzzy_results
+                .get((*selected).min(fuzzy_results.len().saturating_sub(1)))
+                .map(|m| m.path.clone()),
+            _ => None,
+        };
+        let now = Instant::now();
+        fuzzy_preview.select(fuzzy_selected.as_deref(), now);
+        fuzzy_preview.load_due(now, image_picker, &mut state, &config.preview_custom.custom);
+
         // Adjust viewport before rendering
         // Get terminal size to calculate visible height
         let term_size = terminal.size()?;
-        let tree_height = if state.preview_visible {
-            term_size.width / 2
-        } else {
-            term_size.width
-        };
+        let (tree_area, preview_area) = split_main_area(
+            &state,
+            ratatui::layout::Rect::new(0, 0, term_size.width, term_size.height),
+        );
+        // Center the line named on the command line once its preview is loaded
+        if let Some(area) = preview_area {
+            // Status bar (3 lines) and the preview borders
+            preview.apply_anchor(&mut state, area.height.saturating_sub(5) as usize);
+        }
         // Account for status bar (3 lines)
         let vis_height = visible_height(ratatui::layout::Rect {
-            x: 0,
-            y: 0,
-            width: tree_height,
-            height: term_size.height.saturating_sub(3),
+            height: tree_area.height.saturating_sub(3),
+            ..tree_area
         });
         state.adjust_viewport(vis_height);
+        refresh_selection_summary(&mut state);
 
-        // Render
-        let render_context = RenderContext {
-            state: &state,
-            entries,
-            focused_path: focused_path.as_ref(),
-            preview: &mut preview,
-            fuzzy_results: &fuzzy_results,
-            image_picker,
-            tab_manager: Some(&tab_manager),
-        };
-        terminal.draw(|frame| render_frame(frame, render_context))?;
+        if state.take_bell() {
+            ring_bell(terminal);
+        }
+
+        // Render (at most once per frame interval; a skipped frame is drawn later)
+        let frame_due = last_frame.is_none_or(|t| config.cadence.frame_wait(t.elapsed()).is_zero());
+        if frame_due {
+            let render_context = RenderContext {
+                state: &state,
+                entries,
+                focused_path: focused_path.as_ref(),
+                preview: &mut preview,
+                fuzzy_results: &fuzzy_results,
+                fuzzy_preview: &mut fuzzy_preview.preview,
+                fuzzy_max_results: config.fuzzy_max_results,
+                image_picker,
+                tab_manager: Some(&tab_manager),
+            };
+            terminal.draw(|frame| render_frame(frame, render_context))?;
+            last_frame = Some(Instant::now());
+        }
+
+        // Watch the new root after SetRoot / RootUp or a tab switch
+        if !state.stdin_mode && navigator.root().path != watch_root {
+            watch_root = navigator.root().path.clone();
+            file_watcher = start_watcher(&watch_root, config.watch_recursive, &mut state);
+            prev_expanded.clear();
+        }
 
         // Sync watcher with expanded directories (only when changed)
         if let Some(ref mut watcher) = file_watcher {
@@ -268,20 +730,103 @@ pub fn run_app(
 
         // Check file watcher events (auto-refresh on file changes)
         if let Some(ref watcher) = file_watcher {
-            if watcher.poll() {
-                reload_tree(&mut navigator, &mut state)?;
-                last_git_poll = Instant::now(); // Reset git poll timer
+            match watcher.poll() {
+                WatchPoll::Idle => {}
+                WatchPoll::Changed(paths) => {
+                    reload_changed(&mut navigator, &mut state, &paths)?;
+                    last_git_poll = Instant::now(); // Reset git poll timer
+                }
+                WatchPoll::Lost => {
+                    // Events were dropped: reload everything to catch up
+                    reload_tree(&mut navigator, &mut state)?;
+                    state.refresh_git_status();
+                    last_git_poll = Instant::now();
+                    if !watch_loss_reported {
+                        watch_loss_reported = true;
+                        state.set_warning("File watcher lost events; reloaded the tree");
+                    }
+                }
+            }
+        }
+
+        // Live reload of config files
+        if let Some(ref mut reloader) = config_reloader {
+            let changes = reloader.poll();
+            if changes.any() {
+                apply_config_changes(
+                    changes,
+                    reloader,
+                    &mut config,
+                    &mut state,
+                    &mut navigator,
+                    &mut action_context,
+                    &mut key_registry,
+                );
             }
         }
 
         // Git status polling (configurable interval)
-        if last_git_poll.elapsed() >= git_poll_interval {
+        if last_git_poll.elapsed() >= config.git_poll_interval {
             state.refresh_git_status();
             last_git_poll = Instant::now();
         }
 
+        // Disk space polling (also when the root changed); the last figures
+        // stay on screen until the background df finishes, picked up at the
+        // normal tick so a hung df does not keep the loop busy
+        if let Some(usage) = disk_job.as_ref().and_then(DiskUsageJob::poll) {
+            state.disk_usage = usage;
+            disk_job = None;
+        }
+        let root_moved = match &disk_job {
+            Some(job) => job.path != state.root,
+            None => state
+                .disk_usage
+                .as_ref()
+                .is_some_and(|usage| usage.path != state.root),
+        };
+        if root_moved || (disk_job.is_none() && last_disk_poll.elapsed() >= DISK_USAGE_INTERVAL) {
+            disk_job = Some(DiskUsageJob::start(&state.root));
+            last_disk_poll = Instant::now();
+        }
+
         // Poll for completed async image loads
         preview.poll_image_result(image_picker, &mut state);
+        // Continue a text preview search and an anchor waiting for the index
+        if let Some(ref mut tp) = preview.text {
+            tp.poll_anchor();
+            poll_preview_search(&mut state, tp);
+        }
+        // Collect directory size totals counted in the background
+        preview.poll_dir_size();
+        fuzzy_preview.preview.poll_dir_size();
+        fuzzy_preview
+            .preview
+            .poll_image_result(image_picker, &mut state);
+
+        // Collect the delete confirmation's totals counted in the background
+        state.poll_delete_summary();
+
+        // Collect content search results and report progress
+        let search_status = state
+            .content_search
+            .as_mut()
+            .and_then(|search| search.poll().then(|| search.status()));
+        if let Some(status) = search_status {
+            state.set_message(status);
+        }
+
+        // Collect per-file results of a command run over marked files
+        let foreach_status = state
+            .foreach_run
+            .as_mut()
+            .and_then(|run| run.poll().then(|| run.status()));
+        if let Some(status) = foreach_status {
+            state.set_message(status);
+        }
+
+        // Info and warning messages expire on their own
+        state.expire_message(Instant::now());
 
         // Check drop buffer timeout (for file drop detection via rapid key input)
         if path_buffer.is_ready() {
@@ -306,8 +851,43 @@ pub fn run_app(
             }
         }
 
-        // Handle events (60ms timeout balances responsiveness and CPU usage)
-        if event::poll(Duration::from_millis(60))? {
+        // Handle events: poll often while input arrives, back off when idle,
+        // and wake up in time for a frame skipped by the frame cap
+        let searching = state
+            .content_search
+            .as_ref()
+            .is_some_and(|search| search.is_running())
+            || state
+                .foreach_run
+                .as_ref()
+                .is_some_and(|run| run.is_running())
+            || state.delete_summary_job.is_some()
+            || preview.is_counting()
+            || fuzzy_preview.preview.is_counting();
+        let timeout = match last_frame {
+            Some(t) if !frame_due => config.cadence.frame_wait(t.elapsed()),
+            // Keep the results popup or directory totals live while work is running
+            _ if searching => config.cadence.tick,
+            // Wake up to load the settled fuzzy finder preview
+            _ if fuzzy_preview.is_pending() => config
+                .cadence
+                .poll_timeout(last_input.elapsed())
+                .min(FUZZY_PREVIEW_DELAY),
+            // Wake up to load the preview once focus has settled
+            _ if preview.is_settling() => config
+                .cadence
+                .poll_timeout(last_input.elapsed())
+                .min(state.preview_debounce),
+            _ => config.cadence.poll_timeout(last_input.elapsed()),
+        };
+        // Wake up in time to clear an expiring status message or end a flash
+        let now = Instant::now();
+        let timeout = [state.message_remaining(now), state.flash_remaining(now)]
+            .into_iter()
+            .flatten()
+            .fold(timeout, Duration::min);
+        if event::poll(timeout)? {
+            last_input = Instant::now();
             match event::read()? {
                 Event::Key(key) => {
                     // Handle input buffer updates first
@@ -328,18 +908,54 @@ pub fn run_app(
                         }
                     }
 
+                    // The case toggle is a chord; keep it out of the search and fuzzy queries
+                    let case_chord = matches!(
+                        state.mode,
+                        ViewMode::Search { .. } | ViewMode::FuzzyFinder { .. }
+                    ) && matches!(
+                        match key_registry {
+                            Some(ref registry) => {
+                                handle_key_event_with_registry(&state, key, registry)
+                            }
+                            None => handle_key_event(&state, key),
+                        },
+                        KeyAction::ToggleCaseSensitive
+                    );
+
                     if let ViewMode::Search { query } = &state.mode {
-                        if let Some((new_buf, _)) = update_input_buffer(key, query, query.len()) {
+                        if let Some((new_buf, _)) =
+                            update_input_buffer(key, query, query.len()).filter(|_| !case_chord)
+                        {
                             state.mode = ViewMode::Search { query: new_buf };
+                            update_search_matches(&mut state, &snapshots);
                             continue;
                         }
                     }
 
+                    // Handle the fullscreen preview's search prompt
+                    if matches!(state.mode, ViewMode::Preview { .. }) {
+                        if let Some(query) = &state.preview_search {
+                            if let Some((new_buf, _)) = update_input_buffer(key, query, query.len())
+                            {
+                                state.preview_search = Some(new_buf);
+                                continue;
+                            }
+                        }
+                    }
+
                     // Handle fuzzy finder text input
                     if let ViewMode::FuzzyFinder { query, .. } = &state.mode {
-                        if let Some((new_buf, _)) = update_input_buffer(key, query, query.len()) {
+                        if let Some((new_buf, _)) =
+                            update_input_buffer(key, query, query.len()).filter(|_| !case_chord)
+                        {
                             // Refresh results when query changes
-                            fuzzy_results = fuzzy_match(&new_buf, &fuzzy_paths, &state.root);
+                            fuzzy_results = fuzzy_match_with_case(
+                                &new_buf,
+                                &fuzzy_paths,
+                                &state.root,
+                                state.case_sensitive,
+                                config.fuzzy_max_results,
+                            );
                             state.mode = ViewMode::FuzzyFinder {
                                 query: new_buf,
                                 selected: 0, // Reset selection on query change
@@ -348,6 +964,17 @@ pub fn run_app(
                         }
                     }
 
+                    // Handle recent files query input
+                    if let ViewMode::RecentFiles { query, .. } = &state.mode {
+                        if let Some((new_buf, _)) = update_input_buffer(key, query, query.len()) {
+                            state.mode = ViewMode::RecentFiles {
+                                query: new_buf,
+                                selected: 0,
+                            };
+                            continue;
+                        }
+                    }
+
                     // Handle filter text input
                     if let ViewMode::Filter { query } = &state.mode {
                         if let Some((new_buf, _)) = update_input_buffer(key, query, query.len()) {
@@ -356,6 +983,19 @@ pub fn run_app(
                         }
                     }
 
+                    // Handle type-to-narrow input: re-narrow and focus the best match
+                    if let ViewMode::LiveFilter { query, origin } = &state.mode {
+                        if let Some((new_buf, _)) = update_input_buffer(key, query, query.len()) {
+                            state.mode = ViewMode::LiveFilter {
+                                query: new_buf,
+                                origin: origin.clone(),
+                            };
+                            let entries = displayed_entries(&navigator, &state);
+                            focus_best_live_match(&entries, &mut state);
+                            continue;
+                        }
+                    }
+
                     // Handle bulk rename text input
                     if matches!(state.mode, ViewMode::BulkRename { .. })
                         && update_bulk_rename_buffer(key, &mut state)
@@ -381,7 +1021,15 @@ pub fn run_app(
                         }
                     }
 
-                    let mut action = handle_key_event(&state, key);
+                    let mut action = match key_registry {
+                        Some(ref registry) => handle_key_event_with_registry(&state, key, registry),
+                        None => handle_key_event(&state, key),
+                    };
+
+                    // An error stays up until the next action dismisses it
+                    if !matches!(action, KeyAction::None) {
+                        state.clear_message();
+                    }
 
                     // Handle tab operations
                     match &action {
@@ -398,16 +1046,13 @@ pub fn run_app(
                                 })
                                 .unwrap_or_else(|| state.root.clone());
 
+                            // Keep the current tab's marks when opening another
+                            tab_manager.active_mut().save(&state, &navigator);
                             match tab_manager.new_tab(current_dir, state.show_hidden) {
                                 Ok(()) => {
                                     // Sync state from new tab
                                     let tab = tab_manager.active();
-                                    navigator = tab.navigator.clone();
-                                    state.root = tab.root.clone();
-                                    state.focus_index = 0;
-                                    state.viewport_top = 0;
-                                    state.selected_paths.clear();
-                                    state.mode = ViewMode::Browse;
+                                    navigator = tab.load(&mut state);
                                     state.set_message(format!(
                                         "Tab {}: {}",
                                         tab_manager.len(),
@@ -415,86 +1060,50 @@ pub fn run_app(
                                     ));
                                 }
                                 Err(e) => {
-                                    state.set_message(format!("Failed to create tab: {}", e));
+                                    state.set_error(format!("Failed to create tab: {}", e));
                                 }
                             }
                             continue;
                         }
                         KeyAction::CloseTab => {
                             if tab_manager.len() > 1 {
-                                // Save current tab state before closing
-                                tab_manager.active_mut().navigator = navigator.clone();
-                                tab_manager.active_mut().focus_index = state.focus_index;
-                                tab_manager.active_mut().viewport_top = state.viewport_top;
-                                tab_manager.active_mut().selected_paths =
-                                    state.selected_paths.clone();
-                                tab_manager.active_mut().mode = state.mode.clone();
-
                                 if tab_manager.close_tab() {
                                     // Restore state from new active tab
-                                    let tab = tab_manager.active();
-                                    navigator = tab.navigator.clone();
-                                    state.root = tab.root.clone();
-                                    state.focus_index = tab.focus_index;
-                                    state.viewport_top = tab.viewport_top;
-                                    state.selected_paths = tab.selected_paths.clone();
-                                    state.mode = tab.mode.clone();
+                                    navigator = tab_manager.active().load(&mut state);
                                     state.set_message(format!(
                                         "Closed tab, {} remaining",
                                         tab_manager.len()
                                     ));
                                 }
                             } else {
-                                state.set_message("Cannot close last tab");
+                                state.set_warning("Cannot close last tab");
                             }
                             continue;
                         }
                         KeyAction::NextTab => {
                             if tab_manager.len() > 1 {
-                                // Save current tab state
-                                tab_manager.active_mut().navigator = navigator.clone();
-                                tab_manager.active_mut().focus_index = state.focus_index;
-                                tab_manager.active_mut().viewport_top = state.viewport_top;
-                                tab_manager.active_mut().selected_paths =
-                                    state.selected_paths.clone();
-                                tab_manager.active_mut().mode = state.mode.clone();
-
+                                tab_manager.active_mut().save(&state, &navigator);
                                 tab_manager.next_tab();
-
-                                // Restore state from new active tab
-                                let tab = tab_manager.active();
-                                navigator = tab.navigator.clone();
-                                state.root = tab.root.clone();
-                                state.focus_index = tab.focus_index;
-                                state.viewport_top = tab.viewport_top;
-                                state.selected_paths = tab.selected_paths.clone();
-                                state.mode = tab.mode.clone();
+                                navigator = tab_manager.active().load(&mut state);
                             }
                             continue;
                         }
                         KeyAction::PrevTab => {
                             if tab_manager.len() > 1 {
-                                // Save current tab state
-                                tab_manager.active_mut().navigator = navigator.clone();
-                                tab_manager.active_mut().focus_index = state.focus_index;
-                                tab_manager.active_mut().viewport_top = state.viewport_top;
-                                tab_manager.active_mut().selected_paths =
-                                    state.selected_paths.clone();
-                                tab_manager.active_mut().mode = state.mode.clone();
-
+                                tab_manager.active_mut().save(&state, &navigator);
                                 tab_manager.prev_tab();
-
-                                // Restore state from new active tab
-                                let tab = tab_manager.active();
-                                navigator = tab.navigator.clone();
-                                state.root = tab.root.clone();
-                                state.focus_index = tab.focus_index;
-                                state.viewport_top = tab.viewport_top;
-                                state.selected_paths = tab.selected_paths.clone();
-                                state.mode = tab.mode.clone();
+                                navigator = tab_manager.active().load(&mut state);
                             }
                             continue;
                         }
+                        KeyAction::EditorRename if !state.stdin_mode => {
+                            run_editor_rename(terminal, &mut state, &mut navigator, &snapshots)?;
+                            continue;
+                        }
+                        KeyAction::ReloadKeymap => {
+                            reload_key_registry(&mut key_registry, &mut state);
+                            continue;
+                        }
                         _ => {}
                     }
 
@@ -504,9 +1113,25 @@ pub fn run_app(
                         fuzzy_paths = if state.stdin_mode {
                             navigator.collect_all_paths()
                         } else {
-                            collect_paths(&state.root, state.show_hidden)
+                            navigator
+                                .roots()
+                                .flat_map(|root| {
+                                    collect_paths_with(
+                                        &root.path,
+                                        state.show_hidden,
+                                        &config.fuzzy_exclude,
+                                        config.fuzzy_gitignore,
+                                    )
+                                })
+                                .collect()
                         };
-                        fuzzy_results = fuzzy_match("", &fuzzy_paths, &state.root);
+                        fuzzy_results = fuzzy_match_with_case(
+                            "",
+                            &fuzzy_paths,
+                            &state.root,
+                            state.case_sensitive,
+                            config.fuzzy_max_results,
+                        );
                     }
 
                     // Fill in actual path for FuzzyConfirm
@@ -522,6 +1147,8 @@ pub fn run_app(
                         }
                     }
 
+                    let toggles_case = matches!(action, KeyAction::ToggleCaseSensitive);
+
                     match handle_action(
                         action,
                         &mut state,
@@ -531,6 +1158,7 @@ pub fn run_app(
                         &action_context,
                         &mut preview.text,
                         &mut preview.hex,
+                        &mut preview.csv,
                         &mut preview.archive,
                         &mut preview.pdf,
                         &mut preview.diff,
@@ -550,6 +1178,30 @@ pub fn run_app(
                         }
                     }
 
+                    // Keep the active tab in sync with a reroot
+                    if tab_manager.active().root != state.root {
+                        tab_manager.active_mut().set_root(state.root.clone());
+                    }
+
+                    // Refresh other tabs that show directories touched by a paste
+                    if !state.changed_dirs.is_empty() {
+                        let dirs = std::mem::take(&mut state.changed_dirs);
+                        let _ = tab_manager.refresh_inactive_showing(&dirs);
+                    }
+
+                    // Re-run the fuzzy query with the new case sensitivity
+                    if toggles_case {
+                        if let ViewMode::FuzzyFinder { query, .. } = &state.mode {
+                            fuzzy_results = fuzzy_match_with_case(
+                                query,
+                                &fuzzy_paths,
+                                &state.root,
+                                state.case_sensitive,
+                                config.fuzzy_max_results,
+                            );
+                        }
+                    }
+
                     // Clamp fuzzy finder selected index to valid range
                     if let ViewMode::FuzzyFinder { selected, .. } = &mut state.mode {
                         if fuzzy_results.is_empty() {
@@ -561,15 +1213,8 @@ pub fn run_app(
 
                     // Handle fuzzy finder jump target
                     if let Some(target) = state.fuzzy_jump_target.take() {
-                        // Expand parent directories to make the target visible
-                        if let Err(e) = navigator.reveal_path(&target) {
-                            state.set_message(format!("Failed: reveal path - {}", e));
-                        } else {
-                            // Find the target in visible entries and set focus
-                            let entries = navigator.visible_entries();
-                            if let Some(idx) = entries.iter().position(|e| e.path == target) {
-                                state.focus_index = idx;
-                            }
+                        if let Err(e) = reveal_and_focus(&mut navigator, &mut state, &target) {
+                            state.set_error(format!("Failed: reveal path - {}", e));
                         }
                     }
                 }
@@ -577,53 +1222,56 @@ pub fn run_app(
                     let tree_top = 0; // Assuming tree starts at row 0
                     let action = handle_mouse_event(mouse, &mut click_detector, tree_top);
 
-                    // Calculate preview boundary for focus switching
-                    let preview_boundary = if state.preview_visible {
-                        crossterm::terminal::size()
-                            .map(|(w, _)| w / 2)
-                            .unwrap_or(u16::MAX)
-                    } else {
-                        u16::MAX // No preview, all clicks go to tree
+                    // Tree and preview areas for focus switching
+                    let (tree_area, preview_area) = crossterm::terminal::size()
+                        .map(|(w, h)| {
+                            split_main_area(&state, ratatui::layout::Rect::new(0, 0, w, h))
+                        })
+                        .unwrap_or_default();
+                    let in_preview = |col: u16, row: u16| {
+                        preview_area.is_some_and(|area| {
+                            area.contains(ratatui::layout::Position::new(col, row))
+                        })
                     };
+                    // Click rows count from below the top border; make them tree-relative
+                    let tree_row = |row: u16| row.saturating_sub(tree_area.y) as usize;
 
                     match action {
                         MouseAction::Click { row, col } => {
                             // Set focus based on click position
                             if state.preview_visible {
-                                if col >= preview_boundary {
+                                if in_preview(col, row + tree_top + 1) {
                                     state.set_focus(FocusTarget::Preview);
                                 } else {
                                     state.set_focus(FocusTarget::Tree);
                                     // Only update file selection when clicking on tree
-                                    let idx = state.viewport_top + row as usize;
-                                    if idx < snapshots.len() {
+                                    let idx = state.viewport_top + tree_row(row);
+                                    if let Some(entry) = snapshots.get(idx) {
                                         state.focus_index = idx;
+                                        apply_click(entry, false, &mut navigator, &mut state);
                                     }
                                 }
                             } else {
                                 let idx = state.viewport_top + row as usize;
-                                if idx < snapshots.len() {
+                                if let Some(entry) = snapshots.get(idx) {
                                     state.focus_index = idx;
+                                    apply_click(entry, false, &mut navigator, &mut state);
                                 }
                             }
                         }
                         MouseAction::DoubleClick { row, col } => {
                             // Double-click on tree area
-                            if col < preview_boundary {
+                            if !in_preview(col, row + tree_top + 1) {
                                 state.set_focus(FocusTarget::Tree);
-                                let idx = state.viewport_top + row as usize;
-                                if idx < snapshots.len() {
+                                let idx = state.viewport_top + tree_row(row);
+                                if let Some(entry) = snapshots.get(idx) {
                                     state.focus_index = idx;
-                                    if let Some(entry) = snapshots.get(idx) {
-                                        if entry.is_dir {
-                                            let _ = navigator.toggle_expand(&entry.path);
-                                        }
-                                    }
+                                    apply_click(entry, true, &mut navigator, &mut state);
                                 }
                             }
                         }
-                        MouseAction::ScrollUp { amount, col } => {
-                            if state.preview_visible && col >= preview_boundary {
+                        MouseAction::ScrollUp { amount, col, row } => {
+                            if state.preview_visible && in_preview(col, row) {
                                 // Scroll preview (text, hex, or archive)
                                 if let Some(ref mut tp) = preview.text {
                                     tp.scroll = tp.scroll.saturating_sub(amount);
@@ -631,6 +1279,9 @@ pub fn run_app(
                                 if let Some(ref mut hp) = preview.hex {
                                     hp.scroll = hp.scroll.saturating_sub(amount);
                                 }
+                                if let Some(ref mut cp) = preview.csv {
+                                    cp.scroll = cp.scroll.saturating_sub(amount);
+                                }
                                 if let Some(ref mut ap) = preview.archive {
                                     ap.scroll = ap.scroll.saturating_sub(amount);
                                 }
@@ -639,8 +1290,8 @@ pub fn run_app(
                                 state.focus_index = state.focus_index.saturating_sub(amount);
                             }
                         }
-                        MouseAction::ScrollDown { amount, col } => {
-                            if state.preview_visible && col >= preview_boundary {
+                        MouseAction::ScrollDown { amount, col, row } => {
+                            if state.preview_visible && in_preview(col, row) {
                                 // Scroll preview (text, hex, or archive)
                                 if let Some(ref mut tp) = preview.text {
                                     tp.scroll += amount;
@@ -648,6 +1299,10 @@ pub fn run_app(
                                 if let Some(ref mut hp) = preview.hex {
                                     hp.scroll += amount;
                                 }
+                                if let Some(ref mut cp) = preview.csv {
+                                    cp.scroll =
+                                        (cp.scroll + amount).min(cp.line_count().saturating_sub(1));
+                                }
                                 if let Some(ref mut ap) = preview.archive {
                                     ap.scroll += amount;
                                 }
@@ -734,12 +1389,13 @@ pub fn run_app(
                             match TreeNavigator::new(&path, state.show_hidden) {
                                 Ok(new_nav) => {
                                     navigator = new_nav;
+                                    state.workspace_git.clear();
                                     state.root = path;
                                     state.focus_index = 0;
                                     state.viewport_top = 0;
                                 }
                                 Err(e) => {
-                                    state.set_message(format!("Navigate failed: {}", e));
+                                    state.set_error(format!("Navigate failed: {}", e));
                                 }
                             }
                         }
@@ -762,15 +1418,8 @@ pub fn run_app(
                         }
                     }
                     PluginAction::Focus(path) => {
-                        // Expand parent directories to make the target visible
-                        if let Err(e) = navigator.reveal_path(&path) {
-                            state.set_message(format!("Focus failed: {}", e));
-                        } else {
-                            // Find the target in visible entries and set focus
-                            let entries = navigator.visible_entries();
-                            if let Some(idx) = entries.iter().position(|e| e.path == path) {
-                                state.focus_index = idx;
-                            }
+                        if let Err(e) = reveal_and_focus(&mut navigator, &mut state, &path) {
+                            state.set_error(format!("Focus failed: {}", e));
                         }
                     }
                 }
@@ -0,0 +1,210 @@
+//! Content type detection with `file --mime-type`
+//!
+//! Used for files whose extension says nothing about their content (opt-in
+//! via `preview.use_file_command`). Results are cached per path and mtime, so
+//! `file` runs once per version of a file. When `file` is missing or fails,
+//! the content is treated as binary.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::SystemTime;
+
+use crate::core::find_executable;
+
+/// Cached `file` path detection
+static FILE_COMMAND_PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// Find the `file` executable path (lazy detection with caching)
+pub fn find_file_command() -> Option<&'static PathBuf> {
+    FILE_COMMAND_PATH
+        .get_or_init(|| find_executable("file"))
+        .as_ref()
+}
+
+/// How a file's content should be previewed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    Text,
+    Image,
+    Binary,
+}
+
+impl FileKind {
+    /// Classify a MIME type reported by `file`
+    pub fn from_mime(mime: &str) -> Self {
+        let mime = mime.trim();
+        if mime.starts_with("text/") || mime == "inode/x-empty" {
+            return Self::Text;
+        }
+        if mime.starts_with("image/") {
+            return Self::Image;
+        }
+        match mime {
+            "application/json"
+            | "application/javascript"
+            | "application/xml"
+            | "application/x-sh"
+            | "application/x-shellscript"
+            | "application/x-ndjson"
+            | "application/toml"
+            | "application/x-yaml" => Self::Text,
+            _ => Self::Binary,
+        }
+    }
+}
+
+/// Cached `file --mime-type` results keyed by path and mtime
+#[derive(Debug, Clone)]
+pub struct FileTypeCache {
+    /// `file` executable (None = not installed)
+    command: Option<PathBuf>,
+    entries: HashMap<PathBuf, (Option<SystemTime>, Option<String>)>,
+}
+
+impl Default for FileTypeCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FileTypeCache {
+    /// Create a cache using the installed `file` command
+    pub fn new() -> Self {
+        Self::with_command(find_file_command().cloned())
+    }
+
+    /// Create a cache using a specific `file` executable
+    pub fn with_command(command: Option<PathBuf>) -> Self {
+        Self {
+            command,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// MIME type of a file (None if `file` is missing or failed)
+    pub fn mime_type(&mut self, path: &Path) -> Option<&str> {
+        let mtime = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        let stale = self
+            .entries
+            .get(path)
+            .is_none_or(|(cached, _)| mtime.is_none() || *cached != mtime);
+        if stale {
+            let mime = self.run(path);
+            self.entries.insert(path.to_path_buf(), (mtime, mime));
+        }
+        self.entries.get(path).and_then(|(_, mime)| mime.as_deref())
+    }
+
+    /// Classify a file's content, treating it as binary if `file` is unavailable
+    pub fn classify(&mut self, path: &Path) -> FileKind {
+        self.mime_type(path)
+            .map_or(FileKind::Binary, FileKind::from_mime)
+    }
+
+    /// Number of cached paths
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if nothing is cached
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn run(&self, path: &Path) -> Option<String> {
+        let command = self.command.as_ref()?;
+        let output = Command::new(command)
+            .args(["--brief", "--mime-type", "--"])
+            .arg(path)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let mime = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!mime.is_empty()).then_some(mime)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_file_kind_from_mime() {
+        assert_eq!(FileKind::from_mime("text/x-shellscript"), FileKind::Text);
+        assert_eq!(FileKind::from_mime("application/json\n"), FileKind::Text);
+        assert_eq!(FileKind::from_mime("inode/x-empty"), FileKind::Text);
+        assert_eq!(FileKind::from_mime("image/png"), FileKind::Image);
+        assert_eq!(
+            FileKind::from_mime("application/x-executable"),
+            FileKind::Binary
+        );
+        assert_eq!(
+            FileKind::from_mime("application/octet-stream"),
+            FileKind::Binary
+        );
+    }
+
+    #[test]
+    fn test_missing_file_command_treated_as_binary() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("README");
+        fs::write(&path, "plain text").unwrap();
+
+        let mut cache = FileTypeCache::with_command(None);
+        assert_eq!(cache.mime_type(&path), None);
+        assert_eq!(cache.classify(&path), FileKind::Binary);
+
+        let mut cache = FileTypeCache::with_command(Some(temp.path().join("no-such-file")));
+        assert_eq!(cache.classify(&path), FileKind::Binary);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cache_keyed_by_path_and_mtime() {
+        use std::os::unix::fs::PermissionsExt;
+        use std::time::Duration;
+
+        let temp = TempDir::new().unwrap();
+        // Stand-in for `file` that counts its invocations
+        let calls = temp.path().join("calls");
+        let script = temp.path().join("fake-file");
+        fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\necho x >> '{}'\necho text/plain\n",
+                calls.display()
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let call_count = || fs::read_to_string(&calls).map_or(0, |s| s.lines().count());
+
+        let a = temp.path().join("a");
+        let b = temp.path().join("b");
+        fs::write(&a, "one").unwrap();
+        fs::write(&b, "two").unwrap();
+
+        let mut cache = FileTypeCache::with_command(Some(script));
+        assert_eq!(cache.mime_type(&a), Some("text/plain"));
+        assert_eq!(cache.classify(&a), FileKind::Text);
+        assert_eq!(call_count(), 1);
+
+        assert_eq!(cache.classify(&b), FileKind::Text);
+        assert_eq!(call_count(), 2);
+        assert_eq!(cache.len(), 2);
+
+        // A newer mtime invalidates the cached result
+        let file = fs::File::options().write(true).open(&a).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+        assert_eq!(cache.classify(&a), FileKind::Text);
+        assert_eq!(call_count(), 3);
+        assert_eq!(cache.len(), 2);
+    }
+}
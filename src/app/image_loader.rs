@@ -1,7 +1,9 @@
 //! Background image loader using std::thread and mpsc channels
 //!
 //! This module provides asynchronous image loading to prevent UI blocking
-//! when loading large images.
+//! when loading large images. Thumbnail requests decode (or fetch from the
+//! [`ThumbnailCache`]) a downscaled copy on the worker as well, and SVGs are
+//! rasterized there by the external rasterizer.
 
 use std::path::PathBuf;
 use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
@@ -9,16 +11,29 @@ use std::thread::{self, JoinHandle};
 
 use image::DynamicImage;
 
+use super::thumbnail_cache::ThumbnailCache;
+use crate::render::rasterize_svg;
+
 /// Request to load an image
 pub struct ImageLoadRequest {
     /// Path to the image file
     pub path: PathBuf,
+    /// Cache to load a thumbnail through (None = the full image)
+    pub thumbnails: Option<ThumbnailCache>,
+    /// Pixel box to rasterize an SVG into (None = decode a bitmap)
+    pub svg_size: Option<(u32, u32)>,
 }
 
 /// Result of an image load operation
 pub struct ImageLoadResult {
     /// Path that was loaded
     pub path: PathBuf,
+    /// Whether a thumbnail was requested
+    pub thumbnail: bool,
+    /// Whether an SVG was rasterized
+    pub svg: bool,
+    /// Size of the source image when `result` is a downscaled thumbnail
+    pub source_size: Option<(u32, u32)>,
     /// The loaded image or error message
     pub result: Result<DynamicImage, String>,
 }
@@ -36,6 +51,8 @@ pub struct ImageLoader {
     _worker: JoinHandle<()>,
     /// Path currently being loaded (for deduplication)
     loading_path: Option<PathBuf>,
+    /// Whether the path is loading as a thumbnail
+    loading_thumbnail: bool,
 }
 
 impl ImageLoader {
@@ -53,19 +70,36 @@ impl ImageLoader {
             result_rx,
             _worker: worker,
             loading_path: None,
+            loading_thumbnail: false,
         }
     }
 
     /// Worker thread main loop
     fn worker_loop(request_rx: Receiver<ImageLoadRequest>, result_tx: Sender<ImageLoadResult>) {
         while let Ok(request) = request_rx.recv() {
-            let result = match image::open(&request.path) {
-                Ok(img) => Ok(img),
-                Err(e) => Err(format!("Failed to load image: {}", e)),
+            let thumbnail = request.thumbnails.is_some();
+            let svg = request.svg_size.is_some();
+            let loaded = match (request.svg_size, request.thumbnails) {
+                (Some((width, height)), _) => {
+                    rasterize_svg(&request.path, width, height).map(|img| (img, None))
+                }
+                (None, Some(cache)) => cache.load(&request.path),
+                (None, None) => image::open(&request.path)
+                    .map(|img| (img, None))
+                    .map_err(Into::into),
+            };
+            let (result, source_size) = match loaded {
+                Ok((img, source_size)) => (Ok(img), source_size),
+                // Keep the rasterizer's own message for the source fallback
+                Err(e) if svg => (Err(e.to_string()), None),
+                Err(e) => (Err(format!("Failed to load image: {}", e)), None),
             };
 
             let load_result = ImageLoadResult {
                 path: request.path,
+                thumbnail,
+                svg,
+                source_size,
                 result,
             };
 
@@ -81,15 +115,45 @@ impl ImageLoader {
     /// If an image is already being loaded for this path, the request is ignored.
     /// Returns true if the request was sent, false if already loading this path.
     pub fn request(&mut self, path: PathBuf) -> bool {
+        self.send(path, None, None)
+    }
+
+    /// Request a thumbnail of the image at `path`, cached in `thumbnails`
+    ///
+    /// Deduplicated like [`ImageLoader::request`]; a thumbnail and the full
+    /// image of the same path are different requests.
+    pub fn request_thumbnail(&mut self, path: PathBuf, thumbnails: ThumbnailCache) -> bool {
+        self.send(path, Some(thumbnails), None)
+    }
+
+    /// Request an SVG rasterized to fit within `width` x `height` pixels
+    ///
+    /// Deduplicated like [`ImageLoader::request`].
+    pub fn request_svg(&mut self, path: PathBuf, width: u32, height: u32) -> bool {
+        self.send(path, None, Some((width, height)))
+    }
+
+    fn send(
+        &mut self,
+        path: PathBuf,
+        thumbnails: Option<ThumbnailCache>,
+        svg_size: Option<(u32, u32)>,
+    ) -> bool {
+        let thumbnail = thumbnails.is_some();
         // Skip if already loading this path
-        if self.loading_path.as_ref() == Some(&path) {
+        if self.loading_path.as_ref() == Some(&path) && self.loading_thumbnail == thumbnail {
             return false;
         }
 
         self.loading_path = Some(path.clone());
+        self.loading_thumbnail = thumbnail;
 
         // Send the request (ignore errors - worker might have stopped)
-        let _ = self.request_tx.send(ImageLoadRequest { path });
+        let _ = self.request_tx.send(ImageLoadRequest {
+            path,
+            thumbnails,
+            svg_size,
+        });
         true
     }
 
@@ -100,7 +164,9 @@ impl ImageLoader {
         match self.result_rx.try_recv() {
             Ok(result) => {
                 // Clear loading state if this was the expected path
-                if self.loading_path.as_ref() == Some(&result.path) {
+                if self.loading_path.as_ref() == Some(&result.path)
+                    && self.loading_thumbnail == result.thumbnail
+                {
                     self.loading_path = None;
                 }
                 Some(result)
@@ -166,6 +232,18 @@ mod tests {
         assert!(!loader.request(path)); // Should return false for duplicate
     }
 
+    #[test]
+    fn test_full_image_after_thumbnail_is_new_request() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut loader = ImageLoader::new();
+        let path = temp.path().join("test.png");
+        let cache = ThumbnailCache::new(temp.path().join("thumbs"), 0);
+
+        assert!(loader.request_thumbnail(path.clone(), cache.clone()));
+        assert!(!loader.request_thumbnail(path.clone(), cache));
+        assert!(loader.request(path));
+    }
+
     #[test]
     fn test_cancel_clears_loading_state() {
         let mut loader = ImageLoader::new();
@@ -3,19 +3,28 @@
 //! This module contains the main application logic, configuration,
 //! and event loop for FileView.
 
+mod cadence;
 mod config;
 mod config_file;
 mod event_loop;
+mod file_type;
 mod image_loader;
 mod preview;
+mod reload;
 mod render;
+mod thumbnail_cache;
 mod video;
 
-pub use config::{Config, InitAction, PluginAction, SessionAction};
+pub use cadence::Cadence;
+pub use config::{
+    parse_path_list, split_location, Config, InitAction, PluginAction, SessionAction, StateAction,
+};
 pub use config_file::{CommandsConfig, ConfigFile, HooksConfig, PreviewConfig};
 pub use event_loop::{run_app, AppResult};
+pub use file_type::{find_file_command, FileKind, FileTypeCache};
 pub use image_loader::ImageLoader;
-pub use preview::PreviewState;
+pub use preview::{FuzzyPreview, PreviewState, FUZZY_PREVIEW_DELAY};
+pub use thumbnail_cache::ThumbnailCache;
 pub use video::{
     extract_thumbnail, find_ffmpeg, find_ffprobe, get_metadata, is_video_file, VideoMetadata,
 };
@@ -1,19 +1,111 @@
 //! Preview state management
 
 use std::collections::HashMap;
-use std::path::PathBuf;
-
-use image::GenericImageView;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 
+use crate::app::file_type::{FileKind, FileTypeCache};
 use crate::app::video::{extract_thumbnail, find_ffprobe, get_metadata, is_video_file};
-use crate::app::ImageLoader;
-use crate::core::AppState;
+use crate::app::{ImageLoader, ThumbnailCache};
+use crate::core::{diff_files, AppState, ViewMode};
 use crate::git::{self, FileStatus};
+use crate::plugin::{PluginManager, PluginPreview};
 use crate::render::{
-    find_pdftoppm, is_archive_file, is_binary_file, is_image_file, is_pdf_file, is_tar_gz_file,
-    is_text_file, ArchivePreview, CustomPreview, DiffPreview, DirectoryInfo, HexPreview,
-    ImagePreview, PdfPreview, Picker, TextPreview, VideoPreview,
+    find_pdftoppm, find_pdftotext, find_preview_command, find_svg_rasterizer, is_archive_file,
+    is_binary_file, is_csv_file, is_image_file, is_pdf_file, is_svg_file, is_tar_gz_file,
+    is_text_file, svg_raster_size, ArchivePreview, CsvPreview, CustomPreview, DiffPreview,
+    DirSizeJob, DirectoryInfo, HexPreview, ImagePreview, PdfView, Picker, TextPreview,
+    VideoPreview, LARGE_TEXT_THRESHOLD,
 };
+use crate::tree::{cached_member, member_cache_dir, split_archive_path, MemberExtraction};
+
+/// Maximum number of remembered preview scroll positions
+const SCROLL_MEMORY_CAPACITY: usize = 64;
+
+/// How long the fuzzy finder highlight rests on an entry before it is previewed
+pub const FUZZY_PREVIEW_DELAY: Duration = Duration::from_millis(100);
+
+/// Remembered preview scroll positions (most recently left first)
+///
+/// Each position is stored with the file's mtime so an edited file opens
+/// at the top again instead of at a stale offset.
+#[derive(Debug, Clone)]
+pub struct ScrollMemory {
+    entries: Vec<(PathBuf, Option<SystemTime>, usize)>,
+    capacity: usize,
+}
+
+impl Default for ScrollMemory {
+    fn default() -> Self {
+        Self::new(SCROLL_MEMORY_CAPACITY)
+    }
+}
+
+impl ScrollMemory {
+    /// Create an empty memory holding at most `capacity` paths
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            capacity,
+        }
+    }
+
+    /// Remember the scroll offset of a path (an offset of 0 forgets it)
+    pub fn store(&mut self, path: &Path, scroll: usize) {
+        self.store_with_mtime(path, modified(path), scroll);
+    }
+
+    fn store_with_mtime(&mut self, path: &Path, mtime: Option<SystemTime>, scroll: usize) {
+        self.entries.retain(|(p, _, _)| p != path);
+        if scroll == 0 {
+            return;
+        }
+        self.entries.insert(0, (path.to_path_buf(), mtime, scroll));
+        self.entries.truncate(self.capacity);
+    }
+
+    /// Remembered scroll offset of a path, if the file is unchanged since
+    pub fn restore(&mut self, path: &Path) -> Option<usize> {
+        self.restore_with_mtime(path, modified(path))
+    }
+
+    fn restore_with_mtime(&mut self, path: &Path, mtime: Option<SystemTime>) -> Option<usize> {
+        let index = self.entries.iter().position(|(p, _, _)| p == path)?;
+        let (_, stored, scroll) = &self.entries[index];
+        if mtime.is_some() && *stored == mtime {
+            Some(*scroll)
+        } else {
+            self.entries.remove(index);
+            None
+        }
+    }
+
+    /// Number of remembered paths
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if no positions are remembered
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Whether opening the file (or listing the directory) is refused
+///
+/// Only regular files are opened: opening a FIFO without a writer blocks.
+fn is_permission_denied(path: &Path) -> bool {
+    let opened = match std::fs::metadata(path) {
+        Ok(meta) if meta.is_dir() => std::fs::read_dir(path).map(drop),
+        Ok(meta) if meta.is_file() => std::fs::File::open(path).map(drop),
+        _ => return false,
+    };
+    matches!(opened, Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied)
+}
 
 /// Preview state container
 #[derive(Default)]
@@ -21,9 +113,14 @@ pub struct PreviewState {
     pub text: Option<TextPreview>,
     pub image: Option<ImagePreview>,
     pub dir_info: Option<DirectoryInfo>,
+    /// Recursive size count for `dir_info` (dropped, and so cancelled, with it)
+    dir_size: Option<DirSizeJob>,
+    /// Archive member being extracted for the preview of `last_path`
+    extraction: Option<MemberExtraction>,
     pub hex: Option<HexPreview>,
+    pub csv: Option<CsvPreview>,
     pub archive: Option<ArchivePreview>,
-    pub pdf: Option<PdfPreview>,
+    pub pdf: Option<PdfView>,
     pub diff: Option<DiffPreview>,
     pub custom: Option<CustomPreview>,
     pub video: Option<VideoPreview>,
@@ -32,8 +129,23 @@ pub struct PreviewState {
     image_loader: ImageLoader,
     /// Path currently being loaded asynchronously
     pub loading_image_path: Option<PathBuf>,
+    /// Whether `loading_image_path` is loading as a thumbnail
+    loading_image_thumbnail: bool,
     /// Video path currently loading thumbnail
     pub loading_video_thumbnail: Option<PathBuf>,
+    /// Scroll positions restored when returning to a file
+    pub scroll_memory: ScrollMemory,
+    /// Revision diff shown for `last_path` (see `AppState::diff_base`)
+    loaded_diff_base: Option<(PathBuf, String)>,
+    loaded_compare: Option<(PathBuf, PathBuf)>,
+    /// `AppState::svg_source_mode` the preview was loaded with
+    loaded_svg_source: bool,
+    /// `AppState::csv_raw_mode` the preview was loaded with
+    loaded_csv_raw: bool,
+    /// `file --mime-type` results (created on first use)
+    file_types: Option<FileTypeCache>,
+    /// Newly focused entry waiting for `AppState::preview_debounce`
+    settling: Option<(PathBuf, Instant)>,
 }
 
 impl PreviewState {
@@ -46,9 +158,12 @@ impl PreviewState {
         self.text = None;
         self.image = None;
         self.dir_info = None;
+        self.dir_size = None;
+        self.extraction = None;
         self.hex = None;
         self.archive = None;
         self.pdf = None;
+        self.csv = None;
         self.diff = None;
         self.custom = None;
         self.video = None;
@@ -61,33 +176,266 @@ impl PreviewState {
         image_picker: &mut Option<Picker>,
         state: &mut AppState,
     ) {
-        self.update_with_custom(path, image_picker, state, &HashMap::new());
+        self.update_with_custom(path, image_picker, state, &HashMap::new(), None);
     }
 
     /// Update preview with custom preview support
     ///
-    /// `custom_previews` maps file extensions to command templates.
-    /// The command template can use `$f` as a placeholder for the file path.
+    /// Plugin preview providers take precedence, then the colored
+    /// `[preview.commands]` from `state`, then `custom_previews`, which
+    /// maps file extensions to command templates. The command template can use
+    /// `$f` as a placeholder for the file path.
     pub fn update_with_custom(
         &mut self,
         path: Option<&PathBuf>,
         image_picker: &mut Option<Picker>,
         state: &mut AppState,
         custom_previews: &HashMap<String, String>,
+        plugins: Option<&mut PluginManager>,
     ) {
-        // Only reload preview if the path changed
-        if path == self.last_path.as_ref() {
+        // Show an archive member once its extraction finished
+        if let Some(done) = self.extraction.as_ref().and_then(MemberExtraction::poll) {
+            let job_path = self.extraction.take().map(|job| job.path);
+            if job_path.as_ref() == path {
+                match done {
+                    Ok(Some(extracted)) => {
+                        self.load(&extracted, image_picker, state, custom_previews, plugins);
+                    }
+                    Ok(None) => {}
+                    Err(e) => state.set_error(format!("Failed: extract - {}", e)),
+                }
+                return;
+            }
+        }
+
+        // A revision diff only applies while its file stays focused
+        if state.diff_base.as_ref().map(|(p, _)| p) != path {
+            state.diff_base = None;
+        }
+        if state.compare_files.as_ref().map(|(_, shown)| shown) != path {
+            state.compare_files = None;
+            state.compare_diff = None;
+        }
+
+        // Only reload preview if the path, a diff or the SVG view changed,
+        // or fullscreen needs the full image behind a thumbnail
+        if path == self.last_path.as_ref()
+            && state.diff_base == self.loaded_diff_base
+            && state.compare_files == self.loaded_compare
+            && state.svg_source_mode == self.loaded_svg_source
+            && state.csv_raw_mode == self.loaded_csv_raw
+            && !self.needs_full_image(state)
+        {
             return;
         }
+        // Hold off loading a new entry until focus rests on it
+        if path != self.last_path.as_ref() {
+            let settled = path.is_none_or(|path| {
+                settle(
+                    &mut self.settling,
+                    path,
+                    Instant::now(),
+                    state.preview_debounce,
+                )
+            });
+            if !settled {
+                if let Some(previous) = self.last_path.take() {
+                    if let Some(scroll) = self.scroll() {
+                        self.scroll_memory.store(&previous, scroll);
+                    }
+                }
+                self.clear_all();
+                self.dir_size = None;
+                return;
+            }
+            self.settling = None;
+        }
+        self.loaded_diff_base = state.diff_base.clone();
+        self.loaded_compare = state.compare_files.clone();
+        self.loaded_svg_source = state.svg_source_mode;
+        self.loaded_csv_raw = state.csv_raw_mode;
 
+        if let Some(previous) = self.last_path.take() {
+            if let Some(scroll) = self.scroll() {
+                self.scroll_memory.store(&previous, scroll);
+            }
+        }
         self.last_path = path.cloned();
+        // Stop counting the directory that lost focus
+        self.dir_size = None;
 
         let Some(path) = path else {
             self.clear_all();
             return;
         };
 
-        // Check for custom preview first (if not a directory)
+        self.load(path, image_picker, state, custom_previews, plugins);
+
+        if let Some(scroll) = self.scroll_memory.restore(path) {
+            self.set_scroll(scroll);
+        }
+    }
+
+    /// Scroll offset of the displayed scrollable preview
+    ///
+    /// Checked in the same order the previews are rendered.
+    fn scroll(&self) -> Option<usize> {
+        if let Some(ref dp) = self.diff {
+            Some(dp.scroll)
+        } else if let Some(ref cp) = self.custom {
+            Some(cp.scroll)
+        } else if let Some(ref tp) = self.text {
+            Some(tp.scroll)
+        } else if let Some(ref cp) = self.csv {
+            Some(cp.scroll)
+        } else if let Some(ref hp) = self.hex {
+            Some(hp.scroll)
+        } else {
+            self.archive.as_ref().map(|ap| ap.scroll)
+        }
+    }
+
+    /// Set the scroll offset of the displayed preview, clamped to its length
+    fn set_scroll(&mut self, scroll: usize) {
+        if let Some(ref mut dp) = self.diff {
+            dp.scroll = scroll.min(dp.line_count().saturating_sub(1));
+        } else if let Some(ref mut cp) = self.custom {
+            cp.scroll = scroll.min(cp.line_count().saturating_sub(1));
+        } else if let Some(ref mut tp) = self.text {
+            tp.scroll = scroll.min(tp.line_count().saturating_sub(1));
+        } else if let Some(ref mut cp) = self.csv {
+            cp.scroll = scroll.min(cp.line_count().saturating_sub(1));
+        } else if let Some(ref mut hp) = self.hex {
+            hp.scroll = scroll.min(hp.line_count().saturating_sub(1));
+        } else if let Some(ref mut ap) = self.archive {
+            ap.scroll = scroll.min(ap.line_count().saturating_sub(1));
+        }
+    }
+
+    /// Show the diff of two marked files if `path` is the one shown
+    fn load_compare_diff(&mut self, path: &Path, state: &mut AppState) -> bool {
+        let Some((base, shown)) = &state.compare_files else {
+            return false;
+        };
+        if shown != path {
+            return false;
+        }
+        let diff = match state.compare_diff {
+            Some(ref diff) => Ok(diff.clone()),
+            None => diff_files(base, shown),
+        };
+        match diff {
+            Ok(file_diff) if !file_diff.is_empty() => {
+                self.clear_all();
+                self.diff = Some(DiffPreview::new(file_diff));
+                true
+            }
+            Ok(_) => false,
+            Err(e) => {
+                state.set_error(format!("Failed: compare - {}", e));
+                false
+            }
+        }
+    }
+
+    /// Show the diff against `state.diff_base` if it was requested for `path`
+    fn load_revision_diff(&mut self, path: &Path, state: &mut AppState) -> bool {
+        let (Some((base_path, rev)), Some(git)) = (&state.diff_base, &state.git_status) else {
+            return false;
+        };
+        if base_path != path {
+            return false;
+        }
+        match git::try_get_diff(git.repo_root(), path, false, Some(rev)) {
+            Ok(Some(file_diff)) => {
+                self.clear_all();
+                self.diff = Some(DiffPreview::new(file_diff));
+                true
+            }
+            Ok(None) => false,
+            Err(e) => {
+                state.set_error(format!("Failed: {}", e));
+                false
+            }
+        }
+    }
+
+    /// Load the preview for a path, replacing the current one
+    fn load(
+        &mut self,
+        path: &Path,
+        image_picker: &mut Option<Picker>,
+        state: &mut AppState,
+        custom_previews: &HashMap<String, String>,
+        plugins: Option<&mut PluginManager>,
+    ) {
+        // Archive members are previewed from a copy extracted in the background
+        if let Some((archive, member)) = split_archive_path(path) {
+            self.clear_all();
+            let Some(cache) = member_cache_dir() else {
+                state.set_error("Failed: extract - no cache directory");
+                return;
+            };
+            match cached_member(&archive, &member, &cache) {
+                Some(extracted) => {
+                    self.load(&extracted, image_picker, state, custom_previews, plugins)
+                }
+                None => {
+                    self.extraction = Some(MemberExtraction::start(path, archive, member, cache))
+                }
+            }
+            return;
+        }
+
+        // Unreadable entries get a clear message instead of a failed loader
+        if is_permission_denied(path) {
+            self.clear_all();
+            state.set_error(format!("Permission denied: {}", path.display()));
+            return;
+        }
+
+        // Plugin preview providers come first; errors fall back to the built-in preview
+        if !path.is_dir() {
+            if let Some(pm) = plugins {
+                let preview = pm
+                    .preview_for(path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|p| {
+                        p.map(|p| match p {
+                            PluginPreview::Text(text) => {
+                                Ok(CustomPreview::from_text(&text, "plugin"))
+                            }
+                            PluginPreview::Command(cmd) => CustomPreview::execute(&cmd, path),
+                        })
+                        .transpose()
+                    });
+                match preview {
+                    Ok(Some(preview)) => {
+                        self.clear_all();
+                        self.custom = Some(preview);
+                        return;
+                    }
+                    Ok(None) => {}
+                    Err(e) => state.set_error(format!("Plugin preview failed: {}", e)),
+                }
+            }
+        }
+
+        // Colored preview commands; a failing command falls back below
+        if !path.is_dir() {
+            if let Some(cmd) = find_preview_command(&state.preview_commands, path) {
+                match CustomPreview::execute_styled(cmd, path) {
+                    Ok(preview) => {
+                        self.clear_all();
+                        self.custom = Some(preview);
+                        return;
+                    }
+                    Err(e) => state.set_error(format!("Preview command failed: {}", e)),
+                }
+            }
+        }
+
+        // Check for custom preview (if not a directory)
         if !path.is_dir() {
             if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
                 if let Some(cmd) = custom_previews.get(ext) {
@@ -100,11 +448,12 @@ impl PreviewState {
                             self.hex = None;
                             self.archive = None;
                             self.pdf = None;
+                            self.csv = None;
                             self.diff = None;
                             return;
                         }
                         Err(e) => {
-                            state.set_message(format!("Custom preview failed: {}", e));
+                            state.set_error(format!("Custom preview failed: {}", e));
                             // Fall through to default preview
                         }
                     }
@@ -112,19 +461,30 @@ impl PreviewState {
             }
         }
 
+        if self.load_revision_diff(path, state) || self.load_compare_diff(path, state) {
+            return;
+        }
+
+        if is_svg_file(path) && !state.svg_source_mode {
+            self.load_svg(path, image_picker, state);
+            return;
+        }
+
         if path.is_dir() {
             // Load directory info
             if let Ok(info) = DirectoryInfo::from_path(path) {
                 self.dir_info = Some(info);
+                self.dir_size = Some(DirSizeJob::start(path));
                 self.text = None;
                 self.image = None;
                 self.hex = None;
                 self.archive = None;
                 self.pdf = None;
+                self.csv = None;
                 self.diff = None;
                 self.custom = None;
             }
-        } else if is_text_file(path) {
+        } else if is_csv_file(path) || is_text_file(path) {
             // Check if file has git changes - if so, show diff instead
             let git_status = state
                 .git_status
@@ -142,8 +502,8 @@ impl PreviewState {
                 if let Some(ref git) = state.git_status {
                     let repo_root = git.repo_root();
                     // Try staged diff first, then unstaged
-                    let diff = git::get_diff(repo_root, path, true)
-                        .or_else(|| git::get_diff(repo_root, path, false));
+                    let diff = git::get_diff(repo_root, path, true, None)
+                        .or_else(|| git::get_diff(repo_root, path, false, None));
 
                     if let Some(file_diff) = diff {
                         if !file_diff.is_empty() {
@@ -154,6 +514,7 @@ impl PreviewState {
                             self.hex = None;
                             self.archive = None;
                             self.pdf = None;
+                            self.csv = None;
                             self.custom = None;
                             return;
                         }
@@ -161,38 +522,14 @@ impl PreviewState {
                 }
             }
 
-            // Fall back to regular text preview
-            match std::fs::read_to_string(path) {
-                Ok(content) => {
-                    self.text = Some(TextPreview::with_highlighting(&content, path));
-                    self.image = None;
-                    self.dir_info = None;
-                    self.hex = None;
-                    self.archive = None;
-                    self.pdf = None;
-                    self.diff = None;
-                    self.custom = None;
-                }
-                Err(e) => {
-                    state.set_message(format!("Failed: preview - {}", e));
-                    self.clear_all();
-                }
+            // Fall back to the table or the regular text preview
+            if is_csv_file(path) && !state.csv_raw_mode {
+                self.load_csv(path, state);
+            } else {
+                self.load_text(path, state);
             }
         } else if is_image_file(path) {
-            // Start async image loading (non-blocking)
-            if self.image_loader.request(path.to_path_buf()) {
-                // Clear current preview while loading
-                self.image = None;
-                self.text = None;
-                self.dir_info = None;
-                self.hex = None;
-                self.archive = None;
-                self.pdf = None;
-                self.diff = None;
-                self.custom = None;
-                self.video = None;
-                self.loading_image_path = Some(path.to_path_buf());
-            }
+            self.request_image(path, state);
         } else if is_video_file(path) {
             // Video preview - requires ffprobe for metadata
             if find_ffprobe().is_some() {
@@ -221,18 +558,19 @@ impl PreviewState {
                         self.hex = None;
                         self.archive = None;
                         self.pdf = None;
+                        self.csv = None;
                         self.diff = None;
                         self.custom = None;
                     }
                     Err(e) => {
-                        state.set_message(format!("Failed: video preview - {}", e));
+                        state.set_error(format!("Failed: video preview - {}", e));
                         // Fall back to hex preview
                         self.load_hex_fallback(path, state);
                     }
                 }
             } else {
                 // ffprobe not installed - show message and fall back to hex preview
-                state.set_message("Video preview requires ffprobe (ffmpeg)");
+                state.set_warning("Video preview requires ffprobe (ffmpeg)");
                 self.load_hex_fallback(path, state);
             }
         } else if is_tar_gz_file(path) {
@@ -245,11 +583,12 @@ impl PreviewState {
                     self.dir_info = None;
                     self.hex = None;
                     self.pdf = None;
+                    self.csv = None;
                     self.diff = None;
                     self.custom = None;
                 }
                 Err(e) => {
-                    state.set_message(format!("Failed: preview - {}", e));
+                    state.set_error(format!("Failed: preview - {}", e));
                     self.clear_all();
                 }
             }
@@ -262,44 +601,54 @@ impl PreviewState {
                     self.dir_info = None;
                     self.hex = None;
                     self.pdf = None;
+                    self.csv = None;
                     self.diff = None;
                     self.custom = None;
                 }
                 Err(e) => {
-                    state.set_message(format!("Failed: preview - {}", e));
+                    state.set_error(format!("Failed: preview - {}", e));
                     self.clear_all();
                 }
             }
         } else if is_pdf_file(path) {
-            // PDF preview - requires pdftoppm (poppler-utils)
-            if find_pdftoppm().is_some() {
-                if let Some(ref mut picker) = image_picker {
-                    match PdfPreview::load(path, 1, picker) {
-                        Ok(pdf) => {
-                            self.pdf = Some(pdf);
-                            self.text = None;
-                            self.image = None;
-                            self.dir_info = None;
-                            self.hex = None;
-                            self.archive = None;
-                            self.diff = None;
-                            self.custom = None;
-                        }
-                        Err(e) => {
-                            state.set_message(format!("Failed: preview - {}", e));
-                            // Fall back to hex preview
-                            self.load_hex_fallback(path, state);
-                        }
+            // PDF preview - requires poppler-utils (pdftoppm for images, pdftotext for text)
+            let can_render = find_pdftoppm().is_some() && image_picker.is_some();
+            if can_render || find_pdftotext().is_some() {
+                match PdfView::load(path, 1, image_picker.as_mut(), state.pdf_text_mode) {
+                    Ok(pdf) => {
+                        self.pdf = Some(pdf);
+                        self.csv = None;
+                        self.text = None;
+                        self.image = None;
+                        self.dir_info = None;
+                        self.hex = None;
+                        self.archive = None;
+                        self.diff = None;
+                        self.custom = None;
+                    }
+                    Err(e) => {
+                        state.set_error(format!("Failed: preview - {}", e));
+                        // Fall back to hex preview
+                        self.load_hex_fallback(path, state);
                     }
-                } else {
-                    // No image picker available - fall back to hex preview
-                    self.load_hex_fallback(path, state);
                 }
             } else {
-                // pdftoppm not installed - show message and fall back to hex preview
-                state.set_message("PDF preview requires pdftoppm (poppler-utils)");
+                // poppler-utils not installed - show message and fall back to hex preview
+                state.set_warning("PDF preview requires pdftoppm or pdftotext (poppler-utils)");
                 self.load_hex_fallback(path, state);
             }
+        } else if state.use_file_command && path.is_file() {
+            // Unknown type - let file(1) decide; binary if it is unavailable
+            let file_types = self.file_types.get_or_insert_with(FileTypeCache::new);
+            let kind = file_types.classify(path);
+            state.file_mime = file_types
+                .mime_type(path)
+                .map(|mime| (path.to_path_buf(), mime.to_string()));
+            match kind {
+                FileKind::Text => self.load_text(path, state),
+                FileKind::Image => self.request_image(path, state),
+                FileKind::Binary => self.load_hex_fallback(path, state),
+            }
         } else if is_binary_file(path) || path.is_file() {
             // Binary file or unknown type - show hex preview
             match HexPreview::load(path) {
@@ -310,11 +659,12 @@ impl PreviewState {
                     self.dir_info = None;
                     self.archive = None;
                     self.pdf = None;
+                    self.csv = None;
                     self.diff = None;
                     self.custom = None;
                 }
                 Err(e) => {
-                    state.set_message(format!("Failed: preview - {}", e));
+                    state.set_error(format!("Failed: preview - {}", e));
                     self.clear_all();
                 }
             }
@@ -323,7 +673,109 @@ impl PreviewState {
         }
     }
 
-    /// Load hex preview as fallback for PDF files
+    /// Load a syntax-highlighted text preview
+    ///
+    /// Files past [`LARGE_TEXT_THRESHOLD`] are read on demand instead.
+    fn load_text(&mut self, path: &Path, state: &mut AppState) {
+        let is_large = std::fs::metadata(path).is_ok_and(|m| m.len() > LARGE_TEXT_THRESHOLD);
+        let loaded = if is_large {
+            TextPreview::open_large(path)
+        } else {
+            std::fs::read_to_string(path)
+                .map(|content| TextPreview::with_highlighting(&content, path))
+        };
+        match loaded {
+            Ok(preview) => {
+                self.text = Some(preview);
+                self.image = None;
+                self.dir_info = None;
+                self.hex = None;
+                self.archive = None;
+                self.pdf = None;
+                self.csv = None;
+                self.diff = None;
+                self.custom = None;
+            }
+            Err(e) => {
+                state.set_error(format!("Failed: preview - {}", e));
+                self.clear_all();
+            }
+        }
+    }
+
+    /// Load a CSV/TSV table, parsing at most `state.csv_max_rows` rows
+    fn load_csv(&mut self, path: &Path, state: &mut AppState) {
+        match CsvPreview::load(path, state.csv_max_rows) {
+            Ok(csv) => {
+                self.clear_all();
+                self.csv = Some(csv);
+            }
+            Err(e) => {
+                state.set_error(format!("Failed: preview - {}", e));
+                self.clear_all();
+            }
+        }
+    }
+
+    /// Start rasterizing an SVG, or show its source when that is not possible
+    ///
+    /// Malformed files are shown as source with the rasterizer's error once
+    /// the background loader reports it.
+    fn load_svg(&mut self, path: &Path, image_picker: &mut Option<Picker>, state: &mut AppState) {
+        let Some(picker) = image_picker
+            .as_ref()
+            .filter(|_| find_svg_rasterizer().is_some())
+        else {
+            self.load_text(path, state);
+            return;
+        };
+        let (width, height) = svg_raster_size(picker);
+        if self
+            .image_loader
+            .request_svg(path.to_path_buf(), width, height)
+        {
+            self.clear_all();
+            self.loading_image_path = Some(path.to_path_buf());
+            self.loading_image_thumbnail = false;
+        }
+    }
+
+    /// Start async image loading (non-blocking)
+    ///
+    /// The side preview loads a cached thumbnail; fullscreen the full image.
+    fn request_image(&mut self, path: &Path, state: &AppState) {
+        let fullscreen = matches!(state.mode, ViewMode::Preview { .. });
+        let thumbnails = (!fullscreen && state.thumbnail_cache_bytes > 0)
+            .then(ThumbnailCache::default_dir)
+            .flatten()
+            .map(|dir| ThumbnailCache::new(dir, state.thumbnail_cache_bytes));
+        let thumbnail = thumbnails.is_some();
+        let requested = match thumbnails {
+            Some(cache) => self
+                .image_loader
+                .request_thumbnail(path.to_path_buf(), cache),
+            None => self.image_loader.request(path.to_path_buf()),
+        };
+        if requested {
+            // Clear current preview while loading, but keep showing the
+            // thumbnail of this image until the full one arrives
+            let shown = self.image.take().filter(|image| image.path == path);
+            self.clear_all();
+            self.image = shown;
+            self.loading_image_path = Some(path.to_path_buf());
+            self.loading_image_thumbnail = thumbnail;
+        }
+    }
+
+    /// Whether fullscreen shows a thumbnail that should be the full image
+    /// (and the full image has not been requested yet)
+    fn needs_full_image(&self, state: &AppState) -> bool {
+        matches!(state.mode, ViewMode::Preview { .. })
+            && self.loading_image_path.is_none()
+            && self.image.as_ref().is_some_and(|image| image.downscaled)
+    }
+
+    /// Load hex preview as fallback (PDF and video files, binary content)
     fn load_hex_fallback(&mut self, path: &std::path::Path, state: &mut AppState) {
         match HexPreview::load(path) {
             Ok(hex) => {
@@ -334,10 +786,11 @@ impl PreviewState {
                 self.diff = None;
                 self.archive = None;
                 self.pdf = None;
+                self.csv = None;
                 self.custom = None;
             }
             Err(e) => {
-                state.set_message(format!("Failed: preview - {}", e));
+                state.set_error(format!("Failed: preview - {}", e));
                 self.clear_all();
             }
         }
@@ -349,6 +802,7 @@ impl PreviewState {
             || self.image.is_some()
             || self.dir_info.is_some()
             || self.hex.is_some()
+            || self.csv.is_some()
             || self.archive.is_some()
             || self.pdf.is_some()
             || self.diff.is_some()
@@ -356,6 +810,39 @@ impl PreviewState {
             || self.video.is_some()
     }
 
+    /// Collect the running totals of the directory size count
+    ///
+    /// Returns true if the shown totals changed.
+    pub fn poll_dir_size(&mut self) -> bool {
+        let (Some(job), Some(info)) = (self.dir_size.as_mut(), self.dir_info.as_mut()) else {
+            return false;
+        };
+        if !job.poll() {
+            return false;
+        }
+        info.update_totals(job);
+        if job.complete {
+            self.dir_size = None;
+        }
+        true
+    }
+
+    /// Whether a directory size count, an archive extraction, an image
+    /// command or a text search is running
+    pub fn is_counting(&self) -> bool {
+        self.dir_size.as_ref().is_some_and(|job| job.is_running())
+            || self.extraction.is_some()
+            || self
+                .image
+                .iter()
+                .chain(self.video.as_ref().and_then(|v| v.thumbnail.as_ref()))
+                .any(ImagePreview::is_rendering)
+            || self
+                .text
+                .as_ref()
+                .is_some_and(|tp| tp.is_searching() || tp.is_anchoring())
+    }
+
     /// Poll for completed image load results
     ///
     /// This should be called in the main event loop to receive
@@ -369,24 +856,29 @@ impl PreviewState {
     ) -> bool {
         if let Some(result) = self.image_loader.try_recv() {
             // Check if this is for regular image preview
-            if self.loading_image_path.as_ref() == Some(&result.path) {
+            if self.loading_image_path.as_ref() == Some(&result.path)
+                && self.loading_image_thumbnail == result.thumbnail
+            {
                 self.loading_image_path = None;
 
                 match result.result {
                     Ok(dyn_img) => {
                         if let Some(ref mut picker) = image_picker {
-                            let (width, height) = dyn_img.dimensions();
-                            let protocol = picker.new_resize_protocol(dyn_img);
-                            self.image = Some(ImagePreview {
-                                width,
-                                height,
-                                protocol,
+                            self.image = Some(match result.source_size {
+                                Some(size) => {
+                                    ImagePreview::from_thumbnail(result.path, dyn_img, size, picker)
+                                }
+                                None => ImagePreview::from_image(result.path, dyn_img, picker),
                             });
                             return true;
                         }
                     }
+                    Err(e) if result.svg => {
+                        self.load_text(&result.path, state);
+                        state.set_warning(format!("SVG not rendered: {}", e));
+                    }
                     Err(e) => {
-                        state.set_message(format!("Failed: preview - {}", e));
+                        state.set_error(format!("Failed: preview - {}", e));
                     }
                 }
             }
@@ -398,13 +890,8 @@ impl PreviewState {
                     match result.result {
                         Ok(dyn_img) => {
                             if let Some(ref mut picker) = image_picker {
-                                let (width, height) = dyn_img.dimensions();
-                                let protocol = picker.new_resize_protocol(dyn_img);
-                                video.thumbnail = Some(ImagePreview {
-                                    width,
-                                    height,
-                                    protocol,
-                                });
+                                video.thumbnail =
+                                    Some(ImagePreview::from_image(result.path, dyn_img, picker));
                                 self.loading_video_thumbnail = None;
                                 return true;
                             }
@@ -424,4 +911,354 @@ impl PreviewState {
     pub fn is_loading_image(&self) -> bool {
         self.loading_image_path.is_some() || self.loading_video_thumbnail.is_some()
     }
+
+    /// Center the text preview on `state.preview_anchor` once it is loaded
+    ///
+    /// The anchor is used up by the first preview of its file; `height` is
+    /// the number of visible preview lines.
+    pub fn apply_anchor(&mut self, state: &mut AppState, height: usize) {
+        let Some((path, line)) = &state.preview_anchor else {
+            return;
+        };
+        if self.last_path.as_ref() != Some(path) {
+            return;
+        }
+        if let Some(ref mut tp) = self.text {
+            tp.anchor(*line, height);
+        }
+        state.preview_anchor = None;
+    }
+
+    /// Check if the focused entry waits for focus to settle before loading
+    pub fn is_settling(&self) -> bool {
+        self.settling.is_some()
+    }
+}
+
+/// Debounce decision for previewing `path` at `now`
+///
+/// Focus landing on a new entry starts the wait (replacing the previous
+/// entry's); returns true once it has rested on `path` for `delay`.
+fn settle(
+    settling: &mut Option<(PathBuf, Instant)>,
+    path: &Path,
+    now: Instant,
+    delay: Duration,
+) -> bool {
+    if delay.is_zero() {
+        return true;
+    }
+    match settling {
+        Some((pending, since)) if pending == path => now.saturating_duration_since(*since) >= delay,
+        _ => {
+            *settling = Some((path.to_path_buf(), now));
+            false
+        }
+    }
+}
+
+/// Preview of the entry highlighted in the fuzzy finder
+///
+/// Moving the highlight only requests a preview; it is loaded once the
+/// highlight has stayed on the entry for [`FUZZY_PREVIEW_DELAY`], so flicking
+/// through the results does not load every file on the way. The previous
+/// preview stays up until then.
+#[derive(Default)]
+pub struct FuzzyPreview {
+    pub preview: PreviewState,
+    /// Highlighted entry
+    selected: Option<PathBuf>,
+    /// Entry waiting to be loaded and when it was highlighted
+    pending: Option<(PathBuf, Instant)>,
+}
+
+impl FuzzyPreview {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Note the highlighted entry at `now` (None = finder closed or no results)
+    ///
+    /// Returns true if a preview was requested for a newly highlighted entry.
+    pub fn select(&mut self, path: Option<&Path>, now: Instant) -> bool {
+        if self.selected.as_deref() == path {
+            return false;
+        }
+        self.selected = path.map(Path::to_path_buf);
+        match path {
+            Some(path) => {
+                self.pending = Some((path.to_path_buf(), now));
+                true
+            }
+            None => {
+                self.pending = None;
+                self.preview.clear_all();
+                self.preview.last_path = None;
+                false
+            }
+        }
+    }
+
+    /// Take the requested entry once the highlight has rested long enough
+    pub fn due(&mut self, now: Instant) -> Option<PathBuf> {
+        let (_, since) = self.pending.as_ref()?;
+        if now.saturating_duration_since(*since) < FUZZY_PREVIEW_DELAY {
+            return None;
+        }
+        self.pending.take().map(|(path, _)| path)
+    }
+
+    /// Check if a requested preview is still waiting for its delay
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Load the requested preview if it is due at `now`
+    ///
+    /// Uses the built-in and `custom_previews` loaders; plugin providers are
+    /// left to the main preview.
+    pub fn load_due(
+        &mut self,
+        now: Instant,
+        image_picker: &mut Option<Picker>,
+        state: &mut AppState,
+        custom_previews: &HashMap<String, String>,
+    ) -> bool {
+        let Some(path) = self.due(now) else {
+            return false;
+        };
+        self.preview.clear_all();
+        self.preview
+            .load(&path, image_picker, state, custom_previews, None);
+        self.preview.last_path = Some(path);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_permission_probe_skips_fifos() {
+        let temp = TempDir::new().unwrap();
+        let fifo = temp.path().join("pipe");
+        let made = std::process::Command::new("mkfifo").arg(&fifo).status();
+        assert!(made.unwrap().success());
+        // Opening a FIFO without a writer would block here
+        assert!(!is_permission_denied(&fifo));
+        assert!(!is_permission_denied(temp.path()));
+    }
+
+    #[test]
+    fn test_fuzzy_preview_debounces_selection() {
+        let temp = TempDir::new().unwrap();
+        let a = temp.path().join("a.txt");
+        let b = temp.path().join("b.txt");
+        std::fs::write(&a, "alpha").unwrap();
+        std::fs::write(&b, "beta").unwrap();
+        let mut state = AppState::new(temp.path().to_path_buf());
+        let mut fuzzy = FuzzyPreview::new();
+        let start = Instant::now();
+
+        assert!(fuzzy.select(Some(&a), start));
+        // Highlighting the same entry again requests nothing
+        assert!(!fuzzy.select(Some(&a), start));
+        // Moving on before the delay replaces the request
+        let moved = start + FUZZY_PREVIEW_DELAY / 2;
+        assert!(fuzzy.select(Some(&b), moved));
+        assert!(!fuzzy.load_due(
+            start + FUZZY_PREVIEW_DELAY,
+            &mut None,
+            &mut state,
+            &HashMap::new()
+        ));
+        assert!(fuzzy.is_pending());
+
+        assert!(fuzzy.load_due(
+            moved + FUZZY_PREVIEW_DELAY,
+            &mut None,
+            &mut state,
+            &HashMap::new()
+        ));
+        assert!(!fuzzy.is_pending());
+        assert_eq!(fuzzy.preview.last_path.as_ref(), Some(&b));
+        assert!(fuzzy.preview.text.is_some());
+
+        // Closing the finder drops the preview
+        assert!(!fuzzy.select(None, moved));
+        assert!(!fuzzy.preview.has_content());
+    }
+
+    #[test]
+    fn test_settle_waits_for_focus_to_rest() {
+        let delay = Duration::from_millis(80);
+        let (a, b) = (Path::new("/a"), Path::new("/b"));
+        let start = Instant::now();
+        let mut settling = None;
+
+        // Rapid moves keep restarting the wait
+        assert!(!settle(&mut settling, a, start, delay));
+        assert!(!settle(&mut settling, b, start + delay / 2, delay));
+        assert!(!settle(&mut settling, a, start + delay, delay));
+        assert!(!settle(&mut settling, a, start + delay * 3 / 2, delay));
+        // Resting on the entry lets it load
+        assert!(settle(&mut settling, a, start + delay * 2, delay));
+
+        // No delay loads at once
+        assert!(settle(&mut None, b, start, Duration::ZERO));
+    }
+
+    #[test]
+    fn test_update_shows_settled_entry_only() {
+        let temp = TempDir::new().unwrap();
+        let a = temp.path().join("a.txt");
+        std::fs::write(&a, "alpha").unwrap();
+        let mut state = AppState::new(temp.path().to_path_buf());
+        state.preview_debounce = Duration::from_secs(60);
+        let mut preview = PreviewState::new();
+
+        preview.update(Some(&a), &mut None, &mut state);
+        assert!(preview.is_settling());
+        assert!(!preview.has_content());
+
+        // Focus has rested on the entry for the whole delay
+        let (_, since) = preview.settling.as_mut().unwrap();
+        *since -= state.preview_debounce;
+        preview.update(Some(&a), &mut None, &mut state);
+        assert!(!preview.is_settling());
+        assert!(preview.text.is_some());
+    }
+
+    fn update(preview: &mut PreviewState, path: &PathBuf, pm: &mut PluginManager) -> AppState {
+        let mut state = AppState::new(path.parent().unwrap().to_path_buf());
+        preview.update_with_custom(Some(path), &mut None, &mut state, &HashMap::new(), Some(pm));
+        state
+    }
+
+    #[test]
+    fn test_plugin_preview_takes_precedence() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("data.log");
+        std::fs::write(&path, "raw contents").unwrap();
+
+        let mut pm = PluginManager::new().unwrap();
+        pm.exec(r#"fv.register_preview("*.log", function(path) return "line 1\nline 2" end)"#)
+            .unwrap();
+
+        let mut preview = PreviewState::new();
+        update(&mut preview, &path, &mut pm);
+
+        let custom = preview.custom.as_ref().expect("plugin preview");
+        assert_eq!(custom.lines, vec!["line 1", "line 2"]);
+        assert!(preview.text.is_none());
+    }
+
+    #[test]
+    fn test_failing_plugin_preview_falls_back() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("notes.txt");
+        std::fs::write(&path, "plain text").unwrap();
+
+        let mut pm = PluginManager::new().unwrap();
+        pm.exec(r#"fv.register_preview("*.txt", function(path) error("boom") end)"#)
+            .unwrap();
+
+        let mut preview = PreviewState::new();
+        let state = update(&mut preview, &path, &mut pm);
+
+        assert!(preview.custom.is_none());
+        assert!(preview.text.is_some());
+        assert!(state
+            .message_text()
+            .unwrap()
+            .contains("Plugin preview failed"));
+    }
+
+    #[test]
+    fn test_scroll_memory_store_and_restore() {
+        let mtime = Some(SystemTime::UNIX_EPOCH);
+        let mut memory = ScrollMemory::new(2);
+        memory.store_with_mtime(Path::new("/a"), mtime, 10);
+        memory.store_with_mtime(Path::new("/b"), mtime, 20);
+        assert_eq!(memory.restore_with_mtime(Path::new("/a"), mtime), Some(10));
+
+        // Oldest entry is evicted once the capacity is exceeded
+        memory.store_with_mtime(Path::new("/c"), mtime, 30);
+        assert_eq!(memory.len(), 2);
+        assert_eq!(memory.restore_with_mtime(Path::new("/a"), mtime), None);
+
+        // Scrolling back to the top forgets the path
+        memory.store_with_mtime(Path::new("/b"), mtime, 0);
+        assert_eq!(memory.restore_with_mtime(Path::new("/b"), mtime), None);
+    }
+
+    #[test]
+    fn test_scroll_memory_invalidated_by_mtime() {
+        let before = Some(SystemTime::UNIX_EPOCH);
+        let after = Some(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(60));
+        let mut memory = ScrollMemory::new(8);
+        memory.store_with_mtime(Path::new("/a"), before, 10);
+
+        assert_eq!(memory.restore_with_mtime(Path::new("/a"), after), None);
+        assert!(memory.is_empty());
+    }
+
+    #[test]
+    fn test_preview_restores_scroll_on_return() {
+        let temp = TempDir::new().unwrap();
+        let first = temp.path().join("first.txt");
+        let second = temp.path().join("second.txt");
+        let content: String = (0..100).map(|i| format!("line {}\n", i)).collect();
+        std::fs::write(&first, &content).unwrap();
+        std::fs::write(&second, &content).unwrap();
+
+        let mut pm = PluginManager::new().unwrap();
+        let mut preview = PreviewState::new();
+        update(&mut preview, &first, &mut pm);
+        preview.text.as_mut().unwrap().scroll = 42;
+
+        update(&mut preview, &second, &mut pm);
+        assert_eq!(preview.text.as_ref().unwrap().scroll, 0);
+
+        update(&mut preview, &first, &mut pm);
+        assert_eq!(preview.text.as_ref().unwrap().scroll, 42);
+    }
+
+    #[test]
+    fn test_svg_without_picker_shows_source() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("logo.svg");
+        std::fs::write(
+            &path,
+            r#"<svg viewBox="0 0 10 10"><rect width="10" height="10"/></svg>"#,
+        )
+        .unwrap();
+
+        let mut pm = PluginManager::new().unwrap();
+        let mut preview = PreviewState::new();
+        update(&mut preview, &path, &mut pm);
+
+        assert!(preview.image.is_none());
+        assert!(preview.text.as_ref().unwrap().lines[0].contains("<rect"));
+    }
+
+    #[test]
+    fn test_csv_raw_mode_shows_text() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("data.csv");
+        std::fs::write(&path, "a,b\n1,2\n").unwrap();
+
+        let mut preview = PreviewState::new();
+        let mut state = AppState::new(temp.path().to_path_buf());
+        preview.update(Some(&path), &mut None, &mut state);
+        assert!(preview.csv.is_some());
+
+        state.csv_raw_mode = true;
+        preview.update(Some(&path), &mut None, &mut state);
+        assert!(preview.csv.is_none());
+        assert_eq!(preview.text.as_ref().unwrap().lines[0], "a,b");
+    }
 }
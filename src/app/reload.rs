@@ -0,0 +1,116 @@
+//! Live reload of config, keymap, and theme files
+//!
+//! Watches `~/.config/fileview` and reports which of the known files changed.
+//! The last successfully parsed config is kept so that a broken edit never
+//! replaces working settings.
+
+use std::ffi::OsStr;
+use std::path::PathBuf;
+
+use crate::handler::{KeyBindingRegistry, KeymapFile};
+use crate::render::ThemeFile;
+use crate::watcher::FileWatcher;
+
+use super::ConfigFile;
+
+/// Which watched files changed since the last poll
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConfigChanges {
+    pub config: bool,
+    pub keymap: bool,
+    pub theme: bool,
+}
+
+impl ConfigChanges {
+    /// Classify changed paths by file name
+    pub fn from_paths(paths: &[PathBuf]) -> Self {
+        let changed = |target: Option<PathBuf>| {
+            let name = target.and_then(|p| p.file_name().map(OsStr::to_os_string));
+            name.is_some_and(|name| {
+                paths
+                    .iter()
+                    .any(|p| p.file_name() == Some(name.as_os_str()))
+            })
+        };
+        Self {
+            config: changed(ConfigFile::config_path()),
+            keymap: changed(KeymapFile::keymap_path()),
+            theme: changed(ThemeFile::theme_path()),
+        }
+    }
+
+    /// Check if anything changed
+    pub fn any(&self) -> bool {
+        self.config || self.keymap || self.theme
+    }
+}
+
+/// Watches the config directory for edits
+pub struct ConfigReloader {
+    watcher: FileWatcher,
+    /// Last config that parsed successfully
+    config_file: ConfigFile,
+}
+
+impl ConfigReloader {
+    /// Start watching the config directory (None if it doesn't exist)
+    pub fn new() -> Option<Self> {
+        let dir = ConfigFile::config_dir()?;
+        let watcher = FileWatcher::new(&dir).ok()?;
+        Some(Self {
+            watcher,
+            config_file: ConfigFile::load(),
+        })
+    }
+
+    /// Check for changes to the watched files (non-blocking)
+    pub fn poll(&self) -> ConfigChanges {
+        ConfigChanges::from_paths(&self.watcher.poll_paths())
+    }
+
+    /// Re-read the config file, keeping the previous config on error
+    pub fn reload_config(&mut self) -> anyhow::Result<&ConfigFile> {
+        if let Some(path) = ConfigFile::config_path() {
+            self.config_file.reload(&path)?;
+        }
+        Ok(&self.config_file)
+    }
+}
+
+/// Re-read the keymap file into `registry`, keeping its bindings on error
+///
+/// Returns the conflicting bindings found, or None (and no registry, so the
+/// defaults apply) if there is no keymap file.
+pub fn reload_keymap(
+    registry: &mut Option<KeyBindingRegistry>,
+) -> anyhow::Result<Option<Vec<String>>> {
+    let Some(path) = KeymapFile::keymap_path().filter(|path| path.exists()) else {
+        *registry = None;
+        return Ok(None);
+    };
+    registry
+        .get_or_insert_with(KeyBindingRegistry::new)
+        .reload_from_file(&path)
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changes_from_paths() {
+        let dir = PathBuf::from("/home/user/.config/fileview");
+        let changes = ConfigChanges::from_paths(&[dir.join("config.toml"), dir.join("theme.toml")]);
+        assert!(changes.config);
+        assert!(!changes.keymap);
+        assert!(changes.theme);
+    }
+
+    #[test]
+    fn test_unrelated_files_are_not_changes() {
+        let dir = PathBuf::from("/home/user/.config/fileview");
+        let changes = ConfigChanges::from_paths(&[dir.join("recent.json"), dir.join("bookmarks")]);
+        assert!(!changes.any());
+    }
+}
@@ -10,10 +10,13 @@ use crate::core::{AppState, FocusTarget, TabManager, ViewMode};
 use crate::handler::action::get_filename_str;
 use crate::render::{
     render_ai_history_popup, render_archive_preview, render_bulk_rename_dialog,
+    render_command_output_popup, render_content_search_popup, render_csv_preview,
     render_custom_preview, render_diff_preview, render_directory_info, render_fuzzy_finder,
-    render_help_popup, render_hex_preview, render_image_preview, render_input_popup,
-    render_pdf_preview, render_status_bar, render_tab_bar, render_text_preview, render_tree,
-    render_video_preview, FontSize, FuzzyMatch, LayoutEngine, Picker,
+    render_git_stash_popup, render_help_popup, render_hex_preview, render_image_preview,
+    render_input_popup, render_marks_overview_popup, render_operation_history_popup,
+    render_pdf_preview, render_recent_files_popup, render_status_bar, render_tab_bar,
+    render_text_preview, render_tree, render_video_preview, set_preview_borders, split_preview,
+    FontSize, FuzzyMatch, LayoutEngine, Picker, TextViewOptions,
 };
 use crate::tree::TreeEntry;
 
@@ -24,6 +27,10 @@ pub struct RenderContext<'a> {
     pub focused_path: Option<&'a PathBuf>,
     pub preview: &'a mut PreviewState,
     pub fuzzy_results: &'a [FuzzyMatch],
+    /// Preview of the highlighted fuzzy finder entry
+    pub fuzzy_preview: &'a mut PreviewState,
+    /// Results listed by the fuzzy finder
+    pub fuzzy_max_results: usize,
     pub image_picker: &'a mut Option<Picker>,
     pub tab_manager: Option<&'a TabManager>,
 }
@@ -41,6 +48,7 @@ pub fn render_frame(frame: &mut Frame, mut ctx: RenderContext) {
 
     // Check if fullscreen preview mode is active
     let is_fullscreen_preview = matches!(ctx.state.mode, ViewMode::Preview { .. });
+    set_preview_borders(ctx.state.preview_borders);
 
     if is_fullscreen_preview {
         render_fullscreen_preview(frame, &mut ctx, size, font_size);
@@ -69,23 +77,49 @@ fn render_fullscreen_preview(
         render_diff_preview(frame, dp, size, &title, false);
     } else if let Some(ref cp) = ctx.preview.custom {
         render_custom_preview(frame, cp, size, &title, false);
+    } else if let Some(ref cp) = ctx.preview.csv {
+        render_csv_preview(frame, cp, size, &title, false);
     } else if let Some(ref tp) = ctx.preview.text {
-        render_text_preview(frame, tp, size, &title, false);
+        render_text_preview(frame, tp, size, &title, false, text_view_options(ctx.state));
     } else if let Some(ref mut ip) = ctx.preview.image {
         render_image_preview(frame, ip, size, &title, false, font_size);
     } else if let Some(ref mut vp) = ctx.preview.video {
         render_video_preview(frame, vp, size, &title, false, font_size);
     } else if let Some(ref mut pdf) = ctx.preview.pdf {
-        render_pdf_preview(frame, pdf, size, &filename, false, font_size);
+        render_pdf_preview(
+            frame,
+            pdf,
+            size,
+            &filename,
+            false,
+            font_size,
+            text_view_options(ctx.state),
+        );
     } else if let Some(ref hp) = ctx.preview.hex {
         render_hex_preview(frame, hp, size, &title, false);
     } else if let Some(ref ap) = ctx.preview.archive {
         render_archive_preview(frame, ap, size, &title, false);
     } else {
         let block = Block::default().borders(Borders::ALL).title(title);
-        let para = Paragraph::new("No preview available").block(block);
+        let para = Paragraph::new(placeholder(ctx.preview)).block(block);
         frame.render_widget(para, size);
     }
+
+    // Search prompt over the preview
+    render_input_popup(frame, ctx.state);
+}
+
+/// Split the area below the tab bar into the tree and the side preview
+///
+/// The preview is left out when it is hidden or the terminal is too narrow.
+pub fn split_main_area(state: &AppState, area: Rect) -> (Rect, Option<Rect>) {
+    let density = state.ui_density_for_width(area.width);
+    let layout = LayoutEngine::from_rect_with_density(area, density);
+    let preview_pct = layout.preview_percent(
+        state.effective_preview_visible(area.width),
+        state.preview_split,
+    );
+    split_preview(area, state.preview_position, preview_pct)
 }
 
 /// Render normal mode (tree with optional side preview)
@@ -110,44 +144,29 @@ fn render_normal_mode(frame: &mut Frame, ctx: &mut RenderContext, size: Rect, fo
         render_tab_bar(frame, tm, tab_area);
     }
 
-    let density = ctx.state.ui_density_for_width(main_area.width);
-    let layout = LayoutEngine::from_rect_with_density(main_area, density);
-
-    // Use effective_preview_visible to auto-hide preview on narrow terminals
-    let effective_preview =
-        layout.should_show_preview(ctx.state.effective_preview_visible(main_area.width));
-    let (tree_pct, preview_pct) = layout.split_ratio(effective_preview);
-
-    let main_chunks = if preview_pct > 0 {
-        Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(tree_pct),
-                Constraint::Percentage(preview_pct),
-            ])
-            .split(main_area)
-    } else {
-        Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(100)])
-            .split(main_area)
-    };
+    let (tree_area, preview_area) = split_main_area(ctx.state, main_area);
 
     // Tree area with status bar
     let tree_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(0), Constraint::Length(3)])
-        .split(main_chunks[0]);
+        .split(tree_area);
 
     // Render tree (viewport adjustment is done in event loop)
     render_tree(frame, ctx.state, &ctx.entries, tree_chunks[0]);
 
     // Render status bar
-    render_status_bar(frame, ctx.state, ctx.focused_path, tree_chunks[1]);
+    render_status_bar(
+        frame,
+        ctx.state,
+        ctx.focused_path,
+        ctx.entries.len(),
+        tree_chunks[1],
+    );
 
     // Render preview if visible (using effective visibility)
-    if effective_preview && main_chunks.len() > 1 {
-        render_side_preview(frame, ctx, main_chunks[1], font_size);
+    if let Some(preview_area) = preview_area {
+        render_side_preview(frame, ctx, preview_area, font_size);
     }
 
     // Render input popup if needed
@@ -161,12 +180,38 @@ fn render_normal_mode(frame: &mut Frame, ctx: &mut RenderContext, size: Rect, fo
         } else {
             (*selected).min(ctx.fuzzy_results.len() - 1)
         };
-        render_fuzzy_finder(frame, query, ctx.fuzzy_results, bounded_selected, size);
+        let preview_area = render_fuzzy_finder(
+            frame,
+            query,
+            ctx.fuzzy_results,
+            bounded_selected,
+            ctx.state.case_sensitive,
+            ctx.fuzzy_max_results,
+            size,
+        );
+        if let Some(area) = preview_area {
+            let title = get_filename_str(ctx.fuzzy_preview.last_path.as_ref());
+            render_preview_pane(
+                frame,
+                ctx.fuzzy_preview,
+                ctx.state,
+                area,
+                &title,
+                false,
+                font_size,
+            );
+        }
     }
 
     // Render help popup if in Help mode
     render_help_popup(frame, ctx.state);
     render_ai_history_popup(frame, ctx.state);
+    render_recent_files_popup(frame, ctx.state);
+    render_content_search_popup(frame, ctx.state);
+    render_command_output_popup(frame, ctx.state);
+    render_marks_overview_popup(frame, ctx.state);
+    render_operation_history_popup(frame, ctx.state);
+    render_git_stash_popup(frame, ctx.state);
 
     // Render bulk rename dialog if in BulkRename mode
     if matches!(ctx.state.mode, ViewMode::BulkRename { .. }) {
@@ -183,25 +228,62 @@ fn render_side_preview(
 ) {
     let title = get_filename_str(ctx.focused_path);
     let preview_focused = ctx.state.focus_target == FocusTarget::Preview;
+    render_preview_pane(
+        frame,
+        ctx.preview,
+        ctx.state,
+        area,
+        &title,
+        preview_focused,
+        font_size,
+    );
+}
 
-    if let Some(ref di) = ctx.preview.dir_info {
+/// Render whichever preview is loaded in `preview`
+fn render_preview_pane(
+    frame: &mut Frame,
+    preview: &mut PreviewState,
+    state: &AppState,
+    area: Rect,
+    title: &str,
+    preview_focused: bool,
+    font_size: FontSize,
+) {
+    if let Some(ref di) = preview.dir_info {
         render_directory_info(frame, di, area, preview_focused);
-    } else if let Some(ref dp) = ctx.preview.diff {
-        render_diff_preview(frame, dp, area, &title, preview_focused);
-    } else if let Some(ref cp) = ctx.preview.custom {
-        render_custom_preview(frame, cp, area, &title, preview_focused);
-    } else if let Some(ref tp) = ctx.preview.text {
-        render_text_preview(frame, tp, area, &title, preview_focused);
-    } else if let Some(ref mut ip) = ctx.preview.image {
-        render_image_preview(frame, ip, area, &title, preview_focused, font_size);
-    } else if let Some(ref mut vp) = ctx.preview.video {
-        render_video_preview(frame, vp, area, &title, preview_focused, font_size);
-    } else if let Some(ref mut pdf) = ctx.preview.pdf {
-        render_pdf_preview(frame, pdf, area, &title, preview_focused, font_size);
-    } else if let Some(ref hp) = ctx.preview.hex {
-        render_hex_preview(frame, hp, area, &title, preview_focused);
-    } else if let Some(ref ap) = ctx.preview.archive {
-        render_archive_preview(frame, ap, area, &title, preview_focused);
+    } else if let Some(ref dp) = preview.diff {
+        render_diff_preview(frame, dp, area, title, preview_focused);
+    } else if let Some(ref cp) = preview.custom {
+        render_custom_preview(frame, cp, area, title, preview_focused);
+    } else if let Some(ref cp) = preview.csv {
+        render_csv_preview(frame, cp, area, title, preview_focused);
+    } else if let Some(ref tp) = preview.text {
+        render_text_preview(
+            frame,
+            tp,
+            area,
+            title,
+            preview_focused,
+            text_view_options(state),
+        );
+    } else if let Some(ref mut ip) = preview.image {
+        render_image_preview(frame, ip, area, title, preview_focused, font_size);
+    } else if let Some(ref mut vp) = preview.video {
+        render_video_preview(frame, vp, area, title, preview_focused, font_size);
+    } else if let Some(ref mut pdf) = preview.pdf {
+        render_pdf_preview(
+            frame,
+            pdf,
+            area,
+            title,
+            preview_focused,
+            font_size,
+            text_view_options(state),
+        );
+    } else if let Some(ref hp) = preview.hex {
+        render_hex_preview(frame, hp, area, title, preview_focused);
+    } else if let Some(ref ap) = preview.archive {
+        render_archive_preview(frame, ap, area, title, preview_focused);
     } else {
         let border_style = if preview_focused {
             Style::default().fg(Color::Cyan)
@@ -212,7 +294,24 @@ fn render_side_preview(
             .borders(Borders::ALL)
             .title(" Preview ")
             .border_style(border_style);
-        let para = Paragraph::new("No preview available").block(block);
+        let para = Paragraph::new(placeholder(preview)).block(block);
         frame.render_widget(para, area);
     }
 }
+
+/// Text of an empty preview pane ("…" while focus has yet to settle)
+fn placeholder(preview: &PreviewState) -> &'static str {
+    if preview.is_settling() {
+        "…"
+    } else {
+        "No preview available"
+    }
+}
+
+/// Text preview display options from the app state
+fn text_view_options(state: &AppState) -> TextViewOptions {
+    TextViewOptions {
+        wrap: state.preview_wrap,
+        line_numbers: state.preview_line_numbers,
+    }
+}
@@ -0,0 +1,194 @@
+//! On-disk cache of downscaled images for the preview pane
+//!
+//! Thumbnails are PNG files in `~/.cache/fileview/thumbnails`, named after a
+//! hash of the source path and modification time, so an edited image gets a
+//! fresh thumbnail and the cache is reused across sessions. Reading a
+//! thumbnail bumps its file time; once the cache grows past its size bound
+//! the least recently used thumbnails are deleted.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use image::{DynamicImage, ImageFormat};
+
+use crate::core::{cache_dir, cache_key};
+
+/// Longest side of a thumbnail in pixels (smaller images are not cached)
+pub const THUMBNAIL_SIZE: u32 = 1024;
+
+/// A directory of thumbnails bounded to `max_bytes`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThumbnailCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl ThumbnailCache {
+    /// Cache in `dir`, holding at most `max_bytes` of thumbnails
+    pub fn new(dir: PathBuf, max_bytes: u64) -> Self {
+        Self { dir, max_bytes }
+    }
+
+    /// Default location in the user cache directory
+    pub fn default_dir() -> Option<PathBuf> {
+        cache_dir().map(|p| p.join("thumbnails"))
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.png", key))
+    }
+
+    /// Cached thumbnail for `key`, marked as recently used
+    pub fn get(&self, key: &str) -> Option<DynamicImage> {
+        let path = self.entry_path(key);
+        let image = image::open(&path).ok()?;
+        let _ = File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now()));
+        Some(image)
+    }
+
+    /// Store a thumbnail, then evict down to the size bound
+    pub fn put(&self, key: &str, image: &DynamicImage) -> anyhow::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        // Written to a uniquely named file and renamed, so a concurrent reader
+        // never sees half a file and concurrent writers never share one
+        let mut partial = tempfile::NamedTempFile::new_in(&self.dir)?;
+        let mut writer = io::BufWriter::new(partial.as_file_mut());
+        image.write_to(&mut writer, ImageFormat::Png)?;
+        writer.flush()?;
+        drop(writer);
+        partial.persist(self.entry_path(key))?;
+        self.evict()?;
+        Ok(())
+    }
+
+    /// Delete the least recently used thumbnails until the cache fits its bound
+    pub fn evict(&self) -> io::Result<()> {
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.dir)?.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "png") {
+                continue;
+            }
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            let used = meta.modified().unwrap_or(UNIX_EPOCH);
+            entries.push((used, meta.len(), path));
+        }
+
+        let mut total: u64 = entries.iter().map(|(_, len, _)| len).sum();
+        entries.sort();
+        for (_, len, path) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            if fs::remove_file(&path).is_ok() {
+                total -= len;
+            }
+        }
+        Ok(())
+    }
+
+    /// Image at `path` fit into [`THUMBNAIL_SIZE`], from the cache if possible
+    ///
+    /// Returns the image and the size of the source when it was downscaled;
+    /// images already small enough are decoded as they are.
+    pub fn load(&self, path: &Path) -> anyhow::Result<(DynamicImage, Option<(u32, u32)>)> {
+        let size = image::image_dimensions(path)?;
+        if size.0 <= THUMBNAIL_SIZE && size.1 <= THUMBNAIL_SIZE {
+            return Ok((image::open(path)?, None));
+        }
+
+        let key = fs::metadata(path)?
+            .modified()
+            .map(|modified| cache_key(path, modified))
+            .ok();
+        if let Some(image) = key.as_deref().and_then(|key| self.get(key)) {
+            return Ok((image, Some(size)));
+        }
+        let image = image::open(path)?.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+        if let Some(key) = key {
+            // A cache that cannot be written only costs the next decode
+            let _ = self.put(&key, &image);
+        }
+        Ok((image, Some(size)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn image(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::new_rgb8(width, height)
+    }
+
+    fn set_used(cache: &ThumbnailCache, key: &str, secs: u64) {
+        File::options()
+            .write(true)
+            .open(cache.entry_path(key))
+            .unwrap()
+            .set_modified(UNIX_EPOCH + Duration::from_secs(secs))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used_past_bound() {
+        let temp = TempDir::new().unwrap();
+        let mut cache = ThumbnailCache::new(temp.path().to_path_buf(), u64::MAX);
+        for key in ["a", "b", "c"] {
+            cache.put(key, &image(64, 64)).unwrap();
+        }
+        let entry_len = fs::metadata(cache.entry_path("a")).unwrap().len();
+
+        // "a" was written first but read last
+        set_used(&cache, "a", 300);
+        set_used(&cache, "b", 100);
+        set_used(&cache, "c", 200);
+
+        cache.max_bytes = 2 * entry_len;
+        cache.evict().unwrap();
+        assert!(cache.entry_path("a").exists());
+        assert!(!cache.entry_path("b").exists());
+        assert!(cache.entry_path("c").exists());
+
+        // Reading "c" bumps it past "a", so "a" goes next
+        assert!(cache.get("c").is_some());
+        cache.put("d", &image(64, 64)).unwrap();
+        assert!(!cache.entry_path("a").exists());
+        assert!(cache.entry_path("c").exists());
+        assert!(cache.entry_path("d").exists());
+    }
+
+    #[test]
+    fn test_load_downscales_and_caches_large_images() {
+        let temp = TempDir::new().unwrap();
+        let cache = ThumbnailCache::new(temp.path().join("cache"), u64::MAX);
+
+        let large = temp.path().join("large.png");
+        image(THUMBNAIL_SIZE * 2, THUMBNAIL_SIZE)
+            .save(&large)
+            .unwrap();
+        let (thumb, source) = cache.load(&large).unwrap();
+        assert_eq!(
+            (thumb.width(), thumb.height()),
+            (THUMBNAIL_SIZE, THUMBNAIL_SIZE / 2)
+        );
+        assert_eq!(source, Some((THUMBNAIL_SIZE * 2, THUMBNAIL_SIZE)));
+        assert_eq!(fs::read_dir(temp.path().join("cache")).unwrap().count(), 1);
+
+        // Small images are shown as they are and not cached
+        let small = temp.path().join("small.png");
+        image(10, 10).save(&small).unwrap();
+        let (full, source) = cache.load(&small).unwrap();
+        assert_eq!((full.width(), source), (10, None));
+        assert_eq!(fs::read_dir(temp.path().join("cache")).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,53 @@
+//! Per-user cache location and cache keys
+//!
+//! Derived files (thumbnails, extracted archive members) live below
+//! `~/.cache/fileview`, named after a hash of the source path and its
+//! modification time so an edited source gets a fresh entry.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The user's fileview cache directory (`~/.cache/fileview`)
+pub fn cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|p| p.join("fileview"))
+}
+
+/// Cache key of a file: a hash of its path and modification time
+pub fn cache_key(path: &Path, modified: SystemTime) -> String {
+    // FNV-1a, stable across builds (unlike `DefaultHasher`)
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let since_epoch = modified.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs().to_le_bytes();
+    let nanos = since_epoch.subsec_nanos().to_le_bytes();
+    let bytes = path
+        .as_os_str()
+        .as_encoded_bytes()
+        .iter()
+        .chain(&[0])
+        .chain(&secs)
+        .chain(&nanos);
+    for byte in bytes {
+        hash = (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_key_depends_on_path_and_mtime() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let key = cache_key(Path::new("/photos/a.jpg"), time);
+
+        assert_eq!(key.len(), 16);
+        assert_eq!(key, cache_key(Path::new("/photos/a.jpg"), time));
+        assert_ne!(key, cache_key(Path::new("/photos/b.jpg"), time));
+        assert_ne!(
+            key,
+            cache_key(Path::new("/photos/a.jpg"), time + Duration::from_nanos(1))
+        );
+    }
+}
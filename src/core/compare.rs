@@ -0,0 +1,194 @@
+//! Line diff of two arbitrary files (no git involved)
+//!
+//! Produces the same [`FileDiff`] structure as `git diff`, so the diff
+//! preview renders it unchanged. Lines are compared with the `similar`
+//! crate's Myers diff; past [`DIFF_DEADLINE`] the diff is no longer minimized
+//! to keep huge files responsive.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use similar::{capture_diff_slices_deadline, group_diff_ops, Algorithm, DiffOp, DiffTag};
+
+use crate::git::{DiffHunk, DiffLine, FileDiff};
+
+/// Unchanged lines shown around each change
+const CONTEXT_LINES: usize = 3;
+
+/// Time spent minimizing the diff before settling for a larger one
+pub const DIFF_DEADLINE: Duration = Duration::from_millis(500);
+
+/// Diff two texts into a [`FileDiff`] for `path`
+///
+/// The header shows `old_label` and `new_label`; the diff is empty when the
+/// texts have the same lines.
+pub fn diff_texts(
+    old: &str,
+    new: &str,
+    old_label: &str,
+    new_label: &str,
+    path: PathBuf,
+) -> FileDiff {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = capture_diff_slices_deadline(
+        Algorithm::Myers,
+        &old_lines,
+        &new_lines,
+        Some(Instant::now() + DIFF_DEADLINE),
+    );
+    let additions = ops.iter().map(|op| op.new_range().len()).sum::<usize>() - equal_len(&ops);
+    let deletions = ops.iter().map(|op| op.old_range().len()).sum::<usize>() - equal_len(&ops);
+
+    let hunks: Vec<DiffHunk> = group_diff_ops(ops, CONTEXT_LINES)
+        .iter()
+        .map(|group| hunk(group, &old_lines, &new_lines))
+        .collect();
+
+    let mut lines = Vec::new();
+    if !hunks.is_empty() {
+        lines.push(DiffLine::Other(format!("--- {}", old_label)));
+        lines.push(DiffLine::Other(format!("+++ {}", new_label)));
+    }
+    lines.extend(hunks.iter().flat_map(|h| h.lines.iter().cloned()));
+
+    FileDiff {
+        path,
+        additions,
+        deletions,
+        hunks,
+        lines,
+        base: Some(old_label.to_string()),
+    }
+}
+
+/// Lines the two texts have in common
+fn equal_len(ops: &[DiffOp]) -> usize {
+    ops.iter()
+        .filter(|op| op.tag() == DiffTag::Equal)
+        .map(|op| op.old_range().len())
+        .sum()
+}
+
+/// Build one hunk from a group of operations
+fn hunk(group: &[DiffOp], old_lines: &[&str], new_lines: &[&str]) -> DiffHunk {
+    let (first, last) = (&group[0], &group[group.len() - 1]);
+    let old_range = first.old_range().start..last.old_range().end;
+    let new_range = first.new_range().start..last.new_range().end;
+
+    let mut lines = Vec::new();
+    for op in group {
+        let (tag, old, new) = op.as_tag_tuple();
+        match tag {
+            DiffTag::Equal => lines.extend(
+                old_lines[old]
+                    .iter()
+                    .map(|line| DiffLine::Context(line.to_string())),
+            ),
+            DiffTag::Delete | DiffTag::Insert | DiffTag::Replace => {
+                lines.extend(
+                    old_lines[old]
+                        .iter()
+                        .map(|line| DiffLine::Removed(line.to_string())),
+                );
+                lines.extend(
+                    new_lines[new]
+                        .iter()
+                        .map(|line| DiffLine::Added(line.to_string())),
+                );
+            }
+        }
+    }
+
+    // An empty range starts at the line before it, as in `diff -u`
+    let start = |range: &std::ops::Range<usize>| {
+        if range.is_empty() {
+            range.start
+        } else {
+            range.start + 1
+        }
+    };
+    let (old_start, old_count) = (start(&old_range), old_range.len());
+    let (new_start, new_count) = (start(&new_range), new_range.len());
+    lines.insert(
+        0,
+        DiffLine::HunkHeader(format!(
+            "@@ -{},{} +{},{} @@",
+            old_start, old_count, new_start, new_count
+        )),
+    );
+    DiffHunk {
+        old_start,
+        old_count,
+        new_start,
+        new_count,
+        lines,
+    }
+}
+
+/// Diff the contents of two text files (`old` shown as the base)
+pub fn diff_files(old: &Path, new: &Path) -> anyhow::Result<FileDiff> {
+    let read = |path: &Path| {
+        std::fs::read_to_string(path).map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))
+    };
+    let label = |path: &Path| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string())
+    };
+    Ok(diff_texts(
+        &read(old)?,
+        &read(new)?,
+        &label(old),
+        &label(new),
+        new.to_path_buf(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_texts_hunks() {
+        let old = "one\ntwo\nthree\n";
+        let new = "one\n2\nthree\nfour\n";
+        let diff = diff_texts(old, new, "a.txt", "b.txt", PathBuf::from("b.txt"));
+
+        assert_eq!((diff.additions, diff.deletions), (2, 1));
+        assert_eq!(diff.hunks.len(), 1);
+        assert_eq!(diff.base.as_deref(), Some("a.txt"));
+        assert_eq!(
+            diff.lines,
+            vec![
+                DiffLine::Other("--- a.txt".to_string()),
+                DiffLine::Other("+++ b.txt".to_string()),
+                DiffLine::HunkHeader("@@ -1,3 +1,4 @@".to_string()),
+                DiffLine::Context("one".to_string()),
+                DiffLine::Removed("two".to_string()),
+                DiffLine::Added("2".to_string()),
+                DiffLine::Context("three".to_string()),
+                DiffLine::Added("four".to_string()),
+            ]
+        );
+
+        // Far-apart changes get separate hunks
+        let old: String = (1..=20).map(|n| format!("{}\n", n)).collect();
+        let new: String = (1..=20)
+            .map(|n| match n {
+                2 => "two\n".to_string(),
+                19 => "nineteen\n".to_string(),
+                n => format!("{}\n", n),
+            })
+            .collect();
+        let diff = diff_texts(&old, &new, "a", "b", PathBuf::from("b"));
+        let headers: Vec<(usize, usize)> = diff
+            .hunks
+            .iter()
+            .map(|h| (h.old_start, h.old_count))
+            .collect();
+        assert_eq!(headers, vec![(1, 5), (16, 5)]);
+
+        assert!(diff_texts("same\n", "same\n", "a", "b", PathBuf::from("b")).is_empty());
+    }
+}
@@ -0,0 +1,141 @@
+//! Free space on the filesystem holding the root
+//!
+//! Asks POSIX `df -Pk` for the filesystem a path lives on. The status bar
+//! shows the result, refreshed every [`DISK_USAGE_INTERVAL`] rather than per
+//! frame. `df` runs on a worker thread ([`DiskUsageJob`]) so a slow or hung
+//! mount cannot stall the UI. Where `df` is missing (e.g. Windows) the figures
+//! are left out.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+/// How often the disk figures are refreshed
+pub const DISK_USAGE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Space on one filesystem
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskUsage {
+    /// Path that was asked about
+    pub path: PathBuf,
+    /// Bytes available to unprivileged users
+    pub free: u64,
+    /// Size of the filesystem in bytes
+    pub total: u64,
+    /// Where the filesystem is mounted
+    pub mount: PathBuf,
+}
+
+/// `df` running in the background for one path
+#[derive(Debug)]
+pub struct DiskUsageJob {
+    /// Path being asked about
+    pub path: PathBuf,
+    result: Receiver<Option<DiskUsage>>,
+}
+
+impl DiskUsageJob {
+    /// Start asking `df` about `path` on a new worker thread
+    pub fn start(path: &Path) -> Self {
+        let (tx, result) = mpsc::channel();
+        let worker_path = path.to_path_buf();
+        thread::spawn(move || {
+            let _ = tx.send(disk_usage(&worker_path));
+        });
+        Self {
+            path: path.to_path_buf(),
+            result,
+        }
+    }
+
+    /// The figures once `df` finished (None while it is still running)
+    pub fn poll(&self) -> Option<Option<DiskUsage>> {
+        match self.result.try_recv() {
+            Ok(usage) => Some(usage),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(None),
+        }
+    }
+}
+
+/// Free and total space of the filesystem containing `path` (None if unavailable)
+///
+/// Blocks until `df` exits; use [`DiskUsageJob`] from the UI thread.
+pub fn disk_usage(path: &Path) -> Option<DiskUsage> {
+    let output = Command::new("df")
+        .arg("-Pk")
+        .arg(path)
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_df(path, &String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parse `df -Pk` output: a header, then one line per filesystem
+fn parse_df(path: &Path, output: &str) -> Option<DiskUsage> {
+    let line = output.lines().nth(1)?;
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    // Filesystem, 1024-blocks, Used, Available, Capacity, Mounted on (may hold spaces)
+    if fields.len() < 6 {
+        return None;
+    }
+    let total = fields[1].parse::<u64>().ok()? * 1024;
+    let free = fields[3].parse::<u64>().ok()? * 1024;
+    Some(DiskUsage {
+        path: path.to_path_buf(),
+        free,
+        total,
+        mount: PathBuf::from(fields[5..].join(" ")),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_df() {
+        let output = "Filesystem     1024-blocks     Used Available Capacity Mounted on\n\
+                      /dev/vda         264212084 16229320  79828088      17% /mnt/My Disk\n";
+        let usage = parse_df(Path::new("/mnt/My Disk/src"), output).unwrap();
+        assert_eq!(usage.total, 264_212_084 * 1024);
+        assert_eq!(usage.free, 79_828_088 * 1024);
+        assert_eq!(usage.mount, PathBuf::from("/mnt/My Disk"));
+
+        assert_eq!(parse_df(Path::new("/"), "Filesystem\n"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_disk_usage_of_temp_dir() {
+        let temp = tempfile::TempDir::new().unwrap();
+        // Nothing to check without df
+        let Some(usage) = disk_usage(temp.path()) else {
+            return;
+        };
+        assert!(usage.total > 0);
+        assert!(usage.free <= usage.total);
+        assert!(usage.mount.is_absolute());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_job_delivers_usage() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let job = DiskUsageJob::start(temp.path());
+        let deadline = std::time::Instant::now() + Duration::from_secs(10);
+        let usage = loop {
+            if let Some(usage) = job.poll() {
+                break usage;
+            }
+            assert!(std::time::Instant::now() < deadline, "df never finished");
+            thread::sleep(Duration::from_millis(5));
+        };
+        assert_eq!(usage, disk_usage(temp.path()));
+    }
+}
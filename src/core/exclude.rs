@@ -0,0 +1,21 @@
+//! Directories left alone by default
+//!
+//! Common large or generated directories. The file watcher does not watch
+//! them, startup expansion never opens them, and the fuzzy finder skips
+//! them unless `fuzzy_exclude` is configured.
+
+/// Names of directories to skip (common large/generated directories)
+pub const EXCLUDED_DIRS: &[&str] = &[
+    ".git",
+    "target",
+    "node_modules",
+    ".venv",
+    "venv",
+    "__pycache__",
+    ".cache",
+    "dist",
+    "build",
+    ".next",
+    ".nuxt",
+    "vendor",
+];
@@ -0,0 +1,134 @@
+//! Name matching shared by the handlers and renderers
+//!
+//! Smart case substring search (with the match ranges used for highlighting)
+//! and the simple `*`/`?` glob used by filters and per-extension settings.
+
+use std::ops::Range;
+
+/// Whether a query matches case exactly
+///
+/// Smart case: a query with an uppercase letter is case-sensitive,
+/// an all-lowercase query is not.
+pub fn is_case_sensitive(query: &str, case_sensitive: bool) -> bool {
+    case_sensitive || query.chars().any(char::is_uppercase)
+}
+
+/// Byte ranges of every non-overlapping occurrence of the query in a name
+///
+/// Uses the smart case rule of [`is_case_sensitive`]; an empty query
+/// matches nothing.
+pub fn match_ranges(name: &str, query: &str, case_sensitive: bool) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    if query.is_empty() {
+        return ranges;
+    }
+    let exact = is_case_sensitive(query, case_sensitive);
+    let mut start = 0;
+    while start < name.len() {
+        match match_len_at(&name[start..], query, exact) {
+            Some(len) => {
+                ranges.push(start..start + len);
+                start += len;
+            }
+            None => {
+                start += name[start..].chars().next().map_or(1, char::len_utf8);
+            }
+        }
+    }
+    ranges
+}
+
+/// Length in bytes of the query matched at the start of `text`
+fn match_len_at(text: &str, query: &str, exact: bool) -> Option<usize> {
+    let mut chars = text.char_indices();
+    for q in query.chars() {
+        let (_, c) = chars.next()?;
+        let same = if exact {
+            c == q
+        } else {
+            c.to_lowercase().eq(q.to_lowercase())
+        };
+        if !same {
+            return None;
+        }
+    }
+    Some(chars.next().map_or(text.len(), |(i, _)| i))
+}
+
+/// Simple glob matching implementation
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    glob_match_impl(&pattern, &text)
+}
+
+fn glob_match_impl(pattern: &[char], text: &[char]) -> bool {
+    let mut p_idx = 0;
+    let mut t_idx = 0;
+    let mut star_idx: Option<usize> = None;
+    let mut match_idx = 0;
+
+    while t_idx < text.len() {
+        if p_idx < pattern.len() && (pattern[p_idx] == '?' || pattern[p_idx] == text[t_idx]) {
+            // Characters match or pattern has ?
+            p_idx += 1;
+            t_idx += 1;
+        } else if p_idx < pattern.len() && pattern[p_idx] == '*' {
+            // Star found, remember position
+            star_idx = Some(p_idx);
+            match_idx = t_idx;
+            p_idx += 1;
+        } else if let Some(star) = star_idx {
+            // Mismatch after star, backtrack
+            p_idx = star + 1;
+            match_idx += 1;
+            t_idx = match_idx;
+        } else {
+            // No match
+            return false;
+        }
+    }
+
+    // Check remaining pattern characters (should all be stars)
+    while p_idx < pattern.len() && pattern[p_idx] == '*' {
+        p_idx += 1;
+    }
+
+    p_idx == pattern.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_ranges_highlights_every_match() {
+        assert_eq!(
+            match_ranges("test_test.rs", "test", false),
+            vec![0..4, 5..9]
+        );
+        // Smart case: lowercase query ignores case, uppercase query does not
+        assert_eq!(
+            match_ranges("Readme.README", "readme", false),
+            vec![0..6, 7..13]
+        );
+        assert_eq!(match_ranges("Readme.README", "README", false), vec![7..13]);
+        assert_eq!(match_ranges("aaaa", "aa", false), vec![0..2, 2..4]);
+        // Ranges are byte offsets into the name
+        assert_eq!(match_ranges("日本語.txt", "語", false), vec![6..9]);
+    }
+
+    #[test]
+    fn test_match_ranges_no_match() {
+        assert!(match_ranges("main.rs", "lib", false).is_empty());
+        assert!(match_ranges("main.rs", "", false).is_empty());
+        assert!(match_ranges("Main.rs", "main", true).is_empty());
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.rs", "main.rs"));
+        assert!(glob_match("te?t*", "test.txt"));
+        assert!(!glob_match("*.rs", "main.txt"));
+    }
+}
@@ -0,0 +1,155 @@
+//! Status bar messages
+//!
+//! Every message carries a [`Severity`] that picks its color. Info and
+//! warning messages expire on their own after a few seconds; errors stay up
+//! until the next action so they are not missed, and can ring the terminal
+//! bell or flash the status bar for [`ERROR_FLASH`].
+
+use std::time::{Duration, Instant};
+
+/// How long an info message stays up
+pub const INFO_TTL: Duration = Duration::from_secs(3);
+
+/// How long a warning stays up
+pub const WARNING_TTL: Duration = Duration::from_secs(6);
+
+/// How long the status bar stays inverted after an error
+pub const ERROR_FLASH: Duration = Duration::from_millis(200);
+
+/// Message severity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Severity {
+    #[default]
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// Time until a message of this severity expires (None = until the next action)
+    pub fn ttl(self) -> Option<Duration> {
+        match self {
+            Self::Info => Some(INFO_TTL),
+            Self::Warning => Some(WARNING_TTL),
+            Self::Error => None,
+        }
+    }
+}
+
+/// A status bar message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusMessage {
+    /// Message text
+    pub text: String,
+    /// Severity (selects color and lifetime)
+    pub severity: Severity,
+    /// When the message was set
+    pub set_at: Instant,
+}
+
+impl StatusMessage {
+    /// Create a message set now
+    pub fn new(text: impl Into<String>, severity: Severity) -> Self {
+        Self {
+            text: text.into(),
+            severity,
+            set_at: Instant::now(),
+        }
+    }
+
+    /// Time left before the message expires at `now` (None if it never does)
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.severity
+            .ttl()
+            .map(|ttl| ttl.saturating_sub(now.saturating_duration_since(self.set_at)))
+    }
+
+    /// Check if the message has expired at `now`
+    pub fn is_expired(&self, now: Instant) -> bool {
+        self.remaining(now).is_some_and(|left| left.is_zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::AppState;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_info_message_expires_after_ttl() {
+        let message = StatusMessage::new("Copied", Severity::Info);
+        let set_at = message.set_at;
+
+        assert!(!message.is_expired(set_at));
+        assert_eq!(
+            message.remaining(set_at + Duration::from_secs(1)),
+            Some(INFO_TTL - Duration::from_secs(1))
+        );
+        assert!(!message.is_expired(set_at + INFO_TTL - Duration::from_millis(1)));
+        assert!(message.is_expired(set_at + INFO_TTL));
+
+        let warning = StatusMessage::new("Large file", Severity::Warning);
+        assert!(!warning.is_expired(warning.set_at + INFO_TTL));
+        assert!(warning.is_expired(warning.set_at + WARNING_TTL));
+    }
+
+    #[test]
+    fn test_error_message_does_not_expire() {
+        let message = StatusMessage::new("Failed: boom", Severity::Error);
+        assert_eq!(message.remaining(message.set_at), None);
+        assert!(!message.is_expired(message.set_at + Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_expire_message_on_tick() {
+        let mut state = AppState::new(PathBuf::from("/tmp"));
+        state.set_message("Copied 1 item");
+        let set_at = state.message.as_ref().unwrap().set_at;
+
+        state.expire_message(set_at + Duration::from_secs(1));
+        assert_eq!(state.message_text(), Some("Copied 1 item"));
+        state.expire_message(set_at + INFO_TTL);
+        assert_eq!(state.message_text(), None);
+
+        state.set_error("Failed: permission denied");
+        let set_at = state.message.as_ref().unwrap().set_at;
+        state.expire_message(set_at + Duration::from_secs(60));
+        assert_eq!(state.message_text(), Some("Failed: permission denied"));
+        assert_eq!(state.message_severity(), Some(Severity::Error));
+
+        // Errors are dismissed by the next action
+        state.clear_message();
+        assert_eq!(state.message_text(), None);
+    }
+
+    #[test]
+    fn test_error_sets_flash_and_bell_but_info_does_not() {
+        let mut state = AppState::new(PathBuf::from("/tmp"));
+        state.flash_on_error = true;
+        state.bell_on_error = true;
+
+        state.set_message("Copied 1 item");
+        state.set_warning("Large file");
+        let now = Instant::now();
+        assert!(!state.is_flashing(now));
+        assert!(!state.take_bell());
+
+        state.set_error("Failed: permission denied");
+        let now = Instant::now();
+        assert!(state.is_flashing(now));
+        assert!(state
+            .flash_remaining(now)
+            .is_some_and(|left| left <= ERROR_FLASH));
+        assert!(!state.is_flashing(now + ERROR_FLASH));
+        // The bell rings once per error
+        assert!(state.take_bell());
+        assert!(!state.take_bell());
+
+        // Both are off by default
+        let mut state = AppState::new(PathBuf::from("/tmp"));
+        state.set_error("Failed: boom");
+        assert!(!state.is_flashing(Instant::now()));
+        assert!(!state.take_bell());
+    }
+}
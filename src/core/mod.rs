@@ -1,9 +1,27 @@
 //! Core module - Application state and view modes
 
+pub mod cache;
+pub mod compare;
+pub mod disk;
+pub mod exclude;
+pub mod matching;
+pub mod message;
 pub mod mode;
 pub mod state;
 pub mod tab;
+pub mod tools;
 
-pub use mode::{FocusTarget, InputPurpose, PendingAction, ViewMode};
-pub use state::{AppState, PreviewDisplayMode, SortMode, UiDensity, BOOKMARK_SLOTS};
+pub use cache::{cache_dir, cache_key};
+pub use compare::{diff_files, diff_texts};
+pub use disk::{DiskUsage, DiskUsageJob, DISK_USAGE_INTERVAL};
+pub use exclude::EXCLUDED_DIRS;
+pub use matching::{glob_match, is_case_sensitive, match_ranges};
+pub use message::{Severity, StatusMessage};
+pub use mode::{FocusTarget, InputPurpose, PatternSelect, PendingAction, ViewMode};
+pub use state::{
+    AppState, ClickAction, CollapseFallback, FileEnterAction, HiddenScope, IconSet, NameTruncation,
+    PickDirs, PreviewDisplayMode, PreviewPosition, SortMode, TreeGuideStyle, UiDensity,
+    BOOKMARK_SLOTS, CSV_MAX_ROWS, PREVIEW_SPLIT_MAX, PREVIEW_SPLIT_MIN, PREVIEW_SPLIT_STEP,
+};
 pub use tab::{Tab, TabManager};
+pub use tools::find_executable;
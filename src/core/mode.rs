@@ -2,6 +2,9 @@
 
 use std::path::PathBuf;
 
+use crate::action::{LinkKind, PastePlan};
+use crate::git::Stash;
+
 /// Focus target for split view (side preview mode)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum FocusTarget {
@@ -49,12 +52,57 @@ pub enum ViewMode {
         /// Index of selected entry
         selected: usize,
     },
+    /// Recent files popup
+    RecentFiles {
+        /// Text typed to narrow the list
+        query: String,
+        /// Index of selected entry among the matches
+        selected: usize,
+    },
+    /// Operation history popup
+    OperationHistory {
+        /// Index of selected operation (newest first)
+        selected: usize,
+    },
+    /// Marked paths overview popup
+    MarksOverview {
+        /// Index of selected mark (in path order)
+        selected: usize,
+    },
+    /// Content search results popup
+    ContentSearch {
+        /// Index of selected match
+        selected: usize,
+    },
+    /// Output of a command run over marked files
+    CommandOutput {
+        /// First output line shown
+        scroll: usize,
+    },
+    /// Git stash list popup
+    GitStash {
+        /// Stashes of the current repository (newest first)
+        stashes: Vec<Stash>,
+        /// Index of selected stash
+        selected: usize,
+    },
     /// Waiting for bookmark slot input (set bookmark)
     BookmarkSet,
     /// Waiting for bookmark slot input (jump to bookmark)
     BookmarkJump,
+    /// Waiting for sort key input (sort selector)
+    SortSelect,
+    /// Waiting for what to copy of the focused file's git history (diff/blame)
+    YankGit,
     /// File filter input mode
     Filter { query: String },
+    /// Type-to-narrow mode: the tree shows fuzzy matches and their ancestors
+    LiveFilter {
+        /// Typed query
+        query: String,
+        /// Entry focused before narrowing (refocused on cancel)
+        origin: Option<PathBuf>,
+    },
     /// Bulk rename mode
     BulkRename {
         /// Pattern to match (e.g., "*.txt", "old_")
@@ -77,11 +125,54 @@ pub enum InputPurpose {
     CreateDir,
     /// Renaming an existing item
     Rename { original: PathBuf },
+    /// Creating a link to `source` in `dest_dir` (`pending` sources are prompted next)
+    CreateLink {
+        source: PathBuf,
+        pending: Vec<PathBuf>,
+        kind: LinkKind,
+        dest_dir: PathBuf,
+    },
+    /// Revision to diff `path` against (`refs` are offered on Tab)
+    DiffRevision { path: PathBuf, refs: Vec<String> },
+    /// Glob pattern to mark or unmark entries by name
+    SelectPattern { action: PatternSelect },
+    /// Text to search for in file contents
+    ContentSearch,
+    /// Message for committing the staged changes (`amend` replaces the last commit)
+    CommitMessage { amend: bool },
+    /// Shell command run once per target, with `{path}` expanded for each
+    ForeachCommand { targets: Vec<PathBuf> },
+}
+
+/// What a pattern prompt does with the entries whose names match
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatternSelect {
+    /// Mark matching visible entries
+    Select,
+    /// Unmark matching marked entries
+    Deselect,
+    /// Mark matching entries anywhere under `root`, collapsed or not
+    Subtree { root: PathBuf },
 }
 
 /// Action pending confirmation
 #[derive(Debug, Clone, PartialEq)]
 pub enum PendingAction {
-    /// Delete files/directories
-    Delete { targets: Vec<PathBuf> },
+    /// Delete files/directories (to the trash unless `permanent`)
+    Delete {
+        targets: Vec<PathBuf>,
+        permanent: bool,
+    },
+    /// Choose how to paste over existing files
+    ResolvePasteConflict {
+        plan: PastePlan,
+        /// Leave the clipboard as it is after pasting (paste as copy)
+        keep_clipboard: bool,
+    },
+    /// Choose what to do when a rename would replace `target`
+    ResolveRenameConflict { original: PathBuf, target: PathBuf },
+    /// Drop a git stash
+    DropStash { stash: Stash },
+    /// Discard the working tree changes of files
+    GitDiscard { targets: Vec<PathBuf> },
 }
@@ -1,15 +1,28 @@
 //! Application state management
 
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
-use super::{FocusTarget, ViewMode};
-use crate::action::Clipboard;
-use crate::git::GitStatus;
+use super::disk::DiskUsage;
+use super::message::{Severity, StatusMessage, ERROR_FLASH};
+use super::{FocusTarget, PendingAction, ViewMode};
+use crate::action::{
+    Clipboard, DeleteConfirmPolicy, DeleteSummary, DeleteSummaryJob, Operation, OperationKind,
+    OperationLog, RenameSelect,
+};
+use crate::git::{BlameCache, FileDiff, GitStatus};
+use crate::integrate::content_search::DEFAULT_MAX_RESULTS;
+use crate::integrate::{
+    BookmarkStore, ContentSearch, ForeachRun, RecentStore, UiState, RECENT_CAPACITY,
+};
 
 /// Number of bookmark slots (1-9)
 pub const BOOKMARK_SLOTS: usize = 9;
 
+/// Rows of a CSV/TSV file parsed for its table preview by default
+pub const CSV_MAX_ROWS: usize = 10_000;
+
 /// Sort mode for file entries
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum SortMode {
@@ -20,6 +33,12 @@ pub enum SortMode {
     Size,
     /// Sort by modification date (descending, newest first)
     Date,
+    /// Sort by file extension, then name
+    Extension,
+    /// Sort by file type (regular, executable, symlink, other), then name
+    Type,
+    /// Sort by name with digit runs compared by value (`img2` before `img10`)
+    Natural,
 }
 
 impl SortMode {
@@ -28,7 +47,10 @@ impl SortMode {
         match self {
             SortMode::Name => SortMode::Size,
             SortMode::Size => SortMode::Date,
-            SortMode::Date => SortMode::Name,
+            SortMode::Date => SortMode::Extension,
+            SortMode::Extension => SortMode::Type,
+            SortMode::Type => SortMode::Natural,
+            SortMode::Natural => SortMode::Name,
         }
     }
 
@@ -38,6 +60,9 @@ impl SortMode {
             SortMode::Name => "name",
             SortMode::Size => "size",
             SortMode::Date => "date",
+            SortMode::Extension => "ext",
+            SortMode::Type => "type",
+            SortMode::Natural => "natural",
         }
     }
 
@@ -47,6 +72,261 @@ impl SortMode {
             SortMode::Name => "N",
             SortMode::Size => "S",
             SortMode::Date => "D",
+            SortMode::Extension => "E",
+            SortMode::Type => "T",
+            SortMode::Natural => "V",
+        }
+    }
+
+    /// Check if the order is descending for the given reverse flag
+    ///
+    /// Size and date sort descending by default, the rest ascending.
+    pub fn is_descending(self, reverse: bool) -> bool {
+        let natural = matches!(self, SortMode::Size | SortMode::Date);
+        natural != reverse
+    }
+
+    /// Get the sort mode for a selector key (`n`, `s`, `d`, `e`, `t`, `v`)
+    pub fn from_key(c: char) -> Option<Self> {
+        match c {
+            'n' => Some(SortMode::Name),
+            's' => Some(SortMode::Size),
+            'd' => Some(SortMode::Date),
+            'e' => Some(SortMode::Extension),
+            't' => Some(SortMode::Type),
+            'v' => Some(SortMode::Natural),
+            _ => None,
+        }
+    }
+}
+
+/// How pick mode treats directories
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PickDirs {
+    /// Enter on a directory picks it, like a file (default, --allow-dirs)
+    #[default]
+    Allow,
+    /// Enter on a directory expands it; only files are picked (--expand-dirs)
+    Expand,
+    /// Only directories are shown and picked (--dirs-only)
+    Only,
+}
+
+/// What Enter does on a file outside pick and select modes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FileEnterAction {
+    /// Open the fullscreen preview (default)
+    #[default]
+    Preview,
+    /// Open the file with the system opener
+    Open,
+    /// Mark or unmark the file
+    Select,
+}
+
+impl std::str::FromStr for FileEnterAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "preview" => Ok(Self::Preview),
+            "open" => Ok(Self::Open),
+            "select" | "mark" => Ok(Self::Select),
+            _ => Err(()),
+        }
+    }
+}
+
+/// What clicking a tree entry does besides focusing it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClickAction {
+    /// Only focus the entry (default for single clicks)
+    #[default]
+    Focus,
+    /// Expand or collapse a directory
+    Expand,
+    /// Open a file with the system opener (directories expand)
+    Open,
+    /// Open the fullscreen preview of a file (directories expand)
+    Preview,
+}
+
+impl std::str::FromStr for ClickAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "focus" | "" => Ok(Self::Focus),
+            "expand" => Ok(Self::Expand),
+            "open" => Ok(Self::Open),
+            "preview" => Ok(Self::Preview),
+            _ => Err(()),
+        }
+    }
+}
+
+/// What Collapse (`h`) does on an entry that is not an expanded directory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CollapseFallback {
+    /// Nothing (default)
+    #[default]
+    None,
+    /// Focus the parent entry
+    Parent,
+    /// Focus the parent entry; on a top-level entry move the root up
+    RootUp,
+}
+
+impl std::str::FromStr for CollapseFallback {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" | "" => Ok(Self::None),
+            "parent" => Ok(Self::Parent),
+            "root_up" | "rootup" => Ok(Self::RootUp),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Where tree entry names that do not fit are cut
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NameTruncation {
+    /// Cut the end (default)
+    #[default]
+    Tail,
+    /// Keep the start and the end around `…`
+    Middle,
+    /// Cut the end of the stem but keep the extension
+    Smart,
+}
+
+impl std::str::FromStr for NameTruncation {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "tail" | "" => Ok(Self::Tail),
+            "middle" => Ok(Self::Middle),
+            "smart" => Ok(Self::Smart),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Where the hidden files toggle (`.`) applies
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HiddenScope {
+    /// The whole tree (default)
+    #[default]
+    Global,
+    /// Only the directory at the cursor
+    Directory,
+}
+
+impl std::str::FromStr for HiddenScope {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "global" | "" => Ok(Self::Global),
+            "directory" | "dir" => Ok(Self::Directory),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Connector style for tree indentation guides
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TreeGuideStyle {
+    /// Plain space indentation (default)
+    #[default]
+    Spaces,
+    /// ASCII connectors (`|`, `|-`, `` `- ``)
+    Ascii,
+    /// Unicode box-drawing connectors (`│`, `├─`, `└─`)
+    Unicode,
+}
+
+impl TreeGuideStyle {
+    /// Degrade Unicode connectors to ASCII when the terminal lacks Unicode support
+    pub fn resolve(self, ascii_only: bool) -> Self {
+        if ascii_only && self == Self::Unicode {
+            Self::Ascii
+        } else {
+            self
+        }
+    }
+}
+
+impl std::str::FromStr for TreeGuideStyle {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "spaces" | "none" => Ok(Self::Spaces),
+            "ascii" => Ok(Self::Ascii),
+            "unicode" | "box" => Ok(Self::Unicode),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Glyph set for tree icons
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IconSet {
+    /// Nerd Font glyphs per file type (default)
+    #[default]
+    NerdFont,
+    /// Emoji per file category
+    Emoji,
+    /// Bracketed letters (`[D]`, `[F]`) for terminals without icon fonts
+    Ascii,
+    /// No icons
+    None,
+}
+
+impl IconSet {
+    /// Degrade glyph sets to ASCII when the terminal lacks Unicode support
+    pub fn resolve(self, ascii_only: bool) -> Self {
+        match self {
+            Self::NerdFont | Self::Emoji if ascii_only => Self::Ascii,
+            other => other,
+        }
+    }
+
+    /// Guess from `TERM` and the locale whether the terminal lacks Unicode
+    pub fn unicode_unsupported() -> bool {
+        let term = std::env::var("TERM").unwrap_or_default();
+        if matches!(term.as_str(), "dumb" | "linux" | "vt100" | "vt220") {
+            return true;
+        }
+        // The first locale variable that is set decides, as in setlocale(3)
+        ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .is_some_and(|locale| {
+                let locale = locale.to_lowercase();
+                !locale.contains("utf-8") && !locale.contains("utf8")
+            })
+    }
+}
+
+impl std::str::FromStr for IconSet {
+    type Err = ();
+
+    /// Parse a config value; `auto` picks ASCII on terminals without Unicode
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "nerdfont" | "nerd" => Ok(Self::NerdFont),
+            "emoji" => Ok(Self::Emoji),
+            "ascii" => Ok(Self::Ascii),
+            "none" | "off" => Ok(Self::None),
+            "auto" if Self::unicode_unsupported() => Ok(Self::Ascii),
+            "auto" => Ok(Self::NerdFont),
+            _ => Err(()),
         }
     }
 }
@@ -61,6 +341,49 @@ pub enum PreviewDisplayMode {
     Peek,
 }
 
+/// Where the side preview sits relative to the tree
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PreviewPosition {
+    /// Right of the tree (default)
+    #[default]
+    Right,
+    Left,
+    /// Below the tree
+    Bottom,
+    /// Above the tree
+    Top,
+}
+
+impl PreviewPosition {
+    /// Check if the preview is stacked above or below the tree
+    pub fn is_stacked(self) -> bool {
+        matches!(self, Self::Bottom | Self::Top)
+    }
+}
+
+impl std::str::FromStr for PreviewPosition {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "right" => Ok(Self::Right),
+            "left" => Ok(Self::Left),
+            "bottom" => Ok(Self::Bottom),
+            "top" => Ok(Self::Top),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Smallest preview share of the split (percent)
+pub const PREVIEW_SPLIT_MIN: u16 = 20;
+
+/// Largest preview share of the split (percent)
+pub const PREVIEW_SPLIT_MAX: u16 = 80;
+
+/// Change per preview resize key press (percent)
+pub const PREVIEW_SPLIT_STEP: u16 = 10;
+
 /// AI context history entry (stored in-memory per session)
 #[derive(Debug, Clone)]
 pub struct AiHistoryEntry {
@@ -154,7 +477,7 @@ pub struct AppState {
     /// Current view mode
     pub mode: ViewMode,
     /// Status message
-    pub message: Option<String>,
+    pub message: Option<StatusMessage>,
     /// Preview panel visibility
     pub preview_visible: bool,
     /// Focus target for split view (Tree or Preview)
@@ -165,16 +488,24 @@ pub struct AppState {
     pub should_quit: bool,
     /// Pick mode (--pick option)
     pub pick_mode: bool,
+    /// How pick mode treats directories
+    pub pick_dirs: PickDirs,
     /// Select mode (--select-mode option)
     pub select_mode: bool,
     /// Multi-select enabled in select mode
     pub multi_select: bool,
-    /// Clipboard for copy/cut/paste
+    /// Clipboard for copy/cut/paste (shared by all tabs)
     pub clipboard: Option<Clipboard>,
+    /// Directories changed by the last paste (other tabs showing them are refreshed)
+    pub changed_dirs: Vec<PathBuf>,
     /// Git repository status
     pub git_status: Option<GitStatus>,
+    /// Git status of the inactive roots of a multi-root workspace
+    pub workspace_git: HashMap<PathBuf, Option<GitStatus>>,
     /// Whether to show Nerd Fonts icons
     pub icons_enabled: bool,
+    /// Glyph set used when icons are shown
+    pub icon_set: IconSet,
     /// Directory path to cd on exit (shell integration)
     pub choosedir_path: Option<PathBuf>,
     /// Target path to jump to from fuzzy finder
@@ -184,13 +515,32 @@ pub struct AppState {
     /// Whether file watching is enabled
     pub watch_enabled: bool,
     /// Bookmarks (slots 0-8 for keys 1-9)
-    pub bookmarks: [Option<PathBuf>; BOOKMARK_SLOTS],
+    pub bookmarks: BookmarkStore,
     /// File filter pattern (glob-like, e.g., "*.rs", "test*")
     pub filter_pattern: Option<String>,
+    /// Show only files with a git status (and their ancestor directories)
+    pub git_changed_only: bool,
+    /// File whose preview diffs against a revision, and the revision
+    pub diff_base: Option<(PathBuf, String)>,
+    /// Two marked files being compared (base, shown file); the preview
+    /// diffs them while the shown file stays focused
+    pub compare_files: Option<(PathBuf, PathBuf)>,
+    /// Diff of `compare_files`, computed once when they were compared
+    pub compare_diff: Option<FileDiff>,
     /// Current sort mode
     pub sort_mode: SortMode,
+    /// Reverse the sort direction (directories still come first)
+    pub sort_reverse: bool,
     /// Search match info (current_index, total_count)
     pub search_matches: Option<(usize, usize)>,
+    /// Count typed before a motion (`5` of `5j`)
+    pub count_prefix: Option<usize>,
+    /// Query being typed after `/` in the fullscreen preview
+    pub preview_search: Option<String>,
+    /// Blame of recently previewed files (for the preview's blame gutter)
+    pub blame_cache: BlameCache,
+    /// Match case exactly in search, filter, and fuzzy finder (false = smart case)
+    pub case_sensitive: bool,
     /// Threshold width below which preview auto-hides (default: 50)
     pub auto_hide_preview_threshold: u16,
     /// Preview display mode (Normal or Peek)
@@ -203,6 +553,106 @@ pub struct AppState {
     ai_focus_prev_preview_display_mode: PreviewDisplayMode,
     /// AI context history (most recent first)
     pub ai_history: Vec<AiHistoryEntry>,
+    /// Recently opened files (persisted when loaded from the config dir)
+    pub recent_files: RecentStore,
+    /// Layout adjustments remembered between runs (persisted when loaded from the config dir)
+    pub ui_state: UiState,
+    /// File operations performed this session, oldest first
+    pub operation_log: OperationLog,
+    /// Recursive totals shown in the delete confirmation (None while counting)
+    pub delete_summary: Option<DeleteSummary>,
+    /// Background count of the delete confirmation's totals
+    pub delete_summary_job: Option<DeleteSummaryJob>,
+    /// Recursive totals of the marked paths (refreshed when the selection changes)
+    pub selection_summary: Option<DeleteSummary>,
+    /// Fingerprint of the selection `selection_summary` was computed for
+    pub selection_fingerprint: u64,
+    /// Tree indentation guide style
+    pub tree_guides: TreeGuideStyle,
+    /// Draw tree guides in a dimmed color
+    pub dim_tree_guides: bool,
+    /// Where names too long for the tree are cut
+    pub name_truncation: NameTruncation,
+    /// Status bar template (None = built-in layout)
+    pub status_format: Option<String>,
+    /// Ring the terminal bell when an error is shown
+    pub bell_on_error: bool,
+    /// Invert the status bar briefly when an error is shown
+    pub flash_on_error: bool,
+    /// Show relative line numbers left of the tree
+    pub relative_numbers: bool,
+    /// Bell requested by an error and not yet rung
+    bell_pending: bool,
+    /// End of the status bar flash
+    flash_until: Option<Instant>,
+    /// Soft-wrap long lines in text previews
+    pub preview_wrap: bool,
+    /// Show line numbers in text previews
+    pub preview_line_numbers: bool,
+    /// Frame previews with a border and title
+    pub preview_borders: bool,
+    /// Show the text layer of PDFs instead of rendered pages
+    pub pdf_text_mode: bool,
+    /// Show the XML source of SVGs instead of the rasterized image
+    pub svg_source_mode: bool,
+    /// Show CSV/TSV files as plain text instead of a table
+    pub csv_raw_mode: bool,
+    /// Where the side preview sits
+    pub preview_position: PreviewPosition,
+    /// Preview share of the split in percent (0 = by terminal width)
+    pub preview_split: u16,
+    /// Ask `file --mime-type` how to preview files of unknown type
+    pub use_file_command: bool,
+    /// File and 0-based line its text preview opens centered on (`fv file:line`)
+    pub preview_anchor: Option<(PathBuf, usize)>,
+    /// How long focus rests on an entry before it is previewed (0 = at once)
+    pub preview_debounce: Duration,
+    /// Bound of the image thumbnail cache in bytes (0 = always load full images)
+    pub thumbnail_cache_bytes: u64,
+    /// Rows of a CSV/TSV file parsed for its table preview
+    pub csv_max_rows: usize,
+    /// MIME type `file` reported for the previewed path
+    pub file_mime: Option<(PathBuf, String)>,
+    /// Create relative symlinks by default
+    pub relative_symlinks: bool,
+    /// Refuse delete, rename, move and paste outside the root
+    pub confine_to_root: bool,
+    /// Initial cursor of the rename prompt
+    pub rename_select: RenameSelect,
+    /// What Enter does on a file
+    pub file_enter_action: FileEnterAction,
+    /// What a single click does to the entry under it
+    pub mouse_single_click: ClickAction,
+    /// What a double click does to the entry under it
+    pub mouse_double_click: ClickAction,
+    /// What Collapse does on a collapsed directory or a file
+    pub collapse_fallback: CollapseFallback,
+    /// Program that opens files (empty = system default)
+    pub opener: String,
+    /// `[preview.commands]`: extension or name glob -> colored preview command
+    pub preview_commands: HashMap<String, String>,
+    /// When to confirm deletes
+    pub delete_confirm: DeleteConfirmPolicy,
+    /// Deletes go to the system trash instead of being permanent
+    pub use_trash: bool,
+    /// Ask before a rename replaces an existing file
+    pub confirm_rename_overwrite: bool,
+    /// New file/dir names with `/` create missing directories
+    pub create_parents: bool,
+    /// Where the hidden files toggle applies
+    pub hidden_scope: HiddenScope,
+    /// Running or finished search of file contents
+    pub content_search: Option<ContentSearch>,
+    /// Cap on matching lines per content search
+    pub content_search_max_results: usize,
+    /// Command running (or finished) over marked files
+    pub foreach_run: Option<ForeachRun>,
+    /// `c` copies the path relative to the root
+    pub copy_relative_path: bool,
+    /// List directories above files (off = intermixed by the sort key)
+    pub dirs_first: bool,
+    /// Space on the filesystem holding the root (None = not known)
+    pub disk_usage: Option<DiskUsage>,
 }
 
 impl AppState {
@@ -228,25 +678,88 @@ impl AppState {
             show_hidden: false,
             should_quit: false,
             pick_mode: false,
+            pick_dirs: PickDirs::default(),
             select_mode: false,
             multi_select: false,
             clipboard: None,
             git_status: None, // Lazy-initialized for faster startup
+            workspace_git: HashMap::new(),
             icons_enabled,
+            icon_set: IconSet::default(),
             choosedir_path: None,
             fuzzy_jump_target: None,
             stdin_mode: false,
             watch_enabled: false,
-            bookmarks: [const { None }; BOOKMARK_SLOTS],
+            bookmarks: BookmarkStore::default(),
             filter_pattern: None,
+            git_changed_only: false,
+            diff_base: None,
+            compare_files: None,
+            compare_diff: None,
             sort_mode: SortMode::default(),
+            sort_reverse: false,
             search_matches: None,
+            count_prefix: None,
+            preview_search: None,
+            blame_cache: BlameCache::default(),
+            case_sensitive: false,
             auto_hide_preview_threshold: 50,
             preview_display_mode: PreviewDisplayMode::default(),
             ai_focus: false,
             ai_focus_prev_preview_visible: false,
             ai_focus_prev_preview_display_mode: PreviewDisplayMode::default(),
             ai_history: Vec::new(),
+            recent_files: RecentStore::new(RECENT_CAPACITY),
+            ui_state: UiState::default(),
+            operation_log: OperationLog::default(),
+            delete_summary: None,
+            delete_summary_job: None,
+            selection_summary: None,
+            selection_fingerprint: 0,
+            changed_dirs: Vec::new(),
+            tree_guides: TreeGuideStyle::default(),
+            dim_tree_guides: true,
+            name_truncation: NameTruncation::default(),
+            status_format: None,
+            bell_on_error: false,
+            flash_on_error: false,
+            relative_numbers: false,
+            bell_pending: false,
+            flash_until: None,
+            preview_wrap: false,
+            preview_line_numbers: true,
+            preview_borders: true,
+            pdf_text_mode: false,
+            svg_source_mode: false,
+            csv_raw_mode: false,
+            preview_position: PreviewPosition::default(),
+            preview_split: 0,
+            use_file_command: false,
+            preview_debounce: Duration::ZERO,
+            thumbnail_cache_bytes: 0,
+            csv_max_rows: CSV_MAX_ROWS,
+            preview_anchor: None,
+            file_mime: None,
+            relative_symlinks: true,
+            confine_to_root: false,
+            rename_select: RenameSelect::default(),
+            file_enter_action: FileEnterAction::default(),
+            mouse_single_click: ClickAction::Focus,
+            mouse_double_click: ClickAction::Expand,
+            collapse_fallback: CollapseFallback::default(),
+            opener: String::new(),
+            preview_commands: HashMap::new(),
+            delete_confirm: DeleteConfirmPolicy::default(),
+            use_trash: true,
+            confirm_rename_overwrite: true,
+            create_parents: true,
+            hidden_scope: HiddenScope::default(),
+            content_search: None,
+            content_search_max_results: DEFAULT_MAX_RESULTS,
+            foreach_run: None,
+            copy_relative_path: false,
+            dirs_first: true,
+            disk_usage: None,
         }
     }
 
@@ -257,6 +770,34 @@ impl AppState {
         }
     }
 
+    /// Collect the delete confirmation's totals once counted
+    ///
+    /// A count outliving its confirmation is cancelled. Returns whether the
+    /// totals arrived.
+    pub fn poll_delete_summary(&mut self) -> bool {
+        let Some(job) = &self.delete_summary_job else {
+            return false;
+        };
+        let confirming = matches!(
+            self.mode,
+            ViewMode::Confirm {
+                action: PendingAction::Delete { .. }
+            }
+        );
+        if !confirming {
+            self.delete_summary_job = None;
+            return false;
+        }
+        match job.poll() {
+            Some(summary) => {
+                self.delete_summary = Some(summary);
+                self.delete_summary_job = None;
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Refresh git status (call after file operations)
     pub fn refresh_git_status(&mut self) {
         if let Some(ref mut git) = self.git_status {
@@ -264,6 +805,37 @@ impl AppState {
         }
     }
 
+    /// Make `root` the active root of a multi-root workspace
+    ///
+    /// Operations and the status bar follow the active root. Each root keeps
+    /// its own git status; switching back refreshes the kept one.
+    pub fn set_active_root(&mut self, root: PathBuf) {
+        if root == self.root {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.root, root);
+        let git = self.git_status.take();
+        self.workspace_git.insert(previous, git);
+        self.git_status = match self.workspace_git.remove(&self.root) {
+            Some(Some(mut git)) => {
+                git.refresh();
+                Some(git)
+            }
+            _ => GitStatus::detect(&self.root),
+        };
+    }
+
+    /// Git status of the root containing `path`
+    pub fn git_status_for(&self, path: &Path) -> Option<&GitStatus> {
+        if path.starts_with(&self.root) {
+            return self.git_status.as_ref();
+        }
+        self.workspace_git
+            .iter()
+            .find(|(root, _)| path.starts_with(root))
+            .and_then(|(_, git)| git.as_ref())
+    }
+
     /// Adjust viewport to keep focus visible
     pub fn adjust_viewport(&mut self, visible_height: usize) {
         if self.focus_index < self.viewport_top {
@@ -273,9 +845,65 @@ impl AppState {
         }
     }
 
-    /// Set status message
+    /// Marked paths in path order
+    pub fn sorted_marks(&self) -> Vec<PathBuf> {
+        let mut marks: Vec<PathBuf> = self.selected_paths.iter().cloned().collect();
+        marks.sort();
+        marks
+    }
+
+    /// Append a file operation to the session's operation log
+    pub fn log_operation(
+        &mut self,
+        kind: OperationKind,
+        sources: Vec<PathBuf>,
+        results: Vec<PathBuf>,
+    ) {
+        if sources.is_empty() && results.is_empty() {
+            return;
+        }
+        self.operation_log
+            .record(Operation::new(kind, sources, results));
+    }
+
+    /// Set an info status message (expires after a few seconds)
     pub fn set_message(&mut self, msg: impl Into<String>) {
-        self.message = Some(msg.into());
+        self.message = Some(StatusMessage::new(msg, Severity::Info));
+    }
+
+    /// Set a warning status message
+    pub fn set_warning(&mut self, msg: impl Into<String>) {
+        self.message = Some(StatusMessage::new(msg, Severity::Warning));
+    }
+
+    /// Set an error status message (stays until the next action)
+    ///
+    /// Also requests the bell and the status bar flash when enabled.
+    pub fn set_error(&mut self, msg: impl Into<String>) {
+        let message = StatusMessage::new(msg, Severity::Error);
+        self.bell_pending |= self.bell_on_error;
+        if self.flash_on_error {
+            self.flash_until = Some(message.set_at + ERROR_FLASH);
+        }
+        self.message = Some(message);
+    }
+
+    /// Whether a bell was requested since the last call
+    pub fn take_bell(&mut self) -> bool {
+        std::mem::take(&mut self.bell_pending)
+    }
+
+    /// Whether the status bar is flashing at `now`
+    pub fn is_flashing(&self, now: Instant) -> bool {
+        self.flash_remaining(now)
+            .is_some_and(|left| !left.is_zero())
+    }
+
+    /// Time left of the status bar flash at `now`
+    pub fn flash_remaining(&self, now: Instant) -> Option<Duration> {
+        self.flash_until
+            .map(|until| until.saturating_duration_since(now))
+            .filter(|left| !left.is_zero())
     }
 
     /// Clear status message
@@ -283,6 +911,28 @@ impl AppState {
         self.message = None;
     }
 
+    /// Text of the status message
+    pub fn message_text(&self) -> Option<&str> {
+        self.message.as_ref().map(|m| m.text.as_str())
+    }
+
+    /// Severity of the status message
+    pub fn message_severity(&self) -> Option<Severity> {
+        self.message.as_ref().map(|m| m.severity)
+    }
+
+    /// Drop the status message if its time is up at `now`
+    pub fn expire_message(&mut self, now: Instant) {
+        if self.message.as_ref().is_some_and(|m| m.is_expired(now)) {
+            self.message = None;
+        }
+    }
+
+    /// Time until the status message expires at `now`
+    pub fn message_remaining(&self, now: Instant) -> Option<Duration> {
+        self.message.as_ref().and_then(|m| m.remaining(now))
+    }
+
     /// Toggle focus between Tree and Preview (only effective when preview is visible)
     pub fn toggle_focus(&mut self) {
         if self.preview_visible {
@@ -314,6 +964,24 @@ impl AppState {
         self.preview_visible && width >= self.auto_hide_preview_threshold
     }
 
+    /// Grow or shrink the preview share by one step and return the new share
+    ///
+    /// A ratio still picked by terminal width starts from an even split.
+    pub fn resize_preview(&mut self, grow: bool) -> u16 {
+        let current = if self.preview_split == 0 {
+            50
+        } else {
+            self.preview_split
+        };
+        let next = if grow {
+            current + PREVIEW_SPLIT_STEP
+        } else {
+            current.saturating_sub(PREVIEW_SPLIT_STEP)
+        };
+        self.preview_split = next.clamp(PREVIEW_SPLIT_MIN, PREVIEW_SPLIT_MAX);
+        self.preview_split
+    }
+
     /// Toggle peek mode (status bar preview for narrow terminals)
     pub fn toggle_peek_mode(&mut self) {
         self.preview_display_mode = match self.preview_display_mode {
@@ -4,11 +4,11 @@
 //! focus position, selection, and scroll position.
 
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::tree::TreeNavigator;
 
-use super::{FocusTarget, SortMode, ViewMode, BOOKMARK_SLOTS};
+use super::{AppState, FocusTarget, SortMode, ViewMode, BOOKMARK_SLOTS};
 
 /// Represents a single tab in the application
 pub struct Tab {
@@ -41,11 +41,7 @@ pub struct Tab {
 impl Tab {
     /// Create a new tab for the given directory
     pub fn new(root: PathBuf, show_hidden: bool) -> anyhow::Result<Self> {
-        let name = root
-            .file_name()
-            .map(|n| n.to_string_lossy().to_string())
-            .unwrap_or_else(|| root.display().to_string());
-
+        let name = tab_name(&root);
         let navigator = TreeNavigator::new(&root, show_hidden)?;
 
         Ok(Self {
@@ -64,6 +60,31 @@ impl Tab {
         })
     }
 
+    /// Save the live tree, focus, marks, and mode into this tab
+    pub fn save(&mut self, state: &AppState, navigator: &TreeNavigator) {
+        self.navigator = navigator.clone();
+        self.focus_index = state.focus_index;
+        self.viewport_top = state.viewport_top;
+        self.selected_paths = state.selected_paths.clone();
+        self.mode = state.mode.clone();
+    }
+
+    /// Make this tab live: restore its state and return its navigator
+    pub fn load(&self, state: &mut AppState) -> TreeNavigator {
+        state.root = self.root.clone();
+        state.focus_index = self.focus_index;
+        state.viewport_top = self.viewport_top;
+        state.selected_paths = self.selected_paths.clone();
+        state.mode = self.mode.clone();
+        self.navigator.clone()
+    }
+
+    /// Point the tab at a new root directory (after a reroot)
+    pub fn set_root(&mut self, root: PathBuf) {
+        self.name = tab_name(&root);
+        self.root = root;
+    }
+
     /// Get a short display name for the tab bar
     pub fn short_name(&self, max_len: usize) -> String {
         if self.name.len() <= max_len {
@@ -74,6 +95,13 @@ impl Tab {
     }
 }
 
+/// Display name for a root directory (its last component)
+fn tab_name(root: &Path) -> String {
+    root.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| root.display().to_string())
+}
+
 /// Manager for multiple tabs
 pub struct TabManager {
     /// All open tabs
@@ -153,6 +181,24 @@ impl TabManager {
         }
     }
 
+    /// Reload inactive tabs that show any of the given directories
+    ///
+    /// The active tab's navigator lives in the event loop and is reloaded there.
+    /// Returns the number of tabs refreshed.
+    pub fn refresh_inactive_showing(&mut self, dirs: &[PathBuf]) -> anyhow::Result<usize> {
+        let mut refreshed = 0;
+        for (i, tab) in self.tabs.iter_mut().enumerate() {
+            if i == self.active_index {
+                continue;
+            }
+            if dirs.iter().any(|d| tab.navigator.shows_dir(d)) {
+                tab.navigator.reload()?;
+                refreshed += 1;
+            }
+        }
+        Ok(refreshed)
+    }
+
     /// Get the number of tabs
     pub fn len(&self) -> usize {
         self.tabs.len()
@@ -167,8 +213,16 @@ impl TabManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::Path;
     use tempfile::TempDir;
 
+    fn has_entry(tab: &Tab, path: &Path) -> bool {
+        tab.navigator
+            .visible_entries()
+            .iter()
+            .any(|e| e.path == path)
+    }
+
     fn create_temp_tab() -> (TempDir, Tab) {
         let temp = TempDir::new().unwrap();
         let tab = Tab::new(temp.path().to_path_buf(), false).unwrap();
@@ -353,4 +407,70 @@ mod tests {
         tab.filter_pattern = Some("*.rs".to_string());
         assert_eq!(tab.filter_pattern, Some("*.rs".to_string()));
     }
+
+    #[test]
+    fn test_refresh_inactive_showing() {
+        let temp_a = TempDir::new().unwrap();
+        let temp_b = TempDir::new().unwrap();
+        let mut manager = TabManager::new(temp_a.path().to_path_buf(), false).unwrap();
+        manager.new_tab(temp_b.path().to_path_buf(), false).unwrap();
+        assert_eq!(manager.active_index, 1);
+
+        // A file appears in tab 0's directory (e.g. moved there from tab 1)
+        let new_file = temp_a.path().join("moved.txt");
+        std::fs::write(&new_file, "x").unwrap();
+        assert!(!has_entry(&manager.tabs[0], &new_file));
+
+        let refreshed = manager
+            .refresh_inactive_showing(&[temp_a.path().to_path_buf()])
+            .unwrap();
+        assert_eq!(refreshed, 1);
+        assert!(has_entry(&manager.tabs[0], &new_file));
+    }
+
+    #[test]
+    fn test_refresh_inactive_skips_unrelated_tabs() {
+        let temp_a = TempDir::new().unwrap();
+        let temp_b = TempDir::new().unwrap();
+        let other = TempDir::new().unwrap();
+        let mut manager = TabManager::new(temp_a.path().to_path_buf(), false).unwrap();
+        manager.new_tab(temp_b.path().to_path_buf(), false).unwrap();
+
+        let refreshed = manager
+            .refresh_inactive_showing(&[other.path().to_path_buf()])
+            .unwrap();
+        assert_eq!(refreshed, 0);
+    }
+
+    #[test]
+    fn test_tabs_keep_independent_selections() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        let mut manager = TabManager::new(root.clone(), false).unwrap();
+        let mut state = AppState::new(root.clone());
+        let mut navigator = manager.active().load(&mut state);
+
+        let first = root.join("a.txt");
+        state.selected_paths.insert(first.clone());
+
+        // Open a second tab: it starts without marks
+        manager.active_mut().save(&state, &navigator);
+        manager.new_tab(root.clone(), false).unwrap();
+        navigator = manager.active().load(&mut state);
+        assert!(state.selected_paths.is_empty());
+
+        let second = root.join("b.txt");
+        state.selected_paths.insert(second.clone());
+
+        // Back to the first tab
+        manager.active_mut().save(&state, &navigator);
+        manager.prev_tab();
+        navigator = manager.active().load(&mut state);
+        assert_eq!(state.selected_paths, HashSet::from([first]));
+
+        manager.active_mut().save(&state, &navigator);
+        manager.next_tab();
+        manager.active().load(&mut state);
+        assert_eq!(state.selected_paths, HashSet::from([second]));
+    }
 }
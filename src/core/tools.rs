@@ -0,0 +1,41 @@
+//! Lookup of external executables
+//!
+//! Previews that shell out (poppler-utils, `file`, SVG rasterizers) find
+//! their tool here: the usual install directories first, then `which`.
+//! Callers cache the result.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Directories searched before asking `which`
+const TOOL_DIRS: [&str; 4] = ["/usr/bin", "/bin", "/usr/local/bin", "/opt/homebrew/bin"];
+
+/// Find an executable in the usual places, then via `which`
+pub fn find_executable(name: &str) -> Option<PathBuf> {
+    for dir in TOOL_DIRS {
+        let p = Path::new(dir).join(name);
+        if p.exists() {
+            return Some(p);
+        }
+    }
+    Command::new("which")
+        .arg(name)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8(o.stdout).ok())
+        .map(|s| PathBuf::from(s.trim()))
+        .filter(|p| p.exists())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_executable() {
+        assert!(find_executable("sh").is_some_and(|p| p.ends_with("sh")));
+        assert_eq!(find_executable("fileview-no-such-tool"), None);
+    }
+}
@@ -0,0 +1,179 @@
+//! Git blame functionality
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use super::operations::find_git_executable;
+
+/// Files whose blame is kept by [`BlameCache`]
+const BLAME_CACHE_CAPACITY: usize = 16;
+
+/// Last commit that touched one line of a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameLine {
+    /// Abbreviated commit hash (all zeros for uncommitted lines)
+    pub hash: String,
+    /// Author name
+    pub author: String,
+    /// Author time as a Unix timestamp
+    pub time: i64,
+}
+
+/// `git blame` output for a file, one annotated line per source line
+///
+/// Fails with git's reason for untracked files or paths outside the repo.
+pub fn blame_text(repo_root: &Path, file: &Path) -> anyhow::Result<String> {
+    run_blame(repo_root, file, &["--date=short"])
+}
+
+/// Last commit of every line of a file, in line order
+///
+/// Fails like [`blame_text`].
+pub fn blame_lines(repo_root: &Path, file: &Path) -> anyhow::Result<Vec<BlameLine>> {
+    Ok(parse_porcelain(&run_blame(
+        repo_root,
+        file,
+        &["--porcelain"],
+    )?))
+}
+
+fn run_blame(repo_root: &Path, file: &Path, args: &[&str]) -> anyhow::Result<String> {
+    let git = find_git_executable().ok_or_else(|| anyhow::anyhow!("git not found"))?;
+    let relative = file.strip_prefix(repo_root).unwrap_or(file);
+
+    let output = Command::new(git)
+        .arg("blame")
+        .args(args)
+        .arg("--")
+        .arg(relative)
+        .current_dir(repo_root)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().next().unwrap_or("git blame failed");
+        anyhow::bail!("{}", reason.trim_start_matches("fatal: "));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parse `git blame --porcelain` output
+///
+/// A commit's author fields follow only its first line, so they are
+/// remembered per hash for the later lines of that commit.
+fn parse_porcelain(output: &str) -> Vec<BlameLine> {
+    let mut commits: HashMap<&str, (String, i64)> = HashMap::new();
+    let mut lines = Vec::new();
+    let mut current: Option<&str> = None;
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            if let Some(hash) = current.take() {
+                let (author, time) = commits.get(hash).cloned().unwrap_or_default();
+                lines.push(BlameLine {
+                    hash: hash.chars().take(8).collect(),
+                    author,
+                    time,
+                });
+            }
+            continue;
+        }
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        if key.len() >= 40 && key.chars().all(|c| c.is_ascii_hexdigit()) {
+            current = Some(key);
+            commits.entry(key).or_default();
+        } else if let Some(hash) = current {
+            let commit = commits.entry(hash).or_default();
+            match key {
+                "author" => commit.0 = value.to_string(),
+                "author-time" => commit.1 = value.parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+    }
+    lines
+}
+
+/// Blame of recently blamed files, dropped once the file changes
+#[derive(Debug, Default)]
+pub struct BlameCache {
+    /// Most recently used first
+    entries: Vec<(PathBuf, Option<SystemTime>, Arc<Vec<BlameLine>>)>,
+}
+
+impl BlameCache {
+    /// Cached blame of `path` if the file is unchanged since
+    pub fn get(&mut self, path: &Path) -> Option<Arc<Vec<BlameLine>>> {
+        let mtime = modified(path);
+        let index = self.entries.iter().position(|(p, _, _)| p == path)?;
+        let entry = self.entries.remove(index);
+        if mtime.is_none() || entry.1 != mtime {
+            return None;
+        }
+        let lines = Arc::clone(&entry.2);
+        self.entries.insert(0, entry);
+        Some(lines)
+    }
+
+    /// Remember the blame of `path` at its current modification time
+    pub fn insert(&mut self, path: &Path, lines: Arc<Vec<BlameLine>>) {
+        self.entries.retain(|(p, _, _)| p != path);
+        self.entries
+            .insert(0, (path.to_path_buf(), modified(path), lines));
+        self.entries.truncate(BLAME_CACHE_CAPACITY);
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_porcelain_reuses_commit_headers() {
+        let a = "a".repeat(40);
+        let b = "b".repeat(40);
+        let output = format!(
+            "{a} 1 1 2\nauthor Alice\nauthor-mail <a@x>\nauthor-time 1700000000\n\
+             summary first\nfilename f.txt\n\tone\n\
+             {a} 2 2\n\ttwo\n\
+             {b} 3 3 1\nauthor Bob\nauthor-time 1710000000\nfilename f.txt\n\tthree\n"
+        );
+        let lines = parse_porcelain(&output);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].hash, "aaaaaaaa");
+        assert_eq!(lines[1].author, "Alice");
+        assert_eq!(lines[1].time, 1_700_000_000);
+        assert_eq!(
+            (lines[2].hash.as_str(), lines[2].author.as_str()),
+            ("bbbbbbbb", "Bob")
+        );
+    }
+
+    #[test]
+    fn test_cache_drops_changed_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let file = temp.path().join("f.txt");
+        std::fs::write(&file, "x\n").unwrap();
+        let mut cache = BlameCache::default();
+        assert!(cache.get(&file).is_none());
+
+        cache.insert(&file, Arc::new(Vec::new()));
+        assert!(cache.get(&file).is_some());
+
+        let later = SystemTime::now() + std::time::Duration::from_secs(60);
+        std::fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert!(cache.get(&file).is_none());
+    }
+}
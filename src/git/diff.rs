@@ -50,6 +50,8 @@ pub struct FileDiff {
     pub additions: usize,
     /// Total lines removed
     pub deletions: usize,
+    /// Revision diffed against (None for the working tree or index diff)
+    pub base: Option<String>,
 }
 
 impl FileDiff {
@@ -57,6 +59,23 @@ impl FileDiff {
     pub fn is_empty(&self) -> bool {
         self.lines.is_empty()
     }
+
+    /// The diff as unified patch text (as printed by `git diff`)
+    pub fn to_patch(&self) -> String {
+        let mut text = String::new();
+        for line in &self.lines {
+            let (prefix, content) = match line {
+                DiffLine::Added(s) => ("+", s),
+                DiffLine::Removed(s) => ("-", s),
+                DiffLine::Context(s) => (" ", s),
+                DiffLine::HunkHeader(s) | DiffLine::Other(s) => ("", s),
+            };
+            text.push_str(prefix);
+            text.push_str(content);
+            text.push('\n');
+        }
+        text
+    }
 }
 
 /// Get the diff for a file
@@ -65,12 +84,28 @@ impl FileDiff {
 /// * `repo_root` - The root directory of the git repository
 /// * `file` - The absolute path to the file
 /// * `staged` - If true, show staged changes (--cached), otherwise working tree changes
+/// * `base` - Revision to diff against instead of the index (e.g. `HEAD~1`)
 ///
 /// # Returns
 /// * `Some(FileDiff)` if the file has changes
 /// * `None` if there are no changes or an error occurred
-pub fn get_diff(repo_root: &Path, file: &Path, staged: bool) -> Option<FileDiff> {
-    let git = find_git_executable()?;
+pub fn get_diff(
+    repo_root: &Path,
+    file: &Path,
+    staged: bool,
+    base: Option<&str>,
+) -> Option<FileDiff> {
+    try_get_diff(repo_root, file, staged, base).ok().flatten()
+}
+
+/// Like [`get_diff`], but reports why git failed (e.g. an unknown revision)
+pub fn try_get_diff(
+    repo_root: &Path,
+    file: &Path,
+    staged: bool,
+    base: Option<&str>,
+) -> anyhow::Result<Option<FileDiff>> {
+    let git = find_git_executable().ok_or_else(|| anyhow::anyhow!("git not found"))?;
 
     // Get relative path from repo root
     let relative = file.strip_prefix(repo_root).unwrap_or(file);
@@ -82,20 +117,92 @@ pub fn get_diff(repo_root: &Path, file: &Path, staged: bool) -> Option<FileDiff>
         cmd.arg("--cached");
     }
 
+    if let Some(base) = base {
+        // A leading dash would be parsed as an option
+        if base.is_empty() || base.starts_with('-') {
+            anyhow::bail!("Invalid revision: {}", base);
+        }
+        cmd.arg(base);
+    }
+
     cmd.arg("--").arg(relative).current_dir(repo_root);
 
-    let output = cmd.output().ok()?;
+    let output = cmd.output()?;
 
     if !output.status.success() {
-        return None;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().next().unwrap_or("git diff failed");
+        anyhow::bail!("{}", reason.trim_start_matches("fatal: "));
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     if stdout.trim().is_empty() {
-        return None;
+        return Ok(None);
+    }
+
+    let mut diff = parse_diff(&stdout, file.to_path_buf());
+    diff.base = base.map(str::to_string);
+    Ok(Some(diff))
+}
+
+/// Revisions offered for completion: `HEAD`, `HEAD~1`, then branches and
+/// tags by most recent commit
+pub fn recent_refs(repo_root: &Path, limit: usize) -> Vec<String> {
+    let mut refs = vec!["HEAD".to_string(), "HEAD~1".to_string()];
+    let Some(git) = find_git_executable() else {
+        return refs;
+    };
+    let output = Command::new(git)
+        .args([
+            "for-each-ref",
+            "--sort=-committerdate",
+            &format!("--count={}", limit),
+            "--format=%(refname:short)",
+            "refs/heads",
+            "refs/remotes",
+            "refs/tags",
+        ])
+        .current_dir(repo_root)
+        .output();
+    if let Ok(output) = output {
+        if output.status.success() {
+            let listed = String::from_utf8_lossy(&output.stdout);
+            refs.extend(
+                listed
+                    .lines()
+                    .filter(|name| !name.is_empty() && !name.ends_with("/HEAD"))
+                    .map(str::to_string),
+            );
+        }
     }
+    refs
+}
 
-    Some(parse_diff(&stdout, file.to_path_buf()))
+/// Complete `input` against `refs`
+///
+/// Returns the input extended to the longest common prefix of the matching
+/// refs, along with the matches.
+pub fn complete_ref<'a>(input: &str, refs: &'a [String]) -> (String, Vec<&'a str>) {
+    let matches: Vec<&str> = refs
+        .iter()
+        .map(String::as_str)
+        .filter(|name| name.starts_with(input))
+        .collect();
+    let Some(first) = matches.first() else {
+        return (input.to_string(), matches);
+    };
+    let mut common = matches[1..].iter().fold(first.len(), |len, name| {
+        first
+            .bytes()
+            .zip(name.bytes())
+            .take(len)
+            .take_while(|(a, b)| a == b)
+            .count()
+    });
+    while !first.is_char_boundary(common) {
+        common -= 1;
+    }
+    (first[..common].to_string(), matches)
 }
 
 /// Parse diff output into a FileDiff structure
@@ -154,6 +261,7 @@ fn parse_diff(diff_output: &str, path: PathBuf) -> FileDiff {
         lines,
         additions,
         deletions,
+        base: None,
     }
 }
 
@@ -367,6 +475,86 @@ index 1234567..abcdefg 100644
         }
     }
 
+    #[test]
+    fn test_complete_ref() {
+        let refs: Vec<String> = ["HEAD", "HEAD~1", "main", "feature/login", "feature/logout"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        assert_eq!(
+            complete_ref("ma", &refs),
+            ("main".to_string(), vec!["main"])
+        );
+        let (text, matches) = complete_ref("fe", &refs);
+        assert_eq!(text, "feature/log");
+        assert_eq!(matches, vec!["feature/login", "feature/logout"]);
+        assert_eq!(complete_ref("HE", &refs).0, "HEAD");
+        let (text, matches) = complete_ref("v1", &refs);
+        assert_eq!(text, "v1");
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn test_diff_against_revision_in_real_repo() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        let Some(git) = find_git_executable() else {
+            return;
+        };
+        let run = |args: &[&str]| {
+            Command::new(git)
+                .args(args)
+                .current_dir(root)
+                .output()
+                .is_ok_and(|o| o.status.success())
+        };
+        if !run(&["init"])
+            || !run(&["config", "user.name", "Test User"])
+            || !run(&["config", "user.email", "test@example.com"])
+        {
+            return;
+        }
+
+        let file = root.join("notes.txt");
+        std::fs::write(&file, "one\n").unwrap();
+        assert!(run(&["add", "notes.txt"]));
+        assert!(run(&["commit", "-m", "first"]));
+        std::fs::write(&file, "one\ntwo\n").unwrap();
+        assert!(run(&["commit", "-am", "second"]));
+
+        // Clean working tree: no diff against the index, one line against HEAD~1
+        assert!(get_diff(root, &file, false, None).is_none());
+        let diff = try_get_diff(root, &file, false, Some("HEAD~1"))
+            .unwrap()
+            .unwrap();
+        assert_eq!((diff.additions, diff.deletions), (1, 0));
+        assert_eq!(diff.base.as_deref(), Some("HEAD~1"));
+        assert!(diff.lines.contains(&DiffLine::Added("two".to_string())));
+
+        assert!(try_get_diff(root, &file, false, Some("no-such-rev")).is_err());
+        assert!(try_get_diff(root, &file, false, Some("--output=x")).is_err());
+        assert!(recent_refs(root, 10).len() > 2);
+
+        // A modified file yields a patch git itself would print
+        std::fs::write(&file, "one\n2\n").unwrap();
+        let patch = get_diff(root, &file, false, None).unwrap().to_patch();
+        assert!(patch.starts_with("diff --git a/notes.txt b/notes.txt\n"));
+        assert!(patch.contains("\n--- a/notes.txt\n+++ b/notes.txt\n@@ -1,2 +1,2 @@\n"));
+        assert!(patch.ends_with(" one\n-two\n+2\n"));
+
+        let blame = crate::git::blame_text(root, &file).unwrap();
+        assert_eq!(blame.lines().count(), 2);
+        assert!(blame.lines().next().unwrap().ends_with(") one"));
+        assert!(crate::git::blame_text(root, &root.join("untracked.txt")).is_err());
+
+        let lines = crate::git::blame_lines(root, &file).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].hash.len(), 8);
+        assert_eq!(lines[1].hash, "00000000");
+        assert!(crate::git::blame_lines(root, &root.join("untracked.txt")).is_err());
+    }
+
     #[test]
     fn test_diff_context_line() {
         let diff = r#"@@ -1,3 +1,3 @@
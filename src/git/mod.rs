@@ -1,9 +1,13 @@
 //! Git integration module
 
+mod blame;
 mod diff;
 mod operations;
+mod stash;
 mod status;
 
-pub use diff::{get_diff, DiffLine, FileDiff};
-pub use operations::{is_staged, stage, unstage};
-pub use status::{FileStatus, GitStatus};
+pub use blame::{blame_lines, blame_text, BlameCache, BlameLine};
+pub use diff::{complete_ref, get_diff, recent_refs, try_get_diff, DiffHunk, DiffLine, FileDiff};
+pub use operations::{commit, discard, is_staged, last_commit_message, stage, unstage};
+pub use stash::{apply_stash, drop_stash, list_stashes, parse_stash_list, pop_stash, Stash};
+pub use status::{DirStatus, FileStatus, GitStatus, Head, RepoState};
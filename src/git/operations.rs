@@ -97,6 +97,106 @@ pub fn unstage(repo_root: &Path, file: &Path) -> anyhow::Result<()> {
     }
 }
 
+/// Discard the working tree changes of a file (git restore)
+///
+/// Falls back to `git checkout --` for git versions without `restore`.
+/// Staged changes are kept.
+///
+/// # Arguments
+/// * `repo_root` - The root directory of the git repository
+/// * `file` - The absolute path to the file (or directory) to restore
+///
+/// # Returns
+/// * `Ok(())` if the file was restored
+/// * `Err` with error message if git could not restore it
+pub fn discard(repo_root: &Path, file: &Path) -> anyhow::Result<()> {
+    let git = find_git_executable().ok_or_else(|| anyhow::anyhow!("git not found"))?;
+
+    // Get relative path from repo root
+    let relative = file.strip_prefix(repo_root).unwrap_or(file);
+
+    let output = Command::new(git)
+        .args(["restore", "--"])
+        .arg(relative)
+        .current_dir(repo_root)
+        .output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let fallback = Command::new(git)
+        .args(["checkout", "--"])
+        .arg(relative)
+        .current_dir(repo_root)
+        .output()?;
+    if fallback.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&fallback.stderr);
+        anyhow::bail!("git checkout failed: {}", stderr.trim())
+    }
+}
+
+/// Commit the staged changes (git commit)
+///
+/// # Arguments
+/// * `repo_root` - The root directory of the git repository
+/// * `message` - The commit message (may span several lines)
+/// * `amend` - Replace the last commit instead of adding a new one
+///
+/// # Returns
+/// * `Ok(hash)` with the abbreviated hash of the new commit
+/// * `Err` if the message is empty or git refused the commit
+pub fn commit(repo_root: &Path, message: &str, amend: bool) -> anyhow::Result<String> {
+    let message = message.trim();
+    if message.is_empty() {
+        anyhow::bail!("commit message is empty");
+    }
+    let git = find_git_executable().ok_or_else(|| anyhow::anyhow!("git not found"))?;
+
+    let mut command = Command::new(git);
+    command.arg("commit");
+    if amend {
+        command.arg("--amend");
+    }
+    let output = command
+        .args(["-m", message])
+        .current_dir(repo_root)
+        .output()?;
+    if !output.status.success() {
+        // "nothing to commit" is reported on stdout
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let reason = if stderr.trim().is_empty() {
+            stdout.lines().last().unwrap_or_default().to_string()
+        } else {
+            stderr.trim().to_string()
+        };
+        anyhow::bail!("git commit failed: {}", reason);
+    }
+
+    let output = Command::new(git)
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(repo_root)
+        .output()?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Full message of the last commit (None without commits)
+pub fn last_commit_message(repo_root: &Path) -> Option<String> {
+    let git = find_git_executable()?;
+    let output = Command::new(git)
+        .args(["log", "-1", "--format=%B"])
+        .current_dir(repo_root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let message = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!message.is_empty()).then_some(message)
+}
+
 /// Check if a file is staged (has changes in the index)
 ///
 /// # Arguments
@@ -270,6 +370,34 @@ mod tests {
         assert!(!is_staged(temp.path(), &file));
     }
 
+    #[test]
+    fn test_discard_in_real_repo() {
+        let temp = TempDir::new().unwrap();
+
+        if find_git_executable().is_none() {
+            return;
+        }
+
+        if !init_git_repo(&temp) || !configure_git_user(&temp) {
+            return;
+        }
+
+        let file = temp.path().join("test.txt");
+        fs::write(&file, "original").unwrap();
+        stage(temp.path(), &file).unwrap();
+        commit(temp.path(), "Add test file", false).unwrap();
+
+        fs::write(&file, "changed").unwrap();
+        discard(temp.path(), &file).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "original");
+
+        // Untracked files have nothing to restore
+        let untracked = temp.path().join("new.txt");
+        fs::write(&untracked, "new").unwrap();
+        assert!(discard(temp.path(), &untracked).is_err());
+        assert!(untracked.exists());
+    }
+
     #[test]
     fn test_stage_relative_path() {
         let temp = TempDir::new().unwrap();
@@ -313,4 +441,50 @@ mod tests {
         // Should not be staged
         assert!(!is_staged(temp.path(), &file));
     }
+
+    #[test]
+    fn test_commit_in_real_repo() {
+        let temp = TempDir::new().unwrap();
+
+        if find_git_executable().is_none() {
+            return; // Skip if git not available
+        }
+
+        if !init_git_repo(&temp) || !configure_git_user(&temp) {
+            return; // Skip if git init fails
+        }
+
+        let file = temp.path().join("test.txt");
+        fs::write(&file, "content").unwrap();
+        stage(temp.path(), &file).unwrap();
+
+        // Empty messages are rejected before running git
+        assert!(commit(temp.path(), "  \n", false).is_err());
+
+        let hash = commit(temp.path(), "Add test file\n\nWith a body", false).unwrap();
+        assert!(!hash.is_empty());
+        assert!(!is_staged(temp.path(), &file));
+
+        let log = StdCommand::new("git")
+            .args(["log", "--format=%h %s"])
+            .current_dir(temp.path())
+            .output()
+            .unwrap();
+        let log = String::from_utf8_lossy(&log.stdout);
+        assert_eq!(log.trim(), format!("{} Add test file", hash));
+        assert_eq!(
+            last_commit_message(temp.path()).as_deref(),
+            Some("Add test file\n\nWith a body")
+        );
+
+        // Nothing staged: git refuses
+        assert!(commit(temp.path(), "Empty", false).is_err());
+
+        let amended = commit(temp.path(), "Add the test file", true).unwrap();
+        assert_ne!(amended, hash);
+        assert_eq!(
+            last_commit_message(temp.path()).as_deref(),
+            Some("Add the test file")
+        );
+    }
 }
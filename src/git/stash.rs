@@ -0,0 +1,189 @@
+//! Git stash operations (list, apply, pop, drop)
+//!
+//! Stashes are listed with `git stash list --format` and parsed into
+//! [`Stash`] entries; the other operations address a stash by its index.
+
+use std::path::Path;
+use std::process::Command;
+
+use super::operations::find_git_executable;
+
+/// Format for `git stash list`: reflog selector and subject, NUL separated
+const STASH_LIST_FORMAT: &str = "--format=%gd%x00%gs";
+
+/// A stash entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stash {
+    /// Position in the stash list (`stash@{index}`)
+    pub index: usize,
+    /// Branch the stash was created on (empty if unknown)
+    pub branch: String,
+    /// Stash message (for `WIP` stashes, the commit it was based on)
+    pub message: String,
+}
+
+impl Stash {
+    /// Reflog name of the stash (`stash@{0}`)
+    pub fn name(&self) -> String {
+        format!("stash@{{{}}}", self.index)
+    }
+}
+
+/// Parse the output of `git stash list --format=%gd%x00%gs`
+///
+/// Subjects look like `WIP on main: 1a2b3c4 Commit subject` or
+/// `On main: custom message`; anything else is kept whole as the message.
+pub fn parse_stash_list(output: &str) -> Vec<Stash> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (selector, subject) = line.split_once('\0')?;
+            let index = selector
+                .strip_prefix("stash@{")?
+                .strip_suffix('}')?
+                .parse()
+                .ok()?;
+            let (branch, message) = subject
+                .strip_prefix("WIP on ")
+                .or_else(|| subject.strip_prefix("On "))
+                .and_then(|rest| rest.split_once(": "))
+                .unwrap_or(("", subject));
+            Some(Stash {
+                index,
+                branch: branch.to_string(),
+                message: message.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// List the stashes of a repository (newest first)
+pub fn list_stashes(repo_root: &Path) -> anyhow::Result<Vec<Stash>> {
+    let output = run_git(repo_root, &["stash", "list", STASH_LIST_FORMAT])?;
+    Ok(parse_stash_list(&output))
+}
+
+/// Apply a stash, keeping it in the stash list
+pub fn apply_stash(repo_root: &Path, index: usize) -> anyhow::Result<()> {
+    stash_command(repo_root, "apply", index)
+}
+
+/// Apply a stash and remove it from the stash list
+pub fn pop_stash(repo_root: &Path, index: usize) -> anyhow::Result<()> {
+    stash_command(repo_root, "pop", index)
+}
+
+/// Remove a stash without applying it
+pub fn drop_stash(repo_root: &Path, index: usize) -> anyhow::Result<()> {
+    stash_command(repo_root, "drop", index)
+}
+
+fn stash_command(repo_root: &Path, subcommand: &str, index: usize) -> anyhow::Result<()> {
+    let name = format!("stash@{{{}}}", index);
+    run_git(repo_root, &["stash", subcommand, &name]).map(|_| ())
+}
+
+/// Run git in the repository and return stdout
+fn run_git(repo_root: &Path, args: &[&str]) -> anyhow::Result<String> {
+    let git = find_git_executable().ok_or_else(|| anyhow::anyhow!("git not found"))?;
+
+    let output = Command::new(git)
+        .args(args)
+        .current_dir(repo_root)
+        .output()?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git {} failed: {}", args.join(" "), stderr.trim())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_stash_list() {
+        let output = "stash@{0}\0On feature/login: half-done form\n\
+                      stash@{1}\0WIP on main: 1a2b3c4 Add parser\n\
+                      stash@{2}\0autostash\n";
+        let stashes = parse_stash_list(output);
+
+        assert_eq!(
+            stashes,
+            vec![
+                Stash {
+                    index: 0,
+                    branch: "feature/login".to_string(),
+                    message: "half-done form".to_string(),
+                },
+                Stash {
+                    index: 1,
+                    branch: "main".to_string(),
+                    message: "1a2b3c4 Add parser".to_string(),
+                },
+                Stash {
+                    index: 2,
+                    branch: String::new(),
+                    message: "autostash".to_string(),
+                },
+            ]
+        );
+        assert_eq!(stashes[1].name(), "stash@{1}");
+    }
+
+    #[test]
+    fn test_parse_stash_list_empty_and_malformed() {
+        assert!(parse_stash_list("").is_empty());
+        assert!(parse_stash_list("not a stash line\nstash@{x}\0On main: m\n").is_empty());
+    }
+
+    #[test]
+    fn test_list_stashes_not_in_repo() {
+        let temp = TempDir::new().unwrap();
+        if find_git_executable().is_none() {
+            return;
+        }
+        assert!(list_stashes(temp.path()).is_err());
+    }
+
+    #[test]
+    fn test_stash_roundtrip_in_real_repo() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        if find_git_executable().is_none() {
+            return;
+        }
+        let git = |args: &[&str]| run_git(root, args).is_ok();
+        if !git(&["init"])
+            || !git(&["config", "user.name", "Test User"])
+            || !git(&["config", "user.email", "test@example.com"])
+        {
+            return;
+        }
+
+        let file = root.join("notes.txt");
+        fs::write(&file, "one").unwrap();
+        assert!(git(&["add", "notes.txt"]));
+        assert!(git(&["commit", "-m", "initial"]));
+        assert!(list_stashes(root).unwrap().is_empty());
+
+        fs::write(&file, "two").unwrap();
+        assert!(git(&["stash", "push", "-m", "edit notes"]));
+        let stashes = list_stashes(root).unwrap();
+        assert_eq!(stashes.len(), 1);
+        assert_eq!(stashes[0].message, "edit notes");
+
+        apply_stash(root, 0).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), "two");
+        assert_eq!(list_stashes(root).unwrap().len(), 1);
+
+        drop_stash(root, 0).unwrap();
+        assert!(list_stashes(root).unwrap().is_empty());
+        assert!(pop_stash(root, 0).is_err());
+    }
+}
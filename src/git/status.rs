@@ -66,15 +66,76 @@ pub enum FileStatus {
     Clean,
 }
 
+/// Aggregated git status of a directory's descendants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DirStatus {
+    /// Most severe status among the descendants
+    pub status: FileStatus,
+    /// Number of changed (non-ignored) descendant files
+    pub changed: usize,
+}
+
+/// An operation left in progress in the repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepoState {
+    /// Nothing in progress
+    #[default]
+    Clean,
+    Rebasing,
+    Merging,
+    CherryPicking,
+    Reverting,
+    Bisecting,
+}
+
+impl RepoState {
+    /// Label shown next to the branch (None when nothing is in progress)
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            Self::Clean => None,
+            Self::Rebasing => Some("REBASING"),
+            Self::Merging => Some("MERGING"),
+            Self::CherryPicking => Some("CHERRY-PICKING"),
+            Self::Reverting => Some("REVERTING"),
+            Self::Bisecting => Some("BISECTING"),
+        }
+    }
+}
+
+/// What HEAD points at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Head {
+    /// A branch (which may not have commits yet)
+    Branch(String),
+    /// A commit, by hash
+    Detached(String),
+}
+
+impl Head {
+    /// Branch name, or `detached@<short hash>`
+    pub fn display(&self) -> String {
+        match self {
+            Self::Branch(name) => name.clone(),
+            Self::Detached(hash) => format!("detached@{}", &hash[..hash.len().min(7)]),
+        }
+    }
+}
+
 /// Git repository status information
 #[derive(Debug)]
 pub struct GitStatus {
-    /// Root directory of the git repository
+    /// Root directory of the git repository (the git directory for bare repos)
     repo_root: PathBuf,
+    /// Git directory (`.git`, or `.git/worktrees/<name>` for linked worktrees)
+    git_dir: PathBuf,
+    /// Repository without a working tree
+    bare: bool,
+    /// Operation in progress (rebase, merge, ...)
+    state: RepoState,
     /// Cached file statuses
     statuses: HashMap<PathBuf, FileStatus>,
     /// Directory statuses (propagated from children)
-    dir_statuses: HashMap<PathBuf, FileStatus>,
+    dir_statuses: HashMap<PathBuf, DirStatus>,
     /// Current branch name
     branch: Option<String>,
     /// Files that are staged (have changes in the index)
@@ -83,18 +144,28 @@ pub struct GitStatus {
 
 impl GitStatus {
     /// Detect git repository and load status
+    ///
+    /// Bare repositories are detected too; they have a branch but no file
+    /// statuses.
     pub fn detect(path: &Path) -> Option<Self> {
-        let repo_root = find_git_root(path)?;
-        let branch = get_current_branch(&repo_root);
-        let (statuses, dir_statuses, staged_files) = load_git_status(&repo_root);
-
-        Some(Self {
+        let (git_dir, bare) = find_git_dir(path)?;
+        let repo_root = if bare {
+            git_dir.clone()
+        } else {
+            find_git_root(path)?
+        };
+        let mut status = Self {
             repo_root,
-            statuses,
-            dir_statuses,
-            branch,
-            staged_files,
-        })
+            git_dir,
+            bare,
+            state: RepoState::Clean,
+            statuses: HashMap::new(),
+            dir_statuses: HashMap::new(),
+            branch: None,
+            staged_files: std::collections::HashSet::new(),
+        };
+        status.refresh();
+        Some(status)
     }
 
     /// Get the status of a specific file or directory
@@ -105,8 +176,8 @@ impl GitStatus {
         }
 
         // Then check directory statuses
-        if let Some(status) = self.dir_statuses.get(path) {
-            return *status;
+        if let Some(dir) = self.dir_statuses.get(path) {
+            return dir.status;
         }
 
         // Check if path is relative to repo root
@@ -114,19 +185,62 @@ impl GitStatus {
             if let Some(status) = self.statuses.get(relative) {
                 return *status;
             }
-            if let Some(status) = self.dir_statuses.get(relative) {
-                return *status;
+            if let Some(dir) = self.dir_statuses.get(relative) {
+                return dir.status;
             }
         }
 
         FileStatus::Clean
     }
 
-    /// Get the current branch name
+    /// Get the aggregated status of a directory's descendants
+    ///
+    /// Returns None for directories without changed descendants.
+    pub fn dir_status(&self, path: &Path) -> Option<DirStatus> {
+        let relative = path.strip_prefix(&self.repo_root).unwrap_or(path);
+        self.dir_statuses
+            .get(relative)
+            .or_else(|| self.dir_statuses.get(path))
+            .copied()
+            .filter(|dir| dir.changed > 0)
+    }
+
+    /// Check if a path is changed or contains changed files
+    ///
+    /// Ignored files don't count as changes.
+    pub fn has_changes(&self, path: &Path) -> bool {
+        !matches!(
+            self.get_status(path),
+            FileStatus::Clean | FileStatus::Ignored
+        ) || self.dir_status(path).is_some()
+    }
+
+    /// Absolute paths of all changed files (ignored files excluded)
+    pub fn changed_paths(&self) -> Vec<PathBuf> {
+        self.statuses
+            .iter()
+            .filter(|(_, status)| **status != FileStatus::Ignored)
+            .map(|(path, _)| self.repo_root.join(path))
+            .collect()
+    }
+
+    /// Get the current branch name (`detached@<hash>` on a detached HEAD)
+    ///
+    /// During a rebase this is the branch being rebased.
     pub fn branch(&self) -> Option<&str> {
         self.branch.as_deref()
     }
 
+    /// Operation in progress (rebase, merge, cherry-pick, ...)
+    pub fn state(&self) -> RepoState {
+        self.state
+    }
+
+    /// Check if the repository has no working tree
+    pub fn is_bare(&self) -> bool {
+        self.bare
+    }
+
     /// Get the repository root path
     pub fn repo_root(&self) -> &Path {
         &self.repo_root
@@ -134,7 +248,11 @@ impl GitStatus {
 
     /// Refresh git status (call after file operations)
     pub fn refresh(&mut self) {
-        self.branch = get_current_branch(&self.repo_root);
+        self.state = read_repo_state(&self.git_dir);
+        self.branch = read_branch(&self.git_dir, self.state);
+        if self.bare {
+            return;
+        }
         let (statuses, dir_statuses, staged_files) = load_git_status(&self.repo_root);
         self.statuses = statuses;
         self.dir_statuses = dir_statuses;
@@ -162,7 +280,10 @@ impl GitStatus {
     #[cfg(test)]
     pub fn default_with_root(repo_root: PathBuf) -> Self {
         Self {
+            git_dir: repo_root.join(".git"),
             repo_root,
+            bare: false,
+            state: RepoState::Clean,
             statuses: std::collections::HashMap::new(),
             dir_statuses: std::collections::HashMap::new(),
             branch: None,
@@ -187,34 +308,99 @@ fn find_git_root(path: &Path) -> Option<PathBuf> {
     }
 }
 
-/// Get the current branch name
-fn get_current_branch(repo_root: &Path) -> Option<String> {
+/// Find the git directory for a path, and whether the repository is bare
+fn find_git_dir(path: &Path) -> Option<(PathBuf, bool)> {
     let output = git_command()?
-        .args(["rev-parse", "--abbrev-ref", "HEAD"])
-        .current_dir(repo_root)
+        .args(["rev-parse", "--absolute-git-dir", "--is-bare-repository"])
+        .current_dir(path)
         .output()
         .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let git_dir = PathBuf::from(lines.next()?.trim());
+    let bare = lines.next().is_some_and(|line| line.trim() == "true");
+    Some((git_dir, bare))
+}
 
-    if output.status.success() {
-        let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if branch == "HEAD" {
-            // Detached HEAD state - try to get commit hash
-            let hash_output = git_command()?
-                .args(["rev-parse", "--short", "HEAD"])
-                .current_dir(repo_root)
-                .output()
-                .ok()?;
-            if hash_output.status.success() {
-                return Some(format!(
-                    "detached@{}",
-                    String::from_utf8_lossy(&hash_output.stdout).trim()
-                ));
-            }
-        }
-        Some(branch)
+/// Parse the contents of a `HEAD` file
+fn parse_head(contents: &str) -> Option<Head> {
+    let contents = contents.trim();
+    if let Some(target) = contents.strip_prefix("ref:") {
+        let target = target.trim();
+        let name = target
+            .strip_prefix("refs/heads/")
+            .or_else(|| target.strip_prefix("refs/"))
+            .unwrap_or(target);
+        return Some(Head::Branch(name.to_string()));
+    }
+    let is_hash =
+        matches!(contents.len(), 40 | 64) && contents.chars().all(|c| c.is_ascii_hexdigit());
+    is_hash.then(|| Head::Detached(contents.to_string()))
+}
+
+/// Detect an operation in progress from the marker files in the git directory
+fn read_repo_state(git_dir: &Path) -> RepoState {
+    if git_dir.join("rebase-merge").is_dir() || git_dir.join("rebase-apply").is_dir() {
+        RepoState::Rebasing
+    } else if git_dir.join("MERGE_HEAD").exists() {
+        RepoState::Merging
+    } else if git_dir.join("CHERRY_PICK_HEAD").exists() {
+        RepoState::CherryPicking
+    } else if git_dir.join("REVERT_HEAD").exists() {
+        RepoState::Reverting
+    } else if git_dir.join("BISECT_LOG").exists() {
+        RepoState::Bisecting
     } else {
-        None
+        RepoState::Clean
+    }
+}
+
+/// Branch to show for HEAD
+///
+/// A rebase detaches HEAD, so the branch being rebased is read from the
+/// rebase state instead.
+fn read_branch(git_dir: &Path, state: RepoState) -> Option<String> {
+    if state == RepoState::Rebasing {
+        let rebased = ["rebase-merge", "rebase-apply"]
+            .iter()
+            .find_map(|dir| std::fs::read_to_string(git_dir.join(dir).join("head-name")).ok())
+            .and_then(|name| parse_head(&format!("ref: {}", name.trim())));
+        if let Some(head) = rebased {
+            return Some(head.display());
+        }
+    }
+    let contents = std::fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = match parse_head(&contents) {
+        Some(Head::Branch(name)) if name == REFTABLE_HEAD => query_head(git_dir),
+        head => head,
+    };
+    head.map(|head| head.display())
+}
+
+/// Branch a reftable repository's `HEAD` file names; the real HEAD is in the reftable
+const REFTABLE_HEAD: &str = ".invalid";
+
+/// Ask git what HEAD points at
+fn query_head(git_dir: &Path) -> Option<Head> {
+    let run = |args: &[&str]| {
+        let output = git_command()?
+            .arg("--git-dir")
+            .arg(git_dir)
+            .args(args)
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    if let Some(name) = run(&["symbolic-ref", "--quiet", "--short", "HEAD"]) {
+        return Some(Head::Branch(name));
     }
+    run(&["rev-parse", "--verify", "--quiet", "HEAD"]).and_then(|hash| parse_head(&hash))
 }
 
 /// Load git status for all files in the repository
@@ -222,19 +408,18 @@ fn load_git_status(
     repo_root: &Path,
 ) -> (
     HashMap<PathBuf, FileStatus>,
-    HashMap<PathBuf, FileStatus>,
+    HashMap<PathBuf, DirStatus>,
     std::collections::HashSet<PathBuf>,
 ) {
     use std::collections::HashSet;
 
     let mut statuses = HashMap::new();
-    let mut dir_statuses: HashMap<PathBuf, FileStatus> = HashMap::new();
     let mut staged_files: HashSet<PathBuf> = HashSet::new();
 
     // Get status with porcelain format for machine parsing
     // -uall shows all untracked files (required for per-file status display)
     let Some(mut cmd) = git_command() else {
-        return (statuses, dir_statuses, staged_files);
+        return (statuses, HashMap::new(), staged_files);
     };
     let output = cmd
         .args(["status", "--porcelain=v1", "-uall", "--ignored"])
@@ -243,54 +428,80 @@ fn load_git_status(
 
     let output = match output {
         Ok(o) if o.status.success() => o,
-        _ => return (statuses, dir_statuses, staged_files),
+        _ => return (statuses, HashMap::new(), staged_files),
     };
 
     let stdout = String::from_utf8_lossy(&output.stdout);
 
     for line in stdout.lines() {
-        if line.len() < 4 {
+        let Some(entry) = parse_porcelain_line(line) else {
             continue;
-        }
-
-        let index_status = line.chars().next().unwrap_or(' ');
-        let worktree_status = line.chars().nth(1).unwrap_or(' ');
-        let path_str = &line[3..];
-
-        // Handle renamed files (format: "R  old -> new")
-        let file_path = if path_str.contains(" -> ") {
-            path_str.split(" -> ").last().unwrap_or(path_str)
-        } else {
-            path_str
         };
-
-        let path = PathBuf::from(file_path);
-        let status = parse_status(index_status, worktree_status);
-
-        // Track staged files (index has changes: M, A, D, R, C)
-        if matches!(index_status, 'M' | 'A' | 'D' | 'R' | 'C') {
-            staged_files.insert(path.clone());
+        if entry.staged {
+            staged_files.insert(entry.path.clone());
         }
+        if entry.status != FileStatus::Clean {
+            statuses.insert(entry.path, entry.status);
+        }
+    }
 
-        if status != FileStatus::Clean {
-            statuses.insert(path.clone(), status);
+    let dir_statuses = aggregate_dir_statuses(&statuses);
+    (statuses, dir_statuses, staged_files)
+}
 
-            // Propagate status to parent directories
-            let mut parent = path.parent();
-            while let Some(dir) = parent {
-                if dir.as_os_str().is_empty() {
-                    break;
-                }
-                let current = dir_statuses
-                    .entry(dir.to_path_buf())
-                    .or_insert(FileStatus::Clean);
-                *current = merge_status(*current, status);
-                parent = dir.parent();
+/// Propagate file statuses to every ancestor directory
+///
+/// Each directory gets the most severe status among its descendants and the
+/// number of changed descendant files. Ignored files are not counted.
+fn aggregate_dir_statuses(statuses: &HashMap<PathBuf, FileStatus>) -> HashMap<PathBuf, DirStatus> {
+    let mut dir_statuses: HashMap<PathBuf, DirStatus> = HashMap::new();
+    for (path, &status) in statuses {
+        let mut parent = path.parent();
+        while let Some(dir) = parent {
+            if dir.as_os_str().is_empty() {
+                break;
             }
+            let current = dir_statuses.entry(dir.to_path_buf()).or_default();
+            current.status = merge_status(current.status, status);
+            if status != FileStatus::Ignored {
+                current.changed += 1;
+            }
+            parent = dir.parent();
         }
     }
+    dir_statuses
+}
 
-    (statuses, dir_statuses, staged_files)
+/// One line of `git status --porcelain=v1`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PorcelainEntry {
+    path: PathBuf,
+    status: FileStatus,
+    /// The index has changes to commit
+    staged: bool,
+}
+
+/// Parse a porcelain v1 line such as `UU src/lib.rs` or `R  old -> new`
+fn parse_porcelain_line(line: &str) -> Option<PorcelainEntry> {
+    if line.len() < 4 {
+        return None;
+    }
+    let mut codes = line.chars();
+    let index_status = codes.next()?;
+    let worktree_status = codes.next()?;
+    let path_str = line.get(3..)?;
+
+    // Handle renamed files (format: "R  old -> new")
+    let file_path = path_str.rsplit(" -> ").next().unwrap_or(path_str);
+    let status = parse_status(index_status, worktree_status);
+
+    Some(PorcelainEntry {
+        path: PathBuf::from(file_path),
+        status,
+        // Index has changes (M, A, D, R, C); an unmerged `AA` has nothing staged yet
+        staged: status != FileStatus::Conflict
+            && matches!(index_status, 'M' | 'A' | 'D' | 'R' | 'C'),
+    })
 }
 
 /// Parse git status characters into FileStatus
@@ -364,6 +575,91 @@ fn merge_status(a: FileStatus, b: FileStatus) -> FileStatus {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_head() {
+        assert_eq!(
+            parse_head("ref: refs/heads/feature/login\n"),
+            Some(Head::Branch("feature/login".to_string()))
+        );
+        let hash = "3f786850e387550fdab836ed7e6dc881de23001b";
+        let head = parse_head(&format!("{}\n", hash)).unwrap();
+        assert_eq!(head, Head::Detached(hash.to_string()));
+        assert_eq!(head.display(), "detached@3f78685");
+        assert_eq!(parse_head("garbage"), None);
+    }
+
+    #[test]
+    fn test_rebase_in_progress() {
+        let temp = TempDir::new().unwrap();
+        let git_dir = temp.path();
+        std::fs::write(git_dir.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        assert_eq!(read_repo_state(git_dir), RepoState::Clean);
+        assert_eq!(
+            read_branch(git_dir, RepoState::Clean).as_deref(),
+            Some("main")
+        );
+
+        // A rebase detaches HEAD and records the branch being rebased
+        std::fs::write(
+            git_dir.join("HEAD"),
+            "9fceb02d0ae598e95dc970b74767f19372d61af8\n",
+        )
+        .unwrap();
+        std::fs::create_dir(git_dir.join("rebase-merge")).unwrap();
+        std::fs::write(
+            git_dir.join("rebase-merge").join("head-name"),
+            "refs/heads/topic\n",
+        )
+        .unwrap();
+        let state = read_repo_state(git_dir);
+        assert_eq!(state, RepoState::Rebasing);
+        assert_eq!(state.label(), Some("REBASING"));
+        assert_eq!(read_branch(git_dir, state).as_deref(), Some("topic"));
+
+        std::fs::remove_dir_all(git_dir.join("rebase-merge")).unwrap();
+        std::fs::write(git_dir.join("MERGE_HEAD"), "").unwrap();
+        assert_eq!(read_repo_state(git_dir), RepoState::Merging);
+    }
+
+    #[test]
+    fn test_query_head_in_real_repo() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let git = |args: &[&str]| {
+            git_command().is_some_and(|mut cmd| {
+                cmd.args(args)
+                    .current_dir(root)
+                    .output()
+                    .is_ok_and(|o| o.status.success())
+            })
+        };
+        if !git(&["init", "-q"])
+            || !git(&["symbolic-ref", "HEAD", "refs/heads/trunk"])
+            || !git(&[
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-q",
+                "--allow-empty",
+                "-m",
+                "initial",
+            ])
+        {
+            return;
+        }
+        let git_dir = root.join(".git");
+        assert_eq!(
+            query_head(&git_dir),
+            Some(Head::Branch("trunk".to_string()))
+        );
+
+        assert!(git(&["checkout", "-q", "--detach"]));
+        assert!(matches!(query_head(&git_dir), Some(Head::Detached(_))));
+    }
 
     #[test]
     fn test_parse_status_modified() {
@@ -399,6 +695,24 @@ mod tests {
         assert_eq!(parse_status('A', 'A'), FileStatus::Conflict);
     }
 
+    #[test]
+    fn test_parse_porcelain_conflict_entries() {
+        let entry = parse_porcelain_line("UU src/lib.rs").unwrap();
+        assert_eq!(entry.path, PathBuf::from("src/lib.rs"));
+        assert_eq!(entry.status, FileStatus::Conflict);
+        assert!(!entry.staged);
+
+        // Both added: conflicted, not staged
+        let entry = parse_porcelain_line("AA new.rs").unwrap();
+        assert_eq!(entry.status, FileStatus::Conflict);
+        assert!(!entry.staged);
+
+        let entry = parse_porcelain_line("R  old.rs -> new.rs").unwrap();
+        assert_eq!(entry.path, PathBuf::from("new.rs"));
+        assert!(entry.staged);
+        assert!(parse_porcelain_line("M").is_none());
+    }
+
     #[test]
     fn test_parse_status_renamed() {
         assert_eq!(parse_status('R', ' '), FileStatus::Renamed);
@@ -419,4 +733,58 @@ mod tests {
             FileStatus::Added
         );
     }
+
+    #[test]
+    fn test_aggregate_dir_statuses_picks_most_severe() {
+        let statuses: HashMap<PathBuf, FileStatus> = [
+            ("src/main.rs", FileStatus::Untracked),
+            ("src/app/config.rs", FileStatus::Modified),
+            ("src/app/cache.tmp", FileStatus::Ignored),
+            ("docs/guide.md", FileStatus::Untracked),
+            ("target/out.bin", FileStatus::Ignored),
+        ]
+        .into_iter()
+        .map(|(p, s)| (PathBuf::from(p), s))
+        .collect();
+
+        let dirs = aggregate_dir_statuses(&statuses);
+        let dir = |p: &str| dirs.get(Path::new(p)).copied().filter(|d| d.changed > 0);
+        assert_eq!(
+            dir("src"),
+            Some(DirStatus {
+                status: FileStatus::Modified,
+                changed: 2
+            })
+        );
+        assert_eq!(
+            dir("src/app"),
+            Some(DirStatus {
+                status: FileStatus::Modified,
+                changed: 1
+            })
+        );
+        assert_eq!(
+            dir("docs"),
+            Some(DirStatus {
+                status: FileStatus::Untracked,
+                changed: 1
+            })
+        );
+        // Directories with only ignored files have no summary
+        assert_eq!(dir("target"), None);
+    }
+
+    #[test]
+    fn test_dir_status_lookup_by_absolute_path() {
+        let mut git = GitStatus::default_with_root(PathBuf::from("/repo"));
+        git.statuses
+            .insert(PathBuf::from("src/lib.rs"), FileStatus::Modified);
+        git.dir_statuses = aggregate_dir_statuses(&git.statuses);
+
+        let summary = git.dir_status(Path::new("/repo/src")).unwrap();
+        assert_eq!(summary.status, FileStatus::Modified);
+        assert_eq!(summary.changed, 1);
+        assert_eq!(git.get_status(Path::new("/repo/src")), FileStatus::Modified);
+        assert!(git.dir_status(Path::new("/repo/docs")).is_none());
+    }
 }
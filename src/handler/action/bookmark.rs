@@ -26,8 +26,13 @@ pub fn handle(
             if let Some(path) = focused_path {
                 let idx = (slot - 1) as usize;
                 if idx < BOOKMARK_SLOTS {
-                    state.bookmarks[idx] = Some(path.clone());
-                    state.set_message(format!("Bookmark {}: {}", slot, path.display()));
+                    state.bookmarks.set(idx, path.clone());
+                    match state.bookmarks.save() {
+                        Ok(()) => {
+                            state.set_message(format!("Bookmark {}: {}", slot, path.display()))
+                        }
+                        Err(e) => state.set_error(format!("Failed: save bookmarks - {}", e)),
+                    }
                 }
             }
             state.mode = ViewMode::Browse;
@@ -35,11 +40,11 @@ pub fn handle(
         KeyAction::JumpToBookmark { slot } => {
             let idx = (slot - 1) as usize;
             if idx < BOOKMARK_SLOTS {
-                if let Some(ref path) = state.bookmarks[idx] {
+                if let Some(path) = state.bookmarks.get(idx) {
                     let target = path.clone();
                     // Reveal the path in the tree
                     if let Err(e) = navigator.reveal_path(&target) {
-                        state.set_message(format!("Failed: jump to bookmark - {}", e));
+                        state.set_error(format!("Failed: jump to bookmark - {}", e));
                     } else {
                         // Find and focus the target
                         let entries = navigator.visible_entries();
@@ -47,6 +52,8 @@ pub fn handle(
                             state.focus_index = idx;
                         }
                     }
+                } else if state.bookmarks.unavailable(idx).is_some() {
+                    state.set_message(format!("Bookmark {}: path unavailable", slot));
                 } else {
                     state.set_message(format!("Bookmark {} not set", slot));
                 }
@@ -61,6 +68,7 @@ pub fn handle(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::integrate::BookmarkStore;
     use std::path::Path;
     use tempfile::TempDir;
 
@@ -132,7 +140,7 @@ mod tests {
         .unwrap();
 
         // Verify bookmark is stored
-        assert_eq!(state.bookmarks[0], Some(file_path));
+        assert_eq!(state.bookmarks.get(0), Some(&file_path));
         // Verify mode returns to Browse
         assert_eq!(state.mode, ViewMode::Browse);
     }
@@ -157,8 +165,8 @@ mod tests {
         .unwrap();
 
         // Verify all bookmarks are still None
-        for bookmark in &state.bookmarks {
-            assert!(bookmark.is_none());
+        for idx in 0..BOOKMARK_SLOTS {
+            assert!(state.bookmarks.get(idx).is_none());
         }
         // Mode should still return to Browse
         assert_eq!(state.mode, ViewMode::Browse);
@@ -182,7 +190,7 @@ mod tests {
 
         // Verify error message is set
         assert!(state.message.is_some());
-        assert!(state.message.as_ref().unwrap().contains("not set"));
+        assert!(state.message_text().unwrap().contains("not set"));
         // Mode should return to Browse
         assert_eq!(state.mode, ViewMode::Browse);
     }
@@ -200,7 +208,7 @@ mod tests {
         let focused: Option<PathBuf> = None;
 
         // Pre-set bookmark at slot 3
-        state.bookmarks[2] = Some(file_path.clone());
+        state.bookmarks.set(2, file_path.clone());
 
         // Jump to bookmark
         handle(
@@ -220,6 +228,89 @@ mod tests {
         assert!(target_visible, "Target file should be visible after jump");
     }
 
+    #[test]
+    fn test_bookmark_survives_restart() {
+        let temp = TempDir::new().unwrap();
+        let subdir = temp.path().join("subdir");
+        std::fs::create_dir(&subdir).unwrap();
+        let file_path = subdir.join("target.txt");
+        std::fs::write(&file_path, "content").unwrap();
+        let store = temp.path().join("bookmarks.toml");
+
+        let mut state = create_test_state(temp.path());
+        state.bookmarks = BookmarkStore::load_from(&store);
+        let mut navigator = create_test_navigator(temp.path());
+        handle(
+            KeyAction::SetBookmark { slot: 4 },
+            &mut state,
+            &mut navigator,
+            &Some(file_path.clone()),
+        )
+        .unwrap();
+
+        // A fresh session reads the bookmark back
+        let mut state = create_test_state(temp.path());
+        state.bookmarks = BookmarkStore::load_from(&store);
+        let mut navigator = create_test_navigator(temp.path());
+        handle(
+            KeyAction::JumpToBookmark { slot: 4 },
+            &mut state,
+            &mut navigator,
+            &None,
+        )
+        .unwrap();
+
+        let entries = navigator.visible_entries();
+        assert_eq!(entries[state.focus_index].path, file_path);
+    }
+
+    #[test]
+    fn test_set_bookmark_reports_save_failure() {
+        let temp = TempDir::new().unwrap();
+        let blocker = temp.path().join("blocker");
+        std::fs::write(&blocker, "").unwrap();
+
+        let mut state = create_test_state(temp.path());
+        // The parent of the bookmarks file is a regular file
+        state.bookmarks = BookmarkStore::load_from(&blocker.join("bookmarks.toml"));
+        let mut navigator = create_test_navigator(temp.path());
+        handle(
+            KeyAction::SetBookmark { slot: 1 },
+            &mut state,
+            &mut navigator,
+            &Some(temp.path().to_path_buf()),
+        )
+        .unwrap();
+
+        // The bookmark still works for this session
+        assert_eq!(state.bookmarks.get(0), Some(&temp.path().to_path_buf()));
+        assert!(state
+            .message_text()
+            .unwrap()
+            .starts_with("Failed: save bookmarks"));
+    }
+
+    #[test]
+    fn test_jump_to_unavailable_bookmark_says_so() {
+        let temp = TempDir::new().unwrap();
+        let gone = temp.path().join("gone");
+        let store = temp.path().join("bookmarks.toml");
+        std::fs::write(&store, format!("\"2\" = {:?}\n", gone)).unwrap();
+
+        let mut state = create_test_state(temp.path());
+        state.bookmarks = BookmarkStore::load_from(&store);
+        let mut navigator = create_test_navigator(temp.path());
+        handle(
+            KeyAction::JumpToBookmark { slot: 2 },
+            &mut state,
+            &mut navigator,
+            &None,
+        )
+        .unwrap();
+
+        assert_eq!(state.message_text(), Some("Bookmark 2: path unavailable"));
+    }
+
     #[test]
     fn test_set_bookmark_without_focus_does_nothing() {
         let temp = TempDir::new().unwrap();
@@ -236,7 +327,7 @@ mod tests {
         .unwrap();
 
         // Bookmark should not be set
-        assert!(state.bookmarks[0].is_none());
+        assert!(state.bookmarks.get(0).is_none());
         // Mode should still return to Browse
         assert_eq!(state.mode, ViewMode::Browse);
     }
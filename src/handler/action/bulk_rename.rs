@@ -5,10 +5,12 @@
 use std::path::PathBuf;
 
 use crate::action::file as file_ops;
+use crate::action::OperationKind;
 use crate::core::{AppState, ViewMode};
 use crate::handler::key::KeyAction;
 use crate::tree::TreeNavigator;
 
+use super::file_ops::ensure_within_root;
 use super::reload_tree;
 
 /// Handle bulk rename actions
@@ -83,15 +85,24 @@ fn execute_bulk_rename(
     }
 
     let targets: Vec<PathBuf> = state.selected_paths.iter().cloned().collect();
+    if !ensure_within_root(state, &targets) {
+        state.mode = ViewMode::Browse;
+        return Ok(());
+    }
     let mut success_count = 0;
     let mut fail_count = 0;
+    let (mut sources, mut results) = (Vec::new(), Vec::new());
 
     for target in &targets {
         if let Some(filename) = target.file_name().and_then(|n| n.to_str()) {
             if let Some(new_name) = apply_pattern(filename, from_pattern, to_pattern) {
                 if new_name != filename {
                     match file_ops::rename(target, &new_name) {
-                        Ok(_) => success_count += 1,
+                        Ok(renamed) => {
+                            success_count += 1;
+                            sources.push(target.clone());
+                            results.push(renamed);
+                        }
                         Err(_) => fail_count += 1,
                     }
                 }
@@ -99,6 +110,8 @@ fn execute_bulk_rename(
         }
     }
 
+    state.log_operation(OperationKind::Rename, sources, results);
+
     // Clear selection and return to browse mode
     state.selected_paths.clear();
     state.mode = ViewMode::Browse;
@@ -118,7 +131,11 @@ fn execute_bulk_rename(
     } else {
         format!("Renamed {} files, {} failed", success_count, fail_count)
     };
-    state.set_message(message);
+    if fail_count == 0 {
+        state.set_message(message);
+    } else {
+        state.set_warning(message);
+    }
 
     Ok(())
 }
@@ -1,12 +1,56 @@
 //! Custom command execution
 //!
-//! Executes user-defined shell commands with placeholder expansion.
+//! Executes user-defined shell commands with placeholder expansion, and
+//! shell commands typed at the `!` prompt once per marked file.
 
+use std::io;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
 
 use crate::app::CommandsConfig;
-use crate::core::AppState;
+use crate::core::{AppState, InputPurpose, ViewMode};
+use crate::handler::key::KeyAction;
+use crate::integrate::ForeachRun;
+
+/// Program that opens a file with its default application
+fn system_opener() -> (&'static str, &'static [&'static str]) {
+    if cfg!(target_os = "macos") {
+        ("open", &[])
+    } else if cfg!(target_os = "windows") {
+        ("cmd", &["/C", "start", ""])
+    } else {
+        ("xdg-open", &[])
+    }
+}
+
+/// Open a file with `opener` (program and arguments), or the system's default
+/// application when `opener` is empty
+///
+/// The opener runs detached; it is reaped in the background.
+pub fn open_with(path: &Path, opener: &str) -> io::Result<()> {
+    let mut words = opener.split_whitespace();
+    let mut command = match words.next() {
+        Some(program) => {
+            let mut command = Command::new(program);
+            command.args(words);
+            command
+        }
+        None => {
+            let (program, args) = system_opener();
+            let mut command = Command::new(program);
+            command.args(args);
+            command
+        }
+    };
+    let mut child = command
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
 
 /// Result of command execution
 #[derive(Debug)]
@@ -80,6 +124,57 @@ pub fn execute_command(
     }
 }
 
+/// Handle the command-over-marked-files prompt and its output popup
+pub fn handle_foreach(action: KeyAction, state: &mut AppState, focused_path: &Option<PathBuf>) {
+    match action {
+        KeyAction::StartForeachCommand => {
+            let mut targets: Vec<PathBuf> = state.selected_paths.iter().cloned().collect();
+            targets.sort();
+            if targets.is_empty() {
+                targets.extend(focused_path.clone());
+            }
+            if targets.is_empty() {
+                state.set_warning("No files to run a command on");
+                return;
+            }
+            state.mode = ViewMode::Input {
+                purpose: InputPurpose::ForeachCommand { targets },
+                buffer: String::new(),
+                cursor: 0,
+            };
+        }
+        KeyAction::CommandOutputUp => {
+            if let ViewMode::CommandOutput { scroll } = &mut state.mode {
+                *scroll = scroll.saturating_sub(1);
+            }
+        }
+        KeyAction::CommandOutputDown => {
+            let lines = state.foreach_run.as_ref().map_or(0, ForeachRun::line_count);
+            if let ViewMode::CommandOutput { scroll } = &mut state.mode {
+                *scroll = (*scroll + 1).min(lines.saturating_sub(1));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Run `template` once per target on a worker thread and show its output
+///
+/// `{path}` in the template is replaced with each target (appended if
+/// absent). Commands run in the root one after another; a failure on one
+/// file does not stop the rest. A run still going is cancelled.
+pub fn execute_command_foreach(state: &mut AppState, template: &str, targets: Vec<PathBuf>) {
+    let template = template.trim();
+    if template.is_empty() {
+        state.mode = ViewMode::Browse;
+        return;
+    }
+    let run = ForeachRun::start(template, targets, &state.root);
+    state.set_message(run.status());
+    state.foreach_run = Some(run);
+    state.mode = ViewMode::CommandOutput { scroll: 0 };
+}
+
 /// Execute a command and wait for it to complete (for TUI restoration)
 ///
 /// This spawns the command in a way that allows it to take over the terminal,
@@ -3,17 +3,25 @@
 //! Handles TogglePreview, OpenPreview, Refresh, ToggleHidden, ShowHelp, etc.
 
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
-use crate::core::{AppState, ViewMode};
+use crate::core::{AppState, HiddenScope, SortMode, ViewMode};
 use crate::handler::key::KeyAction;
-use crate::integrate::{build_context_pack, exit_code, ContextPackPreset, PickResult};
+use crate::integrate::{
+    build_concat, build_context_pack, exit_code, ConcatOptions, ContextPackPreset, PickResult,
+};
 use crate::render::{
-    ArchivePreview, CustomPreview, DiffPreview, HexPreview, PdfPreview, Picker, TextPreview,
+    find_svg_rasterizer, is_csv_file, is_svg_file, ArchivePreview, CsvPreview, CustomPreview,
+    DiffPreview, HexPreview, PdfView, Picker, TextPreview,
 };
 use crate::tree::TreeNavigator;
 
-use super::{get_filename_str, reload_tree, ActionContext, ActionResult};
+use super::recent::record_opened;
+use super::{
+    displayed_entries, get_filename_str, get_target_directory, reload_tree, ActionContext,
+    ActionResult,
+};
 
 /// Handle app control actions (Quit, QuitAndCd, Cancel)
 pub fn handle_app_control(
@@ -49,7 +57,20 @@ pub fn handle_app_control(
                 }
             }
             _ => {
+                if let ViewMode::LiveFilter {
+                    origin: Some(origin),
+                    ..
+                } = &state.mode
+                {
+                    // Back to where narrowing started in the full tree
+                    state.fuzzy_jump_target = Some(origin.clone());
+                }
                 state.mode = ViewMode::Browse;
+                state.search_matches = None;
+                state.preview_search = None;
+                // Dropping the handles stops a running content search or command
+                state.content_search = None;
+                state.foreach_run = None;
                 state.clear_message();
                 Ok(ActionResult::Continue)
             }
@@ -70,31 +91,56 @@ pub fn handle(
             reload_tree(navigator, state)?;
             state.set_message("Refreshed");
         }
-        KeyAction::ToggleHidden => {
+        // With hidden files shown everywhere, `.` still turns them off
+        KeyAction::ToggleHidden
+            if state.hidden_scope == HiddenScope::Global || state.show_hidden =>
+        {
             state.show_hidden = !state.show_hidden;
             navigator.set_show_hidden(state.show_hidden)?;
+            refocus(state, navigator, focused_path);
             state.set_message(if state.show_hidden {
                 "Showing hidden files"
             } else {
                 "Hiding hidden files"
             });
         }
+        KeyAction::ToggleHidden | KeyAction::ToggleHiddenHere => {
+            let dir = get_target_directory(focused_path.as_ref(), &state.root);
+            if state.show_hidden {
+                state.set_message("Hidden files are shown everywhere");
+            } else {
+                let shown = navigator.toggle_hidden_in(&dir)?;
+                refocus(state, navigator, focused_path);
+                let name = get_filename_str(Some(&dir));
+                state.set_message(if shown {
+                    format!("Showing hidden files in {}", name)
+                } else {
+                    format!("Hiding hidden files in {}", name)
+                });
+            }
+        }
+        KeyAction::CopyPath if state.copy_relative_path => {
+            copy_relative_path(state, focused_path);
+        }
         KeyAction::CopyPath => {
             if let Some(path) = focused_path {
                 match arboard::Clipboard::new()
                     .and_then(|mut cb| cb.set_text(path.display().to_string()))
                 {
                     Ok(_) => state.set_message("Copied path"),
-                    Err(_) => state.set_message("Failed: copy path"),
+                    Err(_) => state.set_error("Failed: copy path"),
                 }
             }
         }
+        KeyAction::CopyRelativePath => {
+            copy_relative_path(state, focused_path);
+        }
         KeyAction::CopyFilename => {
             if let Some(path) = focused_path {
                 let name = get_filename_str(Some(path));
                 match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(name)) {
                     Ok(_) => state.set_message("Copied filename"),
-                    Err(_) => state.set_message("Failed: copy filename"),
+                    Err(_) => state.set_error("Failed: copy filename"),
                 }
             }
         }
@@ -105,7 +151,7 @@ pub fn handle(
             } else {
                 match copy_file_contents_to_clipboard(&paths) {
                     Ok(count) => state.set_message(format!("Copied {} file(s) content", count)),
-                    Err(e) => state.set_message(format!("Failed: {}", e)),
+                    Err(e) => state.set_error(format!("Failed: {}", e)),
                 }
             }
         }
@@ -124,7 +170,7 @@ pub fn handle(
                         );
                         state.set_message(format!("Copied {} file(s) (Claude format)", count))
                     }
-                    Err(e) => state.set_message(format!("Failed: {}", e)),
+                    Err(e) => state.set_error(format!("Failed: {}", e)),
                 }
             }
         }
@@ -133,6 +179,9 @@ pub fn handle(
                 state.mode = ViewMode::Browse;
             } else {
                 state.mode = ViewMode::Preview { scroll: 0 };
+                if let Some(path) = focused_path {
+                    record_opened(state, path);
+                }
             }
         }
         KeyAction::ToggleQuickPreview => {
@@ -142,6 +191,12 @@ pub fn handle(
                 state.reset_focus();
             }
         }
+        KeyAction::ResizePreview { grow } => {
+            let pct = state.resize_preview(grow);
+            state.ui_state.layout.preview_split = Some(pct);
+            let _ = state.ui_state.save();
+            state.set_message(format!("Preview: {}%", pct));
+        }
         KeyAction::ShowHelp => {
             state.mode = ViewMode::Help;
         }
@@ -155,10 +210,22 @@ pub fn handle(
             state.set_focus(crate::core::FocusTarget::Preview);
         }
         KeyAction::CycleSort => {
-            let new_mode = state.sort_mode.next();
-            state.sort_mode = new_mode;
-            navigator.set_sort_mode(new_mode)?;
-            state.set_message(format!("Sort: {}", new_mode.display_name()));
+            apply_sort(state, navigator, state.sort_mode.next(), state.sort_reverse)?;
+        }
+        KeyAction::StartSortSelect => {
+            state.mode = ViewMode::SortSelect;
+        }
+        KeyAction::SetSort { mode } => {
+            apply_sort(state, navigator, mode, state.sort_reverse)?;
+        }
+        KeyAction::ToggleSortReverse => {
+            apply_sort(state, navigator, state.sort_mode, !state.sort_reverse)?;
+        }
+        KeyAction::ToggleDirsFirst => {
+            state.dirs_first = !state.dirs_first;
+            state.mode = ViewMode::Browse;
+            navigator.set_dirs_first(state.dirs_first)?;
+            state.set_message(format!("Directories first: {}", on_off(state.dirs_first)));
         }
         KeyAction::TogglePeekMode => {
             state.toggle_peek_mode();
@@ -168,6 +235,33 @@ pub fn handle(
             };
             state.set_message(format!("Preview: {}", mode_name));
         }
+        KeyAction::TogglePreviewWrap => {
+            state.preview_wrap = !state.preview_wrap;
+            state.set_message(format!("Wrap: {}", on_off(state.preview_wrap)));
+        }
+        KeyAction::TogglePreviewLineNumbers => {
+            state.preview_line_numbers = !state.preview_line_numbers;
+            state.set_message(format!(
+                "Line numbers: {}",
+                on_off(state.preview_line_numbers)
+            ));
+        }
+        KeyAction::TogglePreviewBorders => {
+            state.preview_borders = !state.preview_borders;
+            state.ui_state.layout.preview_borders = Some(state.preview_borders);
+            let _ = state.ui_state.save();
+            state.set_message(format!(
+                "Preview borders: {}",
+                on_off(state.preview_borders)
+            ));
+        }
+        KeyAction::ToggleRelativeNumbers => {
+            state.relative_numbers = !state.relative_numbers;
+            state.set_message(format!(
+                "Relative numbers: {}",
+                on_off(state.relative_numbers)
+            ));
+        }
         KeyAction::CopyCompact => {
             let paths = get_copy_target_paths(state, focused_path);
             if paths.is_empty() {
@@ -183,8 +277,48 @@ pub fn handle(
                         );
                         state.set_message(format!("Copied {} file(s) (compact)", count))
                     }
-                    Err(e) => state.set_message(format!("Failed: {}", e)),
+                    Err(e) => state.set_error(format!("Failed: {}", e)),
+                }
+            }
+        }
+        KeyAction::CopyConcat => {
+            let dir = get_target_directory(focused_path.as_ref(), &state.root);
+            match build_concat(&dir, &ConcatOptions::default()) {
+                Ok(concat) if concat.files == 0 => state.set_message("No text files"),
+                Ok(concat) => {
+                    let omitted = if concat.omitted > 0 {
+                        format!(", {} omitted", concat.omitted)
+                    } else {
+                        String::new()
+                    };
+                    let message = match copy_text_to_clipboard(&concat.text) {
+                        Ok(_) => format!("Copied {} file(s){}", concat.files, omitted),
+                        Err(_) => {
+                            // No clipboard (e.g. over SSH): leave the dump in a
+                            // fresh temp file (a fixed name could be planted)
+                            let mut file = tempfile::Builder::new()
+                                .prefix("fileview-concat-")
+                                .suffix(".txt")
+                                .tempfile()?;
+                            file.write_all(concat.text.as_bytes())?;
+                            let (_, out) = file.keep()?;
+                            format!(
+                                "Wrote {} file(s){} to {}",
+                                concat.files,
+                                omitted,
+                                out.display()
+                            )
+                        }
+                    };
+                    state.push_ai_history_with_meta(
+                        format!("Concat ({} file(s))", concat.files),
+                        concat.text,
+                        Some("concat".to_string()),
+                        concat.files,
+                    );
+                    state.set_message(message);
                 }
+                Err(e) => state.set_error(format!("Failed: {}", e)),
             }
         }
         KeyAction::CopyContextPack => {
@@ -204,9 +338,9 @@ pub fn handle(
                         );
                         state.set_message("Copied context pack");
                     }
-                    Err(e) => state.set_message(format!("Failed: {}", e)),
+                    Err(e) => state.set_error(format!("Failed: {}", e)),
                 },
-                Err(e) => state.set_message(format!("Failed: {}", e)),
+                Err(e) => state.set_error(format!("Failed: {}", e)),
             }
         }
         KeyAction::CopyContextPackReview => {
@@ -226,9 +360,9 @@ pub fn handle(
                         );
                         state.set_message("Copied review context pack");
                     }
-                    Err(e) => state.set_message(format!("Failed: {}", e)),
+                    Err(e) => state.set_error(format!("Failed: {}", e)),
                 },
-                Err(e) => state.set_message(format!("Failed: {}", e)),
+                Err(e) => state.set_error(format!("Failed: {}", e)),
             }
         }
         KeyAction::ToggleAiFocus => {
@@ -266,7 +400,7 @@ pub fn handle(
                             state.mode = ViewMode::Browse;
                             state.set_message(format!("Copied history: {}", entry.title));
                         }
-                        Err(e) => state.set_message(format!("Failed: {}", e)),
+                        Err(e) => state.set_error(format!("Failed: {}", e)),
                     }
                 }
             }
@@ -277,11 +411,14 @@ pub fn handle(
 }
 
 /// Handle preview scroll actions for text, hex, archive, diff, and custom previews
+///
+/// `text_preview` is the file's text preview or the text layer of a PDF page.
 pub fn handle_preview_scroll(
     action: KeyAction,
     state: &mut AppState,
-    text_preview: &mut Option<TextPreview>,
+    mut text_preview: Option<&mut TextPreview>,
     hex_preview: &mut Option<HexPreview>,
+    csv_preview: &mut Option<CsvPreview>,
     archive_preview: &mut Option<ArchivePreview>,
     diff_preview: &mut Option<DiffPreview>,
     custom_preview: &mut Option<CustomPreview>,
@@ -294,6 +431,9 @@ pub fn handle_preview_scroll(
             if let Some(ref mut hp) = hex_preview {
                 hp.scroll = hp.scroll.saturating_sub(1);
             }
+            if let Some(ref mut cp) = csv_preview {
+                cp.scroll = cp.scroll.saturating_sub(1);
+            }
             if let Some(ref mut ap) = archive_preview {
                 ap.scroll = ap.scroll.saturating_sub(1);
             }
@@ -309,13 +449,17 @@ pub fn handle_preview_scroll(
         }
         KeyAction::PreviewScrollDown => {
             if let Some(ref mut tp) = text_preview {
-                let max_scroll = tp.lines.len().saturating_sub(1);
+                let max_scroll = tp.line_count().saturating_sub(1);
                 tp.scroll = (tp.scroll + 1).min(max_scroll);
             }
             if let Some(ref mut hp) = hex_preview {
                 let max_scroll = hp.line_count().saturating_sub(1);
                 hp.scroll = (hp.scroll + 1).min(max_scroll);
             }
+            if let Some(ref mut cp) = csv_preview {
+                let max_scroll = cp.line_count().saturating_sub(1);
+                cp.scroll = (cp.scroll + 1).min(max_scroll);
+            }
             if let Some(ref mut ap) = archive_preview {
                 let max_scroll = ap.line_count().saturating_sub(1);
                 ap.scroll = (ap.scroll + 1).min(max_scroll);
@@ -339,6 +483,9 @@ pub fn handle_preview_scroll(
             if let Some(ref mut hp) = hex_preview {
                 hp.scroll = hp.scroll.saturating_sub(20);
             }
+            if let Some(ref mut cp) = csv_preview {
+                cp.scroll = cp.scroll.saturating_sub(20);
+            }
             if let Some(ref mut ap) = archive_preview {
                 ap.scroll = ap.scroll.saturating_sub(20);
             }
@@ -354,13 +501,17 @@ pub fn handle_preview_scroll(
         }
         KeyAction::PreviewPageDown => {
             if let Some(ref mut tp) = text_preview {
-                let max_scroll = tp.lines.len().saturating_sub(1);
+                let max_scroll = tp.line_count().saturating_sub(1);
                 tp.scroll = (tp.scroll + 20).min(max_scroll);
             }
             if let Some(ref mut hp) = hex_preview {
                 let max_scroll = hp.line_count().saturating_sub(1);
                 hp.scroll = (hp.scroll + 20).min(max_scroll);
             }
+            if let Some(ref mut cp) = csv_preview {
+                let max_scroll = cp.line_count().saturating_sub(1);
+                cp.scroll = (cp.scroll + 20).min(max_scroll);
+            }
             if let Some(ref mut ap) = archive_preview {
                 let max_scroll = ap.line_count().saturating_sub(1);
                 ap.scroll = (ap.scroll + 20).min(max_scroll);
@@ -384,6 +535,9 @@ pub fn handle_preview_scroll(
             if let Some(ref mut hp) = hex_preview {
                 hp.scroll = 0;
             }
+            if let Some(ref mut cp) = csv_preview {
+                cp.scroll = 0;
+            }
             if let Some(ref mut ap) = archive_preview {
                 ap.scroll = 0;
             }
@@ -399,11 +553,14 @@ pub fn handle_preview_scroll(
         }
         KeyAction::PreviewToBottom => {
             if let Some(ref mut tp) = text_preview {
-                tp.scroll = tp.lines.len().saturating_sub(1);
+                tp.scroll = tp.line_count().saturating_sub(1);
             }
             if let Some(ref mut hp) = hex_preview {
                 hp.scroll = hp.line_count().saturating_sub(1);
             }
+            if let Some(ref mut cp) = csv_preview {
+                cp.scroll = cp.line_count().saturating_sub(1);
+            }
             if let Some(ref mut ap) = archive_preview {
                 ap.scroll = ap.line_count().saturating_sub(1);
             }
@@ -416,9 +573,11 @@ pub fn handle_preview_scroll(
             if let ViewMode::Preview { scroll } = &mut state.mode {
                 // Set to max for ViewMode as well
                 if let Some(ref tp) = text_preview {
-                    *scroll = tp.lines.len().saturating_sub(1);
+                    *scroll = tp.line_count().saturating_sub(1);
                 } else if let Some(ref hp) = hex_preview {
                     *scroll = hp.line_count().saturating_sub(1);
+                } else if let Some(ref cp) = csv_preview {
+                    *scroll = cp.line_count().saturating_sub(1);
                 } else if let Some(ref ap) = archive_preview {
                     *scroll = ap.line_count().saturating_sub(1);
                 } else if let Some(ref dp) = diff_preview {
@@ -428,43 +587,267 @@ pub fn handle_preview_scroll(
                 }
             }
         }
+        KeyAction::PreviewScrollLeft | KeyAction::PreviewScrollRight => {
+            if let Some(ref mut cp) = csv_preview {
+                let delta = if matches!(action, KeyAction::PreviewScrollLeft) {
+                    -1
+                } else {
+                    1
+                };
+                cp.scroll_columns(delta);
+            }
+        }
+        KeyAction::PreviewNextConflict | KeyAction::PreviewPrevConflict => {
+            let forward = matches!(action, KeyAction::PreviewNextConflict);
+            let Some(tp) = text_preview else {
+                return;
+            };
+            let Some(idx) = tp.conflict_from_scroll(forward) else {
+                state.set_message("No merge conflicts in this file");
+                return;
+            };
+            tp.scroll = tp.conflicts[idx].start;
+            if let ViewMode::Preview { scroll } = &mut state.mode {
+                *scroll = tp.scroll;
+            }
+            state.set_message(format!("Conflict {} of {}", idx + 1, tp.conflicts.len()));
+        }
         _ => {}
     }
 }
 
-/// Handle PDF page navigation actions
+/// Handle search in the fullscreen text preview
+///
+/// `/` opens the query prompt, Enter searches and scrolls to the first match
+/// from the top line, and `n`/`N` step through the matches. Esc closes the
+/// prompt, then clears the search. Returns false for an Enter or Esc with
+/// no prompt or search to act on.
+pub fn handle_preview_search(
+    action: &KeyAction,
+    state: &mut AppState,
+    text_preview: Option<&mut TextPreview>,
+) -> bool {
+    match action {
+        KeyAction::StartPreviewSearch => {
+            if text_preview.is_some() {
+                state.preview_search = Some(String::new());
+            } else {
+                state.set_message("Search works in text previews");
+            }
+            true
+        }
+        KeyAction::ConfirmInput { value } => {
+            if state.preview_search.take().is_none() {
+                return false;
+            }
+            if let Some(tp) = text_preview {
+                if value.is_empty() {
+                    tp.clear_search();
+                } else {
+                    tp.search(value, state.case_sensitive);
+                    if tp.is_searching() && !has_match_ahead(tp) {
+                        // Jump once the rest of the search turns one up
+                        tp.search_jump_pending = true;
+                        state.set_message(format!("Searching for '{}'...", value));
+                    } else {
+                        jump_to_match(state, tp, true, true);
+                    }
+                }
+            }
+            true
+        }
+        KeyAction::Cancel => {
+            if state.preview_search.take().is_some() {
+                return true;
+            }
+            match text_preview {
+                Some(tp) if tp.search_query.is_some() => {
+                    tp.clear_search();
+                    state.clear_message();
+                    true
+                }
+                _ => false,
+            }
+        }
+        KeyAction::PreviewSearchNext | KeyAction::PreviewSearchPrev => {
+            let forward = matches!(action, KeyAction::PreviewSearchNext);
+            match text_preview {
+                Some(tp) if tp.search_query.is_some() => jump_to_match(state, tp, forward, false),
+                _ => state.set_message("No search (press / to search)"),
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Continue searching the text preview from the event loop
+///
+/// A search confirmed before it found a match below the scroll position
+/// jumps as soon as one turns up (or reports no matches once done).
+pub fn poll_preview_search(state: &mut AppState, tp: &mut TextPreview) {
+    if !tp.is_searching() {
+        return;
+    }
+    tp.continue_search();
+    if tp.search_jump_pending && (has_match_ahead(tp) || !tp.is_searching()) {
+        tp.search_jump_pending = false;
+        jump_to_match(state, tp, true, true);
+    }
+}
+
+/// Whether a match was found at or below the scroll position
+fn has_match_ahead(tp: &TextPreview) -> bool {
+    tp.match_lines.last().is_some_and(|&line| line >= tp.scroll)
+}
+
+/// Scroll a searched text preview to the next (or previous) match
+fn jump_to_match(state: &mut AppState, tp: &mut TextPreview, forward: bool, from_scroll: bool) {
+    let Some(idx) = tp.match_from_scroll(forward, from_scroll) else {
+        let query = tp.search_query.as_deref().unwrap_or_default();
+        state.set_message(format!("No matches for '{}'", query));
+        return;
+    };
+    tp.scroll = tp.match_lines[idx];
+    if let ViewMode::Preview { scroll } = &mut state.mode {
+        *scroll = tp.scroll;
+    }
+    state.set_message(format!("Match {} of {}", idx + 1, tp.match_lines.len()));
+}
+
+/// Handle PDF page navigation and the image/text toggle
+///
+/// The toggle also switches an SVG between its rendered image and its source,
+/// and a CSV/TSV file between its table and its raw text.
 pub fn handle_pdf_navigation(
     action: KeyAction,
     state: &mut AppState,
-    pdf_preview: &mut Option<PdfPreview>,
+    focused_path: &Option<PathBuf>,
+    pdf_preview: &mut Option<PdfView>,
     image_picker: &mut Option<Picker>,
 ) {
-    let Some(ref mut pdf) = pdf_preview else {
+    let on_svg = focused_path.as_deref().is_some_and(is_svg_file);
+    if matches!(action, KeyAction::TogglePdfText) && pdf_preview.is_none() && on_svg {
+        // Without a rasterizer the source is all there is
+        if image_picker.is_none() || find_svg_rasterizer().is_none() {
+            state.set_error(
+                "Failed: SVG preview requires rsvg-convert or resvg and an image-capable terminal",
+            );
+            return;
+        }
+        state.svg_source_mode = !state.svg_source_mode;
+        state.set_message(format!(
+            "SVG source view: {}",
+            on_off(state.svg_source_mode)
+        ));
         return;
-    };
-    let Some(ref mut picker) = image_picker else {
+    }
+    let on_csv = focused_path.as_deref().is_some_and(is_csv_file);
+    if matches!(action, KeyAction::TogglePdfText) && pdf_preview.is_none() && on_csv {
+        state.csv_raw_mode = !state.csv_raw_mode;
+        state.set_message(format!("CSV raw text view: {}", on_off(state.csv_raw_mode)));
+        return;
+    }
+    if matches!(action, KeyAction::TogglePdfText) {
+        if let Some(ref mut pdf) = pdf_preview {
+            if let Err(e) = pdf.toggle_text(image_picker.as_mut()) {
+                state.set_error(format!("Failed: {}", e));
+                return;
+            }
+            state.pdf_text_mode = pdf.is_text();
+        } else {
+            state.pdf_text_mode = !state.pdf_text_mode;
+        }
+        state.set_message(format!("PDF text view: {}", on_off(state.pdf_text_mode)));
+        return;
+    }
+
+    let Some(ref mut pdf) = pdf_preview else {
         return;
     };
 
     match action {
-        KeyAction::PdfPrevPage => {
-            if pdf.current_page > 1 {
-                if let Err(e) = pdf.prev_page(picker) {
-                    state.set_message(format!("Failed: prev page - {}", e));
-                }
+        KeyAction::PdfPrevPage if pdf.current_page() > 1 => {
+            if let Err(e) = pdf.prev_page(image_picker.as_mut()) {
+                state.set_error(format!("Failed: prev page - {}", e));
             }
         }
-        KeyAction::PdfNextPage => {
-            if pdf.current_page < pdf.total_pages {
-                if let Err(e) = pdf.next_page(picker) {
-                    state.set_message(format!("Failed: next page - {}", e));
-                }
+        KeyAction::PdfNextPage if pdf.current_page() < pdf.total_pages() => {
+            if let Err(e) = pdf.next_page(image_picker.as_mut()) {
+                state.set_error(format!("Failed: next page - {}", e));
             }
         }
         _ => {}
     }
 }
 
+/// "on"/"off" for a toggle's status message
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+/// Keep the cursor on `focused_path` after the tree changed
+///
+/// Falls back to the closest shown ancestor when the entry was hidden.
+fn refocus(state: &mut AppState, navigator: &TreeNavigator, focused_path: &Option<PathBuf>) {
+    let Some(focused) = focused_path else {
+        return;
+    };
+    let entries = displayed_entries(navigator, state);
+    if let Some(index) = focused
+        .ancestors()
+        .find_map(|path| entries.iter().position(|e| e.path == path))
+    {
+        state.focus_index = index;
+    }
+}
+
+/// Apply a sort mode and direction to the state and the tree
+fn apply_sort(
+    state: &mut AppState,
+    navigator: &mut TreeNavigator,
+    mode: SortMode,
+    reverse: bool,
+) -> anyhow::Result<()> {
+    state.sort_mode = mode;
+    state.sort_reverse = reverse;
+    state.mode = ViewMode::Browse;
+    navigator.set_sort(mode, reverse)?;
+    let direction = if mode.is_descending(reverse) {
+        "desc"
+    } else {
+        "asc"
+    };
+    state.set_message(format!("Sort: {} {}", mode.display_name(), direction));
+    Ok(())
+}
+
+/// Path of `path` relative to `root` (`.` for the root itself)
+///
+/// Paths outside the root are returned unchanged.
+pub(super) fn relative_to_root(path: &Path, root: &Path) -> PathBuf {
+    match path.strip_prefix(root) {
+        Ok(rel) if rel.as_os_str().is_empty() => PathBuf::from("."),
+        Ok(rel) => rel.to_path_buf(),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Copy the focused path relative to the root
+fn copy_relative_path(state: &mut AppState, focused_path: &Option<PathBuf>) {
+    if let Some(path) = focused_path {
+        let text = relative_to_root(path, &state.root).display().to_string();
+        match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(text)) {
+            Ok(_) => state.set_message("Copied relative path"),
+            Err(_) => state.set_error("Failed: copy path"),
+        }
+    }
+}
+
 /// Get paths to copy (selected paths or focused path)
 fn get_copy_target_paths(state: &AppState, focused_path: &Option<PathBuf>) -> Vec<PathBuf> {
     if state.selected_paths.is_empty() {
@@ -573,7 +956,7 @@ fn copy_file_contents_compact(paths: &[PathBuf]) -> anyhow::Result<(String, usiz
     Ok((text, count))
 }
 
-fn copy_text_to_clipboard(text: &str) -> anyhow::Result<()> {
+pub(super) fn copy_text_to_clipboard(text: &str) -> anyhow::Result<()> {
     arboard::Clipboard::new()
         .and_then(|mut cb| cb.set_text(text.to_string()))
         .map_err(|e| anyhow::anyhow!("Clipboard error: {}", e))?;
@@ -603,7 +986,10 @@ pub fn handle_pick_select(
 
             // Output paths
             let result = PickResult::Selected(paths);
-            return Ok(ActionResult::Quit(result.output(context.output_format)?));
+            return Ok(ActionResult::Quit(result.output_with_options(
+                context.output_format,
+                context.output_options,
+            )?));
         }
     }
     Ok(ActionResult::Continue)
@@ -638,7 +1024,10 @@ pub fn handle_select_confirm(
 
             // Output paths
             let result = PickResult::Selected(paths);
-            return Ok(ActionResult::Quit(result.output(context.output_format)?));
+            return Ok(ActionResult::Quit(result.output_with_options(
+                context.output_format,
+                context.output_options,
+            )?));
         }
     }
     Ok(ActionResult::Continue)
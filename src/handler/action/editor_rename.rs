@@ -0,0 +1,297 @@
+//! External editor rename
+//!
+//! Writes file names to a temporary file, opens `$EDITOR`, and applies the
+//! edited lines as renames (similar to `vidir` / `qmv`).
+//!
+//! Line N of the edited file always maps to the Nth original path. Clearing a
+//! line marks the file for deletion (which still goes through the normal
+//! delete confirmation). Adding or removing lines aborts the whole operation.
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::action::file as file_ops;
+use crate::action::OperationKind;
+use crate::core::AppState;
+use crate::tree::TreeNavigator;
+
+use super::file_ops::{ensure_within_root, open_delete_confirm};
+use super::{reload_tree, EntrySnapshot};
+
+/// Renames and deletions computed from an edited name list
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EditorRenamePlan {
+    /// (original path, new file name)
+    pub renames: Vec<(PathBuf, String)>,
+    /// Paths whose line was cleared
+    pub deletions: Vec<PathBuf>,
+}
+
+impl EditorRenamePlan {
+    /// Check if the plan has nothing to do
+    pub fn is_empty(&self) -> bool {
+        self.renames.is_empty() && self.deletions.is_empty()
+    }
+}
+
+/// Collect the paths to edit: selected entries (in tree order) or all visible entries
+///
+/// The tree root itself is never included.
+pub fn collect_targets(state: &AppState, entries: &[EntrySnapshot]) -> Vec<PathBuf> {
+    entries
+        .iter()
+        .filter(|e| e.depth > 0)
+        .filter(|e| state.selected_paths.is_empty() || state.selected_paths.contains(&e.path))
+        .map(|e| e.path.clone())
+        .collect()
+}
+
+/// Compute the rename plan from the original paths and the edited buffer
+///
+/// Returns an error (and changes nothing) if the line count differs, a name
+/// contains a path separator, or two entries would end up with the same path.
+pub fn plan_editor_rename(originals: &[PathBuf], edited: &str) -> anyhow::Result<EditorRenamePlan> {
+    let lines: Vec<&str> = edited.lines().collect();
+    if lines.len() != originals.len() {
+        anyhow::bail!(
+            "Line count changed ({} -> {}), nothing renamed",
+            originals.len(),
+            lines.len()
+        );
+    }
+
+    let mut plan = EditorRenamePlan::default();
+    let mut final_paths: HashSet<PathBuf> = HashSet::new();
+
+    for (i, (original, line)) in originals.iter().zip(lines).enumerate() {
+        let new_name = line.trim();
+        if new_name.is_empty() {
+            plan.deletions.push(original.clone());
+            continue;
+        }
+        if new_name.contains('/') || new_name.contains('\\') || new_name == "." || new_name == ".."
+        {
+            anyhow::bail!("Invalid name on line {}: {}", i + 1, new_name);
+        }
+
+        let final_path = original
+            .parent()
+            .map(|p| p.join(new_name))
+            .unwrap_or_else(|| PathBuf::from(new_name));
+        if !final_paths.insert(final_path.clone()) {
+            anyhow::bail!("Duplicate name on line {}: {}", i + 1, new_name);
+        }
+
+        let current_name = original
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if new_name != current_name {
+            plan.renames.push((original.clone(), new_name.to_string()));
+        }
+    }
+
+    Ok(plan)
+}
+
+/// Write names to a temporary file, open the user's editor, and return the edited content
+///
+/// The caller is responsible for suspending the TUI while this runs.
+pub fn edit_names_in_editor(names: &[String]) -> anyhow::Result<String> {
+    let mut file = tempfile::Builder::new()
+        .prefix("fileview-rename-")
+        .suffix(".txt")
+        .tempfile()?;
+    for name in names {
+        writeln!(file, "{}", name)?;
+    }
+    file.flush()?;
+
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| {
+            if cfg!(target_os = "windows") {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        });
+
+    let path = file.path().display().to_string();
+    let status = if cfg!(target_os = "windows") {
+        Command::new("cmd")
+            .args(["/C", &format!("{} \"{}\"", editor, path)])
+            .status()?
+    } else {
+        Command::new("sh")
+            .args([
+                "-c",
+                &format!("{} '{}'", editor, path.replace('\'', "'\\''")),
+            ])
+            .status()?
+    };
+    if !status.success() {
+        anyhow::bail!("Editor exited with: {:?}", status.code());
+    }
+
+    Ok(std::fs::read_to_string(file.path())?)
+}
+
+/// Apply a rename plan
+///
+/// Renames are done in two phases (via temporary names) so that swaps such as
+/// `a -> b, b -> a` work. Deletions are handed to the delete confirmation dialog.
+pub fn apply_plan(
+    plan: EditorRenamePlan,
+    state: &mut AppState,
+    navigator: &mut TreeNavigator,
+) -> anyhow::Result<()> {
+    if plan.is_empty() {
+        state.set_message("No changes");
+        return Ok(());
+    }
+
+    let touched: Vec<PathBuf> = plan
+        .renames
+        .iter()
+        .flat_map(|(original, new_name)| [original.clone(), original.with_file_name(new_name)])
+        .collect();
+    if !ensure_within_root(state, &touched) {
+        return Ok(());
+    }
+
+    let sources: HashSet<&Path> = plan.renames.iter().map(|(p, _)| p.as_path()).collect();
+    let mut staged: Vec<(PathBuf, PathBuf, String)> = Vec::new();
+    let mut fail_count = 0;
+
+    for (i, (original, new_name)) in plan.renames.iter().enumerate() {
+        let target = original.with_file_name(new_name);
+        if target.exists() && !sources.contains(target.as_path()) {
+            fail_count += 1;
+            continue;
+        }
+        let temp_name = format!(".fileview-rename-{}-{}", std::process::id(), i);
+        match file_ops::rename(original, &temp_name) {
+            Ok(temp_path) => staged.push((temp_path, original.clone(), new_name.clone())),
+            Err(_) => fail_count += 1,
+        }
+    }
+
+    let mut success_count = 0;
+    let (mut sources, mut results) = (Vec::new(), Vec::new());
+    for (temp_path, original, new_name) in &staged {
+        if let Ok(renamed) = file_ops::rename(temp_path, new_name) {
+            success_count += 1;
+            sources.push(original.clone());
+            results.push(renamed);
+        } else {
+            // Put the file back under its original name
+            let original_name = original
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let _ = file_ops::rename(temp_path, &original_name);
+            fail_count += 1;
+        }
+    }
+
+    state.log_operation(OperationKind::Rename, sources, results);
+    state.selected_paths.clear();
+    reload_tree(navigator, state)?;
+
+    let message = if fail_count == 0 {
+        format!("Renamed {} file(s)", success_count)
+    } else {
+        format!("Renamed {} file(s), {} failed", success_count, fail_count)
+    };
+    if fail_count == 0 {
+        state.set_message(message);
+    } else {
+        state.set_warning(message);
+    }
+
+    if !plan.deletions.is_empty() {
+        open_delete_confirm(state, plan.deletions);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn paths(names: &[&str]) -> Vec<PathBuf> {
+        names
+            .iter()
+            .map(|n| PathBuf::from("/dir").join(n))
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_unchanged_is_empty() {
+        let originals = paths(&["a.txt", "b.txt"]);
+        let plan = plan_editor_rename(&originals, "a.txt\nb.txt\n").unwrap();
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn test_plan_maps_lines_to_originals() {
+        let originals = paths(&["a.txt", "b.txt", "c.txt"]);
+        let plan = plan_editor_rename(&originals, "a.txt\nbee.md\nc.txt\n").unwrap();
+        assert_eq!(
+            plan.renames,
+            vec![(PathBuf::from("/dir/b.txt"), "bee.md".to_string())]
+        );
+        assert!(plan.deletions.is_empty());
+    }
+
+    #[test]
+    fn test_plan_cleared_line_is_deletion() {
+        let originals = paths(&["a.txt", "b.txt"]);
+        let plan = plan_editor_rename(&originals, "a.txt\n\n").unwrap();
+        assert!(plan.renames.is_empty());
+        assert_eq!(plan.deletions, vec![PathBuf::from("/dir/b.txt")]);
+    }
+
+    #[test]
+    fn test_plan_line_count_mismatch_aborts() {
+        let originals = paths(&["a.txt", "b.txt"]);
+        assert!(plan_editor_rename(&originals, "a.txt\n").is_err());
+        assert!(plan_editor_rename(&originals, "a.txt\nb.txt\nc.txt\n").is_err());
+    }
+
+    #[test]
+    fn test_plan_rejects_separator_and_duplicates() {
+        let originals = paths(&["a.txt", "b.txt"]);
+        assert!(plan_editor_rename(&originals, "sub/a.txt\nb.txt\n").is_err());
+        assert!(plan_editor_rename(&originals, "same\nsame\n").is_err());
+    }
+
+    #[test]
+    fn test_plan_swap_is_allowed() {
+        let originals = paths(&["a.txt", "b.txt"]);
+        let plan = plan_editor_rename(&originals, "b.txt\na.txt\n").unwrap();
+        assert_eq!(plan.renames.len(), 2);
+    }
+
+    #[test]
+    fn test_apply_plan_swaps_files() {
+        let temp = TempDir::new().unwrap();
+        let a = temp.path().join("a.txt");
+        let b = temp.path().join("b.txt");
+        std::fs::write(&a, "A").unwrap();
+        std::fs::write(&b, "B").unwrap();
+
+        let mut state = AppState::new(temp.path().to_path_buf());
+        let mut navigator = TreeNavigator::new(temp.path(), false).unwrap();
+        let plan = plan_editor_rename(&[a.clone(), b.clone()], "b.txt\na.txt\n").unwrap();
+        apply_plan(plan, &mut state, &mut navigator).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&a).unwrap(), "B");
+        assert_eq!(std::fs::read_to_string(&b).unwrap(), "A");
+    }
+}
@@ -1,16 +1,133 @@
 //! File operation action handlers
 //!
-//! Handles Paste, ConfirmDelete, ExecuteDelete, StartRename, StartNewFile, StartNewDir
+//! Handles Paste, PasteCopy, ConfirmDelete, ConfirmDeletePermanent, ExecuteDelete, StartRename, StartNewFile, StartNewDir,
+//! CreateSymlink, CreateHardlink
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::action::{file as file_ops, ClipboardContent};
+use crate::action::file::get_unique_path;
+use crate::action::{
+    file as file_ops, is_within_root, summarize_delete, ClipboardContent, ConfirmDeleteMode,
+    ConflictResolution, DeleteSummaryJob, LinkKind, OperationKind, PastePlan, DELETE_SUMMARY_LIMIT,
+};
 use crate::core::{AppState, InputPurpose, PendingAction, ViewMode};
 use crate::handler::key::{create_delete_targets, KeyAction};
 use crate::tree::TreeNavigator;
 
 use super::{get_filename_str, get_target_directory, reload_tree, EntrySnapshot};
 
+/// Check that `paths` stay inside the root when `confine_to_root` is set
+///
+/// On a violation an error names the first offending path and the caller
+/// must leave the operation undone.
+pub(super) fn ensure_within_root(state: &mut AppState, paths: &[PathBuf]) -> bool {
+    if !state.confine_to_root {
+        return true;
+    }
+    match paths.iter().find(|path| !is_within_root(path, &state.root)) {
+        Some(path) => {
+            state.set_error(format!("Refused: {} is outside the root", path.display()));
+            false
+        }
+        None => true,
+    }
+}
+
+/// Show the delete confirmation with recursive totals of `targets`
+pub(super) fn open_delete_confirm(state: &mut AppState, targets: Vec<PathBuf>) {
+    let permanent = !state.use_trash;
+    show_delete_confirm(state, targets, permanent);
+}
+
+/// Ask before deleting `targets`; their totals are counted in the background
+fn show_delete_confirm(state: &mut AppState, targets: Vec<PathBuf>, permanent: bool) {
+    state.delete_summary = None;
+    state.delete_summary_job = Some(DeleteSummaryJob::start(&targets, DELETE_SUMMARY_LIMIT));
+    state.mode = ViewMode::Confirm {
+        action: PendingAction::Delete { targets, permanent },
+    };
+}
+
+/// Move `targets` to the trash (or delete them for good) and refresh the tree
+fn delete_targets(
+    targets: &[PathBuf],
+    permanent: bool,
+    state: &mut AppState,
+    navigator: &mut TreeNavigator,
+) -> anyhow::Result<()> {
+    if !ensure_within_root(state, targets) {
+        state.mode = ViewMode::Browse;
+        return Ok(());
+    }
+    let mut deleted = Vec::new();
+    let result = targets.iter().try_for_each(|path| {
+        if permanent {
+            file_ops::delete(path)?;
+        } else {
+            file_ops::trash(path)?;
+        }
+        deleted.push(path.clone());
+        anyhow::Ok(())
+    });
+    let kind = if permanent {
+        OperationKind::Delete
+    } else {
+        OperationKind::Trash
+    };
+    state.log_operation(kind, deleted, vec![]);
+    result?;
+    if permanent {
+        state.set_message(format!("Deleted {} item(s) permanently", targets.len()));
+    } else {
+        state.set_message(format!("Moved {} item(s) to trash", targets.len()));
+    }
+    state.selected_paths.clear();
+    state.mode = ViewMode::Browse;
+    reload_tree(navigator, state)
+}
+
+/// Rename `original` to `name` in its directory, replacing any entry there
+pub(super) fn rename_entry(
+    state: &mut AppState,
+    navigator: &mut TreeNavigator,
+    original: PathBuf,
+    name: &str,
+) -> anyhow::Result<()> {
+    let renamed = file_ops::rename(&original, name)?;
+    state.log_operation(OperationKind::Rename, vec![original], vec![renamed]);
+    reload_tree(navigator, state)?;
+    state.set_message(format!("Renamed: {}", name));
+    Ok(())
+}
+
+/// Prompt for the name of a link to `source` in `dest_dir`
+///
+/// The name defaults to the source's basename (made unique if taken). The
+/// link is created in `dest_dir` even if the focus moves while prompting.
+pub(super) fn open_link_prompt(
+    state: &mut AppState,
+    source: PathBuf,
+    pending: Vec<PathBuf>,
+    kind: LinkKind,
+    dest_dir: &Path,
+) {
+    let name = source
+        .file_name()
+        .map(|name| get_unique_path(&dest_dir.join(name)))
+        .and_then(|path| path.file_name().map(|n| n.to_string_lossy().into_owned()))
+        .unwrap_or_default();
+    state.mode = ViewMode::Input {
+        purpose: InputPurpose::CreateLink {
+            source,
+            pending,
+            kind,
+            dest_dir: dest_dir.to_path_buf(),
+        },
+        cursor: name.len(),
+        buffer: name,
+    };
+}
+
 /// Handle file operations
 pub fn handle(
     action: KeyAction,
@@ -20,63 +137,117 @@ pub fn handle(
     _entries: &[EntrySnapshot],
 ) -> anyhow::Result<()> {
     match action {
-        KeyAction::Paste => {
-            if let Some(ref mut clipboard) = state.clipboard {
-                if let Some(content) = clipboard.take() {
-                    let dest = get_target_directory(focused_path.as_ref(), &state.root);
-
-                    match content {
-                        ClipboardContent::Copy(paths) => {
-                            for src in &paths {
-                                file_ops::copy_to(src, &dest)?;
-                            }
-                            state.set_message(format!("Pasted {} item(s)", paths.len()));
-                        }
-                        ClipboardContent::Cut(paths) => {
-                            for src in &paths {
-                                if let Some(name) = src.file_name() {
-                                    let new_path = dest.join(name);
-                                    std::fs::rename(src, new_path)?;
-                                }
-                            }
-                            state.set_message(format!("Moved {} item(s)", paths.len()));
-                        }
-                    }
-                    reload_tree(navigator, state)?;
+        KeyAction::Paste | KeyAction::PasteCopy => {
+            // Paste as copy leaves a cut's sources and the clipboard alone
+            let keep_clipboard = matches!(action, KeyAction::PasteCopy);
+            let content = state.clipboard.as_ref().and_then(|c| c.content().cloned());
+            if let Some(content) = content {
+                let dest = get_target_directory(focused_path.as_ref(), &state.root);
+                let (paths, is_cut) = match content {
+                    ClipboardContent::Copy(paths) => (paths, false),
+                    ClipboardContent::Cut(paths) => (paths, !keep_clipboard),
+                };
+                // A cut also removes the sources
+                let mut touched = vec![dest.clone()];
+                if is_cut {
+                    touched.extend(paths.iter().cloned());
+                }
+                if !ensure_within_root(state, &touched) {
+                    return Ok(());
+                }
+                let plan = PastePlan::new(&paths, &dest, is_cut);
+
+                if plan.next_conflict().is_some() {
+                    // Ask per conflicting item before touching anything
+                    state.mode = ViewMode::Confirm {
+                        action: PendingAction::ResolvePasteConflict {
+                            plan,
+                            keep_clipboard,
+                        },
+                    };
+                } else {
+                    execute_paste(plan, keep_clipboard, state, navigator)?;
                 }
             }
         }
-        KeyAction::ConfirmDelete => {
+        KeyAction::ResolvePasteConflict {
+            resolution,
+            apply_all,
+        } => {
+            if let ViewMode::Confirm {
+                action:
+                    PendingAction::ResolvePasteConflict {
+                        plan,
+                        keep_clipboard,
+                    },
+            } = &mut state.mode
+            {
+                plan.resolve(resolution, apply_all);
+                if plan.next_conflict().is_none() {
+                    let (plan, keep_clipboard) = (plan.clone(), *keep_clipboard);
+                    state.mode = ViewMode::Browse;
+                    execute_paste(plan, keep_clipboard, state, navigator)?;
+                }
+            }
+        }
+        KeyAction::ResolveRenameConflict { resolution } => {
+            if let ViewMode::Confirm {
+                action: PendingAction::ResolveRenameConflict { original, target },
+            } = &state.mode
+            {
+                let (original, target) = (original.clone(), target.clone());
+                state.mode = ViewMode::Browse;
+                let target = match resolution {
+                    ConflictResolution::Overwrite => target,
+                    ConflictResolution::Rename => get_unique_path(&target),
+                    ConflictResolution::Skip => {
+                        state.set_message("Rename cancelled");
+                        return Ok(());
+                    }
+                };
+                let name = get_filename_str(Some(&target));
+                rename_entry(state, navigator, original, &name)?;
+            }
+        }
+        KeyAction::ConfirmDelete | KeyAction::ConfirmDeletePermanent => {
+            let permanent = matches!(action, KeyAction::ConfirmDeletePermanent) || !state.use_trash;
             let targets = create_delete_targets(state, focused_path.as_ref());
-            if !targets.is_empty() {
-                state.mode = ViewMode::Confirm {
-                    action: PendingAction::Delete { targets },
+            if !targets.is_empty() && ensure_within_root(state, &targets) {
+                // Nothing to restore a permanent delete from, so only "off" skips asking
+                let ask = if permanent {
+                    state.delete_confirm.mode != ConfirmDeleteMode::Off
+                } else {
+                    // Only the targets themselves are looked at: any directory
+                    // asks anyway, so there is nothing to walk
+                    let shallow = summarize_delete(&targets, targets.len());
+                    state.delete_confirm.needs_confirm(&targets, &shallow)
                 };
+                if ask {
+                    show_delete_confirm(state, targets, permanent);
+                } else {
+                    delete_targets(&targets, permanent, state, navigator)?;
+                }
             }
         }
         KeyAction::ExecuteDelete => {
             if let ViewMode::Confirm {
-                action: PendingAction::Delete { targets },
+                action: PendingAction::Delete { targets, permanent },
             } = &state.mode
             {
-                for path in targets {
-                    file_ops::delete(path)?;
-                }
-                state.set_message(format!("Moved {} item(s) to trash", targets.len()));
-                state.selected_paths.clear();
-                state.mode = ViewMode::Browse;
-                reload_tree(navigator, state)?;
+                let (targets, permanent) = (targets.clone(), *permanent);
+                delete_targets(&targets, permanent, state, navigator)?;
             }
         }
         KeyAction::StartRename => {
             if let Some(path) = focused_path {
                 let name = get_filename_str(Some(path));
+                let cursor = state.rename_select.cursor(&name, path.is_dir());
                 state.mode = ViewMode::Input {
                     purpose: InputPurpose::Rename {
                         original: path.clone(),
                     },
-                    buffer: name.clone(),
-                    cursor: name.len(),
+                    buffer: name,
+                    cursor,
                 };
             }
         }
@@ -94,7 +265,94 @@ pub fn handle(
                 cursor: 0,
             };
         }
+        KeyAction::CreateSymlink | KeyAction::CreateHardlink => {
+            let mut sources: Vec<PathBuf> = match state.clipboard.as_ref() {
+                Some(clipboard) if !clipboard.is_empty() => clipboard.paths().to_vec(),
+                _ => focused_path.clone().into_iter().collect(),
+            };
+            if sources.is_empty() {
+                state.set_message("Nothing to link");
+                return Ok(());
+            }
+            let kind = if matches!(action, KeyAction::CreateSymlink) {
+                LinkKind::Symlink {
+                    relative: state.relative_symlinks,
+                }
+            } else {
+                LinkKind::Hardlink
+            };
+            let dest = get_target_directory(focused_path.as_ref(), &state.root);
+            let source = sources.remove(0);
+            open_link_prompt(state, source, sources, kind, &dest);
+        }
+        KeyAction::ToggleLinkRelative => {
+            if let ViewMode::Input {
+                purpose:
+                    InputPurpose::CreateLink {
+                        kind: LinkKind::Symlink { relative },
+                        ..
+                    },
+                ..
+            } = &mut state.mode
+            {
+                *relative = !*relative;
+            }
+        }
         _ => {}
     }
     Ok(())
 }
+
+/// Execute a fully resolved paste plan and refresh the tree
+///
+/// The clipboard is emptied unless `keep_clipboard` is set.
+fn execute_paste(
+    plan: PastePlan,
+    keep_clipboard: bool,
+    state: &mut AppState,
+    navigator: &mut TreeNavigator,
+) -> anyhow::Result<()> {
+    if !keep_clipboard {
+        if let Some(ref mut clipboard) = state.clipboard {
+            clipboard.take();
+        }
+    }
+
+    let summary = plan.execute();
+
+    let mut changed_dirs = vec![plan.dest_dir.clone()];
+    if plan.is_cut {
+        for item in &plan.items {
+            if let Some(parent) = item.src.parent() {
+                if !changed_dirs.iter().any(|d| d == parent) {
+                    changed_dirs.push(parent.to_path_buf());
+                }
+            }
+        }
+    }
+    state.changed_dirs = changed_dirs;
+
+    // A merged move cannot be undone: moving the destination back would
+    // take along what was already in it
+    let kind = if plan.is_cut && summary.merged {
+        OperationKind::Merge
+    } else if plan.is_cut {
+        OperationKind::Move
+    } else {
+        OperationKind::Copy
+    };
+    let (sources, results) = summary.placed.iter().cloned().unzip();
+    state.log_operation(kind, sources, results);
+
+    let verb = if plan.is_cut { "Moved" } else { "Pasted" };
+    let mut message = format!("{} {} item(s)", verb, summary.pasted);
+    if summary.skipped > 0 {
+        message.push_str(&format!(", {} skipped", summary.skipped));
+    }
+    if summary.failed > 0 {
+        message.push_str(&format!(", {} failed", summary.failed));
+    }
+    state.set_message(message);
+
+    reload_tree(navigator, state)
+}
@@ -1,9 +1,15 @@
 //! Filter action handlers
 //!
-//! Handles file filter operations
+//! Handles file filter operations and type-to-narrow (StartLiveFilter,
+//! LockLiveFilter)
 
-use crate::core::{AppState, ViewMode};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::core::{glob_match, AppState, ViewMode};
 use crate::handler::key::KeyAction;
+use crate::render::fuzzy_scores;
+use crate::tree::TreeEntry;
 
 /// Handle filter-related actions
 pub fn handle(action: KeyAction, state: &mut AppState) {
@@ -34,52 +40,97 @@ pub fn handle(action: KeyAction, state: &mut AppState) {
     }
 }
 
-/// Check if a filename matches the filter pattern
-/// Supports simple glob patterns: * (any chars), ? (single char)
-pub fn matches_filter(filename: &str, pattern: &str) -> bool {
-    glob_match(pattern, filename)
+/// Handle type-to-narrow actions
+pub fn handle_live(action: KeyAction, state: &mut AppState, focused_path: &Option<PathBuf>) {
+    match action {
+        KeyAction::StartLiveFilter => {
+            state.mode = ViewMode::LiveFilter {
+                query: String::new(),
+                origin: focused_path.clone(),
+            };
+        }
+        KeyAction::LockLiveFilter => {
+            if let ViewMode::LiveFilter { query, .. } = &state.mode {
+                let narrowed = !query.is_empty();
+                state.mode = ViewMode::Browse;
+                match focused_path {
+                    // Refocus the match once the full tree is back
+                    Some(path) if narrowed => state.fuzzy_jump_target = Some(path.clone()),
+                    Some(_) => {}
+                    None => state.set_message("No matches"),
+                }
+            }
+        }
+        _ => {}
+    }
 }
 
-/// Simple glob matching implementation
-fn glob_match(pattern: &str, text: &str) -> bool {
-    let pattern: Vec<char> = pattern.chars().collect();
-    let text: Vec<char> = text.chars().collect();
-    glob_match_impl(&pattern, &text)
+/// Entries kept while narrowing, and the best match among them
+#[derive(Debug, Default)]
+pub struct LiveFilterMatches {
+    /// Matching entries, their ancestors, and the root
+    pub keep: HashSet<PathBuf>,
+    /// Highest scoring match (first in tree order on ties)
+    pub best: Option<PathBuf>,
 }
 
-fn glob_match_impl(pattern: &[char], text: &[char]) -> bool {
-    let mut p_idx = 0;
-    let mut t_idx = 0;
-    let mut star_idx: Option<usize> = None;
-    let mut match_idx = 0;
+/// Fuzzy match entry names against `query`
+///
+/// `entries` is the flattened tree with the root first. Every match keeps its
+/// ancestors so the narrowed tree stays connected.
+pub fn live_filter_matches(
+    entries: &[&TreeEntry],
+    query: &str,
+    case_sensitive: bool,
+) -> LiveFilterMatches {
+    let mut matches = LiveFilterMatches::default();
+    let Some((root, children)) = entries.split_first() else {
+        return matches;
+    };
+    matches.keep.insert(root.path.clone());
 
-    while t_idx < text.len() {
-        if p_idx < pattern.len() && (pattern[p_idx] == '?' || pattern[p_idx] == text[t_idx]) {
-            // Characters match or pattern has ?
-            p_idx += 1;
-            t_idx += 1;
-        } else if p_idx < pattern.len() && pattern[p_idx] == '*' {
-            // Star found, remember position
-            star_idx = Some(p_idx);
-            match_idx = t_idx;
-            p_idx += 1;
-        } else if let Some(star) = star_idx {
-            // Mismatch after star, backtrack
-            p_idx = star + 1;
-            match_idx += 1;
-            t_idx = match_idx;
-        } else {
-            // No match
-            return false;
+    let names: Vec<&str> = children.iter().map(|e| e.name.as_str()).collect();
+    let scores = fuzzy_scores(query, &names, case_sensitive);
+    let mut best_score = None;
+    for (entry, score) in children.iter().zip(scores) {
+        let Some(score) = score else {
+            continue;
+        };
+        if best_score.is_none_or(|best| score > best) {
+            best_score = Some(score);
+            matches.best = Some(entry.path.clone());
+        }
+        for ancestor in entry.path.ancestors() {
+            // The root is already kept, so the walk stops there at the latest
+            if !matches.keep.insert(ancestor.to_path_buf()) {
+                break;
+            }
         }
     }
+    matches
+}
 
-    // Check remaining pattern characters (should all be stars)
-    while p_idx < pattern.len() && pattern[p_idx] == '*' {
-        p_idx += 1;
-    }
+/// Focus the best match of the type-to-narrow query in the displayed `entries`
+///
+/// With an empty query the entry focused before narrowing is refocused.
+pub fn focus_best_live_match(entries: &[&TreeEntry], state: &mut AppState) {
+    let ViewMode::LiveFilter { query, origin } = &state.mode else {
+        return;
+    };
+    let target = if query.is_empty() {
+        origin.clone()
+    } else {
+        live_filter_matches(entries, query, state.case_sensitive).best
+    };
+    state.focus_index = target
+        .and_then(|target| entries.iter().position(|e| e.path == target))
+        .unwrap_or(0);
+}
 
-    p_idx == pattern.len()
+/// Check if a filename matches the filter pattern
+/// Supports simple glob patterns: * (any chars), ? (single char)
+pub fn matches_filter(filename: &str, pattern: &str) -> bool {
+    glob_match(pattern, filename)
 }
 
 #[cfg(test)]
@@ -1,18 +1,29 @@
 //! Git operation action handlers
 //!
-//! Handles git stage and unstage actions.
+//! Handles git stage, unstage and discard actions, the git stash popup, diffing the
+//! focused file against a revision, copying its diff or blame, and the
+//! blame gutter of the text preview.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use crate::core::AppState;
+use crate::core::{AppState, InputPurpose, PendingAction, ViewMode};
 use crate::git;
 use crate::handler::key::KeyAction;
+use crate::render::{BlamePreview, TextPreview};
+use crate::tree::TreeNavigator;
 
-/// Handle git operations (stage, unstage)
+use super::display::copy_text_to_clipboard;
+use super::reload_tree;
+
+/// Largest diff or blame copied to the clipboard before it is cut short
+pub const YANK_MAX_BYTES: usize = 512 * 1024;
+
+/// Handle git operations (stage, unstage, discard)
 pub fn handle(action: KeyAction, state: &mut AppState, focused_path: Option<&PathBuf>) {
     // Git operations require a git repo
     let Some(ref git_status) = state.git_status else {
-        state.set_message("Not in a git repository");
+        state.set_warning("Not in a git repository");
         return;
     };
 
@@ -55,7 +66,11 @@ pub fn handle(action: KeyAction, state: &mut AppState, focused_path: Option<&Pat
             } else {
                 format!("Staged {} files, {} failed", success_count, fail_count)
             };
-            state.set_message(message);
+            if fail_count == 0 {
+                state.set_message(message);
+            } else {
+                state.set_warning(message);
+            }
         }
 
         KeyAction::GitUnstage => {
@@ -82,13 +97,470 @@ pub fn handle(action: KeyAction, state: &mut AppState, focused_path: Option<&Pat
             } else {
                 format!("Unstaged {} files, {} failed", success_count, fail_count)
             };
-            state.set_message(message);
+            if fail_count == 0 {
+                state.set_message(message);
+            } else {
+                state.set_warning(message);
+            }
+        }
+
+        KeyAction::GitDiscard => {
+            // Untracked files have no committed version to go back to
+            let targets: Vec<PathBuf> = targets
+                .into_iter()
+                .filter(|p| {
+                    git_status.has_changes(p)
+                        && git_status.get_status(p) != git::FileStatus::Untracked
+                })
+                .collect();
+            if targets.is_empty() {
+                state.set_message("No changes to discard");
+                return;
+            }
+            state.mode = ViewMode::Confirm {
+                action: PendingAction::GitDiscard { targets },
+            };
         }
 
         _ => {}
     }
 }
 
+/// Discard the changes pending confirmation, then reload the tree
+pub fn discard_confirmed(
+    state: &mut AppState,
+    navigator: &mut TreeNavigator,
+) -> anyhow::Result<()> {
+    let ViewMode::Confirm {
+        action: PendingAction::GitDiscard { targets },
+    } = std::mem::replace(&mut state.mode, ViewMode::Browse)
+    else {
+        return Ok(());
+    };
+    let Some(repo_root) = state
+        .git_status
+        .as_ref()
+        .map(|g| g.repo_root().to_path_buf())
+    else {
+        state.set_warning("Not in a git repository");
+        return Ok(());
+    };
+
+    let mut success_count = 0;
+    let mut fail_count = 0;
+    for target in &targets {
+        match git::discard(&repo_root, target) {
+            Ok(()) => success_count += 1,
+            Err(_) => fail_count += 1,
+        }
+    }
+
+    reload_tree(navigator, state)?;
+    state.refresh_git_status();
+
+    let message = if fail_count == 0 {
+        if success_count == 1 {
+            "Restored 1 file".to_string()
+        } else {
+            format!("Restored {} files", success_count)
+        }
+    } else {
+        format!("Restored {} files, {} failed", success_count, fail_count)
+    };
+    if fail_count == 0 {
+        state.set_message(message);
+    } else {
+        state.set_warning(message);
+    }
+    Ok(())
+}
+
+/// Handle git stash popup actions (list, apply, pop, drop)
+pub fn handle_stash(
+    action: KeyAction,
+    state: &mut AppState,
+    navigator: &mut TreeNavigator,
+) -> anyhow::Result<()> {
+    let Some(repo_root) = state
+        .git_status
+        .as_ref()
+        .map(|g| g.repo_root().to_path_buf())
+    else {
+        state.mode = ViewMode::Browse;
+        state.set_warning("Not in a git repository");
+        return Ok(());
+    };
+
+    match action {
+        KeyAction::OpenGitStash => open_stash_list(state, &repo_root, 0),
+        KeyAction::GitStashUp => {
+            if let ViewMode::GitStash { selected, .. } = &mut state.mode {
+                *selected = selected.saturating_sub(1);
+            }
+        }
+        KeyAction::GitStashDown => {
+            if let ViewMode::GitStash { stashes, selected } = &mut state.mode {
+                let max_index = stashes.len().saturating_sub(1);
+                *selected = (*selected + 1).min(max_index);
+            }
+        }
+        KeyAction::GitStashApply | KeyAction::GitStashPop => {
+            let Some(stash) = selected_stash(state) else {
+                return Ok(());
+            };
+            let (result, verb) = if matches!(action, KeyAction::GitStashPop) {
+                (git::pop_stash(&repo_root, stash.index), "Popped")
+            } else {
+                (git::apply_stash(&repo_root, stash.index), "Applied")
+            };
+            state.mode = ViewMode::Browse;
+            match result {
+                Ok(()) => state.set_message(format!("{} {}", verb, stash.name())),
+                Err(e) => state.set_error(format!("Failed: {}", e)),
+            }
+            // A failed apply can still leave conflicted files behind
+            reload_tree(navigator, state)?;
+        }
+        KeyAction::GitStashDrop => {
+            if let Some(stash) = selected_stash(state) {
+                state.mode = ViewMode::Confirm {
+                    action: PendingAction::DropStash { stash },
+                };
+            }
+        }
+        KeyAction::GitStashDropConfirm => {
+            if let ViewMode::Confirm {
+                action: PendingAction::DropStash { stash },
+            } = state.mode.clone()
+            {
+                match git::drop_stash(&repo_root, stash.index) {
+                    Ok(()) => {
+                        open_stash_list(state, &repo_root, stash.index);
+                        state.set_message(format!("Dropped {}", stash.name()));
+                    }
+                    Err(e) => {
+                        state.mode = ViewMode::Browse;
+                        state.set_error(format!("Failed: {}", e));
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Show the stash list with `selected` clamped, or a message if there are none
+fn open_stash_list(state: &mut AppState, repo_root: &Path, selected: usize) {
+    match git::list_stashes(repo_root) {
+        Ok(stashes) if stashes.is_empty() => {
+            state.mode = ViewMode::Browse;
+            state.set_message("No stashes");
+        }
+        Ok(stashes) => {
+            let selected = selected.min(stashes.len() - 1);
+            state.mode = ViewMode::GitStash { stashes, selected };
+            state.set_message("Stashes (Enter: apply, p: pop, d: drop)");
+        }
+        Err(e) => {
+            state.mode = ViewMode::Browse;
+            state.set_error(format!("Failed: {}", e));
+        }
+    }
+}
+
+/// Toggle showing only files with a git status
+///
+/// Turning it on expands the ancestors of every changed file so they are all
+/// visible. The focused entry stays focused when it remains visible.
+pub fn toggle_changed_only(
+    state: &mut AppState,
+    navigator: &mut TreeNavigator,
+    focused_path: Option<&PathBuf>,
+) -> anyhow::Result<()> {
+    if state.git_changed_only {
+        state.git_changed_only = false;
+        state.fuzzy_jump_target = focused_path.cloned();
+        state.set_message("Showing all files");
+        return Ok(());
+    }
+
+    let Some(ref git_status) = state.git_status else {
+        state.set_warning("Not in a git repository");
+        return Ok(());
+    };
+
+    let changed: Vec<PathBuf> = git_status
+        .changed_paths()
+        .into_iter()
+        .filter(|p| p.starts_with(&state.root) && p.symlink_metadata().is_ok())
+        .collect();
+    if changed.is_empty() {
+        state.set_message("No changed files");
+        return Ok(());
+    }
+
+    for path in &changed {
+        navigator.reveal_path(path)?;
+    }
+    state.git_changed_only = true;
+    state.focus_index = 0;
+    state.viewport_top = 0;
+    state.fuzzy_jump_target = focused_path.filter(|p| git_status.has_changes(p)).cloned();
+    state.set_message(format!("Showing {} changed file(s)", changed.len()));
+    Ok(())
+}
+
+/// Number of branches and tags offered in the revision prompt
+const RECENT_REF_LIMIT: usize = 20;
+
+/// Prompt for a revision to diff the focused file against
+pub fn start_diff_revision(state: &mut AppState, focused_path: Option<&PathBuf>) {
+    let Some(ref git_status) = state.git_status else {
+        state.set_warning("Not in a git repository");
+        return;
+    };
+    let Some(path) = focused_path.filter(|p| p.is_file()) else {
+        state.set_message("No file selected");
+        return;
+    };
+    let refs = git::recent_refs(git_status.repo_root(), RECENT_REF_LIMIT);
+    state.mode = ViewMode::Input {
+        purpose: InputPurpose::DiffRevision {
+            path: path.clone(),
+            refs,
+        },
+        buffer: String::new(),
+        cursor: 0,
+    };
+}
+
+/// Prompt for a commit message (amending prefills the last message)
+pub fn start_commit(state: &mut AppState, amend: bool) {
+    let Some(ref git_status) = state.git_status else {
+        state.set_warning("Not in a git repository");
+        return;
+    };
+    let buffer = if amend {
+        git::last_commit_message(git_status.repo_root()).unwrap_or_default()
+    } else {
+        String::new()
+    };
+    state.mode = ViewMode::Input {
+        purpose: InputPurpose::CommitMessage { amend },
+        cursor: buffer.len(),
+        buffer,
+    };
+}
+
+/// Commit the staged changes with `message`
+///
+/// An empty message keeps the prompt open.
+pub fn commit_staged(state: &mut AppState, message: &str, amend: bool) {
+    let Some(repo_root) = state
+        .git_status
+        .as_ref()
+        .map(|git| git.repo_root().to_path_buf())
+    else {
+        state.mode = ViewMode::Browse;
+        state.set_warning("Not in a git repository");
+        return;
+    };
+    if message.trim().is_empty() {
+        state.set_warning("Commit message is empty");
+        return;
+    }
+    state.mode = ViewMode::Browse;
+    match git::commit(&repo_root, message, amend) {
+        Ok(hash) => {
+            state.refresh_git_status();
+            let summary = message.trim().lines().next().unwrap_or_default();
+            let verb = if amend { "Amended" } else { "Committed" };
+            state.set_message(format!("{} {}: {}", verb, hash, summary));
+        }
+        Err(e) => state.set_error(format!("Failed: {}", e)),
+    }
+}
+
+/// Complete the revision prompt from the recent refs
+///
+/// Several matches are listed in the status bar.
+pub fn complete_diff_revision(state: &mut AppState) {
+    let ViewMode::Input {
+        purpose: InputPurpose::DiffRevision { refs, .. },
+        buffer,
+        cursor,
+    } = &mut state.mode
+    else {
+        return;
+    };
+    let (completed, matches) = git::complete_ref(buffer, refs);
+    let listing = (matches.len() > 1).then(|| matches.join("  "));
+    *cursor = completed.len();
+    *buffer = completed;
+    if let Some(listing) = listing {
+        state.set_message(listing);
+    }
+}
+
+/// Show the diff of `path` against `rev` in the preview
+///
+/// An empty revision goes back to the working tree diff. Unknown revisions
+/// are reported in the status bar.
+pub fn diff_against_revision(state: &mut AppState, path: &Path, rev: &str) {
+    let rev = rev.trim();
+    if rev.is_empty() {
+        state.diff_base = None;
+        state.set_message("Diff against working tree");
+        return;
+    }
+    let Some(ref git_status) = state.git_status else {
+        state.set_warning("Not in a git repository");
+        return;
+    };
+    match git::try_get_diff(git_status.repo_root(), path, false, Some(rev)) {
+        Ok(Some(_)) => {
+            state.diff_base = Some((path.to_path_buf(), rev.to_string()));
+            state.preview_visible = true;
+            state.set_message(format!("Diff against {}", rev));
+        }
+        Ok(None) => state.set_message(format!("No differences from {}", rev)),
+        Err(e) => state.set_error(format!("Failed: {}", e)),
+    }
+}
+
+/// Handle git copy mode: enter it, then copy the focused file's diff or blame
+pub fn yank(action: KeyAction, state: &mut AppState, focused_path: Option<&PathBuf>) {
+    if matches!(action, KeyAction::StartYankGit) {
+        state.mode = ViewMode::YankGit;
+        return;
+    }
+    state.mode = ViewMode::Browse;
+
+    let Some(path) = focused_path.filter(|p| p.is_file()) else {
+        state.set_message("No file selected");
+        return;
+    };
+    let Some(repo_root) = state
+        .git_status_for(path)
+        .map(|g| g.repo_root().to_path_buf())
+    else {
+        state.set_warning("Not in a git repository");
+        return;
+    };
+
+    let (what, text) = match action {
+        KeyAction::YankGitDiff => match git::try_get_diff(&repo_root, path, false, None) {
+            Ok(Some(diff)) => ("diff", diff.to_patch()),
+            Ok(None) => {
+                state.set_message("No unstaged changes");
+                return;
+            }
+            Err(e) => {
+                state.set_error(format!("Failed: {}", e));
+                return;
+            }
+        },
+        _ => match git::blame_text(&repo_root, path) {
+            Ok(text) => ("blame", text),
+            Err(e) => {
+                state.set_error(format!("Failed: {}", e));
+                return;
+            }
+        },
+    };
+
+    let (text, truncated) = truncate_text(&text, YANK_MAX_BYTES);
+    if let Err(e) = copy_text_to_clipboard(&text) {
+        state.set_error(format!("Failed: {}", e));
+        return;
+    }
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    if truncated {
+        state.set_warning(format!("Copied {} of {} (truncated)", what, name));
+    } else {
+        state.set_message(format!("Copied {} of {}", what, name));
+    }
+}
+
+/// Show or hide the git blame gutter of the text preview
+///
+/// Blame is cached per file until it is modified, so toggling back is
+/// instant.
+pub fn toggle_blame(
+    state: &mut AppState,
+    focused_path: Option<&PathBuf>,
+    text_preview: Option<&mut TextPreview>,
+) {
+    let Some(tp) = text_preview else {
+        state.set_message("Blame works in text previews");
+        return;
+    };
+    if tp.blame.take().is_some() {
+        state.set_message("Blame: off");
+        return;
+    }
+    let Some(path) = focused_path.filter(|p| p.is_file()) else {
+        state.set_message("No file selected");
+        return;
+    };
+
+    let lines = match state.blame_cache.get(path) {
+        Some(lines) => lines,
+        None => {
+            let repo_root = state
+                .git_status_for(path)
+                .map(|g| g.repo_root().to_path_buf())
+                .or_else(|| {
+                    git::GitStatus::detect(path.parent().unwrap_or(path))
+                        .map(|g| g.repo_root().to_path_buf())
+                });
+            let Some(repo_root) = repo_root else {
+                state.set_warning("Blame: not in git");
+                return;
+            };
+            match git::blame_lines(&repo_root, path) {
+                Ok(lines) => {
+                    let lines = Arc::new(lines);
+                    state.blame_cache.insert(path, Arc::clone(&lines));
+                    lines
+                }
+                Err(e) => {
+                    state.set_warning(format!("Blame: not in git ({})", e));
+                    return;
+                }
+            }
+        }
+    };
+    tp.blame = Some(BlamePreview::new(lines));
+    state.set_message("Blame: on");
+}
+
+/// Cut `text` at the last line fitting in `max` bytes, noting what was left
+/// out (returns whether anything was cut)
+pub fn truncate_text(text: &str, max: usize) -> (String, bool) {
+    if text.len() <= max {
+        return (text.to_string(), false);
+    }
+    let mut end = max;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let kept = text[..end].rfind('\n').map_or(0, |i| i + 1);
+    let omitted = text[kept..].lines().count();
+    (
+        format!("{}[... truncated: {} more lines]\n", &text[..kept], omitted),
+        true,
+    )
+}
+
+fn selected_stash(state: &AppState) -> Option<git::Stash> {
+    match &state.mode {
+        ViewMode::GitStash { stashes, selected } => stashes.get(*selected).cloned(),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,14 +574,186 @@ mod tests {
     fn test_git_stage_no_repo() {
         let mut state = test_state();
         handle(KeyAction::GitStage, &mut state, None);
-        assert_eq!(state.message, Some("Not in a git repository".to_string()));
+        assert_eq!(state.message_text(), Some("Not in a git repository"));
     }
 
     #[test]
     fn test_git_unstage_no_repo() {
         let mut state = test_state();
         handle(KeyAction::GitUnstage, &mut state, None);
-        assert_eq!(state.message, Some("Not in a git repository".to_string()));
+        assert_eq!(state.message_text(), Some("Not in a git repository"));
+    }
+
+    #[test]
+    fn test_yank_git_no_repo() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let file = temp.path().join("a.txt");
+        std::fs::write(&file, "a\n").unwrap();
+        let mut state = AppState::new(temp.path().to_path_buf());
+
+        yank(KeyAction::StartYankGit, &mut state, Some(&file));
+        assert_eq!(state.mode, ViewMode::YankGit);
+        yank(KeyAction::YankGitDiff, &mut state, Some(&file));
+        assert_eq!(state.mode, ViewMode::Browse);
+        assert_eq!(state.message_text(), Some("Not in a git repository"));
+    }
+
+    #[test]
+    fn test_truncate_text() {
+        assert_eq!(truncate_text("a\nb\n", 10), ("a\nb\n".to_string(), false));
+        assert_eq!(
+            truncate_text("one\ntwo\nthree\n", 9),
+            (
+                "one\ntwo\n[... truncated: 1 more lines]\n".to_string(),
+                true
+            )
+        );
+        // Never cuts inside a character
+        let (text, truncated) = truncate_text("ééé\n", 3);
+        assert!(truncated);
+        assert_eq!(text, "[... truncated: 1 more lines]\n");
+    }
+
+    #[test]
+    fn test_git_stash_no_repo() {
+        let mut state = test_state();
+        let mut navigator = TreeNavigator::new(Path::new("/tmp"), false).unwrap();
+        handle_stash(KeyAction::OpenGitStash, &mut state, &mut navigator).unwrap();
+        assert_eq!(state.message_text(), Some("Not in a git repository"));
+        assert_eq!(state.mode, ViewMode::Browse);
+    }
+
+    #[test]
+    fn test_toggle_changed_only_no_repo() {
+        let mut state = test_state();
+        let mut navigator = TreeNavigator::new(Path::new("/tmp"), false).unwrap();
+        toggle_changed_only(&mut state, &mut navigator, None).unwrap();
+        assert_eq!(state.message_text(), Some("Not in a git repository"));
+        assert!(!state.git_changed_only);
+    }
+
+    #[test]
+    fn test_toggle_changed_only_in_real_repo() {
+        use std::fs;
+        use std::process::Command;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(&root)
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false)
+        };
+        if !git(&["init"])
+            || !git(&["config", "user.name", "Test User"])
+            || !git(&["config", "user.email", "test@example.com"])
+        {
+            return;
+        }
+
+        fs::create_dir_all(root.join("src/app")).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(root.join("src/app/config.rs"), "old").unwrap();
+        fs::write(root.join("src/lib.rs"), "lib").unwrap();
+        fs::write(root.join("docs/guide.md"), "guide").unwrap();
+        fs::write(root.join("README.md"), "readme").unwrap();
+        assert!(git(&["add", "."]));
+        assert!(git(&["commit", "-m", "initial"]));
+
+        fs::write(root.join("src/app/config.rs"), "new").unwrap();
+        fs::write(root.join("notes.txt"), "untracked").unwrap();
+
+        let mut state = AppState::new(root.clone());
+        state.git_status = git::GitStatus::detect(&root);
+        let mut navigator = TreeNavigator::new(&root, false).unwrap();
+
+        toggle_changed_only(&mut state, &mut navigator, None).unwrap();
+        assert!(state.git_changed_only);
+
+        let git_status = state.git_status.as_ref().unwrap();
+        let visible: Vec<PathBuf> = navigator
+            .visible_entries_where(|e| git_status.has_changes(&e.path))
+            .iter()
+            .map(|e| e.path.clone())
+            .collect();
+        assert_eq!(
+            visible,
+            vec![
+                root.clone(),
+                root.join("src"),
+                root.join("src/app"),
+                root.join("src/app/config.rs"),
+                root.join("notes.txt"),
+            ]
+        );
+
+        toggle_changed_only(&mut state, &mut navigator, None).unwrap();
+        assert!(!state.git_changed_only);
+        assert_eq!(state.message_text(), Some("Showing all files"));
+    }
+
+    #[test]
+    fn test_git_discard_in_real_repo() {
+        use std::fs;
+        use std::process::Command;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(&root)
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false)
+        };
+        if !git(&["init"])
+            || !git(&["config", "user.name", "Test User"])
+            || !git(&["config", "user.email", "test@example.com"])
+        {
+            return;
+        }
+
+        fs::write(root.join("a.txt"), "a").unwrap();
+        fs::write(root.join("b.txt"), "b").unwrap();
+        assert!(git(&["add", "."]));
+        assert!(git(&["commit", "-m", "initial"]));
+        fs::write(root.join("a.txt"), "changed a").unwrap();
+        fs::write(root.join("b.txt"), "changed b").unwrap();
+        fs::write(root.join("new.txt"), "untracked").unwrap();
+
+        let mut state = AppState::new(root.clone());
+        state.git_status = git::GitStatus::detect(&root);
+        let mut navigator = TreeNavigator::new(&root, false).unwrap();
+        state.selected_paths = [root.join("a.txt"), root.join("b.txt"), root.join("new.txt")]
+            .into_iter()
+            .collect();
+
+        // Untracked files are left out of the confirmation
+        handle(KeyAction::GitDiscard, &mut state, None);
+        let ViewMode::Confirm {
+            action: PendingAction::GitDiscard { targets },
+        } = &state.mode
+        else {
+            panic!("expected the discard confirmation");
+        };
+        assert_eq!(targets.len(), 2);
+        assert!(!targets.contains(&root.join("new.txt")));
+
+        discard_confirmed(&mut state, &mut navigator).unwrap();
+        assert_eq!(state.mode, ViewMode::Browse);
+        assert_eq!(state.message_text(), Some("Restored 2 files"));
+        assert_eq!(fs::read_to_string(root.join("a.txt")).unwrap(), "a");
+        assert_eq!(fs::read_to_string(root.join("b.txt")).unwrap(), "b");
+        assert!(root.join("new.txt").exists());
+
+        state.selected_paths.clear();
+        handle(KeyAction::GitDiscard, &mut state, Some(&root.join("a.txt")));
+        assert_eq!(state.message_text(), Some("No changes to discard"));
+        assert_eq!(state.mode, ViewMode::Browse);
     }
 
     #[test]
@@ -119,7 +763,7 @@ mod tests {
         state.git_status = Some(git::GitStatus::default_with_root(PathBuf::from("/tmp")));
 
         handle(KeyAction::GitStage, &mut state, None);
-        assert_eq!(state.message, Some("No file selected".to_string()));
+        assert_eq!(state.message_text(), Some("No file selected"));
     }
 
     #[test]
@@ -128,7 +772,7 @@ mod tests {
         state.git_status = Some(git::GitStatus::default_with_root(PathBuf::from("/tmp")));
 
         handle(KeyAction::GitUnstage, &mut state, None);
-        assert_eq!(state.message, Some("No file selected".to_string()));
+        assert_eq!(state.message_text(), Some("No file selected"));
     }
 
     #[test]
@@ -1,14 +1,21 @@
 //! Input confirmation handler
 //!
-//! Handles ConfirmInput for file creation, directory creation, and rename operations
+//! Handles ConfirmInput for file creation, directory creation, rename, link,
+//! diff revision, and pattern selection prompts
 
 use std::path::PathBuf;
 
+use super::command::execute_command_foreach;
+use super::file_ops::{ensure_within_root, open_link_prompt, rename_entry};
+use super::git_ops::{commit_staged, diff_against_revision};
+use super::search::start_content_search;
+use super::selection::apply_pattern_select;
 use crate::action::file as file_ops;
-use crate::core::{AppState, InputPurpose, ViewMode};
+use crate::action::OperationKind;
+use crate::core::{AppState, InputPurpose, PendingAction, ViewMode};
 use crate::tree::TreeNavigator;
 
-use super::{get_target_directory, reload_tree};
+use super::{get_target_directory, reload_tree, EntrySnapshot};
 
 /// Handle input confirmation
 pub fn handle_confirm(
@@ -16,25 +23,88 @@ pub fn handle_confirm(
     state: &mut AppState,
     navigator: &mut TreeNavigator,
     focused_path: &Option<PathBuf>,
+    entries: &[EntrySnapshot],
 ) -> anyhow::Result<()> {
     match &state.mode {
         ViewMode::Input { purpose, .. } => {
             let parent = get_target_directory(focused_path.as_ref(), &state.root);
             match purpose {
-                InputPurpose::CreateFile => {
-                    file_ops::create_file(&parent, &value)?;
-                    reload_tree(navigator, state)?;
-                    state.set_message(format!("Created: {}", value));
-                }
-                InputPurpose::CreateDir => {
-                    file_ops::create_dir(&parent, &value)?;
+                InputPurpose::CreateFile | InputPurpose::CreateDir => {
+                    let as_dir = matches!(purpose, InputPurpose::CreateDir);
+                    let created = if state.create_parents {
+                        file_ops::create_nested(&parent, &value, as_dir)?
+                    } else if as_dir || value.ends_with('/') {
+                        vec![file_ops::create_dir(&parent, value.trim_end_matches('/'))?]
+                    } else {
+                        vec![file_ops::create_file(&parent, &value)?]
+                    };
+                    state.log_operation(OperationKind::Create, vec![], created);
                     reload_tree(navigator, state)?;
                     state.set_message(format!("Created: {}", value));
                 }
                 InputPurpose::Rename { original } => {
-                    file_ops::rename(original, &value)?;
+                    let original = original.clone();
+                    let target = original.with_file_name(&value);
+                    if !ensure_within_root(state, &[original.clone(), target]) {
+                        state.mode = ViewMode::Browse;
+                        return Ok(());
+                    }
+                    if state.confirm_rename_overwrite {
+                        if let Some(target) = file_ops::rename_conflict(&original, &value) {
+                            // Ask instead of silently replacing the existing entry
+                            state.mode = ViewMode::Confirm {
+                                action: PendingAction::ResolveRenameConflict { original, target },
+                            };
+                            return Ok(());
+                        }
+                    }
+                    rename_entry(state, navigator, original, &value)?;
+                }
+                InputPurpose::CreateLink {
+                    source,
+                    pending,
+                    kind,
+                    dest_dir,
+                } => {
+                    let (source, mut pending, kind, dest_dir) =
+                        (source.clone(), pending.clone(), *kind, dest_dir.clone());
+                    let link = file_ops::create_link(&source, &dest_dir, &value, kind)?;
+                    state.log_operation(
+                        OperationKind::Link,
+                        vec![source.clone()],
+                        vec![link.clone()],
+                    );
                     reload_tree(navigator, state)?;
-                    state.set_message(format!("Renamed: {}", value));
+                    state.set_message(format!("Linked: {} -> {}", value, source.display()));
+                    // Reveal and focus through the fuzzy jump path
+                    state.fuzzy_jump_target = Some(link);
+                    if !pending.is_empty() {
+                        let next = pending.remove(0);
+                        open_link_prompt(state, next, pending, kind, &dest_dir);
+                        return Ok(());
+                    }
+                }
+                InputPurpose::DiffRevision { path, .. } => {
+                    let path = path.clone();
+                    state.mode = ViewMode::Browse;
+                    diff_against_revision(state, &path, &value);
+                }
+                InputPurpose::SelectPattern { action } => {
+                    let action = action.clone();
+                    apply_pattern_select(state, entries, &action, value.trim());
+                }
+                InputPurpose::ContentSearch => {
+                    start_content_search(state, &value);
+                    return Ok(());
+                }
+                InputPurpose::CommitMessage { amend } => {
+                    commit_staged(state, &value, *amend);
+                    return Ok(());
+                }
+                InputPurpose::ForeachCommand { targets } => {
+                    let targets = targets.clone();
+                    execute_command_foreach(state, &value, targets);
+                    return Ok(());
                 }
             }
             state.mode = ViewMode::Browse;
@@ -47,3 +117,11 @@ pub fn handle_confirm(
     }
     Ok(())
 }
+
+/// Insert a line break at the cursor of the input buffer
+pub fn insert_newline(state: &mut AppState) {
+    if let ViewMode::Input { buffer, cursor, .. } = &mut state.mode {
+        buffer.insert(*cursor, '\n');
+        *cursor += 1;
+    }
+}
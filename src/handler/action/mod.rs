@@ -7,29 +7,36 @@ mod bookmark;
 mod bulk_rename;
 pub mod command;
 mod display;
+pub mod editor_rename;
 mod file_ops;
 mod filter;
 mod git_ops;
 mod input;
 mod navigation;
+mod oplog;
+mod recent;
 mod search;
 mod selection;
 mod tree_ops;
 
 pub use bulk_rename::update_bulk_rename_buffer;
 pub use command::{execute_command, CommandResult};
-pub use filter::matches_filter;
+pub use display::poll_preview_search;
+pub use filter::{focus_best_live_match, live_filter_matches, matches_filter, LiveFilterMatches};
+pub use search::update_search_matches;
+pub use selection::refresh_selection_summary;
 
 use std::path::{Path, PathBuf};
 
 use crate::app::CommandsConfig;
-use crate::core::{AppState, ViewMode};
+use crate::core::{AppState, PickDirs, ViewMode};
 use crate::handler::key::KeyAction;
-use crate::integrate::{Callback, OutputFormat};
+use crate::integrate::{Callback, OutputFormat, OutputOptions};
 use crate::render::{
-    ArchivePreview, CustomPreview, DiffPreview, HexPreview, PdfPreview, Picker, TextPreview,
+    ArchivePreview, CsvPreview, CustomPreview, DiffPreview, HexPreview, PdfView, Picker,
+    TextPreview,
 };
-use crate::tree::TreeNavigator;
+use crate::tree::{split_archive_path, TreeEntry, TreeNavigator, FVIGNORE_FILENAME};
 
 /// Result of action execution
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -56,6 +63,8 @@ pub struct ActionContext {
     pub callback: Option<Callback>,
     /// Output format for pick mode
     pub output_format: OutputFormat,
+    /// Type indicators for pick mode output
+    pub output_options: OutputOptions,
     /// Custom commands configuration
     pub commands: CommandsConfig,
 }
@@ -90,6 +99,80 @@ pub fn reload_tree(navigator: &mut TreeNavigator, state: &mut AppState) -> anyho
     Ok(())
 }
 
+/// Reload only the directories holding the `changed` paths, then refresh git status
+///
+/// A changed `.fvignore` reloads the whole tree, since its rules apply below it.
+pub fn reload_changed(
+    navigator: &mut TreeNavigator,
+    state: &mut AppState,
+    changed: &[PathBuf],
+) -> anyhow::Result<()> {
+    if changed.iter().any(|path| {
+        path.file_name()
+            .is_some_and(|name| name == FVIGNORE_FILENAME)
+    }) {
+        return reload_tree(navigator, state);
+    }
+    let mut dirs: Vec<&Path> = changed.iter().filter_map(|path| path.parent()).collect();
+    dirs.sort();
+    dirs.dedup();
+    // Reloading a directory rereads its expanded subdirectories as well
+    for dir in &dirs {
+        if !dirs
+            .iter()
+            .any(|other| other != dir && dir.starts_with(other))
+        {
+            navigator.reload_subtree(dir)?;
+        }
+    }
+    state.refresh_git_status();
+    Ok(())
+}
+
+/// Entries shown in the tree
+///
+/// The visible entries, narrowed to changed files in the git changed-only
+/// view, to directories when picking with `--dirs-only`, by the filter
+/// pattern (directories always pass the filter), and while typing to narrow
+/// to fuzzy matches and their ancestors.
+pub fn displayed_entries<'a>(navigator: &'a TreeNavigator, state: &AppState) -> Vec<&'a TreeEntry> {
+    let mut entries = match state.git_status.as_ref().filter(|_| state.git_changed_only) {
+        Some(git) => navigator.visible_entries_where(|e| git.has_changes(&e.path)),
+        None => navigator.visible_entries(),
+    };
+    if state.pick_mode && state.pick_dirs == PickDirs::Only {
+        entries.retain(|e| e.is_dir);
+    }
+    if let Some(ref pattern) = state.filter_pattern {
+        entries.retain(|e| e.is_dir || matches_filter(&e.name, pattern));
+    }
+    if let ViewMode::LiveFilter { query, .. } = &state.mode {
+        if !query.is_empty() {
+            let keep = live_filter_matches(&entries, query, state.case_sensitive).keep;
+            entries.retain(|e| keep.contains(&e.path));
+        }
+    }
+    entries
+}
+
+/// Reveal a path and focus it in the displayed entries
+///
+/// Returns false if the path is not shown (e.g. hidden by a filter).
+pub fn reveal_and_focus(
+    navigator: &mut TreeNavigator,
+    state: &mut AppState,
+    target: &Path,
+) -> anyhow::Result<bool> {
+    navigator.reveal_path(target)?;
+    let index = displayed_entries(navigator, state)
+        .iter()
+        .position(|e| e.path == target);
+    if let Some(index) = index {
+        state.focus_index = index;
+    }
+    Ok(index.is_some())
+}
+
 /// Handle a KeyAction and update state accordingly
 #[allow(clippy::too_many_arguments)]
 pub fn handle_action(
@@ -101,12 +184,23 @@ pub fn handle_action(
     context: &ActionContext,
     text_preview: &mut Option<TextPreview>,
     hex_preview: &mut Option<HexPreview>,
+    csv_preview: &mut Option<CsvPreview>,
     archive_preview: &mut Option<ArchivePreview>,
-    pdf_preview: &mut Option<PdfPreview>,
+    pdf_preview: &mut Option<PdfView>,
     diff_preview: &mut Option<DiffPreview>,
     custom_preview: &mut Option<CustomPreview>,
     image_picker: &mut Option<Picker>,
 ) -> anyhow::Result<ActionResult> {
+    // A count applies to the next action only
+    let count = match action {
+        KeyAction::CountDigit { digit } => {
+            let count = state.count_prefix.unwrap_or(0);
+            state.count_prefix = Some(count.saturating_mul(10).saturating_add(digit as usize));
+            return Ok(ActionResult::Continue);
+        }
+        _ => state.count_prefix.take(),
+    };
+
     // Disable CRUD operations in stdin mode
     if state.stdin_mode {
         let is_crud_action = matches!(
@@ -115,9 +209,20 @@ pub fn handle_action(
                 | KeyAction::StartNewDir
                 | KeyAction::StartRename
                 | KeyAction::ConfirmDelete
+                | KeyAction::ConfirmDeletePermanent
+                | KeyAction::Undo
                 | KeyAction::ExecuteDelete
                 | KeyAction::Paste
+                | KeyAction::PasteCopy
+                | KeyAction::ResolvePasteConflict { .. }
+                | KeyAction::ResolveRenameConflict { .. }
+                | KeyAction::CreateSymlink
+                | KeyAction::CreateHardlink
                 | KeyAction::Refresh
+                | KeyAction::EditorRename
+                | KeyAction::OpenGitStash
+                | KeyAction::SetRoot
+                | KeyAction::RootUp
         );
         if is_crud_action {
             state.set_message("File operations disabled in stdin mode");
@@ -125,9 +230,64 @@ pub fn handle_action(
         }
     }
 
+    // Mounted archives are read-only
+    let writes = matches!(
+        action,
+        KeyAction::StartNewFile
+            | KeyAction::StartNewDir
+            | KeyAction::StartRename
+            | KeyAction::ConfirmDelete
+            | KeyAction::ConfirmDeletePermanent
+            | KeyAction::Undo
+            | KeyAction::ExecuteDelete
+            | KeyAction::Paste
+            | KeyAction::PasteCopy
+            | KeyAction::ResolvePasteConflict { .. }
+            | KeyAction::ResolveRenameConflict { .. }
+            | KeyAction::CreateSymlink
+            | KeyAction::CreateHardlink
+            | KeyAction::EditorRename
+            | KeyAction::SetRoot
+    );
+    // Pasting a cut moves its sources out of wherever they are
+    let moves_clipboard = matches!(
+        action,
+        KeyAction::Paste | KeyAction::ResolvePasteConflict { .. }
+    ) && state.clipboard.as_ref().is_some_and(|c| c.is_cut());
+    let clipboard_sources = state
+        .clipboard
+        .as_ref()
+        .filter(|_| moves_clipboard)
+        .map(|c| c.paths())
+        .unwrap_or_default();
+    if writes
+        && focused_path
+            .iter()
+            .chain(&state.selected_paths)
+            .chain(clipboard_sources)
+            .any(|path| split_archive_path(path).is_some())
+    {
+        state.set_error("Archive contents are read-only");
+        return Ok(ActionResult::Continue);
+    }
+
+    // Enter and Esc act on the fullscreen preview's search first
+    if matches!(action, KeyAction::Cancel | KeyAction::ConfirmInput { .. })
+        && matches!(state.mode, ViewMode::Preview { .. })
+        && display::handle_preview_search(
+            &action,
+            state,
+            text_preview
+                .as_mut()
+                .or(pdf_preview.as_mut().and_then(PdfView::text_mut)),
+        )
+    {
+        return Ok(ActionResult::Continue);
+    }
+
     match action {
-        // No action
-        KeyAction::None => Ok(ActionResult::Continue),
+        // No action (counts were handled above)
+        KeyAction::None | KeyAction::CountDigit { .. } => Ok(ActionResult::Continue),
 
         // App control
         KeyAction::Quit | KeyAction::QuitAndCd | KeyAction::Cancel => {
@@ -139,7 +299,7 @@ pub fn handle_action(
         | KeyAction::MoveDown
         | KeyAction::MoveToTop
         | KeyAction::MoveToBottom => {
-            navigation::handle(action, state, entries);
+            navigation::handle(action, state, entries, count);
             Ok(ActionResult::Continue)
         }
 
@@ -148,7 +308,9 @@ pub fn handle_action(
         | KeyAction::Collapse
         | KeyAction::ToggleExpand
         | KeyAction::CollapseAll
-        | KeyAction::ExpandAll => {
+        | KeyAction::ExpandAll
+        | KeyAction::SetRoot
+        | KeyAction::RootUp => {
             tree_ops::handle(action, state, navigator, focused_path, entries)?;
             Ok(ActionResult::Continue)
         }
@@ -159,43 +321,87 @@ pub fn handle_action(
             Ok(ActionResult::Continue)
         }
 
+        // Marks overview
+        KeyAction::OpenMarksOverview
+        | KeyAction::MarksUp
+        | KeyAction::MarksDown
+        | KeyAction::MarksJump
+        | KeyAction::MarksUnmark => {
+            selection::handle_marks_overview(action, state);
+            Ok(ActionResult::Continue)
+        }
+
         // File operations
         KeyAction::Paste
+        | KeyAction::PasteCopy
+        | KeyAction::ResolvePasteConflict { .. }
+        | KeyAction::ResolveRenameConflict { .. }
         | KeyAction::ConfirmDelete
+        | KeyAction::ConfirmDeletePermanent
         | KeyAction::ExecuteDelete
         | KeyAction::StartRename
         | KeyAction::StartNewFile
-        | KeyAction::StartNewDir => {
+        | KeyAction::StartNewDir
+        | KeyAction::CreateSymlink
+        | KeyAction::CreateHardlink
+        | KeyAction::ToggleLinkRelative => {
             file_ops::handle(action, state, navigator, focused_path, entries)?;
             Ok(ActionResult::Continue)
         }
 
         // Search
-        KeyAction::StartSearch | KeyAction::SearchNext | KeyAction::SearchPrev => {
+        KeyAction::StartSearch
+        | KeyAction::SearchNext
+        | KeyAction::SearchPrev
+        | KeyAction::ToggleCaseSensitive => {
             search::handle(action, state, entries);
             Ok(ActionResult::Continue)
         }
 
+        // Content search
+        KeyAction::StartContentSearch
+        | KeyAction::ContentSearchUp
+        | KeyAction::ContentSearchDown
+        | KeyAction::ContentSearchSelect => {
+            search::handle_content(action, state);
+            Ok(ActionResult::Continue)
+        }
+
+        // Command over marked files
+        KeyAction::StartForeachCommand
+        | KeyAction::CommandOutputUp
+        | KeyAction::CommandOutputDown => {
+            command::handle_foreach(action, state, focused_path);
+            Ok(ActionResult::Continue)
+        }
+
         // Input confirmation
         KeyAction::ConfirmInput { value } => {
-            input::handle_confirm(value, state, navigator, focused_path)?;
+            input::handle_confirm(value, state, navigator, focused_path, entries)?;
             Ok(ActionResult::Continue)
         }
 
         // Display and preview
         KeyAction::ToggleHidden
+        | KeyAction::ToggleHiddenHere
         | KeyAction::OpenPreview
         | KeyAction::ToggleQuickPreview
+        | KeyAction::ResizePreview { .. }
         | KeyAction::ShowHelp
         | KeyAction::ToggleFocus
         | KeyAction::FocusTree
         | KeyAction::FocusPreview
         | KeyAction::CopyPath
+        | KeyAction::CopyRelativePath
         | KeyAction::CopyFilename
         | KeyAction::CopyContent
         | KeyAction::CopyForClaude
         | KeyAction::Refresh
-        | KeyAction::CycleSort => {
+        | KeyAction::CycleSort
+        | KeyAction::StartSortSelect
+        | KeyAction::SetSort { .. }
+        | KeyAction::ToggleSortReverse
+        | KeyAction::ToggleDirsFirst => {
             display::handle(action, state, navigator, focused_path)?;
             Ok(ActionResult::Continue)
         }
@@ -206,12 +412,19 @@ pub fn handle_action(
         | KeyAction::PreviewPageUp
         | KeyAction::PreviewPageDown
         | KeyAction::PreviewToTop
-        | KeyAction::PreviewToBottom => {
+        | KeyAction::PreviewToBottom
+        | KeyAction::PreviewScrollLeft
+        | KeyAction::PreviewScrollRight
+        | KeyAction::PreviewNextConflict
+        | KeyAction::PreviewPrevConflict => {
             display::handle_preview_scroll(
                 action,
                 state,
-                text_preview,
+                text_preview
+                    .as_mut()
+                    .or(pdf_preview.as_mut().and_then(PdfView::text_mut)),
                 hex_preview,
+                csv_preview,
                 archive_preview,
                 diff_preview,
                 custom_preview,
@@ -219,11 +432,50 @@ pub fn handle_action(
             Ok(ActionResult::Continue)
         }
 
+        // Search in the fullscreen preview
+        KeyAction::StartPreviewSearch
+        | KeyAction::PreviewSearchNext
+        | KeyAction::PreviewSearchPrev => {
+            display::handle_preview_search(
+                &action,
+                state,
+                text_preview
+                    .as_mut()
+                    .or(pdf_preview.as_mut().and_then(PdfView::text_mut)),
+            );
+            Ok(ActionResult::Continue)
+        }
+
         // Pick mode selection
-        KeyAction::PickSelect => display::handle_pick_select(state, focused_path, context),
+        KeyAction::PickSelect => {
+            // With --expand-dirs Enter on a directory browses into it instead of picking it
+            let expands = state.pick_mode
+                && state.pick_dirs == PickDirs::Expand
+                && state.selected_paths.is_empty()
+                && focused_path.as_ref().is_some_and(|p| p.is_dir());
+            if expands {
+                tree_ops::handle(
+                    KeyAction::ToggleExpand,
+                    state,
+                    navigator,
+                    focused_path,
+                    entries,
+                )?;
+                return Ok(ActionResult::Continue);
+            }
+            if state.pick_mode {
+                recent::record_targets(state, focused_path);
+            }
+            display::handle_pick_select(state, focused_path, context)
+        }
 
         // Select mode confirmation
-        KeyAction::SelectConfirm => display::handle_select_confirm(state, focused_path, context),
+        KeyAction::SelectConfirm => {
+            if state.select_mode {
+                recent::record_targets(state, focused_path);
+            }
+            display::handle_select_confirm(state, focused_path, context)
+        }
 
         // Fuzzy finder
         KeyAction::OpenFuzzyFinder | KeyAction::FuzzyUp | KeyAction::FuzzyDown => {
@@ -250,18 +502,74 @@ pub fn handle_action(
             filter::handle(action, state);
             Ok(ActionResult::Continue)
         }
+        KeyAction::StartLiveFilter | KeyAction::LockLiveFilter => {
+            filter::handle_live(action, state, focused_path);
+            Ok(ActionResult::Continue)
+        }
 
         // PDF navigation
-        KeyAction::PdfPrevPage | KeyAction::PdfNextPage => {
-            display::handle_pdf_navigation(action, state, pdf_preview, image_picker);
+        KeyAction::PdfPrevPage | KeyAction::PdfNextPage | KeyAction::TogglePdfText => {
+            display::handle_pdf_navigation(action, state, focused_path, pdf_preview, image_picker);
             Ok(ActionResult::Continue)
         }
 
         // Git operations
-        KeyAction::GitStage | KeyAction::GitUnstage => {
+        KeyAction::GitStage | KeyAction::GitUnstage | KeyAction::GitDiscard => {
             git_ops::handle(action, state, focused_path.as_ref());
             Ok(ActionResult::Continue)
         }
+        KeyAction::GitDiscardConfirm => {
+            git_ops::discard_confirmed(state, navigator)?;
+            Ok(ActionResult::Continue)
+        }
+        KeyAction::ToggleGitChangedOnly => {
+            git_ops::toggle_changed_only(state, navigator, focused_path.as_ref())?;
+            Ok(ActionResult::Continue)
+        }
+        KeyAction::StartDiffRevision => {
+            git_ops::start_diff_revision(state, focused_path.as_ref());
+            Ok(ActionResult::Continue)
+        }
+        KeyAction::DiffMarked => {
+            selection::diff_marked(state, focused_path.as_ref());
+            Ok(ActionResult::Continue)
+        }
+        KeyAction::StartYankGit | KeyAction::YankGitDiff | KeyAction::YankGitBlame => {
+            git_ops::yank(action, state, focused_path.as_ref());
+            Ok(ActionResult::Continue)
+        }
+        KeyAction::ToggleBlame => {
+            git_ops::toggle_blame(
+                state,
+                focused_path.as_ref(),
+                text_preview
+                    .as_mut()
+                    .or(pdf_preview.as_mut().and_then(PdfView::text_mut)),
+            );
+            Ok(ActionResult::Continue)
+        }
+        KeyAction::CompleteDiffRevision => {
+            git_ops::complete_diff_revision(state);
+            Ok(ActionResult::Continue)
+        }
+        KeyAction::StartGitCommit { amend } => {
+            git_ops::start_commit(state, amend);
+            Ok(ActionResult::Continue)
+        }
+        KeyAction::InputNewline => {
+            input::insert_newline(state);
+            Ok(ActionResult::Continue)
+        }
+        KeyAction::OpenGitStash
+        | KeyAction::GitStashUp
+        | KeyAction::GitStashDown
+        | KeyAction::GitStashApply
+        | KeyAction::GitStashPop
+        | KeyAction::GitStashDrop
+        | KeyAction::GitStashDropConfirm => {
+            git_ops::handle_stash(action, state, navigator)?;
+            Ok(ActionResult::Continue)
+        }
 
         // Bulk rename operations
         KeyAction::StartBulkRename
@@ -276,6 +584,12 @@ pub fn handle_action(
             Ok(ActionResult::Continue)
         }
 
+        // Keymap reload (handled in event loop, which owns the registry)
+        KeyAction::ReloadKeymap => Ok(ActionResult::Continue),
+
+        // External editor rename (handled in event loop, needs the terminal)
+        KeyAction::EditorRename => Ok(ActionResult::Continue),
+
         // Shell integration - open subshell
         KeyAction::OpenSubshell => {
             command::open_subshell(state, focused_path.as_ref());
@@ -312,6 +626,13 @@ pub fn handle_action(
             Ok(ActionResult::Continue)
         }
 
+        KeyAction::SelectByPattern
+        | KeyAction::DeselectByPattern
+        | KeyAction::SelectByPatternInSubtree => {
+            selection::start_pattern_select(action, state, focused_path);
+            Ok(ActionResult::Continue)
+        }
+
         // Extended smart selection
         KeyAction::SelectByExtension { index } => {
             selection::select_by_extension(state, entries, index);
@@ -340,9 +661,14 @@ pub fn handle_action(
 
         // Toggle peek mode
         KeyAction::TogglePeekMode
+        | KeyAction::TogglePreviewWrap
+        | KeyAction::TogglePreviewLineNumbers
+        | KeyAction::TogglePreviewBorders
+        | KeyAction::ToggleRelativeNumbers
         | KeyAction::CopyCompact
         | KeyAction::CopyContextPack
         | KeyAction::CopyContextPackReview
+        | KeyAction::CopyConcat
         | KeyAction::ToggleAiFocus
         | KeyAction::OpenAiHistory
         | KeyAction::AiHistoryUp
@@ -352,8 +678,31 @@ pub fn handle_action(
             Ok(ActionResult::Continue)
         }
 
+        // Recent files
+        KeyAction::OpenRecentFiles
+        | KeyAction::RecentFilesUp
+        | KeyAction::RecentFilesDown
+        | KeyAction::RecentFilesSelect => {
+            recent::handle(action, state);
+            Ok(ActionResult::Continue)
+        }
+
+        // Operation history
+        KeyAction::OpenOperationHistory
+        | KeyAction::OperationHistoryUp
+        | KeyAction::OperationHistoryDown
+        | KeyAction::OperationHistoryReveal => {
+            oplog::handle(action, state);
+            Ok(ActionResult::Continue)
+        }
+        KeyAction::Undo => {
+            oplog::undo(state, navigator)?;
+            Ok(ActionResult::Continue)
+        }
+
         // Custom command execution
         KeyAction::RunCommand { name } => {
+            recent::record_targets(state, focused_path);
             let selected: Vec<PathBuf> = state.selected_paths.iter().cloned().collect();
             match command::execute_command(
                 &name,
@@ -371,7 +720,7 @@ pub fn handle_action(
                     }
                 }
                 CommandResult::Error(err) => {
-                    state.set_message(format!("Error: {}", err));
+                    state.set_error(format!("Error: {}", err));
                 }
                 CommandResult::NotFound => {
                     state.set_message(format!("Command '{}' not found", name));
@@ -1,6 +1,6 @@
 //! Navigation action handlers
 //!
-//! Handles MoveUp, MoveDown, MoveToTop, MoveToBottom
+//! Handles MoveUp, MoveDown, MoveToTop, MoveToBottom, with an optional count
 
 use crate::core::{AppState, ViewMode};
 use crate::handler::key::KeyAction;
@@ -9,7 +9,15 @@ use super::selection::select_range;
 use super::EntrySnapshot;
 
 /// Handle navigation actions
-pub fn handle(action: KeyAction, state: &mut AppState, entries: &[EntrySnapshot]) {
+///
+/// A `count` moves that many rows up or down, and makes `g`/`G` jump to
+/// that row (1-based) instead of the top or bottom.
+pub fn handle(
+    action: KeyAction,
+    state: &mut AppState,
+    entries: &[EntrySnapshot],
+    count: Option<usize>,
+) {
     // Get anchor if in visual select mode (before navigation)
     let visual_anchor = if let ViewMode::VisualSelect { anchor } = state.mode {
         Some(anchor)
@@ -17,20 +25,25 @@ pub fn handle(action: KeyAction, state: &mut AppState, entries: &[EntrySnapshot]
         None
     };
 
-    match action {
-        KeyAction::MoveUp => {
-            state.focus_index = state.focus_index.saturating_sub(1);
+    let last = entries.len().saturating_sub(1);
+    match (action, count) {
+        (KeyAction::MoveUp, _) => {
+            state.focus_index = state.focus_index.saturating_sub(count.unwrap_or(1));
         }
-        KeyAction::MoveDown => {
-            if state.focus_index < entries.len().saturating_sub(1) {
-                state.focus_index += 1;
-            }
+        (KeyAction::MoveDown, _) if state.focus_index < last => {
+            state.focus_index = state
+                .focus_index
+                .saturating_add(count.unwrap_or(1))
+                .min(last);
         }
-        KeyAction::MoveToTop => {
+        (KeyAction::MoveToTop | KeyAction::MoveToBottom, Some(row)) => {
+            state.focus_index = row.saturating_sub(1).min(last);
+        }
+        (KeyAction::MoveToTop, None) => {
             state.focus_index = 0;
         }
-        KeyAction::MoveToBottom => {
-            state.focus_index = entries.len().saturating_sub(1);
+        (KeyAction::MoveToBottom, None) => {
+            state.focus_index = last;
         }
         _ => {}
     }
@@ -0,0 +1,92 @@
+//! Operation history action handlers
+//!
+//! Handles OpenOperationHistory, OperationHistoryUp, OperationHistoryDown,
+//! OperationHistoryReveal, Undo
+
+use crate::core::{AppState, ViewMode};
+use crate::handler::key::KeyAction;
+use crate::tree::TreeNavigator;
+
+use super::reload_tree;
+
+/// Handle operation history popup actions
+pub fn handle(action: KeyAction, state: &mut AppState) {
+    match action {
+        KeyAction::OpenOperationHistory => {
+            if state.operation_log.is_empty() {
+                state.set_message("No file operations yet");
+            } else {
+                state.mode = ViewMode::OperationHistory { selected: 0 };
+                state.set_message("Operation history (j/k + Enter)");
+            }
+        }
+        KeyAction::OperationHistoryUp => {
+            if let ViewMode::OperationHistory { selected } = &mut state.mode {
+                *selected = selected.saturating_sub(1);
+            }
+        }
+        KeyAction::OperationHistoryDown => {
+            if let ViewMode::OperationHistory { selected } = &mut state.mode {
+                let max_index = state.operation_log.len().saturating_sub(1);
+                *selected = (*selected + 1).min(max_index);
+            }
+        }
+        KeyAction::OperationHistoryReveal => {
+            if let ViewMode::OperationHistory { selected } = state.mode {
+                state.mode = ViewMode::Browse;
+                let target = state
+                    .operation_log
+                    .entries()
+                    .iter()
+                    .rev()
+                    .nth(selected)
+                    .and_then(|op| op.reveal_target())
+                    .map(|path| path.to_path_buf());
+                match target {
+                    Some(path) if path.exists() && path.starts_with(&state.root) => {
+                        // Reveal and focus through the fuzzy jump path
+                        state.fuzzy_jump_target = Some(path);
+                        state.clear_message();
+                    }
+                    Some(path) if path.exists() => {
+                        state.set_message(format!("Outside current root: {}", path.display()));
+                    }
+                    Some(path) => {
+                        state.set_warning(format!("No longer exists: {}", path.display()));
+                    }
+                    None => state.set_message("Nothing to reveal for this operation"),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Revert the last logged operation if it is a rename, move or trash
+///
+/// Only one level is kept: an undone operation is not undone again.
+pub fn undo(state: &mut AppState, navigator: &mut TreeNavigator) -> anyhow::Result<()> {
+    let root = state.root.clone();
+    let Some(operation) = state.operation_log.last_mut().filter(|op| !op.undone) else {
+        state.set_message("Nothing to undo");
+        return Ok(());
+    };
+    let summary = operation.summary(&root);
+    if !operation.kind.is_undoable() {
+        state.set_warning(format!("Cannot undo: {}", summary));
+        return Ok(());
+    }
+    let result = operation.undo();
+    let reveal = operation.sources.first().cloned();
+
+    reload_tree(navigator, state)?;
+    state.refresh_git_status();
+    match result {
+        Ok(()) => {
+            state.fuzzy_jump_target = reveal.filter(|path| path.starts_with(&state.root));
+            state.set_message(format!("Undone: {}", summary));
+        }
+        Err(e) => state.set_error(format!("Undo failed: {}", e)),
+    }
+    Ok(())
+}
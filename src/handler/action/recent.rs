@@ -0,0 +1,79 @@
+//! Recent files action handlers
+//!
+//! Handles OpenRecentFiles, RecentFilesUp, RecentFilesDown, RecentFilesSelect
+
+use std::path::{Path, PathBuf};
+
+use crate::core::{AppState, ViewMode};
+use crate::handler::key::KeyAction;
+use crate::render::recent_matches;
+
+/// Record an opened file in the recent files list (directories are ignored)
+pub fn record_opened(state: &mut AppState, path: &Path) {
+    if path.is_file() {
+        state.recent_files.record(path);
+        let _ = state.recent_files.save();
+    }
+}
+
+/// Record the files an action operates on (selection, or the focused entry)
+pub fn record_targets(state: &mut AppState, focused_path: &Option<PathBuf>) {
+    let targets: Vec<PathBuf> = if state.selected_paths.is_empty() {
+        focused_path.clone().into_iter().collect()
+    } else {
+        state.selected_paths.iter().cloned().collect()
+    };
+    for path in &targets {
+        record_opened(state, path);
+    }
+}
+
+/// Handle recent files popup actions
+pub fn handle(action: KeyAction, state: &mut AppState) {
+    match action {
+        KeyAction::OpenRecentFiles => {
+            if state.recent_files.is_empty() {
+                state.set_message("No recent files");
+            } else {
+                state.mode = ViewMode::RecentFiles {
+                    query: String::new(),
+                    selected: 0,
+                };
+                state.set_message("Recent files (type to filter, ↑/↓ + Enter)");
+            }
+        }
+        KeyAction::RecentFilesUp => {
+            if let ViewMode::RecentFiles { selected, .. } = &mut state.mode {
+                *selected = selected.saturating_sub(1);
+            }
+        }
+        KeyAction::RecentFilesDown => {
+            if let ViewMode::RecentFiles { query, selected } = &state.mode {
+                let max_index = recent_matches(state, query).len().saturating_sub(1);
+                let selected = (*selected + 1).min(max_index);
+                state.mode = ViewMode::RecentFiles {
+                    query: query.clone(),
+                    selected,
+                };
+            }
+        }
+        KeyAction::RecentFilesSelect => {
+            if let ViewMode::RecentFiles { query, selected } = &state.mode {
+                let target = recent_matches(state, query)
+                    .get(*selected)
+                    .map(|entry| entry.path.clone());
+                state.mode = ViewMode::Browse;
+                if let Some(path) = target {
+                    if path.starts_with(&state.root) {
+                        // Reveal and focus through the fuzzy jump path
+                        state.fuzzy_jump_target = Some(path);
+                        state.clear_message();
+                    } else {
+                        state.set_message(format!("Outside current root: {}", path.display()));
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
@@ -1,11 +1,13 @@
 //! Search action handlers
 //!
-//! Handles StartSearch, SearchNext, SearchPrev, and fuzzy finder actions
+//! Handles StartSearch, SearchNext, SearchPrev, case sensitivity, content search,
+//! and fuzzy finder actions
 
 use std::path::PathBuf;
 
-use crate::core::{AppState, ViewMode};
+use crate::core::{is_case_sensitive, AppState, InputPurpose, ViewMode};
 use crate::handler::key::KeyAction;
+use crate::integrate::{ContentSearch, SearchQuery};
 
 use super::EntrySnapshot;
 
@@ -24,10 +26,53 @@ pub fn handle(action: KeyAction, state: &mut AppState, entries: &[EntrySnapshot]
         KeyAction::SearchPrev => {
             search_direction(state, entries, SearchDirection::Backward);
         }
+        KeyAction::ToggleCaseSensitive => {
+            state.case_sensitive = !state.case_sensitive;
+            state.set_message(if state.case_sensitive {
+                "Case sensitive: ON"
+            } else {
+                "Case sensitive: OFF (smart case)"
+            });
+            update_search_matches(state, entries);
+        }
         _ => {}
     }
 }
 
+/// Check if a name contains the query, honoring case sensitivity
+pub fn name_matches(name: &str, query: &str, case_sensitive: bool) -> bool {
+    if is_case_sensitive(query, case_sensitive) {
+        name.contains(query)
+    } else {
+        name.to_lowercase().contains(&query.to_lowercase())
+    }
+}
+
+/// Refresh the match count for the query being typed
+///
+/// The current position is the focused entry's place among the matches, or
+/// 0 if the focused entry does not match.
+pub fn update_search_matches(state: &mut AppState, entries: &[EntrySnapshot]) {
+    let ViewMode::Search { query } = &state.mode else {
+        return;
+    };
+    if query.is_empty() {
+        state.search_matches = None;
+        return;
+    }
+    let matches: Vec<usize> = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| name_matches(&e.name, query, state.case_sensitive))
+        .map(|(i, _)| i)
+        .collect();
+    let current = matches
+        .iter()
+        .position(|&i| i == state.focus_index)
+        .map_or(0, |p| p + 1);
+    state.search_matches = Some((current, matches.len()));
+}
+
 /// Search direction
 enum SearchDirection {
     Forward,
@@ -42,13 +87,11 @@ fn search_direction(state: &mut AppState, entries: &[EntrySnapshot], direction:
             return;
         }
 
-        let query_lower = query.to_lowercase();
-
         // Collect all matching indices
         let matches: Vec<usize> = entries
             .iter()
             .enumerate()
-            .filter(|(_, e)| e.name.to_lowercase().contains(&query_lower))
+            .filter(|(_, e)| name_matches(&e.name, query, state.case_sensitive))
             .map(|(i, _)| i)
             .collect();
 
@@ -107,6 +150,65 @@ pub fn handle_fuzzy(action: KeyAction, state: &mut AppState) {
     }
 }
 
+/// Handle content search actions
+pub fn handle_content(action: KeyAction, state: &mut AppState) {
+    match action {
+        KeyAction::StartContentSearch => {
+            state.mode = ViewMode::Input {
+                purpose: InputPurpose::ContentSearch,
+                buffer: String::new(),
+                cursor: 0,
+            };
+        }
+        KeyAction::ContentSearchUp => {
+            if let ViewMode::ContentSearch { selected } = &mut state.mode {
+                *selected = selected.saturating_sub(1);
+            }
+        }
+        KeyAction::ContentSearchDown => {
+            let count = state.content_search.as_ref().map_or(0, |s| s.matches.len());
+            if let ViewMode::ContentSearch { selected } = &mut state.mode {
+                *selected = (*selected + 1).min(count.saturating_sub(1));
+            }
+        }
+        KeyAction::ContentSearchSelect => {
+            if let ViewMode::ContentSearch { selected } = state.mode {
+                let hit = state
+                    .content_search
+                    .take()
+                    .and_then(|mut search| search.matches.drain(..).nth(selected));
+                state.mode = ViewMode::Browse;
+                if let Some(hit) = hit {
+                    let rel = hit.path.strip_prefix(&state.root).unwrap_or(&hit.path);
+                    state.set_message(format!("{}:{}", rel.display(), hit.line));
+                    state.fuzzy_jump_target = Some(hit.path);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Start searching the contents of the files under the root
+///
+/// Any search still running is cancelled when its handle is replaced.
+pub fn start_content_search(state: &mut AppState, text: &str) {
+    if text.is_empty() {
+        state.content_search = None;
+        state.mode = ViewMode::Browse;
+        return;
+    }
+    let query = SearchQuery {
+        text: text.to_string(),
+        case_sensitive: is_case_sensitive(text, state.case_sensitive),
+        max_results: state.content_search_max_results,
+    };
+    let search = ContentSearch::start(&state.root, query);
+    state.set_message(search.status());
+    state.content_search = Some(search);
+    state.mode = ViewMode::ContentSearch { selected: 0 };
+}
+
 /// Handle fuzzy finder confirm action
 pub fn handle_fuzzy_confirm(path: PathBuf, state: &mut AppState) {
     if !path.as_os_str().is_empty() {
@@ -1,18 +1,24 @@
 //! Selection and clipboard action handlers
 //!
 //! Handles ToggleMark, ClearMarks, Copy, Cut, SelectAll, InvertSelection,
-//! SelectGitChanged, SelectTestPair, SelectByExtension, SelectRecentCommit, SelectGitStaged
+//! SelectGitChanged, SelectTestPair, SelectByExtension, SelectRecentCommit, SelectGitStaged,
+//! and keeps the selection totals shown in the status bar up to date
 
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use crate::action::Clipboard;
-use crate::core::AppState;
+use globset::{GlobBuilder, GlobMatcher};
+
+use crate::action::{summarize_delete, Clipboard};
+use crate::core::{diff_files, is_case_sensitive, AppState, InputPurpose, PatternSelect, ViewMode};
 use crate::git::FileStatus;
 use crate::handler::key::KeyAction;
 use crate::integrate::collect_related_candidates;
 
-use super::EntrySnapshot;
+use super::{get_target_directory, EntrySnapshot};
 
 /// Common file extensions mapped to Ctrl+1..9
 const EXTENSION_SLOTS: [&[&str]; 9] = [
@@ -27,6 +33,83 @@ const EXTENSION_SLOTS: [&[&str]; 9] = [
     &["css", "scss", "sass"],  // Ctrl+9: Stylesheets
 ];
 
+/// Maximum number of entries visited when summarizing the selection
+///
+/// Lower than the delete limit since the summary is refreshed while marking.
+pub const SELECTION_SUMMARY_LIMIT: usize = 10_000;
+
+/// Recompute the selection totals if the marked paths changed
+pub fn refresh_selection_summary(state: &mut AppState) {
+    if state.selected_paths.is_empty() {
+        state.selection_summary = None;
+        return;
+    }
+    let fingerprint = selection_fingerprint(&state.selected_paths);
+    if state.selection_summary.is_some() && state.selection_fingerprint == fingerprint {
+        return;
+    }
+    let paths: Vec<PathBuf> = state.selected_paths.iter().cloned().collect();
+    state.selection_summary = Some(summarize_delete(&paths, SELECTION_SUMMARY_LIMIT));
+    state.selection_fingerprint = fingerprint;
+}
+
+/// Order-independent hash of a set of paths
+fn selection_fingerprint(paths: &HashSet<PathBuf>) -> u64 {
+    paths.iter().fold(paths.len() as u64, |acc, path| {
+        let mut hasher = DefaultHasher::new();
+        path.hash(&mut hasher);
+        acc.wrapping_add(hasher.finish())
+    })
+}
+
+/// Show the diff of the two marked files in the preview
+///
+/// The focused file (or the later path) is shown against the other one.
+pub fn diff_marked(state: &mut AppState, focused_path: Option<&PathBuf>) {
+    if state.selected_paths.len() != 2 {
+        state.set_error(format!(
+            "Mark exactly two files to compare ({} marked)",
+            state.selected_paths.len()
+        ));
+        return;
+    }
+    let mut pair: Vec<PathBuf> = state.selected_paths.iter().cloned().collect();
+    pair.sort();
+    if let Some(dir) = pair.iter().find(|p| p.is_dir()) {
+        state.set_error(format!("Cannot compare a directory: {}", dir.display()));
+        return;
+    }
+    if focused_path == Some(&pair[0]) {
+        pair.swap(0, 1);
+    }
+    let (base, shown) = (pair.remove(0), pair.remove(0));
+
+    match diff_files(&base, &shown) {
+        Ok(diff) if diff.is_empty() => state.set_message("Files are identical"),
+        Ok(diff) => {
+            state.set_message(format!(
+                "Comparing {} with {} (+{} -{})",
+                get_name(&shown),
+                get_name(&base),
+                diff.additions,
+                diff.deletions
+            ));
+            state.compare_files = Some((base, shown.clone()));
+            state.compare_diff = Some(diff);
+            state.preview_visible = true;
+            // The diff stays up while the shown file is focused
+            state.fuzzy_jump_target = Some(shown);
+        }
+        Err(e) => state.set_error(format!("Failed: compare - {}", e)),
+    }
+}
+
+fn get_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
 /// Handle selection and clipboard actions
 pub fn handle(action: KeyAction, state: &mut AppState, focused_path: &Option<PathBuf>) {
     match action {
@@ -74,6 +157,57 @@ pub fn handle(action: KeyAction, state: &mut AppState, focused_path: &Option<Pat
     }
 }
 
+/// Handle marks overview actions
+pub fn handle_marks_overview(action: KeyAction, state: &mut AppState) {
+    match action {
+        KeyAction::OpenMarksOverview => {
+            if state.selected_paths.is_empty() {
+                state.set_message("No marked entries");
+            } else {
+                state.mode = ViewMode::MarksOverview { selected: 0 };
+                state.set_message("Marks (j/k, Enter: jump, d: unmark)");
+            }
+        }
+        KeyAction::MarksUp => {
+            if let ViewMode::MarksOverview { selected } = &mut state.mode {
+                *selected = selected.saturating_sub(1);
+            }
+        }
+        KeyAction::MarksDown => {
+            let max_index = state.selected_paths.len().saturating_sub(1);
+            if let ViewMode::MarksOverview { selected } = &mut state.mode {
+                *selected = (*selected + 1).min(max_index);
+            }
+        }
+        KeyAction::MarksJump => {
+            if let ViewMode::MarksOverview { selected } = state.mode {
+                state.mode = ViewMode::Browse;
+                if let Some(path) = state.sorted_marks().into_iter().nth(selected) {
+                    state.fuzzy_jump_target = Some(path);
+                    state.clear_message();
+                }
+            }
+        }
+        KeyAction::MarksUnmark => {
+            if let ViewMode::MarksOverview { selected } = state.mode {
+                if let Some(path) = state.sorted_marks().into_iter().nth(selected) {
+                    state.selected_paths.remove(&path);
+                }
+                let remaining = state.selected_paths.len();
+                state.mode = if remaining == 0 {
+                    state.set_message("No marked entries");
+                    ViewMode::Browse
+                } else {
+                    ViewMode::MarksOverview {
+                        selected: selected.min(remaining - 1),
+                    }
+                };
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Handle selection with entries context
 pub fn handle_with_entries(action: KeyAction, state: &mut AppState, entries: &[EntrySnapshot]) {
     match action {
@@ -112,6 +246,146 @@ pub fn handle_with_entries(action: KeyAction, state: &mut AppState, entries: &[E
     }
 }
 
+/// Entries visited at most when marking a subtree by pattern
+pub const SUBTREE_PATTERN_LIMIT: usize = 10_000;
+
+/// Open the glob prompt for marking or unmarking entries by name
+pub fn start_pattern_select(
+    action: KeyAction,
+    state: &mut AppState,
+    focused_path: &Option<PathBuf>,
+) {
+    let action = match action {
+        KeyAction::SelectByPattern => PatternSelect::Select,
+        KeyAction::DeselectByPattern => PatternSelect::Deselect,
+        KeyAction::SelectByPatternInSubtree => PatternSelect::Subtree {
+            root: get_target_directory(focused_path.as_ref(), &state.root),
+        },
+        _ => return,
+    };
+    state.mode = ViewMode::Input {
+        purpose: InputPurpose::SelectPattern { action },
+        buffer: String::new(),
+        cursor: 0,
+    };
+}
+
+/// Compile a selection glob, ignoring case unless smart case says otherwise
+fn compile_pattern(pattern: &str, case_sensitive: bool) -> Result<GlobMatcher, globset::Error> {
+    GlobBuilder::new(pattern)
+        .case_insensitive(!is_case_sensitive(pattern, case_sensitive))
+        .literal_separator(true)
+        .build()
+        .map(|glob| glob.compile_matcher())
+}
+
+/// Mark or unmark the entries whose names match `pattern`
+///
+/// Full glob syntax (`*`, `?`, `[abc]`, `{a,b}`) with smart case. A pattern
+/// containing `/` is matched against the path relative to the root (or the
+/// subtree), so `**` spans directories, e.g. `src/**/*.rs`.
+pub fn apply_pattern_select(
+    state: &mut AppState,
+    entries: &[EntrySnapshot],
+    action: &PatternSelect,
+    pattern: &str,
+) {
+    if pattern.is_empty() {
+        return;
+    }
+    let glob = match compile_pattern(pattern, state.case_sensitive) {
+        Ok(glob) => glob,
+        Err(e) => {
+            state.set_error(format!("Invalid pattern: {}", e.kind()));
+            return;
+        }
+    };
+    let base = match action {
+        PatternSelect::Subtree { root } => root.clone(),
+        _ => state.root.clone(),
+    };
+    let by_path = pattern.contains('/');
+    let matches = |path: &Path| {
+        if by_path {
+            path.strip_prefix(&base)
+                .is_ok_and(|relative| glob.is_match(relative))
+        } else {
+            path.file_name().is_some_and(|name| glob.is_match(name))
+        }
+    };
+
+    match action {
+        PatternSelect::Select => {
+            let mut count = 0;
+            for entry in entries.iter().filter(|e| matches(&e.path)) {
+                state.selected_paths.insert(entry.path.clone());
+                count += 1;
+            }
+            if count > 0 {
+                state.set_message(format!("Selected {} item(s) matching {}", count, pattern));
+            } else {
+                state.set_message(format!("Nothing in view matches {}", pattern));
+            }
+        }
+        PatternSelect::Deselect => {
+            let before = state.selected_paths.len();
+            state.selected_paths.retain(|path| !matches(path));
+            let count = before - state.selected_paths.len();
+            state.set_message(format!("Deselected {} item(s) matching {}", count, pattern));
+        }
+        PatternSelect::Subtree { root } => {
+            let mut found = Vec::new();
+            let mut visited = 0;
+            collect_matching(root, state.show_hidden, &matches, &mut found, &mut visited);
+            let count = found.len();
+            state.selected_paths.extend(found);
+            let truncated = if visited >= SUBTREE_PATTERN_LIMIT {
+                format!(" (stopped after {} entries)", SUBTREE_PATTERN_LIMIT)
+            } else {
+                String::new()
+            };
+            state.set_message(format!(
+                "Selected {} item(s) matching {} under {}{}",
+                count,
+                pattern,
+                root.file_name()
+                    .unwrap_or(root.as_os_str())
+                    .to_string_lossy(),
+                truncated
+            ));
+        }
+    }
+}
+
+/// Recursively collect matching paths under `dir` (symlinked dirs are not followed)
+fn collect_matching(
+    dir: &Path,
+    show_hidden: bool,
+    matches: &dyn Fn(&Path) -> bool,
+    found: &mut Vec<PathBuf>,
+    visited: &mut usize,
+) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if *visited >= SUBTREE_PATTERN_LIMIT {
+            return;
+        }
+        *visited += 1;
+        let path = entry.path();
+        if !show_hidden && entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        if matches(&path) {
+            found.push(path.clone());
+        }
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            collect_matching(&path, show_hidden, matches, found, visited);
+        }
+    }
+}
+
 /// Select range of entries (for visual select mode)
 pub fn select_range(
     state: &mut AppState,
@@ -136,7 +410,7 @@ pub fn select_git_changed(state: &mut AppState, entries: &[EntrySnapshot]) {
     let git_status = match &state.git_status {
         Some(status) => status,
         None => {
-            state.set_message("Not in a git repository");
+            state.set_warning("Not in a git repository");
             return;
         }
     };
@@ -235,7 +509,7 @@ pub fn select_recent_commit(state: &mut AppState, entries: &[EntrySnapshot]) {
             .map(|s| s.to_string())
             .collect(),
         _ => {
-            state.set_message("Failed to get recent commit files");
+            state.set_error("Failed to get recent commit files");
             return;
         }
     };
@@ -269,7 +543,7 @@ pub fn select_git_staged(state: &mut AppState, entries: &[EntrySnapshot]) {
     let git_status = match &state.git_status {
         Some(status) => status,
         None => {
-            state.set_message("Not in a git repository");
+            state.set_warning("Not in a git repository");
             return;
         }
     };
@@ -1,28 +1,31 @@
 //! Tests for action handlers
 
 use std::path::Path;
+use std::sync::Arc;
 
 use tempfile::TempDir;
 
-use crate::core::{AppState, FocusTarget, ViewMode};
+use crate::core::{AppState, FocusTarget, PendingAction, ViewMode};
 use crate::handler::key::KeyAction;
 use crate::integrate::exit_code;
 use crate::render::{
-    ArchiveEntry, ArchivePreview, CustomPreview, DiffPreview, HexPreview, PdfPreview, Picker,
-    TextPreview,
+    ArchiveEntry, ArchivePreview, BlamePreview, CsvPreview, CustomPreview, DiffPreview, HexPreview,
+    PdfView, Picker, TextPreview,
 };
 use crate::tree::TreeNavigator;
 
 use super::{
-    get_filename_str, get_target_directory, handle_action, ActionContext, ActionResult,
-    EntrySnapshot,
+    displayed_entries, focus_best_live_match, get_filename_str, get_target_directory,
+    handle_action, refresh_selection_summary, reveal_and_focus, update_search_matches,
+    ActionContext, ActionResult, EntrySnapshot,
 };
 
 /// Helper macro to call handle_action with all required preview arguments
 macro_rules! call_handle_action {
     ($action:expr, $state:expr, $navigator:expr, $path:expr, $entries:expr, $context:expr,
      $text_preview:expr, $hex_preview:expr, $archive_preview:expr) => {{
-        let mut pdf_preview: Option<PdfPreview> = None;
+        let mut csv_preview: Option<CsvPreview> = None;
+        let mut pdf_preview: Option<PdfView> = None;
         let mut diff_preview: Option<DiffPreview> = None;
         let mut custom_preview: Option<CustomPreview> = None;
         let mut image_picker: Option<Picker> = None;
@@ -35,6 +38,7 @@ macro_rules! call_handle_action {
             $context,
             $text_preview,
             $hex_preview,
+            &mut csv_preview,
             $archive_preview,
             &mut pdf_preview,
             &mut diff_preview,
@@ -275,6 +279,83 @@ fn test_toggle_hidden_action() {
     assert!(state.show_hidden);
 }
 
+#[test]
+fn test_toggle_hidden_directory_scope_keeps_focus() {
+    let temp = TempDir::new().unwrap();
+    let sub = temp.path().join("sub");
+    std::fs::create_dir(&sub).unwrap();
+    std::fs::write(temp.path().join(".root_hidden"), "").unwrap();
+    std::fs::write(sub.join(".sub_hidden"), "").unwrap();
+    std::fs::write(sub.join("visible.txt"), "").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    state.hidden_scope = crate::core::HiddenScope::Directory;
+    let mut navigator = create_test_navigator(temp.path());
+    navigator.expand(&sub).unwrap();
+    let focused = sub.join("visible.txt");
+    let entries = create_test_entries(&navigator);
+    state.focus_index = entries.iter().position(|e| e.path == focused).unwrap();
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    call_handle_action!(
+        KeyAction::ToggleHidden,
+        &mut state,
+        &mut navigator,
+        &Some(focused.clone()),
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+
+    assert!(!state.show_hidden);
+    let shown = create_test_entries(&navigator);
+    let has = |path: &Path| shown.iter().any(|e| e.path == path);
+    assert!(has(&sub.join(".sub_hidden")));
+    assert!(!has(&temp.path().join(".root_hidden")));
+    // The cursor stays on the same file although an entry appeared above it
+    assert_eq!(shown[state.focus_index].path, focused);
+}
+
+#[test]
+fn test_toggle_hidden_directory_scope_turns_global_off() {
+    let temp = TempDir::new().unwrap();
+    std::fs::write(temp.path().join(".hidden"), "").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    state.hidden_scope = crate::core::HiddenScope::Directory;
+    state.show_hidden = true;
+    let mut navigator = create_test_navigator(temp.path());
+    navigator.set_show_hidden(true).unwrap();
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    call_handle_action!(
+        KeyAction::ToggleHidden,
+        &mut state,
+        &mut navigator,
+        &None,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+
+    assert!(!state.show_hidden);
+    let shown = create_test_entries(&navigator);
+    assert!(shown.iter().all(|e| e.path != temp.path().join(".hidden")));
+}
+
 #[test]
 fn test_open_preview_action() {
     let temp = TempDir::new().unwrap();
@@ -876,6 +957,68 @@ fn test_move_to_bottom() {
     assert_eq!(state.focus_index, last_index);
 }
 
+/// Test: a count prefix repeats j/k, retargets g/G, and is dropped by other actions
+#[test]
+fn test_count_prefix_moves() {
+    let temp = TempDir::new().unwrap();
+    for n in 0..12 {
+        std::fs::write(temp.path().join(format!("f{:02}.txt", n)), "").unwrap();
+    }
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    let last_index = entries.len() - 1;
+
+    let mut run = |actions: Vec<KeyAction>, state: &mut AppState| {
+        for action in actions {
+            call_handle_action!(
+                action,
+                state,
+                &mut navigator,
+                &None,
+                &entries,
+                &context,
+                &mut text_preview,
+                &mut hex_preview,
+                &mut archive_preview
+            )
+            .unwrap();
+        }
+    };
+    let digit = |digit| KeyAction::CountDigit { digit };
+
+    run(vec![digit(5), KeyAction::MoveDown], &mut state);
+    assert_eq!(state.focus_index, 5);
+    assert_eq!(state.count_prefix, None);
+
+    run(vec![digit(2), KeyAction::MoveUp], &mut state);
+    assert_eq!(state.focus_index, 3);
+
+    run(
+        vec![digit(1), digit(0), KeyAction::MoveToBottom],
+        &mut state,
+    );
+    assert_eq!(state.focus_index, 9);
+
+    run(vec![digit(2), KeyAction::MoveToTop], &mut state);
+    assert_eq!(state.focus_index, 1);
+
+    // Past the end clamps to the last row
+    run(vec![digit(9), digit(9), KeyAction::MoveDown], &mut state);
+    assert_eq!(state.focus_index, last_index);
+
+    // Any other action drops the count
+    run(vec![digit(3), KeyAction::None], &mut state);
+    assert_eq!(state.count_prefix, None);
+    run(vec![KeyAction::MoveToTop, KeyAction::MoveDown], &mut state);
+    assert_eq!(state.focus_index, 1);
+}
+
 /// Test: ClearMarks clears all selected paths
 #[test]
 fn test_clear_marks() {
@@ -913,6 +1056,52 @@ fn test_clear_marks() {
     assert!(state.selected_paths.is_empty());
 }
 
+/// Test: DiffMarked needs exactly two marks and shows the focused file
+#[test]
+fn test_diff_marked() {
+    let temp = TempDir::new().unwrap();
+    let file1 = temp.path().join("a.txt");
+    let file2 = temp.path().join("b.txt");
+    std::fs::write(&file1, "one\ntwo\n").unwrap();
+    std::fs::write(&file2, "one\n2\n").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    state.selected_paths.insert(file1.clone());
+    for focused in [None, Some(file1.clone())] {
+        call_handle_action!(
+            KeyAction::DiffMarked,
+            &mut state,
+            &mut navigator,
+            &focused,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+        if state.selected_paths.len() == 1 {
+            assert!(state.compare_files.is_none());
+            assert!(state.message.as_ref().unwrap().text.contains("1 marked"));
+            state.selected_paths.insert(file2.clone());
+        }
+    }
+
+    // The focused file is shown against the other one
+    assert_eq!(state.compare_files, Some((file2, file1.clone())));
+    // The diff is computed once and handed to the preview
+    assert_eq!(state.compare_diff.as_ref().unwrap().path, file1);
+    assert_eq!(state.fuzzy_jump_target, Some(file1));
+    assert!(state.preview_visible);
+}
+
 /// Test: StartSearch enters Search mode
 #[test]
 fn test_start_search() {
@@ -1313,7 +1502,7 @@ fn test_sequence_copy_paste_workflow() {
     )
     .unwrap();
     assert!(state.clipboard.is_some());
-    assert!(state.message.as_ref().unwrap().contains("Copied"));
+    assert!(state.message_text().unwrap().contains("Copied"));
 
     // Step 3: Navigate to dest directory
     let dest_idx = entries.iter().position(|e| e.name == "dest").unwrap_or(0);
@@ -1339,7 +1528,7 @@ fn test_sequence_copy_paste_workflow() {
         dest_dir.join("file1.txt").exists(),
         "File should be copied to destination"
     );
-    assert!(state.message.as_ref().unwrap().contains("Pasted"));
+    assert!(state.message_text().unwrap().contains("Pasted"));
 
     // Refresh entries after paste
     entries = create_test_entries(&navigator);
@@ -1593,6 +1782,134 @@ fn test_sequence_create_delete_workflow() {
     assert!(matches!(state.mode, ViewMode::Browse));
 }
 
+/// Test: Shift+Delete always asks, even in threshold mode, then deletes for good
+#[test]
+fn test_delete_permanently_asks_and_skips_trash() {
+    let temp = TempDir::new().unwrap();
+    let file = temp.path().join("gone.txt");
+    std::fs::write(&file, "x").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    state.delete_confirm.mode = crate::action::ConfirmDeleteMode::Threshold;
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    let focused = Some(file.clone());
+
+    for action in [KeyAction::ConfirmDeletePermanent, KeyAction::ExecuteDelete] {
+        if matches!(action, KeyAction::ExecuteDelete) {
+            assert!(matches!(
+                state.mode,
+                ViewMode::Confirm {
+                    action: PendingAction::Delete {
+                        permanent: true,
+                        ..
+                    }
+                }
+            ));
+        }
+        call_handle_action!(
+            action,
+            &mut state,
+            &mut navigator,
+            &focused,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    }
+    assert!(!file.exists());
+    assert_eq!(state.message_text(), Some("Deleted 1 item(s) permanently"));
+
+    // With the trash turned off, `D` deletes permanently too
+    std::fs::write(&file, "x").unwrap();
+    state.use_trash = false;
+    call_handle_action!(
+        KeyAction::ConfirmDelete,
+        &mut state,
+        &mut navigator,
+        &focused,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert!(matches!(
+        state.mode,
+        ViewMode::Confirm {
+            action: PendingAction::Delete {
+                permanent: true,
+                ..
+            }
+        }
+    ));
+}
+
+/// Sequence: Rename -> Undo puts the file back, a second Undo has nothing left
+#[test]
+fn test_sequence_rename_undo() {
+    let temp = TempDir::new().unwrap();
+    let original = temp.path().join("a.txt");
+    std::fs::write(&original, "a").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    let focused = Some(original.clone());
+
+    let actions = [
+        KeyAction::StartRename,
+        KeyAction::ConfirmInput {
+            value: "b.txt".to_string(),
+        },
+        KeyAction::Undo,
+    ];
+    for action in actions {
+        call_handle_action!(
+            action,
+            &mut state,
+            &mut navigator,
+            &focused,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    }
+    assert!(original.exists());
+    assert!(!temp.path().join("b.txt").exists());
+    assert_eq!(state.message_text(), Some("Undone: Rename a.txt -> b.txt"));
+    assert_eq!(state.fuzzy_jump_target.as_ref(), Some(&original));
+
+    call_handle_action!(
+        KeyAction::Undo,
+        &mut state,
+        &mut navigator,
+        &focused,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert_eq!(state.message_text(), Some("Nothing to undo"));
+}
+
 /// Sequence: Multiple marks -> Cut -> Paste (move operation)
 #[test]
 fn test_sequence_cut_paste_multiple() {
@@ -1630,7 +1947,7 @@ fn test_sequence_cut_paste_multiple() {
     )
     .unwrap();
     assert!(state.clipboard.is_some());
-    assert!(state.message.as_ref().unwrap().contains("Cut"));
+    assert!(state.message_text().unwrap().contains("Cut"));
 
     // Files should still exist (not moved yet)
     assert!(file1.exists());
@@ -1663,16 +1980,16 @@ fn test_sequence_cut_paste_multiple() {
     assert!(!file2.exists(), "Original file2 should not exist");
 }
 
-// =========================================================================
-// Edge Case Tests (Phase 13.4)
-// These tests verify behavior in unusual or boundary conditions
-// =========================================================================
-
-/// Edge case: Empty directory - navigation should handle gracefully
+/// Sequence: Cut -> Paste as copy (source kept) -> Paste (moved)
 #[test]
-fn test_edge_empty_directory_navigation() {
+fn test_sequence_cut_paste_copy_keeps_source() {
     let temp = TempDir::new().unwrap();
-    // Don't create any files - empty directory
+    let copy_dir = temp.path().join("copy");
+    let move_dir = temp.path().join("move");
+    std::fs::create_dir(&copy_dir).unwrap();
+    std::fs::create_dir(&move_dir).unwrap();
+    let file = temp.path().join("keep.txt");
+    std::fs::write(&file, "content").unwrap();
 
     let mut state = create_test_state(temp.path());
     let mut navigator = create_test_navigator(temp.path());
@@ -1682,15 +1999,40 @@ fn test_edge_empty_directory_navigation() {
     let mut hex_preview: Option<HexPreview> = None;
     let mut archive_preview: Option<ArchivePreview> = None;
 
-    // Root is always present, so entries should have at least 1
-    assert!(!entries.is_empty());
+    for (action, focused) in [
+        (KeyAction::Cut, file.clone()),
+        (KeyAction::PasteCopy, copy_dir.clone()),
+    ] {
+        call_handle_action!(
+            action,
+            &mut state,
+            &mut navigator,
+            &Some(focused),
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    }
+
+    assert_eq!(
+        std::fs::read_to_string(copy_dir.join("keep.txt")).unwrap(),
+        "content"
+    );
+    assert!(
+        file.exists(),
+        "Paste as copy must leave the source in place"
+    );
+    // The cut is still pending
+    assert!(state.clipboard.as_ref().is_some_and(|c| c.is_cut()));
 
-    // Move down should not panic
     call_handle_action!(
-        KeyAction::MoveDown,
+        KeyAction::Paste,
         &mut state,
         &mut navigator,
-        &None,
+        &Some(move_dir.clone()),
         &entries,
         &context,
         &mut text_preview,
@@ -1698,13 +2040,37 @@ fn test_edge_empty_directory_navigation() {
         &mut archive_preview
     )
     .unwrap();
+    assert!(move_dir.join("keep.txt").exists());
+    assert!(!file.exists());
+    assert!(copy_dir.join("keep.txt").exists());
+    assert!(state.clipboard.as_ref().is_none_or(|c| c.is_empty()));
+}
 
-    // Move up should not panic
+/// Sequence: Cut in one tab -> switch tab -> Paste into the other tab's directory
+#[test]
+fn test_sequence_cut_paste_across_tabs() {
+    use crate::core::TabManager;
+
+    let temp_a = TempDir::new().unwrap();
+    let temp_b = TempDir::new().unwrap();
+    let file = temp_a.path().join("shared.txt");
+    std::fs::write(&file, "content").unwrap();
+
+    let mut tabs = TabManager::new(temp_a.path().to_path_buf(), false).unwrap();
+    let mut state = create_test_state(temp_a.path());
+    let mut navigator = tabs.active().navigator.clone();
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    // Cut in tab A
     call_handle_action!(
-        KeyAction::MoveUp,
+        KeyAction::Cut,
         &mut state,
         &mut navigator,
-        &None,
+        &Some(file.clone()),
         &entries,
         &context,
         &mut text_preview,
@@ -1713,9 +2079,17 @@ fn test_edge_empty_directory_navigation() {
     )
     .unwrap();
 
-    // MoveToBottom should work
+    // Open tab B (the clipboard stays on the shared app state)
+    tabs.active_mut().navigator = navigator.clone();
+    tabs.new_tab(temp_b.path().to_path_buf(), false).unwrap();
+    let mut navigator = tabs.active().navigator.clone();
+    state.root = temp_b.path().to_path_buf();
+    assert!(state.clipboard.is_some());
+
+    // Paste in tab B
+    let entries = create_test_entries(&navigator);
     call_handle_action!(
-        KeyAction::MoveToBottom,
+        KeyAction::Paste,
         &mut state,
         &mut navigator,
         &None,
@@ -1727,9 +2101,372 @@ fn test_edge_empty_directory_navigation() {
     )
     .unwrap();
 
-    // MoveToTop should work
+    let moved = temp_b.path().join("shared.txt");
+    assert!(moved.exists());
+    assert!(!file.exists());
+    assert!(navigator.visible_entries().iter().any(|e| e.path == moved));
+
+    // Tab A shows the source directory and must be refreshed
+    assert!(state.changed_dirs.iter().any(|d| d == temp_a.path()));
+    let dirs = std::mem::take(&mut state.changed_dirs);
+    assert_eq!(tabs.refresh_inactive_showing(&dirs).unwrap(), 1);
+    assert!(!tabs.tabs[0]
+        .navigator
+        .visible_entries()
+        .iter()
+        .any(|e| e.path == file));
+}
+
+/// Sequence: Open preview -> Open recent files -> Select (reveals the file)
+#[test]
+fn test_sequence_recent_files_select() {
+    let temp = TempDir::new().unwrap();
+    let sub = temp.path().join("sub");
+    std::fs::create_dir(&sub).unwrap();
+    let file = sub.join("seen.txt");
+    std::fs::write(&file, "content").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    // Empty list shows a message instead of the popup
     call_handle_action!(
-        KeyAction::MoveToTop,
+        KeyAction::OpenRecentFiles,
+        &mut state,
+        &mut navigator,
+        &None,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert!(matches!(state.mode, ViewMode::Browse));
+
+    // Opening a preview records the file
+    call_handle_action!(
+        KeyAction::OpenPreview,
+        &mut state,
+        &mut navigator,
+        &Some(file.clone()),
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert_eq!(state.recent_files.get(0).unwrap().path, file);
+    state.mode = ViewMode::Browse;
+
+    for action in [KeyAction::OpenRecentFiles, KeyAction::RecentFilesSelect] {
+        call_handle_action!(
+            action,
+            &mut state,
+            &mut navigator,
+            &None,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    }
+    assert!(matches!(state.mode, ViewMode::Browse));
+    assert_eq!(state.fuzzy_jump_target, Some(file));
+}
+
+/// Typing in the recent files popup narrows the list before selecting
+#[test]
+fn test_recent_files_query_filters_selection() {
+    let temp = TempDir::new().unwrap();
+    let notes = temp.path().join("notes.md");
+    let main = temp.path().join("main.rs");
+    let lib = temp.path().join("lib.rs");
+    for file in [&notes, &main, &lib] {
+        std::fs::write(file, "").unwrap();
+    }
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    for file in [&notes, &main, &lib] {
+        state.recent_files.record(file);
+    }
+
+    // Newest first; the query keeps that order among the matches
+    assert_eq!(crate::render::recent_matches(&state, "").len(), 3);
+    let matched: Vec<_> = crate::render::recent_matches(&state, "rs")
+        .into_iter()
+        .map(|e| e.path.clone())
+        .collect();
+    assert_eq!(matched, vec![lib.clone(), main.clone()]);
+
+    state.mode = ViewMode::RecentFiles {
+        query: "rs".to_string(),
+        selected: 0,
+    };
+    // Down stops at the last match, not the last recent file
+    for action in [
+        KeyAction::RecentFilesDown,
+        KeyAction::RecentFilesDown,
+        KeyAction::RecentFilesSelect,
+    ] {
+        call_handle_action!(
+            action,
+            &mut state,
+            &mut navigator,
+            &None,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    }
+    assert!(matches!(state.mode, ViewMode::Browse));
+    assert_eq!(state.fuzzy_jump_target, Some(main));
+}
+
+/// Sequence: Cut -> Paste onto an existing file -> resolve conflict
+#[test]
+fn test_sequence_paste_conflict_overwrite() {
+    use crate::action::ConflictResolution;
+    use crate::core::PendingAction;
+
+    let temp = TempDir::new().unwrap();
+    let dest_dir = temp.path().join("dest");
+    std::fs::create_dir(&dest_dir).unwrap();
+    let src = temp.path().join("same.txt");
+    std::fs::write(&src, "new").unwrap();
+    std::fs::write(dest_dir.join("same.txt"), "old").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    let mut clipboard = crate::action::Clipboard::new();
+    clipboard.cut(vec![src.clone()]);
+    state.clipboard = Some(clipboard);
+
+    call_handle_action!(
+        KeyAction::Paste,
+        &mut state,
+        &mut navigator,
+        &Some(dest_dir.clone()),
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+
+    // Nothing happens until the conflict is resolved
+    assert!(matches!(
+        state.mode,
+        ViewMode::Confirm {
+            action: PendingAction::ResolvePasteConflict { .. }
+        }
+    ));
+    assert!(src.exists());
+    assert!(state.clipboard.as_ref().is_some_and(|c| !c.is_empty()));
+
+    call_handle_action!(
+        KeyAction::ResolvePasteConflict {
+            resolution: ConflictResolution::Overwrite,
+            apply_all: false,
+        },
+        &mut state,
+        &mut navigator,
+        &Some(dest_dir.clone()),
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+
+    assert!(matches!(state.mode, ViewMode::Browse));
+    assert!(!src.exists());
+    assert_eq!(
+        std::fs::read_to_string(dest_dir.join("same.txt")).unwrap(),
+        "new"
+    );
+    assert!(state.message_text().unwrap().contains("Moved 1"));
+}
+
+/// Sequence: Alt+l on a focused file -> name prompt -> Tab toggles target -> Enter
+#[cfg(unix)]
+#[test]
+fn test_sequence_create_symlink_from_focused() {
+    use crate::action::LinkKind;
+    use crate::core::InputPurpose;
+
+    let temp = TempDir::new().unwrap();
+    let root = temp.path().canonicalize().unwrap();
+    let src = root.join("target.txt");
+    std::fs::write(&src, "data").unwrap();
+
+    let mut state = create_test_state(&root);
+    state.relative_symlinks = true;
+    let mut navigator = create_test_navigator(&root);
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    let focused = Some(src.clone());
+
+    call_handle_action!(
+        KeyAction::CreateSymlink,
+        &mut state,
+        &mut navigator,
+        &focused,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+
+    // The default name is the basename, made unique next to the source
+    match &state.mode {
+        ViewMode::Input {
+            purpose: InputPurpose::CreateLink { kind, .. },
+            buffer,
+            ..
+        } => {
+            assert_eq!(buffer, "target_1.txt");
+            assert_eq!(*kind, LinkKind::Symlink { relative: true });
+        }
+        other => panic!("expected link prompt, got {:?}", other),
+    }
+
+    call_handle_action!(
+        KeyAction::ToggleLinkRelative,
+        &mut state,
+        &mut navigator,
+        &focused,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+
+    // Moving the focus into a directory while prompting keeps the destination
+    let sub = root.join("sub");
+    std::fs::create_dir(&sub).unwrap();
+    let moved_focus = Some(sub.clone());
+    call_handle_action!(
+        KeyAction::ConfirmInput {
+            value: "link.txt".to_string()
+        },
+        &mut state,
+        &mut navigator,
+        &moved_focus,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+
+    let link = root.join("link.txt");
+    assert!(!sub.join("link.txt").exists());
+    assert!(matches!(state.mode, ViewMode::Browse));
+    assert_eq!(std::fs::read_link(&link).unwrap(), src);
+    assert_eq!(state.fuzzy_jump_target, Some(link));
+}
+
+// =========================================================================
+// Edge Case Tests (Phase 13.4)
+// These tests verify behavior in unusual or boundary conditions
+// =========================================================================
+
+/// Edge case: Empty directory - navigation should handle gracefully
+#[test]
+fn test_edge_empty_directory_navigation() {
+    let temp = TempDir::new().unwrap();
+    // Don't create any files - empty directory
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    // Root is always present, so entries should have at least 1
+    assert!(!entries.is_empty());
+
+    // Move down should not panic
+    call_handle_action!(
+        KeyAction::MoveDown,
+        &mut state,
+        &mut navigator,
+        &None,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+
+    // Move up should not panic
+    call_handle_action!(
+        KeyAction::MoveUp,
+        &mut state,
+        &mut navigator,
+        &None,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+
+    // MoveToBottom should work
+    call_handle_action!(
+        KeyAction::MoveToBottom,
+        &mut state,
+        &mut navigator,
+        &None,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+
+    // MoveToTop should work
+    call_handle_action!(
+        KeyAction::MoveToTop,
         &mut state,
         &mut navigator,
         &None,
@@ -2166,28 +2903,30 @@ fn test_edge_search_next_with_query() {
     // (or stay in place if no match found)
 }
 
-/// Edge case: Paste with empty clipboard
+/// SetRoot on a focused subdirectory rebuilds the tree there
 #[test]
-fn test_edge_paste_empty_clipboard() {
+fn test_set_root_to_subdir() {
     let temp = TempDir::new().unwrap();
-
-    let mut state = create_test_state(temp.path());
-    let mut navigator = create_test_navigator(temp.path());
+    let root = temp.path().canonicalize().unwrap();
+    let sub = root.join("sub");
+    std::fs::create_dir(&sub).unwrap();
+    std::fs::write(sub.join("inner.txt"), "").unwrap();
+    std::fs::write(root.join("outer.txt"), "").unwrap();
+
+    let mut state = create_test_state(&root);
+    let mut navigator = create_test_navigator(&root);
     let entries = create_test_entries(&navigator);
     let context = ActionContext::default();
     let mut text_preview: Option<TextPreview> = None;
     let mut hex_preview: Option<HexPreview> = None;
     let mut archive_preview: Option<ArchivePreview> = None;
+    state.focus_index = 1;
 
-    // Clipboard is None
-    assert!(state.clipboard.is_none());
-
-    // Paste should not crash
     call_handle_action!(
-        KeyAction::Paste,
+        KeyAction::SetRoot,
         &mut state,
         &mut navigator,
-        &Some(temp.path().to_path_buf()),
+        &Some(sub.clone()),
         &entries,
         &context,
         &mut text_preview,
@@ -2195,9 +2934,408 @@ fn test_edge_paste_empty_clipboard() {
         &mut archive_preview
     )
     .unwrap();
+
+    assert_eq!(state.root, sub);
+    assert_eq!(state.focus_index, 0);
+    assert_eq!(navigator.root().path, sub);
+    let names: Vec<String> = create_test_entries(&navigator)
+        .into_iter()
+        .map(|e| e.name)
+        .collect();
+    assert!(names.contains(&"inner.txt".to_string()));
+    assert!(!names.contains(&"outer.txt".to_string()));
 }
 
-/// Edge case: ConfirmDelete with no targets
+/// RootUp moves the root to the parent and focuses the previous root
+#[test]
+fn test_root_up_to_parent() {
+    let temp = TempDir::new().unwrap();
+    let parent = temp.path().canonicalize().unwrap();
+    let sub = parent.join("sub");
+    std::fs::create_dir(&sub).unwrap();
+    std::fs::write(parent.join("sibling.txt"), "").unwrap();
+
+    let mut state = create_test_state(&sub);
+    let mut navigator = create_test_navigator(&sub);
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    call_handle_action!(
+        KeyAction::RootUp,
+        &mut state,
+        &mut navigator,
+        &None,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+
+    assert_eq!(state.root, parent);
+    assert_eq!(navigator.root().path, parent);
+    assert_eq!(state.fuzzy_jump_target, Some(sub));
+    let names: Vec<String> = create_test_entries(&navigator)
+        .into_iter()
+        .map(|e| e.name)
+        .collect();
+    assert!(names.contains(&"sibling.txt".to_string()));
+}
+
+/// Collapse on a collapsed top-level directory under each collapse_fallback
+#[test]
+fn test_collapse_fallback_on_collapsed_top_level() {
+    use crate::core::CollapseFallback;
+
+    let temp = TempDir::new().unwrap();
+    let parent = temp.path().canonicalize().unwrap();
+    let root = parent.join("root");
+    let top = root.join("top");
+    let nested = top.join("nested");
+    std::fs::create_dir_all(&nested).unwrap();
+
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    let mut collapse = |fallback: CollapseFallback, path: &Path, expand_top: bool| {
+        let mut state = create_test_state(&root);
+        state.collapse_fallback = fallback;
+        let mut navigator = create_test_navigator(&root);
+        if expand_top {
+            navigator.expand(&top).unwrap();
+        }
+        let entries = create_test_entries(&navigator);
+        call_handle_action!(
+            KeyAction::Collapse,
+            &mut state,
+            &mut navigator,
+            &Some(path.to_path_buf()),
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+        state
+    };
+
+    // Default: nothing happens
+    let state = collapse(CollapseFallback::None, &top, false);
+    assert_eq!(state.root, root);
+    assert_eq!(state.fuzzy_jump_target, None);
+
+    // Parent: focus the root entry
+    let state = collapse(CollapseFallback::Parent, &top, false);
+    assert_eq!(state.root, root);
+    assert_eq!(state.fuzzy_jump_target, Some(root.clone()));
+
+    // RootUp: move the root up and focus the previous root
+    let state = collapse(CollapseFallback::RootUp, &top, false);
+    assert_eq!(state.root, parent);
+    assert_eq!(state.fuzzy_jump_target, Some(root.clone()));
+
+    // Deeper collapsed entries focus their parent under RootUp too
+    let state = collapse(CollapseFallback::RootUp, &nested, true);
+    assert_eq!(state.root, root);
+    assert_eq!(state.fuzzy_jump_target, Some(top.clone()));
+
+    // An expanded directory still just collapses
+    let state = collapse(CollapseFallback::RootUp, &top, true);
+    assert_eq!(state.root, root);
+    assert_eq!(state.fuzzy_jump_target, None);
+}
+
+/// Smart case vs case-sensitive SearchNext on mixed-case names
+#[test]
+fn test_search_case_sensitive_toggle() {
+    let temp = TempDir::new().unwrap();
+    std::fs::write(temp.path().join("Config.toml"), "").unwrap();
+    std::fs::write(temp.path().join("config.rs"), "").unwrap();
+    std::fs::write(temp.path().join("other.txt"), "").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    let mut search = |state: &mut AppState, action: KeyAction| {
+        state.mode = ViewMode::Search {
+            query: "config".to_string(),
+        };
+        call_handle_action!(
+            action,
+            state,
+            &mut navigator,
+            &None,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    };
+
+    // Smart case: a lowercase query matches both spellings
+    search(&mut state, KeyAction::SearchNext);
+    assert_eq!(state.search_matches.map(|(_, total)| total), Some(2));
+
+    search(&mut state, KeyAction::ToggleCaseSensitive);
+    assert!(state.case_sensitive);
+    search(&mut state, KeyAction::SearchNext);
+    assert_eq!(state.search_matches.map(|(_, total)| total), Some(1));
+    assert_eq!(entries[state.focus_index].name, "config.rs");
+}
+
+#[test]
+fn test_update_search_matches_counts_while_typing() {
+    let temp = TempDir::new().unwrap();
+    std::fs::write(temp.path().join("a_test.rs"), "").unwrap();
+    std::fs::write(temp.path().join("b_test.rs"), "").unwrap();
+    std::fs::write(temp.path().join("main.rs"), "").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    let navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+
+    state.mode = ViewMode::Search {
+        query: "test".to_string(),
+    };
+    update_search_matches(&mut state, &entries);
+    assert_eq!(state.search_matches, Some((0, 2)));
+
+    state.focus_index = entries.iter().position(|e| e.name == "b_test.rs").unwrap();
+    update_search_matches(&mut state, &entries);
+    assert_eq!(state.search_matches, Some((2, 2)));
+
+    state.mode = ViewMode::Search {
+        query: String::new(),
+    };
+    update_search_matches(&mut state, &entries);
+    assert_eq!(state.search_matches, None);
+}
+
+/// Edge case: Paste with empty clipboard
+#[test]
+fn test_edge_paste_empty_clipboard() {
+    let temp = TempDir::new().unwrap();
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    // Clipboard is None
+    assert!(state.clipboard.is_none());
+
+    // Paste should not crash
+    call_handle_action!(
+        KeyAction::Paste,
+        &mut state,
+        &mut navigator,
+        &Some(temp.path().to_path_buf()),
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+}
+
+/// ConfirmDelete counts recursive totals for the confirmation in the background
+#[test]
+fn test_confirm_delete_summarizes_directory() {
+    let temp = TempDir::new().unwrap();
+    let dir = temp.path().join("big");
+    std::fs::create_dir_all(dir.join("sub")).unwrap();
+    std::fs::write(dir.join("a.txt"), "aaaa").unwrap();
+    std::fs::write(dir.join("sub/b.txt"), "bb").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    call_handle_action!(
+        KeyAction::ConfirmDelete,
+        &mut state,
+        &mut navigator,
+        &Some(dir.clone()),
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+
+    assert!(matches!(state.mode, ViewMode::Confirm { .. }));
+    // The popup opens at once; the totals follow from the background count
+    assert!(state.delete_summary.is_none());
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while !state.poll_delete_summary() {
+        assert!(std::time::Instant::now() < deadline, "count never finished");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    let summary = state.delete_summary.unwrap();
+    assert_eq!(summary.items, 1);
+    assert_eq!(summary.files, 2);
+    assert_eq!(summary.bytes, 6);
+    assert!(!summary.truncated);
+}
+
+/// Threshold mode deletes a small file without asking, but confirms a directory
+#[test]
+fn test_confirm_delete_threshold_skips_small_file() {
+    let temp = TempDir::new().unwrap();
+    let file = temp.path().join("scratch.txt");
+    std::fs::write(&file, "tmp").unwrap();
+    let dir = temp.path().join("keep");
+    std::fs::create_dir(&dir).unwrap();
+
+    let mut state = create_test_state(temp.path());
+    state.delete_confirm.mode = crate::action::ConfirmDeleteMode::Threshold;
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    call_handle_action!(
+        KeyAction::ConfirmDelete,
+        &mut state,
+        &mut navigator,
+        &Some(file.clone()),
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert!(
+        !file.exists(),
+        "Small file should be deleted without asking"
+    );
+    assert!(matches!(state.mode, ViewMode::Browse));
+
+    let entries = create_test_entries(&navigator);
+    call_handle_action!(
+        KeyAction::ConfirmDelete,
+        &mut state,
+        &mut navigator,
+        &Some(dir.clone()),
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert!(dir.exists());
+    assert!(matches!(state.mode, ViewMode::Confirm { .. }));
+}
+
+/// Launching with a nested file reveals it and focuses it
+#[test]
+fn test_reveal_and_focus_nested_file_on_launch() {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path();
+    std::fs::create_dir_all(root.join("src/app")).unwrap();
+    std::fs::create_dir_all(root.join("docs")).unwrap();
+    std::fs::write(root.join("src/app/config.rs"), "").unwrap();
+    std::fs::write(root.join("src/app/mod.rs"), "").unwrap();
+    std::fs::write(root.join("README.md"), "").unwrap();
+    let file = root.join("src/app/config.rs");
+
+    let mut state = create_test_state(root);
+    let mut navigator = create_test_navigator(root);
+    assert!(reveal_and_focus(&mut navigator, &mut state, &file).unwrap());
+
+    let entries = displayed_entries(&navigator, &state);
+    assert_eq!(entries[state.focus_index].path, file);
+    // Only the file's ancestors were expanded
+    assert!(entries
+        .iter()
+        .any(|e| e.path == root.join("src/app/mod.rs")));
+    let docs = entries
+        .iter()
+        .find(|e| e.path == root.join("docs"))
+        .unwrap();
+    assert!(!docs.is_expanded());
+}
+
+/// A path hidden by the filter is revealed but not focused
+#[test]
+fn test_reveal_and_focus_filtered_out() {
+    let temp = TempDir::new().unwrap();
+    std::fs::write(temp.path().join("a.rs"), "").unwrap();
+    std::fs::write(temp.path().join("b.txt"), "").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    state.filter_pattern = Some("*.rs".to_string());
+    let mut navigator = create_test_navigator(temp.path());
+    let target = temp.path().join("b.txt");
+    assert!(!reveal_and_focus(&mut navigator, &mut state, &target).unwrap());
+    assert_eq!(state.focus_index, 0);
+}
+
+/// Selection totals include directory contents and follow selection changes
+#[test]
+fn test_selection_summary_mixed_files_and_dirs() {
+    let temp = TempDir::new().unwrap();
+    let dir = temp.path().join("assets");
+    std::fs::create_dir_all(dir.join("icons")).unwrap();
+    std::fs::write(dir.join("logo.svg"), "12345").unwrap();
+    std::fs::write(dir.join("icons/a.png"), "123").unwrap();
+    let file = temp.path().join("notes.txt");
+    std::fs::write(&file, "1234567890").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    refresh_selection_summary(&mut state);
+    assert!(state.selection_summary.is_none());
+
+    state.selected_paths.insert(dir.clone());
+    state.selected_paths.insert(file.clone());
+    refresh_selection_summary(&mut state);
+    let summary = state.selection_summary.unwrap();
+    assert_eq!(summary.items, 2);
+    assert_eq!(summary.files, 3);
+    assert_eq!(summary.bytes, 18);
+    assert!(!summary.truncated);
+
+    // Unchanged selection keeps the cached totals
+    std::fs::write(&file, "1").unwrap();
+    refresh_selection_summary(&mut state);
+    assert_eq!(state.selection_summary.unwrap().bytes, 18);
+
+    state.selected_paths.remove(&dir);
+    refresh_selection_summary(&mut state);
+    assert_eq!(state.selection_summary.unwrap().bytes, 1);
+
+    state.selected_paths.clear();
+    refresh_selection_summary(&mut state);
+    assert!(state.selection_summary.is_none());
+}
+
+/// Edge case: ConfirmDelete with no targets
 #[test]
 fn test_edge_confirm_delete_no_targets() {
     let temp = TempDir::new().unwrap();
@@ -2802,28 +3940,38 @@ fn test_preview_to_bottom_syncs_viewmode() {
     }
 }
 
-/// Test: Hex preview scroll is capped at max
+/// Test: `/` searches the fullscreen text preview, `n` steps, Esc clears but stays
 #[test]
-fn test_hex_preview_scroll_capped() {
+fn test_preview_search_jumps_and_esc_clears() {
     let temp = TempDir::new().unwrap();
-    let hex_file = temp.path().join("test.bin");
-    // Create a small binary file (32 bytes = 2 lines at 16 bytes per line)
-    std::fs::write(&hex_file, vec![0u8; 32]).unwrap();
-
     let mut state = create_test_state(temp.path());
     let mut navigator = create_test_navigator(temp.path());
     let entries = create_test_entries(&navigator);
     let context = ActionContext::default();
 
-    let mut text_preview: Option<TextPreview> = None;
-    let mut hex_preview = Some(HexPreview::load(&hex_file).unwrap());
+    let text: String = (0..40)
+        .map(|n| {
+            if n % 10 == 5 {
+                format!("needle {}\n", n)
+            } else {
+                "hay\n".to_string()
+            }
+        })
+        .collect();
+    let mut text_preview = Some(TextPreview::new(&text));
+    let mut hex_preview: Option<HexPreview> = None;
     let mut archive_preview: Option<ArchivePreview> = None;
-    hex_preview.as_mut().unwrap().scroll = 0;
+    state.mode = ViewMode::Preview { scroll: 0 };
 
-    // Scroll down multiple times
-    for _ in 0..10 {
+    let actions = [
+        KeyAction::StartPreviewSearch,
+        KeyAction::ConfirmInput {
+            value: "needle".to_string(),
+        },
+    ];
+    for action in actions {
         call_handle_action!(
-            KeyAction::PreviewScrollDown,
+            action,
             &mut state,
             &mut navigator,
             &None,
@@ -2835,23 +3983,157 @@ fn test_hex_preview_scroll_capped() {
         )
         .unwrap();
     }
+    let tp = text_preview.as_ref().unwrap();
+    assert_eq!(tp.match_lines, vec![5, 15, 25, 35]);
+    assert_eq!(tp.scroll, 5);
+    assert!(state.preview_search.is_none());
+    assert!(matches!(state.mode, ViewMode::Preview { scroll: 5 }));
 
-    // line_count for 32 bytes = 2 lines, max scroll = 1
-    assert_eq!(
-        hex_preview.as_ref().unwrap().scroll,
-        1,
-        "HexPreview scroll should be capped at max (line_count - 1)"
-    );
-}
-
-/// Test: Archive preview scroll is capped at max
-#[test]
-fn test_archive_preview_scroll_capped() {
-    let temp = TempDir::new().unwrap();
-    let mut state = create_test_state(temp.path());
-    let mut navigator = create_test_navigator(temp.path());
-    let entries = create_test_entries(&navigator);
-    let context = ActionContext::default();
+    call_handle_action!(
+        KeyAction::PreviewSearchNext,
+        &mut state,
+        &mut navigator,
+        &None,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert_eq!(text_preview.as_ref().unwrap().scroll, 15);
+
+    // Esc clears the search and stays in the preview; a second Esc leaves it
+    call_handle_action!(
+        KeyAction::Cancel,
+        &mut state,
+        &mut navigator,
+        &None,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert!(text_preview.as_ref().unwrap().search_query.is_none());
+    assert!(matches!(state.mode, ViewMode::Preview { .. }));
+
+    call_handle_action!(
+        KeyAction::Cancel,
+        &mut state,
+        &mut navigator,
+        &None,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert!(matches!(state.mode, ViewMode::Browse));
+}
+
+/// Test: `B` toggles the blame gutter off, and outside git says so
+#[test]
+fn test_toggle_blame_outside_git_and_off() {
+    let temp = TempDir::new().unwrap();
+    let file = temp.path().join("notes.txt");
+    std::fs::write(&file, "one\ntwo\n").unwrap();
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+
+    let mut text_preview = Some(TextPreview::new("one\ntwo\n"));
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    state.mode = ViewMode::Preview { scroll: 0 };
+
+    if crate::git::GitStatus::detect(temp.path()).is_none() {
+        call_handle_action!(
+            KeyAction::ToggleBlame,
+            &mut state,
+            &mut navigator,
+            &Some(file.clone()),
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+        assert!(text_preview.as_ref().unwrap().blame.is_none());
+        assert!(state.message_text().unwrap().contains("not in git"));
+    }
+
+    text_preview.as_mut().unwrap().blame = Some(BlamePreview::new(Arc::new(Vec::new())));
+    call_handle_action!(
+        KeyAction::ToggleBlame,
+        &mut state,
+        &mut navigator,
+        &Some(file),
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert!(text_preview.as_ref().unwrap().blame.is_none());
+    assert_eq!(state.message_text(), Some("Blame: off"));
+}
+
+/// Test: Hex preview scroll is capped at max
+#[test]
+fn test_hex_preview_scroll_capped() {
+    let temp = TempDir::new().unwrap();
+    let hex_file = temp.path().join("test.bin");
+    // Create a small binary file (32 bytes = 2 lines at 16 bytes per line)
+    std::fs::write(&hex_file, vec![0u8; 32]).unwrap();
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview = Some(HexPreview::load(&hex_file).unwrap());
+    let mut archive_preview: Option<ArchivePreview> = None;
+    hex_preview.as_mut().unwrap().scroll = 0;
+
+    // Scroll down multiple times
+    for _ in 0..10 {
+        call_handle_action!(
+            KeyAction::PreviewScrollDown,
+            &mut state,
+            &mut navigator,
+            &None,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    }
+
+    // line_count for 32 bytes = 2 lines, max scroll = 1
+    assert_eq!(
+        hex_preview.as_ref().unwrap().scroll,
+        1,
+        "HexPreview scroll should be capped at max (line_count - 1)"
+    );
+}
+
+/// Test: Archive preview scroll is capped at max
+#[test]
+fn test_archive_preview_scroll_capped() {
+    let temp = TempDir::new().unwrap();
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
 
     let mut text_preview: Option<TextPreview> = None;
     let mut hex_preview: Option<HexPreview> = None;
@@ -2917,3 +4199,1263 @@ fn test_archive_preview_scroll_capped() {
         "ArchivePreview scroll should be capped at max (line_count - 1)"
     );
 }
+
+/// Alt+d prompts for a revision; Tab completes it and unknown revisions are reported
+#[test]
+fn test_diff_revision_prompt() {
+    use crate::core::InputPurpose;
+
+    let temp = TempDir::new().unwrap();
+    let root = temp.path();
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .output()
+            .is_ok_and(|o| o.status.success())
+    };
+    if !git(&["init"])
+        || !git(&["config", "user.name", "Test User"])
+        || !git(&["config", "user.email", "test@example.com"])
+    {
+        return;
+    }
+    let file = root.join("notes.txt");
+    std::fs::write(&file, "one\n").unwrap();
+    assert!(git(&["add", "notes.txt"]));
+    assert!(git(&["commit", "-m", "first"]));
+    std::fs::write(&file, "one\ntwo\n").unwrap();
+    assert!(git(&["commit", "-am", "second"]));
+
+    let mut state = create_test_state(root);
+    state.init_git_status();
+    let mut navigator = create_test_navigator(root);
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    let focused = Some(file.clone());
+
+    call_handle_action!(
+        KeyAction::StartDiffRevision,
+        &mut state,
+        &mut navigator,
+        &focused,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    if let ViewMode::Input { buffer, cursor, .. } = &mut state.mode {
+        *buffer = "HE".to_string();
+        *cursor = 2;
+    }
+    call_handle_action!(
+        KeyAction::CompleteDiffRevision,
+        &mut state,
+        &mut navigator,
+        &focused,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    match &state.mode {
+        ViewMode::Input {
+            purpose: InputPurpose::DiffRevision { path, .. },
+            buffer,
+            cursor,
+        } => {
+            assert_eq!(path, &file);
+            // HEAD and HEAD~1 both match
+            assert_eq!((buffer.as_str(), *cursor), ("HEAD", 4));
+        }
+        other => panic!("expected revision prompt, got {:?}", other),
+    }
+
+    call_handle_action!(
+        KeyAction::ConfirmInput {
+            value: "no-such-rev".to_string()
+        },
+        &mut state,
+        &mut navigator,
+        &focused,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert!(matches!(state.mode, ViewMode::Browse));
+    assert!(state.message_text().unwrap().starts_with("Failed:"));
+    assert!(state.diff_base.is_none());
+
+    call_handle_action!(
+        KeyAction::StartDiffRevision,
+        &mut state,
+        &mut navigator,
+        &focused,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    call_handle_action!(
+        KeyAction::ConfirmInput {
+            value: "HEAD~1".to_string()
+        },
+        &mut state,
+        &mut navigator,
+        &focused,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert_eq!(state.diff_base, Some((file.clone(), "HEAD~1".to_string())));
+    assert!(state.preview_visible);
+}
+
+/// Alt+c copies paths relative to the root; paths outside it stay absolute
+#[test]
+fn test_relative_to_root() {
+    use super::display::relative_to_root;
+
+    let root = Path::new("/home/user/project");
+    assert_eq!(
+        relative_to_root(Path::new("/home/user/project/src/app/main.rs"), root),
+        Path::new("src/app/main.rs")
+    );
+    assert_eq!(relative_to_root(root, root), Path::new("."));
+    // A sibling of the root has no relative path below it
+    assert_eq!(
+        relative_to_root(Path::new("/home/user/project-old/notes.md"), root),
+        Path::new("/home/user/project-old/notes.md")
+    );
+}
+
+/// Alt+m commits the staged changes; empty messages keep the prompt open
+#[test]
+fn test_git_commit_prompt() {
+    use crate::core::InputPurpose;
+
+    let temp = TempDir::new().unwrap();
+    let root = temp.path();
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    };
+    if git(&["init"]).is_none()
+        || git(&["config", "user.name", "Test User"]).is_none()
+        || git(&["config", "user.email", "test@example.com"]).is_none()
+    {
+        return;
+    }
+    std::fs::write(root.join("notes.txt"), "one\n").unwrap();
+    assert!(git(&["add", "notes.txt"]).is_some());
+
+    let mut state = create_test_state(root);
+    state.init_git_status();
+    let mut navigator = create_test_navigator(root);
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    let focused = None;
+
+    call_handle_action!(
+        KeyAction::StartGitCommit { amend: false },
+        &mut state,
+        &mut navigator,
+        &focused,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    call_handle_action!(
+        KeyAction::ConfirmInput {
+            value: "  \n".to_string()
+        },
+        &mut state,
+        &mut navigator,
+        &focused,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert!(matches!(
+        state.mode,
+        ViewMode::Input {
+            purpose: InputPurpose::CommitMessage { amend: false },
+            ..
+        }
+    ));
+    assert_eq!(state.message_text(), Some("Commit message is empty"));
+
+    call_handle_action!(
+        KeyAction::ConfirmInput {
+            value: "Add notes\n\nFirst draft".to_string()
+        },
+        &mut state,
+        &mut navigator,
+        &focused,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert!(matches!(state.mode, ViewMode::Browse));
+    let hash = git(&["rev-parse", "--short", "HEAD"]).unwrap();
+    assert_eq!(
+        state.message_text(),
+        Some(format!("Committed {}: Add notes", hash).as_str())
+    );
+    assert_eq!(
+        git(&["log", "-1", "--format=%B"]).as_deref(),
+        Some("Add notes\n\nFirst draft")
+    );
+
+    // Amending prefills the last message
+    call_handle_action!(
+        KeyAction::StartGitCommit { amend: true },
+        &mut state,
+        &mut navigator,
+        &focused,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    match &state.mode {
+        ViewMode::Input { buffer, .. } => assert!(buffer.starts_with("Add notes")),
+        other => panic!("expected commit prompt, got {:?}", other),
+    }
+}
+
+/// `+` with `*.rs` marks exactly the Rust files in view; `-` unmarks by pattern
+#[test]
+fn test_select_by_pattern_marks_matching_files() {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path();
+    for name in ["main.rs", "Lib.RS", "notes.md", "Cargo.toml", "rs"] {
+        std::fs::write(root.join(name), "").unwrap();
+    }
+    std::fs::create_dir(root.join("src")).unwrap();
+    std::fs::write(root.join("src/nested.rs"), "").unwrap();
+
+    let mut state = create_test_state(root);
+    let mut navigator = create_test_navigator(root);
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    let mut run = |action: KeyAction, state: &mut AppState| {
+        call_handle_action!(
+            action,
+            state,
+            &mut navigator,
+            &None,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    };
+
+    run(KeyAction::SelectByPattern, &mut state);
+    run(
+        KeyAction::ConfirmInput {
+            value: "*.rs".to_string(),
+        },
+        &mut state,
+    );
+
+    // Lowercase patterns match case-insensitively; collapsed src/ is not in view
+    let expected: std::collections::HashSet<_> = [root.join("main.rs"), root.join("Lib.RS")]
+        .into_iter()
+        .collect();
+    assert_eq!(state.selected_paths, expected);
+    assert!(matches!(state.mode, ViewMode::Browse));
+
+    // An uppercase letter makes the pattern case-sensitive
+    run(KeyAction::DeselectByPattern, &mut state);
+    run(
+        KeyAction::ConfirmInput {
+            value: "*.RS".to_string(),
+        },
+        &mut state,
+    );
+    assert_eq!(
+        state.selected_paths,
+        [root.join("main.rs")].into_iter().collect()
+    );
+
+    // Character classes and alternatives
+    let mut select = |pattern: &str, state: &mut AppState| {
+        state.selected_paths.clear();
+        run(KeyAction::SelectByPattern, state);
+        run(
+            KeyAction::ConfirmInput {
+                value: pattern.to_string(),
+            },
+            state,
+        );
+        state.selected_paths.clone()
+    };
+    assert_eq!(
+        select("*.{md,toml}", &mut state),
+        [root.join("notes.md"), root.join("Cargo.toml")]
+            .into_iter()
+            .collect()
+    );
+    assert_eq!(
+        select("[mn]*", &mut state),
+        [root.join("main.rs"), root.join("notes.md")]
+            .into_iter()
+            .collect()
+    );
+    // An invalid glob reports the error and marks nothing
+    assert!(select("[", &mut state).is_empty());
+    assert!(state.message_text().unwrap().starts_with("Invalid pattern"));
+}
+
+/// Alt++ marks matches under the focused directory, including collapsed ones
+#[test]
+fn test_select_by_pattern_in_subtree() {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path();
+    std::fs::create_dir_all(root.join("src/deep")).unwrap();
+    std::fs::create_dir_all(root.join("src/.hidden")).unwrap();
+    std::fs::write(root.join("src/a.rs"), "").unwrap();
+    std::fs::write(root.join("src/deep/b.rs"), "").unwrap();
+    std::fs::write(root.join("src/deep/c.txt"), "").unwrap();
+    std::fs::write(root.join("src/.hidden/d.rs"), "").unwrap();
+    std::fs::write(root.join("top.rs"), "").unwrap();
+
+    let mut state = create_test_state(root);
+    let mut navigator = create_test_navigator(root);
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    let focused = Some(root.join("src"));
+
+    for action in [
+        KeyAction::SelectByPatternInSubtree,
+        KeyAction::ConfirmInput {
+            value: "*.rs".to_string(),
+        },
+    ] {
+        call_handle_action!(
+            action,
+            &mut state,
+            &mut navigator,
+            &focused,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    }
+
+    let expected: std::collections::HashSet<_> =
+        [root.join("src/a.rs"), root.join("src/deep/b.rs")]
+            .into_iter()
+            .collect();
+    assert_eq!(state.selected_paths, expected);
+
+    // A pattern with `/` matches paths below the subtree, `**` spanning directories
+    state.selected_paths.clear();
+    for action in [
+        KeyAction::SelectByPatternInSubtree,
+        KeyAction::ConfirmInput {
+            value: "deep/**".to_string(),
+        },
+    ] {
+        call_handle_action!(
+            action,
+            &mut state,
+            &mut navigator,
+            &focused,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    }
+    let expected: std::collections::HashSet<_> =
+        [root.join("src/deep/b.rs"), root.join("src/deep/c.txt")]
+            .into_iter()
+            .collect();
+    assert_eq!(state.selected_paths, expected);
+}
+
+/// Enter on a directory in pick mode picks it by default (--allow-dirs) and
+/// with --dirs-only, and expands it with --expand-dirs; --dirs-only also
+/// hides files
+#[test]
+fn test_pick_select_directory_per_flag() {
+    use crate::core::PickDirs;
+
+    let temp = TempDir::new().unwrap();
+    let dir = temp.path().join("assets");
+    std::fs::create_dir(&dir).unwrap();
+    std::fs::write(dir.join("logo.png"), "").unwrap();
+    std::fs::write(temp.path().join("readme.md"), "").unwrap();
+
+    for pick_dirs in [PickDirs::default(), PickDirs::Expand, PickDirs::Only] {
+        let mut state = create_test_state(temp.path());
+        state.pick_mode = true;
+        state.pick_dirs = pick_dirs;
+        let mut navigator = create_test_navigator(temp.path());
+        let entries = create_test_entries(&navigator);
+        let context = ActionContext::default();
+        let mut text_preview: Option<TextPreview> = None;
+        let mut hex_preview: Option<HexPreview> = None;
+        let mut archive_preview: Option<ArchivePreview> = None;
+
+        let result = call_handle_action!(
+            KeyAction::PickSelect,
+            &mut state,
+            &mut navigator,
+            &Some(dir.clone()),
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+
+        // Skip the root entry
+        let shown: Vec<String> = displayed_entries(&navigator, &state)
+            .iter()
+            .skip(1)
+            .map(|e| e.name.clone())
+            .collect();
+        match pick_dirs {
+            PickDirs::Expand => {
+                assert_eq!(result, ActionResult::Continue);
+                assert_eq!(shown, ["assets", "logo.png", "readme.md"]);
+            }
+            PickDirs::Allow => {
+                assert_eq!(result, ActionResult::Quit(exit_code::SUCCESS));
+                assert_eq!(shown, ["assets", "readme.md"]);
+            }
+            PickDirs::Only => {
+                assert_eq!(result, ActionResult::Quit(exit_code::SUCCESS));
+                assert_eq!(shown, ["assets"]);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_content_search_cancel_discards_results() {
+    let temp = TempDir::new().unwrap();
+    std::fs::write(temp.path().join("notes.txt"), "todo: write tests\n").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    for action in [
+        KeyAction::StartContentSearch,
+        KeyAction::ConfirmInput {
+            value: "todo".to_string(),
+        },
+    ] {
+        call_handle_action!(
+            action,
+            &mut state,
+            &mut navigator,
+            &None,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    }
+    assert_eq!(state.mode, ViewMode::ContentSearch { selected: 0 });
+    assert_eq!(
+        state.content_search.as_ref().map(|s| s.query.text.as_str()),
+        Some("todo")
+    );
+
+    call_handle_action!(
+        KeyAction::Cancel,
+        &mut state,
+        &mut navigator,
+        &None,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert_eq!(state.mode, ViewMode::Browse);
+    assert!(state.content_search.is_none());
+}
+
+#[test]
+fn test_marks_overview_unmark_and_jump() {
+    let temp = TempDir::new().unwrap();
+    let (a, b, c) = (
+        temp.path().join("a.txt"),
+        temp.path().join("b.txt"),
+        temp.path().join("c.txt"),
+    );
+    for path in [&a, &b, &c] {
+        std::fs::write(path, "").unwrap();
+    }
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    state
+        .selected_paths
+        .extend([a.clone(), b.clone(), c.clone()]);
+
+    let mut run = |state: &mut AppState, action: KeyAction| {
+        call_handle_action!(
+            action,
+            state,
+            &mut navigator,
+            &None,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    };
+
+    run(&mut state, KeyAction::OpenMarksOverview);
+    run(&mut state, KeyAction::MarksDown);
+    run(&mut state, KeyAction::MarksUnmark);
+    assert_eq!(state.sorted_marks(), vec![a.clone(), c.clone()]);
+    assert_eq!(state.mode, ViewMode::MarksOverview { selected: 1 });
+
+    run(&mut state, KeyAction::MarksUnmark);
+    assert_eq!(state.sorted_marks(), vec![a.clone()]);
+    assert_eq!(state.mode, ViewMode::MarksOverview { selected: 0 });
+
+    run(&mut state, KeyAction::MarksJump);
+    assert_eq!(state.mode, ViewMode::Browse);
+    assert_eq!(state.fuzzy_jump_target, Some(a.clone()));
+    assert_eq!(state.sorted_marks(), vec![a]);
+}
+
+#[test]
+fn test_operation_history_records_in_order_and_reveals() {
+    let temp = TempDir::new().unwrap();
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    // Empty log: the popup does not open
+    call_handle_action!(
+        KeyAction::OpenOperationHistory,
+        &mut state,
+        &mut navigator,
+        &None,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert!(matches!(state.mode, ViewMode::Browse));
+    assert_eq!(state.message_text(), Some("No file operations yet"));
+
+    // Create a file, then rename it
+    let focused = Some(temp.path().to_path_buf());
+    for action in [
+        KeyAction::StartNewFile,
+        KeyAction::ConfirmInput {
+            value: "draft.txt".to_string(),
+        },
+    ] {
+        call_handle_action!(
+            action,
+            &mut state,
+            &mut navigator,
+            &focused,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    }
+    let draft = temp.path().join("draft.txt");
+    let focused = Some(draft.clone());
+    for action in [
+        KeyAction::StartRename,
+        KeyAction::ConfirmInput {
+            value: "final.txt".to_string(),
+        },
+    ] {
+        call_handle_action!(
+            action,
+            &mut state,
+            &mut navigator,
+            &focused,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    }
+
+    let summaries: Vec<String> = state
+        .operation_log
+        .entries()
+        .iter()
+        .map(|op| op.summary(temp.path()))
+        .collect();
+    assert_eq!(
+        summaries,
+        vec!["Create draft.txt", "Rename draft.txt -> final.txt"]
+    );
+
+    // The newest entry is selected first and reveals the renamed file
+    for action in [
+        KeyAction::OpenOperationHistory,
+        KeyAction::OperationHistoryReveal,
+    ] {
+        call_handle_action!(
+            action,
+            &mut state,
+            &mut navigator,
+            &focused,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    }
+    assert!(matches!(state.mode, ViewMode::Browse));
+    assert_eq!(state.fuzzy_jump_target, Some(temp.path().join("final.txt")));
+}
+
+#[test]
+fn test_confine_to_root_refuses_rename_via_parent() {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path().join("root");
+    std::fs::create_dir(&root).unwrap();
+    let file_path = root.join("notes.txt");
+    std::fs::write(&file_path, "content").unwrap();
+
+    let mut state = create_test_state(&root);
+    state.confine_to_root = true;
+    let mut navigator = create_test_navigator(&root);
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    let focused = Some(file_path.clone());
+
+    for action in [
+        KeyAction::StartRename,
+        KeyAction::ConfirmInput {
+            value: "../escaped.txt".to_string(),
+        },
+    ] {
+        call_handle_action!(
+            action,
+            &mut state,
+            &mut navigator,
+            &focused,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    }
+
+    assert!(file_path.exists());
+    assert!(!temp.path().join("escaped.txt").exists());
+    assert!(matches!(state.mode, ViewMode::Browse));
+    assert_eq!(state.message_severity(), Some(crate::core::Severity::Error));
+    assert!(state.operation_log.is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_confine_to_root_refuses_symlink_outside() {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path().join("root");
+    let outside = temp.path().join("outside");
+    std::fs::create_dir(&root).unwrap();
+    std::fs::create_dir(&outside).unwrap();
+    let link = root.join("escape");
+    std::os::unix::fs::symlink(&outside, &link).unwrap();
+
+    let mut state = create_test_state(&root);
+    state.confine_to_root = true;
+    let mut navigator = create_test_navigator(&root);
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    let focused = Some(link.clone());
+
+    // Delete is refused before the confirmation opens
+    call_handle_action!(
+        KeyAction::ConfirmDelete,
+        &mut state,
+        &mut navigator,
+        &focused,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert!(matches!(state.mode, ViewMode::Browse));
+    assert!(state
+        .message_text()
+        .is_some_and(|text| text.starts_with("Refused:")));
+    assert!(link.exists());
+
+    // Pasting into the linked directory is refused too
+    let source = root.join("a.txt");
+    std::fs::write(&source, "a").unwrap();
+    let mut clipboard = crate::action::Clipboard::new();
+    clipboard.copy(vec![source]);
+    state.clipboard = Some(clipboard);
+    call_handle_action!(
+        KeyAction::Paste,
+        &mut state,
+        &mut navigator,
+        &focused,
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert!(!outside.join("a.txt").exists());
+    assert!(state.operation_log.is_empty());
+}
+
+fn live_filter_tree() -> (TempDir, TreeNavigator) {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path();
+    std::fs::create_dir_all(root.join("src/render")).unwrap();
+    std::fs::create_dir_all(root.join("docs")).unwrap();
+    std::fs::write(root.join("src/main.rs"), "").unwrap();
+    std::fs::write(root.join("src/render/tree.rs"), "").unwrap();
+    std::fs::write(root.join("docs/guide.txt"), "").unwrap();
+    std::fs::write(root.join("README"), "").unwrap();
+
+    let mut navigator = create_test_navigator(root);
+    for dir in ["src", "src/render", "docs"] {
+        navigator.expand(&root.join(dir)).unwrap();
+    }
+    (temp, navigator)
+}
+
+fn displayed_names(navigator: &TreeNavigator, state: &AppState) -> Vec<String> {
+    displayed_entries(navigator, state)
+        .iter()
+        .skip(1)
+        .map(|e| e.name.clone())
+        .collect()
+}
+
+#[test]
+fn test_live_filter_narrows_and_widens() {
+    let (temp, navigator) = live_filter_tree();
+    let mut state = create_test_state(temp.path());
+    let all = displayed_names(&navigator, &state);
+
+    let set_query = |state: &mut AppState, query: &str| {
+        state.mode = ViewMode::LiveFilter {
+            query: query.to_string(),
+            origin: None,
+        };
+    };
+
+    set_query(&mut state, "");
+    assert_eq!(displayed_names(&navigator, &state), all);
+
+    set_query(&mut state, "gui");
+    assert_eq!(
+        displayed_names(&navigator, &state),
+        vec!["docs", "guide.txt"]
+    );
+
+    set_query(&mut state, "tree");
+    assert_eq!(
+        displayed_names(&navigator, &state),
+        vec!["src", "render", "tree.rs"]
+    );
+
+    // Backspace widens again
+    set_query(&mut state, "t");
+    let widened = displayed_names(&navigator, &state);
+    assert!(widened.contains(&"tree.rs".to_string()));
+    assert!(widened.contains(&"guide.txt".to_string()));
+
+    set_query(&mut state, "zzz");
+    assert!(displayed_names(&navigator, &state).is_empty());
+
+    // Leaving the mode restores the full tree
+    state.mode = ViewMode::Browse;
+    assert_eq!(displayed_names(&navigator, &state), all);
+}
+
+#[test]
+fn test_live_filter_keeps_ancestors_and_focuses_best_match() {
+    let (temp, mut navigator) = live_filter_tree();
+    let mut state = create_test_state(temp.path());
+    state.mode = ViewMode::LiveFilter {
+        query: "tree".to_string(),
+        origin: Some(temp.path().join("README")),
+    };
+
+    assert_eq!(
+        displayed_names(&navigator, &state),
+        vec!["src", "render", "tree.rs"]
+    );
+    let entries = displayed_entries(&navigator, &state);
+    focus_best_live_match(&entries, &mut state);
+    assert_eq!(entries[state.focus_index].name, "tree.rs");
+
+    // Enter returns to the full tree focused on the match
+    let tree_rs = temp.path().join("src/render/tree.rs");
+    let snapshots = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    call_handle_action!(
+        KeyAction::LockLiveFilter,
+        &mut state,
+        &mut navigator,
+        &Some(tree_rs.clone()),
+        &snapshots,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert!(matches!(state.mode, ViewMode::Browse));
+    assert_eq!(state.fuzzy_jump_target, Some(tree_rs));
+}
+
+#[test]
+fn test_live_filter_cancel_refocuses_origin() {
+    let (temp, mut navigator) = live_filter_tree();
+    let mut state = create_test_state(temp.path());
+    let readme = temp.path().join("README");
+    state.mode = ViewMode::LiveFilter {
+        query: "main".to_string(),
+        origin: Some(readme.clone()),
+    };
+
+    let snapshots = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    call_handle_action!(
+        KeyAction::Cancel,
+        &mut state,
+        &mut navigator,
+        &Some(temp.path().join("src/main.rs")),
+        &snapshots,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+    assert!(matches!(state.mode, ViewMode::Browse));
+    assert_eq!(state.fuzzy_jump_target, Some(readme));
+}
+
+#[test]
+fn test_start_rename_cursor_before_extension() {
+    let temp = TempDir::new().unwrap();
+    let archive = temp.path().join("foo.tar.gz");
+    std::fs::write(&archive, "").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    let focused = Some(archive);
+
+    for (select, expected) in [
+        (crate::action::RenameSelect::Stem, "foo.tar".len()),
+        (crate::action::RenameSelect::Name, "foo.tar.gz".len()),
+    ] {
+        state.rename_select = select;
+        call_handle_action!(
+            KeyAction::StartRename,
+            &mut state,
+            &mut navigator,
+            &focused,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+        let ViewMode::Input { buffer, cursor, .. } = &state.mode else {
+            panic!("expected rename prompt");
+        };
+        assert_eq!((buffer.as_str(), *cursor), ("foo.tar.gz", expected));
+    }
+}
+
+/// Alt+= / Alt+- resize the preview in steps, starting from an even split
+#[test]
+fn test_resize_preview() {
+    let temp = TempDir::new().unwrap();
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    let mut resize = |state: &mut AppState, grow: bool| {
+        call_handle_action!(
+            KeyAction::ResizePreview { grow },
+            state,
+            &mut navigator,
+            &None,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    };
+
+    resize(&mut state, true);
+    assert_eq!(state.preview_split, 60);
+    assert_eq!(state.message_text(), Some("Preview: 60%"));
+
+    for _ in 0..5 {
+        resize(&mut state, true);
+    }
+    assert_eq!(state.preview_split, 80);
+
+    for _ in 0..10 {
+        resize(&mut state, false);
+    }
+    assert_eq!(state.preview_split, 20);
+    // The last share is kept for the next run
+    assert_eq!(state.ui_state.layout.preview_split, Some(20));
+}
+
+/// Enter on a file runs general.file_enter_action; directories still toggle
+#[test]
+fn test_file_enter_action() {
+    use crate::core::FileEnterAction;
+
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("notes.txt");
+    let dir_path = temp.path().join("subdir");
+    std::fs::write(&file_path, "content").unwrap();
+    std::fs::create_dir(&dir_path).unwrap();
+    std::fs::write(dir_path.join("inner.txt"), "").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    state.preview_visible = false;
+
+    let mut enter = |state: &mut AppState, navigator: &mut TreeNavigator, path: &Path| {
+        call_handle_action!(
+            KeyAction::ToggleExpand,
+            state,
+            navigator,
+            &Some(path.to_path_buf()),
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    };
+
+    state.file_enter_action = FileEnterAction::Select;
+    enter(&mut state, &mut navigator, &file_path);
+    assert!(state.selected_paths.contains(&file_path));
+    assert!(matches!(state.mode, ViewMode::Browse));
+    enter(&mut state, &mut navigator, &file_path);
+    assert!(state.selected_paths.is_empty());
+
+    // Directories expand whatever the file action is
+    let before = navigator.visible_count();
+    enter(&mut state, &mut navigator, &dir_path);
+    assert!(navigator.visible_count() > before);
+    assert!(state.selected_paths.is_empty());
+
+    #[cfg(unix)]
+    {
+        state.file_enter_action = FileEnterAction::Open;
+        state.opener = "true".to_string();
+        enter(&mut state, &mut navigator, &file_path);
+        assert_eq!(state.message_text(), Some("Opened notes.txt"));
+        assert!(matches!(state.mode, ViewMode::Browse));
+
+        state.opener = temp.path().join("no-such-opener").display().to_string();
+        enter(&mut state, &mut navigator, &file_path);
+        assert!(state.message_text().unwrap().starts_with("Failed: open"));
+    }
+
+    state.file_enter_action = FileEnterAction::Preview;
+    enter(&mut state, &mut navigator, &file_path);
+    assert!(matches!(state.mode, ViewMode::Preview { scroll: 0 }));
+}
+
+/// `!` runs the typed command once per marked file and streams the results
+#[cfg(unix)]
+#[test]
+fn test_foreach_command_runs_per_marked_file() {
+    let temp = TempDir::new().unwrap();
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    let a = temp.path().join("a.txt");
+    let b = temp.path().join("b.txt");
+    std::fs::write(&a, "one\n").unwrap();
+    std::fs::write(&b, "one\ntwo\n").unwrap();
+    state.selected_paths.insert(b.clone());
+    state.selected_paths.insert(a.clone());
+
+    for action in [
+        KeyAction::StartForeachCommand,
+        KeyAction::ConfirmInput {
+            value: "wc -l < {path}".to_string(),
+        },
+    ] {
+        call_handle_action!(
+            action,
+            &mut state,
+            &mut navigator,
+            &None,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    }
+    assert_eq!(state.mode, ViewMode::CommandOutput { scroll: 0 });
+
+    let run = state.foreach_run.as_mut().unwrap();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while run.is_running() && std::time::Instant::now() < deadline {
+        run.poll();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    let results: Vec<(std::path::PathBuf, String)> = run
+        .results
+        .iter()
+        .map(|r| (r.path.clone(), r.output.trim().to_string()))
+        .collect();
+    assert_eq!(results, vec![(a, "1".to_string()), (b, "2".to_string())]);
+}
+
+#[test]
+fn test_rename_onto_existing_file_asks_first() {
+    let temp = TempDir::new().unwrap();
+    let draft = temp.path().join("draft.txt");
+    let existing = temp.path().join("final.txt");
+    std::fs::write(&draft, "new").unwrap();
+    std::fs::write(&existing, "old").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+    let focused = Some(draft.clone());
+
+    let mut run = |action: KeyAction, state: &mut AppState| {
+        call_handle_action!(
+            action,
+            state,
+            &mut navigator,
+            &focused,
+            &entries,
+            &context,
+            &mut text_preview,
+            &mut hex_preview,
+            &mut archive_preview
+        )
+        .unwrap();
+    };
+    let rename_to_final = [
+        KeyAction::StartRename,
+        KeyAction::ConfirmInput {
+            value: "final.txt".to_string(),
+        },
+    ];
+
+    // Nothing is replaced until the prompt is answered
+    for action in rename_to_final.clone() {
+        run(action, &mut state);
+    }
+    assert!(matches!(
+        &state.mode,
+        ViewMode::Confirm {
+            action: crate::core::PendingAction::ResolveRenameConflict { target, .. },
+        } if *target == existing
+    ));
+    assert_eq!(std::fs::read_to_string(&existing).unwrap(), "old");
+
+    // Cancel keeps both files
+    run(
+        KeyAction::ResolveRenameConflict {
+            resolution: crate::action::ConflictResolution::Skip,
+        },
+        &mut state,
+    );
+    assert!(matches!(state.mode, ViewMode::Browse));
+    assert!(draft.exists());
+
+    // Unique name keeps the existing file
+    for action in rename_to_final {
+        run(action, &mut state);
+    }
+    run(
+        KeyAction::ResolveRenameConflict {
+            resolution: crate::action::ConflictResolution::Rename,
+        },
+        &mut state,
+    );
+    assert!(!draft.exists());
+    assert_eq!(std::fs::read_to_string(&existing).unwrap(), "old");
+    assert_eq!(
+        std::fs::read_to_string(temp.path().join("final_1.txt")).unwrap(),
+        "new"
+    );
+}
+
+#[test]
+fn test_paste_of_cut_archive_member_is_refused() {
+    let temp = TempDir::new().unwrap();
+    let archive = temp.path().join("bundle.zip");
+    std::fs::write(&archive, "").unwrap();
+
+    let mut state = create_test_state(temp.path());
+    let mut navigator = create_test_navigator(temp.path());
+    let entries = create_test_entries(&navigator);
+    let context = ActionContext::default();
+    let mut text_preview: Option<TextPreview> = None;
+    let mut hex_preview: Option<HexPreview> = None;
+    let mut archive_preview: Option<ArchivePreview> = None;
+
+    let mut clipboard = crate::action::Clipboard::new();
+    clipboard.cut(vec![archive.join("docs/guide.md")]);
+    state.clipboard = Some(clipboard);
+
+    call_handle_action!(
+        KeyAction::Paste,
+        &mut state,
+        &mut navigator,
+        &Some(temp.path().to_path_buf()),
+        &entries,
+        &context,
+        &mut text_preview,
+        &mut hex_preview,
+        &mut archive_preview
+    )
+    .unwrap();
+
+    assert_eq!(state.message_text(), Some("Archive contents are read-only"));
+    assert!(state.clipboard.as_ref().is_some_and(|c| !c.is_empty()));
+}
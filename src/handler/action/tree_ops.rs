@@ -1,14 +1,41 @@
 //! Tree operation action handlers
 //!
-//! Handles Expand, Collapse, ToggleExpand, CollapseAll, ExpandAll
+//! Handles Expand, Collapse, ToggleExpand, CollapseAll, ExpandAll, SetRoot, RootUp
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::core::{AppState, ViewMode};
+use crate::core::{AppState, CollapseFallback, FileEnterAction, ViewMode};
+use crate::git::GitStatus;
 use crate::handler::key::KeyAction;
 use crate::tree::TreeNavigator;
 
-use super::EntrySnapshot;
+use super::{command, EntrySnapshot};
+
+/// Run the configured Enter action on a file
+fn open_file(state: &mut AppState, path: &Path) {
+    match state.file_enter_action {
+        FileEnterAction::Preview => state.mode = ViewMode::Preview { scroll: 0 },
+        FileEnterAction::Open => match command::open_with(path, &state.opener) {
+            Ok(()) => state.set_message(format!(
+                "Opened {}",
+                path.file_name().unwrap_or_default().to_string_lossy()
+            )),
+            Err(e) => state.set_error(format!("Failed: open - {}", e)),
+        },
+        FileEnterAction::Select => {
+            if !state.selected_paths.remove(path) {
+                state.selected_paths.insert(path.to_path_buf());
+            }
+        }
+    }
+}
+
+/// Explain why an unreadable directory did not expand
+fn report_denied(state: &mut AppState, navigator: &TreeNavigator, path: &Path) {
+    if navigator.is_denied(path) {
+        state.set_error(format!("Permission denied: {}", path.display()));
+    }
+}
 
 /// Handle tree operations
 pub fn handle(
@@ -22,11 +49,16 @@ pub fn handle(
         KeyAction::Expand => {
             if let Some(path) = focused_path {
                 navigator.expand(path)?;
+                report_denied(state, navigator, path);
             }
         }
         KeyAction::Collapse => {
             if let Some(path) = focused_path {
-                navigator.collapse(path);
+                if navigator.is_expanded(path) {
+                    navigator.collapse(path);
+                } else {
+                    collapse_fallback(state, navigator, path, entries)?;
+                }
             }
         }
         KeyAction::ToggleExpand => {
@@ -34,11 +66,11 @@ pub fn handle(
                 // Close side preview panel
                 state.preview_visible = false;
             } else if let Some(ref path) = focused_path {
-                if path.is_dir() {
+                if path.is_dir() || navigator.is_expandable(path) {
                     navigator.toggle_expand(path)?;
+                    report_denied(state, navigator, path);
                 } else {
-                    // File: open fullscreen preview
-                    state.mode = ViewMode::Preview { scroll: 0 };
+                    open_file(state, path);
                 }
             }
         }
@@ -64,7 +96,86 @@ pub fn handle(
                 navigator.expand(&path)?;
             }
         }
+        KeyAction::SetRoot => {
+            let target = focused_path.as_ref().and_then(|p| {
+                if p.is_dir() {
+                    Some(p.clone())
+                } else {
+                    p.parent().map(Path::to_path_buf)
+                }
+            });
+            match target {
+                Some(dir) if dir != state.root => reroot(state, navigator, dir)?,
+                _ => state.set_message("Already the root"),
+            }
+        }
+        KeyAction::RootUp => root_up(state, navigator)?,
         _ => {}
     }
     Ok(())
 }
+
+/// Apply `state.collapse_fallback` to Collapse on an entry with nothing to collapse
+fn collapse_fallback(
+    state: &mut AppState,
+    navigator: &mut TreeNavigator,
+    path: &Path,
+    entries: &[EntrySnapshot],
+) -> anyhow::Result<()> {
+    let fallback = state.collapse_fallback;
+    if fallback == CollapseFallback::None {
+        return Ok(());
+    }
+    let depth = entries
+        .iter()
+        .find(|e| e.path == path)
+        .map_or(0, |e| e.depth);
+    match (fallback, depth) {
+        // Top-level entries (and the root itself) have no parent to focus in the tree
+        (CollapseFallback::RootUp, 0 | 1) => root_up(state, navigator)?,
+        (_, 0) => {}
+        _ => state.fuzzy_jump_target = path.parent().map(Path::to_path_buf),
+    }
+    Ok(())
+}
+
+/// Move the root to its parent, focusing the previous root
+fn root_up(state: &mut AppState, navigator: &mut TreeNavigator) -> anyhow::Result<()> {
+    let previous = state.root.clone();
+    match previous.parent() {
+        Some(parent) => {
+            reroot(state, navigator, parent.to_path_buf())?;
+            // Focus the directory we came from
+            state.fuzzy_jump_target = Some(previous);
+        }
+        None => state.set_message("Already at the filesystem root"),
+    }
+    Ok(())
+}
+
+/// Rebuild the tree rooted at `root` and re-detect git status
+fn reroot(
+    state: &mut AppState,
+    navigator: &mut TreeNavigator,
+    root: PathBuf,
+) -> anyhow::Result<()> {
+    let mut new_navigator = match TreeNavigator::new(&root, state.show_hidden) {
+        Ok(nav) => nav,
+        Err(e) => {
+            state.set_error(format!("Failed: {}", e));
+            return Ok(());
+        }
+    };
+    new_navigator.set_sort(state.sort_mode, state.sort_reverse)?;
+    new_navigator.set_dirs_first(state.dirs_first)?;
+    *navigator = new_navigator;
+
+    state.git_status = GitStatus::detect(&root);
+    state.workspace_git.clear();
+    state.set_message(format!("Root: {}", root.display()));
+    state.root = root;
+    state.focus_index = 0;
+    state.viewport_top = 0;
+    state.selected_paths.clear();
+    Ok(())
+}
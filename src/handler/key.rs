@@ -4,7 +4,8 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::path::PathBuf;
 
 use super::keymap::KeyBindingRegistry;
-use crate::core::{AppState, FocusTarget, ViewMode};
+use crate::action::{ConflictResolution, LinkKind};
+use crate::core::{AppState, FocusTarget, InputPurpose, PendingAction, SortMode, ViewMode};
 
 /// Actions that can result from key handling
 #[derive(Debug, Clone)]
@@ -23,6 +24,8 @@ pub enum KeyAction {
     MoveToTop,
     /// Move to bottom
     MoveToBottom,
+    /// Append a digit to the count prefix of the next motion
+    CountDigit { digit: u8 },
     /// Expand current entry
     Expand,
     /// Collapse current entry
@@ -33,20 +36,49 @@ pub enum KeyAction {
     CollapseAll,
     /// Expand all entries
     ExpandAll,
+    /// Make the focused directory the tree root
+    SetRoot,
+    /// Make the parent of the current root the tree root
+    RootUp,
     /// Toggle selection mark
     ToggleMark,
     /// Clear all marks
     ClearMarks,
+    /// Open the marked paths overview
+    OpenMarksOverview,
+    /// Move up in the marks overview
+    MarksUp,
+    /// Move down in the marks overview
+    MarksDown,
+    /// Reveal and focus the selected mark
+    MarksJump,
+    /// Unmark the selected mark
+    MarksUnmark,
     /// Copy selected to clipboard
     Copy,
     /// Cut selected to clipboard
     Cut,
     /// Paste from clipboard
     Paste,
+    /// Paste as a copy, even when the clipboard holds a cut
+    PasteCopy,
     /// Start delete confirmation
     ConfirmDelete,
+    /// Revert the last rename, move or trash
+    Undo,
+    /// Re-read keymap.toml (handled in event loop)
+    ReloadKeymap,
+    /// Start delete confirmation, deleting permanently instead of to the trash
+    ConfirmDeletePermanent,
     /// Execute confirmed delete
     ExecuteDelete,
+    /// Resolve the current paste conflict (or all remaining ones)
+    ResolvePasteConflict {
+        resolution: ConflictResolution,
+        apply_all: bool,
+    },
+    /// Resolve a rename onto an existing name (Skip cancels the rename)
+    ResolveRenameConflict { resolution: ConflictResolution },
     /// Start rename input
     StartRename,
     /// Start new file input
@@ -57,12 +89,30 @@ pub enum KeyAction {
     StartSearch,
     /// Search for next match
     SearchNext,
+    /// Prompt for text to search for in file contents
+    StartContentSearch,
+    /// Move up in content search results
+    ContentSearchUp,
+    /// Move down in content search results
+    ContentSearchDown,
+    /// Reveal the file of the selected content search match
+    ContentSearchSelect,
+    /// Prompt for a shell command to run on each marked file (`{path}` = the file)
+    StartForeachCommand,
+    /// Scroll the command output up
+    CommandOutputUp,
+    /// Scroll the command output down
+    CommandOutputDown,
     /// Refresh tree
     Refresh,
     /// Toggle hidden files
     ToggleHidden,
+    /// Toggle hidden files in the directory at the cursor only
+    ToggleHiddenHere,
     /// Copy path to system clipboard
     CopyPath,
+    /// Copy path relative to the root to system clipboard
+    CopyRelativePath,
     /// Copy filename to system clipboard
     CopyFilename,
     /// Copy file content to system clipboard
@@ -73,10 +123,14 @@ pub enum KeyAction {
     CopyContextPack,
     /// Copy review-oriented AI context pack to clipboard
     CopyContextPackReview,
+    /// Copy all text files of the focused directory, concatenated
+    CopyConcat,
     /// Open preview
     OpenPreview,
     /// Toggle quick preview panel
     ToggleQuickPreview,
+    /// Grow or shrink the side preview's share of the split
+    ResizePreview { grow: bool },
     /// Confirm current input
     ConfirmInput { value: String },
     /// Cancel current input/mode
@@ -93,6 +147,22 @@ pub enum KeyAction {
     PreviewToTop,
     /// Preview scroll to bottom
     PreviewToBottom,
+    /// Scroll the table preview one column left
+    PreviewScrollLeft,
+    /// Scroll the table preview one column right
+    PreviewScrollRight,
+    /// Prompt for text to search for in the fullscreen text preview
+    StartPreviewSearch,
+    /// Scroll the text preview to the next search match
+    PreviewSearchNext,
+    /// Scroll the text preview to the previous search match
+    PreviewSearchPrev,
+    /// Show or hide git blame beside the text preview
+    ToggleBlame,
+    /// Scroll the text preview to the next merge conflict block
+    PreviewNextConflict,
+    /// Scroll the text preview to the previous merge conflict block
+    PreviewPrevConflict,
     /// Select and quit (pick mode)
     PickSelect,
     /// Select and quit (select mode)
@@ -113,6 +183,12 @@ pub enum KeyAction {
     FuzzyDown,
     /// Confirm fuzzy finder selection
     FuzzyConfirm { path: std::path::PathBuf },
+    /// Create symlinks to the clipboard (or focused) entries
+    CreateSymlink,
+    /// Create hard links to the clipboard (or focused) entries
+    CreateHardlink,
+    /// Switch the symlink being created between relative and absolute target
+    ToggleLinkRelative,
     /// Enter bookmark set mode
     StartBookmarkSet,
     /// Enter bookmark jump mode
@@ -127,18 +203,38 @@ pub enum KeyAction {
     ApplyFilter { pattern: String },
     /// Clear filter
     ClearFilter,
-    /// Cycle sort mode (Name -> Size -> Date -> Name)
+    /// Start type-to-narrow filtering of the tree
+    StartLiveFilter,
+    /// Leave type-to-narrow, keeping focus on the focused match
+    LockLiveFilter,
+    /// Cycle sort mode (Name -> Size -> Date -> Extension -> Type -> Name)
     CycleSort,
+    /// Enter sort selector mode (waiting for a sort key)
+    StartSortSelect,
+    /// Set the sort mode directly
+    SetSort { mode: SortMode },
+    /// Toggle ascending/descending sort direction
+    ToggleSortReverse,
+    /// Toggle listing directories above files
+    ToggleDirsFirst,
     /// Search for previous match
     SearchPrev,
+    /// Toggle case-sensitive matching in search and fuzzy finder
+    ToggleCaseSensitive,
     /// Go to previous PDF page
     PdfPrevPage,
     /// Go to next PDF page
     PdfNextPage,
+    /// Switch PDF preview between rendered page and text layer
+    TogglePdfText,
     /// Stage file(s) for git commit
     GitStage,
     /// Unstage file(s) from git commit
     GitUnstage,
+    /// Ask to discard the working tree changes of file(s)
+    GitDiscard,
+    /// Discard the changes pending confirmation
+    GitDiscardConfirm,
     /// Start bulk rename mode
     StartBulkRename,
     /// Switch to next field in bulk rename
@@ -148,6 +244,8 @@ pub enum KeyAction {
         from_pattern: String,
         to_pattern: String,
     },
+    /// Rename selected (or all visible) entries in $EDITOR
+    EditorRename,
     /// Open a new tab
     NewTab,
     /// Close the current tab
@@ -166,10 +264,24 @@ pub enum KeyAction {
     SelectAll,
     /// Invert selection
     InvertSelection,
+    /// Prompt for a glob and mark matching visible entries
+    SelectByPattern,
+    /// Prompt for a glob and unmark matching entries
+    DeselectByPattern,
+    /// Prompt for a glob and mark matching entries under the focused directory
+    SelectByPatternInSubtree,
     /// Select all git changed files
     SelectGitChanged,
     /// Select test pair for current file
     SelectTestPair,
+    /// Toggle soft-wrapping in text previews
+    TogglePreviewWrap,
+    /// Toggle the line number gutter in text previews
+    TogglePreviewLineNumbers,
+    /// Toggle preview borders and titles
+    TogglePreviewBorders,
+    /// Toggle the relative-number gutter in the tree
+    ToggleRelativeNumbers,
     /// Toggle peek mode (mini preview in status bar)
     TogglePeekMode,
     /// Toggle AI focus mode (ultra-compact UI)
@@ -194,6 +306,54 @@ pub enum KeyAction {
     AiHistoryDown,
     /// Select AI history entry
     AiHistorySelect,
+    /// Open recent files popup
+    OpenRecentFiles,
+    /// Move up in recent files list
+    RecentFilesUp,
+    /// Move down in recent files list
+    RecentFilesDown,
+    /// Reveal and focus the selected recent file
+    RecentFilesSelect,
+    /// Open the operation history popup
+    OpenOperationHistory,
+    /// Move up in the operation history
+    OperationHistoryUp,
+    /// Move down in the operation history
+    OperationHistoryDown,
+    /// Reveal the file produced by the selected operation
+    OperationHistoryReveal,
+    /// Toggle showing only git-changed files and their ancestors
+    ToggleGitChangedOnly,
+    /// Prompt for a revision to diff the focused file against
+    StartDiffRevision,
+    /// Complete the revision in the diff prompt
+    CompleteDiffRevision,
+    /// Diff the two marked files against each other
+    DiffMarked,
+    /// Enter git copy mode (waiting for `d` diff or `b` blame)
+    StartYankGit,
+    /// Copy the working tree diff of the focused file
+    YankGitDiff,
+    /// Copy the blame of the focused file
+    YankGitBlame,
+    /// Prompt for a commit message and commit the staged changes
+    StartGitCommit { amend: bool },
+    /// Insert a line break in a multi-line prompt
+    InputNewline,
+    /// Open git stash list popup
+    OpenGitStash,
+    /// Move up in git stash list
+    GitStashUp,
+    /// Move down in git stash list
+    GitStashDown,
+    /// Apply the selected stash (keep it)
+    GitStashApply,
+    /// Apply and remove the selected stash
+    GitStashPop,
+    /// Ask to drop the selected stash
+    GitStashDrop,
+    /// Drop the stash pending confirmation
+    GitStashDropConfirm,
 }
 
 /// Handle key event and return the resulting action
@@ -202,15 +362,53 @@ pub fn handle_key_event(state: &AppState, key: KeyEvent) -> KeyAction {
         ViewMode::Browse => handle_browse_mode(state, key),
         ViewMode::VisualSelect { .. } => handle_visual_select_mode(state, key),
         ViewMode::Search { query } => handle_search_mode(key, query),
+        ViewMode::Input {
+            purpose: InputPurpose::CreateLink { kind, .. },
+            ..
+        } if key.code == KeyCode::Tab => toggle_link_relative(*kind),
+        ViewMode::Input {
+            purpose: InputPurpose::DiffRevision { .. },
+            ..
+        } if key.code == KeyCode::Tab => KeyAction::CompleteDiffRevision,
+        ViewMode::Input {
+            purpose: InputPurpose::CommitMessage { .. },
+            ..
+        } if key.code == KeyCode::Enter && key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::InputNewline
+        }
         ViewMode::Input { buffer, .. } => handle_input_mode(key, buffer),
+        ViewMode::Confirm {
+            action: PendingAction::ResolvePasteConflict { .. },
+        } => handle_paste_conflict_mode(key),
+        ViewMode::Confirm {
+            action: PendingAction::ResolveRenameConflict { .. },
+        } => handle_rename_conflict_mode(key),
+        ViewMode::Confirm {
+            action: PendingAction::DropStash { .. },
+        } => handle_stash_drop_confirm_mode(key),
+        ViewMode::Confirm {
+            action: PendingAction::GitDiscard { .. },
+        } => handle_git_discard_confirm_mode(key),
         ViewMode::Confirm { .. } => handle_confirm_mode(key),
-        ViewMode::Preview { .. } => handle_preview_mode(key),
+        ViewMode::Preview { .. } => match &state.preview_search {
+            Some(query) => handle_preview_search_mode(key, query),
+            None => handle_preview_mode(key),
+        },
         ViewMode::FuzzyFinder { .. } => handle_fuzzy_finder_mode(key),
         ViewMode::Help => handle_help_mode(key),
         ViewMode::AiHistory { .. } => handle_ai_history_mode(key),
+        ViewMode::RecentFiles { .. } => handle_recent_files_mode(key),
+        ViewMode::OperationHistory { .. } => handle_operation_history_mode(key),
+        ViewMode::ContentSearch { .. } => handle_content_search_mode(key),
+        ViewMode::CommandOutput { .. } => handle_command_output_mode(key),
+        ViewMode::MarksOverview { .. } => handle_marks_overview_mode(key),
+        ViewMode::GitStash { .. } => handle_git_stash_mode(key),
         ViewMode::BookmarkSet => handle_bookmark_set_mode(key),
         ViewMode::BookmarkJump => handle_bookmark_jump_mode(key),
+        ViewMode::SortSelect => handle_sort_select_mode(key),
+        ViewMode::YankGit => handle_yank_git_mode(key),
         ViewMode::Filter { query } => handle_filter_mode(key, query),
+        ViewMode::LiveFilter { .. } => handle_live_filter_mode(key),
         ViewMode::BulkRename {
             from_pattern,
             to_pattern,
@@ -249,13 +447,42 @@ pub fn handle_key_event_with_registry(
                 handle_search_mode(key, query)
             }
         }
+        ViewMode::Input {
+            purpose: InputPurpose::CreateLink { kind, .. },
+            ..
+        } if key.code == KeyCode::Tab => toggle_link_relative(*kind),
+        ViewMode::Input {
+            purpose: InputPurpose::DiffRevision { .. },
+            ..
+        } if key.code == KeyCode::Tab => KeyAction::CompleteDiffRevision,
+        ViewMode::Input {
+            purpose: InputPurpose::CommitMessage { .. },
+            ..
+        } if key.code == KeyCode::Enter && key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::InputNewline
+        }
         ViewMode::Input { buffer, .. } => handle_input_mode(key, buffer),
+        ViewMode::Confirm {
+            action: PendingAction::ResolvePasteConflict { .. },
+        } => handle_paste_conflict_mode(key),
+        ViewMode::Confirm {
+            action: PendingAction::ResolveRenameConflict { .. },
+        } => handle_rename_conflict_mode(key),
+        ViewMode::Confirm {
+            action: PendingAction::DropStash { .. },
+        } => handle_stash_drop_confirm_mode(key),
+        ViewMode::Confirm {
+            action: PendingAction::GitDiscard { .. },
+        } => handle_git_discard_confirm_mode(key),
         ViewMode::Confirm { .. } => registry
             .lookup_confirm(&key)
             .unwrap_or_else(|| handle_confirm_mode(key)),
-        ViewMode::Preview { .. } => registry
-            .lookup_preview(&key)
-            .unwrap_or_else(|| handle_preview_mode(key)),
+        ViewMode::Preview { .. } => match &state.preview_search {
+            Some(query) => handle_preview_search_mode(key, query),
+            None => registry
+                .lookup_preview(&key)
+                .unwrap_or_else(|| handle_preview_mode(key)),
+        },
         ViewMode::FuzzyFinder { .. } => registry
             .lookup_fuzzy(&key)
             .unwrap_or_else(|| handle_fuzzy_finder_mode(key)),
@@ -263,8 +490,16 @@ pub fn handle_key_event_with_registry(
             .lookup_help(&key)
             .unwrap_or_else(|| handle_help_mode(key)),
         ViewMode::AiHistory { .. } => handle_ai_history_mode(key),
+        ViewMode::RecentFiles { .. } => handle_recent_files_mode(key),
+        ViewMode::OperationHistory { .. } => handle_operation_history_mode(key),
+        ViewMode::ContentSearch { .. } => handle_content_search_mode(key),
+        ViewMode::CommandOutput { .. } => handle_command_output_mode(key),
+        ViewMode::MarksOverview { .. } => handle_marks_overview_mode(key),
+        ViewMode::GitStash { .. } => handle_git_stash_mode(key),
         ViewMode::BookmarkSet => handle_bookmark_set_mode(key),
         ViewMode::BookmarkJump => handle_bookmark_jump_mode(key),
+        ViewMode::SortSelect => handle_sort_select_mode(key),
+        ViewMode::YankGit => handle_yank_git_mode(key),
         ViewMode::Filter { query } => {
             if let Some(mut action) = registry.lookup_filter(&key) {
                 if let KeyAction::ApplyFilter { ref mut pattern } = action {
@@ -278,6 +513,7 @@ pub fn handle_key_event_with_registry(
                 handle_filter_mode(key, query)
             }
         }
+        ViewMode::LiveFilter { .. } => handle_live_filter_mode(key),
         ViewMode::BulkRename {
             from_pattern,
             to_pattern,
@@ -368,6 +604,13 @@ fn apply_browse_context(state: &AppState, action: KeyAction) -> KeyAction {
                 KeyAction::Refresh
             }
         }
+        KeyAction::GitDiscard => {
+            if state.focus_target == FocusTarget::Preview {
+                KeyAction::PreviewPrevConflict
+            } else {
+                KeyAction::GitDiscard
+            }
+        }
         KeyAction::StartFilter => {
             if state.filter_pattern.is_some() {
                 KeyAction::ClearFilter
@@ -375,7 +618,12 @@ fn apply_browse_context(state: &AppState, action: KeyAction) -> KeyAction {
                 KeyAction::StartFilter
             }
         }
-        KeyAction::PreviewPageUp | KeyAction::PreviewPageDown => {
+        KeyAction::PreviewPageUp
+        | KeyAction::PreviewPageDown
+        | KeyAction::PreviewScrollLeft
+        | KeyAction::PreviewScrollRight
+        | KeyAction::PreviewNextConflict
+        | KeyAction::PreviewPrevConflict => {
             if state.focus_target == FocusTarget::Preview {
                 action
             } else {
@@ -414,6 +662,22 @@ fn handle_browse_mode(state: &AppState, key: KeyEvent) -> KeyAction {
             }
         }
 
+        // Count prefix (`5j`, `10G`) for tree motions; `0` only continues a count
+        KeyCode::Char(c @ '0'..='9')
+            if !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+                && state.focus_target != FocusTarget::Preview
+                && (c != '0' || state.count_prefix.is_some()) =>
+        {
+            KeyAction::CountDigit {
+                digit: c as u8 - b'0',
+            }
+        }
+
+        // Reload keymap.toml (Alt+K) - before plain 'k'
+        KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::ALT) => KeyAction::ReloadKeymap,
+
         // Navigation (focus-aware: Tree navigates files, Preview scrolls content)
         KeyCode::Up | KeyCode::Char('k') => {
             if state.focus_target == FocusTarget::Preview {
@@ -436,6 +700,10 @@ fn handle_browse_mode(state: &AppState, key: KeyEvent) -> KeyAction {
         KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::ALT) => {
             KeyAction::SelectGitStaged
         }
+        // Show only git-changed files (Alt+G)
+        KeyCode::Char('G') if key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::ToggleGitChangedOnly
+        }
         KeyCode::Char('g') => {
             if state.focus_target == FocusTarget::Preview {
                 KeyAction::PreviewToTop
@@ -465,15 +733,61 @@ fn handle_browse_mode(state: &AppState, key: KeyEvent) -> KeyAction {
                 KeyAction::None
             }
         }
-        KeyCode::Char('b') if state.focus_target == FocusTarget::Preview => {
+        KeyCode::Char('b')
+            if state.focus_target == FocusTarget::Preview
+                && !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+        {
             KeyAction::PreviewPageUp
         }
-        KeyCode::Char('f') if state.focus_target == FocusTarget::Preview => {
+        KeyCode::Char('f')
+            if state.focus_target == FocusTarget::Preview
+                && !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+        {
             KeyAction::PreviewPageDown
         }
+        KeyCode::Char('x') if state.focus_target == FocusTarget::Preview => {
+            KeyAction::PreviewNextConflict
+        }
+        KeyCode::Char('X') if state.focus_target == FocusTarget::Preview => {
+            KeyAction::PreviewPrevConflict
+        }
+        KeyCode::Char('w')
+            if state.focus_target == FocusTarget::Preview
+                && !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+        {
+            KeyAction::TogglePreviewWrap
+        }
+        KeyCode::Char('#')
+            if state.focus_target == FocusTarget::Preview
+                && !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+        {
+            KeyAction::TogglePreviewLineNumbers
+        }
+        KeyCode::Char('t')
+            if state.focus_target == FocusTarget::Preview
+                && !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+        {
+            KeyAction::TogglePdfText
+        }
 
         // Expand/Collapse and Focus switching
         // Arrow keys switch focus when preview is visible, l/h always expand/collapse
+        KeyCode::Right if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            KeyAction::ResizePreview { grow: true }
+        }
+        KeyCode::Left if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            KeyAction::ResizePreview { grow: false }
+        }
         KeyCode::Right => {
             if state.preview_visible {
                 KeyAction::FocusPreview
@@ -481,6 +795,11 @@ fn handle_browse_mode(state: &AppState, key: KeyEvent) -> KeyAction {
                 KeyAction::Expand
             }
         }
+        // Create links (Alt+l symlink, Alt+L hard link) - before plain 'l'/'L'
+        KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::ALT) => KeyAction::CreateSymlink,
+        KeyCode::Char('L') if key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::CreateHardlink
+        }
         KeyCode::Char('l') => KeyAction::Expand,
         KeyCode::Left => {
             if state.preview_visible {
@@ -500,6 +819,8 @@ fn handle_browse_mode(state: &AppState, key: KeyEvent) -> KeyAction {
         }
         KeyCode::Char('H') => KeyAction::CollapseAll,
         KeyCode::Char('L') => KeyAction::ExpandAll,
+        KeyCode::Char('>') => KeyAction::SetRoot,
+        KeyCode::Char('<') => KeyAction::RootUp,
 
         // Selection
         KeyCode::Char(' ') => KeyAction::ToggleMark,
@@ -536,7 +857,19 @@ fn handle_browse_mode(state: &AppState, key: KeyEvent) -> KeyAction {
             KeyAction::CopyForClaude
         }
         KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::ALT) => KeyAction::CopyCompact,
+        KeyCode::Char('Y') if key.modifiers.contains(KeyModifiers::ALT) => KeyAction::CopyConcat,
         KeyCode::Char('y') => KeyAction::Copy,
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::StartDiffRevision
+        }
+        KeyCode::Char('D') if key.modifiers.contains(KeyModifiers::ALT) => KeyAction::DiffMarked,
+        KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::ALT) => KeyAction::StartYankGit,
+        KeyCode::Char('m') if key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::StartGitCommit { amend: false }
+        }
+        KeyCode::Char('M') if key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::StartGitCommit { amend: true }
+        }
         KeyCode::Char('d') => {
             if key.modifiers.contains(KeyModifiers::SHIFT) {
                 KeyAction::ConfirmDelete
@@ -544,6 +877,9 @@ fn handle_browse_mode(state: &AppState, key: KeyEvent) -> KeyAction {
                 KeyAction::Cut
             }
         }
+        KeyCode::Delete if key.modifiers.contains(KeyModifiers::SHIFT) => {
+            KeyAction::ConfirmDeletePermanent
+        }
         KeyCode::Char('D') | KeyCode::Delete => KeyAction::ConfirmDelete,
         // AI history (Ctrl+Shift+P)
         KeyCode::Char('P')
@@ -565,6 +901,7 @@ fn handle_browse_mode(state: &AppState, key: KeyEvent) -> KeyAction {
         KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::ALT) => {
             KeyAction::TogglePeekMode
         }
+        KeyCode::Char('P') if key.modifiers.contains(KeyModifiers::ALT) => KeyAction::PasteCopy,
         KeyCode::Char('p') => KeyAction::Paste,
 
         // Select recent commit files (Alt+R) - before plain 'r'
@@ -584,14 +921,25 @@ fn handle_browse_mode(state: &AppState, key: KeyEvent) -> KeyAction {
         KeyCode::Char('r') => KeyAction::StartRename,
         KeyCode::Char('a') => KeyAction::StartNewFile,
         KeyCode::Char('A') => KeyAction::StartNewDir,
+        KeyCode::Char('E') => KeyAction::EditorRename,
+        KeyCode::Char('O') => KeyAction::OpenRecentFiles,
+        KeyCode::Char('U') => KeyAction::OpenOperationHistory,
+        KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyAction::Undo,
+        KeyCode::Char('!') => KeyAction::StartForeachCommand,
 
         // Search
         KeyCode::Char('/') => KeyAction::StartSearch,
+        KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            KeyAction::StartContentSearch
+        }
+        KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::ToggleRelativeNumbers
+        }
         KeyCode::Char('n') => KeyAction::SearchNext,
         KeyCode::Char('N') => KeyAction::SearchPrev,
 
         // Sort
-        KeyCode::Char('S') => KeyAction::CycleSort,
+        KeyCode::Char('S') => KeyAction::StartSortSelect,
 
         // Refresh, bulk rename, and toggle
         KeyCode::Char('R') => {
@@ -603,9 +951,15 @@ fn handle_browse_mode(state: &AppState, key: KeyEvent) -> KeyAction {
             }
         }
         KeyCode::F(5) => KeyAction::Refresh,
+        KeyCode::Char('.') if key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::ToggleHiddenHere
+        }
         KeyCode::Char('.') => KeyAction::ToggleHidden,
 
         // Copy to system clipboard
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::CopyRelativePath
+        }
         KeyCode::Char('c') => KeyAction::CopyPath,
         KeyCode::Char('C') => KeyAction::CopyFilename,
         // Copy content to clipboard (Y for content)
@@ -620,7 +974,22 @@ fn handle_browse_mode(state: &AppState, key: KeyEvent) -> KeyAction {
 
         // Visual selection and batch operations
         KeyCode::Char('V') => KeyAction::StartVisualSelect,
+        KeyCode::Char('M') => KeyAction::OpenMarksOverview,
         KeyCode::Char('*') => KeyAction::SelectAll,
+        KeyCode::Char('+') if key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::SelectByPatternInSubtree
+        }
+        KeyCode::Char('+') => KeyAction::SelectByPattern,
+        KeyCode::Char('=') if key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::ResizePreview { grow: true }
+        }
+        KeyCode::Char('-') if key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::ResizePreview { grow: false }
+        }
+        KeyCode::Char('-') => KeyAction::DeselectByPattern,
+        KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::TogglePreviewBorders
+        }
         KeyCode::Char('i') if key.modifiers.contains(KeyModifiers::ALT) => {
             KeyAction::InvertSelection
         }
@@ -641,6 +1010,7 @@ fn handle_browse_mode(state: &AppState, key: KeyEvent) -> KeyAction {
                 KeyAction::StartFilter
             }
         }
+        KeyCode::Char('i') => KeyAction::StartLiveFilter,
 
         // Shell integration - Alt+S for subshell (before Git operations)
         KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::ALT) => KeyAction::OpenSubshell,
@@ -648,6 +1018,8 @@ fn handle_browse_mode(state: &AppState, key: KeyEvent) -> KeyAction {
         // Git operations
         KeyCode::Char('s') => KeyAction::GitStage,
         KeyCode::Char('u') => KeyAction::GitUnstage,
+        KeyCode::Char('X') => KeyAction::GitDiscard,
+        KeyCode::Char('Z') => KeyAction::OpenGitStash,
 
         // Tab operations
         KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyAction::NewTab,
@@ -680,6 +1052,9 @@ fn handle_search_mode(key: KeyEvent, current_query: &str) -> KeyAction {
         },
         // Same key to cancel (toggle behavior)
         KeyCode::Char('/') | KeyCode::Esc => KeyAction::Cancel,
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::ToggleCaseSensitive
+        }
         _ => KeyAction::None, // Buffer updates handled separately
     }
 }
@@ -695,6 +1070,14 @@ fn handle_input_mode(key: KeyEvent, current_buffer: &str) -> KeyAction {
     }
 }
 
+/// Tab in the link name prompt (only symlinks have a target style)
+fn toggle_link_relative(kind: LinkKind) -> KeyAction {
+    match kind {
+        LinkKind::Symlink { .. } => KeyAction::ToggleLinkRelative,
+        LinkKind::Hardlink => KeyAction::None,
+    }
+}
+
 /// Handle keys in confirm mode
 fn handle_confirm_mode(key: KeyEvent) -> KeyAction {
     match key.code {
@@ -704,6 +1087,37 @@ fn handle_confirm_mode(key: KeyEvent) -> KeyAction {
     }
 }
 
+/// Handle keys in the paste conflict dialog
+fn handle_paste_conflict_mode(key: KeyEvent) -> KeyAction {
+    let (resolution, apply_all) = match key.code {
+        KeyCode::Char('o') => (ConflictResolution::Overwrite, false),
+        KeyCode::Char('O') => (ConflictResolution::Overwrite, true),
+        KeyCode::Char('s') => (ConflictResolution::Skip, false),
+        KeyCode::Char('S') => (ConflictResolution::Skip, true),
+        KeyCode::Char('r') => (ConflictResolution::Rename, false),
+        KeyCode::Char('R') => (ConflictResolution::Rename, true),
+        KeyCode::Esc | KeyCode::Char('q') => return KeyAction::Cancel,
+        _ => return KeyAction::None,
+    };
+    KeyAction::ResolvePasteConflict {
+        resolution,
+        apply_all,
+    }
+}
+
+/// Handle keys in the rename overwrite dialog
+fn handle_rename_conflict_mode(key: KeyEvent) -> KeyAction {
+    let resolution = match key.code {
+        KeyCode::Char('o') | KeyCode::Char('y') => ConflictResolution::Overwrite,
+        KeyCode::Char('u') | KeyCode::Char('r') => ConflictResolution::Rename,
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('n') | KeyCode::Char('c') => {
+            ConflictResolution::Skip
+        }
+        _ => return KeyAction::None,
+    };
+    KeyAction::ResolveRenameConflict { resolution }
+}
+
 /// Handle keys in preview mode
 fn handle_preview_mode(key: KeyEvent) -> KeyAction {
     match key.code {
@@ -712,17 +1126,46 @@ fn handle_preview_mode(key: KeyEvent) -> KeyAction {
         }
         KeyCode::Up | KeyCode::Char('k') => KeyAction::PreviewScrollUp,
         KeyCode::Down | KeyCode::Char('j') => KeyAction::PreviewScrollDown,
+        KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::TogglePreviewBorders
+        }
         KeyCode::PageUp | KeyCode::Char('b') => KeyAction::PreviewPageUp,
         KeyCode::PageDown | KeyCode::Char('f') | KeyCode::Char(' ') => KeyAction::PreviewPageDown,
         KeyCode::Char('g') => KeyAction::PreviewToTop,
         KeyCode::Char('G') => KeyAction::PreviewToBottom,
+        KeyCode::Left | KeyCode::Char('h') => KeyAction::PreviewScrollLeft,
+        KeyCode::Right | KeyCode::Char('l') => KeyAction::PreviewScrollRight,
         // PDF navigation
         KeyCode::Char('[') => KeyAction::PdfPrevPage,
         KeyCode::Char(']') => KeyAction::PdfNextPage,
+        KeyCode::Char('t') => KeyAction::TogglePdfText,
+        // Text display
+        KeyCode::Char('w') => KeyAction::TogglePreviewWrap,
+        KeyCode::Char('#') => KeyAction::TogglePreviewLineNumbers,
+        // Search
+        KeyCode::Char('/') => KeyAction::StartPreviewSearch,
+        KeyCode::Char('n') => KeyAction::PreviewSearchNext,
+        KeyCode::Char('N') => KeyAction::PreviewSearchPrev,
+        // Git
+        KeyCode::Char('B') => KeyAction::ToggleBlame,
+        // Merge conflicts
+        KeyCode::Char('x') => KeyAction::PreviewNextConflict,
+        KeyCode::Char('X') => KeyAction::PreviewPrevConflict,
         _ => KeyAction::None,
     }
 }
 
+/// Handle keys while typing a search in the fullscreen preview
+fn handle_preview_search_mode(key: KeyEvent, current_query: &str) -> KeyAction {
+    match key.code {
+        KeyCode::Enter => KeyAction::ConfirmInput {
+            value: current_query.to_string(),
+        },
+        KeyCode::Esc => KeyAction::Cancel,
+        _ => KeyAction::None, // Buffer updates handled separately
+    }
+}
+
 /// Handle keys in fuzzy finder mode
 fn handle_fuzzy_finder_mode(key: KeyEvent) -> KeyAction {
     match key.code {
@@ -735,6 +1178,9 @@ fn handle_fuzzy_finder_mode(key: KeyEvent) -> KeyAction {
         KeyCode::Down | KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             KeyAction::FuzzyDown
         }
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::ALT) => {
+            KeyAction::ToggleCaseSensitive
+        }
         KeyCode::Up => KeyAction::FuzzyUp,
         KeyCode::Down => KeyAction::FuzzyDown,
         KeyCode::Enter => {
@@ -772,6 +1218,9 @@ fn handle_visual_select_mode(state: &AppState, key: KeyEvent) -> KeyAction {
         // Actions on selected items
         KeyCode::Char('y') => KeyAction::Copy,
         KeyCode::Char('d') => KeyAction::Cut,
+        KeyCode::Delete if key.modifiers.contains(KeyModifiers::SHIFT) => {
+            KeyAction::ConfirmDeletePermanent
+        }
         KeyCode::Char('D') | KeyCode::Delete => KeyAction::ConfirmDelete,
 
         // Confirm selection and exit visual mode
@@ -869,6 +1318,32 @@ fn handle_bookmark_set_mode(key: KeyEvent) -> KeyAction {
     }
 }
 
+/// Handle keys in sort selector mode (waiting for a sort key)
+fn handle_sort_select_mode(key: KeyEvent) -> KeyAction {
+    match key.code {
+        KeyCode::Char('r') => KeyAction::ToggleSortReverse,
+        KeyCode::Char('f') => KeyAction::ToggleDirsFirst,
+        // Same key again cycles like the old single-key binding
+        KeyCode::Char('S') => KeyAction::CycleSort,
+        KeyCode::Char(c) => match SortMode::from_key(c) {
+            Some(mode) => KeyAction::SetSort { mode },
+            None => KeyAction::None,
+        },
+        KeyCode::Esc => KeyAction::Cancel,
+        _ => KeyAction::None,
+    }
+}
+
+/// Handle keys in git copy mode (waiting for what to copy)
+fn handle_yank_git_mode(key: KeyEvent) -> KeyAction {
+    match key.code {
+        KeyCode::Char('d') => KeyAction::YankGitDiff,
+        KeyCode::Char('b') => KeyAction::YankGitBlame,
+        KeyCode::Esc => KeyAction::Cancel,
+        _ => KeyAction::None,
+    }
+}
+
 /// Handle keys in bookmark jump mode (waiting for slot number)
 fn handle_bookmark_jump_mode(key: KeyEvent) -> KeyAction {
     match key.code {
@@ -924,6 +1399,113 @@ fn handle_ai_history_mode(key: KeyEvent) -> KeyAction {
     }
 }
 
+/// Handle keys in type-to-narrow mode (text input handled separately)
+fn handle_live_filter_mode(key: KeyEvent) -> KeyAction {
+    match key.code {
+        KeyCode::Enter => KeyAction::LockLiveFilter,
+        KeyCode::Esc => KeyAction::Cancel,
+        KeyCode::Up => KeyAction::MoveUp,
+        KeyCode::Down => KeyAction::MoveDown,
+        _ => KeyAction::None,
+    }
+}
+
+/// Handle keys in recent files popup mode
+fn handle_recent_files_mode(key: KeyEvent) -> KeyAction {
+    match key.code {
+        KeyCode::Esc => KeyAction::Cancel,
+        KeyCode::Up | KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            KeyAction::RecentFilesUp
+        }
+        KeyCode::Down | KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            KeyAction::RecentFilesDown
+        }
+        KeyCode::Up => KeyAction::RecentFilesUp,
+        KeyCode::Down => KeyAction::RecentFilesDown,
+        KeyCode::Enter => KeyAction::RecentFilesSelect,
+        _ => KeyAction::None, // Query input handled separately
+    }
+}
+
+/// Handle keys in operation history popup mode
+fn handle_operation_history_mode(key: KeyEvent) -> KeyAction {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('U') => KeyAction::Cancel,
+        KeyCode::Up | KeyCode::Char('k') => KeyAction::OperationHistoryUp,
+        KeyCode::Down | KeyCode::Char('j') => KeyAction::OperationHistoryDown,
+        KeyCode::Enter => KeyAction::OperationHistoryReveal,
+        _ => KeyAction::None,
+    }
+}
+
+/// Handle keys in the command output popup
+fn handle_command_output_mode(key: KeyEvent) -> KeyAction {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => KeyAction::Cancel,
+        KeyCode::Up | KeyCode::Char('k') => KeyAction::CommandOutputUp,
+        KeyCode::Down | KeyCode::Char('j') => KeyAction::CommandOutputDown,
+        _ => KeyAction::None,
+    }
+}
+
+/// Handle keys in marks overview mode
+fn handle_marks_overview_mode(key: KeyEvent) -> KeyAction {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('M') => KeyAction::Cancel,
+        KeyCode::Up | KeyCode::Char('k') => KeyAction::MarksUp,
+        KeyCode::Down | KeyCode::Char('j') => KeyAction::MarksDown,
+        KeyCode::Enter => KeyAction::MarksJump,
+        KeyCode::Char(' ') | KeyCode::Char('d') | KeyCode::Delete => KeyAction::MarksUnmark,
+        _ => KeyAction::None,
+    }
+}
+
+/// Handle keys in content search results mode
+fn handle_content_search_mode(key: KeyEvent) -> KeyAction {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => KeyAction::Cancel,
+        KeyCode::Up | KeyCode::Char('k') => KeyAction::ContentSearchUp,
+        KeyCode::Down | KeyCode::Char('j') => KeyAction::ContentSearchDown,
+        KeyCode::Enter => KeyAction::ContentSearchSelect,
+        KeyCode::Char('/') => KeyAction::StartContentSearch,
+        KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            KeyAction::StartContentSearch
+        }
+        _ => KeyAction::None,
+    }
+}
+
+/// Handle keys in git stash popup mode
+fn handle_git_stash_mode(key: KeyEvent) -> KeyAction {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Z') => KeyAction::Cancel,
+        KeyCode::Up | KeyCode::Char('k') => KeyAction::GitStashUp,
+        KeyCode::Down | KeyCode::Char('j') => KeyAction::GitStashDown,
+        KeyCode::Enter | KeyCode::Char('a') => KeyAction::GitStashApply,
+        KeyCode::Char('p') => KeyAction::GitStashPop,
+        KeyCode::Char('d') => KeyAction::GitStashDrop,
+        _ => KeyAction::None,
+    }
+}
+
+/// Handle keys in the drop stash confirmation
+fn handle_stash_drop_confirm_mode(key: KeyEvent) -> KeyAction {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => KeyAction::GitStashDropConfirm,
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => KeyAction::OpenGitStash,
+        _ => KeyAction::None,
+    }
+}
+
+/// Handle keys in the discard changes confirmation
+fn handle_git_discard_confirm_mode(key: KeyEvent) -> KeyAction {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => KeyAction::GitDiscardConfirm,
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => KeyAction::Cancel,
+        _ => KeyAction::None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -951,6 +1533,93 @@ mod tests {
         assert!(matches!(action, KeyAction::Cancel));
     }
 
+    #[test]
+    fn test_git_stash_mode_keys() {
+        assert!(matches!(
+            handle_git_stash_mode(key_event(KeyCode::Enter)),
+            KeyAction::GitStashApply
+        ));
+        assert!(matches!(
+            handle_git_stash_mode(key_event(KeyCode::Char('p'))),
+            KeyAction::GitStashPop
+        ));
+        assert!(matches!(
+            handle_git_stash_mode(key_event(KeyCode::Char('d'))),
+            KeyAction::GitStashDrop
+        ));
+        // Declining a drop returns to the stash list
+        assert!(matches!(
+            handle_stash_drop_confirm_mode(key_event(KeyCode::Char('n'))),
+            KeyAction::OpenGitStash
+        ));
+        assert!(matches!(
+            handle_stash_drop_confirm_mode(key_event(KeyCode::Char('y'))),
+            KeyAction::GitStashDropConfirm
+        ));
+    }
+
+    #[test]
+    fn test_browse_digits_build_a_count() {
+        let mut state = AppState::new(std::path::PathBuf::from("/tmp"));
+        assert!(matches!(
+            handle_browse_mode(&state, key_event(KeyCode::Char('5'))),
+            KeyAction::CountDigit { digit: 5 }
+        ));
+        // `0` only continues a count
+        assert!(!matches!(
+            handle_browse_mode(&state, key_event(KeyCode::Char('0'))),
+            KeyAction::CountDigit { .. }
+        ));
+        state.count_prefix = Some(1);
+        assert!(matches!(
+            handle_browse_mode(&state, key_event(KeyCode::Char('0'))),
+            KeyAction::CountDigit { digit: 0 }
+        ));
+        // Ctrl+digit keeps selecting by extension
+        assert!(matches!(
+            handle_browse_mode(
+                &state,
+                key_event_with_modifiers(KeyCode::Char('1'), KeyModifiers::CONTROL)
+            ),
+            KeyAction::SelectByExtension { index: 1 }
+        ));
+        // The preview does not take counts
+        state.focus_target = FocusTarget::Preview;
+        assert!(!matches!(
+            handle_browse_mode(&state, key_event(KeyCode::Char('5'))),
+            KeyAction::CountDigit { .. }
+        ));
+    }
+
+    #[test]
+    fn test_git_discard_confirm_keys() {
+        assert!(matches!(
+            handle_git_discard_confirm_mode(key_event(KeyCode::Enter)),
+            KeyAction::GitDiscardConfirm
+        ));
+        assert!(matches!(
+            handle_git_discard_confirm_mode(key_event(KeyCode::Esc)),
+            KeyAction::Cancel
+        ));
+        assert!(matches!(
+            handle_git_discard_confirm_mode(key_event(KeyCode::Char('x'))),
+            KeyAction::None
+        ));
+    }
+
+    #[test]
+    fn test_alt_c_toggles_case_in_search_and_fuzzy() {
+        let alt_c = key_event_with_modifiers(KeyCode::Char('c'), KeyModifiers::ALT);
+        assert!(matches!(
+            handle_search_mode(alt_c, "query"),
+            KeyAction::ToggleCaseSensitive
+        ));
+        assert!(matches!(
+            handle_fuzzy_finder_mode(alt_c),
+            KeyAction::ToggleCaseSensitive
+        ));
+    }
+
     #[test]
     fn test_bookmark_set_mode_m_cancels() {
         let action = handle_bookmark_set_mode(key_event(KeyCode::Char('m')));
@@ -987,6 +1656,36 @@ mod tests {
         assert!(matches!(action, KeyAction::JumpToBookmark { slot: 3 }));
     }
 
+    #[test]
+    fn test_sort_select_mode_keys_set_mode() {
+        for (c, expected) in [
+            ('n', SortMode::Name),
+            ('s', SortMode::Size),
+            ('d', SortMode::Date),
+            ('e', SortMode::Extension),
+            ('t', SortMode::Type),
+            ('v', SortMode::Natural),
+        ] {
+            let action = handle_sort_select_mode(key_event(KeyCode::Char(c)));
+            assert!(
+                matches!(action, KeyAction::SetSort { mode } if mode == expected),
+                "key {c}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sort_select_mode_reverse_and_cancel() {
+        let action = handle_sort_select_mode(key_event(KeyCode::Char('r')));
+        assert!(matches!(action, KeyAction::ToggleSortReverse));
+        let action = handle_sort_select_mode(key_event(KeyCode::Char('S')));
+        assert!(matches!(action, KeyAction::CycleSort));
+        let action = handle_sort_select_mode(key_event(KeyCode::Esc));
+        assert!(matches!(action, KeyAction::Cancel));
+        let action = handle_sort_select_mode(key_event(KeyCode::Char('x')));
+        assert!(matches!(action, KeyAction::None));
+    }
+
     #[test]
     fn test_filter_mode_f_cancels() {
         let action = handle_filter_mode(key_event(KeyCode::Char('F')), "*.rs");
@@ -1032,6 +1731,16 @@ mod tests {
         assert!(matches!(action, KeyAction::Cancel));
     }
 
+    #[test]
+    fn test_preview_mode_text_display_toggles() {
+        let action = handle_preview_mode(key_event(KeyCode::Char('w')));
+        assert!(matches!(action, KeyAction::TogglePreviewWrap));
+        let action = handle_preview_mode(key_event(KeyCode::Char('#')));
+        assert!(matches!(action, KeyAction::TogglePreviewLineNumbers));
+        let action = handle_preview_mode(key_event(KeyCode::Char('t')));
+        assert!(matches!(action, KeyAction::TogglePdfText));
+    }
+
     // Tests for arrow key focus switching when preview is visible
 
     fn test_state() -> AppState {
@@ -1068,6 +1777,21 @@ mod tests {
         assert!(matches!(action, KeyAction::Collapse));
     }
 
+    #[test]
+    fn test_ctrl_arrows_resize_preview() {
+        let mut state = test_state();
+        state.preview_visible = true;
+        let ctrl = |code| key_event_with_modifiers(code, KeyModifiers::CONTROL);
+        assert!(matches!(
+            handle_browse_mode(&state, ctrl(KeyCode::Right)),
+            KeyAction::ResizePreview { grow: true }
+        ));
+        assert!(matches!(
+            handle_browse_mode(&state, ctrl(KeyCode::Left)),
+            KeyAction::ResizePreview { grow: false }
+        ));
+    }
+
     #[test]
     fn test_l_always_expands_regardless_of_preview() {
         let mut state = test_state();
@@ -1083,4 +1807,58 @@ mod tests {
         let action = handle_browse_mode(&state, key_event(KeyCode::Char('h')));
         assert!(matches!(action, KeyAction::Collapse));
     }
+
+    #[test]
+    fn test_preview_focus_keeps_modified_keys() {
+        let mut state = test_state();
+        state.preview_visible = true;
+        state.focus_target = FocusTarget::Preview;
+        let ctrl = |c| key_event_with_modifiers(KeyCode::Char(c), KeyModifiers::CONTROL);
+        assert!(matches!(
+            handle_browse_mode(&state, key_event(KeyCode::Char('w'))),
+            KeyAction::TogglePreviewWrap
+        ));
+        assert!(matches!(
+            handle_browse_mode(&state, ctrl('w')),
+            KeyAction::CloseTab
+        ));
+        assert!(matches!(
+            handle_browse_mode(&state, ctrl('f')),
+            KeyAction::StartContentSearch
+        ));
+        assert!(matches!(
+            handle_browse_mode(
+                &state,
+                key_event_with_modifiers(KeyCode::Char('b'), KeyModifiers::ALT)
+            ),
+            KeyAction::TogglePreviewBorders
+        ));
+        assert!(matches!(
+            handle_browse_mode(&state, ctrl('t')),
+            KeyAction::NewTab
+        ));
+        assert!(matches!(
+            handle_browse_mode(
+                &state,
+                key_event_with_modifiers(KeyCode::Char('t'), KeyModifiers::ALT)
+            ),
+            KeyAction::NextTab
+        ));
+    }
+
+    #[test]
+    fn test_registry_x_discards_in_tree_and_steps_conflicts_in_preview() {
+        let registry = KeyBindingRegistry::new();
+        let mut state = test_state();
+        let shift_x = key_event_with_modifiers(KeyCode::Char('X'), KeyModifiers::SHIFT);
+        assert!(matches!(
+            handle_key_event_with_registry(&state, shift_x, &registry),
+            KeyAction::GitDiscard
+        ));
+        state.focus_target = FocusTarget::Preview;
+        assert!(matches!(
+            handle_key_event_with_registry(&state, shift_x, &registry),
+            KeyAction::PreviewPrevConflict
+        ));
+    }
 }
@@ -6,7 +6,7 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::key::KeyAction;
 use crate::app::ConfigFile;
@@ -50,6 +50,12 @@ impl KeymapFile {
             .and_then(|content| toml::from_str(&content).ok())
             .unwrap_or_default()
     }
+
+    /// Load keymap from a specific path, reporting parse errors
+    pub fn load_from(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(toml::from_str(&content)?)
+    }
 }
 
 /// Key binding registry for dynamic key dispatch
@@ -94,39 +100,57 @@ impl KeyBindingRegistry {
 
     /// Load registry from keymap file, merging with defaults
     pub fn from_file() -> Self {
-        let mut registry = Self::new();
-        let keymap = KeymapFile::load();
+        Self::from_keymap(KeymapFile::load())
+    }
 
-        // Merge user bindings (override defaults)
-        for (key, action) in keymap.browse {
-            registry.browse.insert(key, action);
-        }
-        for (key, action) in keymap.preview {
-            registry.preview.insert(key, action);
-        }
-        for (key, action) in keymap.search {
-            registry.search.insert(key, action);
-        }
-        for (key, action) in keymap.confirm {
-            registry.confirm.insert(key, action);
-        }
-        for (key, action) in keymap.fuzzy {
-            registry.fuzzy.insert(key, action);
-        }
-        for (key, action) in keymap.help {
-            registry.help.insert(key, action);
-        }
-        for (key, action) in keymap.filter {
-            registry.filter.insert(key, action);
+    /// Build a registry from a parsed keymap, merging with defaults
+    pub fn from_keymap(keymap: KeymapFile) -> Self {
+        Self::merged(keymap).0
+    }
+
+    /// Re-read a keymap file and swap in its bindings
+    ///
+    /// On a parse error the current bindings stay in effect. Returns the
+    /// conflicts found: spellings of one key bound to different actions.
+    pub fn reload_from_file(&mut self, path: &Path) -> anyhow::Result<Vec<String>> {
+        let (registry, conflicts) = Self::merged(KeymapFile::load_from(path)?);
+        *self = registry;
+        Ok(conflicts)
+    }
+
+    /// Merge user bindings over the defaults, collecting conflicts
+    fn merged(keymap: KeymapFile) -> (Self, Vec<String>) {
+        let mut registry = Self::new();
+        let mut conflicts = Vec::new();
+
+        // User bindings override defaults
+        for (context, user, bindings) in [
+            ("browse", keymap.browse, &mut registry.browse),
+            ("preview", keymap.preview, &mut registry.preview),
+            ("search", keymap.search, &mut registry.search),
+            ("confirm", keymap.confirm, &mut registry.confirm),
+            ("fuzzy", keymap.fuzzy, &mut registry.fuzzy),
+            ("help", keymap.help, &mut registry.help),
+            ("filter", keymap.filter, &mut registry.filter),
+        ] {
+            merge_bindings(context, user, bindings, &mut conflicts);
         }
 
-        registry
+        (registry, conflicts)
     }
 
     /// Load default key bindings
-    fn load_defaults(&mut self) {
+    ///
+    /// Returns the conflicts among the defaults: one key bound twice.
+    fn load_defaults(&mut self) -> Vec<String> {
+        let mut conflicts = Vec::new();
+
         // Browse mode defaults
-        let browse = &mut self.browse;
+        let mut browse = Defaults {
+            context: "browse",
+            bindings: &mut self.browse,
+            conflicts: &mut conflicts,
+        };
         browse.insert("q".to_string(), "quit".to_string());
         browse.insert("Q".to_string(), "quit_and_cd".to_string());
         browse.insert("esc".to_string(), "cancel_or_clear".to_string());
@@ -144,51 +168,85 @@ impl KeyBindingRegistry {
         browse.insert("tab".to_string(), "toggle_focus_or_expand".to_string());
         browse.insert("H".to_string(), "collapse_all".to_string());
         browse.insert("L".to_string(), "expand_all".to_string());
+        browse.insert(">".to_string(), "set_root".to_string());
+        browse.insert("<".to_string(), "root_up".to_string());
         browse.insert("space".to_string(), "toggle_mark".to_string());
+        browse.insert("M".to_string(), "marks_overview".to_string());
         browse.insert("enter".to_string(), "pick_or_toggle".to_string());
         browse.insert("y".to_string(), "copy".to_string());
         browse.insert("d".to_string(), "cut".to_string());
         browse.insert("D".to_string(), "confirm_delete".to_string());
         browse.insert("delete".to_string(), "confirm_delete".to_string());
+        browse.insert("shift+delete".to_string(), "delete_permanently".to_string());
         browse.insert("ctrl+p".to_string(), "open_fuzzy_finder".to_string());
         browse.insert("p".to_string(), "paste".to_string());
+        browse.insert("alt+P".to_string(), "paste_copy".to_string());
         browse.insert("r".to_string(), "start_rename".to_string());
         browse.insert("a".to_string(), "start_new_file".to_string());
         browse.insert("A".to_string(), "start_new_dir".to_string());
+        browse.insert("E".to_string(), "editor_rename".to_string());
+        browse.insert("O".to_string(), "open_recent_files".to_string());
+        browse.insert("U".to_string(), "operation_history".to_string());
+        browse.insert("ctrl+z".to_string(), "undo".to_string());
+        browse.insert("alt+k".to_string(), "reload_keymap".to_string());
+        browse.insert("!".to_string(), "foreach_command".to_string());
         browse.insert("/".to_string(), "start_search".to_string());
+        browse.insert("ctrl+f".to_string(), "content_search".to_string());
         browse.insert("n".to_string(), "search_next".to_string());
         browse.insert("N".to_string(), "search_prev".to_string());
-        browse.insert("S".to_string(), "cycle_sort".to_string());
+        browse.insert("S".to_string(), "start_sort_select".to_string());
         browse.insert("R".to_string(), "refresh_or_bulk_rename".to_string());
         browse.insert("f5".to_string(), "refresh".to_string());
         browse.insert(".".to_string(), "toggle_hidden".to_string());
+        browse.insert("alt+.".to_string(), "toggle_hidden_here".to_string());
         browse.insert("c".to_string(), "copy_path".to_string());
+        browse.insert("alt+c".to_string(), "copy_relative_path".to_string());
         browse.insert("C".to_string(), "copy_filename".to_string());
         browse.insert("Y".to_string(), "copy_content".to_string());
         browse.insert("ctrl+y".to_string(), "copy_for_claude".to_string());
         browse.insert("o".to_string(), "open_preview".to_string());
         browse.insert("P".to_string(), "toggle_quick_preview".to_string());
+        browse.insert("alt+=".to_string(), "preview_grow".to_string());
+        browse.insert("alt+-".to_string(), "preview_shrink".to_string());
+        browse.insert("alt+b".to_string(), "toggle_preview_borders".to_string());
+        browse.insert("alt+n".to_string(), "toggle_relative_numbers".to_string());
         browse.insert("?".to_string(), "show_help".to_string());
         browse.insert("[".to_string(), "pdf_prev_page".to_string());
         browse.insert("]".to_string(), "pdf_next_page".to_string());
         browse.insert("m".to_string(), "start_bookmark_set".to_string());
         browse.insert("'".to_string(), "start_bookmark_jump".to_string());
         browse.insert("F".to_string(), "toggle_filter".to_string());
+        browse.insert("i".to_string(), "live_filter".to_string());
         browse.insert("s".to_string(), "git_stage".to_string());
+        browse.insert("Z".to_string(), "git_stash".to_string());
+        browse.insert("alt+G".to_string(), "toggle_git_changed_only".to_string());
+        browse.insert("alt+d".to_string(), "git_diff_revision".to_string());
+        browse.insert("alt+D".to_string(), "diff_marked".to_string());
+        browse.insert("alt+e".to_string(), "yank_git".to_string());
+        browse.insert("alt+m".to_string(), "git_commit".to_string());
+        browse.insert("alt+M".to_string(), "git_commit_amend".to_string());
         browse.insert("u".to_string(), "git_unstage".to_string());
+        browse.insert("X".to_string(), "git_discard".to_string());
         browse.insert("ctrl+t".to_string(), "new_tab".to_string());
         browse.insert("ctrl+w".to_string(), "close_tab".to_string());
         browse.insert("alt+t".to_string(), "next_tab".to_string());
         browse.insert("alt+T".to_string(), "prev_tab".to_string());
+        browse.insert("alt+l".to_string(), "create_symlink".to_string());
+        browse.insert("alt+L".to_string(), "create_hardlink".to_string());
         browse.insert("pageup".to_string(), "preview_page_up".to_string());
         browse.insert("pagedown".to_string(), "preview_page_down".to_string());
         browse.insert("b".to_string(), "preview_page_up_if_preview".to_string());
         browse.insert("f".to_string(), "preview_page_down_if_preview".to_string());
+        browse.insert("x".to_string(), "preview_next_conflict".to_string());
         browse.insert("ctrl+g".to_string(), "select_git_changed".to_string());
         browse.insert("ctrl+T".to_string(), "select_test_pair".to_string());
 
         // Preview mode defaults
-        let preview = &mut self.preview;
+        let mut preview = Defaults {
+            context: "preview",
+            bindings: &mut self.preview,
+            conflicts: &mut conflicts,
+        };
         preview.insert("esc".to_string(), "cancel".to_string());
         preview.insert("q".to_string(), "cancel".to_string());
         preview.insert("o".to_string(), "cancel".to_string());
@@ -204,17 +262,40 @@ impl KeyBindingRegistry {
         preview.insert("space".to_string(), "page_down".to_string());
         preview.insert("g".to_string(), "to_top".to_string());
         preview.insert("G".to_string(), "to_bottom".to_string());
+        preview.insert("left".to_string(), "scroll_left".to_string());
+        preview.insert("h".to_string(), "scroll_left".to_string());
+        preview.insert("right".to_string(), "scroll_right".to_string());
+        preview.insert("l".to_string(), "scroll_right".to_string());
         preview.insert("[".to_string(), "pdf_prev_page".to_string());
         preview.insert("]".to_string(), "pdf_next_page".to_string());
+        preview.insert("t".to_string(), "pdf_toggle_text".to_string());
+        preview.insert("w".to_string(), "toggle_wrap".to_string());
+        preview.insert("#".to_string(), "toggle_line_numbers".to_string());
+        preview.insert("alt+b".to_string(), "toggle_preview_borders".to_string());
+        preview.insert("/".to_string(), "search".to_string());
+        preview.insert("n".to_string(), "search_next".to_string());
+        preview.insert("N".to_string(), "search_prev".to_string());
+        preview.insert("B".to_string(), "toggle_blame".to_string());
+        preview.insert("x".to_string(), "next_conflict".to_string());
+        preview.insert("X".to_string(), "prev_conflict".to_string());
 
         // Search mode defaults
-        let search = &mut self.search;
+        let mut search = Defaults {
+            context: "search",
+            bindings: &mut self.search,
+            conflicts: &mut conflicts,
+        };
         search.insert("enter".to_string(), "confirm".to_string());
         search.insert("/".to_string(), "cancel".to_string());
         search.insert("esc".to_string(), "cancel".to_string());
+        search.insert("alt+c".to_string(), "toggle_case".to_string());
 
         // Confirm mode defaults
-        let confirm = &mut self.confirm;
+        let mut confirm = Defaults {
+            context: "confirm",
+            bindings: &mut self.confirm,
+            conflicts: &mut conflicts,
+        };
         confirm.insert("y".to_string(), "execute".to_string());
         confirm.insert("Y".to_string(), "execute".to_string());
         confirm.insert("enter".to_string(), "execute".to_string());
@@ -223,7 +304,11 @@ impl KeyBindingRegistry {
         confirm.insert("esc".to_string(), "cancel".to_string());
 
         // Fuzzy finder defaults
-        let fuzzy = &mut self.fuzzy;
+        let mut fuzzy = Defaults {
+            context: "fuzzy",
+            bindings: &mut self.fuzzy,
+            conflicts: &mut conflicts,
+        };
         fuzzy.insert("esc".to_string(), "cancel".to_string());
         fuzzy.insert("ctrl+p".to_string(), "cancel".to_string());
         fuzzy.insert("up".to_string(), "up".to_string());
@@ -231,19 +316,30 @@ impl KeyBindingRegistry {
         fuzzy.insert("down".to_string(), "down".to_string());
         fuzzy.insert("ctrl+j".to_string(), "down".to_string());
         fuzzy.insert("enter".to_string(), "confirm".to_string());
+        fuzzy.insert("alt+c".to_string(), "toggle_case".to_string());
 
         // Help mode defaults
-        let help = &mut self.help;
+        let mut help = Defaults {
+            context: "help",
+            bindings: &mut self.help,
+            conflicts: &mut conflicts,
+        };
         help.insert("esc".to_string(), "cancel".to_string());
         help.insert("enter".to_string(), "cancel".to_string());
         help.insert("q".to_string(), "cancel".to_string());
         help.insert("?".to_string(), "cancel".to_string());
 
         // Filter mode defaults
-        let filter = &mut self.filter;
+        let mut filter = Defaults {
+            context: "filter",
+            bindings: &mut self.filter,
+            conflicts: &mut conflicts,
+        };
         filter.insert("enter".to_string(), "apply".to_string());
         filter.insert("F".to_string(), "cancel".to_string());
         filter.insert("esc".to_string(), "cancel".to_string());
+
+        conflicts
     }
 
     /// Look up action for a key event in browse mode
@@ -272,6 +368,7 @@ impl KeyBindingRegistry {
                     value: String::new(),
                 }),
                 "cancel" => Some(KeyAction::Cancel),
+                "toggle_case" => Some(KeyAction::ToggleCaseSensitive),
                 _ => None,
             })
     }
@@ -300,6 +397,7 @@ impl KeyBindingRegistry {
                 "confirm" => Some(KeyAction::FuzzyConfirm {
                     path: PathBuf::new(),
                 }),
+                "toggle_case" => Some(KeyAction::ToggleCaseSensitive),
                 _ => None,
             })
     }
@@ -330,6 +428,106 @@ impl KeyBindingRegistry {
     }
 }
 
+/// Default bindings of one context, recording keys bound twice
+struct Defaults<'a> {
+    context: &'static str,
+    bindings: &'a mut HashMap<String, String>,
+    conflicts: &'a mut Vec<String>,
+}
+
+impl Defaults<'_> {
+    fn insert(&mut self, key: String, action: String) {
+        let normalized = normalize_key_string(&key);
+        if let Some(previous) = self.bindings.get(&normalized) {
+            if *previous != action {
+                self.conflicts.push(format!(
+                    "{}: '{}' bound to both {} and {}",
+                    self.context, key, previous, action
+                ));
+            }
+        }
+        self.bindings.insert(normalized, action);
+    }
+}
+
+/// Merge one context's user bindings, keyed by their normalized spelling
+///
+/// Bindings are visited in key order so conflicts are reported stably.
+fn merge_bindings(
+    context: &str,
+    user: HashMap<String, String>,
+    bindings: &mut HashMap<String, String>,
+    conflicts: &mut Vec<String>,
+) {
+    let mut user: Vec<(String, String)> = user.into_iter().collect();
+    user.sort();
+    let mut seen: HashMap<String, (String, String)> = HashMap::new();
+    for (key, action) in user {
+        let normalized = normalize_key_string(&key);
+        if let Some((first_key, first_action)) = seen.get(&normalized) {
+            if *first_action != action {
+                conflicts.push(format!(
+                    "{}: '{}' ({}) vs '{}' ({})",
+                    context, first_key, first_action, key, action
+                ));
+            }
+            continue;
+        }
+        seen.insert(normalized.clone(), (key, action.clone()));
+        bindings.insert(normalized, action);
+    }
+}
+
+/// Spell a keymap key the way [`key_event_to_string`] produces it
+///
+/// Modifiers are lowercased and ordered `ctrl`, `alt`, `shift`, and
+/// `shift+x` becomes `X`, so `"Shift+d"` and `"D"` name the same key.
+/// Keys with an unknown modifier are kept as written.
+fn normalize_key_string(key: &str) -> String {
+    let (modifiers, name) = if let Some(modifiers) = key.strip_suffix("++") {
+        (modifiers, "+")
+    } else {
+        match key.rsplit_once('+') {
+            Some((modifiers, name)) if !name.is_empty() => (modifiers, name),
+            _ => ("", key),
+        }
+    };
+
+    let (mut ctrl, mut alt, mut shift) = (false, false, false);
+    for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+        match modifier.to_lowercase().as_str() {
+            "ctrl" => ctrl = true,
+            "alt" => alt = true,
+            "shift" => shift = true,
+            _ => return key.to_string(),
+        }
+    }
+
+    let mut chars = name.chars();
+    let single = chars.next().filter(|_| chars.next().is_none());
+    let name = match single {
+        Some(c) if shift && !ctrl && !alt => {
+            shift = false;
+            c.to_uppercase().to_string()
+        }
+        Some(c) => c.to_string(),
+        None => name.to_lowercase(),
+    };
+
+    let mut parts = Vec::new();
+    if ctrl {
+        parts.push("ctrl");
+    }
+    if alt {
+        parts.push("alt");
+    }
+    if shift {
+        parts.push("shift");
+    }
+    parts.push(&name);
+    parts.join("+")
+}
+
 /// Convert a KeyEvent to a string representation
 fn key_event_to_string(key: &KeyEvent) -> String {
     let mut parts = Vec::new();
@@ -390,29 +588,41 @@ fn parse_browse_action(action: &str) -> Option<KeyAction> {
         "toggle_expand" | "toggle_focus_or_expand" => Some(KeyAction::ToggleExpand),
         "collapse_all" => Some(KeyAction::CollapseAll),
         "expand_all" => Some(KeyAction::ExpandAll),
+        "set_root" => Some(KeyAction::SetRoot),
+        "root_up" => Some(KeyAction::RootUp),
         "toggle_mark" => Some(KeyAction::ToggleMark),
         "clear_marks" => Some(KeyAction::ClearMarks),
+        "marks_overview" => Some(KeyAction::OpenMarksOverview),
         "copy" => Some(KeyAction::Copy),
         "cut" => Some(KeyAction::Cut),
         "paste" => Some(KeyAction::Paste),
+        "paste_copy" => Some(KeyAction::PasteCopy),
         "confirm_delete" => Some(KeyAction::ConfirmDelete),
+        "delete_permanently" => Some(KeyAction::ConfirmDeletePermanent),
         "start_rename" => Some(KeyAction::StartRename),
         "start_new_file" => Some(KeyAction::StartNewFile),
         "start_new_dir" => Some(KeyAction::StartNewDir),
         "start_search" => Some(KeyAction::StartSearch),
+        "content_search" => Some(KeyAction::StartContentSearch),
+        "foreach_command" => Some(KeyAction::StartForeachCommand),
         "search_next" => Some(KeyAction::SearchNext),
         "search_prev" => Some(KeyAction::SearchPrev),
         "refresh" | "refresh_or_bulk_rename" => Some(KeyAction::Refresh),
         "toggle_hidden" => Some(KeyAction::ToggleHidden),
+        "toggle_hidden_here" => Some(KeyAction::ToggleHiddenHere),
         "copy_path" => Some(KeyAction::CopyPath),
+        "copy_relative_path" => Some(KeyAction::CopyRelativePath),
         "copy_filename" => Some(KeyAction::CopyFilename),
         "copy_content" => Some(KeyAction::CopyContent),
         "copy_for_claude" => Some(KeyAction::CopyForClaude),
         "copy_context_pack" => Some(KeyAction::CopyContextPack),
         "copy_context_pack_review" => Some(KeyAction::CopyContextPackReview),
         "copy_compact" => Some(KeyAction::CopyCompact),
+        "copy_concat" => Some(KeyAction::CopyConcat),
         "open_preview" => Some(KeyAction::OpenPreview),
         "toggle_quick_preview" => Some(KeyAction::ToggleQuickPreview),
+        "preview_grow" => Some(KeyAction::ResizePreview { grow: true }),
+        "preview_shrink" => Some(KeyAction::ResizePreview { grow: false }),
         "toggle_ai_focus" => Some(KeyAction::ToggleAiFocus),
         "open_ai_history" => Some(KeyAction::OpenAiHistory),
         "show_help" => Some(KeyAction::ShowHelp),
@@ -424,12 +634,34 @@ fn parse_browse_action(action: &str) -> Option<KeyAction> {
         "start_bookmark_jump" => Some(KeyAction::StartBookmarkJump),
         "start_filter" | "toggle_filter" => Some(KeyAction::StartFilter),
         "clear_filter" => Some(KeyAction::ClearFilter),
+        "live_filter" => Some(KeyAction::StartLiveFilter),
         "cycle_sort" => Some(KeyAction::CycleSort),
+        "create_symlink" => Some(KeyAction::CreateSymlink),
+        "create_hardlink" => Some(KeyAction::CreateHardlink),
+        "start_sort_select" => Some(KeyAction::StartSortSelect),
+        "toggle_sort_reverse" => Some(KeyAction::ToggleSortReverse),
+        "toggle_dirs_first" => Some(KeyAction::ToggleDirsFirst),
+        "toggle_preview_borders" => Some(KeyAction::TogglePreviewBorders),
+        "toggle_relative_numbers" => Some(KeyAction::ToggleRelativeNumbers),
         "pdf_prev_page" => Some(KeyAction::PdfPrevPage),
         "pdf_next_page" => Some(KeyAction::PdfNextPage),
+        "pdf_toggle_text" => Some(KeyAction::TogglePdfText),
         "git_stage" => Some(KeyAction::GitStage),
         "git_unstage" => Some(KeyAction::GitUnstage),
+        "git_discard" => Some(KeyAction::GitDiscard),
+        "git_stash" => Some(KeyAction::OpenGitStash),
+        "toggle_git_changed_only" => Some(KeyAction::ToggleGitChangedOnly),
+        "git_diff_revision" => Some(KeyAction::StartDiffRevision),
+        "diff_marked" => Some(KeyAction::DiffMarked),
+        "yank_git" => Some(KeyAction::StartYankGit),
+        "git_commit" => Some(KeyAction::StartGitCommit { amend: false }),
+        "git_commit_amend" => Some(KeyAction::StartGitCommit { amend: true }),
         "start_bulk_rename" => Some(KeyAction::StartBulkRename),
+        "editor_rename" => Some(KeyAction::EditorRename),
+        "open_recent_files" => Some(KeyAction::OpenRecentFiles),
+        "operation_history" => Some(KeyAction::OpenOperationHistory),
+        "undo" => Some(KeyAction::Undo),
+        "reload_keymap" => Some(KeyAction::ReloadKeymap),
         "new_tab" => Some(KeyAction::NewTab),
         "close_tab" => Some(KeyAction::CloseTab),
         "next_tab" => Some(KeyAction::NextTab),
@@ -441,13 +673,20 @@ fn parse_browse_action(action: &str) -> Option<KeyAction> {
         "preview_scroll_down" => Some(KeyAction::PreviewScrollDown),
         "preview_page_up" | "preview_page_up_if_preview" => Some(KeyAction::PreviewPageUp),
         "preview_page_down" | "preview_page_down_if_preview" => Some(KeyAction::PreviewPageDown),
+        "preview_next_conflict" => Some(KeyAction::PreviewNextConflict),
+        "preview_prev_conflict" => Some(KeyAction::PreviewPrevConflict),
         "preview_to_top" => Some(KeyAction::PreviewToTop),
         "preview_to_bottom" => Some(KeyAction::PreviewToBottom),
+        "preview_scroll_left" => Some(KeyAction::PreviewScrollLeft),
+        "preview_scroll_right" => Some(KeyAction::PreviewScrollRight),
         "select_git_changed" => Some(KeyAction::SelectGitChanged),
         "select_test_pair" => Some(KeyAction::SelectTestPair),
         "select_related" => Some(KeyAction::SelectRelated),
         "select_error_context" => Some(KeyAction::SelectErrorContext),
         "select_all" => Some(KeyAction::SelectAll),
+        "select_by_pattern" => Some(KeyAction::SelectByPattern),
+        "deselect_by_pattern" => Some(KeyAction::DeselectByPattern),
+        "select_by_pattern_in_subtree" => Some(KeyAction::SelectByPatternInSubtree),
         "invert_selection" => Some(KeyAction::InvertSelection),
         _ => {
             // Check for command:name pattern
@@ -470,8 +709,20 @@ fn parse_preview_action(action: &str) -> Option<KeyAction> {
         "page_down" => Some(KeyAction::PreviewPageDown),
         "to_top" => Some(KeyAction::PreviewToTop),
         "to_bottom" => Some(KeyAction::PreviewToBottom),
+        "scroll_left" => Some(KeyAction::PreviewScrollLeft),
+        "scroll_right" => Some(KeyAction::PreviewScrollRight),
         "pdf_prev_page" => Some(KeyAction::PdfPrevPage),
         "pdf_next_page" => Some(KeyAction::PdfNextPage),
+        "pdf_toggle_text" => Some(KeyAction::TogglePdfText),
+        "toggle_wrap" => Some(KeyAction::TogglePreviewWrap),
+        "toggle_line_numbers" => Some(KeyAction::TogglePreviewLineNumbers),
+        "toggle_preview_borders" => Some(KeyAction::TogglePreviewBorders),
+        "search" => Some(KeyAction::StartPreviewSearch),
+        "search_next" => Some(KeyAction::PreviewSearchNext),
+        "search_prev" => Some(KeyAction::PreviewSearchPrev),
+        "toggle_blame" => Some(KeyAction::ToggleBlame),
+        "next_conflict" => Some(KeyAction::PreviewNextConflict),
+        "prev_conflict" => Some(KeyAction::PreviewPrevConflict),
         _ => None,
     }
 }
@@ -562,4 +813,55 @@ mod tests {
         let action = registry.lookup_browse(&key);
         assert!(matches!(action, Some(KeyAction::Quit)));
     }
+
+    #[test]
+    fn test_normalize_key_string() {
+        assert_eq!(normalize_key_string("shift+d"), "D");
+        assert_eq!(normalize_key_string("Shift+Ctrl+P"), "ctrl+shift+P");
+        assert_eq!(normalize_key_string("Alt+Enter"), "alt+enter");
+        assert_eq!(normalize_key_string("shift+Delete"), "shift+delete");
+        assert_eq!(normalize_key_string("ctrl++"), "ctrl++");
+        assert_eq!(normalize_key_string("+"), "+");
+        assert_eq!(normalize_key_string("hyper+x"), "hyper+x");
+    }
+
+    #[test]
+    fn test_defaults_bind_each_key_once() {
+        // Loading the defaults again over themselves reports any key that
+        // two defaults claim (as `X` once was for discard and conflicts)
+        let mut registry = KeyBindingRegistry::new();
+        assert_eq!(registry.load_defaults(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_reload_from_file_reports_conflicts_and_keeps_bindings_on_error() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("keymap.toml");
+        let mut registry = KeyBindingRegistry::new();
+        let shift_x = KeyEvent::new(KeyCode::Char('X'), KeyModifiers::SHIFT);
+
+        fs::write(
+            &path,
+            "[browse]\n\"X\" = \"quit\"\n\"shift+x\" = \"copy\"\n\"Alt+Q\" = \"quit\"\n",
+        )
+        .unwrap();
+        let conflicts = registry.reload_from_file(&path).unwrap();
+        assert_eq!(conflicts, vec!["browse: 'X' (quit) vs 'shift+x' (copy)"]);
+        assert!(matches!(
+            registry.lookup_browse(&shift_x),
+            Some(KeyAction::Quit)
+        ));
+        let alt_q = KeyEvent::new(KeyCode::Char('Q'), KeyModifiers::ALT);
+        assert!(matches!(
+            registry.lookup_browse(&alt_q),
+            Some(KeyAction::Quit)
+        ));
+
+        fs::write(&path, "[browse\n").unwrap();
+        assert!(registry.reload_from_file(&path).is_err());
+        assert!(matches!(
+            registry.lookup_browse(&shift_x),
+            Some(KeyAction::Quit)
+        ));
+    }
 }
@@ -16,4 +16,6 @@ pub use key::{
     KeyAction,
 };
 pub use keymap::{KeyBindingRegistry, KeymapFile};
-pub use mouse::{handle_mouse_event, ClickDetector, MouseAction, PathBuffer};
+pub use mouse::{
+    click_effect, handle_mouse_event, ClickDetector, ClickEffect, MouseAction, PathBuffer,
+};
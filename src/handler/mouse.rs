@@ -4,6 +4,8 @@ use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
+use crate::core::ClickAction;
+
 /// Timing constants for input detection
 const RAPID_INPUT_THRESHOLD_MS: u64 = 50;
 const INPUT_TIMEOUT_MS: u64 = 100;
@@ -14,11 +16,54 @@ pub enum MouseAction {
     None,
     Click { row: u16, col: u16 },
     DoubleClick { row: u16, col: u16 },
-    ScrollUp { amount: usize, col: u16 },
-    ScrollDown { amount: usize, col: u16 },
+    ScrollUp { amount: usize, col: u16, row: u16 },
+    ScrollDown { amount: usize, col: u16, row: u16 },
     FileDrop { paths: Vec<PathBuf> },
 }
 
+/// What a click does to the entry under it, after focusing it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClickEffect {
+    /// Nothing beyond focus
+    None,
+    /// Expand or collapse the directory
+    ToggleDir,
+    /// Open the file with the system opener
+    OpenFile,
+    /// Show the file in the fullscreen preview
+    PreviewFile,
+}
+
+fn effect_of(action: ClickAction, is_dir: bool) -> ClickEffect {
+    match (action, is_dir) {
+        (ClickAction::Focus, _) | (ClickAction::Expand, false) => ClickEffect::None,
+        (_, true) => ClickEffect::ToggleDir,
+        (ClickAction::Open, false) => ClickEffect::OpenFile,
+        (ClickAction::Preview, false) => ClickEffect::PreviewFile,
+    }
+}
+
+/// Effect of a click on an entry under the configured click actions
+///
+/// The first click of a double click already ran the single-click action,
+/// so a double click that would repeat it does nothing (a directory
+/// expanded by a single click is not collapsed again).
+pub fn click_effect(
+    single: ClickAction,
+    double: ClickAction,
+    is_double: bool,
+    is_dir: bool,
+) -> ClickEffect {
+    let first = effect_of(single, is_dir);
+    if !is_double {
+        return first;
+    }
+    match effect_of(double, is_dir) {
+        effect if effect == first => ClickEffect::None,
+        effect => effect,
+    }
+}
+
 /// Detects double-clicks by tracking click timing
 pub struct ClickDetector {
     last_click: Option<(Instant, u16)>,
@@ -258,10 +303,12 @@ pub fn handle_mouse_event(
         MouseEventKind::ScrollUp => MouseAction::ScrollUp {
             amount: 3,
             col: event.column,
+            row: event.row,
         },
         MouseEventKind::ScrollDown => MouseAction::ScrollDown {
             amount: 3,
             col: event.column,
+            row: event.row,
         },
         _ => MouseAction::None,
     }
@@ -271,6 +318,81 @@ pub fn handle_mouse_event(
 mod tests {
     use super::*;
 
+    // ========================================
+    // Click action tests
+    // ========================================
+
+    #[test]
+    fn click_effect_defaults_focus_then_expand() {
+        use ClickAction::*;
+        assert_eq!(click_effect(Focus, Expand, false, true), ClickEffect::None);
+        assert_eq!(
+            click_effect(Focus, Expand, true, true),
+            ClickEffect::ToggleDir
+        );
+        // Files only take focus
+        assert_eq!(click_effect(Focus, Expand, false, false), ClickEffect::None);
+        assert_eq!(click_effect(Focus, Expand, true, false), ClickEffect::None);
+    }
+
+    #[test]
+    fn click_effect_double_click_opens_or_previews_files() {
+        use ClickAction::*;
+        assert_eq!(
+            click_effect(Focus, Open, true, false),
+            ClickEffect::OpenFile
+        );
+        assert_eq!(
+            click_effect(Focus, Preview, true, false),
+            ClickEffect::PreviewFile
+        );
+        // Directories expand under open and preview
+        assert_eq!(
+            click_effect(Focus, Open, true, true),
+            ClickEffect::ToggleDir
+        );
+        assert_eq!(
+            click_effect(Focus, Preview, true, true),
+            ClickEffect::ToggleDir
+        );
+        assert_eq!(click_effect(Focus, Focus, true, true), ClickEffect::None);
+    }
+
+    #[test]
+    fn click_effect_single_click_expand_is_not_undone_by_double_click() {
+        use ClickAction::*;
+        assert_eq!(
+            click_effect(Expand, Open, false, true),
+            ClickEffect::ToggleDir
+        );
+        assert_eq!(click_effect(Expand, Open, true, true), ClickEffect::None);
+        assert_eq!(click_effect(Expand, Expand, true, true), ClickEffect::None);
+        // Files: the single click did nothing, so the double click opens
+        assert_eq!(click_effect(Expand, Open, false, false), ClickEffect::None);
+        assert_eq!(
+            click_effect(Expand, Open, true, false),
+            ClickEffect::OpenFile
+        );
+        // Single-click open: the second click does not open again
+        assert_eq!(
+            click_effect(Open, Open, false, false),
+            ClickEffect::OpenFile
+        );
+        assert_eq!(click_effect(Open, Open, true, false), ClickEffect::None);
+        assert_eq!(
+            click_effect(Open, Preview, true, false),
+            ClickEffect::PreviewFile
+        );
+    }
+
+    #[test]
+    fn click_action_from_str() {
+        use std::str::FromStr;
+        assert_eq!(ClickAction::from_str("Expand"), Ok(ClickAction::Expand));
+        assert_eq!(ClickAction::from_str("preview"), Ok(ClickAction::Preview));
+        assert!(ClickAction::from_str("launch").is_err());
+    }
+
     // ========================================
     // ClickDetector tests
     // ========================================
@@ -588,7 +710,7 @@ mod tests {
         };
         let action = handle_mouse_event(event, &mut detector, 2);
         match action {
-            MouseAction::ScrollUp { amount, col } => {
+            MouseAction::ScrollUp { amount, col, .. } => {
                 assert_eq!(amount, 3);
                 assert_eq!(col, 5);
             }
@@ -607,7 +729,7 @@ mod tests {
         };
         let action = handle_mouse_event(event, &mut detector, 2);
         match action {
-            MouseAction::ScrollDown { amount, col } => {
+            MouseAction::ScrollDown { amount, col, .. } => {
                 assert_eq!(amount, 3);
                 assert_eq!(col, 8);
             }
@@ -680,10 +802,14 @@ mod tests {
 
     #[test]
     fn mouse_action_clone() {
-        let action = MouseAction::ScrollUp { amount: 3, col: 5 };
+        let action = MouseAction::ScrollUp {
+            amount: 3,
+            col: 5,
+            row: 0,
+        };
         let cloned = action.clone();
         match cloned {
-            MouseAction::ScrollUp { amount, col } => {
+            MouseAction::ScrollUp { amount, col, .. } => {
                 assert_eq!(amount, 3);
                 assert_eq!(col, 5);
             }
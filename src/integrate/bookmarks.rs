@@ -0,0 +1,221 @@
+//! Bookmarks remembered between runs
+//!
+//! Keeps the bookmark slots (`m1`-`m9`) in `~/.config/fileview/bookmarks.toml`
+//! as slot number → absolute path.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::app::ConfigFile;
+use crate::core::BOOKMARK_SLOTS;
+
+const BOOKMARKS_FILENAME: &str = "bookmarks.toml";
+
+/// Bookmark slots bound to the file they are saved in
+#[derive(Debug, Clone, Default)]
+pub struct BookmarkStore {
+    /// Slots 0-8 for keys 1-9
+    slots: [Option<PathBuf>; BOOKMARK_SLOTS],
+    /// Saved bookmarks whose paths are missing, kept for when they return
+    /// (e.g. a drive that is mounted again)
+    unavailable: BTreeMap<usize, PathBuf>,
+    /// File the bookmarks are persisted to (None = in-memory only)
+    file: Option<PathBuf>,
+}
+
+impl BookmarkStore {
+    /// Default location in the config directory
+    pub fn default_path() -> Option<PathBuf> {
+        ConfigFile::config_dir().map(|p| p.join(BOOKMARKS_FILENAME))
+    }
+
+    /// Load the bookmarks from the config directory
+    pub fn load() -> Self {
+        match Self::default_path() {
+            Some(path) => Self::load_from(&path),
+            None => Self::default(),
+        }
+    }
+
+    /// Load the bookmarks from a file
+    ///
+    /// Bookmarks whose paths no longer exist are not set but stay in the
+    /// file. A missing or unreadable file yields empty slots bound to `path`.
+    pub fn load_from(path: &Path) -> Self {
+        let saved: BTreeMap<String, PathBuf> = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default();
+
+        let mut store = Self {
+            file: Some(path.to_path_buf()),
+            ..Self::default()
+        };
+        for (slot, target) in saved {
+            if let Ok(number) = slot.parse::<usize>() {
+                store.restore(number, target);
+            }
+        }
+        store
+    }
+
+    /// Put a saved bookmark back into slot `number` (1-9)
+    ///
+    /// A missing path is kept as unavailable; relative paths and slots out
+    /// of range are ignored. Returns whether the bookmark was taken.
+    fn restore(&mut self, number: usize, target: PathBuf) -> bool {
+        let idx = match number {
+            1..=BOOKMARK_SLOTS => number - 1,
+            _ => return false,
+        };
+        if !target.is_absolute() {
+            return false;
+        }
+        if target.exists() {
+            self.slots[idx] = Some(target);
+            self.unavailable.remove(&idx);
+        } else {
+            self.slots[idx] = None;
+            self.unavailable.insert(idx, target);
+        }
+        true
+    }
+
+    /// Every saved bookmark by slot number (1-9), unavailable ones included
+    pub fn saved(&self) -> BTreeMap<usize, PathBuf> {
+        let mut saved: BTreeMap<usize, PathBuf> = self
+            .unavailable
+            .iter()
+            .map(|(idx, target)| (idx + 1, target.clone()))
+            .collect();
+        for (idx, target) in self.slots.iter().enumerate() {
+            if let Some(target) = target {
+                saved.insert(idx + 1, target.clone());
+            }
+        }
+        saved
+    }
+
+    /// Take bookmarks by slot number (1-9), e.g. from a state export
+    ///
+    /// Slots that already hold a bookmark are kept; `replace` drops all
+    /// current bookmarks first. Returns the number of bookmarks taken.
+    pub fn import(&mut self, saved: BTreeMap<usize, PathBuf>, replace: bool) -> usize {
+        if replace {
+            self.slots = Default::default();
+            self.unavailable.clear();
+        }
+        let current = self.saved();
+        let mut taken = 0;
+        for (number, target) in saved {
+            if !current.contains_key(&number) && self.restore(number, target) {
+                taken += 1;
+            }
+        }
+        taken
+    }
+
+    /// Save the bookmarks (no-op for in-memory bookmarks)
+    pub fn save(&self) -> io::Result<()> {
+        let Some(ref path) = self.file else {
+            return Ok(());
+        };
+        let saved: BTreeMap<String, PathBuf> = self
+            .saved()
+            .into_iter()
+            .map(|(number, target)| (number.to_string(), target))
+            .collect();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content =
+            toml::to_string(&saved).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, content)
+    }
+
+    /// Get the bookmark in a slot (0-based)
+    pub fn get(&self, idx: usize) -> Option<&PathBuf> {
+        self.slots.get(idx)?.as_ref()
+    }
+
+    /// Get the saved path of an unavailable bookmark in a slot (0-based)
+    pub fn unavailable(&self, idx: usize) -> Option<&PathBuf> {
+        self.unavailable.get(&idx)
+    }
+
+    /// Set the bookmark in a slot (0-based), replacing an unavailable one
+    pub fn set(&mut self, idx: usize, path: PathBuf) {
+        if let Some(slot) = self.slots.get_mut(idx) {
+            *slot = Some(path);
+            self.unavailable.remove(&idx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_missing_paths_stay_in_file() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("nested").join(BOOKMARKS_FILENAME);
+        let gone = temp.path().join("unmounted");
+        fs::create_dir(&gone).unwrap();
+
+        let mut store = BookmarkStore::load_from(&path);
+        store.set(0, temp.path().to_path_buf());
+        store.set(4, gone.clone());
+        store.save().unwrap();
+        fs::remove_dir(&gone).unwrap();
+
+        let store = BookmarkStore::load_from(&path);
+        assert_eq!(store.get(0), Some(&temp.path().to_path_buf()));
+        assert_eq!(store.get(4), None);
+        assert_eq!(store.unavailable(4), Some(&gone));
+
+        // Saving again keeps the missing bookmark for when it returns
+        store.save().unwrap();
+        fs::create_dir(&gone).unwrap();
+        assert_eq!(BookmarkStore::load_from(&path).get(4), Some(&gone));
+
+        // Garbage and out-of-range slots are ignored
+        fs::write(
+            &path,
+            "\"0\" = \"/\"\n\"12\" = \"/\"\n\"3\" = \"relative\"\n",
+        )
+        .unwrap();
+        let store = BookmarkStore::load_from(&path);
+        assert!((0..BOOKMARK_SLOTS).all(|idx| store.get(idx).is_none()));
+    }
+
+    #[test]
+    fn test_import_fills_free_slots_unless_replace() {
+        let temp = TempDir::new().unwrap();
+        let gone = temp.path().join("gone");
+        let mut store = BookmarkStore::default();
+        store.set(0, temp.path().to_path_buf());
+
+        let saved = BTreeMap::from([
+            (1, PathBuf::from("/")),
+            (2, gone.clone()),
+            (3, PathBuf::from("relative")),
+            (12, PathBuf::from("/")),
+        ]);
+        assert_eq!(store.import(saved.clone(), false), 1);
+        assert_eq!(store.get(0), Some(&temp.path().to_path_buf()));
+        // Missing paths are kept for later, like in the bookmarks file
+        assert_eq!(store.get(1), None);
+        assert_eq!(store.saved().get(&2), Some(&gone));
+
+        assert_eq!(store.import(saved, true), 2);
+        assert_eq!(store.get(0), Some(&PathBuf::from("/")));
+        assert_eq!(
+            store.saved(),
+            BTreeMap::from([(1, PathBuf::from("/")), (2, gone)])
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! Flat dump of a directory's text files (--concat)
+//!
+//! Every text file under a directory is written out in path order behind an
+//! `=== path ===` header. Inside a git work tree the file list comes from
+//! `git ls-files`, so ignored files are skipped; elsewhere hidden entries and
+//! common build directories are skipped. Binary files are never included.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::render::is_text_file;
+
+/// Bytes sniffed for NUL when the extension is not a known text type
+const SNIFF_BYTES: usize = 8192;
+
+/// Limits for a concatenation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConcatOptions {
+    /// Maximum total bytes of file content
+    pub max_bytes: usize,
+    /// Maximum number of files included
+    pub max_files: usize,
+}
+
+impl Default for ConcatOptions {
+    fn default() -> Self {
+        Self {
+            max_bytes: 1024 * 1024,
+            max_files: 500,
+        }
+    }
+}
+
+/// Result of a concatenation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Concat {
+    /// Concatenated output
+    pub text: String,
+    /// Files included
+    pub files: usize,
+    /// Known text files left out because a limit was reached
+    pub omitted: usize,
+}
+
+/// Concatenate the text files under `dir`
+pub fn build_concat(dir: &Path, options: &ConcatOptions) -> io::Result<Concat> {
+    let candidates = list_files(dir, None);
+
+    let mut concat = Concat::default();
+    let mut used = 0usize;
+    for path in candidates {
+        // Check the limits before reading so large files are never loaded
+        let len = fs::metadata(&path).map_or(0, |meta| meta.len() as usize);
+        if concat.files >= options.max_files || used + len > options.max_bytes {
+            if is_text_file(&path) {
+                concat.omitted += 1;
+            }
+            continue;
+        }
+        let Some(content) = read_text(&path) else {
+            continue;
+        };
+        used += content.len();
+        concat.files += 1;
+
+        let rel = path.strip_prefix(dir).unwrap_or(&path);
+        concat
+            .text
+            .push_str(&format!("=== {} ===\n", rel.display()));
+        concat.text.push_str(&content);
+        if !content.ends_with('\n') {
+            concat.text.push('\n');
+        }
+        concat.text.push('\n');
+    }
+    if concat.omitted > 0 {
+        concat.text.push_str(&format!(
+            "=== {} more file(s) omitted (limit reached) ===\n",
+            concat.omitted
+        ));
+    }
+    Ok(concat)
+}
+
+/// Print the concatenation of `dir` to stdout
+pub fn output_concat(dir: &Path, options: &ConcatOptions) -> io::Result<()> {
+    let concat = build_concat(dir, options)?;
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    write!(handle, "{}", concat.text)?;
+    handle.flush()
+}
+
+/// Files under `dir` in path order, gitignore-aware inside a work tree
+///
+/// With `max_depth` only files at most that many directories below `dir`
+/// are listed (0 = files directly in `dir`), and deeper directories are not
+/// walked at all.
+pub(crate) fn list_files(dir: &Path, max_depth: Option<usize>) -> Vec<PathBuf> {
+    let mut files = git_listed_files(dir, max_depth).unwrap_or_else(|| {
+        let mut out = Vec::new();
+        walk_files(dir, max_depth, &mut out);
+        out
+    });
+    files.sort();
+    files
+}
+
+/// Tracked and untracked, non-ignored files (None outside a git work tree)
+fn git_listed_files(dir: &Path, max_depth: Option<usize>) -> Option<Vec<PathBuf>> {
+    let mut command = Command::new("git");
+    command.args([
+        "ls-files",
+        "-z",
+        "--cached",
+        "--others",
+        "--exclude-standard",
+    ]);
+    if let Some(depth) = max_depth {
+        // In glob pathspecs `*` stops at `/`, so one pattern per level
+        command.arg("--");
+        command.args((0..=depth).map(|level| format!(":(glob){}*", "*/".repeat(level))));
+    }
+    let output = command.current_dir(dir).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let listed = String::from_utf8_lossy(&output.stdout);
+    Some(
+        listed
+            .split('\0')
+            .filter(|rel| !rel.is_empty())
+            .map(|rel| dir.join(rel))
+            // Deleted but still tracked files are listed too
+            .filter(|path| path.is_file())
+            .collect(),
+    )
+}
+
+/// Collect the files under `dir`, descending at most `max_depth` directories
+fn walk_files(dir: &Path, max_depth: Option<usize>, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') {
+            continue;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            let skipped = matches!(name.as_ref(), "target" | "node_modules" | "dist" | "build");
+            if !skipped && max_depth != Some(0) {
+                walk_files(&entry.path(), max_depth.map(|depth| depth - 1), out);
+            }
+        } else if file_type.is_file() {
+            out.push(entry.path());
+        }
+    }
+}
+
+/// Read a file as text, or None if it looks binary
+pub(crate) fn read_text(path: &Path) -> Option<String> {
+    let bytes = fs::read(path).ok()?;
+    if is_text_file(path) {
+        return Some(String::from_utf8_lossy(&bytes).into_owned());
+    }
+    if bytes[..bytes.len().min(SNIFF_BYTES)].contains(&0) {
+        return None;
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_concat_headers_and_skips_binary() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir(root.join("src")).unwrap();
+        fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(root.join("README"), "hello").unwrap();
+        fs::write(root.join("logo.bin"), [0x89, b'P', 0, 0, 1, 2]).unwrap();
+
+        let concat = build_concat(root, &ConcatOptions::default()).unwrap();
+
+        assert_eq!(concat.files, 2);
+        assert_eq!(concat.omitted, 0);
+        assert_eq!(
+            concat.text,
+            "=== README ===\nhello\n\n=== src/main.rs ===\nfn main() {}\n\n"
+        );
+    }
+
+    #[test]
+    fn test_concat_respects_limits() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            fs::write(root.join(name), "0123456789").unwrap();
+        }
+
+        let by_count = ConcatOptions {
+            max_bytes: usize::MAX,
+            max_files: 2,
+        };
+        let concat = build_concat(root, &by_count).unwrap();
+        assert_eq!((concat.files, concat.omitted), (2, 1));
+        assert!(concat
+            .text
+            .ends_with("=== 1 more file(s) omitted (limit reached) ===\n"));
+
+        let by_size = ConcatOptions {
+            max_bytes: 25,
+            max_files: usize::MAX,
+        };
+        let concat = build_concat(root, &by_size).unwrap();
+        assert_eq!((concat.files, concat.omitted), (2, 1));
+    }
+
+    #[test]
+    fn test_list_files_stops_at_max_depth() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        for name in ["top.rs", "a/mid.rs", "a/b/deep.rs"] {
+            fs::write(root.join(name), "").unwrap();
+        }
+        let expected = vec![root.join("a/mid.rs"), root.join("top.rs")];
+        assert_eq!(list_files(root, Some(1)), expected);
+        assert_eq!(list_files(root, None).len(), 3);
+
+        // Inside a work tree git is asked for the same depth
+        let git_init = Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(root)
+            .status();
+        if git_init.is_ok_and(|status| status.success()) {
+            assert_eq!(list_files(root, Some(1)), expected);
+            assert_eq!(list_files(root, Some(0)), vec![root.join("top.rs")]);
+        }
+    }
+}
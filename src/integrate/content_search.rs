@@ -0,0 +1,380 @@
+//! Background search of file contents
+//!
+//! A [`ContentSearch`] scans the text files under a directory on a worker
+//! thread and streams matching lines back over a channel. The files are the
+//! same ones `--concat` would dump: gitignore-aware inside a work tree,
+//! binaries skipped. Cancelling or dropping the handle sets an atomic flag
+//! the worker checks before every file and line, so starting a new query
+//! never waits for the previous one. The worker also stops on its own once
+//! `max_results` lines have matched.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+
+use super::concat::{list_files, read_text};
+
+/// Default cap on the number of matching lines
+pub const DEFAULT_MAX_RESULTS: usize = 1000;
+
+/// Files larger than this are not searched
+const MAX_FILE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Scanned files between progress reports
+const PROGRESS_EVERY: usize = 64;
+
+/// A matching line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentMatch {
+    /// File containing the match
+    pub path: PathBuf,
+    /// Line number (1-based)
+    pub line: usize,
+    /// Line text, trimmed
+    pub text: String,
+}
+
+/// What to search for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// Text to find
+    pub text: String,
+    /// Match case exactly (callers resolve smart case)
+    pub case_sensitive: bool,
+    /// Stop after this many matching lines
+    pub max_results: usize,
+}
+
+/// Message from the search worker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchEvent {
+    /// Files scanned so far
+    Progress(usize),
+    /// A matching line
+    Match(ContentMatch),
+    /// The worker stopped
+    Finished(SearchOutcome),
+}
+
+/// How a search ended
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchOutcome {
+    /// Files scanned
+    pub scanned: usize,
+    /// Matching lines found
+    pub matches: usize,
+    /// Stopped because `max_results` was reached
+    pub capped: bool,
+    /// Stopped because the cancel flag was set
+    pub cancelled: bool,
+}
+
+/// Search the text files under `root`, sending events to `emit`
+///
+/// Runs on the calling thread; returns early when `cancel` is set or when
+/// `emit` returns false (the receiver went away).
+pub fn search_contents(
+    root: &Path,
+    query: &SearchQuery,
+    cancel: &AtomicBool,
+    mut emit: impl FnMut(SearchEvent) -> bool,
+) -> SearchOutcome {
+    let mut outcome = SearchOutcome::default();
+    if query.text.is_empty() || query.max_results == 0 {
+        return outcome;
+    }
+    let needle = if query.case_sensitive {
+        query.text.clone()
+    } else {
+        query.text.to_lowercase()
+    };
+
+    'files: for path in list_files(root, None) {
+        if cancel.load(Ordering::Relaxed) {
+            outcome.cancelled = true;
+            break;
+        }
+        outcome.scanned += 1;
+        if outcome.scanned % PROGRESS_EVERY == 0 && !emit(SearchEvent::Progress(outcome.scanned)) {
+            outcome.cancelled = true;
+            break;
+        }
+        if fs::metadata(&path).map_or(true, |meta| meta.len() > MAX_FILE_BYTES) {
+            continue;
+        }
+        let Some(content) = read_text(&path) else {
+            continue;
+        };
+
+        for (index, line) in content.lines().enumerate() {
+            if cancel.load(Ordering::Relaxed) {
+                outcome.cancelled = true;
+                break 'files;
+            }
+            let found = if query.case_sensitive {
+                line.contains(&needle)
+            } else {
+                line.to_lowercase().contains(&needle)
+            };
+            if !found {
+                continue;
+            }
+            let hit = ContentMatch {
+                path: path.clone(),
+                line: index + 1,
+                text: line.trim().to_string(),
+            };
+            if !emit(SearchEvent::Match(hit)) {
+                outcome.cancelled = true;
+                break 'files;
+            }
+            outcome.matches += 1;
+            if outcome.matches >= query.max_results {
+                outcome.capped = true;
+                break 'files;
+            }
+        }
+    }
+    outcome
+}
+
+/// A running (or finished) search on a worker thread
+pub struct ContentSearch {
+    /// Query being searched
+    pub query: SearchQuery,
+    /// Matches received so far
+    pub matches: Vec<ContentMatch>,
+    /// Files scanned so far
+    pub scanned: usize,
+    /// Final outcome once the worker stopped
+    pub outcome: Option<SearchOutcome>,
+    cancel: Arc<AtomicBool>,
+    events: Receiver<SearchEvent>,
+}
+
+impl ContentSearch {
+    /// Start searching `root` on a new worker thread
+    pub fn start(root: &Path, query: SearchQuery) -> Self {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (tx, events) = mpsc::channel();
+
+        let root = root.to_path_buf();
+        let worker_query = query.clone();
+        let worker_cancel = Arc::clone(&cancel);
+        thread::spawn(move || {
+            let outcome = search_contents(&root, &worker_query, &worker_cancel, |event| {
+                tx.send(event).is_ok()
+            });
+            let _ = tx.send(SearchEvent::Finished(outcome));
+        });
+
+        Self {
+            query,
+            matches: Vec::new(),
+            scanned: 0,
+            outcome: None,
+            cancel,
+            events,
+        }
+    }
+
+    /// Ask the worker to stop; results already received are discarded
+    pub fn cancel(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        self.matches.clear();
+    }
+
+    /// Whether the worker is still scanning
+    pub fn is_running(&self) -> bool {
+        self.outcome.is_none() && !self.cancel.load(Ordering::Relaxed)
+    }
+
+    /// Collect pending worker events (returns true if anything changed)
+    ///
+    /// Nothing is collected after [`cancel`](Self::cancel), so an aborted
+    /// search never shows results that arrived late.
+    pub fn poll(&mut self) -> bool {
+        if self.cancel.load(Ordering::Relaxed) {
+            return false;
+        }
+        let mut changed = false;
+        loop {
+            match self.events.try_recv() {
+                Ok(SearchEvent::Progress(scanned)) => self.scanned = scanned,
+                Ok(SearchEvent::Match(hit)) => self.matches.push(hit),
+                Ok(SearchEvent::Finished(outcome)) => {
+                    self.scanned = outcome.scanned;
+                    self.outcome = Some(outcome);
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    if self.outcome.is_none() {
+                        self.outcome = Some(SearchOutcome {
+                            scanned: self.scanned,
+                            matches: self.matches.len(),
+                            ..SearchOutcome::default()
+                        });
+                    }
+                    break;
+                }
+            }
+            changed = true;
+        }
+        changed
+    }
+
+    /// One-line progress summary for the status bar
+    pub fn status(&self) -> String {
+        let count = self.matches.len();
+        match self.outcome {
+            None => format!(
+                "Searching \"{}\": {} files scanned, {} matches",
+                self.query.text, self.scanned, count
+            ),
+            Some(outcome) if outcome.capped => format!(
+                "\"{}\": first {} matches ({} files scanned, limit reached)",
+                self.query.text, count, outcome.scanned
+            ),
+            Some(outcome) => format!(
+                "\"{}\": {} matches in {} files",
+                self.query.text, count, outcome.scanned
+            ),
+        }
+    }
+}
+
+impl Drop for ContentSearch {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+    use tempfile::TempDir;
+
+    fn query(text: &str, max_results: usize) -> SearchQuery {
+        SearchQuery {
+            text: text.to_string(),
+            case_sensitive: false,
+            max_results,
+        }
+    }
+
+    fn fixture(files: usize, lines: usize) -> TempDir {
+        let temp = TempDir::new().unwrap();
+        for i in 0..files {
+            let body: String = (0..lines).map(|l| format!("needle {}\n", l)).collect();
+            fs::write(temp.path().join(format!("f{:03}.txt", i)), body).unwrap();
+        }
+        temp
+    }
+
+    #[test]
+    fn test_search_contents_finds_lines() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("a.rs"), "fn main() {\n    Needle();\n}\n").unwrap();
+        fs::write(temp.path().join("b.txt"), "nothing here\n").unwrap();
+
+        let mut hits = Vec::new();
+        let cancel = AtomicBool::new(false);
+        let outcome = search_contents(temp.path(), &query("needle", 10), &cancel, |event| {
+            if let SearchEvent::Match(hit) = event {
+                hits.push(hit);
+            }
+            true
+        });
+
+        assert_eq!(outcome.scanned, 2);
+        assert!(!outcome.capped && !outcome.cancelled);
+        assert_eq!(
+            hits,
+            vec![ContentMatch {
+                path: temp.path().join("a.rs"),
+                line: 2,
+                text: "Needle();".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_search_contents_honors_result_cap() {
+        let temp = fixture(5, 10);
+        let cancel = AtomicBool::new(false);
+        let mut matches = 0;
+        let outcome = search_contents(temp.path(), &query("needle", 25), &cancel, |event| {
+            if matches!(event, SearchEvent::Match(_)) {
+                matches += 1;
+            }
+            true
+        });
+
+        assert_eq!(matches, 25);
+        assert_eq!(outcome.matches, 25);
+        assert!(outcome.capped);
+        // Stopped in the third file
+        assert_eq!(outcome.scanned, 3);
+    }
+
+    #[test]
+    fn test_search_contents_stops_when_cancelled() {
+        let temp = fixture(5, 10);
+        let cancel = AtomicBool::new(false);
+        let mut matches = 0;
+        let outcome = search_contents(temp.path(), &query("needle", 1000), &cancel, |event| {
+            if matches!(event, SearchEvent::Match(_)) {
+                matches += 1;
+                if matches == 3 {
+                    cancel.store(true, Ordering::Relaxed);
+                }
+            }
+            true
+        });
+
+        assert!(outcome.cancelled);
+        assert_eq!(matches, 3);
+        assert_eq!(outcome.scanned, 1);
+    }
+
+    #[test]
+    fn test_cancelled_search_keeps_no_results() {
+        let temp = fixture(200, 50);
+        let mut search = ContentSearch::start(temp.path(), query("needle", usize::MAX));
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while search.matches.is_empty() && Instant::now() < deadline {
+            search.poll();
+            thread::sleep(Duration::from_millis(1));
+        }
+        search.cancel();
+
+        assert!(search.matches.is_empty());
+        assert!(!search.is_running());
+        thread::sleep(Duration::from_millis(20));
+        assert!(!search.poll());
+        assert!(search.matches.is_empty());
+    }
+
+    #[test]
+    fn test_content_search_completes() {
+        let temp = fixture(3, 2);
+        let mut search = ContentSearch::start(temp.path(), query("NEEDLE 1", 100));
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while search.outcome.is_none() && Instant::now() < deadline {
+            search.poll();
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        let outcome = search.outcome.expect("search finished");
+        assert_eq!(outcome.scanned, 3);
+        assert_eq!(search.matches.len(), 3);
+        assert!(search.matches.iter().all(|hit| hit.line == 2));
+        assert_eq!(search.status(), "\"NEEDLE 1\": 3 matches in 3 files");
+    }
+}
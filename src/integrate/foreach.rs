@@ -0,0 +1,319 @@
+//! Running a shell command once per marked file
+//!
+//! A [`ForeachRun`] expands `{path}` in a command template for each file and
+//! runs the commands one after another on a worker thread, streaming each
+//! file's exit status and combined stdout/stderr back over a channel. A
+//! failing command does not stop the run. Cancelling or dropping the handle
+//! stops it before the next file. Commands get no stdin, so one that reads
+//! input sees end-of-file instead of competing with the TUI for the terminal.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+
+/// Placeholder replaced with the (shell-quoted) path of each file
+pub const PATH_PLACEHOLDER: &str = "{path}";
+
+/// Outcome of the command for one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRun {
+    /// File the command ran for
+    pub path: PathBuf,
+    /// Exit code (None if killed by a signal or not started)
+    pub code: Option<i32>,
+    /// Whether the command exited successfully
+    pub success: bool,
+    /// Combined stdout and stderr (or why the command could not start)
+    pub output: String,
+}
+
+/// Expand `{path}` in `template` for one file
+///
+/// A template without the placeholder gets the path appended, so `wc -l`
+/// behaves like `wc -l {path}`.
+pub fn expand_path_template(template: &str, path: &Path) -> String {
+    let quoted = shell_escape(&path.display().to_string());
+    if template.contains(PATH_PLACEHOLDER) {
+        template.replace(PATH_PLACEHOLDER, &quoted)
+    } else {
+        format!("{} {}", template, quoted)
+    }
+}
+
+/// Run `template` for each path in `cwd`, sending each result to `emit`
+///
+/// Runs on the calling thread; returns the number of files run, stopping
+/// early when `cancel` is set or `emit` returns false.
+pub fn run_foreach(
+    template: &str,
+    paths: &[PathBuf],
+    cwd: &Path,
+    cancel: &AtomicBool,
+    mut emit: impl FnMut(FileRun) -> bool,
+) -> usize {
+    let mut ran = 0;
+    for path in paths {
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        let run = run_one(&expand_path_template(template, path), path, cwd);
+        ran += 1;
+        if !emit(run) {
+            break;
+        }
+    }
+    ran
+}
+
+fn run_one(cmd: &str, path: &Path, cwd: &Path) -> FileRun {
+    let output = if cfg!(target_os = "windows") {
+        Command::new("cmd")
+            .args(["/C", cmd])
+            .current_dir(cwd)
+            .stdin(Stdio::null())
+            .output()
+    } else {
+        Command::new("sh")
+            .args(["-c", cmd])
+            .current_dir(cwd)
+            .stdin(Stdio::null())
+            .output()
+    };
+    match output {
+        Ok(output) => {
+            let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+            text.push_str(&String::from_utf8_lossy(&output.stderr));
+            FileRun {
+                path: path.to_path_buf(),
+                code: output.status.code(),
+                success: output.status.success(),
+                output: text,
+            }
+        }
+        Err(e) => FileRun {
+            path: path.to_path_buf(),
+            code: None,
+            success: false,
+            output: format!("Failed to execute command: {}", e),
+        },
+    }
+}
+
+fn shell_escape(value: &str) -> String {
+    if cfg!(target_os = "windows") {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// A command running (or finished) over a list of files on a worker thread
+pub struct ForeachRun {
+    /// Command template as typed
+    pub template: String,
+    /// Number of files to run
+    pub total: usize,
+    /// Results received so far, in path order
+    pub results: Vec<FileRun>,
+    /// Set once the worker stopped
+    pub finished: bool,
+    cancel: Arc<AtomicBool>,
+    events: Receiver<FileRun>,
+}
+
+impl ForeachRun {
+    /// Start running `template` over `paths` in `cwd` on a new worker thread
+    pub fn start(template: &str, paths: Vec<PathBuf>, cwd: &Path) -> Self {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (tx, events) = mpsc::channel();
+
+        let total = paths.len();
+        let worker_template = template.to_string();
+        let worker_cancel = Arc::clone(&cancel);
+        let cwd = cwd.to_path_buf();
+        thread::spawn(move || {
+            run_foreach(&worker_template, &paths, &cwd, &worker_cancel, |run| {
+                tx.send(run).is_ok()
+            });
+        });
+
+        Self {
+            template: template.to_string(),
+            total,
+            results: Vec::new(),
+            finished: total == 0,
+            cancel,
+            events,
+        }
+    }
+
+    /// Ask the worker to stop after the file it is running
+    pub fn cancel(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether commands are still running
+    pub fn is_running(&self) -> bool {
+        !self.finished && !self.cancel.load(Ordering::Relaxed)
+    }
+
+    /// Number of files whose command failed
+    pub fn failures(&self) -> usize {
+        self.results.iter().filter(|run| !run.success).count()
+    }
+
+    /// Lines of the output pane: a status line per file, then its output
+    pub fn line_count(&self) -> usize {
+        self.results
+            .iter()
+            .map(|run| 1 + run.output.lines().count())
+            .sum()
+    }
+
+    /// Collect pending results (returns true if anything changed)
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        loop {
+            match self.events.try_recv() {
+                Ok(run) => {
+                    self.results.push(run);
+                    changed = true;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    changed |= !self.finished;
+                    self.finished = true;
+                    break;
+                }
+            }
+        }
+        changed
+    }
+
+    /// One-line progress summary for the status bar
+    pub fn status(&self) -> String {
+        let failed = match self.failures() {
+            0 => String::new(),
+            n => format!(", {} failed", n),
+        };
+        if self.is_running() {
+            format!(
+                "Running \"{}\": {}/{} files{}",
+                self.template,
+                self.results.len(),
+                self.total,
+                failed
+            )
+        } else {
+            format!(
+                "\"{}\": ran on {} of {} files{}",
+                self.template,
+                self.results.len(),
+                self.total,
+                failed
+            )
+        }
+    }
+}
+
+impl Drop for ForeachRun {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+    use tempfile::TempDir;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_expand_path_template() {
+        let path = Path::new("/tmp/it's.txt");
+        assert_eq!(
+            expand_path_template("wc -l {path}", path),
+            "wc -l '/tmp/it'\\''s.txt'"
+        );
+        assert_eq!(
+            expand_path_template("wc -l", Path::new("/tmp/a")),
+            "wc -l '/tmp/a'"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_foreach_expands_per_file_and_continues_on_failure() {
+        let temp = TempDir::new().unwrap();
+        let mut paths = Vec::new();
+        for (name, lines) in [("a.txt", 1), ("b.txt", 2), ("c.txt", 3)] {
+            let path = temp.path().join(name);
+            std::fs::write(&path, "x\n".repeat(lines)).unwrap();
+            paths.push(path);
+        }
+        paths.insert(1, temp.path().join("missing.txt"));
+
+        let mut runs = Vec::new();
+        let ran = run_foreach(
+            "wc -l < {path}",
+            &paths,
+            temp.path(),
+            &AtomicBool::new(false),
+            |run| {
+                runs.push(run);
+                true
+            },
+        );
+
+        assert_eq!(ran, 4);
+        let outputs: Vec<(bool, String)> = runs
+            .iter()
+            .map(|run| (run.success, run.output.trim().to_string()))
+            .collect();
+        assert_eq!(outputs[0], (true, "1".to_string()));
+        assert!(!outputs[1].0);
+        assert_eq!(outputs[2], (true, "2".to_string()));
+        assert_eq!(outputs[3], (true, "3".to_string()));
+        assert_eq!(runs[1].path, paths[1]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_reads_no_stdin() {
+        let temp = TempDir::new().unwrap();
+        let run = run_one(
+            "read -r line && echo got || echo eof",
+            temp.path(),
+            temp.path(),
+        );
+        assert!(run.success);
+        assert_eq!(run.output, "eof\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_foreach_run_streams_results() {
+        let temp = TempDir::new().unwrap();
+        let paths: Vec<PathBuf> = ["a", "b"].iter().map(|n| temp.path().join(n)).collect();
+        let mut run = ForeachRun::start("echo {path}; exit 3", paths.clone(), temp.path());
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while run.is_running() && Instant::now() < deadline {
+            run.poll();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        assert_eq!(run.results.len(), 2);
+        assert_eq!(run.failures(), 2);
+        assert_eq!(run.results[0].code, Some(3));
+        assert!(run.results[1].output.contains(&*paths[1].to_string_lossy()));
+        assert_eq!(
+            run.status(),
+            "\"echo {path}; exit 3\": ran on 2 of 2 files, 2 failed"
+        );
+    }
+}
@@ -6,33 +6,72 @@
 //! - Tree mode: Output directory tree to stdout (--tree)
 //! - Content output: Include file contents in pick output (--with-content)
 //! - Context mode: Output project context for AI tools (--context)
+//! - Concat mode: Dump all text files of a directory (--concat)
+//! - Symbols: JSON index of top-level code symbols (--symbols)
+//! - Content search: Background search of file contents
+//! - Foreach: Background run of a shell command over marked files
 //! - Session: Save/restore selection state
+//! - Recent: Persisted list of recently opened files
+//! - State file: Export/import of recent files and sessions (--export-state)
+//! - UI state: Layout adjustments remembered between runs
+//! - Serve: Local HTTP/JSON API for the running tree (--serve, `serve` feature)
 
 pub mod benchmark;
+pub mod bookmarks;
 pub mod callback;
 pub mod claude_init;
+pub mod concat;
+pub mod content_search;
 pub mod context;
 pub mod context_pack;
+pub mod foreach;
 pub mod pick;
 pub mod plugin_cmd;
+pub mod recent;
 pub mod related;
+#[cfg(feature = "serve")]
+pub mod serve;
 pub mod session;
+pub mod state_file;
+pub mod symbols;
 pub mod tree;
+pub mod ui_state;
+pub mod watch;
 
 pub use benchmark::run_ai_benchmark;
+pub use bookmarks::BookmarkStore;
 pub use callback::{Callback, CallbackResult};
 pub use claude_init::claude_init;
+pub use concat::{build_concat, output_concat, Concat, ConcatOptions};
+pub use content_search::{
+    search_contents, ContentMatch, ContentSearch, SearchEvent, SearchOutcome, SearchQuery,
+};
 pub use context::{build_project_context, output_context};
 pub use context_pack::{
     build_context_pack, build_context_pack_with_options, output_context_pack,
     output_context_pack_with_options, ContextAgent, ContextPackFormat, ContextPackOptions,
     ContextPackPreset,
 };
+pub use foreach::{expand_path_template, run_foreach, FileRun, ForeachRun, PATH_PLACEHOLDER};
 pub use pick::{
-    exit_code, output_paths, output_paths_claude_format, output_paths_with_content, OutputFormat,
-    PickResult,
+    exit_code, output_paths, output_paths_claude_format, output_paths_with_content,
+    output_paths_with_options, OutputFormat, OutputOptions, PickResult,
 };
 pub use plugin_cmd::{plugin_init, plugin_test};
+pub use recent::{RecentEntry, RecentStore, RECENT_CAPACITY};
 pub use related::{collect_related_candidates, collect_related_paths, RelatedCandidate};
+#[cfg(feature = "serve")]
+pub use serve::{
+    handle_request, parse_request, HttpRequest, HttpResponse, ServeCommand, SnapshotEntry,
+    StateServer, TreeSnapshot,
+};
 pub use session::{load_session, load_session_named, save_session, save_session_named, Session};
-pub use tree::{output_tree, print_tree_recursive_pub};
+pub use state_file::{ImportSummary, NamedSession, StateFile, STATE_FILE_VERSION};
+pub use symbols::{
+    build_symbol_index, file_symbols, output_symbols, WorkspaceSymbol, SYMBOLS_DEFAULT_DEPTH,
+};
+pub use tree::{
+    build_tree_json, output_tree, output_tree_json, print_tree_recursive_pub, TreeJsonNode,
+};
+pub use ui_state::{UiLayout, UiState};
+pub use watch::{run_watch, watch_dirs, watch_stdout, WATCH_DEBOUNCE};
@@ -5,7 +5,7 @@
 
 use std::fs;
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 /// Exit codes for the application
@@ -27,7 +27,7 @@ pub mod exit_code {
 }
 
 /// Output format for picked paths
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum OutputFormat {
     /// One path per line (default)
     #[default]
@@ -51,39 +51,93 @@ impl FromStr for OutputFormat {
     }
 }
 
+/// Per-path type indicators in pick output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutputOptions {
+    /// Append `/` to directory paths, like `ls -F` (--dir-slash)
+    pub trailing_slash: bool,
+    /// Emit `{"path", "type"}` objects in JSON output (--with-types)
+    pub types: bool,
+}
+
 /// Output selected paths to stdout
 pub fn output_paths(paths: &[PathBuf], format: OutputFormat) -> io::Result<()> {
+    output_paths_with_options(paths, format, OutputOptions::default())
+}
+
+/// Output selected paths to stdout with type indicators
+pub fn output_paths_with_options(
+    paths: &[PathBuf],
+    format: OutputFormat,
+    options: OutputOptions,
+) -> io::Result<()> {
     let stdout = io::stdout();
     let mut handle = stdout.lock();
+    write_paths(&mut handle, paths, format, options)?;
+    handle.flush()
+}
+
+/// Write paths in the given format
+///
+/// The trailing slash only changes the path text, so null-separated output
+/// still splits on `\0` alone.
+fn write_paths(
+    out: &mut impl Write,
+    paths: &[PathBuf],
+    format: OutputFormat,
+    options: OutputOptions,
+) -> io::Result<()> {
+    let display = |path: &Path| {
+        let text = path.display().to_string();
+        if options.trailing_slash && path.is_dir() && !text.ends_with('/') {
+            format!("{}/", text)
+        } else {
+            text
+        }
+    };
 
     match format {
         OutputFormat::Lines => {
             for path in paths {
-                writeln!(handle, "{}", path.display())?;
+                writeln!(out, "{}", display(path))?;
             }
         }
         OutputFormat::NullSeparated => {
             for (i, path) in paths.iter().enumerate() {
                 if i > 0 {
-                    write!(handle, "\0")?;
+                    write!(out, "\0")?;
                 }
-                write!(handle, "{}", path.display())?;
+                write!(out, "{}", display(path))?;
             }
             // Final null for xargs compatibility
             if !paths.is_empty() {
-                write!(handle, "\0")?;
+                write!(out, "\0")?;
             }
         }
+        OutputFormat::Json if options.types => {
+            let entries: Vec<serde_json::Value> = paths
+                .iter()
+                .map(|p| serde_json::json!({ "path": display(p), "type": path_type(p) }))
+                .collect();
+            writeln!(out, "{}", serde_json::Value::Array(entries))?;
+        }
         OutputFormat::Json => {
-            let json_paths: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
-            writeln!(handle, "{}", serde_json_mini(&json_paths))?;
+            let json_paths: Vec<String> = paths.iter().map(|p| display(p)).collect();
+            writeln!(out, "{}", serde_json_mini(&json_paths))?;
         }
     }
-
-    handle.flush()?;
     Ok(())
 }
 
+/// Entry type for JSON output: "dir", "file", or "symlink"
+fn path_type(path: &Path) -> &'static str {
+    match fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_symlink() => "symlink",
+        Ok(meta) if meta.is_dir() => "dir",
+        _ => "file",
+    }
+}
+
 /// Output selected paths with their file contents
 ///
 /// Format:
@@ -207,9 +261,18 @@ impl PickResult {
 
     /// Output result to stdout if paths were selected
     pub fn output(&self, format: OutputFormat) -> io::Result<i32> {
+        self.output_with_options(format, OutputOptions::default())
+    }
+
+    /// Output result to stdout with type indicators if paths were selected
+    pub fn output_with_options(
+        &self,
+        format: OutputFormat,
+        options: OutputOptions,
+    ) -> io::Result<i32> {
         match self {
             Self::Selected(paths) => {
-                output_paths(paths, format)?;
+                output_paths_with_options(paths, format, options)?;
                 Ok(exit_code::SUCCESS)
             }
             Self::Cancelled => Ok(exit_code::CANCELLED),
@@ -251,4 +314,76 @@ mod tests {
         ));
         assert!(OutputFormat::from_str("invalid").is_err());
     }
+
+    fn written(paths: &[PathBuf], format: OutputFormat, options: OutputOptions) -> String {
+        let mut out = Vec::new();
+        write_paths(&mut out, paths, format, options).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_trailing_slash_marks_directories() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path().join("src");
+        let file = temp.path().join("main.rs");
+        fs::create_dir(&dir).unwrap();
+        fs::write(&file, "").unwrap();
+        let paths = vec![dir.clone(), file.clone()];
+        let slash = OutputOptions {
+            trailing_slash: true,
+            ..OutputOptions::default()
+        };
+
+        assert_eq!(
+            written(&paths, OutputFormat::Lines, slash),
+            format!("{}/\n{}\n", dir.display(), file.display())
+        );
+        assert_eq!(
+            written(&paths, OutputFormat::Lines, OutputOptions::default()),
+            format!("{}\n{}\n", dir.display(), file.display())
+        );
+
+        let null = written(&paths, OutputFormat::NullSeparated, slash);
+        let parts: Vec<&str> = null.split('\0').collect();
+        assert_eq!(
+            parts,
+            vec![
+                format!("{}/", dir.display()).as_str(),
+                file.display().to_string().as_str(),
+                ""
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_types() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path().join("src");
+        let file = temp.path().join("main.rs");
+        fs::create_dir(&dir).unwrap();
+        fs::write(&file, "").unwrap();
+        let paths = vec![dir.clone(), file.clone()];
+
+        let json = written(
+            &paths,
+            OutputFormat::Json,
+            OutputOptions {
+                types: true,
+                ..OutputOptions::default()
+            },
+        );
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!([
+                { "path": dir.display().to_string(), "type": "dir" },
+                { "path": file.display().to_string(), "type": "file" },
+            ])
+        );
+
+        // Without types the output stays a plain array of strings
+        let plain = written(&paths, OutputFormat::Json, OutputOptions::default());
+        let value: serde_json::Value = serde_json::from_str(&plain).unwrap();
+        assert_eq!(value[0], dir.display().to_string());
+    }
 }
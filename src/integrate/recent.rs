@@ -0,0 +1,245 @@
+//! Recently opened files
+//!
+//! Keeps a persisted ring buffer of opened files (newest first) in
+//! `~/.config/fileview/recent.json`.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::ConfigFile;
+
+const RECENT_FILENAME: &str = "recent.json";
+
+/// Maximum number of remembered files
+pub const RECENT_CAPACITY: usize = 100;
+
+/// A recently opened file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentEntry {
+    /// Absolute path of the file
+    pub path: PathBuf,
+    /// Unix timestamp of the last open
+    pub timestamp: u64,
+}
+
+/// Ring buffer of recently opened files (newest first)
+#[derive(Debug, Clone, Default)]
+pub struct RecentStore {
+    entries: Vec<RecentEntry>,
+    capacity: usize,
+    /// File the store is persisted to (None = in-memory only)
+    file: Option<PathBuf>,
+}
+
+impl RecentStore {
+    /// Create an empty in-memory store
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            capacity,
+            file: None,
+        }
+    }
+
+    /// Default store location in the config directory
+    pub fn default_path() -> Option<PathBuf> {
+        ConfigFile::config_dir().map(|p| p.join(RECENT_FILENAME))
+    }
+
+    /// Load the store from the config directory
+    pub fn load() -> Self {
+        match Self::default_path() {
+            Some(path) => Self::load_from(&path, RECENT_CAPACITY),
+            None => Self::new(RECENT_CAPACITY),
+        }
+    }
+
+    /// Load the store from a file, dropping entries whose paths no longer exist
+    ///
+    /// A missing or unreadable file yields an empty store bound to `path`.
+    pub fn load_from(path: &Path, capacity: usize) -> Self {
+        let mut entries: Vec<RecentEntry> = fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        entries.retain(|e| e.path.exists());
+        entries.truncate(capacity);
+
+        Self {
+            entries,
+            capacity,
+            file: Some(path.to_path_buf()),
+        }
+    }
+
+    /// Save the store (no-op for in-memory stores)
+    pub fn save(&self) -> io::Result<()> {
+        let Some(ref path) = self.file else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&self.entries)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, json)
+    }
+
+    /// Record an opened file, moving it to the front if already present
+    pub fn record(&mut self, path: &Path) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.record_at(path, timestamp);
+    }
+
+    fn record_at(&mut self, path: &Path, timestamp: u64) {
+        self.entries.retain(|e| e.path != path);
+        self.entries.insert(
+            0,
+            RecentEntry {
+                path: path.to_path_buf(),
+                timestamp,
+            },
+        );
+        self.entries.truncate(self.capacity);
+    }
+
+    /// Merge entries from elsewhere, keeping the newest open of each path
+    pub fn merge(&mut self, entries: &[RecentEntry]) {
+        for entry in entries {
+            match self.entries.iter_mut().find(|e| e.path == entry.path) {
+                Some(existing) => existing.timestamp = existing.timestamp.max(entry.timestamp),
+                None => self.entries.push(entry.clone()),
+            }
+        }
+        self.entries.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+        self.entries.truncate(self.capacity);
+    }
+
+    /// Replace all entries
+    pub fn replace(&mut self, mut entries: Vec<RecentEntry>) {
+        entries.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+        entries.truncate(self.capacity);
+        self.entries = entries;
+    }
+
+    /// Entries, newest first
+    pub fn entries(&self) -> &[RecentEntry] {
+        &self.entries
+    }
+
+    /// Get the entry at an index (0 = newest)
+    pub fn get(&self, index: usize) -> Option<&RecentEntry> {
+        self.entries.get(index)
+    }
+
+    /// Number of remembered files
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if no files are remembered
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn paths(store: &RecentStore) -> Vec<PathBuf> {
+        store.entries().iter().map(|e| e.path.clone()).collect()
+    }
+
+    #[test]
+    fn test_record_newest_first() {
+        let mut store = RecentStore::new(10);
+        store.record_at(Path::new("/a"), 1);
+        store.record_at(Path::new("/b"), 2);
+        store.record_at(Path::new("/c"), 3);
+        assert_eq!(
+            paths(&store),
+            vec![
+                PathBuf::from("/c"),
+                PathBuf::from("/b"),
+                PathBuf::from("/a")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_record_dedup_moves_to_front() {
+        let mut store = RecentStore::new(10);
+        store.record_at(Path::new("/a"), 1);
+        store.record_at(Path::new("/b"), 2);
+        store.record_at(Path::new("/a"), 3);
+        assert_eq!(store.len(), 2);
+        assert_eq!(
+            paths(&store),
+            vec![PathBuf::from("/a"), PathBuf::from("/b")]
+        );
+        assert_eq!(store.get(0).unwrap().timestamp, 3);
+    }
+
+    #[test]
+    fn test_record_respects_capacity() {
+        let mut store = RecentStore::new(2);
+        store.record_at(Path::new("/a"), 1);
+        store.record_at(Path::new("/b"), 2);
+        store.record_at(Path::new("/c"), 3);
+        assert_eq!(
+            paths(&store),
+            vec![PathBuf::from("/c"), PathBuf::from("/b")]
+        );
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("a.txt");
+        fs::write(&file, "a").unwrap();
+        let store_path = temp.path().join("state").join(RECENT_FILENAME);
+
+        let mut store = RecentStore::load_from(&store_path, 10);
+        assert!(store.is_empty());
+        store.record(&file);
+        store.save().unwrap();
+
+        let loaded = RecentStore::load_from(&store_path, 10);
+        assert_eq!(paths(&loaded), vec![file]);
+    }
+
+    #[test]
+    fn test_load_prunes_missing_paths() {
+        let temp = TempDir::new().unwrap();
+        let kept = temp.path().join("kept.txt");
+        let gone = temp.path().join("gone.txt");
+        fs::write(&kept, "k").unwrap();
+        fs::write(&gone, "g").unwrap();
+        let store_path = temp.path().join(RECENT_FILENAME);
+
+        let mut store = RecentStore::load_from(&store_path, 10);
+        store.record_at(&kept, 1);
+        store.record_at(&gone, 2);
+        store.save().unwrap();
+
+        fs::remove_file(&gone).unwrap();
+        let loaded = RecentStore::load_from(&store_path, 10);
+        assert_eq!(paths(&loaded), vec![kept]);
+    }
+
+    #[test]
+    fn test_in_memory_save_is_noop() {
+        let mut store = RecentStore::new(10);
+        store.record(Path::new("/a"));
+        assert!(store.save().is_ok());
+    }
+}
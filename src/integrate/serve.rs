@@ -0,0 +1,651 @@
+//! Local HTTP/JSON API for the running tree (`--serve PORT`)
+//!
+//! A [`StateServer`] listens on `127.0.0.1` only and answers two routes:
+//!
+//! - `GET /state`: the displayed entries, focus, marks and git status as JSON
+//! - `POST /command`: `{"command": "focus" | "expand" | "reveal", "path": ...}`
+//!
+//! Requests are served on a background thread from the last snapshot the
+//! event loop published; commands are queued and applied by the event loop
+//! on its next pass. With a token set, every request must carry
+//! `Authorization: Bearer <token>`. Requests naming any host but the loopback
+//! in their `Host` header are refused, so a web page cannot reach the API by
+//! rebinding its own domain to 127.0.0.1. Browsers send an `Origin` header
+//! with cross-site requests, so those are refused too, and commands must be
+//! `application/json`, which a page cannot post without a preflight. Command
+//! paths must stay inside the root. Built only with the `serve` feature.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::tree::git_status_name;
+use crate::git::GitStatus;
+use crate::tree::TreeEntry;
+
+/// Largest request body accepted
+const MAX_BODY: usize = 64 * 1024;
+
+/// How long a client may take to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// One displayed entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SnapshotEntry {
+    pub path: String,
+    pub name: String,
+    /// Depth in the tree (0 = root)
+    pub depth: usize,
+    /// Entry type: "dir" or "file"
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// Whether a directory is expanded (always false for files)
+    pub expanded: bool,
+    /// Whether the entry is marked
+    pub marked: bool,
+    /// Git status, omitted for clean files and outside repositories
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git: Option<&'static str>,
+}
+
+/// Repository summary
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GitSnapshot {
+    /// Branch name, or `detached@<sha>`
+    pub branch: Option<String>,
+    /// Operation in progress ("rebasing", "merging", ...)
+    pub state: Option<&'static str>,
+    pub bare: bool,
+    /// Number of changed files
+    pub changed: usize,
+}
+
+/// What `GET /state` returns
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TreeSnapshot {
+    pub root: String,
+    /// Path of the focused entry
+    pub focus: Option<String>,
+    /// Entries as displayed, top to bottom
+    pub entries: Vec<SnapshotEntry>,
+    /// Omitted outside repositories
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git: Option<GitSnapshot>,
+}
+
+impl TreeSnapshot {
+    /// Capture the displayed entries and the focus
+    pub fn build(
+        root: &Path,
+        entries: &[&TreeEntry],
+        focus_index: usize,
+        marked: &HashSet<PathBuf>,
+        git: Option<&GitStatus>,
+    ) -> Self {
+        Self {
+            root: root.display().to_string(),
+            focus: entries
+                .get(focus_index)
+                .map(|e| e.path.display().to_string()),
+            entries: entries
+                .iter()
+                .map(|e| SnapshotEntry {
+                    path: e.path.display().to_string(),
+                    name: e.name.clone(),
+                    depth: e.depth,
+                    kind: if e.is_dir { "dir" } else { "file" },
+                    expanded: e.is_dir && e.expanded,
+                    marked: marked.contains(&e.path),
+                    git: git.and_then(|g| git_status_name(g.get_status(&e.path))),
+                })
+                .collect(),
+            git: git.map(|g| GitSnapshot {
+                branch: g.branch().map(str::to_string),
+                state: g.state().label(),
+                bare: g.is_bare(),
+                changed: g.changed_paths().len(),
+            }),
+        }
+    }
+
+    /// Digest of what [`build`](Self::build) would capture, so an unchanged
+    /// tree is not built and serialized again
+    pub fn digest(
+        root: &Path,
+        entries: &[&TreeEntry],
+        focus_index: usize,
+        marked: &HashSet<PathBuf>,
+        git: Option<&GitStatus>,
+    ) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        root.hash(&mut hasher);
+        focus_index.hash(&mut hasher);
+        for e in entries {
+            e.path.hash(&mut hasher);
+            (e.is_dir && e.expanded).hash(&mut hasher);
+            marked.contains(&e.path).hash(&mut hasher);
+            git.and_then(|g| git_status_name(g.get_status(&e.path)))
+                .hash(&mut hasher);
+        }
+        if let Some(g) = git {
+            g.branch().hash(&mut hasher);
+            g.state().label().hash(&mut hasher);
+            g.changed_paths().len().hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+}
+
+/// A command accepted by `POST /command`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ServeCommand {
+    /// Focus a displayed entry
+    Focus { path: PathBuf },
+    /// Expand a directory
+    Expand { path: PathBuf },
+    /// Expand the parents of a path and focus it
+    Reveal { path: PathBuf },
+}
+
+impl ServeCommand {
+    /// Target path, with relative paths taken from `root`
+    ///
+    /// None when the path leads outside `root` (absolute elsewhere or `..`).
+    pub fn target(&self, root: &Path) -> Option<PathBuf> {
+        let path = match self {
+            Self::Focus { path } | Self::Expand { path } | Self::Reveal { path } => path,
+        };
+        let target = root.join(path);
+        let escapes = target.components().any(|c| c == Component::ParentDir);
+        (!escapes && target.starts_with(root)).then_some(target)
+    }
+}
+
+/// A parsed HTTP request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: String,
+    /// Request target without the query string
+    pub path: String,
+    /// Bearer token from the `Authorization` header
+    pub token: Option<String>,
+    /// Value of the `Host` header
+    pub host: Option<String>,
+    /// Value of the `Origin` header (sent by browsers)
+    pub origin: Option<String>,
+    /// Media type of the `Content-Type` header, without parameters
+    pub content_type: Option<String>,
+    pub body: String,
+}
+
+/// An HTTP response with a JSON body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl HttpResponse {
+    fn json(status: u16, value: serde_json::Value) -> Self {
+        Self {
+            status,
+            body: value.to_string(),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, json!({ "error": message }))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            202 => "Accepted",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            415 => "Unsupported Media Type",
+            503 => "Service Unavailable",
+            _ => "Error",
+        }
+    }
+
+    fn write_to(&self, mut out: impl Write) -> std::io::Result<()> {
+        write!(
+            out,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.reason(),
+            self.body.len(),
+            self.body
+        )?;
+        out.flush()
+    }
+}
+
+/// Read one HTTP/1.1 request: request line, headers, then a `Content-Length` body
+///
+/// A request that cannot be read comes back as the error response to send.
+pub fn parse_request(mut reader: impl BufRead) -> Result<HttpRequest, HttpResponse> {
+    let bad_request = |e: std::io::Error| HttpResponse::error(400, &e.to_string());
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(bad_request)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(HttpResponse::error(400, "malformed request line"));
+    };
+    let method = method.to_string();
+    let path = target.split('?').next().unwrap_or(target).to_string();
+
+    let mut token = None;
+    let mut host = None;
+    let mut origin = None;
+    let mut content_type = None;
+    let mut length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).map_err(bad_request)? == 0 {
+            break;
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            length = value
+                .parse()
+                .map_err(|_| HttpResponse::error(400, "invalid Content-Length"))?;
+        } else if name.eq_ignore_ascii_case("authorization") {
+            token = value.strip_prefix("Bearer ").map(|t| t.trim().to_string());
+        } else if name.eq_ignore_ascii_case("host") {
+            host = Some(value.to_string());
+        } else if name.eq_ignore_ascii_case("origin") {
+            origin = Some(value.to_string());
+        } else if name.eq_ignore_ascii_case("content-type") {
+            let media = value.split(';').next().unwrap_or(value);
+            content_type = Some(media.trim().to_ascii_lowercase());
+        }
+    }
+    if length > MAX_BODY {
+        return Err(HttpResponse::error(413, "request body too large"));
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(bad_request)?;
+    Ok(HttpRequest {
+        method,
+        path,
+        token,
+        host,
+        origin,
+        content_type,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+/// Whether a `Host` header names the loopback interface (any port)
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or(rest),
+        None => host.rsplit_once(':').map_or(host, |(name, _)| name),
+    };
+    name.eq_ignore_ascii_case("localhost") || name == "127.0.0.1" || name == "::1"
+}
+
+/// Answer a request from the published snapshot, queueing commands
+pub fn handle_request(
+    request: &HttpRequest,
+    token: Option<&str>,
+    snapshot: &str,
+    commands: &Sender<ServeCommand>,
+) -> HttpResponse {
+    if request
+        .host
+        .as_deref()
+        .is_some_and(|host| !is_loopback_host(host))
+    {
+        return HttpResponse::error(403, "host not allowed");
+    }
+    if request.origin.is_some() {
+        return HttpResponse::error(403, "cross-origin requests not allowed");
+    }
+    if token.is_some_and(|token| request.token.as_deref() != Some(token)) {
+        return HttpResponse::error(401, "missing or wrong token");
+    }
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/state") => HttpResponse {
+            status: 200,
+            body: snapshot.to_string(),
+        },
+        ("POST", "/command") if request.content_type.as_deref() != Some("application/json") => {
+            HttpResponse::error(415, "commands must be application/json")
+        }
+        ("POST", "/command") => match serde_json::from_str::<ServeCommand>(&request.body) {
+            Ok(command) => {
+                if commands.send(command).is_err() {
+                    return HttpResponse::error(503, "fv is shutting down");
+                }
+                HttpResponse::json(202, json!({ "queued": true }))
+            }
+            Err(e) => HttpResponse::error(400, &format!("invalid command: {}", e)),
+        },
+        (_, "/state") | (_, "/command") => HttpResponse::error(405, "method not allowed"),
+        _ => HttpResponse::error(404, "not found"),
+    }
+}
+
+/// The API server running on a background thread
+pub struct StateServer {
+    addr: SocketAddr,
+    snapshot: Arc<Mutex<String>>,
+    commands: Receiver<ServeCommand>,
+    stop: Arc<AtomicBool>,
+}
+
+impl StateServer {
+    /// Listen on `127.0.0.1:port` (0 picks a free port)
+    pub fn start(port: u16, token: Option<String>) -> anyhow::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .with_context(|| format!("failed to listen on 127.0.0.1:{}", port))?;
+        let addr = listener.local_addr()?;
+        let snapshot = Arc::new(Mutex::new(serde_json::to_string(&TreeSnapshot::default())?));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (tx, commands) = mpsc::channel();
+
+        let worker_snapshot = Arc::clone(&snapshot);
+        let worker_stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if worker_stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    serve_connection(stream, token.as_deref(), &worker_snapshot, &tx);
+                }
+            }
+        });
+
+        Ok(Self {
+            addr,
+            snapshot,
+            commands,
+            stop,
+        })
+    }
+
+    /// Address the server listens on
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Replace the state served by `GET /state`
+    pub fn publish(&self, snapshot: &TreeSnapshot) {
+        if let Ok(json) = serde_json::to_string(snapshot) {
+            if let Ok(mut current) = self.snapshot.lock() {
+                *current = json;
+            }
+        }
+    }
+
+    /// Commands received since the last call, oldest first
+    pub fn take_commands(&self) -> Vec<ServeCommand> {
+        self.commands.try_iter().collect()
+    }
+}
+
+impl Drop for StateServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // Wake the accept loop so it sees the flag
+        let _ = TcpStream::connect(self.addr);
+    }
+}
+
+fn serve_connection(
+    stream: TcpStream,
+    token: Option<&str>,
+    snapshot: &Mutex<String>,
+    commands: &Sender<ServeCommand>,
+) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let response = match parse_request(BufReader::new(&stream)) {
+        Ok(request) => {
+            let snapshot = snapshot.lock().map(|s| s.clone()).unwrap_or_default();
+            handle_request(&request, token, &snapshot, commands)
+        }
+        Err(response) => response,
+    };
+    let _ = response.write_to(&stream);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree::TreeNavigator;
+    use std::fs;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    fn request(method: &str, path: &str, token: Option<&str>, body: &str) -> HttpRequest {
+        HttpRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            token: token.map(str::to_string),
+            host: Some("127.0.0.1:7878".to_string()),
+            origin: None,
+            content_type: (method == "POST").then(|| "application/json".to_string()),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_json() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join("src")).unwrap();
+        fs::write(temp.path().join("src/main.rs"), "").unwrap();
+        fs::write(temp.path().join("README.md"), "").unwrap();
+
+        let mut navigator = TreeNavigator::new(temp.path(), false).unwrap();
+        navigator.expand(&temp.path().join("src")).unwrap();
+        let entries = navigator.visible_entries();
+        let marked = HashSet::from([temp.path().join("README.md")]);
+        let snapshot = TreeSnapshot::build(temp.path(), &entries, 2, &marked, None);
+
+        let value = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(value["root"], temp.path().display().to_string());
+        assert_eq!(
+            value["focus"],
+            temp.path().join("src/main.rs").display().to_string()
+        );
+        assert!(value.get("git").is_none());
+        let names: Vec<&str> = value["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names[1..], ["src", "main.rs", "README.md"]);
+        assert_eq!(value["entries"][1]["type"], "dir");
+        assert_eq!(value["entries"][1]["expanded"], true);
+        assert_eq!(value["entries"][2]["depth"], 2);
+        assert_eq!(value["entries"][3]["marked"], true);
+        assert!(value["entries"][3].get("git").is_none());
+    }
+
+    #[test]
+    fn test_parse_request() {
+        let raw = "POST /command?x=1 HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer s3cret\r\nContent-Type: Application/JSON; charset=utf-8\r\nOrigin: null\r\ncontent-length: 9\r\n\r\n{\"a\": 1}\nextra";
+        let request = parse_request(raw.as_bytes()).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/command");
+        assert_eq!(request.token.as_deref(), Some("s3cret"));
+        assert_eq!(request.host.as_deref(), Some("localhost"));
+        assert_eq!(request.content_type.as_deref(), Some("application/json"));
+        assert_eq!(request.origin.as_deref(), Some("null"));
+        assert_eq!(request.body, "{\"a\": 1}\n");
+
+        assert_eq!(parse_request("\r\n".as_bytes()).unwrap_err().status, 400);
+        let huge = format!(
+            "POST /command HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY + 1
+        );
+        assert_eq!(parse_request(huge.as_bytes()).unwrap_err().status, 413);
+    }
+
+    #[test]
+    fn test_handle_request() {
+        let (tx, rx) = mpsc::channel();
+        let snapshot = r#"{"root":"/p"}"#;
+
+        let response = handle_request(&request("GET", "/state", None, ""), None, snapshot, &tx);
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, snapshot);
+
+        let body = r#"{"command": "reveal", "path": "src/lib.rs"}"#;
+        let response = handle_request(
+            &request("POST", "/command", None, body),
+            None,
+            snapshot,
+            &tx,
+        );
+        assert_eq!(response.status, 202);
+        let command = rx.try_recv().unwrap();
+        assert_eq!(
+            command,
+            ServeCommand::Reveal {
+                path: PathBuf::from("src/lib.rs")
+            }
+        );
+        assert_eq!(
+            command.target(Path::new("/p")),
+            Some(PathBuf::from("/p/src/lib.rs"))
+        );
+        for outside in ["/etc/passwd", "../x", "src/../../x"] {
+            let command = ServeCommand::Focus {
+                path: PathBuf::from(outside),
+            };
+            assert_eq!(command.target(Path::new("/p")), None, "{}", outside);
+        }
+
+        let bad = r#"{"command": "delete", "path": "x"}"#;
+        let status = |req: HttpRequest, token: Option<&str>| {
+            handle_request(&req, token, snapshot, &tx).status
+        };
+        assert_eq!(status(request("POST", "/command", None, bad), None), 400);
+        assert_eq!(status(request("DELETE", "/state", None, ""), None), 405);
+        assert_eq!(status(request("GET", "/", None, ""), None), 404);
+        assert_eq!(status(request("GET", "/state", None, ""), Some("t")), 401);
+        assert_eq!(
+            status(request("GET", "/state", Some("u"), ""), Some("t")),
+            401
+        );
+        assert_eq!(
+            status(request("GET", "/state", Some("t"), ""), Some("t")),
+            200
+        );
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_cross_site_requests_are_refused() {
+        let (tx, rx) = mpsc::channel();
+        let body = r#"{"command": "focus", "path": "x"}"#;
+
+        // A page can post text/plain without a preflight
+        let mut req = request("POST", "/command", None, body);
+        req.content_type = Some("text/plain".to_string());
+        assert_eq!(handle_request(&req, None, "{}", &tx).status, 415);
+        req.content_type = None;
+        assert_eq!(handle_request(&req, None, "{}", &tx).status, 415);
+
+        let mut req = request("POST", "/command", None, body);
+        req.origin = Some("https://evil.example".to_string());
+        assert_eq!(handle_request(&req, None, "{}", &tx).status, 403);
+        let mut req = request("GET", "/state", None, "");
+        req.origin = Some("http://127.0.0.1:7878".to_string());
+        assert_eq!(handle_request(&req, None, "{}", &tx).status, 403);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_rebound_host_is_refused() {
+        let (tx, _rx) = mpsc::channel();
+        let mut req = request("GET", "/state", None, "");
+        for host in ["localhost", "LOCALHOST:80", "127.0.0.1:7878", "[::1]:7878"] {
+            req.host = Some(host.to_string());
+            assert_eq!(
+                handle_request(&req, None, "{}", &tx).status,
+                200,
+                "{}",
+                host
+            );
+        }
+        for host in ["evil.example:7878", "localhost.evil.example", "127.0.0.2"] {
+            req.host = Some(host.to_string());
+            assert_eq!(
+                handle_request(&req, None, "{}", &tx).status,
+                403,
+                "{}",
+                host
+            );
+        }
+    }
+
+    #[test]
+    fn test_server_roundtrip() {
+        let server = StateServer::start(0, Some("t".to_string())).unwrap();
+        assert!(server.addr().ip().is_loopback());
+        server.publish(&TreeSnapshot {
+            root: "/p".to_string(),
+            ..TreeSnapshot::default()
+        });
+
+        let send = |raw: &str| {
+            let mut stream = TcpStream::connect(server.addr()).unwrap();
+            stream.write_all(raw.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        let response = send("GET /state HTTP/1.1\r\nAuthorization: Bearer t\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with(r#"{"root":"/p","focus":null,"entries":[]}"#));
+
+        let body = r#"{"command":"expand","path":"/p/src"}"#;
+        let response = send(&format!(
+            "POST /command HTTP/1.1\r\nAuthorization: Bearer t\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        ));
+        assert!(response.starts_with("HTTP/1.1 202 Accepted\r\n"));
+        assert_eq!(
+            server.take_commands(),
+            vec![ServeCommand::Expand {
+                path: PathBuf::from("/p/src")
+            }]
+        );
+
+        assert!(send("GET /state HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 401"));
+    }
+}
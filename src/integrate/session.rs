@@ -133,6 +133,45 @@ impl Session {
         now.saturating_sub(self.timestamp) < DAY_SECS
     }
 
+    /// Names of the sessions saved under a root (None = the default session)
+    pub fn list_names(root: &Path) -> io::Result<Vec<Option<String>>> {
+        let mut names = Vec::new();
+        for entry in fs::read_dir(root)? {
+            let file_name = entry?.file_name();
+            let Some(file_name) = file_name.to_str() else {
+                continue;
+            };
+            if file_name == SESSION_FILENAME {
+                names.push(None);
+            } else if let Some(name) = file_name
+                .strip_prefix(".fileview-session-")
+                .and_then(|rest| rest.strip_suffix(".json"))
+            {
+                names.push(Some(name.to_string()));
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    /// Merge another session of the same root into this one
+    ///
+    /// Selections are combined; the focus and timestamp come from the newer
+    /// session.
+    pub fn merge(&mut self, other: Session) {
+        for path in other.selected_paths {
+            if !self.selected_paths.contains(&path) {
+                self.selected_paths.push(path);
+            }
+        }
+        if other.timestamp >= self.timestamp {
+            if other.focus_path.is_some() {
+                self.focus_path = other.focus_path;
+            }
+            self.timestamp = other.timestamp;
+        }
+    }
+
     /// Delete session file
     pub fn delete(root: &Path) -> io::Result<()> {
         Self::delete_named(root, None)
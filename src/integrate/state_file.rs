@@ -0,0 +1,297 @@
+//! Portable export of persisted state
+//!
+//! `--export-state FILE` bundles the recent files list, the bookmarks and the
+//! sessions saved under the root into one JSON file, and `--import-state FILE` restores them
+//! on another machine. Paths are stored as-is (absolute), so an export only
+//! lines up with a machine that has the same directory layout.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use super::bookmarks::BookmarkStore;
+use super::recent::{RecentEntry, RecentStore};
+use super::session::Session;
+
+/// Version written to new exports
+pub const STATE_FILE_VERSION: u32 = 1;
+
+const STATE_FILE_NOTE: &str =
+    "Paths are absolute and machine-specific; they only resolve where the same directories exist";
+
+/// A session with the name it was saved under
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamedSession {
+    /// Session name (None = the default session)
+    pub name: Option<String>,
+    pub session: Session,
+}
+
+/// Exported state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateFile {
+    pub version: u32,
+    /// Reminder that the paths belong to the exporting machine
+    #[serde(default)]
+    pub note: String,
+    /// Recently opened files, newest first
+    #[serde(default)]
+    pub recent: Vec<RecentEntry>,
+    /// Bookmarks by slot number (1-9)
+    #[serde(default)]
+    pub bookmarks: BTreeMap<usize, PathBuf>,
+    /// Sessions saved under the exported root
+    #[serde(default)]
+    pub sessions: Vec<NamedSession>,
+}
+
+/// What an import changed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// Recent files in the store afterwards
+    pub recent: usize,
+    /// Bookmarks taken into free slots (all of them with `replace`)
+    pub bookmarks: usize,
+    /// Sessions written
+    pub sessions: usize,
+    /// Sessions skipped because their root does not exist here
+    pub skipped: usize,
+}
+
+impl StateFile {
+    /// Collect the recent files, the bookmarks and the sessions saved under `root`
+    pub fn collect(
+        recent: &RecentStore,
+        bookmarks: &BookmarkStore,
+        root: &Path,
+    ) -> anyhow::Result<Self> {
+        let mut sessions = Vec::new();
+        for name in Session::list_names(root)? {
+            let session = Session::load_named(root, name.as_deref()).with_context(|| {
+                format!(
+                    "failed to read session {}",
+                    name.as_deref().unwrap_or("default")
+                )
+            })?;
+            sessions.push(NamedSession { name, session });
+        }
+        Ok(Self {
+            version: STATE_FILE_VERSION,
+            note: STATE_FILE_NOTE.to_string(),
+            recent: recent.entries().to_vec(),
+            bookmarks: bookmarks.saved(),
+            sessions,
+        })
+    }
+
+    /// Write the export as pretty-printed JSON
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// Read an export written by [`StateFile::write`]
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let state: Self = serde_json::from_str(&json)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        if state.version > STATE_FILE_VERSION {
+            anyhow::bail!(
+                "{} has version {}, this fv reads up to {}",
+                path.display(),
+                state.version,
+                STATE_FILE_VERSION
+            );
+        }
+        Ok(state)
+    }
+
+    /// Restore the state into `recent`, `bookmarks` and the session files
+    ///
+    /// By default recent files and sessions are merged with what is already
+    /// there and bookmarks only fill free slots; `replace` overwrites them.
+    /// Sessions are written under the root they were saved in and skipped
+    /// when that directory does not exist. The caller saves `recent` and
+    /// `bookmarks`.
+    pub fn apply(
+        self,
+        recent: &mut RecentStore,
+        bookmarks: &mut BookmarkStore,
+        replace: bool,
+    ) -> anyhow::Result<ImportSummary> {
+        if replace {
+            recent.replace(self.recent);
+        } else {
+            recent.merge(&self.recent);
+        }
+
+        let mut summary = ImportSummary {
+            recent: recent.len(),
+            bookmarks: bookmarks.import(self.bookmarks, replace),
+            ..ImportSummary::default()
+        };
+        for NamedSession { name, mut session } in self.sessions {
+            let root = PathBuf::from(&session.root);
+            if !root.is_dir() {
+                summary.skipped += 1;
+                continue;
+            }
+            if !replace {
+                if let Ok(existing) = Session::load_named(&root, name.as_deref()) {
+                    let mut merged = existing;
+                    merged.merge(session);
+                    session = merged;
+                }
+            }
+            session
+                .save_named(&root, name.as_deref())
+                .with_context(|| format!("failed to write session under {}", root.display()))?;
+            summary.sessions += 1;
+        }
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_export_import_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("project");
+        fs::create_dir(&root).unwrap();
+        let a = root.join("a.txt");
+        let b = root.join("b.txt");
+        fs::write(&a, "a").unwrap();
+        fs::write(&b, "b").unwrap();
+
+        let mut recent = RecentStore::load_from(&temp.path().join("recent.json"), 10);
+        recent.record(&a);
+        recent.record(&b);
+        let mut bookmarks = BookmarkStore::default();
+        bookmarks.set(2, root.clone());
+        Session::new(&root, &HashSet::from([a.clone()]), Some(&a))
+            .save(&root)
+            .unwrap();
+        Session::new(&root, &HashSet::from([b.clone()]), None)
+            .save_named(&root, Some("ai"))
+            .unwrap();
+
+        let export = temp.path().join("state.json");
+        StateFile::collect(&recent, &bookmarks, &root)
+            .unwrap()
+            .write(&export)
+            .unwrap();
+
+        // Import into an empty machine state
+        Session::delete(&root).unwrap();
+        Session::delete_named(&root, Some("ai")).unwrap();
+        let mut fresh = RecentStore::load_from(&temp.path().join("other.json"), 10);
+        let mut fresh_bookmarks = BookmarkStore::default();
+        let summary = StateFile::read(&export)
+            .unwrap()
+            .apply(&mut fresh, &mut fresh_bookmarks, false)
+            .unwrap();
+
+        assert_eq!(
+            summary,
+            ImportSummary {
+                recent: 2,
+                bookmarks: 1,
+                sessions: 2,
+                skipped: 0
+            }
+        );
+        assert_eq!(fresh_bookmarks.get(2), Some(&root));
+        let paths: Vec<&Path> = fresh.entries().iter().map(|e| e.path.as_path()).collect();
+        assert_eq!(paths, vec![b.as_path(), a.as_path()]);
+        assert_eq!(
+            Session::list_names(&root).unwrap(),
+            vec![None, Some("ai".to_string())]
+        );
+        let (selected, focus) = Session::load(&root).unwrap().to_absolute_paths(&root);
+        assert_eq!(selected, HashSet::from([a.clone()]));
+        assert_eq!(focus, Some(a));
+    }
+
+    #[test]
+    fn test_import_merges_unless_replace() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let a = root.join("a.txt");
+        let b = root.join("b.txt");
+        fs::write(&a, "a").unwrap();
+        fs::write(&b, "b").unwrap();
+
+        let state = StateFile {
+            version: STATE_FILE_VERSION,
+            note: String::new(),
+            recent: vec![RecentEntry {
+                path: a.clone(),
+                timestamp: 5,
+            }],
+            bookmarks: BTreeMap::new(),
+            sessions: vec![NamedSession {
+                name: None,
+                session: Session::new(root, &HashSet::from([a.clone()]), None),
+            }],
+        };
+        Session::new(root, &HashSet::from([b.clone()]), None)
+            .save(root)
+            .unwrap();
+        let mut recent = RecentStore::new(10);
+        recent.merge(&[RecentEntry {
+            path: b.clone(),
+            timestamp: 9,
+        }]);
+
+        let mut bookmarks = BookmarkStore::default();
+        state
+            .clone()
+            .apply(&mut recent, &mut bookmarks, false)
+            .unwrap();
+        assert_eq!(recent.len(), 2);
+        let session = Session::load(root).unwrap();
+        assert_eq!(session.selected_paths.len(), 2);
+
+        state.apply(&mut recent, &mut bookmarks, true).unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent.get(0).unwrap().path, a);
+        let session = Session::load(root).unwrap();
+        assert_eq!(session.selected_paths, vec!["a.txt".to_string()]);
+    }
+
+    #[test]
+    fn test_import_skips_missing_roots() {
+        let temp = TempDir::new().unwrap();
+        let mut session = Session::new(temp.path(), &HashSet::new(), None);
+        session.root = temp.path().join("gone").display().to_string();
+        let state = StateFile {
+            version: STATE_FILE_VERSION,
+            note: String::new(),
+            recent: Vec::new(),
+            bookmarks: BTreeMap::new(),
+            sessions: vec![NamedSession {
+                name: None,
+                session,
+            }],
+        };
+
+        let summary = state
+            .apply(
+                &mut RecentStore::new(10),
+                &mut BookmarkStore::default(),
+                false,
+            )
+            .unwrap();
+        assert_eq!(summary.sessions, 0);
+        assert_eq!(summary.skipped, 1);
+    }
+}
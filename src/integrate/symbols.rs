@@ -0,0 +1,189 @@
+//! Workspace symbol index (--symbols)
+//!
+//! Lists the top-level symbols (functions, types, classes, ...) of every
+//! Rust, Python, JavaScript/TypeScript, Go and Java/Kotlin file under a
+//! directory as one JSON array. Symbols come from the same patterns as the
+//! MCP `get_file_symbols` tool; the file list skips gitignored and
+//! `.fvignore`d files like `--concat` does, down to a depth cap.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::concat::list_files;
+use crate::mcp::handlers::analysis::extract_symbols;
+use crate::tree::FvIgnore;
+
+/// Directory levels searched when no `--depth` is given
+pub const SYMBOLS_DEFAULT_DEPTH: usize = 16;
+
+/// Files larger than this are not parsed
+const MAX_SOURCE_BYTES: u64 = 1024 * 1024;
+
+/// One top-level symbol
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorkspaceSymbol {
+    pub name: String,
+    /// Symbol kind (`function`, `struct`, `class`, ...)
+    pub kind: &'static str,
+    /// File relative to the root
+    pub path: String,
+    /// 1-based line of the definition
+    pub line: usize,
+}
+
+/// Extensions with symbol patterns
+fn is_supported(ext: &str) -> bool {
+    matches!(
+        ext,
+        "rs" | "py" | "ts" | "tsx" | "js" | "jsx" | "go" | "java" | "kt"
+    )
+}
+
+/// Top-level symbols of one file's `content`, with `path` as given
+///
+/// Definitions indented under another (methods, nested items) are left out.
+pub fn file_symbols(content: &str, ext: &str, path: &str) -> Vec<WorkspaceSymbol> {
+    let lines: Vec<&str> = content.lines().collect();
+    extract_symbols(content, ext)
+        .into_iter()
+        .filter(|symbol| {
+            lines
+                .get(symbol.line - 1)
+                .is_some_and(|line| !line.starts_with(char::is_whitespace))
+        })
+        .map(|symbol| WorkspaceSymbol {
+            name: symbol.name,
+            kind: symbol.kind.as_str(),
+            path: path.to_string(),
+            line: symbol.line,
+        })
+        .collect()
+}
+
+/// Symbols of the source files under `root`, at most `max_depth` levels down
+///
+/// Files are visited in path order; unreadable files are skipped.
+pub fn build_symbol_index(root: &Path, max_depth: usize) -> Vec<WorkspaceSymbol> {
+    let ignore = FvIgnore::load(root);
+    let mut symbols = Vec::new();
+    // A file directly in the root is at depth 0
+    for path in list_files(root, Some(max_depth)) {
+        let Ok(rel) = path.strip_prefix(root) else {
+            continue;
+        };
+        let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+            continue;
+        };
+        if !is_supported(ext) || is_excluded(&ignore, root, &path) {
+            continue;
+        }
+        if fs::metadata(&path).map_or(true, |meta| meta.len() > MAX_SOURCE_BYTES) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let rel = rel.to_string_lossy().replace('\\', "/");
+        symbols.extend(file_symbols(&content, ext, &rel));
+    }
+    symbols
+}
+
+/// Whether `path` or a directory above it (below `root`) is ignored
+fn is_excluded(ignore: &FvIgnore, root: &Path, path: &Path) -> bool {
+    if ignore.is_empty() {
+        return false;
+    }
+    ignore.is_ignored(path, false)
+        || path
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| *dir != root)
+            .any(|dir| ignore.is_ignored(dir, true))
+}
+
+/// Print the symbol index of `root` to stdout as a JSON array
+pub fn output_symbols(root: &Path, max_depth: usize) -> io::Result<()> {
+    let symbols = build_symbol_index(root, max_depth);
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    serde_json::to_writer(&mut handle, &symbols).map_err(io::Error::other)?;
+    writeln!(handle)?;
+    handle.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const RUST: &str = "\
+use std::fmt;
+
+pub struct Config {
+    name: String,
+}
+
+impl Config {
+    pub fn new() -> Self {
+        todo!()
+    }
+}
+
+fn main() {}
+";
+
+    #[test]
+    fn test_rust_top_level_symbols_with_lines() {
+        let symbols = file_symbols(RUST, "rs", "src/main.rs");
+        let found: Vec<(&str, &str, usize)> = symbols
+            .iter()
+            .map(|s| (s.kind, s.name.as_str(), s.line))
+            .collect();
+        // `Config::new` is a method, not a top-level symbol
+        assert_eq!(
+            found,
+            vec![("struct", "Config", 3), ("function", "main", 13)]
+        );
+        assert!(symbols.iter().all(|s| s.path == "src/main.rs"));
+    }
+
+    #[test]
+    fn test_index_walks_supported_files_within_depth() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("src/deep/er")).unwrap();
+        fs::write(root.join("src/lib.rs"), RUST).unwrap();
+        fs::write(
+            root.join("app.py"),
+            "class App:\n    def run(self):\n        pass\n",
+        )
+        .unwrap();
+        fs::write(root.join("web.ts"), "export function render() {}\n").unwrap();
+        fs::write(root.join("notes.txt"), "fn not_code() {}\n").unwrap();
+        fs::write(root.join("src/deep/er/far.rs"), "fn far() {}\n").unwrap();
+        fs::write(root.join("skipped.rs"), "fn skipped() {}\n").unwrap();
+        fs::write(root.join(".fvignore"), "skipped.rs\n").unwrap();
+
+        let names = |symbols: Vec<WorkspaceSymbol>| -> Vec<String> {
+            symbols
+                .into_iter()
+                .map(|s| format!("{}:{}:{}", s.path, s.line, s.name))
+                .collect()
+        };
+        assert_eq!(
+            names(build_symbol_index(root, 1)),
+            vec![
+                "app.py:1:App",
+                "src/lib.rs:3:Config",
+                "src/lib.rs:13:main",
+                "web.ts:1:render",
+            ]
+        );
+        assert!(names(build_symbol_index(root, SYMBOLS_DEFAULT_DEPTH))
+            .contains(&"src/deep/er/far.rs:1:far".to_string()));
+    }
+}
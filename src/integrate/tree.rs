@@ -1,10 +1,38 @@
 //! Tree output mode for CLI integration
 //!
-//! Outputs directory tree structure to stdout in a format suitable for AI tools.
+//! Outputs directory tree structure to stdout in a format suitable for AI tools,
+//! either as an ASCII tree or as nested JSON (`--tree --format json`).
 
+use std::collections::HashMap;
 use std::fs;
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+use ignore::WalkBuilder;
+use serde::Serialize;
+
+use crate::git::{FileStatus, GitStatus};
+
+/// A node of the JSON tree output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TreeJsonNode {
+    /// File name (the root uses its full display path)
+    pub name: String,
+    /// Path of the entry (root-joined)
+    pub path: String,
+    /// Entry type: "dir", "file", or "symlink"
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// File size in bytes (files only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Git status, omitted for clean files and outside repositories
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git: Option<&'static str>,
+    /// Child entries (directories only; empty when cut off by the depth limit)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<TreeJsonNode>>,
+}
 
 /// Output a directory tree to stdout
 ///
@@ -25,41 +53,138 @@ pub fn output_tree(root: &Path, max_depth: Option<usize>, show_hidden: bool) ->
     handle.flush()
 }
 
-/// Recursively print tree structure (public for MCP)
-pub fn print_tree_recursive_pub<W: Write>(
-    out: &mut W,
-    path: &Path,
-    prefix: &str,
+/// Output a directory tree to stdout as nested JSON
+///
+/// Git status is included when `root` is inside a git repository.
+pub fn output_tree_json(
+    root: &Path,
     max_depth: Option<usize>,
-    current_depth: usize,
     show_hidden: bool,
 ) -> io::Result<()> {
-    print_tree_recursive(out, path, prefix, max_depth, current_depth, show_hidden)
+    let git = GitStatus::detect(root);
+    let tree = build_tree_json(root, max_depth, show_hidden, git.as_ref());
+
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    serde_json::to_writer_pretty(&mut handle, &tree).map_err(io::Error::other)?;
+    writeln!(handle)?;
+    handle.flush()
 }
 
-/// Recursively print tree structure
-fn print_tree_recursive<W: Write>(
-    out: &mut W,
-    path: &Path,
-    prefix: &str,
+/// Build the JSON tree for `root`
+///
+/// Entries matched by `.gitignore` (and `.ignore`) files are left out.
+pub fn build_tree_json(
+    root: &Path,
     max_depth: Option<usize>,
-    current_depth: usize,
     show_hidden: bool,
-) -> io::Result<()> {
-    // Check depth limit
-    if let Some(max) = max_depth {
-        if current_depth >= max {
-            return Ok(());
+    git: Option<&GitStatus>,
+) -> TreeJsonNode {
+    let mut listing = list_unignored(root, max_depth, show_hidden);
+    let mut node = json_node(root, root.display().to_string(), git);
+    if node.children.is_some() {
+        node.children = Some(json_children(root, &mut listing, git));
+    }
+    node
+}
+
+/// Entries below `root` that are not gitignored, grouped by parent directory
+fn list_unignored(
+    root: &Path,
+    max_depth: Option<usize>,
+    show_hidden: bool,
+) -> HashMap<PathBuf, Vec<ignore::DirEntry>> {
+    let walker = WalkBuilder::new(root)
+        .hidden(!show_hidden)
+        .require_git(false)
+        .max_depth(max_depth)
+        .build();
+    let mut listing: HashMap<PathBuf, Vec<ignore::DirEntry>> = HashMap::new();
+    for entry in walker.flatten().filter(|e| e.depth() > 0) {
+        if let Some(parent) = entry.path().parent() {
+            listing.entry(parent.to_path_buf()).or_default().push(entry);
         }
     }
+    listing
+}
+
+/// Build a node without children (directories get an empty child list)
+fn json_node(path: &Path, name: String, git: Option<&GitStatus>) -> TreeJsonNode {
+    let meta = fs::symlink_metadata(path).ok();
+    let is_symlink = meta.as_ref().is_some_and(|m| m.file_type().is_symlink());
+    let is_dir = !is_symlink && meta.as_ref().is_some_and(|m| m.is_dir());
+
+    let kind = if is_symlink {
+        "symlink"
+    } else if is_dir {
+        "dir"
+    } else {
+        "file"
+    };
+    let size = match meta {
+        Some(ref m) if m.is_file() => Some(m.len()),
+        _ => None,
+    };
+
+    TreeJsonNode {
+        name,
+        path: path.display().to_string(),
+        kind,
+        size,
+        git: git.and_then(|g| git_status_name(g.get_status(path))),
+        children: is_dir.then(Vec::new),
+    }
+}
+
+/// Child nodes of `path` from the walk, directories first, then by name
+fn json_children(
+    path: &Path,
+    listing: &mut HashMap<PathBuf, Vec<ignore::DirEntry>>,
+    git: Option<&GitStatus>,
+) -> Vec<TreeJsonNode> {
+    let mut entries = listing.remove(path).unwrap_or_default();
+    let is_dir = |e: &ignore::DirEntry| e.file_type().is_some_and(|t| t.is_dir());
+    entries.sort_by(|a, b| {
+        is_dir(b)
+            .cmp(&is_dir(a))
+            .then_with(|| a.file_name().cmp(b.file_name()))
+    });
+
+    entries
+        .into_iter()
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let mut node = json_node(entry.path(), name, git);
+            if node.children.is_some() {
+                node.children = Some(json_children(entry.path(), listing, git));
+            }
+            node
+        })
+        .collect()
+}
+
+pub(crate) fn git_status_name(status: FileStatus) -> Option<&'static str> {
+    match status {
+        FileStatus::Modified => Some("modified"),
+        FileStatus::Added => Some("added"),
+        FileStatus::Untracked => Some("untracked"),
+        FileStatus::Deleted => Some("deleted"),
+        FileStatus::Renamed => Some("renamed"),
+        FileStatus::Ignored => Some("ignored"),
+        FileStatus::Conflict => Some("conflict"),
+        FileStatus::Clean => None,
+    }
+}
 
-    // Read directory entries
+/// Read directory entries, filtered by visibility and sorted (directories first)
+///
+/// Unreadable directories yield no entries.
+fn read_sorted_entries(path: &Path, show_hidden: bool) -> Vec<fs::DirEntry> {
     let entries = match fs::read_dir(path) {
         Ok(entries) => entries,
-        Err(_) => return Ok(()), // Skip unreadable directories
+        Err(_) => return Vec::new(),
     };
 
-    // Collect and sort entries
     let mut entries: Vec<_> = entries
         .filter_map(|e| e.ok())
         .filter(|e| {
@@ -82,6 +207,39 @@ fn print_tree_recursive<W: Write>(
         }
     });
 
+    entries
+}
+
+/// Recursively print tree structure (public for MCP)
+pub fn print_tree_recursive_pub<W: Write>(
+    out: &mut W,
+    path: &Path,
+    prefix: &str,
+    max_depth: Option<usize>,
+    current_depth: usize,
+    show_hidden: bool,
+) -> io::Result<()> {
+    print_tree_recursive(out, path, prefix, max_depth, current_depth, show_hidden)
+}
+
+/// Recursively print tree structure
+fn print_tree_recursive<W: Write>(
+    out: &mut W,
+    path: &Path,
+    prefix: &str,
+    max_depth: Option<usize>,
+    current_depth: usize,
+    show_hidden: bool,
+) -> io::Result<()> {
+    // Check depth limit
+    if let Some(max) = max_depth {
+        if current_depth >= max {
+            return Ok(());
+        }
+    }
+
+    let entries = read_sorted_entries(path, show_hidden);
+
     let count = entries.len();
     for (i, entry) in entries.into_iter().enumerate() {
         let is_last = i == count - 1;
@@ -178,4 +336,92 @@ mod tests {
         // Should contain tree connectors
         assert!(output.contains("├── ") || output.contains("└── "));
     }
+
+    #[test]
+    fn test_tree_json_shape() {
+        let temp = setup_test_dir();
+        fs::create_dir(temp.path().join("src/nested")).unwrap();
+        fs::write(temp.path().join("src/nested/deep.rs"), "").unwrap();
+
+        let tree = build_tree_json(temp.path(), None, false, None);
+        let value = serde_json::to_value(&tree).unwrap();
+        let sub = &value["children"][0];
+        let src = temp.path().join("src");
+
+        assert_eq!(value["type"], "dir");
+        assert_eq!(value["children"].as_array().unwrap().len(), 2);
+        assert_eq!(sub["name"], "src");
+        assert_eq!(sub["path"], src.display().to_string());
+        assert_eq!(sub["type"], "dir");
+        assert!(sub.get("size").is_none());
+
+        // Directories first, then files alphabetically
+        let names: Vec<&str> = sub["children"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["nested", "lib.rs", "main.rs"]);
+
+        let nested = &sub["children"][0];
+        assert_eq!(nested["children"][0]["name"], "deep.rs");
+        assert_eq!(nested["children"][0]["size"], 0);
+
+        let main = &sub["children"][2];
+        assert_eq!(main["type"], "file");
+        assert_eq!(main["size"], 12);
+        assert!(main.get("children").is_none());
+        assert!(main.get("git").is_none());
+    }
+
+    #[test]
+    fn test_tree_json_depth_limit_and_hidden() {
+        let temp = setup_test_dir();
+
+        let tree = build_tree_json(temp.path(), Some(1), false, None);
+        let src = &tree.children.as_ref().unwrap()[0];
+        assert_eq!(src.name, "src");
+        assert_eq!(src.children, Some(Vec::new()));
+        assert!(!tree
+            .children
+            .as_ref()
+            .unwrap()
+            .iter()
+            .any(|c| c.name == ".hidden"));
+
+        let tree = build_tree_json(temp.path(), Some(1), true, None);
+        assert!(tree
+            .children
+            .as_ref()
+            .unwrap()
+            .iter()
+            .any(|c| c.name == ".hidden"));
+    }
+
+    #[test]
+    fn test_tree_json_skips_gitignored() {
+        let temp = setup_test_dir();
+        fs::write(temp.path().join(".gitignore"), "target/\n*.log\n").unwrap();
+        fs::create_dir(temp.path().join("target")).unwrap();
+        fs::write(temp.path().join("target/out.bin"), "").unwrap();
+        fs::write(temp.path().join("src/debug.log"), "").unwrap();
+
+        let tree = build_tree_json(temp.path(), None, false, None);
+        let names: Vec<&str> = tree
+            .children
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["src", "Cargo.toml"]);
+        let src = &tree.children.as_ref().unwrap()[0];
+        assert!(!src
+            .children
+            .as_ref()
+            .unwrap()
+            .iter()
+            .any(|c| c.name == "debug.log"));
+    }
 }
@@ -0,0 +1,100 @@
+//! Layout adjustments remembered between runs
+//!
+//! Keeps what was changed live in the UI (the preview split resized with
+//! `Alt+=` / `Alt+-`, preview borders toggled with `Alt+b`) in
+//! `~/.config/fileview/ui-state.json`. A saved value takes precedence over
+//! the config file default at startup.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::ConfigFile;
+
+const UI_STATE_FILENAME: &str = "ui-state.json";
+
+/// Persisted layout values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UiLayout {
+    /// Preview share of the split in percent (None = not resized yet)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_split: Option<u16>,
+    /// Preview borders and titles (None = not toggled yet)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_borders: Option<bool>,
+}
+
+/// Layout values bound to the file they are saved in
+#[derive(Debug, Clone, Default)]
+pub struct UiState {
+    pub layout: UiLayout,
+    /// File the state is persisted to (None = in-memory only)
+    file: Option<PathBuf>,
+}
+
+impl UiState {
+    /// Default location in the config directory
+    pub fn default_path() -> Option<PathBuf> {
+        ConfigFile::config_dir().map(|p| p.join(UI_STATE_FILENAME))
+    }
+
+    /// Load the state from the config directory
+    pub fn load() -> Self {
+        match Self::default_path() {
+            Some(path) => Self::load_from(&path),
+            None => Self::default(),
+        }
+    }
+
+    /// Load the state from a file
+    ///
+    /// A missing or unreadable file yields default values bound to `path`.
+    pub fn load_from(path: &Path) -> Self {
+        let layout = fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Self {
+            layout,
+            file: Some(path.to_path_buf()),
+        }
+    }
+
+    /// Save the state (no-op for in-memory state)
+    pub fn save(&self) -> io::Result<()> {
+        let Some(ref path) = self.file else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&self.layout)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_save_and_load_preview_split() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("nested").join(UI_STATE_FILENAME);
+
+        let mut state = UiState::load_from(&path);
+        assert_eq!(state.layout.preview_split, None);
+        state.layout.preview_split = Some(70);
+        state.save().unwrap();
+
+        assert_eq!(UiState::load_from(&path).layout.preview_split, Some(70));
+
+        // Garbage resets to defaults
+        fs::write(&path, "not json").unwrap();
+        assert_eq!(UiState::load_from(&path).layout, UiLayout::default());
+    }
+}
@@ -0,0 +1,313 @@
+//! Live CLI output (`--tree --watch`, `--context --watch`)
+//!
+//! Emits the output once, then again after every (debounced) change below
+//! the root. Events only count when the size, time or set of entries in a
+//! watched directory changed: reading files and directories also raises
+//! watcher events, and the outputs do both. Only the directories an event
+//! names are fingerprinted again. On a terminal the screen is cleared
+//! between emissions; when piped, each new emission follows a `---`
+//! separator line. The loop runs until interrupted (Ctrl-C) or until stdout
+//! is closed.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::core::EXCLUDED_DIRS;
+use crate::watcher::FileWatcher;
+
+/// Quiet time after a change before the output is emitted again
+pub const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watches beyond this many directories are not added (inotify limits)
+pub const MAX_WATCHED_DIRS: usize = 4096;
+
+/// How often the loop wakes up while nothing changes
+const WAIT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Separator written before each emission after the first
+pub fn emission_separator(is_terminal: bool) -> &'static str {
+    if is_terminal {
+        // Clear the screen and move home
+        "\x1b[2J\x1b[H"
+    } else {
+        "---\n"
+    }
+}
+
+/// `root` and the directories below it, down to `max_depth` levels
+///
+/// Skips the directories the TUI watcher skips (`.git`, `target`, ...).
+pub fn watch_dirs(root: &Path, max_depth: Option<usize>) -> Vec<PathBuf> {
+    let mut dirs = vec![root.to_path_buf()];
+    let mut level = vec![root.to_path_buf()];
+    let mut depth = 1;
+    while !level.is_empty() && max_depth.is_none_or(|max| depth < max) {
+        let mut next: Vec<PathBuf> = level.iter().flat_map(|dir| child_dirs(dir)).collect();
+        if dirs.len() + next.len() > MAX_WATCHED_DIRS {
+            next.truncate(MAX_WATCHED_DIRS - dirs.len());
+            dirs.extend(next);
+            break;
+        }
+        dirs.extend(next.iter().cloned());
+        level = next;
+        depth += 1;
+    }
+    dirs
+}
+
+/// Subdirectories of `dir` that are watched (not in [`EXCLUDED_DIRS`])
+fn child_dirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|entry| !EXCLUDED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()))
+        .map(|entry| entry.path())
+        .collect()
+}
+
+/// Hash of the names, sizes and modification times of the entries in `dir`
+/// (None once it cannot be read)
+fn fingerprint(dir: &Path) -> Option<u64> {
+    let mut entries: Vec<_> = fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| {
+            let meta = entry.metadata().ok();
+            let len = meta.as_ref().map(|m| m.len());
+            let modified = meta.and_then(|m| m.modified().ok());
+            (entry.file_name(), len, modified)
+        })
+        .collect();
+    entries.sort();
+    let mut hasher = DefaultHasher::new();
+    entries.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// Fingerprint every directory in `dirs`
+fn fingerprint_all(dirs: Vec<PathBuf>) -> HashMap<PathBuf, u64> {
+    dirs.into_iter()
+        .filter_map(|dir| fingerprint(&dir).map(|print| (dir, print)))
+        .collect()
+}
+
+/// Re-fingerprint the watched directories named by `changed` (or holding a
+/// changed path); returns whether any of them differ
+///
+/// Directories created in a changed directory start being tracked (down to
+/// `max_depth` below `root`), and directories that are gone are dropped.
+fn refresh(
+    prints: &mut HashMap<PathBuf, u64>,
+    root: &Path,
+    max_depth: Option<usize>,
+    changed: &[PathBuf],
+) -> bool {
+    let touched: BTreeSet<&Path> = changed
+        .iter()
+        .flat_map(|path| [Some(path.as_path()), path.parent()])
+        .flatten()
+        .filter(|dir| prints.contains_key(*dir))
+        .collect();
+    let mut differs = false;
+    for dir in touched {
+        let Some(print) = fingerprint(dir) else {
+            differs = true;
+            prints.retain(|tracked, _| !tracked.starts_with(dir));
+            continue;
+        };
+        if prints.get(dir) == Some(&print) {
+            continue;
+        }
+        differs = true;
+        prints.insert(dir.to_path_buf(), print);
+
+        // `watch_dirs` keeps directories less than `max_depth` levels down
+        let child_depth = dir
+            .strip_prefix(root)
+            .map_or(0, |rel| rel.components().count())
+            + 1;
+        let Some(remaining) = max_depth.map_or(Some(None), |max| {
+            max.checked_sub(child_depth).filter(|&n| n > 0).map(Some)
+        }) else {
+            continue;
+        };
+        for child in child_dirs(dir) {
+            if prints.contains_key(&child) {
+                continue;
+            }
+            for sub in watch_dirs(&child, remaining) {
+                if prints.len() >= MAX_WATCHED_DIRS {
+                    break;
+                }
+                if let Some(print) = fingerprint(&sub) {
+                    prints.insert(sub, print);
+                }
+            }
+        }
+    }
+    differs
+}
+
+/// Call `emit` now and after every change below `root`
+///
+/// `emit` gets the number of the emission (0 for the first) and returns
+/// whether to keep watching. A closed stdout ends the loop without error.
+pub fn run_watch(
+    root: &Path,
+    max_depth: Option<usize>,
+    debounce: Duration,
+    mut emit: impl FnMut(usize) -> io::Result<bool>,
+) -> anyhow::Result<()> {
+    let mut watcher = FileWatcher::with_debounce(root, debounce)?;
+    let dirs = watch_dirs(root, max_depth);
+    // Watch new directories before emitting, so no change is missed
+    watcher.sync_with_expanded(&dirs);
+    let mut prints = fingerprint_all(dirs);
+    let mut emission = 0;
+    loop {
+        match emit(emission) {
+            Ok(true) => {}
+            Ok(false) => return Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            Err(e) => return Err(e.into()),
+        }
+        emission += 1;
+
+        loop {
+            let Some(paths) = watcher.wait_paths(WAIT_INTERVAL) else {
+                // Events were lost: start over from a fresh scan
+                let dirs = watch_dirs(root, max_depth);
+                watcher.sync_with_expanded(&dirs);
+                prints = fingerprint_all(dirs);
+                break;
+            };
+            if refresh(&mut prints, root, max_depth, &paths) {
+                let dirs: Vec<PathBuf> = prints.keys().cloned().collect();
+                watcher.sync_with_expanded(&dirs);
+                break;
+            }
+        }
+    }
+}
+
+/// Run `output` under [`run_watch`], separating emissions on stdout
+pub fn watch_stdout(
+    root: &Path,
+    max_depth: Option<usize>,
+    mut output: impl FnMut() -> io::Result<()>,
+) -> anyhow::Result<()> {
+    use std::io::IsTerminal;
+
+    let separator = emission_separator(io::stdout().is_terminal());
+    run_watch(root, max_depth, WATCH_DEBOUNCE, |emission| {
+        if emission > 0 {
+            let mut stdout = io::stdout();
+            stdout.write_all(separator.as_bytes())?;
+            stdout.flush()?;
+        }
+        output()?;
+        Ok(true)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_watch_dirs_respects_depth_and_exclusions() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("src/deep/deeper")).unwrap();
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::write(root.join("file.txt"), "").unwrap();
+
+        let mut all = watch_dirs(root, None);
+        all.sort();
+        assert_eq!(
+            all,
+            vec![
+                root.to_path_buf(),
+                root.join("src"),
+                root.join("src/deep"),
+                root.join("src/deep/deeper"),
+            ]
+        );
+        assert_eq!(watch_dirs(root, Some(2)).len(), 2);
+    }
+
+    #[test]
+    fn test_change_triggers_second_emission() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        fs::create_dir(root.join("sub")).unwrap();
+
+        let (tx, rx) = channel();
+        let watched = root.clone();
+        std::thread::spawn(move || {
+            let result = run_watch(&watched, None, Duration::from_millis(50), |emission| {
+                let _ = tx.send(emission);
+                if emission == 0 {
+                    // A change in a subdirectory, after the watches are set
+                    fs::write(watched.join("sub").join("new.txt"), "x")?;
+                }
+                Ok(emission == 0)
+            });
+            result.unwrap();
+        });
+
+        let timeout = Duration::from_secs(10);
+        assert_eq!(rx.recv_timeout(timeout), Ok(0));
+        assert_eq!(rx.recv_timeout(timeout), Ok(1));
+    }
+
+    #[test]
+    fn test_refresh_only_reports_real_changes() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir(root.join("sub")).unwrap();
+        let mut prints = fingerprint_all(watch_dirs(root, None));
+
+        // Reading a directory names it, but nothing changed
+        assert!(!refresh(&mut prints, root, None, &[root.join("sub")]));
+        // Paths outside the watched directories are ignored
+        assert!(!refresh(
+            &mut prints,
+            root,
+            None,
+            &[PathBuf::from("/elsewhere/x")]
+        ));
+
+        // A new directory (and what is below it) starts being tracked
+        fs::create_dir_all(root.join("sub/new/inner")).unwrap();
+        assert!(refresh(&mut prints, root, None, &[root.join("sub/new")]));
+        assert!(prints.contains_key(&root.join("sub/new/inner")));
+        // ...but not past the depth limit
+        let mut shallow = fingerprint_all(watch_dirs(root, Some(2)));
+        fs::create_dir(root.join("other")).unwrap();
+        assert!(refresh(&mut shallow, root, Some(2), &[root.join("other")]));
+        assert!(shallow.contains_key(&root.join("other")));
+        assert!(!shallow.contains_key(&root.join("sub/new")));
+
+        // A removed directory is dropped with everything below it
+        fs::remove_dir_all(root.join("sub/new")).unwrap();
+        assert!(refresh(&mut prints, root, None, &[root.join("sub/new")]));
+        assert!(!prints.contains_key(&root.join("sub/new/inner")));
+    }
+
+    #[test]
+    fn test_emission_separator() {
+        assert_eq!(emission_separator(false), "---\n");
+        assert!(emission_separator(true).starts_with("\x1b[2J"));
+    }
+}
@@ -11,11 +11,13 @@ use crossterm::{
 };
 use ratatui::prelude::*;
 
-use fileview::app::{run_app, Config, InitAction, PluginAction, SessionAction};
+use fileview::app::{run_app, Config, InitAction, PluginAction, SessionAction, StateAction};
 use fileview::integrate::{
     claude_init, collect_related_candidates, collect_related_paths, exit_code, load_session,
-    load_session_named, output_context, output_context_pack_with_options, output_paths,
-    output_tree, plugin_init, plugin_test, run_ai_benchmark, Session,
+    load_session_named, output_concat, output_context, output_context_pack_with_options,
+    output_paths, output_symbols, output_tree, output_tree_json, plugin_init, plugin_test,
+    run_ai_benchmark, watch_stdout, BookmarkStore, RecentStore, Session, StateFile,
+    SYMBOLS_DEFAULT_DEPTH,
 };
 use fileview::render::create_image_picker;
 
@@ -46,6 +48,14 @@ fn main() -> ExitCode {
         return run_context_pack_mode(&config, preset);
     }
 
+    if config.concat_mode {
+        return run_concat_mode(&config);
+    }
+
+    if config.symbols_mode {
+        return run_symbols_mode(&config);
+    }
+
     if let Some(ref path) = config.select_related_path {
         return run_select_related_mode(path, config.explain_selection);
     }
@@ -63,6 +73,10 @@ fn main() -> ExitCode {
         return run_session_action(&config, action);
     }
 
+    if let Some(ref action) = config.state_action {
+        return run_state_action(&config, action);
+    }
+
     if let Some(action) = config.init_action {
         return run_init_action(&config, action);
     }
@@ -82,7 +96,19 @@ fn main() -> ExitCode {
 
 /// Run in tree output mode (non-interactive)
 fn run_tree_mode(config: &Config) -> ExitCode {
-    match output_tree(&config.root, config.tree_depth, config.show_hidden) {
+    let output = || {
+        if config.output_format == fileview::integrate::OutputFormat::Json {
+            output_tree_json(&config.root, config.tree_depth, config.show_hidden)
+        } else {
+            output_tree(&config.root, config.tree_depth, config.show_hidden)
+        }
+    };
+    let result = if config.watch_output {
+        watch_stdout(&config.root, config.tree_depth, output)
+    } else {
+        output().map_err(Into::into)
+    };
+    match result {
         Ok(_) => ExitCode::from(exit_code::SUCCESS as u8),
         Err(e) => {
             eprintln!("Error: {}", e);
@@ -93,7 +119,12 @@ fn run_tree_mode(config: &Config) -> ExitCode {
 
 /// Run in context output mode (non-interactive)
 fn run_context_mode(config: &Config) -> ExitCode {
-    match output_context(&config.root) {
+    let result = if config.watch_output {
+        watch_stdout(&config.root, None, || output_context(&config.root))
+    } else {
+        output_context(&config.root).map_err(Into::into)
+    };
+    match result {
         Ok(_) => ExitCode::from(exit_code::SUCCESS as u8),
         Err(e) => {
             eprintln!("Error: {}", e);
@@ -131,6 +162,29 @@ fn run_context_pack_mode(
     }
 }
 
+/// Run in concat output mode (non-interactive)
+fn run_concat_mode(config: &Config) -> ExitCode {
+    match output_concat(&config.root, &config.concat_options) {
+        Ok(_) => ExitCode::from(exit_code::SUCCESS as u8),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(exit_code::ERROR as u8)
+        }
+    }
+}
+
+/// Run in symbol index output mode (non-interactive)
+fn run_symbols_mode(config: &Config) -> ExitCode {
+    let depth = config.tree_depth.unwrap_or(SYMBOLS_DEFAULT_DEPTH);
+    match output_symbols(&config.root, depth) {
+        Ok(_) => ExitCode::from(exit_code::SUCCESS as u8),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(exit_code::ERROR as u8)
+        }
+    }
+}
+
 /// Run in related-file output mode (non-interactive)
 fn run_select_related_mode(path: &std::path::Path, explain: bool) -> ExitCode {
     if explain {
@@ -206,6 +260,52 @@ fn run_session_action(config: &Config, action: SessionAction) -> ExitCode {
     }
 }
 
+/// Run state file action (export/import)
+fn run_state_action(config: &Config, action: &StateAction) -> ExitCode {
+    let result = match action {
+        StateAction::Export(path) => {
+            StateFile::collect(&RecentStore::load(), &BookmarkStore::load(), &config.root).and_then(
+                |state| {
+                    state.write(path)?;
+                    println!(
+                        "Exported {} recent file(s), {} bookmark(s) and {} session(s) to {}",
+                        state.recent.len(),
+                        state.bookmarks.len(),
+                        state.sessions.len(),
+                        path.display()
+                    );
+                    Ok(())
+                },
+            )
+        }
+        StateAction::Import { path, replace } => StateFile::read(path).and_then(|state| {
+            let mut recent = RecentStore::load();
+            let mut bookmarks = BookmarkStore::load();
+            let summary = state.apply(&mut recent, &mut bookmarks, *replace)?;
+            recent.save()?;
+            bookmarks.save()?;
+            println!(
+                "Imported {} session(s) and {} bookmark(s); {} recent file(s) remembered",
+                summary.sessions, summary.bookmarks, summary.recent
+            );
+            if summary.skipped > 0 {
+                println!(
+                    "  Skipped {} session(s) whose root does not exist here",
+                    summary.skipped
+                );
+            }
+            Ok(())
+        }),
+    };
+    match result {
+        Ok(()) => ExitCode::from(exit_code::SUCCESS as u8),
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::from(exit_code::ERROR as u8)
+        }
+    }
+}
+
 /// Resume named AI session and print selected files.
 fn run_resume_ai_session(config: &Config, name: &str) -> ExitCode {
     match load_session_named(&config.root, Some(name)) {
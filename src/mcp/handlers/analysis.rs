@@ -27,7 +27,7 @@ pub enum SymbolKind {
 }
 
 impl SymbolKind {
-    fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &'static str {
         match self {
             Self::Function => "function",
             Self::Class => "class",
@@ -1,6 +1,6 @@
 //! Dependency analysis handlers
 //!
-//! Implements get_dependency_graph, get_import_tree, find_circular_deps.
+//! Implements get_dependency_graph, get_import_tree, file_imports, find_circular_deps.
 
 use std::collections::{HashMap, HashSet};
 use std::fs;
@@ -109,6 +109,67 @@ pub fn get_import_tree(root: &Path, path: &str) -> ToolCallResult {
     success_result(result)
 }
 
+/// List the files and modules a source file imports
+///
+/// Local imports are resolved to repo-relative paths. Imports of packages
+/// outside the repo are reported by their bare name and flagged external.
+pub fn file_imports(root: &Path, path: &str) -> ToolCallResult {
+    let canonical = match validate_path(root, path) {
+        Ok(p) => p,
+        Err(e) => return error_result(&e.to_string()),
+    };
+
+    if canonical.is_dir() {
+        return error_result("Path must be a file, not a directory");
+    }
+
+    let ext = canonical.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let language = match ImportLanguage::from_extension(ext) {
+        Some(language) => language,
+        None => {
+            return error_result(
+                "Unsupported file type (supported: Rust, Python, JavaScript, TypeScript)",
+            )
+        }
+    };
+
+    let content = match fs::read_to_string(&canonical) {
+        Ok(c) => c,
+        Err(e) => return error_result(&format!("Failed to read file: {}", e)),
+    };
+
+    let mut imports: Vec<FileImport> = Vec::new();
+    for spec in language.parse(&content) {
+        let import = language.resolve(root, &canonical, &spec);
+        let duplicate = imports
+            .iter()
+            .any(|existing| match (&existing.path, &import.path) {
+                (Some(a), Some(b)) => a == b,
+                (None, None) => existing.module == import.module,
+                _ => false,
+            });
+        if !duplicate {
+            imports.push(import);
+        }
+    }
+
+    if imports.is_empty() {
+        return success_result(format!("No imports found in {}", path));
+    }
+
+    let mut result = format!("Imports for {} ({}):\n", path, imports.len());
+    for import in &imports {
+        let line = match (&import.path, import.external) {
+            (Some(resolved), _) => format!("  {} -> {}\n", import.module, resolved.display()),
+            (None, true) => format!("  {} (external)\n", import.module),
+            (None, false) => format!("  {} (unresolved)\n", import.module),
+        };
+        result.push_str(&line);
+    }
+
+    success_result(result)
+}
+
 /// Find circular dependencies
 pub fn find_circular_deps(root: &Path, path: Option<&str>) -> ToolCallResult {
     let start_path = match path {
@@ -470,3 +531,592 @@ fn resolve_go_import(_root: &Path, _import: &str) -> Option<PathBuf> {
     // Local imports would need module path resolution
     None
 }
+
+/// An import of a source file, as reported by `file_imports`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileImport {
+    /// Module as written (bare package name for external imports)
+    module: String,
+    /// Repo-relative path of the imported file
+    path: Option<PathBuf>,
+    /// Import refers to a package outside the repo
+    external: bool,
+}
+
+impl FileImport {
+    fn local(root: &Path, module: &str, path: Option<PathBuf>) -> Self {
+        Self {
+            module: module.to_string(),
+            path: path.and_then(|p| repo_relative(root, &p)),
+            external: false,
+        }
+    }
+
+    fn external(module: &str) -> Self {
+        Self {
+            module: module.to_string(),
+            path: None,
+            external: true,
+        }
+    }
+}
+
+/// Languages supported by `file_imports`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImportLanguage {
+    Rust,
+    Python,
+    JavaScript,
+}
+
+impl ImportLanguage {
+    fn from_extension(ext: &str) -> Option<Self> {
+        match ext {
+            "rs" => Some(Self::Rust),
+            "py" => Some(Self::Python),
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" => Some(Self::JavaScript),
+            _ => None,
+        }
+    }
+
+    /// Extract import specifiers from source code
+    fn parse(self, content: &str) -> Vec<String> {
+        match self {
+            Self::Rust => parse_rust_imports(content),
+            Self::Python => parse_python_imports(content),
+            Self::JavaScript => parse_js_imports(content),
+        }
+    }
+
+    /// Resolve an import specifier to a file in the repo
+    fn resolve(self, root: &Path, source: &Path, spec: &str) -> FileImport {
+        match self {
+            Self::Rust => resolve_rust_spec(root, source, spec),
+            Self::Python => resolve_python_spec(root, source, spec),
+            Self::JavaScript => resolve_js_spec(root, source, spec),
+        }
+    }
+}
+
+/// Canonicalize a path and strip the root (None if outside the root)
+fn repo_relative(root: &Path, path: &Path) -> Option<PathBuf> {
+    let canonical = path.canonicalize().ok()?;
+    canonical.strip_prefix(root).ok().map(Path::to_path_buf)
+}
+
+fn first_existing(candidates: &[PathBuf]) -> Option<PathBuf> {
+    candidates.iter().find(|c| c.is_file()).cloned()
+}
+
+/// Extract Rust `use` paths and `mod` declarations
+///
+/// Use trees are expanded (`use a::{b, c}` yields `a::b` and `a::c`) and
+/// `mod name;` is reported as `self::name`.
+fn parse_rust_imports(content: &str) -> Vec<String> {
+    let mut imports = Vec::new();
+    let mut statement: Option<String> = None;
+
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        let text = match statement.take() {
+            Some(pending) => format!("{} {}", pending, line),
+            None => {
+                let item = strip_rust_visibility(line);
+                if let Some(name) = item.strip_prefix("mod ").and_then(|r| r.strip_suffix(';')) {
+                    imports.push(format!("self::{}", name.trim()));
+                    continue;
+                }
+                match item.strip_prefix("use ") {
+                    Some(rest) => rest.to_string(),
+                    None => continue,
+                }
+            }
+        };
+
+        match text.split_once(';') {
+            Some((tree, _)) => imports.extend(expand_use_tree(tree)),
+            None => statement = Some(text),
+        }
+    }
+
+    dedup_preserving_order(imports)
+}
+
+/// Strip a leading `pub`, `pub(crate)`, etc.
+fn strip_rust_visibility(line: &str) -> &str {
+    let Some(rest) = line.strip_prefix("pub") else {
+        return line;
+    };
+    let rest = rest.trim_start();
+    match rest.strip_prefix('(') {
+        Some(inner) => inner.split_once(')').map_or(rest, |(_, r)| r.trim_start()),
+        None if rest.len() < line.len() - 3 => rest,
+        None => line,
+    }
+}
+
+/// Expand a use tree into flat paths
+fn expand_use_tree(tree: &str) -> Vec<String> {
+    let tree: String = tree.split_whitespace().collect::<Vec<_>>().join(" ");
+    let tree = tree.replace(" ::", "::").replace(":: ", "::");
+
+    let Some(open) = tree.find('{') else {
+        let path = tree.split(" as ").next().unwrap_or("").trim();
+        let path = path.trim_end_matches("::*");
+        return if path.is_empty() || path == "*" {
+            Vec::new()
+        } else {
+            vec![path.to_string()]
+        };
+    };
+
+    let prefix = tree[..open].trim_end_matches("::");
+    let close = tree.rfind('}').unwrap_or(tree.len());
+    let inner = &tree[open + 1..close.max(open + 1)];
+
+    let mut paths = Vec::new();
+    for item in split_top_level(inner) {
+        for path in expand_use_tree(item) {
+            paths.push(match (prefix.is_empty(), path.as_str()) {
+                (true, _) => path,
+                (false, "self") => prefix.to_string(),
+                (false, _) => format!("{}::{}", prefix, path),
+            });
+        }
+    }
+    if paths.is_empty() && !prefix.is_empty() {
+        // `use a::{*}` or an empty group still imports the module
+        paths.push(prefix.to_string());
+    }
+    paths
+}
+
+/// Split on commas that are not nested inside braces
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Directory holding the children of the module defined by `source`
+fn rust_module_dir(source: &Path) -> PathBuf {
+    let parent = source.parent().unwrap_or(Path::new(""));
+    match source.file_stem().and_then(|s| s.to_str()) {
+        Some("mod" | "lib" | "main") | None => parent.to_path_buf(),
+        Some(stem) => parent.join(stem),
+    }
+}
+
+/// Crate source directory for a file (nearest `src` ancestor)
+fn rust_crate_dir(root: &Path, source: &Path) -> PathBuf {
+    source
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(root))
+        .find(|dir| dir.file_name().is_some_and(|n| n == "src"))
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| root.join("src"))
+}
+
+/// Find the file of the longest module prefix of `segments` under `dir`
+fn find_rust_module(dir: &Path, segments: &[&str]) -> Option<PathBuf> {
+    for n in (1..=segments.len()).rev() {
+        let base = segments[..n - 1]
+            .iter()
+            .fold(dir.to_path_buf(), |p, s| p.join(s));
+        let name = segments[n - 1];
+        let found = first_existing(&[
+            base.join(format!("{}.rs", name)),
+            base.join(name).join("mod.rs"),
+        ]);
+        if found.is_some() {
+            return found;
+        }
+    }
+
+    // The path names the module itself (`use super::*`, `use crate::Item`)
+    let module_file = dir.with_extension("rs");
+    first_existing(&[
+        dir.join("mod.rs"),
+        module_file,
+        dir.join("lib.rs"),
+        dir.join("main.rs"),
+    ])
+}
+
+fn resolve_rust_spec(root: &Path, source: &Path, spec: &str) -> FileImport {
+    let segments: Vec<&str> = spec.split("::").collect();
+    let module_dir = rust_module_dir(source);
+
+    let path = match segments.first().copied() {
+        // `use ::name` always refers to an external crate
+        Some("") => return FileImport::external(segments.get(1).copied().unwrap_or("")),
+        Some("crate") => find_rust_module(&rust_crate_dir(root, source), &segments[1..]),
+        Some("self") => find_rust_module(&module_dir, &segments[1..]),
+        Some("super") => {
+            let supers = segments.iter().take_while(|s| **s == "super").count();
+            let mut dir = module_dir;
+            for _ in 0..supers {
+                dir = dir.parent().map(Path::to_path_buf).unwrap_or(dir);
+            }
+            find_rust_module(&dir, &segments[supers..])
+        }
+        Some(name) => {
+            // A child module in scope, otherwise an external crate
+            let child = first_existing(&[
+                module_dir.join(format!("{}.rs", name)),
+                module_dir.join(name).join("mod.rs"),
+            ]);
+            match child {
+                Some(_) => find_rust_module(&module_dir, &segments),
+                None => return FileImport::external(name),
+            }
+        }
+        None => None,
+    };
+
+    FileImport::local(root, spec, path)
+}
+
+/// Extract Python `import` and `from ... import` modules
+///
+/// Relative imports keep their leading dots; `from . import a` yields `.a`.
+fn parse_python_imports(content: &str) -> Vec<String> {
+    let mut imports = Vec::new();
+
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if let Some(rest) = line.strip_prefix("import ") {
+            for module in rest.split(',') {
+                let module = module.split(" as ").next().unwrap_or("").trim();
+                if !module.is_empty() {
+                    imports.push(module.to_string());
+                }
+            }
+        } else if let Some(rest) = line.strip_prefix("from ") {
+            let Some((module, names)) = rest.split_once(" import ") else {
+                continue;
+            };
+            let module = module.trim();
+            if module.chars().all(|c| c == '.') {
+                // `from . import a, b` imports sibling modules
+                let names = names.trim_matches(|c: char| c == '(' || c == ')' || c == '\\');
+                for name in names.split(',') {
+                    let name = name.split(" as ").next().unwrap_or("").trim();
+                    if !name.is_empty() && name != "*" {
+                        imports.push(format!("{}{}", module, name));
+                    }
+                }
+            } else {
+                imports.push(module.to_string());
+            }
+        }
+    }
+
+    dedup_preserving_order(imports)
+}
+
+fn python_module_candidates(base: &Path, dotted: &str) -> [PathBuf; 2] {
+    let path = dotted.split('.').fold(base.to_path_buf(), |p, s| p.join(s));
+    [path.with_extension("py"), path.join("__init__.py")]
+}
+
+fn resolve_python_spec(root: &Path, source: &Path, spec: &str) -> FileImport {
+    let dots = spec.chars().take_while(|c| *c == '.').count();
+    let dotted = &spec[dots..];
+    let source_dir = source.parent().unwrap_or(root);
+
+    if dots > 0 {
+        let mut base = source_dir.to_path_buf();
+        for _ in 1..dots {
+            base = base.parent().map(Path::to_path_buf).unwrap_or(base);
+        }
+        let path = if dotted.is_empty() {
+            first_existing(&[base.join("__init__.py")])
+        } else {
+            first_existing(&python_module_candidates(&base, dotted))
+        };
+        return FileImport::local(root, spec, path);
+    }
+
+    // Absolute imports: repo root, the importing file's directory, then `src/`
+    let bases = [
+        root.to_path_buf(),
+        source_dir.to_path_buf(),
+        root.join("src"),
+    ];
+    let path = bases
+        .iter()
+        .find_map(|base| first_existing(&python_module_candidates(base, dotted)));
+    match path {
+        Some(path) => FileImport::local(root, spec, Some(path)),
+        None => FileImport::external(dotted.split('.').next().unwrap_or(dotted)),
+    }
+}
+
+/// Extract JS/TS `import`/`export ... from` and `require()` specifiers
+fn parse_js_imports(content: &str) -> Vec<String> {
+    let mut imports = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with("//") || line.starts_with('*') || line.starts_with("/*") {
+            continue;
+        }
+
+        let is_module_statement =
+            line.starts_with("import ") || line.starts_with("export ") || line.starts_with('}');
+        if is_module_statement {
+            if let Some(idx) = line.rfind(" from ") {
+                imports.extend(quoted_prefix(&line[idx + 6..]));
+            } else if let Some(rest) = line.strip_prefix("import ") {
+                // Side-effect import: `import './styles.css'`
+                imports.extend(quoted_prefix(rest));
+            }
+        }
+
+        for call in ["require(", "import("] {
+            let mut rest = line;
+            while let Some(idx) = rest.find(call) {
+                rest = &rest[idx + call.len()..];
+                imports.extend(quoted_prefix(rest));
+            }
+        }
+    }
+
+    dedup_preserving_order(imports)
+}
+
+/// Parse a leading quoted string literal
+fn quoted_prefix(s: &str) -> Option<String> {
+    let s = s.trim_start();
+    let quote = s.chars().next().filter(|c| matches!(c, '\'' | '"' | '`'))?;
+    let rest = &s[1..];
+    let end = rest.find(quote)?;
+    Some(rest[..end].to_string()).filter(|spec| !spec.is_empty())
+}
+
+fn resolve_js_spec(root: &Path, source: &Path, spec: &str) -> FileImport {
+    let base = if spec.starts_with('.') {
+        source.parent().unwrap_or(root).join(spec)
+    } else if let Some(absolute) = spec.strip_prefix('/') {
+        root.join(absolute)
+    } else {
+        // Bare specifier: package name (`@scope/pkg` or `pkg`)
+        let mut parts = spec.split('/');
+        let first = parts.next().unwrap_or(spec);
+        let package = match (first.starts_with('@'), parts.next()) {
+            (true, Some(name)) => format!("{}/{}", first, name),
+            _ => first.to_string(),
+        };
+        return FileImport::external(&package);
+    };
+
+    let suffixes = [
+        "",
+        ".ts",
+        ".tsx",
+        ".js",
+        ".jsx",
+        ".mjs",
+        ".cjs",
+        "/index.ts",
+        "/index.tsx",
+        "/index.js",
+        "/index.jsx",
+    ];
+    let candidates: Vec<PathBuf> = suffixes
+        .iter()
+        .map(|suffix| PathBuf::from(format!("{}{}", base.display(), suffix)))
+        .collect();
+    FileImport::local(root, spec, first_existing(&candidates))
+}
+
+fn dedup_preserving_order(items: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    items
+        .into_iter()
+        .filter(|item| seen.insert(item.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_rust_imports() {
+        let source = r#"
+//! Module docs
+mod config;
+pub mod state;
+pub(crate) use crate::core::{AppState, mode::ViewMode};
+use std::io::{self, Read};
+use super::helper::*;
+use anyhow::Result as AnyResult;
+use crate::render::{
+    theme,
+    Picker,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+}
+"#;
+        assert_eq!(
+            parse_rust_imports(source),
+            vec![
+                "self::config",
+                "self::state",
+                "crate::core::AppState",
+                "crate::core::mode::ViewMode",
+                "std::io",
+                "std::io::Read",
+                "super::helper",
+                "anyhow::Result",
+                "crate::render::theme",
+                "crate::render::Picker",
+                "super",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_python_imports() {
+        let source = r#"
+import os
+import os.path as osp, sys
+from collections import OrderedDict
+from .models import User  # relative
+from . import utils, helpers as h
+from ..core.db import (
+    session,
+)
+"#;
+        assert_eq!(
+            parse_python_imports(source),
+            vec![
+                "os",
+                "os.path",
+                "sys",
+                "collections",
+                ".models",
+                ".utils",
+                ".helpers",
+                "..core.db",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_js_imports() {
+        let source = r#"
+import React from 'react';
+import { a, b } from "./util";
+import './styles.css';
+export { c } from '../lib/c';
+const fs = require('fs');
+const lazy = () => import("@scope/pkg/sub");
+"#;
+        assert_eq!(
+            parse_js_imports(source),
+            vec![
+                "react",
+                "./util",
+                "./styles.css",
+                "../lib/c",
+                "fs",
+                "@scope/pkg/sub",
+            ]
+        );
+    }
+
+    fn result_text(result: &ToolCallResult) -> String {
+        let value = serde_json::to_value(&result.content[0]).unwrap();
+        value["text"].as_str().unwrap_or_default().to_string()
+    }
+
+    #[test]
+    fn test_file_imports_resolves_rust_modules() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("src/app")).unwrap();
+        fs::write(
+            root.join("src/main.rs"),
+            "mod app;\nuse crate::app::config::Config;\nuse anyhow::Result;\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/app/mod.rs"), "pub mod config;\n").unwrap();
+        fs::write(root.join("src/app/config.rs"), "use super::*;\n").unwrap();
+
+        let main = root.join("src/main.rs");
+        assert_eq!(
+            resolve_rust_spec(&root, &main, "crate::app::config::Config"),
+            FileImport::local(
+                &root,
+                "crate::app::config::Config",
+                Some(root.join("src/app/config.rs"))
+            )
+        );
+        let config = root.join("src/app/config.rs");
+        assert_eq!(
+            resolve_rust_spec(&root, &config, "super").path,
+            Some(PathBuf::from("src/app/mod.rs"))
+        );
+
+        let text = result_text(&file_imports(&root, "src/main.rs"));
+        assert!(text.contains("self::app -> src/app/mod.rs"));
+        assert!(text.contains("crate::app::config::Config -> src/app/config.rs"));
+        assert!(text.contains("anyhow (external)"));
+    }
+
+    #[test]
+    fn test_file_imports_resolves_python_modules() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("pkg")).unwrap();
+        fs::write(root.join("pkg/__init__.py"), "").unwrap();
+        fs::write(root.join("pkg/models.py"), "").unwrap();
+        fs::write(
+            root.join("pkg/app.py"),
+            "import requests\nfrom pkg.models import User\nfrom . import models\nfrom .missing import x\n",
+        )
+        .unwrap();
+
+        let text = result_text(&file_imports(&root, "pkg/app.py"));
+        assert!(text.contains("requests (external)"));
+        assert!(text.contains("pkg.models -> pkg/models.py"));
+        assert!(text.contains(".missing (unresolved)"));
+        // `.models` resolves to the same file as `pkg.models`
+        assert_eq!(text.matches("pkg/models.py").count(), 1);
+    }
+
+    #[test]
+    fn test_file_imports_rejects_invalid_paths() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        fs::write(root.join("notes.txt"), "").unwrap();
+
+        assert_eq!(file_imports(&root, "../outside.rs").is_error, Some(true));
+        assert_eq!(file_imports(&root, "notes.txt").is_error, Some(true));
+    }
+}
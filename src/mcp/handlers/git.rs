@@ -20,7 +20,16 @@ pub fn get_git_status(root: &Path) -> ToolCallResult {
 
     // Add branch info
     if let Some(branch) = git_status.branch() {
-        output.push_str(&format!("Branch: {}\n\n", branch));
+        output.push_str(&format!("Branch: {}\n", branch));
+    }
+    if let Some(label) = git_status.state().label() {
+        output.push_str(&format!("State: {}\n", label));
+    }
+    if git_status.is_bare() {
+        output.push_str("Bare repository\n");
+    }
+    if !output.is_empty() {
+        output.push('\n');
     }
 
     // Get status output using git command
@@ -81,7 +90,7 @@ pub fn get_git_diff(root: &Path, path: &str, staged: bool) -> ToolCallResult {
         Err(e) => return error_result(&format!("Invalid path '{}': {}", path, e)),
     };
 
-    match get_diff(root, &canonical, staged) {
+    match get_diff(root, &canonical, staged, None) {
         Some(diff) => {
             let mut output = String::new();
             output.push_str(&format!("File: {}\n", path));
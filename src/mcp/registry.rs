@@ -412,6 +412,21 @@ fn dependency_tools() -> Vec<ToolDefinition> {
             }),
             category: ToolCategory::Dependency,
         },
+        ToolDefinition {
+            name: "file_imports",
+            description: "List files and modules a source file imports (Rust, Python, JS/TS), resolved to repo-relative paths; external packages are flagged",
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Relative path to the source file"
+                    }
+                },
+                "required": ["path"]
+            }),
+            category: ToolCategory::Dependency,
+        },
         ToolDefinition {
             name: "find_circular_deps",
             description: "Find circular dependencies in the codebase",
@@ -4,6 +4,7 @@
 
 use std::path::{Path, PathBuf};
 
+use crate::action::is_within_root;
 use crate::error::{FileviewError, Result};
 
 /// Maximum length for entry names (prevent DoS from malicious input)
@@ -29,7 +30,7 @@ pub fn validate_path(root: &Path, path: &str) -> Result<PathBuf> {
 
     match target.canonicalize() {
         Ok(canonical) => {
-            if !canonical.starts_with(root) {
+            if !is_within_root(&canonical, root) {
                 return Err(FileviewError::path(
                     canonical,
                     "path is outside root directory",
@@ -52,50 +53,18 @@ pub fn validate_path(root: &Path, path: &str) -> Result<PathBuf> {
 /// * `Err(FileviewError)` - If the parent is invalid or outside root
 pub fn validate_new_path(root: &Path, path: &str) -> Result<PathBuf> {
     let target = root.join(path);
-    let parent = target.parent().unwrap_or(root);
 
-    // For new files, check if parent exists and is within root
-    if parent.exists() {
-        if let Ok(canonical_parent) = parent.canonicalize() {
-            if !canonical_parent.starts_with(root) {
-                return Err(FileviewError::path(
-                    target,
-                    "parent directory is outside root",
-                ));
-            }
-        }
-    } else {
-        // Parent doesn't exist - check if path traverses outside root
-        // by checking if normalized path stays within root
-        let normalized = normalize_path(&target);
-        let root_normalized = normalize_path(root);
-        if !normalized.starts_with(&root_normalized) {
-            return Err(FileviewError::path(
-                target,
-                "path would be outside root directory",
-            ));
-        }
+    // Resolves symlinks in the existing part and `..` in the rest
+    if !is_within_root(&target, root) {
+        return Err(FileviewError::path(
+            target,
+            "path would be outside root directory",
+        ));
     }
 
     Ok(target)
 }
 
-/// Normalize a path without requiring it to exist.
-/// Removes `.` and `..` components.
-fn normalize_path(path: &Path) -> PathBuf {
-    let mut normalized = PathBuf::new();
-    for component in path.components() {
-        match component {
-            std::path::Component::ParentDir => {
-                normalized.pop();
-            }
-            std::path::Component::CurDir => {}
-            _ => normalized.push(component),
-        }
-    }
-    normalized
-}
-
 /// Check if a path is the root directory itself.
 pub fn is_root(root: &Path, path: &Path) -> bool {
     match (root.canonicalize(), path.canonicalize()) {
@@ -193,6 +162,32 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_validate_new_path_outside_root() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("root");
+        fs::create_dir(&root).unwrap();
+
+        assert!(validate_new_path(&root, "new/file.txt").is_ok());
+        assert!(validate_new_path(&root, "../escape.txt").is_err());
+        assert!(validate_new_path(&root, "missing/../../escape.txt").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_path_rejects_symlink_outside_root() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("root");
+        let outside = temp.path().join("outside");
+        fs::create_dir(&root).unwrap();
+        fs::create_dir(&outside).unwrap();
+        fs::write(outside.join("secret.txt"), "x").unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+
+        assert!(validate_path(&root, "link/secret.txt").is_err());
+        assert!(validate_new_path(&root, "link/new.txt").is_err());
+    }
+
     #[test]
     fn test_truncate_entry_name() {
         let short = "short.txt".to_string();
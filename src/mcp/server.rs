@@ -286,6 +286,13 @@ fn dispatch_tool_call(root: &Path, params: &ToolCallParams) -> ToolCallResult {
                 None => missing_param("path"),
             }
         }
+        "file_imports" => {
+            let path = args.get("path").and_then(|v| v.as_str());
+            match path {
+                Some(p) => dependency::file_imports(root, p),
+                None => missing_param("path"),
+            }
+        }
         "find_circular_deps" => {
             let path = args.get("path").and_then(|v| v.as_str());
             dependency::find_circular_deps(root, path)
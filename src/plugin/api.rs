@@ -64,6 +64,15 @@ pub enum PluginAction {
     Focus(PathBuf),
 }
 
+/// Preview content returned by a plugin preview provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginPreview {
+    /// Text to show as-is
+    Text(String),
+    /// Shell command whose output is shown (`$f` expands to the file path)
+    Command(String),
+}
+
 /// Context shared between FileView and Lua plugins
 ///
 /// This structure holds the current state that plugins can read and
@@ -5,7 +5,7 @@ use std::sync::{Arc, Mutex};
 
 use mlua::{Function, Lua};
 
-use super::api::{PluginAction, PluginContext, PluginEvent};
+use super::api::{PluginAction, PluginContext, PluginEvent, PluginPreview};
 
 /// Plugin system error
 #[derive(Debug)]
@@ -317,8 +317,9 @@ impl PluginManager {
             fv.set("on", on).map_err(PluginError::from)?;
         }
 
-        // fv.register_previewer(pattern, fn) -> nil
-        // Register a custom previewer for a file pattern
+        // fv.register_preview(pattern, fn) -> nil (alias: fv.register_previewer)
+        // Register a preview provider for a file pattern. The function gets the
+        // file path and returns text, {command = "..."}, or nil for the built-in preview
         {
             let register_previewer = lua
                 .create_function(|lua, (pattern, func): (String, Function)| {
@@ -329,6 +330,8 @@ impl PluginManager {
                     Ok(())
                 })
                 .map_err(PluginError::from)?;
+            fv.set("register_preview", register_previewer.clone())
+                .map_err(PluginError::from)?;
             fv.set("register_previewer", register_previewer)
                 .map_err(PluginError::from)?;
         }
@@ -561,6 +564,49 @@ impl PluginManager {
         self.collect_actions();
         Ok(result)
     }
+
+    /// Run the preview provider matching `path`'s file name, if any
+    ///
+    /// Returns `Ok(None)` when no provider matches or the provider returned nil.
+    pub fn preview_for(&mut self, path: &Path) -> Result<Option<PluginPreview>, PluginError> {
+        let Some(filename) = path.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            return Ok(None);
+        };
+        let Some(pattern) = self.find_previewer(&filename) else {
+            return Ok(None);
+        };
+
+        let globals = self.lua.globals();
+        let fv: mlua::Table = globals.get("fv").map_err(PluginError::from)?;
+        let previewers: mlua::Table = fv.get("_previewers").map_err(PluginError::from)?;
+        let func: Function = previewers
+            .get(pattern.as_str())
+            .map_err(PluginError::from)?;
+
+        let value: mlua::Value = func.call(path.display().to_string()).map_err(|e| {
+            PluginError::ExecutionError(format!("Error in previewer '{}': {}", pattern, e))
+        })?;
+
+        let preview = match value {
+            mlua::Value::Nil => None,
+            mlua::Value::String(s) => Some(PluginPreview::Text(s.to_str()?.to_string())),
+            mlua::Value::Table(t) => match t.get::<Option<String>>("command")? {
+                Some(command) => Some(PluginPreview::Command(command)),
+                None => t.get::<Option<String>>("text")?.map(PluginPreview::Text),
+            },
+            other => {
+                return Err(PluginError::ExecutionError(format!(
+                    "Previewer '{}' returned {}, expected string or table",
+                    pattern,
+                    format_lua_value(&other)
+                )))
+            }
+        };
+
+        self.collect_notifications();
+        self.collect_actions();
+        Ok(preview)
+    }
 }
 
 /// Simple glob pattern matching (supports * and ?)
@@ -1129,6 +1175,49 @@ mod tests {
         assert!(previewers.contains(&"*.yaml".to_string()));
     }
 
+    #[test]
+    fn test_preview_for_text_and_command() {
+        let mut manager = PluginManager::new().unwrap();
+
+        manager
+            .exec(
+                r#"
+            fv.register_preview("*.log", function(path) return "log: " .. path end)
+            fv.register_preview("*.csv", function(path) return { command = "head $f" } end)
+            fv.register_preview("*.bin", function(path) return nil end)
+        "#,
+            )
+            .unwrap();
+
+        assert_eq!(
+            manager.preview_for(Path::new("/tmp/a.log")).unwrap(),
+            Some(PluginPreview::Text("log: /tmp/a.log".to_string()))
+        );
+        assert_eq!(
+            manager.preview_for(Path::new("/tmp/a.csv")).unwrap(),
+            Some(PluginPreview::Command("head $f".to_string()))
+        );
+        assert_eq!(manager.preview_for(Path::new("/tmp/a.bin")).unwrap(), None);
+        assert_eq!(manager.preview_for(Path::new("/tmp/a.txt")).unwrap(), None);
+    }
+
+    #[test]
+    fn test_preview_for_error() {
+        let mut manager = PluginManager::new().unwrap();
+
+        manager
+            .exec(
+                r#"
+            fv.register_preview("*.txt", function(path) error("boom") end)
+            fv.register_preview("*.md", function(path) return 42 end)
+        "#,
+            )
+            .unwrap();
+
+        assert!(manager.preview_for(Path::new("a.txt")).is_err());
+        assert!(manager.preview_for(Path::new("a.md")).is_err());
+    }
+
     // === Glob matching tests ===
 
     #[test]
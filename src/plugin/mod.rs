@@ -24,5 +24,5 @@
 mod api;
 mod lua;
 
-pub use api::{PluginAction, PluginContext, PluginEvent};
+pub use api::{PluginAction, PluginContext, PluginEvent, PluginPreview};
 pub use lua::{PluginError, PluginManager};
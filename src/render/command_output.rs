@@ -0,0 +1,83 @@
+//! Output popup of a command run over marked files.
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::core::{AppState, ViewMode};
+
+/// Render the per-file status and output of the `!` command
+pub fn render_command_output_popup(frame: &mut Frame, state: &AppState) {
+    let ViewMode::CommandOutput { scroll } = &state.mode else {
+        return;
+    };
+    let Some(run) = &state.foreach_run else {
+        return;
+    };
+
+    let area = frame.area();
+    let width = area.width.saturating_sub(6).clamp(30, 120);
+    let height = area.height.saturating_sub(4).clamp(8, 40);
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+    let popup = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, popup);
+
+    let mut lines: Vec<Line> = Vec::with_capacity(run.line_count());
+    for file in &run.results {
+        let rel = file.path.strip_prefix(&state.root).unwrap_or(&file.path);
+        let (mark, color) = if file.success {
+            ("\u{2713}", Color::Green)
+        } else {
+            ("\u{2717}", Color::Red)
+        };
+        let status = match (file.success, file.code) {
+            (true, _) => String::new(),
+            (false, Some(code)) => format!(" (exit {})", code),
+            (false, None) => " (no exit code)".to_string(),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", mark), Style::default().fg(color)),
+            Span::styled(
+                rel.display().to_string(),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(status, Style::default().fg(color)),
+        ]));
+        lines.extend(
+            file.output
+                .lines()
+                .map(|line| Line::raw(format!("  {}", line))),
+        );
+    }
+
+    let running = if run.is_running() { " …" } else { "" };
+    let failed = match run.failures() {
+        0 => String::new(),
+        n => format!(", {} failed", n),
+    };
+    let title = format!(
+        " Run: {} ({}/{}{}){} ",
+        run.template,
+        run.results.len(),
+        run.total,
+        failed,
+        running
+    );
+    let widget = Paragraph::new(lines)
+        .scroll((u16::try_from(*scroll).unwrap_or(u16::MAX), 0))
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+    frame.render_widget(widget, popup);
+}
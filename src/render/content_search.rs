@@ -0,0 +1,76 @@
+//! Content search results popup rendering.
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem},
+    Frame,
+};
+
+use crate::core::{AppState, ViewMode};
+
+/// Render content search results popup (Ctrl+F)
+pub fn render_content_search_popup(frame: &mut Frame, state: &AppState) {
+    let ViewMode::ContentSearch { selected } = &state.mode else {
+        return;
+    };
+    let Some(search) = &state.content_search else {
+        return;
+    };
+
+    let area = frame.area();
+    let width = area.width.saturating_sub(6).clamp(30, 100);
+    let height = area.height.saturating_sub(6).clamp(8, 24);
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+    let popup = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, popup);
+
+    // Keep the selected match visible when the list is longer than the popup
+    let max_items = (height.saturating_sub(2) as usize).max(1);
+    let offset = selected.saturating_sub(max_items - 1);
+
+    let items: Vec<ListItem> = search
+        .matches
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(max_items)
+        .map(|(idx, hit)| {
+            let (location_style, text_style) = if idx == *selected {
+                let style = Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD);
+                (style, style)
+            } else {
+                (
+                    Style::default().fg(Color::Cyan),
+                    Style::default().fg(Color::White),
+                )
+            };
+            let rel = hit.path.strip_prefix(&state.root).unwrap_or(&hit.path);
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{}:{}: ", rel.display(), hit.line), location_style),
+                Span::styled(hit.text.clone(), text_style),
+            ]))
+        })
+        .collect();
+
+    let running = if search.is_running() { " …" } else { "" };
+    let title = format!(
+        " Search in files: {} ({}){} ",
+        search.query.text,
+        search.matches.len(),
+        running
+    );
+    let widget = List::new(items).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    frame.render_widget(widget, popup);
+}
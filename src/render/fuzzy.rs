@@ -1,6 +1,8 @@
 //! Fuzzy finder rendering and matching
 
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 use nucleo_matcher::{
     pattern::{CaseMatching, Normalization, Pattern},
@@ -10,12 +12,18 @@ use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
-/// Maximum number of results to display
-const MAX_RESULTS: usize = 15;
+use crate::core::EXCLUDED_DIRS;
+use crate::tree::FvIgnore;
+
+/// Default number of results shown (`general.fuzzy_max_results`)
+pub const DEFAULT_MAX_RESULTS: usize = 15;
+
+/// Narrowest terminal that shows the preview column next to the results
+const PREVIEW_MIN_WIDTH: u16 = 100;
 
 /// Fuzzy match result
 #[derive(Debug, Clone)]
@@ -30,13 +38,26 @@ pub struct FuzzyMatch {
     pub indices: Vec<usize>,
 }
 
-/// Perform fuzzy matching on a list of paths
+/// Perform fuzzy matching on a list of paths (smart case)
 pub fn fuzzy_match(query: &str, paths: &[PathBuf], root: &PathBuf) -> Vec<FuzzyMatch> {
+    fuzzy_match_with_case(query, paths, root, false, DEFAULT_MAX_RESULTS)
+}
+
+/// Perform fuzzy matching, matching case exactly if `case_sensitive` is set
+///
+/// At most `max_results` of the best matches are returned.
+pub fn fuzzy_match_with_case(
+    query: &str,
+    paths: &[PathBuf],
+    root: &PathBuf,
+    case_sensitive: bool,
+    max_results: usize,
+) -> Vec<FuzzyMatch> {
     if query.is_empty() {
-        // Return first MAX_RESULTS paths when no query
+        // Return the first paths when no query
         return paths
             .iter()
-            .take(MAX_RESULTS)
+            .take(max_results)
             .map(|p| {
                 let display = p
                     .strip_prefix(root)
@@ -54,7 +75,7 @@ pub fn fuzzy_match(query: &str, paths: &[PathBuf], root: &PathBuf) -> Vec<FuzzyM
     }
 
     let mut matcher = Matcher::new(nucleo_matcher::Config::DEFAULT);
-    let pattern = Pattern::parse(query, CaseMatching::Smart, Normalization::Smart);
+    let pattern = parse_pattern(query, case_sensitive);
 
     let mut results: Vec<FuzzyMatch> = paths
         .iter()
@@ -84,27 +105,62 @@ pub fn fuzzy_match(query: &str, paths: &[PathBuf], root: &PathBuf) -> Vec<FuzzyM
         .collect();
 
     // Sort by score descending
-    results.sort_by(|a, b| b.score.cmp(&a.score));
+    results.sort_by_key(|m| std::cmp::Reverse(m.score));
 
     // Limit results
-    results.truncate(MAX_RESULTS);
+    results.truncate(max_results);
 
     results
 }
 
+/// Score every candidate against `query` (None = no match)
+///
+/// Unlike [`fuzzy_match_with_case`] nothing is dropped or reordered, so the
+/// scores line up with `candidates`.
+pub fn fuzzy_scores(query: &str, candidates: &[&str], case_sensitive: bool) -> Vec<Option<u32>> {
+    let mut matcher = Matcher::new(nucleo_matcher::Config::DEFAULT);
+    let pattern = parse_pattern(query, case_sensitive);
+    let mut buf = Vec::new();
+    candidates
+        .iter()
+        .map(|candidate| pattern.score(Utf32Str::new(candidate, &mut buf), &mut matcher))
+        .collect()
+}
+
+fn parse_pattern(query: &str, case_sensitive: bool) -> Pattern {
+    let case_matching = if case_sensitive {
+        CaseMatching::Respect
+    } else {
+        CaseMatching::Smart
+    };
+    Pattern::parse(query, case_matching, Normalization::Smart)
+}
+
 /// Render the fuzzy finder popup
+///
+/// On terminals at least [`PREVIEW_MIN_WIDTH`] columns wide the popup gets a
+/// preview column on the right; its area is returned for the caller to fill.
 pub fn render_fuzzy_finder(
     frame: &mut Frame,
     query: &str,
     results: &[FuzzyMatch],
     selected: usize,
+    case_sensitive: bool,
+    max_results: usize,
     area: Rect,
-) {
+) -> Option<Rect> {
+    let with_preview = area.width >= PREVIEW_MIN_WIDTH;
+
     // Calculate popup dimensions (handle very small terminals)
-    let popup_width = (area.width * 70 / 100)
-        .clamp(40, 80)
-        .min(area.width.saturating_sub(2));
-    let popup_height = (MAX_RESULTS as u16 + 4)
+    let popup_width = if with_preview {
+        (area.width * 90 / 100).min(160)
+    } else {
+        (area.width * 70 / 100)
+            .clamp(40, 80)
+            .min(area.width.saturating_sub(2))
+    };
+    let popup_height = (max_results.min(u16::MAX as usize) as u16)
+        .saturating_add(4)
         .min(area.height.saturating_sub(4))
         .max(6); // Minimum height for usability
 
@@ -116,11 +172,24 @@ pub fn render_fuzzy_finder(
     // Clear the popup area
     frame.render_widget(Clear, popup_area);
 
+    // Results on the left, preview on the right
+    let (popup_area, preview_area) = if with_preview {
+        let columns = Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(popup_area);
+        (columns[0], Some(columns[1]))
+    } else {
+        (popup_area, None)
+    };
+
     // Create popup block
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan))
-        .title(" Fuzzy Find (Ctrl+P) ");
+        .title(if case_sensitive {
+            " Fuzzy Find (Ctrl+P) /c "
+        } else {
+            " Fuzzy Find (Ctrl+P) / "
+        });
 
     let inner = block.inner(popup_area);
     frame.render_widget(block, popup_area);
@@ -182,9 +251,13 @@ pub fn render_fuzzy_finder(
             })
             .collect();
 
+        // Scrolls to keep the selection in view when results outnumber rows
         let list = List::new(items);
-        frame.render_widget(list, chunks[2]);
+        let mut list_state = ListState::default().with_selected(Some(selected));
+        frame.render_stateful_widget(list, chunks[2], &mut list_state);
     }
+
+    preview_area
 }
 
 /// Create spans with matched characters highlighted
@@ -232,46 +305,154 @@ fn create_highlighted_spans(
 }
 
 /// Collect all file paths from a directory recursively
-pub fn collect_paths(root: &PathBuf, show_hidden: bool) -> Vec<PathBuf> {
+///
+/// Skips the build and dependency directories in [`EXCLUDED_DIRS`].
+pub fn collect_paths(root: &Path, show_hidden: bool) -> Vec<PathBuf> {
+    let exclude: Vec<String> = EXCLUDED_DIRS.iter().map(|d| d.to_string()).collect();
+    collect_paths_with(root, show_hidden, &exclude, false)
+}
+
+/// Collect file paths, pruning directories named in `exclude`
+///
+/// With `gitignore`, paths git ignores are pruned too (inside a work tree).
+/// `.fvignore` rules apply unless `show_hidden` is set.
+pub fn collect_paths_with(
+    root: &Path,
+    show_hidden: bool,
+    exclude: &[String],
+    gitignore: bool,
+) -> Vec<PathBuf> {
+    let walk = Walk {
+        show_hidden,
+        exclude: exclude.iter().map(String::as_str).collect(),
+        ignored: if gitignore {
+            git_ignored_paths(root)
+        } else {
+            HashSet::new()
+        },
+        fvignore: (!show_hidden).then(|| FvIgnore::load(root)),
+        max_depth: 10,
+    };
     let mut paths = Vec::new();
-    collect_paths_recursive(root, &mut paths, show_hidden, 0, 10);
+    walk.collect(root, &mut paths, 0);
     paths
 }
 
-fn collect_paths_recursive(
-    dir: &PathBuf,
-    paths: &mut Vec<PathBuf>,
+/// What a path walk skips
+struct Walk<'a> {
     show_hidden: bool,
-    depth: usize,
+    /// Directory names pruned with their contents
+    exclude: HashSet<&'a str>,
+    /// Paths git ignores
+    ignored: HashSet<PathBuf>,
+    /// `.fvignore` rules (None while showing hidden files)
+    fvignore: Option<FvIgnore>,
     max_depth: usize,
-) {
-    if depth > max_depth {
-        return;
-    }
+}
 
-    if let Ok(entries) = std::fs::read_dir(dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            let name = entry.file_name().to_string_lossy().to_string();
+impl Walk<'_> {
+    fn collect(&self, dir: &Path, paths: &mut Vec<PathBuf>, depth: usize) {
+        if depth > self.max_depth {
+            return;
+        }
 
-            // Skip hidden files if not showing them
-            if !show_hidden && name.starts_with('.') {
-                continue;
-            }
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let name = entry.file_name().to_string_lossy().to_string();
+
+                // Skip hidden files if not showing them
+                if !self.show_hidden && name.starts_with('.') {
+                    continue;
+                }
+                if self.ignored.contains(&path) {
+                    continue;
+                }
+
+                let is_dir = path.is_dir();
+                if is_dir && self.exclude.contains(name.as_str()) {
+                    continue;
+                }
+                if self
+                    .fvignore
+                    .as_ref()
+                    .is_some_and(|rules| rules.is_ignored(&path, is_dir))
+                {
+                    continue;
+                }
 
-            paths.push(path.clone());
+                paths.push(path.clone());
 
-            if path.is_dir() {
-                collect_paths_recursive(&path, paths, show_hidden, depth + 1, max_depth);
+                if is_dir {
+                    self.collect(&path, paths, depth + 1);
+                }
             }
         }
     }
 }
 
+/// Ignored files and directories under `root` (empty outside a work tree)
+fn git_ignored_paths(root: &Path) -> HashSet<PathBuf> {
+    let output = Command::new("git")
+        .args([
+            "ls-files",
+            "-z",
+            "--others",
+            "--ignored",
+            "--exclude-standard",
+            "--directory",
+        ])
+        .current_dir(root)
+        .output();
+    let Ok(output) = output else {
+        return HashSet::new();
+    };
+    if !output.status.success() {
+        return HashSet::new();
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|rel| !rel.is_empty())
+        // Directories are listed with a trailing slash
+        .map(|rel| root.join(rel.trim_end_matches('/')))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_render_keeps_selection_visible() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let results: Vec<FuzzyMatch> = (0..50)
+            .map(|i| FuzzyMatch {
+                path: PathBuf::from(format!("file{i:02}.txt")),
+                display: format!("file{i:02}.txt"),
+                score: 0,
+                indices: Vec::new(),
+            })
+            .collect();
+        let mut terminal = Terminal::new(TestBackend::new(60, 20)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.area();
+                render_fuzzy_finder(frame, "", &results, 45, false, 50, area);
+            })
+            .unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("file45.txt"));
+        assert!(!screen.contains("file00.txt"));
+    }
+
     #[test]
     fn test_fuzzy_match_empty_query() {
         let root = PathBuf::from("/test");
@@ -370,7 +551,13 @@ mod tests {
             .collect();
 
         let results = fuzzy_match("file", &paths, &root);
-        assert!(results.len() <= MAX_RESULTS);
+        assert_eq!(results.len(), DEFAULT_MAX_RESULTS);
+
+        // The configured cap applies with and without a query
+        let results = fuzzy_match_with_case("file", &paths, &root, false, 40);
+        assert_eq!(results.len(), 40);
+        let results = fuzzy_match_with_case("", &paths, &root, false, 3);
+        assert_eq!(results.len(), 3);
     }
 
     #[test]
@@ -429,7 +616,7 @@ mod tests {
         use tempfile::TempDir;
         let temp = TempDir::new().unwrap();
 
-        let paths = collect_paths(&temp.path().to_path_buf(), false);
+        let paths = collect_paths(temp.path(), false);
         assert!(paths.is_empty());
     }
 
@@ -442,7 +629,7 @@ mod tests {
         fs::write(temp.path().join("test.txt"), "").unwrap();
         fs::write(temp.path().join("test2.txt"), "").unwrap();
 
-        let paths = collect_paths(&temp.path().to_path_buf(), false);
+        let paths = collect_paths(temp.path(), false);
         assert_eq!(paths.len(), 2);
     }
 
@@ -456,14 +643,111 @@ mod tests {
         fs::write(temp.path().join("visible"), "").unwrap();
 
         // Without showing hidden
-        let paths_no_hidden = collect_paths(&temp.path().to_path_buf(), false);
+        let paths_no_hidden = collect_paths(temp.path(), false);
         assert_eq!(paths_no_hidden.len(), 1);
 
         // With showing hidden
-        let paths_with_hidden = collect_paths(&temp.path().to_path_buf(), true);
+        let paths_with_hidden = collect_paths(temp.path(), true);
         assert_eq!(paths_with_hidden.len(), 2);
     }
 
+    #[test]
+    fn test_collect_paths_skips_excluded_dirs() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("target/debug")).unwrap();
+        fs::write(temp.path().join("target/debug/app"), "").unwrap();
+        fs::create_dir_all(temp.path().join("src/generated")).unwrap();
+        fs::write(temp.path().join("src/generated/out.rs"), "").unwrap();
+        fs::write(temp.path().join("src/main.rs"), "").unwrap();
+        let names = |paths: Vec<PathBuf>| -> Vec<String> {
+            let mut names: Vec<String> = paths
+                .iter()
+                .map(|p| {
+                    let rel = p.strip_prefix(temp.path()).unwrap();
+                    rel.to_string_lossy().replace('\\', "/")
+                })
+                .collect();
+            names.sort();
+            names
+        };
+
+        // target/ is skipped by default
+        assert_eq!(
+            names(collect_paths(temp.path(), false)),
+            vec![
+                "src",
+                "src/generated",
+                "src/generated/out.rs",
+                "src/main.rs"
+            ]
+        );
+
+        // A custom list replaces the defaults
+        let exclude = vec!["generated".to_string()];
+        assert_eq!(
+            names(collect_paths_with(temp.path(), false, &exclude, false)),
+            vec![
+                "src",
+                "src/main.rs",
+                "target",
+                "target/debug",
+                "target/debug/app"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_paths_gitignore() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let initialized = Command::new("git")
+            .arg("init")
+            .current_dir(root)
+            .output()
+            .is_ok_and(|o| o.status.success());
+        if !initialized {
+            return;
+        }
+        fs::write(root.join(".gitignore"), "*.log\nout/\n").unwrap();
+        fs::write(root.join("app.log"), "").unwrap();
+        fs::create_dir(root.join("out")).unwrap();
+        fs::write(root.join("out/bundle.js"), "").unwrap();
+        fs::write(root.join("main.rs"), "").unwrap();
+
+        let paths = collect_paths_with(root, false, &[], true);
+        assert_eq!(paths, vec![root.join("main.rs")]);
+        // Off by default
+        assert_eq!(collect_paths_with(root, false, &[], false).len(), 4);
+    }
+
+    #[test]
+    fn test_collect_paths_fvignore() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(
+            root.join(crate::tree::FVIGNORE_FILENAME),
+            "*.psd\nfixtures/\n",
+        )
+        .unwrap();
+        fs::write(root.join("logo.psd"), "").unwrap();
+        fs::create_dir(root.join("fixtures")).unwrap();
+        fs::write(root.join("fixtures/big.json"), "").unwrap();
+        fs::write(root.join("main.rs"), "").unwrap();
+
+        assert_eq!(collect_paths(root, false), vec![root.join("main.rs")]);
+        // Showing hidden files reveals ignored ones (and the dotfile itself)
+        assert_eq!(collect_paths(root, true).len(), 5);
+    }
+
     #[test]
     fn test_collect_paths_nested_directories() {
         use std::fs;
@@ -473,7 +757,7 @@ mod tests {
         fs::create_dir_all(temp.path().join("a/b/c")).unwrap();
         fs::write(temp.path().join("a/b/c/file.txt"), "").unwrap();
 
-        let paths = collect_paths(&temp.path().to_path_buf(), false);
+        let paths = collect_paths(temp.path(), false);
         // Should include a, a/b, a/b/c, and a/b/c/file.txt
         assert!(paths.len() >= 4);
     }
@@ -508,7 +792,29 @@ mod tests {
     #[test]
     fn test_max_results_constant() {
         // Verify constant is reasonable (compile-time check)
-        const { assert!(MAX_RESULTS > 0) };
-        const { assert!(MAX_RESULTS <= 100) }; // Should not be too large
+        const { assert!(DEFAULT_MAX_RESULTS > 0) };
+        const { assert!(DEFAULT_MAX_RESULTS <= 100) }; // Should not be too large
+    }
+
+    #[test]
+    fn test_fuzzy_match_case_sensitive() {
+        let root = PathBuf::from("/test");
+        let paths = vec![
+            PathBuf::from("/test/Config.toml"),
+            PathBuf::from("/test/config.toml"),
+        ];
+        let matched = |query: &str, case_sensitive: bool| -> Vec<String> {
+            let mut names: Vec<String> =
+                fuzzy_match_with_case(query, &paths, &root, case_sensitive, DEFAULT_MAX_RESULTS)
+                    .into_iter()
+                    .map(|m| m.display)
+                    .collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(matched("config", false), vec!["Config.toml", "config.toml"]);
+        assert_eq!(matched("config", true), vec!["config.toml"]);
+        assert_eq!(matched("Config", false), vec!["Config.toml"]);
     }
 }
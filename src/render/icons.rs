@@ -1,14 +1,78 @@
-//! Nerd Fonts icon mappings for files and directories
-//! Based on yazi file manager's icon system with additional customizations
+//! Icon mappings for files and directories
+//!
+//! Nerd Font glyphs are based on yazi file manager's icon system with
+//! additional customizations. The emoji and ASCII sets only distinguish
+//! broad file categories.
 
 use std::path::Path;
 
-/// Get the appropriate icon for a file or directory
-pub fn get_icon(path: &Path, is_dir: bool, expanded: bool) -> &'static str {
-    if is_dir {
-        get_directory_icon(path, expanded)
-    } else {
-        get_file_icon(path)
+use crate::core::IconSet;
+
+/// Get the appropriate icon for a file or directory in the given glyph set
+pub fn get_icon(path: &Path, is_dir: bool, expanded: bool, set: IconSet) -> &'static str {
+    match set {
+        IconSet::NerdFont if is_dir => get_directory_icon(path, expanded),
+        IconSet::NerdFont => get_file_icon(path),
+        IconSet::Emoji => match (is_dir, expanded) {
+            (true, true) => "\u{1f4c2}",  // 📂
+            (true, false) => "\u{1f4c1}", // 📁
+            _ => match file_category(path) {
+                FileCategory::Code => "\u{1f4dc}",     // 📜
+                FileCategory::Document => "\u{1f4d8}", // 📘
+                FileCategory::Image => "\u{1f3a8}",    // 🎨
+                FileCategory::Audio => "\u{1f3b5}",    // 🎵
+                FileCategory::Video => "\u{1f3ac}",    // 🎬
+                FileCategory::Archive => "\u{1f4e6}",  // 📦
+                FileCategory::Other => "\u{1f4c4}",    // 📄
+            },
+        },
+        IconSet::Ascii if is_dir => "[D]",
+        IconSet::Ascii => match file_category(path) {
+            FileCategory::Code => "[C]",
+            FileCategory::Document => "[T]",
+            FileCategory::Image => "[I]",
+            FileCategory::Audio | FileCategory::Video => "[M]",
+            FileCategory::Archive => "[Z]",
+            FileCategory::Other => "[F]",
+        },
+        IconSet::None => "",
+    }
+}
+
+/// Broad file category for the emoji and ASCII sets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileCategory {
+    Code,
+    Document,
+    Image,
+    Audio,
+    Video,
+    Archive,
+    Other,
+}
+
+fn file_category(path: &Path) -> FileCategory {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    match ext.as_str() {
+        "rs" | "py" | "js" | "jsx" | "ts" | "tsx" | "go" | "c" | "h" | "cpp" | "hpp" | "cc"
+        | "java" | "kt" | "rb" | "php" | "swift" | "cs" | "lua" | "sh" | "bash" | "zsh"
+        | "fish" | "ex" | "exs" | "hs" | "scala" | "zig" | "nim" | "dart" | "vue" | "svelte"
+        | "html" | "css" | "scss" | "sql" => FileCategory::Code,
+        "md" | "markdown" | "txt" | "rst" | "org" | "pdf" | "doc" | "docx" | "odt" | "rtf"
+        | "tex" | "json" | "toml" | "yaml" | "yml" | "xml" | "ini" | "csv" => {
+            FileCategory::Document
+        }
+        "png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "svg" | "ico" | "tiff" | "avif" => {
+            FileCategory::Image
+        }
+        "mp3" | "wav" | "flac" | "ogg" | "m4a" | "aac" | "opus" => FileCategory::Audio,
+        "mp4" | "mkv" | "webm" | "avi" | "mov" | "wmv" | "m4v" => FileCategory::Video,
+        "zip" | "tar" | "gz" | "tgz" | "bz2" | "xz" | "7z" | "rar" | "zst" => FileCategory::Archive,
+        _ => FileCategory::Other,
     }
 }
 
@@ -484,23 +548,43 @@ mod tests {
 
     #[test]
     fn test_special_directories() {
-        assert_eq!(get_icon(&PathBuf::from(".git"), true, false), "\u{f1d3}");
-        assert_eq!(get_icon(&PathBuf::from(".config"), true, false), "\u{e5fc}");
         assert_eq!(
-            get_icon(&PathBuf::from("node_modules"), true, false),
+            get_icon(&PathBuf::from(".git"), true, false, IconSet::NerdFont),
+            "\u{f1d3}"
+        );
+        assert_eq!(
+            get_icon(&PathBuf::from(".config"), true, false, IconSet::NerdFont),
+            "\u{e5fc}"
+        );
+        assert_eq!(
+            get_icon(
+                &PathBuf::from("node_modules"),
+                true,
+                false,
+                IconSet::NerdFont
+            ),
             "\u{e718}"
         );
-        assert_eq!(get_icon(&PathBuf::from("src"), true, false), "\u{e5fc}");
         assert_eq!(
-            get_icon(&PathBuf::from("Downloads"), true, false),
+            get_icon(&PathBuf::from("src"), true, false, IconSet::NerdFont),
+            "\u{e5fc}"
+        );
+        assert_eq!(
+            get_icon(&PathBuf::from("Downloads"), true, false, IconSet::NerdFont),
             "\u{f019}"
         );
     }
 
     #[test]
     fn test_directory_open_close() {
-        assert_eq!(get_icon(&PathBuf::from("mydir"), true, false), "\u{f07b}");
-        assert_eq!(get_icon(&PathBuf::from("mydir"), true, true), "\u{f07c}");
+        assert_eq!(
+            get_icon(&PathBuf::from("mydir"), true, false, IconSet::NerdFont),
+            "\u{f07b}"
+        );
+        assert_eq!(
+            get_icon(&PathBuf::from("mydir"), true, true, IconSet::NerdFont),
+            "\u{f07c}"
+        );
     }
 
     // ==========================================================================
@@ -510,17 +594,23 @@ mod tests {
     #[test]
     fn test_programming_languages() {
         assert_eq!(
-            get_icon(&PathBuf::from("main.rs"), false, false),
+            get_icon(&PathBuf::from("main.rs"), false, false, IconSet::NerdFont),
             "\u{e7a8}"
         );
         assert_eq!(
-            get_icon(&PathBuf::from("script.py"), false, false),
+            get_icon(&PathBuf::from("script.py"), false, false, IconSet::NerdFont),
             "\u{e73c}"
         );
-        assert_eq!(get_icon(&PathBuf::from("app.js"), false, false), "\u{e74e}");
-        assert_eq!(get_icon(&PathBuf::from("app.ts"), false, false), "\u{e628}");
         assert_eq!(
-            get_icon(&PathBuf::from("main.go"), false, false),
+            get_icon(&PathBuf::from("app.js"), false, false, IconSet::NerdFont),
+            "\u{e74e}"
+        );
+        assert_eq!(
+            get_icon(&PathBuf::from("app.ts"), false, false, IconSet::NerdFont),
+            "\u{e628}"
+        );
+        assert_eq!(
+            get_icon(&PathBuf::from("main.go"), false, false, IconSet::NerdFont),
             "\u{e627}"
         );
     }
@@ -528,11 +618,21 @@ mod tests {
     #[test]
     fn test_react_files() {
         assert_eq!(
-            get_icon(&PathBuf::from("Component.jsx"), false, false),
+            get_icon(
+                &PathBuf::from("Component.jsx"),
+                false,
+                false,
+                IconSet::NerdFont
+            ),
             "\u{e7ba}"
         );
         assert_eq!(
-            get_icon(&PathBuf::from("Component.tsx"), false, false),
+            get_icon(
+                &PathBuf::from("Component.tsx"),
+                false,
+                false,
+                IconSet::NerdFont
+            ),
             "\u{e7ba}"
         );
     }
@@ -540,15 +640,25 @@ mod tests {
     #[test]
     fn test_web_files() {
         assert_eq!(
-            get_icon(&PathBuf::from("index.html"), false, false),
+            get_icon(
+                &PathBuf::from("index.html"),
+                false,
+                false,
+                IconSet::NerdFont
+            ),
             "\u{e736}"
         );
         assert_eq!(
-            get_icon(&PathBuf::from("style.css"), false, false),
+            get_icon(&PathBuf::from("style.css"), false, false, IconSet::NerdFont),
             "\u{e749}"
         );
         assert_eq!(
-            get_icon(&PathBuf::from("style.scss"), false, false),
+            get_icon(
+                &PathBuf::from("style.scss"),
+                false,
+                false,
+                IconSet::NerdFont
+            ),
             "\u{e603}"
         );
     }
@@ -556,15 +666,30 @@ mod tests {
     #[test]
     fn test_config_files() {
         assert_eq!(
-            get_icon(&PathBuf::from("config.json"), false, false),
+            get_icon(
+                &PathBuf::from("config.json"),
+                false,
+                false,
+                IconSet::NerdFont
+            ),
             "\u{e60b}"
         );
         assert_eq!(
-            get_icon(&PathBuf::from("config.yaml"), false, false),
+            get_icon(
+                &PathBuf::from("config.yaml"),
+                false,
+                false,
+                IconSet::NerdFont
+            ),
             "\u{e6a8}"
         );
         assert_eq!(
-            get_icon(&PathBuf::from("config.toml"), false, false),
+            get_icon(
+                &PathBuf::from("config.toml"),
+                false,
+                false,
+                IconSet::NerdFont
+            ),
             "\u{e6b2}"
         );
     }
@@ -576,23 +701,43 @@ mod tests {
     #[test]
     fn test_special_files() {
         assert_eq!(
-            get_icon(&PathBuf::from("Cargo.toml"), false, false),
+            get_icon(
+                &PathBuf::from("Cargo.toml"),
+                false,
+                false,
+                IconSet::NerdFont
+            ),
             "\u{e7a8}"
         );
         assert_eq!(
-            get_icon(&PathBuf::from("package.json"), false, false),
+            get_icon(
+                &PathBuf::from("package.json"),
+                false,
+                false,
+                IconSet::NerdFont
+            ),
             "\u{e71e}"
         );
         assert_eq!(
-            get_icon(&PathBuf::from("Dockerfile"), false, false),
+            get_icon(
+                &PathBuf::from("Dockerfile"),
+                false,
+                false,
+                IconSet::NerdFont
+            ),
             "\u{f308}"
         );
         assert_eq!(
-            get_icon(&PathBuf::from(".gitignore"), false, false),
+            get_icon(
+                &PathBuf::from(".gitignore"),
+                false,
+                false,
+                IconSet::NerdFont
+            ),
             "\u{f1d3}"
         );
         assert_eq!(
-            get_icon(&PathBuf::from("README.md"), false, false),
+            get_icon(&PathBuf::from("README.md"), false, false, IconSet::NerdFont),
             "\u{f48a}"
         );
     }
@@ -600,10 +745,13 @@ mod tests {
     #[test]
     fn test_shell_config_files() {
         assert_eq!(
-            get_icon(&PathBuf::from(".bashrc"), false, false),
+            get_icon(&PathBuf::from(".bashrc"), false, false, IconSet::NerdFont),
+            "\u{e795}"
+        );
+        assert_eq!(
+            get_icon(&PathBuf::from(".zshrc"), false, false, IconSet::NerdFont),
             "\u{e795}"
         );
-        assert_eq!(get_icon(&PathBuf::from(".zshrc"), false, false), "\u{e795}");
     }
 
     // ==========================================================================
@@ -613,15 +761,15 @@ mod tests {
     #[test]
     fn test_image_files() {
         assert_eq!(
-            get_icon(&PathBuf::from("photo.png"), false, false),
+            get_icon(&PathBuf::from("photo.png"), false, false, IconSet::NerdFont),
             "\u{f03e}"
         );
         assert_eq!(
-            get_icon(&PathBuf::from("photo.jpg"), false, false),
+            get_icon(&PathBuf::from("photo.jpg"), false, false, IconSet::NerdFont),
             "\u{f03e}"
         );
         assert_eq!(
-            get_icon(&PathBuf::from("logo.svg"), false, false),
+            get_icon(&PathBuf::from("logo.svg"), false, false, IconSet::NerdFont),
             "\u{f1c5}"
         );
     }
@@ -629,11 +777,11 @@ mod tests {
     #[test]
     fn test_audio_video_files() {
         assert_eq!(
-            get_icon(&PathBuf::from("song.mp3"), false, false),
+            get_icon(&PathBuf::from("song.mp3"), false, false, IconSet::NerdFont),
             "\u{f001}"
         );
         assert_eq!(
-            get_icon(&PathBuf::from("video.mp4"), false, false),
+            get_icon(&PathBuf::from("video.mp4"), false, false, IconSet::NerdFont),
             "\u{f008}"
         );
     }
@@ -641,11 +789,21 @@ mod tests {
     #[test]
     fn test_archive_files() {
         assert_eq!(
-            get_icon(&PathBuf::from("archive.zip"), false, false),
+            get_icon(
+                &PathBuf::from("archive.zip"),
+                false,
+                false,
+                IconSet::NerdFont
+            ),
             "\u{f1c6}"
         );
         assert_eq!(
-            get_icon(&PathBuf::from("archive.tar.gz"), false, false),
+            get_icon(
+                &PathBuf::from("archive.tar.gz"),
+                false,
+                false,
+                IconSet::NerdFont
+            ),
             "\u{f1c6}"
         );
     }
@@ -657,15 +815,20 @@ mod tests {
     #[test]
     fn test_case_insensitivity() {
         assert_eq!(
-            get_icon(&PathBuf::from("FILE.RS"), false, false),
+            get_icon(&PathBuf::from("FILE.RS"), false, false, IconSet::NerdFont),
             "\u{e7a8}"
         );
         assert_eq!(
-            get_icon(&PathBuf::from("README.MD"), false, false),
+            get_icon(&PathBuf::from("README.MD"), false, false, IconSet::NerdFont),
             "\u{f48a}"
         );
         assert_eq!(
-            get_icon(&PathBuf::from("DOCKERFILE"), false, false),
+            get_icon(
+                &PathBuf::from("DOCKERFILE"),
+                false,
+                false,
+                IconSet::NerdFont
+            ),
             "\u{f308}"
         );
     }
@@ -673,7 +836,7 @@ mod tests {
     #[test]
     fn test_unknown_extension() {
         assert_eq!(
-            get_icon(&PathBuf::from("file.xyz"), false, false),
+            get_icon(&PathBuf::from("file.xyz"), false, false, IconSet::NerdFont),
             "\u{f15b}"
         );
     }
@@ -681,8 +844,54 @@ mod tests {
     #[test]
     fn test_default_file() {
         assert_eq!(
-            get_icon(&PathBuf::from("noextension"), false, false),
+            get_icon(
+                &PathBuf::from("noextension"),
+                false,
+                false,
+                IconSet::NerdFont
+            ),
             "\u{f15b}"
         );
     }
+
+    // ==========================================================================
+    // Icon Sets
+    // ==========================================================================
+
+    #[test]
+    fn test_icon_sets() {
+        let dir = PathBuf::from("project");
+        let rust = PathBuf::from("main.rs");
+
+        assert_eq!(get_icon(&dir, true, false, IconSet::NerdFont), "\u{f07b}");
+        assert_eq!(get_icon(&rust, false, false, IconSet::NerdFont), "\u{e7a8}");
+
+        assert_eq!(get_icon(&dir, true, false, IconSet::Emoji), "\u{1f4c1}");
+        assert_eq!(get_icon(&dir, true, true, IconSet::Emoji), "\u{1f4c2}");
+        assert_eq!(get_icon(&rust, false, false, IconSet::Emoji), "\u{1f4dc}");
+
+        assert_eq!(get_icon(&dir, true, true, IconSet::Ascii), "[D]");
+        assert_eq!(get_icon(&rust, false, false, IconSet::Ascii), "[C]");
+        assert_eq!(
+            get_icon(&PathBuf::from("unknown.xyz"), false, false, IconSet::Ascii),
+            "[F]"
+        );
+
+        assert_eq!(get_icon(&dir, true, false, IconSet::None), "");
+        assert_eq!(get_icon(&rust, false, false, IconSet::None), "");
+    }
+
+    #[test]
+    fn test_icon_set_parse_and_resolve() {
+        assert_eq!("nerdfont".parse(), Ok(IconSet::NerdFont));
+        assert_eq!("Emoji".parse(), Ok(IconSet::Emoji));
+        assert_eq!("ascii".parse(), Ok(IconSet::Ascii));
+        assert_eq!("none".parse(), Ok(IconSet::None));
+        assert_eq!("tofu".parse::<IconSet>(), Err(()));
+
+        assert_eq!(IconSet::NerdFont.resolve(true), IconSet::Ascii);
+        assert_eq!(IconSet::Emoji.resolve(true), IconSet::Ascii);
+        assert_eq!(IconSet::None.resolve(true), IconSet::None);
+        assert_eq!(IconSet::Emoji.resolve(false), IconSet::Emoji);
+    }
 }
@@ -3,9 +3,9 @@
 //! Provides layout calculations that adapt to terminal width,
 //! optimized for AI pair programming workflows with narrow terminals.
 
-use ratatui::layout::Rect;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
-use crate::core::UiDensity;
+use crate::core::{PreviewPosition, UiDensity};
 
 /// Tree column configuration
 #[derive(Debug, Clone)]
@@ -235,6 +235,19 @@ impl LayoutEngine {
         }
     }
 
+    /// Preview share of the split in percent (0 = no side preview)
+    ///
+    /// A non-zero `split` overrides the width-based ratio.
+    pub fn preview_percent(&self, preview_enabled: bool, split: u16) -> u16 {
+        if !self.should_show_preview(preview_enabled) {
+            return 0;
+        }
+        match split {
+            0 => self.split_ratio(true).1,
+            pct => pct,
+        }
+    }
+
     /// Check if preview should be visible given current settings
     pub fn should_show_preview(&self, preview_enabled: bool) -> bool {
         if !preview_enabled {
@@ -264,6 +277,47 @@ impl LayoutEngine {
     }
 }
 
+/// Layout direction and constraints for the tree/preview split
+///
+/// Constraints are in screen order, so the preview comes first when it sits
+/// left of or above the tree.
+pub fn preview_split_constraints(
+    position: PreviewPosition,
+    preview_pct: u16,
+) -> (Direction, [Constraint; 2]) {
+    let preview = Constraint::Percentage(preview_pct);
+    let tree = Constraint::Percentage(100 - preview_pct.min(100));
+    let direction = if position.is_stacked() {
+        Direction::Vertical
+    } else {
+        Direction::Horizontal
+    };
+    match position {
+        PreviewPosition::Right | PreviewPosition::Bottom => (direction, [tree, preview]),
+        PreviewPosition::Left | PreviewPosition::Top => (direction, [preview, tree]),
+    }
+}
+
+/// Split an area into the tree and the preview (None when `preview_pct` is 0)
+pub fn split_preview(
+    area: Rect,
+    position: PreviewPosition,
+    preview_pct: u16,
+) -> (Rect, Option<Rect>) {
+    if preview_pct == 0 {
+        return (area, None);
+    }
+    let (direction, constraints) = preview_split_constraints(position, preview_pct);
+    let chunks = Layout::default()
+        .direction(direction)
+        .constraints(constraints)
+        .split(area);
+    match position {
+        PreviewPosition::Right | PreviewPosition::Bottom => (chunks[0], Some(chunks[1])),
+        PreviewPosition::Left | PreviewPosition::Top => (chunks[1], Some(chunks[0])),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -329,6 +383,48 @@ mod tests {
         assert_eq!(engine.split_ratio(true), (100, 0)); // No preview in ultra
     }
 
+    #[test]
+    fn test_preview_percent_override() {
+        let engine = LayoutEngine::new(100, 24);
+        assert_eq!(engine.preview_percent(true, 0), 60);
+        assert_eq!(engine.preview_percent(true, 30), 30);
+        assert_eq!(engine.preview_percent(false, 30), 0);
+
+        let engine = LayoutEngine::new(30, 24);
+        assert_eq!(engine.preview_percent(true, 30), 0);
+    }
+
+    #[test]
+    fn test_preview_split_constraints() {
+        let (direction, constraints) = preview_split_constraints(PreviewPosition::Right, 60);
+        assert_eq!(direction, Direction::Horizontal);
+        assert_eq!(
+            constraints,
+            [Constraint::Percentage(40), Constraint::Percentage(60)]
+        );
+
+        let (direction, constraints) = preview_split_constraints(PreviewPosition::Top, 30);
+        assert_eq!(direction, Direction::Vertical);
+        assert_eq!(
+            constraints,
+            [Constraint::Percentage(30), Constraint::Percentage(70)]
+        );
+
+        let area = Rect::new(0, 0, 100, 40);
+        let (tree, preview) = split_preview(area, PreviewPosition::Left, 40);
+        assert_eq!(preview, Some(Rect::new(0, 0, 40, 40)));
+        assert_eq!(tree, Rect::new(40, 0, 60, 40));
+
+        let (tree, preview) = split_preview(area, PreviewPosition::Bottom, 25);
+        assert_eq!(tree, Rect::new(0, 0, 100, 30));
+        assert_eq!(preview, Some(Rect::new(0, 30, 100, 10)));
+
+        assert_eq!(
+            split_preview(area, PreviewPosition::Bottom, 0),
+            (area, None)
+        );
+    }
+
     #[test]
     fn test_peek_preview_lines() {
         assert_eq!(UiDensity::Ultra.peek_preview_lines(), 2);
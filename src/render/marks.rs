@@ -0,0 +1,75 @@
+//! Marks overview popup rendering.
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem},
+    Frame,
+};
+
+use super::preview::format_size;
+use crate::core::{AppState, ViewMode};
+
+/// Render marked paths overview popup (M)
+pub fn render_marks_overview_popup(frame: &mut Frame, state: &AppState) {
+    let ViewMode::MarksOverview { selected } = &state.mode else {
+        return;
+    };
+
+    let area = frame.area();
+    let width = area.width.saturating_sub(6).clamp(30, 80);
+    let height = area.height.saturating_sub(6).clamp(8, 20);
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+    let popup = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, popup);
+
+    // Keep the selected mark visible when the list is longer than the popup
+    let max_items = (height.saturating_sub(2) as usize).max(1);
+    let offset = selected.saturating_sub(max_items - 1);
+
+    let marks = state.sorted_marks();
+    let items: Vec<ListItem> = marks
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(max_items)
+        .map(|(idx, path)| {
+            let style = if idx == *selected {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let mut text = path
+                .strip_prefix(&state.root)
+                .unwrap_or(path)
+                .display()
+                .to_string();
+            if path.is_dir() {
+                text.push('/');
+            }
+            ListItem::new(Line::from(vec![Span::styled(text, style)]))
+        })
+        .collect();
+
+    let size = state
+        .selection_summary
+        .map(|summary| {
+            let approx = if summary.truncated { "+" } else { "" };
+            format!(", {}{}", format_size(summary.bytes), approx)
+        })
+        .unwrap_or_default();
+    let title = format!(" Marks ({}{}) ", marks.len(), size);
+    let widget = List::new(items).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    frame.render_widget(widget, popup);
+}
@@ -1,11 +1,18 @@
 //! Render module - UI rendering
 
 pub mod bulk_rename;
+pub mod command_output;
+pub mod content_search;
 pub mod fuzzy;
 pub mod history;
 pub mod icons;
 pub mod layout;
+pub mod marks;
+pub mod oplog;
 pub mod preview;
+pub mod recent;
+pub mod scrollbar;
+pub mod stash;
 pub mod status;
 pub mod tabs;
 pub mod terminal;
@@ -13,24 +20,37 @@ pub mod theme;
 pub mod tree;
 
 pub use bulk_rename::render_bulk_rename_dialog;
-pub use fuzzy::{collect_paths, fuzzy_match, render_fuzzy_finder, FuzzyMatch};
+pub use command_output::render_command_output_popup;
+pub use content_search::render_content_search_popup;
+pub use fuzzy::{
+    collect_paths, collect_paths_with, fuzzy_match, fuzzy_match_with_case, fuzzy_scores,
+    render_fuzzy_finder, FuzzyMatch,
+};
 pub use history::render_ai_history_popup;
 pub use icons::get_icon;
-pub use layout::{LayoutEngine, StatusLayout, TreeColumns};
+pub use layout::{split_preview, LayoutEngine, StatusLayout, TreeColumns};
+pub use marks::render_marks_overview_popup;
+pub use oplog::render_operation_history_popup;
 pub use preview::{
-    calculate_centered_image_area, find_pdftoppm, is_archive_file, is_binary_file, is_image_file,
-    is_pdf_file, is_tar_gz_file, is_text_file, render_archive_preview, render_custom_preview,
-    render_diff_preview, render_directory_info, render_hex_preview, render_image_preview,
-    render_pdf_preview, render_text_preview, render_video_preview, ArchiveEntry, ArchivePreview,
-    CustomPreview, DiffPreview, DirectoryInfo, HexPreview, ImagePreview, PdfPreview, StyledLine,
-    StyledSegment, TextPreview, VideoPreview,
+    calculate_centered_image_area, find_pdftoppm, find_pdftotext, find_preview_command,
+    find_svg_rasterizer, is_archive_file, is_binary_file, is_csv_file, is_image_file, is_pdf_file,
+    is_svg_file, is_tar_gz_file, is_text_file, rasterize_svg, render_archive_preview,
+    render_csv_preview, render_custom_preview, render_diff_preview, render_directory_info,
+    render_hex_preview, render_image_preview, render_pdf_preview, render_text_preview,
+    render_video_preview, set_preview_borders, svg_raster_size, ArchiveEntry, ArchivePreview,
+    BlamePreview, CsvPreview, CustomPreview, DiffPreview, DirSizeJob, DirectoryInfo, HexPreview,
+    ImagePreview, LargeText, PdfPreview, PdfTextPreview, PdfView, StyledLine, StyledSegment,
+    TextPreview, TextViewOptions, VideoPreview, LARGE_TEXT_THRESHOLD,
 };
 pub use ratatui_image::picker::Picker;
 pub use ratatui_image::FontSize;
+pub use recent::{recent_matches, render_recent_files_popup};
+pub use scrollbar::{render_scrollbar, scrollbar_state};
+pub use stash::render_git_stash_popup;
 pub use status::{render_help_popup, render_input_popup, render_status_bar};
 pub use tabs::render_tab_bar;
 pub use terminal::{RecommendedProtocol, TerminalBrand};
-pub use theme::{parse_color, theme, Theme, ThemeFile};
+pub use theme::{parse_color, reload_theme, theme, Theme, ThemeFile};
 pub use tree::{render_tree, visible_height};
 
 /// Create an image picker for protocol detection
@@ -48,6 +68,8 @@ pub use tree::{render_tree, visible_height};
 /// - `halfblocks`: Force halfblock rendering (most compatible)
 /// - `chafa`: Force Chafa rendering (requires `chafa` feature and libchafa)
 /// - `sixel`, `kitty`, `iterm2`: Force specific protocol
+/// - `command`: Render with the external image command (halfblocks fallback);
+///   the terminal is queried once here for sixel support
 pub fn create_image_picker() -> Option<Picker> {
     use ratatui_image::picker::ProtocolType;
 
@@ -55,6 +77,15 @@ pub fn create_image_picker() -> Option<Picker> {
     if let Ok(protocol) = std::env::var("FILEVIEW_IMAGE_PROTOCOL") {
         match protocol.to_lowercase().as_str() {
             "halfblocks" | "half" => return Some(Picker::halfblocks()),
+            "command" => {
+                let sixel = matches!(
+                    TerminalBrand::detect().recommended_protocol(),
+                    RecommendedProtocol::Sixel
+                ) || Picker::from_query_stdio()
+                    .is_ok_and(|picker| picker.protocol_type() == ProtocolType::Sixel);
+                preview::configure_command_sixel(sixel);
+                return Some(Picker::halfblocks());
+            }
             "chafa" => {
                 return try_chafa_picker().or_else(|| Some(Picker::halfblocks()));
             }
@@ -0,0 +1,115 @@
+//! Operation history popup rendering.
+
+use std::path::Path;
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem},
+    Frame,
+};
+
+use super::status::format_relative_time;
+use crate::action::Operation;
+use crate::core::{AppState, ViewMode};
+
+/// Width reserved for the relative time column
+const TIME_WIDTH: usize = 10;
+
+/// One history line: when the operation ran and what it did
+pub fn operation_line(operation: &Operation, root: &Path) -> String {
+    format!(
+        "{:<width$}{}{}",
+        format_relative_time(operation.time),
+        operation.summary(root),
+        if operation.undone { " (undone)" } else { "" },
+        width = TIME_WIDTH
+    )
+}
+
+/// Render operation history popup (U)
+pub fn render_operation_history_popup(frame: &mut Frame, state: &AppState) {
+    let ViewMode::OperationHistory { selected } = &state.mode else {
+        return;
+    };
+
+    let area = frame.area();
+    let width = area.width.saturating_sub(6).clamp(30, 100);
+    let height = area.height.saturating_sub(6).clamp(8, 20);
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+    let popup = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, popup);
+
+    // Keep the selected entry visible when the list is longer than the popup
+    let max_items = (height.saturating_sub(2) as usize).max(1);
+    let offset = selected.saturating_sub(max_items - 1);
+
+    let items: Vec<ListItem> = state
+        .operation_log
+        .entries()
+        .iter()
+        .rev()
+        .enumerate()
+        .skip(offset)
+        .take(max_items)
+        .map(|(idx, operation)| {
+            let style = if idx == *selected {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let text = operation_line(operation, &state.root);
+            ListItem::new(Line::from(vec![Span::styled(text, style)]))
+        })
+        .collect();
+
+    let title = format!(" Operation History ({}) ", state.operation_log.len());
+    let widget = List::new(items).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    frame.render_widget(widget, popup);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::OperationKind;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_operation_line_per_entry() {
+        let root = PathBuf::from("/project");
+        let mut state = AppState::new(root.clone());
+        state.log_operation(OperationKind::Create, vec![], vec![root.join("notes.md")]);
+        state.log_operation(
+            OperationKind::Rename,
+            vec![root.join("notes.md")],
+            vec![root.join("todo.md")],
+        );
+        state.log_operation(OperationKind::Delete, vec![root.join("todo.md")], vec![]);
+
+        let lines: Vec<String> = state
+            .operation_log
+            .entries()
+            .iter()
+            .map(|op| operation_line(op, &root))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "Just now  Create notes.md",
+                "Just now  Rename notes.md -> todo.md",
+                "Just now  Delete todo.md",
+            ]
+        );
+    }
+}
@@ -6,12 +6,13 @@ use ratatui::{
     layout::Rect,
     style::{Color, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::Paragraph,
     Frame,
 };
 
 use super::common::{
-    format_size, get_border_style, truncate_entry_name, unix_timestamp_to_date, ARCHIVE_MAX_ENTRIES,
+    format_size, preview_block, preview_content_area, truncate_entry_name, unix_timestamp_to_date,
+    ARCHIVE_MAX_ENTRIES,
 };
 
 /// Archive entry information
@@ -160,7 +161,7 @@ pub fn render_archive_preview(
     title: &str,
     focused: bool,
 ) {
-    let visible_height = area.height.saturating_sub(2) as usize;
+    let visible_height = preview_content_area(area).height as usize;
     let separator = "─".repeat(area.width.saturating_sub(4) as usize);
 
     let mut lines: Vec<Line> = Vec::new();
@@ -227,12 +228,7 @@ pub fn render_archive_preview(
         ]));
     }
 
-    let widget = Paragraph::new(lines).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(format!(" {} ", title))
-            .border_style(get_border_style(focused)),
-    );
+    let widget = Paragraph::new(lines).block(preview_block(format!(" {} ", title), focused));
 
     frame.render_widget(widget, area);
 }
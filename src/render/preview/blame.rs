@@ -0,0 +1,133 @@
+//! Git blame gutter for the text preview
+
+use std::sync::Arc;
+
+use ratatui::{
+    style::{Color, Style},
+    text::Span,
+};
+use unicode_width::UnicodeWidthChar;
+
+use super::common::unix_timestamp_to_date;
+use crate::git::BlameLine;
+
+/// Columns of the author name in the gutter
+const AUTHOR_WIDTH: usize = 12;
+
+/// Blame shown beside a text preview
+#[derive(Debug, Clone)]
+pub struct BlamePreview {
+    /// Last commit of each source line
+    pub lines: Arc<Vec<BlameLine>>,
+}
+
+impl BlamePreview {
+    /// Create a blame gutter from the lines of `git blame`
+    pub fn new(lines: Arc<Vec<BlameLine>>) -> Self {
+        Self { lines }
+    }
+
+    /// Width of the gutter: hash, author, date and a separating space
+    pub fn gutter_width(&self) -> usize {
+        8 + 1 + AUTHOR_WIDTH + 1 + 10 + 1
+    }
+
+    /// Gutter of source line `idx`
+    ///
+    /// Only the first line of a run from the same commit (or the top line
+    /// shown) is annotated, so commit boundaries stand out.
+    pub fn gutter(&self, idx: usize, top: usize) -> Span<'static> {
+        let blank = || Span::raw(" ".repeat(self.gutter_width()));
+        let Some(line) = self.lines.get(idx) else {
+            return blank();
+        };
+        let repeated = idx > top
+            && self
+                .lines
+                .get(idx - 1)
+                .is_some_and(|prev| prev.hash == line.hash);
+        if repeated {
+            return blank();
+        }
+        let uncommitted = line.hash.bytes().all(|b| b == b'0');
+        let style = if uncommitted {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let author = fit_author(if uncommitted {
+            "uncommitted"
+        } else {
+            &line.author
+        });
+        let date = if uncommitted {
+            String::new()
+        } else {
+            unix_timestamp_to_date(line.time)
+        };
+        Span::styled(format!("{:8} {} {:10} ", line.hash, author, date), style)
+    }
+}
+
+/// Cut or pad an author name to exactly [`AUTHOR_WIDTH`] display columns
+fn fit_author(author: &str) -> String {
+    let mut out = String::new();
+    let mut used = 0;
+    for c in author.chars().filter(|c| !c.is_control()) {
+        let w = c.width().unwrap_or(0);
+        if used + w > AUTHOR_WIDTH {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    out.push_str(&" ".repeat(AUTHOR_WIDTH - used));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blame_line(hash: &str, author: &str) -> BlameLine {
+        BlameLine {
+            hash: hash.to_string(),
+            author: author.to_string(),
+            time: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn test_gutter_annotates_commit_runs() {
+        let blame = BlamePreview::new(Arc::new(vec![
+            blame_line("1234abcd", "Alice Anderson-Longname"),
+            blame_line("1234abcd", "Alice Anderson-Longname"),
+            blame_line("00000000", "Not Committed Yet"),
+        ]));
+        let first = blame.gutter(0, 0).content.into_owned();
+        assert_eq!(first, "1234abcd Alice Anders 2023-11-14 ");
+        assert_eq!(first.len(), blame.gutter_width());
+        assert!(blame.gutter(1, 0).content.trim().is_empty());
+        // The top line shown is always annotated
+        assert_eq!(blame.gutter(1, 1).content, first);
+        assert!(blame.gutter(2, 0).content.contains("uncommitted"));
+        assert!(blame.gutter(9, 0).content.trim().is_empty());
+    }
+
+    #[test]
+    fn test_gutter_fits_wide_author_names() {
+        use unicode_width::UnicodeWidthStr;
+
+        let blame = BlamePreview::new(Arc::new(vec![
+            blame_line("1234abcd", "山田太郎"),
+            blame_line("5678abcd", "田中花子まりこ"),
+        ]));
+        for idx in 0..2 {
+            let gutter = blame.gutter(idx, 0).content.into_owned();
+            assert_eq!(gutter.width(), blame.gutter_width());
+        }
+        // A wide character that does not fit whole is left out
+        assert_eq!(fit_author("田中花子まりこ"), "田中花子まり");
+        assert_eq!(fit_author("a田中花子まりこ"), "a田中花子ま ");
+    }
+}
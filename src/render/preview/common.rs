@@ -1,6 +1,14 @@
 //! Common utilities for preview rendering
 
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ratatui::layout::Rect;
 use ratatui::style::{Color, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders};
+
+/// Whether preview panes are framed by a border and title
+static PREVIEW_BORDERS: AtomicBool = AtomicBool::new(true);
 
 /// Maximum depth for recursive directory size calculation (for performance)
 pub const MAX_DIR_SIZE_DEPTH: u32 = 3;
@@ -26,6 +34,44 @@ pub fn get_border_style(focused: bool) -> Style {
     }
 }
 
+/// Frame preview panes with a border and title, or draw them bare
+pub fn set_preview_borders(enabled: bool) {
+    PREVIEW_BORDERS.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether preview panes are framed (see [`set_preview_borders`])
+pub fn preview_borders() -> bool {
+    PREVIEW_BORDERS.load(Ordering::Relaxed)
+}
+
+/// Block around a preview pane
+///
+/// Bordered and titled by default; without borders the title is dropped
+/// too, so the content fills the whole area.
+pub fn preview_block<'a>(title: impl Into<Line<'a>>, focused: bool) -> Block<'a> {
+    framed_block(title, focused, preview_borders())
+}
+
+fn framed_block<'a>(title: impl Into<Line<'a>>, focused: bool, borders: bool) -> Block<'a> {
+    if borders {
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(get_border_style(focused))
+    } else {
+        Block::default()
+    }
+}
+
+/// Area left for content inside a preview pane's block
+pub fn preview_content_area(area: Rect) -> Rect {
+    content_area(area, preview_borders())
+}
+
+fn content_area(area: Rect, borders: bool) -> Rect {
+    framed_block("", false, borders).inner(area)
+}
+
 /// Truncate archive entry name if too long
 pub fn truncate_entry_name(name: String) -> String {
     if name.len() > MAX_ENTRY_NAME_LEN {
@@ -143,6 +189,16 @@ fn calculate_dir_size_recursive(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_borderless_preview_gains_content_area() {
+        let area = Rect::new(0, 0, 40, 12);
+        let framed = content_area(area, true);
+        let bare = content_area(area, false);
+        assert_eq!((framed.width, framed.height), (38, 10));
+        assert_eq!(bare, area);
+        assert!(bare.width * bare.height > framed.width * framed.height);
+    }
+
     #[test]
     fn test_format_size_bytes() {
         assert_eq!(format_size(0), "0 B");
@@ -0,0 +1,388 @@
+//! Tabular preview for CSV and TSV files
+
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use unicode_width::UnicodeWidthChar;
+
+use super::common::{preview_block, preview_borders, preview_content_area};
+use crate::render::scrollbar::render_scrollbar;
+
+/// Bytes of a CSV file parsed at most, whatever the row budget
+const CSV_MAX_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Rows sampled to size the columns
+const WIDTH_SAMPLE_ROWS: usize = 200;
+
+/// Widest a column is drawn; longer cells end in `…`
+const MAX_COLUMN_WIDTH: usize = 40;
+
+/// Separator drawn between columns
+const COLUMN_SEPARATOR: &str = " │ ";
+
+/// CSV/TSV preview content
+pub struct CsvPreview {
+    /// First record, drawn above the rows
+    pub header: Vec<String>,
+    /// Remaining records
+    pub rows: Vec<Vec<String>>,
+    /// Display width of each column
+    pub widths: Vec<usize>,
+    /// Whether the row budget stopped parsing before the end of the file
+    pub truncated: bool,
+    /// Scroll position (in rows)
+    pub scroll: usize,
+    /// First column shown (horizontal scroll)
+    pub column: usize,
+}
+
+impl CsvPreview {
+    /// Load at most `max_rows` records after the header from a file
+    ///
+    /// `.tsv` and `.tab` files are split on tabs, everything else on commas.
+    pub fn load(path: &Path, max_rows: usize) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let (records, truncated) = parse_records(
+            BufReader::new(file),
+            delimiter_for(path),
+            max_rows + 1,
+            CSV_MAX_BYTES,
+        )?;
+        Ok(Self::from_records(records, truncated))
+    }
+
+    fn from_records(mut records: Vec<Vec<String>>, truncated: bool) -> Self {
+        let header = if records.is_empty() {
+            Vec::new()
+        } else {
+            records.remove(0)
+        };
+        let columns = records
+            .iter()
+            .map(Vec::len)
+            .chain([header.len()])
+            .max()
+            .unwrap_or(0);
+        let mut widths = vec![0; columns];
+        for record in std::iter::once(&header).chain(records.iter().take(WIDTH_SAMPLE_ROWS)) {
+            for (width, cell) in widths.iter_mut().zip(record) {
+                *width = (*width).max(cell_width(cell).min(MAX_COLUMN_WIDTH));
+            }
+        }
+        Self {
+            header,
+            rows: records,
+            widths,
+            truncated,
+            scroll: 0,
+            column: 0,
+        }
+    }
+
+    /// Get the number of data rows
+    pub fn line_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Number of columns
+    pub fn column_count(&self) -> usize {
+        self.widths.len()
+    }
+
+    /// Move the first shown column, keeping at least one column visible
+    pub fn scroll_columns(&mut self, delta: isize) {
+        let max = self.column_count().saturating_sub(1);
+        self.column = self.column.saturating_add_signed(delta).min(max);
+    }
+
+    /// Render one record from the first shown column on
+    fn render_record(&self, record: &[String], style: Style) -> Line<'static> {
+        let separator = Style::default().fg(Color::DarkGray);
+        let mut spans = Vec::new();
+        for (i, width) in self.widths.iter().enumerate().skip(self.column) {
+            if i > self.column {
+                spans.push(Span::styled(COLUMN_SEPARATOR, separator));
+            }
+            let cell = record.get(i).map(String::as_str).unwrap_or("");
+            spans.push(Span::styled(fit_cell(cell, *width), style));
+        }
+        Line::from(spans)
+    }
+}
+
+/// Check if a file is previewed as a table
+pub fn is_csv_file(path: &Path) -> bool {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+    matches!(ext.as_deref(), Some("csv" | "tsv" | "tab"))
+}
+
+fn delimiter_for(path: &Path) -> char {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+    match ext.as_deref() {
+        Some("tsv" | "tab") => '\t',
+        _ => ',',
+    }
+}
+
+/// Read records until `limit` are parsed or `max_bytes` are read
+///
+/// Quoted fields may hold the delimiter, newlines, and `""` for a quote.
+/// Returns whether input was left unread; a record cut short by the byte
+/// budget is dropped.
+fn parse_records(
+    reader: impl BufRead,
+    delimiter: char,
+    limit: usize,
+    max_bytes: u64,
+) -> anyhow::Result<(Vec<Vec<String>>, bool)> {
+    let mut reader = reader.take(max_bytes);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut buf = Vec::new();
+
+    loop {
+        buf.clear();
+        let read = reader.read_until(b'\n', &mut buf)?;
+        let over_budget = reader.limit() == 0 && !reader.get_mut().fill_buf()?.is_empty();
+        if read == 0 {
+            if over_budget {
+                return Ok((records, true));
+            }
+            break;
+        }
+        if records.len() == limit || (over_budget && !buf.ends_with(b"\n")) {
+            return Ok((records, true));
+        }
+        let line = String::from_utf8_lossy(&buf);
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if in_quotes {
+                match c {
+                    '"' if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    '"' => in_quotes = false,
+                    _ => field.push(c),
+                }
+            } else if c == '"' && field.is_empty() {
+                in_quotes = true;
+            } else if c == delimiter {
+                record.push(std::mem::take(&mut field));
+            } else if c == '\n' || (c == '\r' && chars.peek() == Some(&'\n')) {
+                // End of record; the '\n' after a '\r' is consumed next
+            } else {
+                field.push(c);
+            }
+        }
+        if !in_quotes {
+            record.push(std::mem::take(&mut field));
+            // Blank lines hold no record
+            if record.len() > 1 || !record[0].is_empty() {
+                records.push(std::mem::take(&mut record));
+            } else {
+                record.clear();
+            }
+        }
+    }
+    // An unterminated quote keeps what was read
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok((records, false))
+}
+
+fn cell_width(cell: &str) -> usize {
+    cell.chars()
+        .map(|c| display_char(c).width().unwrap_or(0))
+        .sum()
+}
+
+/// Control characters (newlines in quoted cells) are drawn as spaces
+fn display_char(c: char) -> char {
+    if c.is_control() {
+        ' '
+    } else {
+        c
+    }
+}
+
+/// Pad or cut a cell to exactly `width` display columns
+fn fit_cell(cell: &str, width: usize) -> String {
+    let mut out = String::new();
+    let mut used = 0;
+    let fits = cell_width(cell) <= width;
+    let room = if fits { width } else { width.saturating_sub(1) };
+    for c in cell.chars().map(display_char) {
+        let w = c.width().unwrap_or(0);
+        if used + w > room {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    if !fits && width > 0 {
+        out.push('…');
+        used += 1;
+    }
+    out.push_str(&" ".repeat(width.saturating_sub(used)));
+    out
+}
+
+/// Render CSV preview as an aligned table under a highlighted header
+pub fn render_csv_preview(
+    frame: &mut Frame,
+    preview: &CsvPreview,
+    area: Rect,
+    title: &str,
+    focused: bool,
+) {
+    let visible_height = (preview_content_area(area).height as usize).saturating_sub(1);
+    let header_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+
+    let mut lines = vec![preview.render_record(&preview.header, header_style)];
+    lines.extend(
+        preview
+            .rows
+            .iter()
+            .skip(preview.scroll)
+            .take(visible_height)
+            .map(|row| preview.render_record(row, Style::default())),
+    );
+
+    let rows = if preview.truncated {
+        format!("first {} rows", preview.rows.len())
+    } else {
+        format!("{} rows", preview.rows.len())
+    };
+    let columns = if preview.column > 0 {
+        format!("col {}/{}", preview.column + 1, preview.column_count())
+    } else {
+        format!("{} cols", preview.column_count())
+    };
+    let widget = Paragraph::new(lines).block(preview_block(
+        format!(" {} ({}, {}) ", title, rows, columns),
+        focused,
+    ));
+
+    frame.render_widget(widget, area);
+    if preview_borders() {
+        render_scrollbar(
+            frame,
+            area,
+            preview.rows.len(),
+            visible_height,
+            preview.scroll,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str, limit: usize) -> (Vec<Vec<String>>, bool) {
+        parse_records(input.as_bytes(), ',', limit, CSV_MAX_BYTES).unwrap()
+    }
+
+    #[test]
+    fn test_quoted_fields_keep_commas_quotes_and_newlines() {
+        let (records, truncated) = parse(
+            "name,note\r\n\"Doe, Jane\",\"says \"\"hi\"\"\"\nbob,\"two\nlines\"\nlast,x",
+            10,
+        );
+        assert!(!truncated);
+        assert_eq!(
+            records,
+            vec![
+                vec!["name", "note"],
+                vec!["Doe, Jane", "says \"hi\""],
+                vec!["bob", "two\nlines"],
+                vec!["last", "x"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_row_budget_stops_parsing() {
+        let input: String = (0..100).map(|n| format!("{},{}\n", n, n * 2)).collect();
+        let (records, truncated) = parse(&input, 11);
+        assert!(truncated);
+        assert_eq!(records.len(), 11);
+
+        let (records, truncated) = parse(&input, 100);
+        assert!(!truncated);
+        assert_eq!(records.len(), 100);
+    }
+
+    #[test]
+    fn test_byte_budget_stops_parsing() {
+        // One endless line stops at the budget and is not kept
+        let input = format!("a,b\n1,{}\n", "x".repeat(100));
+        let (records, truncated) = parse_records(input.as_bytes(), ',', 10, 20).unwrap();
+        assert!(truncated);
+        assert_eq!(records, vec![vec!["a", "b"]]);
+
+        // Input ending exactly at the budget is complete
+        let (records, truncated) = parse_records(&b"a,b\n1,2"[..], ',', 10, 7).unwrap();
+        assert!(!truncated);
+        assert_eq!(records.len(), 2);
+    }
+
+    #[test]
+    fn test_columns_sized_from_widest_cell_and_capped() {
+        let long = "y".repeat(MAX_COLUMN_WIDTH * 2);
+        let (records, _) = parse(&format!("id,name,extra\n1,alice\n22,{}\n", long), 10);
+        let preview = CsvPreview::from_records(records, false);
+        assert_eq!(preview.header, vec!["id", "name", "extra"]);
+        assert_eq!(preview.line_count(), 2);
+        assert_eq!(preview.widths, vec![2, MAX_COLUMN_WIDTH, 5]);
+    }
+
+    #[test]
+    fn test_fit_cell_pads_and_truncates() {
+        assert_eq!(fit_cell("ab", 4), "ab  ");
+        assert_eq!(fit_cell("abcdef", 4), "abc…");
+        assert_eq!(fit_cell("a\nb", 3), "a b");
+        assert_eq!(fit_cell("日本語", 5), "日本…");
+    }
+
+    #[test]
+    fn test_scroll_columns_clamps() {
+        let (records, _) = parse("a,b,c\n1,2,3\n", 10);
+        let mut preview = CsvPreview::from_records(records, false);
+        preview.scroll_columns(-1);
+        assert_eq!(preview.column, 0);
+        preview.scroll_columns(5);
+        assert_eq!(preview.column, 2);
+        let line = preview.render_record(&preview.rows[0], Style::default());
+        assert_eq!(line.to_string(), "3");
+    }
+
+    #[test]
+    fn test_tsv_detection() {
+        assert!(is_csv_file(Path::new("data.CSV")));
+        assert!(is_csv_file(Path::new("data.tsv")));
+        assert!(!is_csv_file(Path::new("data.txt")));
+        assert_eq!(delimiter_for(Path::new("data.tsv")), '\t');
+        assert_eq!(delimiter_for(Path::new("data.csv")), ',');
+    }
+}
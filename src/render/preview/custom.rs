@@ -1,19 +1,65 @@
 //! Custom preview from external command
+//!
+//! `[preview.custom]` commands are shown as plain text. `[preview.commands]`
+//! commands (e.g. `bat --color=always $f`) may print ANSI colors, which are
+//! kept as styled lines.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::{Command, Output};
 
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::Paragraph,
     Frame,
 };
 
-use super::common::get_border_style;
+use super::common::{preview_block, preview_content_area};
+use super::image::parse_ansi_lines;
+use crate::core::glob_match;
+
+/// Preview command configured for a file in `[preview.commands]`
+///
+/// Keys are globs matched against the file name (`*.md`, `Makefile`) or
+/// bare extensions (`rs`), both case-insensitive. When several keys match,
+/// the longest one wins.
+pub fn find_preview_command<'a>(
+    commands: &'a HashMap<String, String>,
+    path: &Path,
+) -> Option<&'a str> {
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+    let ext = path.extension().map(|e| e.to_string_lossy().to_lowercase());
+    commands
+        .iter()
+        .filter(|(key, _)| {
+            let key = key.to_lowercase();
+            if key.contains(['*', '?', '.']) || ext.is_none() {
+                glob_match(&key, &name)
+            } else {
+                ext.as_deref() == Some(key.as_str())
+            }
+        })
+        .max_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| b.cmp(a)))
+        .map(|(_, command)| command.as_str())
+}
+
+/// Run a command line through the platform shell
+fn run_shell(cmd: &str) -> std::io::Result<Output> {
+    if cfg!(target_os = "windows") {
+        Command::new("cmd").args(["/C", cmd]).output()
+    } else {
+        Command::new("sh").args(["-c", cmd]).output()
+    }
+}
 
 /// Custom preview content from external command
 pub struct CustomPreview {
-    /// Output lines from the command
+    /// Output lines from the command (ANSI sequences removed)
     pub lines: Vec<String>,
+    /// Colored output lines (empty = plain `lines`)
+    pub styled: Vec<Line<'static>>,
     /// The command that was executed
     pub command: String,
     /// Scroll position
@@ -25,42 +71,89 @@ impl CustomPreview {
     ///
     /// The command template can use $f as a placeholder for the file path.
     /// Security: File path is shell-escaped to prevent command injection.
-    pub fn execute(command_template: &str, file_path: &std::path::Path) -> anyhow::Result<Self> {
-        use std::process::Command;
-
-        // Security: Shell-escape the file path to prevent command injection
-        let escaped_path = Self::shell_escape(&file_path.display().to_string());
-
-        // Expand $f placeholder with escaped path
-        let cmd = command_template.replace("$f", &escaped_path);
-
-        // Execute command via shell
-        let output = if cfg!(target_os = "windows") {
-            Command::new("cmd").args(["/C", &cmd]).output()
-        } else {
-            Command::new("sh").args(["-c", &cmd]).output()
-        }?;
+    pub fn execute(command_template: &str, file_path: &Path) -> anyhow::Result<Self> {
+        let cmd = Self::expand(command_template, file_path);
+        let output = run_shell(&cmd)?;
 
         let stdout = String::from_utf8_lossy(&output.stdout);
         let lines: Vec<String> = stdout.lines().map(String::from).collect();
 
         Ok(Self {
             lines,
+            styled: Vec::new(),
             command: cmd,
             scroll: 0,
         })
     }
 
+    /// Execute a `[preview.commands]` command, keeping its ANSI colors
+    ///
+    /// Fails when the command exits unsuccessfully, prints nothing, or emits
+    /// terminal graphics, so the caller can fall back to the built-in preview.
+    pub fn execute_styled(command_template: &str, file_path: &Path) -> anyhow::Result<Self> {
+        let cmd = Self::expand(command_template, file_path);
+        let output = run_shell(&cmd)?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = stderr
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .map(String::from)
+                .unwrap_or_else(|| format!("exited with {}", output.status));
+            anyhow::bail!("{}", reason);
+        }
+        let preview = Self::from_ansi(&String::from_utf8_lossy(&output.stdout), &cmd)
+            .ok_or_else(|| anyhow::anyhow!("output contains terminal graphics"))?;
+        if preview.lines.is_empty() {
+            anyhow::bail!("no output");
+        }
+        Ok(preview)
+    }
+
+    /// Create a preview from ANSI-colored text (None for terminal graphics)
+    pub fn from_ansi(text: &str, source: &str) -> Option<Self> {
+        let styled = parse_ansi_lines(text)?;
+        let lines = styled
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        Some(Self {
+            lines,
+            styled,
+            command: source.to_string(),
+            scroll: 0,
+        })
+    }
+
+    /// Create a preview from already rendered text (e.g. returned by a plugin)
+    pub fn from_text(text: &str, source: &str) -> Self {
+        Self {
+            lines: text.lines().map(String::from).collect(),
+            styled: Vec::new(),
+            command: source.to_string(),
+            scroll: 0,
+        }
+    }
+
     /// Get the total number of lines
     pub fn line_count(&self) -> usize {
         self.lines.len()
     }
 
+    /// Expand `$f` with the shell-escaped file path
+    ///
+    /// Security: File path is shell-escaped to prevent command injection.
+    fn expand(command_template: &str, file_path: &Path) -> String {
+        let escaped_path = Self::shell_escape(&file_path.display().to_string());
+        command_template.replace("$f", &escaped_path)
+    }
+
     /// Shell-escape a string to prevent command injection
     ///
     /// On Unix: wraps in single quotes and escapes embedded single quotes
     /// On Windows: wraps in double quotes and escapes embedded double quotes
-    fn shell_escape(s: &str) -> String {
+    pub(super) fn shell_escape(s: &str) -> String {
         if cfg!(target_os = "windows") {
             // Windows: use double quotes and escape embedded quotes
             format!("\"{}\"", s.replace('"', "\\\""))
@@ -81,31 +174,84 @@ pub fn render_custom_preview(
     title: &str,
     focused: bool,
 ) {
-    let visible_height = area.height.saturating_sub(2) as usize;
+    let visible_height = preview_content_area(area).height as usize;
     let start = preview.scroll;
     let end = (start + visible_height).min(preview.lines.len());
 
-    let lines: Vec<Line> = preview.lines[start..end]
-        .iter()
-        .enumerate()
-        .map(|(i, line)| {
-            let line_num = start + i + 1;
-            Line::from(vec![
-                Span::styled(
-                    format!("{:4} ", line_num),
-                    Style::default().fg(Color::DarkGray),
-                ),
-                Span::raw(line.as_str()),
-            ])
-        })
-        .collect();
+    // Colored command output carries its own decoration; no line numbers
+    let lines: Vec<Line> = if !preview.styled.is_empty() {
+        let end = (start + visible_height).min(preview.styled.len());
+        preview.styled[start.min(end)..end].to_vec()
+    } else {
+        preview.lines[start..end]
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let line_num = start + i + 1;
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:4} ", line_num),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::raw(line.as_str()),
+                ])
+            })
+            .collect()
+    };
 
-    let widget = Paragraph::new(lines).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(format!(" {} ", title))
-            .border_style(get_border_style(focused)),
-    );
+    let widget = Paragraph::new(lines).block(preview_block(format!(" {} ", title), focused));
 
     frame.render_widget(widget, area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+
+    #[test]
+    fn test_find_preview_command() {
+        let commands: HashMap<String, String> = [
+            ("rs", "bat --color=always $f"),
+            ("*.md", "glow $f"),
+            ("*.test.md", "cat $f"),
+            ("Makefile", "bat -l make $f"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let find = |path: &str| find_preview_command(&commands, Path::new(path));
+        assert_eq!(find("src/main.rs"), Some("bat --color=always $f"));
+        assert_eq!(find("LIB.RS"), Some("bat --color=always $f"));
+        assert_eq!(find("docs/README.md"), Some("glow $f"));
+        // The longer glob is more specific
+        assert_eq!(find("api.test.md"), Some("cat $f"));
+        assert_eq!(find("Makefile"), Some("bat -l make $f"));
+        assert_eq!(find("notes.txt"), None);
+        assert_eq!(find("trs"), None);
+    }
+
+    #[test]
+    fn test_from_ansi_keeps_colors() {
+        let preview = CustomPreview::from_ansi("\x1b[31mfn\x1b[0m main() {}\n", "bat").unwrap();
+
+        assert_eq!(preview.lines, vec!["fn main() {}".to_string()]);
+        assert_eq!(preview.styled.len(), 1);
+        let spans = &preview.styled[0].spans;
+        assert_eq!(spans[0].content, "fn");
+        assert_eq!(spans[0].style.fg, Some(Color::Indexed(1)));
+        assert_eq!(spans[1].content, " main() {}");
+        assert_eq!(spans[1].style.fg, None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_styled_fails_for_fallback() {
+        let path = Path::new("/tmp/x.rs");
+        assert!(CustomPreview::execute_styled("echo oops >&2; exit 1", path).is_err());
+        assert!(CustomPreview::execute_styled("true", path).is_err());
+        let preview = CustomPreview::execute_styled("printf 'x %s' $f", path).unwrap();
+        assert_eq!(preview.lines, vec!["x /tmp/x.rs".to_string()]);
+    }
+}
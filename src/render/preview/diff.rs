@@ -4,11 +4,11 @@ use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::Paragraph,
     Frame,
 };
 
-use super::common::get_border_style;
+use super::common::{preview_block, preview_content_area};
 
 /// Git diff preview content
 pub struct DiffPreview {
@@ -40,7 +40,7 @@ pub fn render_diff_preview(
 ) {
     use crate::git::DiffLine;
 
-    let visible_height = area.height.saturating_sub(2) as usize;
+    let visible_height = preview_content_area(area).height as usize;
     let start = preview.scroll;
     let end = (start + visible_height).min(preview.diff.lines.len());
 
@@ -75,17 +75,18 @@ pub fn render_diff_preview(
         .collect();
 
     // Title with additions/deletions info
+    let base = preview
+        .diff
+        .base
+        .as_ref()
+        .map(|rev| format!(" vs {}", rev))
+        .unwrap_or_default();
     let full_title = format!(
-        " {} (+{} -{}) ",
-        title, preview.diff.additions, preview.diff.deletions
+        " {}{} (+{} -{}) ",
+        title, base, preview.diff.additions, preview.diff.deletions
     );
 
-    let widget = Paragraph::new(lines).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(full_title)
-            .border_style(get_border_style(focused)),
-    );
+    let widget = Paragraph::new(lines).block(preview_block(full_title, focused));
 
     frame.render_widget(widget, area);
 }
@@ -1,16 +1,159 @@
 //! Directory info preview
+//!
+//! The counts of a directory's own entries are read right away. The
+//! recursive file count and size come from a [`DirSizeJob`] walking the tree
+//! on a worker thread: the panel shows the running totals marked as
+//! in progress, and the final totals once the walk is done. Dropping the job
+//! (when the focus moves on) stops the walk.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::Paragraph,
     Frame,
 };
 
-use super::common::{calculate_dir_size, format_size, get_border_style};
+use super::common::{format_size, preview_block};
+use crate::render::status::format_count;
+
+/// How often a running walk reports its totals
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Recursive totals of a directory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DirTotals {
+    /// Files (non-directories) below the directory
+    pub files: usize,
+    /// Subdirectories below the directory
+    pub dirs: usize,
+    /// Size of those files in bytes
+    pub bytes: u64,
+}
+
+/// Walk `path` recursively, adding up its files, subdirectories and bytes
+///
+/// Symlinks are counted as files and not followed; unreadable directories
+/// are skipped. `report` receives the running totals every
+/// [`PROGRESS_INTERVAL`]. Returns None if `cancel` was set before the walk
+/// finished.
+pub fn walk_dir_totals(
+    path: &Path,
+    cancel: &AtomicBool,
+    mut report: impl FnMut(DirTotals),
+) -> Option<DirTotals> {
+    let mut totals = DirTotals::default();
+    let mut pending = vec![path.to_path_buf()];
+    let mut last_report = Instant::now();
+
+    while let Some(dir) = pending.pop() {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            match entry.file_type() {
+                Ok(kind) if kind.is_dir() => {
+                    totals.dirs += 1;
+                    pending.push(entry.path());
+                }
+                Ok(_) => {
+                    totals.files += 1;
+                    totals.bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+                }
+                Err(_) => {}
+            }
+        }
+        if last_report.elapsed() >= PROGRESS_INTERVAL {
+            report(totals);
+            last_report = Instant::now();
+        }
+    }
+    Some(totals)
+}
+
+/// A recursive size count running on a worker thread
+pub struct DirSizeJob {
+    /// Directory being counted
+    pub path: PathBuf,
+    /// Totals received so far (only ever grow)
+    pub totals: DirTotals,
+    /// Set once the final totals arrived
+    pub complete: bool,
+    cancel: Arc<AtomicBool>,
+    events: Receiver<(DirTotals, bool)>,
+}
+
+impl DirSizeJob {
+    /// Start counting `path` on a new worker thread
+    pub fn start(path: &Path) -> Self {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (tx, events) = mpsc::channel();
+
+        let worker_path = path.to_path_buf();
+        let worker_cancel = Arc::clone(&cancel);
+        thread::spawn(move || {
+            let progress = tx.clone();
+            let done = walk_dir_totals(&worker_path, &worker_cancel, |totals| {
+                let _ = progress.send((totals, false));
+            });
+            if let Some(totals) = done {
+                let _ = tx.send((totals, true));
+            }
+        });
+
+        Self {
+            path: path.to_path_buf(),
+            totals: DirTotals::default(),
+            complete: false,
+            cancel,
+            events,
+        }
+    }
+
+    /// Whether the walk is still running
+    pub fn is_running(&self) -> bool {
+        !self.complete && !self.cancel.load(Ordering::Relaxed)
+    }
+
+    /// Collect pending totals (returns true if anything changed)
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        loop {
+            match self.events.try_recv() {
+                Ok((totals, complete)) => {
+                    self.totals = totals;
+                    self.complete = complete;
+                    changed = true;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    // A walk that ended without final totals was cancelled
+                    if !self.complete {
+                        self.cancel.store(true, Ordering::Relaxed);
+                    }
+                    break;
+                }
+            }
+        }
+        changed
+    }
+}
+
+impl Drop for DirSizeJob {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
 
 /// Directory information for preview
 #[derive(Debug, Clone)]
@@ -23,12 +166,14 @@ pub struct DirectoryInfo {
     pub dir_count: usize,
     /// Number of hidden items
     pub hidden_count: usize,
-    /// Total size in bytes
-    pub total_size: u64,
+    /// Recursive totals counted so far
+    pub totals: DirTotals,
+    /// Whether `totals` is final
+    pub complete: bool,
 }
 
 impl DirectoryInfo {
-    /// Compute directory info from path
+    /// Count the directory's own entries (the recursive totals start empty)
     pub fn from_path(path: &Path) -> anyhow::Result<Self> {
         let name = path
             .file_name()
@@ -38,7 +183,6 @@ impl DirectoryInfo {
         let mut file_count = 0;
         let mut dir_count = 0;
         let mut hidden_count = 0;
-        let mut total_size = 0u64;
 
         if let Ok(entries) = std::fs::read_dir(path) {
             for entry in entries.flatten() {
@@ -52,13 +196,8 @@ impl DirectoryInfo {
                 if let Ok(metadata) = entry.metadata() {
                     if metadata.is_file() {
                         file_count += 1;
-                        total_size += metadata.len();
                     } else if metadata.is_dir() {
                         dir_count += 1;
-                        // Optionally calculate subdirectory size (can be slow for large dirs)
-                        if let Ok(dir_size) = calculate_dir_size(&entry.path()) {
-                            total_size += dir_size;
-                        }
                     }
                 }
             }
@@ -69,9 +208,34 @@ impl DirectoryInfo {
             file_count,
             dir_count,
             hidden_count,
-            total_size,
+            totals: DirTotals::default(),
+            complete: false,
         })
     }
+
+    /// Take the latest totals of a size count of this directory
+    pub fn update_totals(&mut self, job: &DirSizeJob) {
+        self.totals = job.totals;
+        self.complete = job.complete;
+    }
+}
+
+/// Text of the total size line, marked as in progress until the count is final
+fn totals_text(info: &DirectoryInfo) -> String {
+    let files = format!(
+        "{} file{}",
+        format_count(info.totals.files),
+        if info.totals.files == 1 { "" } else { "s" }
+    );
+    if info.complete {
+        format!("{} ({})", format_size(info.totals.bytes), files)
+    } else {
+        format!(
+            "calculating… {}, {} so far",
+            files,
+            format_size(info.totals.bytes)
+        )
+    }
 }
 
 /// Render directory info preview
@@ -116,20 +280,87 @@ pub fn render_directory_info(frame: &mut Frame, info: &DirectoryInfo, area: Rect
         Line::from(vec![
             Span::styled("  Total Size:   ", Style::default().fg(Color::DarkGray)),
             Span::styled(
-                format_size(info.total_size),
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
+                totals_text(info),
+                if info.complete {
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::ITALIC)
+                },
             ),
         ]),
     ];
 
-    let widget = Paragraph::new(lines).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(" Directory Info ")
-            .border_style(get_border_style(focused)),
-    );
+    let widget = Paragraph::new(lines).block(preview_block(" Directory Info ", focused));
 
     frame.render_widget(widget, area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// root/{a.txt(3), src/{b.rs(5), deep/{c.md(7), d(0)}}, empty/}
+    fn nested_fixture() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("src/deep")).unwrap();
+        fs::create_dir(root.join("empty")).unwrap();
+        fs::write(root.join("a.txt"), "abc").unwrap();
+        fs::write(root.join("src/b.rs"), "fn x;").unwrap();
+        fs::write(root.join("src/deep/c.md"), "# title").unwrap();
+        fs::write(root.join("src/deep/d"), "").unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_walk_dir_totals_nested() {
+        let temp = nested_fixture();
+        let mut reports = Vec::new();
+        let totals = walk_dir_totals(temp.path(), &AtomicBool::new(false), |t| reports.push(t));
+
+        let expected = DirTotals {
+            files: 4,
+            dirs: 3,
+            bytes: 15,
+        };
+        assert_eq!(totals, Some(expected));
+        // Running totals never shrink and never pass the final ones
+        for pair in reports.windows(2) {
+            assert!(pair[0].files <= pair[1].files && pair[0].bytes <= pair[1].bytes);
+        }
+        assert!(reports.iter().all(|t| t.files <= 4 && t.bytes <= 15));
+
+        assert_eq!(
+            walk_dir_totals(temp.path(), &AtomicBool::new(true), |_| {}),
+            None
+        );
+    }
+
+    #[test]
+    fn test_dir_size_job_reaches_final_totals() {
+        let temp = nested_fixture();
+        let mut info = DirectoryInfo::from_path(temp.path()).unwrap();
+        assert_eq!((info.file_count, info.dir_count), (1, 2));
+        assert_eq!(totals_text(&info), "calculating… 0 files, 0 B so far");
+
+        let mut job = DirSizeJob::start(temp.path());
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while job.is_running() && Instant::now() < deadline {
+            if job.poll() {
+                info.update_totals(&job);
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        assert!(info.complete);
+        assert_eq!(info.totals.files, 4);
+        assert_eq!(info.totals.bytes, 15);
+        assert_eq!(totals_text(&info), "15 B (4 files)");
+    }
+}
@@ -6,11 +6,15 @@ use ratatui::{
     layout::Rect,
     style::{Color, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::Paragraph,
     Frame,
 };
 
-use super::common::{format_size, get_border_style, HEX_BYTES_PER_LINE, HEX_PREVIEW_MAX_BYTES};
+use super::common::{
+    format_size, preview_block, preview_borders, preview_content_area, HEX_BYTES_PER_LINE,
+    HEX_PREVIEW_MAX_BYTES,
+};
+use crate::render::scrollbar::render_scrollbar;
 
 /// Hex preview content for binary files
 pub struct HexPreview {
@@ -56,7 +60,7 @@ pub fn render_hex_preview(
     title: &str,
     focused: bool,
 ) {
-    let visible_height = area.height.saturating_sub(2) as usize;
+    let visible_height = preview_content_area(area).height as usize;
 
     let lines: Vec<Line> = preview
         .bytes
@@ -71,14 +75,21 @@ pub fn render_hex_preview(
         .collect();
 
     let size_str = format_size(preview.size);
-    let widget = Paragraph::new(lines).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(format!(" {} ({}) ", title, size_str))
-            .border_style(get_border_style(focused)),
-    );
+    let widget = Paragraph::new(lines).block(preview_block(
+        format!(" {} ({}) ", title, size_str),
+        focused,
+    ));
 
     frame.render_widget(widget, area);
+    if preview_borders() {
+        render_scrollbar(
+            frame,
+            area,
+            preview.bytes.len().div_ceil(HEX_BYTES_PER_LINE),
+            visible_height,
+            preview.scroll,
+        );
+    }
 }
 
 /// Render a single hex dump line
@@ -1,14 +1,36 @@
 //! Image preview with ratatui-image protocol support
+//!
+//! With `FILEVIEW_IMAGE_PROTOCOL=command` and an image command configured,
+//! images are rendered by an external program (e.g. the `chafa` CLI) whose
+//! ANSI text output is drawn into the preview area instead. The command runs
+//! on a background thread; sixel output is passed through when the terminal
+//! reported sixel support at startup.
 
-use image::GenericImageView;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::RwLock;
+use std::thread;
+
+use image::{DynamicImage, GenericImageView};
 use ratatui::{
     layout::Rect,
-    widgets::{Block, Borders},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
     Frame,
 };
 use ratatui_image::{picker::Picker, protocol::StatefulProtocol, FontSize, Resize, StatefulImage};
 
-use super::common::get_border_style;
+use super::common::preview_block;
+use super::custom::CustomPreview;
+
+/// External image command template (None = use the picker protocol)
+static IMAGE_COMMAND: RwLock<Option<String>> = RwLock::new(None);
+
+/// Whether the terminal reported sixel support (probed once at startup)
+static COMMAND_SIXEL: AtomicBool = AtomicBool::new(false);
 
 /// Image preview with ratatui-image protocol support
 pub struct ImagePreview {
@@ -16,21 +38,292 @@ pub struct ImagePreview {
     pub height: u32,
     /// Protocol state for ratatui-image rendering (Sixel/Kitty/iTerm2/Halfblock)
     pub protocol: StatefulProtocol,
+    /// Image file (passed to the external image command)
+    pub path: PathBuf,
+    /// Cached external command output for the last rendered size
+    pub command_output: Option<CommandOutput>,
+    /// Whether `protocol` holds a thumbnail (`width`/`height` are the source's)
+    pub downscaled: bool,
+}
+
+/// Output of the external image command for one preview size
+pub struct CommandOutput {
+    /// Cell size (width, height) the command was run for
+    pub size: (u16, u16),
+    /// Parsed output (None while running or if the command failed)
+    pub render: Option<CommandRender>,
+    /// Result of the command while it runs
+    pending: Option<Receiver<Option<CommandRender>>>,
+}
+
+/// What the external image command drew
+pub enum CommandRender {
+    /// ANSI text
+    Text(Vec<Line<'static>>),
+    /// Sixel graphics, handed to the terminal as they are
+    Sixel(String),
 }
 
 impl ImagePreview {
     /// Load image from file path using ratatui-image picker
-    pub fn load(path: &std::path::Path, picker: &mut Picker) -> anyhow::Result<Self> {
+    pub fn load(path: &Path, picker: &mut Picker) -> anyhow::Result<Self> {
         let dyn_img = image::open(path)?;
+        Ok(Self::from_image(path.to_path_buf(), dyn_img, picker))
+    }
+
+    /// Create a preview from an already decoded image
+    pub fn from_image(path: PathBuf, dyn_img: DynamicImage, picker: &mut Picker) -> Self {
         let (width, height) = dyn_img.dimensions();
         let protocol = picker.new_resize_protocol(dyn_img);
 
-        Ok(Self {
+        Self {
             width,
             height,
             protocol,
-        })
+            path,
+            command_output: None,
+            downscaled: false,
+        }
+    }
+
+    /// Create a preview from a thumbnail of an image of `source_size`
+    pub fn from_thumbnail(
+        path: PathBuf,
+        thumbnail: DynamicImage,
+        source_size: (u32, u32),
+        picker: &mut Picker,
+    ) -> Self {
+        let mut preview = Self::from_image(path, thumbnail, picker);
+        (preview.width, preview.height) = source_size;
+        preview.downscaled = true;
+        preview
+    }
+
+    /// Output of the external image command for an area
+    ///
+    /// Starts the command in the background when the size changed; None
+    /// until it finishes or if it failed.
+    fn command_render(&mut self, template: &str, area: Rect) -> Option<&CommandRender> {
+        let size = (area.width, area.height);
+        if self.command_output.as_ref().map(|o| o.size) != Some(size) {
+            let command = expand_image_command(template, &self.path, area.width, area.height);
+            let sixel = COMMAND_SIXEL.load(Ordering::Relaxed);
+            let (tx, rx) = mpsc::channel();
+            thread::spawn(move || {
+                let _ = tx.send(run_image_command(&command, sixel));
+            });
+            self.command_output = Some(CommandOutput {
+                size,
+                render: None,
+                pending: Some(rx),
+            });
+        }
+        let output = self.command_output.as_mut()?;
+        if let Some(ref rx) = output.pending {
+            match rx.try_recv() {
+                Ok(render) => {
+                    output.render = render;
+                    output.pending = None;
+                }
+                Err(TryRecvError::Disconnected) => output.pending = None,
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+        output.render.as_ref()
+    }
+
+    /// Whether the external image command is still running
+    pub fn is_rendering(&self) -> bool {
+        self.command_output
+            .as_ref()
+            .is_some_and(|o| o.pending.is_some())
+    }
+}
+
+/// Set the external image command template (None disables it)
+pub fn configure_image_command(template: Option<String>) {
+    if let Ok(mut slot) = IMAGE_COMMAND.write() {
+        *slot = template;
+    }
+}
+
+/// Record whether the terminal reported sixel support, so sixel output of
+/// the image command is shown instead of rejected
+pub fn configure_command_sixel(supported: bool) {
+    COMMAND_SIXEL.store(supported, Ordering::Relaxed);
+}
+
+/// Resolve the external image command from the environment and config
+///
+/// Only active with `FILEVIEW_IMAGE_PROTOCOL=command`. `FILEVIEW_IMAGE_COMMAND`
+/// overrides the configured template; an empty template disables it.
+pub fn resolve_image_command(configured: &str) -> Option<String> {
+    let protocol = std::env::var("FILEVIEW_IMAGE_PROTOCOL").ok()?;
+    if !protocol.eq_ignore_ascii_case("command") {
+        return None;
     }
+    let template = std::env::var("FILEVIEW_IMAGE_COMMAND").unwrap_or_else(|_| configured.into());
+    let template = template.trim();
+    (!template.is_empty()).then(|| template.to_string())
+}
+
+/// Expand an image command template
+///
+/// `$f` is replaced with the shell-escaped file path, `$w` and `$h` with the
+/// width and height of the preview area in cells.
+pub fn expand_image_command(template: &str, path: &Path, width: u16, height: u16) -> String {
+    let escaped_path = CustomPreview::shell_escape(&path.display().to_string());
+    // Path last, so a `$w` inside the file name is left alone
+    template
+        .replace("$w", &width.to_string())
+        .replace("$h", &height.to_string())
+        .replace("$f", &escaped_path)
+}
+
+/// Run an image command and parse its output
+///
+/// Returns None if the command fails, prints nothing, or emits terminal
+/// graphics that can't be drawn as text: kitty/iTerm2 always, sixel unless
+/// `sixel` says the terminal supports it.
+fn run_image_command(command: &str, sixel: bool) -> Option<CommandRender> {
+    let output = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(["/C", command]).output()
+    } else {
+        Command::new("sh").args(["-c", command]).output()
+    }
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    if sixel && text.contains("\x1bP") {
+        return Some(CommandRender::Sixel(text.trim_end().to_string()));
+    }
+    let lines = parse_ansi_lines(&text)?;
+    (!lines.is_empty()).then_some(CommandRender::Text(lines))
+}
+
+/// Hand raw terminal graphics to the terminal at the top left of `area`
+///
+/// The data goes into the first cell and the rest of the area is skipped so
+/// the picture is not drawn over (as ratatui-image does for sixel).
+fn render_passthrough(frame: &mut Frame, data: &str, area: Rect) {
+    let buf = frame.buffer_mut();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            if let Some(cell) = buf.cell_mut((x, y)) {
+                if (x, y) == (area.x, area.y) {
+                    cell.set_symbol(data);
+                } else {
+                    cell.set_skip(true);
+                }
+            }
+        }
+    }
+}
+
+/// Parse ANSI text (SGR colors and attributes) into lines
+///
+/// Other CSI sequences (cursor movement, visibility) are dropped. Returns None
+/// for output containing graphics protocols.
+pub(super) fn parse_ansi_lines(text: &str) -> Option<Vec<Line<'static>>> {
+    let mut lines = Vec::new();
+    for raw in text.lines() {
+        let mut spans = Vec::new();
+        let mut style = Style::default();
+        let mut current = String::new();
+        let mut chars = raw.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\x1b' => match chars.next() {
+                    Some('[') => {
+                        let mut params = String::new();
+                        let mut terminator = None;
+                        for c in chars.by_ref() {
+                            if ('@'..='~').contains(&c) {
+                                terminator = Some(c);
+                                break;
+                            }
+                            params.push(c);
+                        }
+                        if terminator == Some('m') {
+                            if !current.is_empty() {
+                                spans.push(Span::styled(std::mem::take(&mut current), style));
+                            }
+                            style = apply_sgr(style, &params);
+                        }
+                    }
+                    // DCS (sixel), APC (kitty), OSC (iTerm2 images)
+                    Some('P' | '_' | ']') => return None,
+                    _ => {}
+                },
+                '\r' => {}
+                c => current.push(c),
+            }
+        }
+        if !current.is_empty() {
+            spans.push(Span::styled(current, style));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    // Drop trailing blank lines (e.g. a final cursor-restore line)
+    while lines.last().is_some_and(|l: &Line| l.width() == 0) {
+        lines.pop();
+    }
+    Some(lines)
+}
+
+/// Apply SGR parameters (`1;38;5;208`) to a style
+fn apply_sgr(mut style: Style, params: &str) -> Style {
+    let codes: Vec<u16> = params.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+    let mut i = 0;
+    while i < codes.len() {
+        match codes[i] {
+            0 => style = Style::default(),
+            1 => style = style.add_modifier(Modifier::BOLD),
+            2 => style = style.add_modifier(Modifier::DIM),
+            3 => style = style.add_modifier(Modifier::ITALIC),
+            4 => style = style.add_modifier(Modifier::UNDERLINED),
+            7 => style = style.add_modifier(Modifier::REVERSED),
+            22 => style = style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style = style.remove_modifier(Modifier::ITALIC),
+            24 => style = style.remove_modifier(Modifier::UNDERLINED),
+            27 => style = style.remove_modifier(Modifier::REVERSED),
+            c @ 30..=37 => style = style.fg(Color::Indexed((c - 30) as u8)),
+            c @ 90..=97 => style = style.fg(Color::Indexed((c - 90 + 8) as u8)),
+            c @ 40..=47 => style = style.bg(Color::Indexed((c - 40) as u8)),
+            c @ 100..=107 => style = style.bg(Color::Indexed((c - 100 + 8) as u8)),
+            39 => style.fg = None,
+            49 => style.bg = None,
+            c @ (38 | 48) => {
+                let color = match codes.get(i + 1) {
+                    Some(5) => {
+                        let color = codes.get(i + 2).map(|&n| Color::Indexed(n as u8));
+                        i += 2;
+                        color
+                    }
+                    Some(2) => {
+                        let rgb = codes.get(i + 2..i + 5);
+                        i += 4;
+                        rgb.map(|v| Color::Rgb(v[0] as u8, v[1] as u8, v[2] as u8))
+                    }
+                    _ => None,
+                };
+                if let Some(color) = color {
+                    style = if c == 38 {
+                        style.fg(color)
+                    } else {
+                        style.bg(color)
+                    };
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    style
 }
 
 /// Calculate centered area for an image within a given area
@@ -93,14 +386,30 @@ pub fn render_image_preview(
     focused: bool,
     font_size: FontSize,
 ) {
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .title(format!(" {} ({}x{}) ", title, img.width, img.height))
-        .border_style(get_border_style(focused));
+    let block = preview_block(
+        format!(" {} ({}x{}) ", title, img.width, img.height),
+        focused,
+    );
 
     let inner_area = block.inner(area);
     frame.render_widget(block, area);
 
+    // External image command, falling back to the picker protocol on failure
+    let template = IMAGE_COMMAND.read().ok().and_then(|t| t.clone());
+    if let Some(template) = template {
+        match img.command_render(&template, inner_area) {
+            Some(CommandRender::Text(lines)) => {
+                frame.render_widget(Paragraph::new(lines.to_vec()), inner_area);
+                return;
+            }
+            Some(CommandRender::Sixel(data)) => {
+                render_passthrough(frame, data, inner_area);
+                return;
+            }
+            None => {}
+        }
+    }
+
     // Calculate centered area for the image
     let centered_area = calculate_centered_image_area(inner_area, img.width, img.height, font_size);
 
@@ -111,7 +420,7 @@ pub fn render_image_preview(
 }
 
 /// Check if a file is likely an image
-pub fn is_image_file(path: &std::path::Path) -> bool {
+pub fn is_image_file(path: &Path) -> bool {
     let ext = path
         .extension()
         .and_then(|e| e.to_str())
@@ -122,3 +431,74 @@ pub fn is_image_file(path: &std::path::Path) -> bool {
         Some("png" | "jpg" | "jpeg" | "gif" | "webp" | "bmp" | "ico")
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_image_command() {
+        let command = expand_image_command(
+            "chafa -f symbols -s $wx$h $f",
+            Path::new("/tmp/my photo.png"),
+            80,
+            24,
+        );
+        assert_eq!(command, "chafa -f symbols -s 80x24 '/tmp/my photo.png'");
+
+        let command = expand_image_command("render $f $w $h", Path::new("/a/$w.png"), 10, 5);
+        assert_eq!(command, "render '/a/$w.png' 10 5");
+    }
+
+    #[test]
+    fn test_parse_ansi_lines_colors() {
+        let lines = parse_ansi_lines(
+            "\x1b[?25l\x1b[38;2;255;0;0mab\x1b[0m c\r\n\x1b[1;44mx\x1b[0m\n\x1b[?25h\n",
+        )
+        .unwrap();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].spans.len(), 2);
+        assert_eq!(lines[0].spans[0].content, "ab");
+        assert_eq!(lines[0].spans[0].style.fg, Some(Color::Rgb(255, 0, 0)));
+        assert_eq!(lines[0].spans[1].content, " c");
+        assert_eq!(lines[0].spans[1].style, Style::default());
+        assert_eq!(lines[1].spans[0].style.bg, Some(Color::Indexed(4)));
+        assert!(lines[1].spans[0]
+            .style
+            .add_modifier
+            .contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_parse_ansi_lines_rejects_graphics() {
+        assert!(parse_ansi_lines("\x1bPq#0;2;0;0;0-\x1b\\").is_none());
+        assert!(parse_ansi_lines("\x1b_Ga=T,f=100;AAAA\x1b\\").is_none());
+    }
+
+    #[test]
+    fn test_failing_command_has_no_output() {
+        if cfg!(target_os = "windows") {
+            return;
+        }
+        assert!(run_image_command("exit 1", false).is_none());
+        assert!(run_image_command("true", false).is_none());
+        let Some(CommandRender::Text(lines)) = run_image_command("printf 'ab'", false) else {
+            panic!("expected text output");
+        };
+        assert_eq!(lines.len(), 1);
+    }
+
+    #[test]
+    fn test_sixel_output_needs_terminal_support() {
+        if cfg!(target_os = "windows") {
+            return;
+        }
+        let command = r"printf '\033Pq#0-\033\\\n'";
+        assert!(run_image_command(command, false).is_none());
+        let Some(CommandRender::Sixel(data)) = run_image_command(command, true) else {
+            panic!("expected sixel output");
+        };
+        assert_eq!(data, "\x1bPq#0-\x1b\\");
+    }
+}
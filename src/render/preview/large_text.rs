@@ -0,0 +1,241 @@
+//! Line access into text files too large to read into memory
+//!
+//! A background thread scans the file once and records the byte offset of
+//! every [`INDEX_STRIDE`]th line. Reading a range seeks to the nearest
+//! recorded offset and skips at most a stride of lines, so scrolling to any
+//! position costs the same no matter where it is in the file.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Files larger than this are previewed through a [`LargeText`]
+pub const LARGE_TEXT_THRESHOLD: u64 = 8 * 1024 * 1024;
+
+/// Lines between two recorded offsets
+pub const INDEX_STRIDE: usize = 64;
+
+/// Bytes of a single line kept when reading (the rest is skipped)
+const MAX_LINE_BYTES: usize = 64 * 1024;
+
+/// Size of the chunks the index is built from
+const SCAN_CHUNK: usize = 256 * 1024;
+
+/// Sparse line offsets, filled in while the scan runs
+#[derive(Debug, Default)]
+struct LineIndex {
+    /// Byte offset of line `k * INDEX_STRIDE`
+    offsets: Vec<u64>,
+    /// Lines found so far
+    lines: usize,
+    /// Whether the whole file has been scanned
+    complete: bool,
+}
+
+/// A text file read on demand through a line-offset index
+#[derive(Debug)]
+pub struct LargeText {
+    file: File,
+    index: Arc<Mutex<LineIndex>>,
+}
+
+impl LargeText {
+    /// Open `path` and start indexing its lines in the background
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // A handle of its own: a cloned one would share the read position
+        // with the seeks in `lines`
+        let scan = File::open(path)?;
+        let index = Arc::new(Mutex::new(LineIndex {
+            offsets: vec![0],
+            ..LineIndex::default()
+        }));
+        let shared = Arc::clone(&index);
+        thread::spawn(move || build_index(scan, &shared));
+        Ok(Self { file, index })
+    }
+
+    /// Lines found so far (all of them once [`is_indexed`](Self::is_indexed))
+    pub fn line_count(&self) -> usize {
+        self.index.lock().map(|index| index.lines).unwrap_or(0)
+    }
+
+    /// Whether the scan has reached the end of the file
+    pub fn is_indexed(&self) -> bool {
+        self.index.lock().is_ok_and(|index| index.complete)
+    }
+
+    /// Number of recorded offsets
+    pub fn index_len(&self) -> usize {
+        self.index
+            .lock()
+            .map(|index| index.offsets.len())
+            .unwrap_or(0)
+    }
+
+    /// Up to `count` lines from line `start` (0-based), without line endings
+    ///
+    /// Only lines already indexed are returned; invalid UTF-8 is replaced.
+    pub fn lines(&self, start: usize, count: usize) -> io::Result<Vec<String>> {
+        let (offset, end) = {
+            let index = self
+                .index
+                .lock()
+                .map_err(|_| io::Error::other("line index poisoned"))?;
+            if start >= index.lines {
+                return Ok(Vec::new());
+            }
+            let block = (start / INDEX_STRIDE).min(index.offsets.len() - 1);
+            (
+                (block * INDEX_STRIDE, index.offsets[block]),
+                index.lines.min(start.saturating_add(count)),
+            )
+        };
+        let (mut line, offset) = offset;
+
+        let mut file = &self.file;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut reader = BufReader::new(file);
+        let mut buf = Vec::new();
+        let mut result = Vec::with_capacity(end - start);
+        while line < end {
+            buf.clear();
+            if !read_line_capped(&mut reader, &mut buf, MAX_LINE_BYTES)? {
+                break;
+            }
+            if line >= start {
+                if buf.last() == Some(&b'\r') {
+                    buf.pop();
+                }
+                result.push(String::from_utf8_lossy(&buf).into_owned());
+            }
+            line += 1;
+        }
+        Ok(result)
+    }
+}
+
+/// Scan `file` for line starts, publishing progress into `index`
+///
+/// Stops early once the preview holding the other end of `index` is gone.
+fn build_index(mut file: File, index: &Arc<Mutex<LineIndex>>) {
+    let mut chunk = vec![0u8; SCAN_CHUNK];
+    let mut pos: u64 = 0;
+    let mut lines = 0usize;
+    let mut at_line_start = true;
+    loop {
+        if Arc::strong_count(index) == 1 {
+            return;
+        }
+        let read = match file.read(&mut chunk) {
+            Ok(0) | Err(_) => break,
+            Ok(read) => read,
+        };
+        let mut offsets = Vec::new();
+        for (i, &byte) in chunk[..read].iter().enumerate() {
+            if byte == b'\n' {
+                lines += 1;
+                if lines.is_multiple_of(INDEX_STRIDE) {
+                    offsets.push(pos + i as u64 + 1);
+                }
+            }
+        }
+        at_line_start = chunk[read - 1] == b'\n';
+        pos += read as u64;
+        let Ok(mut shared) = index.lock() else {
+            return;
+        };
+        shared.offsets.extend(offsets);
+        shared.lines = lines;
+    }
+    if let Ok(mut shared) = index.lock() {
+        // A last line without a newline still counts, as in `str::lines`
+        if !at_line_start {
+            lines += 1;
+        }
+        shared.lines = lines;
+        shared.complete = true;
+    }
+}
+
+/// Read one line into `buf`, keeping at most `cap` bytes of it
+///
+/// Returns false at the end of the input.
+fn read_line_capped(reader: &mut impl BufRead, buf: &mut Vec<u8>, cap: usize) -> io::Result<bool> {
+    let mut any = false;
+    loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            return Ok(any);
+        }
+        any = true;
+        let (used, done) = match available.iter().position(|&b| b == b'\n') {
+            Some(i) => (i + 1, true),
+            None => (available.len(), false),
+        };
+        let keep = if done { used - 1 } else { used };
+        let room = cap.saturating_sub(buf.len());
+        buf.extend_from_slice(&available[..keep.min(room)]);
+        reader.consume(used);
+        if done {
+            return Ok(true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::time::{Duration, Instant};
+    use tempfile::NamedTempFile;
+
+    fn indexed(content: &[u8]) -> (NamedTempFile, LargeText) {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content).unwrap();
+        file.flush().unwrap();
+        let text = LargeText::open(file.path()).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !text.is_indexed() {
+            assert!(Instant::now() < deadline, "indexing did not finish");
+            thread::sleep(Duration::from_millis(5));
+        }
+        (file, text)
+    }
+
+    #[test]
+    fn test_far_offset_reads_correct_lines() {
+        let total = 200_000;
+        let content: String = (0..total).map(|n| format!("line {}\n", n)).collect();
+        let (_file, text) = indexed(content.as_bytes());
+
+        assert_eq!(text.line_count(), total);
+        // Sparse: one offset per stride, not one per line
+        assert!(text.index_len() <= total / INDEX_STRIDE + 1);
+        assert_eq!(
+            text.lines(150_000, 3).unwrap(),
+            vec!["line 150000", "line 150001", "line 150002"]
+        );
+        assert_eq!(text.lines(0, 1).unwrap(), vec!["line 0"]);
+        assert_eq!(
+            text.lines(total - 2, 10).unwrap(),
+            vec!["line 199998", "line 199999"]
+        );
+        assert!(text.lines(total, 5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_line_endings_and_long_lines() {
+        let long = "x".repeat(MAX_LINE_BYTES * 2);
+        let content = format!("a\r\n{}\nb\nlast", long);
+        let (_file, text) = indexed(content.as_bytes());
+
+        assert_eq!(text.line_count(), 4);
+        let lines = text.lines(0, 10).unwrap();
+        assert_eq!(lines[0], "a");
+        assert_eq!(lines[1].len(), MAX_LINE_BYTES);
+        assert_eq!(&lines[2..], ["b", "last"]);
+    }
+}
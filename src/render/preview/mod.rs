@@ -2,54 +2,86 @@
 //!
 //! This module provides preview functionality for various file types:
 //! - Text files with syntax highlighting
+//! - CSV/TSV files as aligned tables
 //! - Binary files with hex dump
 //! - Images with terminal graphics protocols
 //! - Archives (zip, tar.gz)
 //! - PDFs (requires poppler-utils)
+//! - SVGs rasterized as images (requires rsvg-convert or resvg)
 //! - Videos with thumbnail and metadata
 //! - Git diffs
+//! - Git blame beside text
 //! - Custom external command output
 //! - Directory information
 
 pub mod archive;
+pub mod blame;
 pub mod common;
+pub mod csv;
 pub mod custom;
 pub mod diff;
 pub mod directory;
 pub mod hex;
 pub mod image;
+pub mod large_text;
 pub mod pdf;
+pub mod svg;
 pub mod text;
 pub mod video;
 
 // Re-export common utilities
-pub use common::{format_size, get_border_style};
+pub use common::{
+    format_size, get_border_style, preview_block, preview_borders, preview_content_area,
+    set_preview_borders,
+};
 
 // Re-export archive types and functions
 pub use archive::{
     is_archive_file, is_tar_gz_file, render_archive_preview, ArchiveEntry, ArchivePreview,
 };
 
+// Re-export blame gutter
+pub use blame::BlamePreview;
+
+// Re-export CSV preview
+pub use csv::{is_csv_file, render_csv_preview, CsvPreview};
+
 // Re-export custom preview
-pub use custom::{render_custom_preview, CustomPreview};
+pub use custom::{find_preview_command, render_custom_preview, CustomPreview};
 
 // Re-export diff preview
 pub use diff::{render_diff_preview, DiffPreview};
 
 // Re-export directory info
-pub use directory::{render_directory_info, DirectoryInfo};
+pub use directory::{render_directory_info, walk_dir_totals, DirSizeJob, DirTotals, DirectoryInfo};
 
 // Re-export hex preview and binary detection
 pub use hex::{is_binary_file, render_hex_preview, HexPreview};
 
 // Re-export image preview
-pub use image::{calculate_centered_image_area, is_image_file, render_image_preview, ImagePreview};
+pub use image::{
+    calculate_centered_image_area, configure_command_sixel, configure_image_command,
+    expand_image_command, is_image_file, render_image_preview, resolve_image_command,
+    CommandOutput, CommandRender, ImagePreview,
+};
 
 // Re-export PDF preview
-pub use pdf::{find_pdftoppm, is_pdf_file, render_pdf_preview, PdfPreview};
+pub use pdf::{
+    find_pdftoppm, find_pdftotext, is_pdf_file, render_pdf_preview, PdfPreview, PdfTextPreview,
+    PdfView,
+};
+
+// Re-export SVG preview
+pub use svg::{find_svg_rasterizer, is_svg_file, rasterize_svg, svg_raster_size};
+
+// Re-export large text access
+pub use large_text::{LargeText, LARGE_TEXT_THRESHOLD};
 
 // Re-export text preview and detection
-pub use text::{is_text_file, render_text_preview, StyledLine, StyledSegment, TextPreview};
+pub use text::{
+    configure_text_preview, is_text_file, render_text_preview, StyledLine, StyledSegment,
+    TextPreview, TextViewOptions,
+};
 
 // Re-export video preview
 pub use video::{render_video_preview, VideoPreview};
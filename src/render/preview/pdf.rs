@@ -1,14 +1,20 @@
-//! PDF preview using pdftoppm
+//! PDF preview using poppler-utils
+//!
+//! Pages are rendered to an image with pdftoppm when the terminal has an
+//! image protocol. Otherwise, or when toggled with `t`, the page's text layer
+//! is extracted with pdftotext and shown as scrollable text.
 
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
-use ratatui::{layout::Rect, widgets::Block, widgets::Borders, Frame};
+use ratatui::{layout::Rect, Frame};
 use ratatui_image::{picker::Picker, FontSize, Resize, StatefulImage};
 use tempfile::NamedTempFile;
 
-use super::common::get_border_style;
+use super::common::preview_block;
 use super::image::{calculate_centered_image_area, ImagePreview};
+use super::text::{render_text_preview, TextPreview, TextViewOptions};
+use crate::core::find_executable;
 
 /// Cached pdftoppm path detection
 static PDFTOPPM_PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
@@ -16,59 +22,27 @@ static PDFTOPPM_PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
 /// Cached pdfinfo path detection
 static PDFINFO_PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
 
+/// Cached pdftotext path detection
+static PDFTOTEXT_PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
+
 /// Find pdftoppm executable path (lazy detection with caching)
 pub fn find_pdftoppm() -> Option<&'static PathBuf> {
     PDFTOPPM_PATH
-        .get_or_init(|| {
-            let candidates = [
-                "/usr/bin/pdftoppm",
-                "/usr/local/bin/pdftoppm",
-                "/opt/homebrew/bin/pdftoppm",
-            ];
-            for path in candidates {
-                let p = PathBuf::from(path);
-                if p.exists() {
-                    return Some(p);
-                }
-            }
-            // fallback: which pdftoppm
-            std::process::Command::new("which")
-                .arg("pdftoppm")
-                .output()
-                .ok()
-                .filter(|o| o.status.success())
-                .and_then(|o| String::from_utf8(o.stdout).ok())
-                .map(|s| PathBuf::from(s.trim()))
-                .filter(|p| p.exists())
-        })
+        .get_or_init(|| find_executable("pdftoppm"))
+        .as_ref()
+}
+
+/// Find pdftotext executable path (lazy detection with caching)
+pub fn find_pdftotext() -> Option<&'static PathBuf> {
+    PDFTOTEXT_PATH
+        .get_or_init(|| find_executable("pdftotext"))
         .as_ref()
 }
 
 /// Find pdfinfo executable path (lazy detection with caching)
 fn find_pdfinfo() -> Option<&'static PathBuf> {
     PDFINFO_PATH
-        .get_or_init(|| {
-            let candidates = [
-                "/usr/bin/pdfinfo",
-                "/usr/local/bin/pdfinfo",
-                "/opt/homebrew/bin/pdfinfo",
-            ];
-            for path in candidates {
-                let p = PathBuf::from(path);
-                if p.exists() {
-                    return Some(p);
-                }
-            }
-            // fallback: which pdfinfo
-            std::process::Command::new("which")
-                .arg("pdfinfo")
-                .output()
-                .ok()
-                .filter(|o| o.status.success())
-                .and_then(|o| String::from_utf8(o.stdout).ok())
-                .map(|s| PathBuf::from(s.trim()))
-                .filter(|p| p.exists())
-        })
+        .get_or_init(|| find_executable("pdfinfo"))
         .as_ref()
 }
 
@@ -204,25 +178,196 @@ impl PdfPreview {
     }
 }
 
+/// Text layer of a single PDF page
+pub struct PdfTextPreview {
+    /// Original PDF file path
+    pub path: PathBuf,
+    /// Current page number (1-indexed)
+    pub current_page: usize,
+    /// Total number of pages
+    pub total_pages: usize,
+    /// Extracted page text
+    pub text: TextPreview,
+}
+
+impl PdfTextPreview {
+    /// Extract the text of a specific page
+    pub fn load(path: &Path, page: usize) -> anyhow::Result<Self> {
+        let pdftotext = find_pdftotext().ok_or_else(|| {
+            anyhow::anyhow!("PDF text preview requires pdftotext (poppler-utils)")
+        })?;
+
+        let total_pages = get_pdf_page_count(path)?;
+        let page = page.clamp(1, total_pages);
+
+        // pdftotext -f <page> -l <page> -layout input.pdf -
+        let output = std::process::Command::new(pdftotext)
+            .arg("-f")
+            .arg(page.to_string())
+            .arg("-l")
+            .arg(page.to_string())
+            .arg("-layout")
+            .arg(path)
+            .arg("-")
+            .output()?;
+
+        if !output.status.success() {
+            anyhow::bail!("pdftotext failed to extract page text");
+        }
+
+        let text = page_text(&String::from_utf8_lossy(&output.stdout));
+        Ok(Self {
+            path: path.to_path_buf(),
+            current_page: page,
+            total_pages,
+            text: TextPreview::new(&text),
+        })
+    }
+}
+
+/// Strip the form feed pdftotext writes after each page
+fn page_text(raw: &str) -> String {
+    let text = raw.trim_end_matches(['\x0c', '\n']);
+    if text.trim().is_empty() {
+        "(no text layer on this page)".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// A PDF page shown either as a rendered image or as its text layer
+pub enum PdfView {
+    Image(Box<PdfPreview>),
+    Text(Box<PdfTextPreview>),
+}
+
+impl PdfView {
+    /// Load a page as an image when a picker is available and pdftoppm is
+    /// installed (unless `prefer_text`), otherwise as text
+    pub fn load(
+        path: &Path,
+        page: usize,
+        picker: Option<&mut Picker>,
+        prefer_text: bool,
+    ) -> anyhow::Result<Self> {
+        match picker {
+            Some(picker) if !prefer_text && find_pdftoppm().is_some() => {
+                PdfPreview::load(path, page, picker).map(|pdf| Self::Image(Box::new(pdf)))
+            }
+            _ => PdfTextPreview::load(path, page).map(|pdf| Self::Text(Box::new(pdf))),
+        }
+    }
+
+    /// Original PDF file path
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Image(pdf) => &pdf.path,
+            Self::Text(pdf) => &pdf.path,
+        }
+    }
+
+    /// Current page number (1-indexed)
+    pub fn current_page(&self) -> usize {
+        match self {
+            Self::Image(pdf) => pdf.current_page,
+            Self::Text(pdf) => pdf.current_page,
+        }
+    }
+
+    /// Total number of pages
+    pub fn total_pages(&self) -> usize {
+        match self {
+            Self::Image(pdf) => pdf.total_pages,
+            Self::Text(pdf) => pdf.total_pages,
+        }
+    }
+
+    /// Check if the text layer is shown
+    pub fn is_text(&self) -> bool {
+        matches!(self, Self::Text(_))
+    }
+
+    /// Extracted page text (text mode only)
+    pub fn text_mut(&mut self) -> Option<&mut TextPreview> {
+        match self {
+            Self::Image(_) => None,
+            Self::Text(pdf) => Some(&mut pdf.text),
+        }
+    }
+
+    /// Navigate to a different page, keeping the current mode
+    pub fn go_to_page(&mut self, page: usize, picker: Option<&mut Picker>) -> anyhow::Result<()> {
+        let page = page.clamp(1, self.total_pages());
+        if page == self.current_page() {
+            return Ok(());
+        }
+        match self {
+            Self::Image(pdf) => {
+                let picker =
+                    picker.ok_or_else(|| anyhow::anyhow!("image preview is not available"))?;
+                pdf.go_to_page(page, picker)
+            }
+            Self::Text(pdf) => {
+                **pdf = PdfTextPreview::load(&pdf.path, page)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Go to the previous page
+    pub fn prev_page(&mut self, picker: Option<&mut Picker>) -> anyhow::Result<()> {
+        let page = self.current_page().saturating_sub(1);
+        self.go_to_page(page, picker)
+    }
+
+    /// Go to the next page
+    pub fn next_page(&mut self, picker: Option<&mut Picker>) -> anyhow::Result<()> {
+        let page = self.current_page() + 1;
+        self.go_to_page(page, picker)
+    }
+
+    /// Switch between image and text mode on the same page
+    pub fn toggle_text(&mut self, picker: Option<&mut Picker>) -> anyhow::Result<()> {
+        let page = self.current_page();
+        let prefer_text = !self.is_text();
+        if !prefer_text && (picker.is_none() || find_pdftoppm().is_none()) {
+            anyhow::bail!("PDF image preview requires pdftoppm and an image-capable terminal");
+        }
+        let path = self.path().to_path_buf();
+        *self = Self::load(&path, page, picker, prefer_text)?;
+        Ok(())
+    }
+}
+
 /// Render PDF preview
 pub fn render_pdf_preview(
     frame: &mut Frame,
-    pdf: &mut PdfPreview,
+    pdf: &mut PdfView,
     area: Rect,
     title: &str,
     focused: bool,
     font_size: FontSize,
+    options: TextViewOptions,
 ) {
+    let pdf = match pdf {
+        PdfView::Image(pdf) => pdf,
+        PdfView::Text(pdf) => {
+            let full_title = format!(
+                "{} ({}/{}) text [/] prev/next",
+                title, pdf.current_page, pdf.total_pages
+            );
+            render_text_preview(frame, &pdf.text, area, &full_title, focused, options);
+            return;
+        }
+    };
+
     // Title with page info and navigation hint
     let full_title = format!(
         " {} ({}/{}) [/] prev/next ",
         title, pdf.current_page, pdf.total_pages
     );
 
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .title(full_title)
-        .border_style(get_border_style(focused));
+    let block = preview_block(full_title, focused);
 
     let inner_area = block.inner(area);
     frame.render_widget(block, area);
@@ -267,6 +412,82 @@ mod tests {
         assert!(!is_pdf_file(Path::new("no_extension")));
     }
 
+    /// Build a minimal PDF with one line of Helvetica text per page
+    fn tiny_pdf(pages: &[&str]) -> Vec<u8> {
+        let n = pages.len();
+        let kids: Vec<String> = (0..n).map(|i| format!("{} 0 R", 4 + i * 2)).collect();
+        let mut objects = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), n),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+        ];
+        for (i, text) in pages.iter().enumerate() {
+            let stream = format!("BT /F1 24 Tf 72 720 Td ({}) Tj ET", text);
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+                 /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+                5 + i * 2
+            ));
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{}\nendstream",
+                stream.len(),
+                stream
+            ));
+        }
+
+        let mut pdf = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::new();
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).bytes());
+        }
+        let xref = pdf.len();
+        pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).bytes());
+        for offset in offsets {
+            pdf.extend(format!("{:010} 00000 n \n", offset).bytes());
+        }
+        pdf.extend(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref
+            )
+            .bytes(),
+        );
+        pdf
+    }
+
+    #[test]
+    fn test_page_text_strips_form_feed() {
+        assert_eq!(page_text("Hello\n\n\x0c"), "Hello");
+        assert_eq!(page_text("  \n\x0c"), "(no text layer on this page)");
+    }
+
+    #[test]
+    fn test_pdf_text_preview_extracts_page() {
+        if find_pdftotext().is_none() || find_pdfinfo().is_none() {
+            return;
+        }
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("tiny.pdf");
+        std::fs::write(&path, tiny_pdf(&["Hello fileview", "Second page"])).unwrap();
+
+        let mut view = PdfView::load(&path, 1, None, false).unwrap();
+        assert!(view.is_text());
+        assert_eq!(view.total_pages(), 2);
+        let text = view.text_mut().unwrap().lines.join("\n");
+        assert!(text.contains("Hello fileview"), "got {:?}", text);
+
+        view.next_page(None).unwrap();
+        assert_eq!(view.current_page(), 2);
+        let text = view.text_mut().unwrap().lines.join("\n");
+        assert!(text.contains("Second page"), "got {:?}", text);
+
+        // Already on the last page
+        view.next_page(None).unwrap();
+        assert_eq!(view.current_page(), 2);
+    }
+
     #[test]
     fn test_find_pdftoppm_returns_consistent() {
         let result1 = find_pdftoppm();
@@ -0,0 +1,171 @@
+//! SVG preview by rasterization
+//!
+//! SVG files are rasterized with `rsvg-convert` (librsvg) or the `resvg`
+//! command-line tool and shown through the regular image preview. Without
+//! either tool or an image-capable terminal, or when toggled with `t`, the
+//! XML source is shown instead. A file the rasterizer rejects falls back to
+//! the source with the rasterizer's error. Rasterizing runs on the
+//! background image loader.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use image::DynamicImage;
+use ratatui::layout::Rect;
+use ratatui_image::{picker::Picker, FontSize};
+
+use crate::core::find_executable;
+
+/// Cached rasterizer detection
+static SVG_RASTERIZER: OnceLock<Option<Rasterizer>> = OnceLock::new();
+
+/// External tool used to rasterize SVG
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rasterizer {
+    /// `rsvg-convert` from librsvg
+    Rsvg(PathBuf),
+    /// The `resvg` command-line tool
+    Resvg(PathBuf),
+}
+
+/// Find an SVG rasterizer (lazy detection with caching, rsvg-convert first)
+pub fn find_svg_rasterizer() -> Option<&'static Rasterizer> {
+    SVG_RASTERIZER
+        .get_or_init(|| {
+            find_executable("rsvg-convert")
+                .map(Rasterizer::Rsvg)
+                .or_else(|| find_executable("resvg").map(Rasterizer::Resvg))
+        })
+        .as_ref()
+}
+
+/// Rasterize an SVG to fit within `width` x `height` pixels, keeping its aspect ratio
+pub fn rasterize_svg(path: &Path, width: u32, height: u32) -> anyhow::Result<DynamicImage> {
+    let rasterizer = find_svg_rasterizer()
+        .ok_or_else(|| anyhow::anyhow!("SVG preview requires rsvg-convert or resvg"))?;
+    let output = match rasterizer {
+        // rsvg-convert -w <w> -h <h> -a input.svg (PNG on stdout)
+        Rasterizer::Rsvg(tool) => Command::new(tool)
+            .arg("-w")
+            .arg(width.to_string())
+            .arg("-h")
+            .arg(height.to_string())
+            .arg("-a")
+            .arg(path)
+            .output()?,
+        // resvg scales by one side only; pick the one that keeps it inside the box
+        Rasterizer::Resvg(tool) => {
+            let side = match svg_aspect(path) {
+                Some(aspect) if (width as f64 / height as f64) > aspect => {
+                    ["-h".to_string(), height.to_string()]
+                }
+                _ => ["-w".to_string(), width.to_string()],
+            };
+            Command::new(tool).args(side).arg(path).arg("-c").output()?
+        }
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or("rasterizer failed");
+        anyhow::bail!("{}", reason);
+    }
+    Ok(image::load_from_memory(&output.stdout)?)
+}
+
+/// Width / height from the root element's `viewBox` (None if absent)
+fn svg_aspect(path: &Path) -> Option<f64> {
+    let source = std::fs::read_to_string(path).ok()?;
+    let start = source.find("viewBox=")? + "viewBox=".len();
+    let quote = source[start..].chars().next()?;
+    let rest = &source[start + quote.len_utf8()..];
+    let values: Vec<f64> = rest[..rest.find(quote)?]
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .filter_map(|s| s.parse().ok())
+        .collect();
+    match values.as_slice() {
+        [_, _, w, h] if *w > 0.0 && *h > 0.0 => Some(w / h),
+        _ => None,
+    }
+}
+
+/// Pixel size of a terminal area for the picker's font
+fn area_pixels(area: Rect, font_size: FontSize) -> (u32, u32) {
+    (
+        u32::from(area.width.max(1)) * u32::from(font_size.0.max(1)),
+        u32::from(area.height.max(1)) * u32::from(font_size.1.max(1)),
+    )
+}
+
+/// Pixel box to rasterize an SVG into for the terminal
+///
+/// The bitmap is sized to the whole terminal; the image preview scales it
+/// down into the preview pane.
+pub fn svg_raster_size(picker: &Picker) -> (u32, u32) {
+    let (cols, rows) = crossterm::terminal::size().unwrap_or((80, 24));
+    area_pixels(Rect::new(0, 0, cols, rows), picker.font_size())
+}
+
+/// Check if a file is an SVG
+pub fn is_svg_file(path: &Path) -> bool {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+
+    matches!(ext.as_deref(), Some("svg"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::GenericImageView;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_svg_file() {
+        assert!(is_svg_file(Path::new("logo.svg")));
+        assert!(is_svg_file(Path::new("LOGO.SVG")));
+        assert!(!is_svg_file(Path::new("logo.png")));
+    }
+
+    #[test]
+    fn test_svg_aspect() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("wide.svg");
+        std::fs::write(&path, r#"<svg viewBox="0 0 200 100"/>"#).unwrap();
+        assert_eq!(svg_aspect(&path), Some(2.0));
+        std::fs::write(&path, "<svg/>").unwrap();
+        assert_eq!(svg_aspect(&path), None);
+        // A multi-byte quote character must not split a char boundary
+        std::fs::write(&path, "<svg viewBox=\u{201c}0 0 200 100\u{201c}/>").unwrap();
+        assert_eq!(svg_aspect(&path), Some(2.0));
+    }
+
+    #[test]
+    #[ignore = "Requires rsvg-convert or resvg"]
+    fn test_rasterize_rect() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("rect.svg");
+        std::fs::write(
+            &path,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="40" height="20" viewBox="0 0 40 20"><rect width="40" height="20" fill="#ff0000"/></svg>"##,
+        )
+        .unwrap();
+
+        let image = rasterize_svg(&path, 80, 80).unwrap();
+        let (width, height) = image.dimensions();
+        assert!(width > 0 && height > 0);
+        assert!(width <= 80 && height <= 80);
+        assert_eq!(image.get_pixel(width / 2, height / 2).0, [255, 0, 0, 255]);
+
+        std::fs::write(&path, "<svg").unwrap();
+        assert!(rasterize_svg(&path, 80, 80).is_err());
+    }
+}
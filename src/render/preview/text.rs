@@ -1,27 +1,51 @@
 //! Text preview with syntax highlighting
 
+use std::borrow::Cow;
+use std::ops::Range;
 use std::path::Path;
-use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
 
 use ratatui::{
     layout::Rect,
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::Paragraph,
     Frame,
 };
 use syntect::easy::HighlightLines;
 use syntect::highlighting::{Theme, ThemeSet};
 use syntect::parsing::SyntaxSet;
 use syntect::util::LinesWithEndings;
+use unicode_width::UnicodeWidthChar;
 
-use super::common::get_border_style;
+use super::blame::BlamePreview;
+use super::common::{preview_block, preview_borders, preview_content_area};
+use super::large_text::LargeText;
+use crate::core::match_ranges;
+use crate::render::scrollbar::render_scrollbar;
+use crate::render::theme::theme;
 
 /// Lazy-initialized syntax set (100+ languages)
 static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
 
-/// Lazy-initialized theme (base16-ocean.dark)
-static THEME: OnceLock<Theme> = OnceLock::new();
+/// Lazy-initialized theme (preview.syntax_theme, default base16-ocean.dark)
+static THEME: RwLock<Option<Arc<Theme>>> = RwLock::new(None);
+
+/// Tab width used when expanding tabs in text previews
+static TAB_WIDTH: AtomicUsize = AtomicUsize::new(DEFAULT_TAB_WIDTH);
+
+/// Columns a previewed line is cut at (0 = no limit)
+static MAX_LINE_WIDTH: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_LINE_WIDTH);
+
+/// Default syntax highlighting theme
+pub const DEFAULT_SYNTAX_THEME: &str = "base16-ocean.dark";
+
+/// Default tab width for text previews
+pub const DEFAULT_TAB_WIDTH: usize = 4;
+
+/// Default column limit for previewed lines
+pub const DEFAULT_MAX_LINE_WIDTH: usize = 1000;
 
 /// Get the shared syntax set (lazy-initialized)
 fn get_syntax_set() -> &'static SyntaxSet {
@@ -29,11 +53,156 @@ fn get_syntax_set() -> &'static SyntaxSet {
 }
 
 /// Get the shared theme (lazy-initialized)
-fn get_theme() -> &'static Theme {
-    THEME.get_or_init(|| {
-        let ts = ThemeSet::load_defaults();
-        ts.themes["base16-ocean.dark"].clone()
-    })
+fn get_theme() -> Arc<Theme> {
+    if let Some(theme) = THEME.read().ok().and_then(|t| t.clone()) {
+        return theme;
+    }
+    let init = || Arc::new(load_theme(DEFAULT_SYNTAX_THEME).0);
+    match THEME.write() {
+        Ok(mut slot) => Arc::clone(slot.get_or_insert_with(init)),
+        Err(_) => init(),
+    }
+}
+
+/// Load a bundled syntect theme by name (case-insensitive)
+///
+/// Falls back to the default theme when the name is unknown. The returned
+/// flag is false when the fallback was used.
+fn load_theme(name: &str) -> (Theme, bool) {
+    let mut ts = ThemeSet::load_defaults();
+    let key = ts
+        .themes
+        .keys()
+        .find(|k| k.eq_ignore_ascii_case(name))
+        .cloned();
+    match key.and_then(|k| ts.themes.remove(&k)) {
+        Some(theme) => (theme, true),
+        None => {
+            let theme = ts.themes.remove(DEFAULT_SYNTAX_THEME).unwrap_or_default();
+            (theme, false)
+        }
+    }
+}
+
+/// Names of the bundled syntax highlighting themes
+pub(crate) fn available_syntax_themes() -> Vec<String> {
+    ThemeSet::load_defaults().themes.into_keys().collect()
+}
+
+/// Configure text preview highlighting (at startup and on config reload)
+///
+/// The theme, tab width and line limit are shared by all previews created
+/// afterwards. Returns false if `theme_name` is unknown and the default theme
+/// was used instead.
+pub fn configure_text_preview(theme_name: &str, tab_width: usize, max_line_width: usize) -> bool {
+    TAB_WIDTH.store(tab_width, Ordering::Relaxed);
+    MAX_LINE_WIDTH.store(max_line_width, Ordering::Relaxed);
+    let (theme, found) = load_theme(theme_name);
+    if let Ok(mut slot) = THEME.write() {
+        *slot = Some(Arc::new(theme));
+    }
+    found
+}
+
+/// Expand tabs to spaces so they render with a consistent width
+///
+/// A tab width of 0 leaves tabs untouched.
+pub fn expand_tabs(content: &str, tab_width: usize) -> Cow<'_, str> {
+    if tab_width == 0 || !content.contains('\t') {
+        return Cow::Borrowed(content);
+    }
+
+    let mut out = String::with_capacity(content.len());
+    let mut column = 0;
+    for c in content.chars() {
+        match c {
+            '\t' => {
+                let spaces = tab_width - (column % tab_width);
+                out.extend(std::iter::repeat_n(' ', spaces));
+                column += spaces;
+            }
+            '\n' => {
+                out.push(c);
+                column = 0;
+            }
+            _ => {
+                out.push(c);
+                column += 1;
+            }
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Make file content safe and cheap to render
+///
+/// Expands tabs like [`expand_tabs`], shows control characters in caret
+/// notation (`^@` for NUL, `^[` for ESC, `^?` for DEL) and other
+/// non-printable characters as `·`, so they cannot drive the terminal, and
+/// cuts lines wider than `max_width` columns (0 = no limit) with a marker
+/// counting the dropped characters. Line breaks (`\n`, `\r\n`) are kept.
+pub fn sanitize_content(content: &str, tab_width: usize, max_width: usize) -> Cow<'_, str> {
+    let clean = content.lines().all(|line| {
+        (max_width == 0 || line.len() <= max_width)
+            && !line
+                .chars()
+                .any(|c| c.is_control() && !(c == '\t' && tab_width == 0))
+    });
+    if clean {
+        return Cow::Borrowed(content);
+    }
+
+    let mut out = String::with_capacity(content.len().min(1 << 20));
+    let mut shown = String::new();
+    let mut column = 0;
+    let mut dropped = 0;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\n' || (c == '\r' && chars.peek() == Some(&'\n')) {
+            if dropped > 0 {
+                out.push_str(&truncation_marker(dropped));
+            }
+            out.push(c);
+            column = 0;
+            dropped = 0;
+            continue;
+        }
+        if dropped > 0 {
+            dropped += 1;
+            continue;
+        }
+
+        shown.clear();
+        match c {
+            '\t' if tab_width > 0 => {
+                shown.extend(std::iter::repeat_n(' ', tab_width - (column % tab_width)))
+            }
+            '\t' => shown.push('\t'),
+            '\x7f' => shown.push_str("^?"),
+            c if (c as u32) < 0x20 => {
+                shown.push('^');
+                shown.push(char::from(c as u8 + 0x40));
+            }
+            c if c.is_control() => shown.push('·'),
+            c => shown.push(c),
+        }
+        let width: usize = shown.chars().map(|c| c.width().unwrap_or(0)).sum();
+        if max_width > 0 && column + width > max_width {
+            dropped = 1;
+            continue;
+        }
+        out.push_str(&shown);
+        column += width;
+    }
+    if dropped > 0 {
+        out.push_str(&truncation_marker(dropped));
+    }
+    Cow::Owned(out)
+}
+
+/// Marker ending a line cut by [`sanitize_content`]
+fn truncation_marker(dropped: usize) -> String {
+    format!(" … [+{} chars]", dropped)
 }
 
 /// A segment of styled text (text with color)
@@ -49,34 +218,403 @@ pub struct StyledLine {
     pub segments: Vec<StyledSegment>,
 }
 
+/// Display options for text previews
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextViewOptions {
+    /// Soft-wrap long lines within the preview width
+    pub wrap: bool,
+    /// Show a line number gutter
+    pub line_numbers: bool,
+}
+
+impl Default for TextViewOptions {
+    fn default() -> Self {
+        Self {
+            wrap: false,
+            line_numbers: true,
+        }
+    }
+}
+
+/// A merge conflict block (0-based line indices of its markers)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConflictRegion {
+    /// `<<<<<<<` line opening our side
+    pub start: usize,
+    /// `|||||||` line opening the common base (diff3 style only)
+    pub base: Option<usize>,
+    /// `=======` line opening their side
+    pub separator: usize,
+    /// `>>>>>>>` line closing the block
+    pub end: usize,
+}
+
+/// Part of a conflict block a line belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConflictPart {
+    Marker,
+    Ours,
+    Base,
+    Theirs,
+}
+
+/// Check if `line` is a conflict marker made of seven `marker` characters
+fn is_conflict_marker(line: &str, marker: char) -> bool {
+    let Some(rest) = line.strip_prefix(&marker.to_string().repeat(7)) else {
+        return false;
+    };
+    rest.is_empty() || rest.starts_with(' ')
+}
+
+/// Locate the merge conflict blocks in `lines`
+///
+/// Blocks missing their separator or closing marker are ignored.
+pub fn find_conflicts(lines: &[String]) -> Vec<ConflictRegion> {
+    let mut regions = Vec::new();
+    let mut open: Option<ConflictRegion> = None;
+    for (idx, line) in lines.iter().enumerate() {
+        if is_conflict_marker(line, '<') {
+            open = Some(ConflictRegion {
+                start: idx,
+                base: None,
+                separator: 0,
+                end: 0,
+            });
+            continue;
+        }
+        let Some(region) = open.as_mut() else {
+            continue;
+        };
+        if region.separator == 0 {
+            if is_conflict_marker(line, '|') && region.base.is_none() {
+                region.base = Some(idx);
+            } else if line == "=======" {
+                region.separator = idx;
+            }
+        } else if is_conflict_marker(line, '>') {
+            region.end = idx;
+            regions.extend(open.take());
+        }
+    }
+    regions
+}
+
+/// Lines searched per step of a text preview search
+pub const SEARCH_STEP_LINES: usize = 64 * 1024;
+
 /// Text preview content
+///
+/// Small files are held in `lines`; a file past
+/// [`LARGE_TEXT_THRESHOLD`](super::large_text::LARGE_TEXT_THRESHOLD) is
+/// read on demand instead and `lines` stays empty. Go through
+/// [`line_count`](Self::line_count) and [`window`](Self::window) to cover both.
 pub struct TextPreview {
     pub lines: Vec<String>,
+    /// File read through a line index (None when `lines` holds the text)
+    large: Option<LargeText>,
     /// Syntax-highlighted lines (None for plain text)
     pub styled_lines: Option<Vec<StyledLine>>,
     pub scroll: usize,
+    /// Merge conflict blocks in the text
+    pub conflicts: Vec<ConflictRegion>,
+    /// Highlighted line (0-based), e.g. from `fv file:line`
+    pub highlight: Option<usize>,
+    /// Query searched for with `/` in the fullscreen preview
+    pub search_query: Option<String>,
+    /// Lines (0-based, ascending) containing `search_query`
+    pub match_lines: Vec<usize>,
+    /// Whether `search_query` matched case-sensitively (see `AppState::case_sensitive`)
+    search_case_sensitive: bool,
+    /// Lines searched for `search_query` so far
+    search_scanned: usize,
+    /// Jump to the first match once one is found (set when confirming a
+    /// search that has not reached one yet)
+    pub search_jump_pending: bool,
+    /// Anchor past the lines indexed so far, as `(line, height)`
+    pending_anchor: Option<(usize, usize)>,
+    /// Git blame drawn left of the lines (toggled with `B`)
+    pub blame: Option<BlamePreview>,
 }
 
 impl TextPreview {
     /// Create a new text preview without syntax highlighting
     pub fn new(content: &str) -> Self {
+        let content = sanitize_content(
+            content,
+            TAB_WIDTH.load(Ordering::Relaxed),
+            MAX_LINE_WIDTH.load(Ordering::Relaxed),
+        );
         let lines: Vec<String> = content.lines().map(String::from).collect();
         Self {
+            conflicts: find_conflicts(&lines),
             lines,
+            large: None,
+            styled_lines: None,
+            scroll: 0,
+            highlight: None,
+            search_query: None,
+            match_lines: Vec::new(),
+            search_case_sensitive: false,
+            search_scanned: 0,
+            search_jump_pending: false,
+            pending_anchor: None,
+            blame: None,
+        }
+    }
+
+    /// Preview a large file without reading it into memory
+    ///
+    /// Lines are indexed in the background, without highlighting or
+    /// conflict detection.
+    pub fn open_large(path: &Path) -> std::io::Result<Self> {
+        Ok(Self {
+            lines: Vec::new(),
+            large: Some(LargeText::open(path)?),
             styled_lines: None,
             scroll: 0,
+            conflicts: Vec::new(),
+            highlight: None,
+            search_query: None,
+            match_lines: Vec::new(),
+            search_case_sensitive: false,
+            search_scanned: 0,
+            search_jump_pending: false,
+            pending_anchor: None,
+            blame: None,
+        })
+    }
+
+    /// Whether the text is read on demand from a large file
+    pub fn is_large(&self) -> bool {
+        self.large.is_some()
+    }
+
+    /// Number of lines (for a large file, those indexed so far)
+    pub fn line_count(&self) -> usize {
+        match &self.large {
+            Some(large) => large.line_count(),
+            None => self.lines.len(),
+        }
+    }
+
+    /// Up to `count` lines from line `start`
+    ///
+    /// Lines of a large file are read and sanitized on each call; a read
+    /// error yields no lines.
+    pub fn window(&self, start: usize, count: usize) -> Vec<Cow<'_, str>> {
+        match &self.large {
+            Some(large) => large
+                .lines(start, count)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|line| {
+                    Cow::Owned(
+                        sanitize_content(
+                            &line,
+                            TAB_WIDTH.load(Ordering::Relaxed),
+                            MAX_LINE_WIDTH.load(Ordering::Relaxed),
+                        )
+                        .into_owned(),
+                    )
+                })
+                .collect(),
+            None => self
+                .lines
+                .iter()
+                .skip(start)
+                .take(count)
+                .map(|line| Cow::Borrowed(line.as_str()))
+                .collect(),
         }
     }
 
     /// Create a new text preview with syntax highlighting based on file extension
     pub fn with_highlighting(content: &str, path: &Path) -> Self {
+        let content = sanitize_content(
+            content,
+            TAB_WIDTH.load(Ordering::Relaxed),
+            MAX_LINE_WIDTH.load(Ordering::Relaxed),
+        );
         let lines: Vec<String> = content.lines().map(String::from).collect();
-        let styled_lines = highlight_content(content, path);
+        let styled_lines = highlight_content(&content, path);
         Self {
+            conflicts: find_conflicts(&lines),
             lines,
+            large: None,
             styled_lines,
             scroll: 0,
+            highlight: None,
+            search_query: None,
+            match_lines: Vec::new(),
+            search_case_sensitive: false,
+            search_scanned: 0,
+            search_jump_pending: false,
+            pending_anchor: None,
+            blame: None,
+        }
+    }
+
+    /// Highlight `line` (0-based) and scroll it to the middle of `height` rows
+    ///
+    /// A line past the end leaves the preview at the top. For a large file
+    /// still being indexed the anchor waits until the index reaches it (see
+    /// [`poll_anchor`](Self::poll_anchor)).
+    pub fn anchor(&mut self, line: usize, height: usize) {
+        if line >= self.line_count() {
+            if self.large.as_ref().is_some_and(|large| !large.is_indexed()) {
+                self.pending_anchor = Some((line, height));
+            }
+            return;
         }
+        self.pending_anchor = None;
+        self.highlight = Some(line);
+        self.scroll = line.saturating_sub(height / 2);
+    }
+
+    /// Apply an anchor that was waiting for the index
+    pub fn poll_anchor(&mut self) {
+        if let Some((line, height)) = self.pending_anchor.take() {
+            self.anchor(line, height);
+        }
+    }
+
+    /// Whether an anchor is waiting for the index to reach its line
+    pub fn is_anchoring(&self) -> bool {
+        self.pending_anchor.is_some()
+    }
+
+    /// Start searching for the lines containing `query` (smart case unless
+    /// `case_sensitive`)
+    ///
+    /// The first [`SEARCH_STEP_LINES`] lines are searched right away; the
+    /// event loop searches the rest through [`continue_search`](Self::continue_search).
+    pub fn search(&mut self, query: &str, case_sensitive: bool) {
+        self.search_query = Some(query.to_string());
+        self.search_case_sensitive = case_sensitive;
+        self.match_lines.clear();
+        self.search_scanned = 0;
+        self.search_jump_pending = false;
+        self.continue_search();
+    }
+
+    /// Search up to [`SEARCH_STEP_LINES`] more lines
+    ///
+    /// A large file is read through in chunks of its index, as far as it is
+    /// indexed so far.
+    pub fn continue_search(&mut self) {
+        const CHUNK_LINES: usize = 4096;
+
+        let Some(query) = self.search_query.clone() else {
+            return;
+        };
+        let end = self
+            .line_count()
+            .min(self.search_scanned.saturating_add(SEARCH_STEP_LINES));
+        while self.search_scanned < end {
+            let start = self.search_scanned;
+            let (found, read): (Vec<usize>, usize) = {
+                let window = self.window(start, CHUNK_LINES.min(end - start));
+                let found = window
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, line)| {
+                        !match_ranges(line, &query, self.search_case_sensitive).is_empty()
+                    })
+                    .map(|(offset, _)| start + offset)
+                    .collect();
+                (found, window.len())
+            };
+            if read == 0 {
+                break;
+            }
+            self.match_lines.extend(found);
+            self.search_scanned += read;
+        }
+    }
+
+    /// Whether lines remain to be searched (including lines not indexed yet)
+    pub fn is_searching(&self) -> bool {
+        self.search_query.is_some()
+            && (self.search_scanned < self.line_count()
+                || self.large.as_ref().is_some_and(|large| !large.is_indexed()))
+    }
+
+    /// Forget the search query and its matches
+    pub fn clear_search(&mut self) {
+        self.search_query = None;
+        self.match_lines.clear();
+        self.search_scanned = 0;
+        self.search_jump_pending = false;
+    }
+
+    /// Index in `match_lines` of the match to jump to from the scroll position
+    ///
+    /// `from_scroll` includes a match on the top line itself (for a new
+    /// search); otherwise searches strictly forward (or backward). Wraps
+    /// around at the ends.
+    pub fn match_from_scroll(&self, forward: bool, from_scroll: bool) -> Option<usize> {
+        let scroll = self.scroll;
+        let found = if from_scroll {
+            self.match_lines.iter().position(|&line| line >= scroll)
+        } else if forward {
+            self.match_lines.iter().position(|&line| line > scroll)
+        } else {
+            self.match_lines.iter().rposition(|&line| line < scroll)
+        };
+        match found {
+            Some(idx) => Some(idx),
+            None if self.match_lines.is_empty() => None,
+            None if forward => Some(0),
+            None => Some(self.match_lines.len() - 1),
+        }
+    }
+
+    /// Byte ranges of the search matches in line `idx`
+    fn search_ranges(&self, idx: usize, line: &str) -> Vec<Range<usize>> {
+        match &self.search_query {
+            Some(query) if self.match_lines.binary_search(&idx).is_ok() => {
+                match_ranges(line, query, self.search_case_sensitive)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Index of the conflict block to jump to from the scroll position
+    ///
+    /// Searches forward (or backward) and wraps around at the ends.
+    pub fn conflict_from_scroll(&self, forward: bool) -> Option<usize> {
+        let scroll = self.scroll;
+        let found = if forward {
+            self.conflicts.iter().position(|c| c.start > scroll)
+        } else {
+            self.conflicts.iter().rposition(|c| c.start < scroll)
+        };
+        match found {
+            Some(idx) => Some(idx),
+            None if self.conflicts.is_empty() => None,
+            None if forward => Some(0),
+            None => Some(self.conflicts.len() - 1),
+        }
+    }
+
+    /// Part of a conflict block line `idx` belongs to
+    fn conflict_part(&self, idx: usize) -> Option<ConflictPart> {
+        let at = self.conflicts.partition_point(|c| c.end < idx);
+        let region = self.conflicts.get(at).filter(|c| c.start <= idx)?;
+        let part = if idx == region.start
+            || idx == region.separator
+            || idx == region.end
+            || Some(idx) == region.base
+        {
+            ConflictPart::Marker
+        } else if idx > region.separator {
+            ConflictPart::Theirs
+        } else if region.base.is_some_and(|base| idx > base) {
+            ConflictPart::Base
+        } else {
+            ConflictPart::Ours
+        };
+        Some(part)
     }
 }
 
@@ -92,7 +630,7 @@ fn highlight_content(content: &str, path: &Path) -> Option<Vec<StyledLine>> {
         .and_then(|ext| ss.find_syntax_by_extension(ext))
         .or_else(|| ss.find_syntax_by_first_line(content.lines().next().unwrap_or("")))?;
 
-    let mut h = HighlightLines::new(syntax, theme);
+    let mut h = HighlightLines::new(syntax, &theme);
     let mut styled_lines = Vec::new();
 
     for line in LinesWithEndings::from(content) {
@@ -113,64 +651,243 @@ fn highlight_content(content: &str, path: &Path) -> Option<Vec<StyledLine>> {
     Some(styled_lines)
 }
 
+/// Width of the line number gutter for a file with `line_count` lines
+///
+/// Holds the widest line number (at least 3 digits) plus a separating space.
+pub fn gutter_width(line_count: usize) -> usize {
+    line_count.max(1).to_string().len().max(3) + 1
+}
+
+/// Number of rows a line takes when soft-wrapped to `width` columns
+pub fn wrapped_line_count(line: &str, width: usize) -> usize {
+    wrap_line(line, width).1.len()
+}
+
+/// Split a line into rows of at most `width` display columns
+///
+/// Returns the continuation indent and the byte range of each row.
+/// Continuation rows are indented by the line's leading whitespace so they
+/// align under the code (unless that would leave less than half the width).
+fn wrap_line(line: &str, width: usize) -> (usize, Vec<(usize, usize)>) {
+    let indent = line.chars().take_while(|c| *c == ' ').count();
+    let indent = if indent * 2 > width { 0 } else { indent };
+
+    let mut rows = Vec::new();
+    let mut row_start = 0;
+    let mut row_width = 0;
+    for (i, c) in line.char_indices() {
+        let capacity = if rows.is_empty() {
+            width
+        } else {
+            width - indent
+        };
+        let char_width = c.width().unwrap_or(0);
+        if row_width + char_width > capacity && row_width > 0 {
+            rows.push((row_start, i));
+            row_start = i;
+            row_width = 0;
+        }
+        row_width += char_width;
+    }
+    rows.push((row_start, line.len()));
+    (indent, rows)
+}
+
+/// Spans for the byte range `start..end` of a line, keeping highlight colors
+fn line_spans<'a>(
+    line: &'a str,
+    styled: Option<&'a StyledLine>,
+    start: usize,
+    end: usize,
+) -> Vec<Span<'a>> {
+    let Some(styled) = styled else {
+        return vec![Span::raw(&line[start..end])];
+    };
+
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    for segment in &styled.segments {
+        let seg_start = offset;
+        offset += segment.text.len();
+        let (from, to) = (seg_start.max(start), offset.min(end));
+        if from < to {
+            spans.push(Span::styled(
+                &segment.text[from - seg_start..to - seg_start],
+                Style::default().fg(segment.color),
+            ));
+        }
+        if offset >= end {
+            break;
+        }
+    }
+    spans
+}
+
+/// Spans for `start..end` of a line with the search matches in `ranges` marked
+fn search_spans<'a>(
+    line: &'a str,
+    styled: Option<&'a StyledLine>,
+    start: usize,
+    end: usize,
+    ranges: &[Range<usize>],
+) -> Vec<Span<'a>> {
+    let match_style = Style::default()
+        .fg(Color::Black)
+        .bg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let mut spans = Vec::new();
+    let mut pos = start;
+    for range in ranges {
+        let (from, to) = (range.start.max(start), range.end.min(end));
+        if from >= to {
+            continue;
+        }
+        if pos < from {
+            spans.extend(line_spans(line, styled, pos, from));
+        }
+        spans.extend(
+            line_spans(line, styled, from, to)
+                .into_iter()
+                .map(|span| span.patch_style(match_style)),
+        );
+        pos = to;
+    }
+    if pos < end {
+        spans.extend(line_spans(line, styled, pos, end));
+    }
+    spans
+}
+
 /// Render text preview
+///
+/// `scroll` counts source lines; with wrapping enabled the scrollbar is
+/// measured in wrapped rows.
 pub fn render_text_preview(
     frame: &mut Frame,
     preview: &TextPreview,
     area: Rect,
     title: &str,
     focused: bool,
+    options: TextViewOptions,
 ) {
-    let visible_height = area.height.saturating_sub(2) as usize;
-    let start = preview.scroll;
-    let end = (start + visible_height).min(preview.lines.len());
-
-    let lines: Vec<Line> = if let Some(ref styled_lines) = preview.styled_lines {
-        // Render with syntax highlighting
-        styled_lines[start..end]
-            .iter()
-            .enumerate()
-            .map(|(i, styled_line)| {
-                let line_num = start + i + 1;
-                let mut spans = vec![Span::styled(
-                    format!("{:4} ", line_num),
-                    Style::default().fg(Color::DarkGray),
-                )];
-                for segment in &styled_line.segments {
-                    spans.push(Span::styled(
-                        segment.text.clone(),
-                        Style::default().fg(segment.color),
-                    ));
-                }
-                Line::from(spans)
-            })
-            .collect()
+    let visible_height = preview_content_area(area).height as usize;
+    let gutter = if options.line_numbers {
+        gutter_width(preview.line_count())
     } else {
-        // Render plain text (fallback)
-        preview.lines[start..end]
-            .iter()
-            .enumerate()
-            .map(|(i, line)| {
-                let line_num = start + i + 1;
-                Line::from(vec![
-                    Span::styled(
-                        format!("{:4} ", line_num),
-                        Style::default().fg(Color::DarkGray),
-                    ),
-                    Span::raw(line.as_str()),
-                ])
-            })
-            .collect()
+        0
     };
+    let blame_width = preview.blame.as_ref().map_or(0, BlamePreview::gutter_width);
+    let text_width = (preview_content_area(area).width as usize)
+        .saturating_sub(gutter + blame_width)
+        .max(1);
+    let start = preview.scroll.min(preview.line_count());
+    let gutter_style = Style::default().fg(Color::DarkGray);
+
+    let mut lines: Vec<Line> = Vec::with_capacity(visible_height);
+    let window = preview.window(start, visible_height);
+    for (offset, line) in window.iter().enumerate() {
+        let (idx, line) = (start + offset, line.as_ref());
+        if lines.len() >= visible_height {
+            break;
+        }
+        let conflict = preview.conflict_part(idx);
+        // Marker lines stand out; the gutter shows which side a line is on
+        let styled = match conflict {
+            Some(ConflictPart::Marker) => None,
+            _ => preview.styled_lines.as_ref().and_then(|s| s.get(idx)),
+        };
+        let line_gutter_style = match conflict {
+            Some(ConflictPart::Ours) => gutter_style.fg(Color::Green),
+            Some(ConflictPart::Base) => gutter_style.fg(Color::Yellow),
+            Some(ConflictPart::Theirs) => gutter_style.fg(Color::Cyan),
+            _ => gutter_style,
+        };
+        let ranges = preview.search_ranges(idx, line);
+        let (indent, rows) = if options.wrap {
+            wrap_line(line, text_width)
+        } else {
+            (0, vec![(0, line.len())])
+        };
+
+        for (row, &(from, to)) in rows.iter().enumerate() {
+            if lines.len() >= visible_height {
+                break;
+            }
+            let mut spans = Vec::new();
+            if let Some(blame) = &preview.blame {
+                spans.push(if row == 0 {
+                    blame.gutter(idx, start)
+                } else {
+                    Span::raw(" ".repeat(blame_width))
+                });
+            }
+            if options.line_numbers {
+                let label = if row == 0 {
+                    format!("{:>width$} ", idx + 1, width = gutter - 1)
+                } else {
+                    " ".repeat(gutter)
+                };
+                spans.push(Span::styled(label, line_gutter_style));
+            }
+            if row > 0 && indent > 0 {
+                spans.push(Span::raw(" ".repeat(indent)));
+            }
+            if preview.highlight == Some(idx) {
+                // The anchored line keeps its colors on the selection background
+                let bg = theme().selection;
+                spans.extend(
+                    line_spans(line, styled, from, to)
+                        .into_iter()
+                        .map(|span| span.patch_style(Style::default().bg(bg))),
+                );
+            } else if conflict == Some(ConflictPart::Marker) {
+                spans.push(Span::styled(
+                    &line[from..to],
+                    Style::default()
+                        .fg(theme().git_conflict)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            } else if !ranges.is_empty() {
+                spans.extend(search_spans(line, styled, from, to, &ranges));
+            } else {
+                spans.extend(line_spans(line, styled, from, to));
+            }
+            lines.push(Line::from(spans));
+        }
+    }
 
-    let widget = Paragraph::new(lines).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(format!(" {} ", title))
-            .border_style(get_border_style(focused)),
-    );
+    let title = match &preview.search_query {
+        Some(query) => format!(
+            " {} [/{}: {} match{}] ",
+            title,
+            query,
+            preview.match_lines.len(),
+            if preview.match_lines.len() == 1 {
+                ""
+            } else {
+                "es"
+            }
+        ),
+        None => format!(" {} ", title),
+    };
+    let widget = Paragraph::new(lines).block(preview_block(title, focused));
 
     frame.render_widget(widget, area);
+
+    // Wrapped rows of a large file are not counted; its bar follows lines
+    let (total_rows, scroll_rows) = if options.wrap && !preview.is_large() {
+        let counts: Vec<usize> = preview
+            .lines
+            .iter()
+            .map(|line| wrapped_line_count(line, text_width))
+            .collect();
+        (counts.iter().sum(), counts[..start].iter().sum())
+    } else {
+        (preview.line_count(), preview.scroll)
+    };
+    if preview_borders() {
+        render_scrollbar(frame, area, total_rows, visible_height, scroll_rows);
+    }
 }
 
 /// Check if a file is likely a text file
@@ -241,3 +958,260 @@ pub fn is_text_file(path: &std::path::Path) -> bool {
         )
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_centers_line() {
+        let text: String = (1..=100).map(|n| format!("{}\n", n)).collect();
+        let mut preview = TextPreview::new(&text);
+
+        preview.anchor(41, 20);
+        assert_eq!(preview.highlight, Some(41));
+        assert_eq!(preview.scroll, 31);
+
+        // Near the top there is nothing to center against
+        preview.anchor(3, 20);
+        assert_eq!(preview.scroll, 0);
+
+        // Lines past the end open at the top without a highlight
+        let mut preview = TextPreview::new(&text);
+        preview.anchor(500, 20);
+        assert_eq!((preview.highlight, preview.scroll), (None, 0));
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        let text = "keep\n<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> feature\n\
+                    mid\n<<<<<<< HEAD\na\n||||||| base\nb\n=======\nc\n>>>>>>> other\n\
+                    <<<<<<< unterminated\n=======\n";
+        let preview = TextPreview::new(text);
+        assert_eq!(
+            preview.conflicts,
+            vec![
+                ConflictRegion {
+                    start: 1,
+                    base: None,
+                    separator: 3,
+                    end: 5
+                },
+                ConflictRegion {
+                    start: 7,
+                    base: Some(9),
+                    separator: 11,
+                    end: 13
+                },
+            ]
+        );
+        assert_eq!(preview.conflict_part(0), None);
+        assert_eq!(preview.conflict_part(2), Some(ConflictPart::Ours));
+        assert_eq!(preview.conflict_part(4), Some(ConflictPart::Theirs));
+        assert_eq!(preview.conflict_part(9), Some(ConflictPart::Marker));
+        assert_eq!(preview.conflict_part(10), Some(ConflictPart::Base));
+        assert_eq!(preview.conflict_part(6), None);
+
+        // Jumping wraps around
+        assert_eq!(preview.conflict_from_scroll(true), Some(0));
+        let mut preview = preview;
+        preview.scroll = 7;
+        assert_eq!(preview.conflict_from_scroll(true), Some(0));
+        assert_eq!(preview.conflict_from_scroll(false), Some(0));
+        preview.scroll = 1;
+        assert_eq!(preview.conflict_from_scroll(false), Some(1));
+
+        // Marker-like lines outside a block are plain text
+        assert!(find_conflicts(&["=======".to_string(), "<<<<<<<<".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn test_search_matches_and_wraps() {
+        let mut preview = TextPreview::new("fn main\nlet x\nMain loop\nend\nmain again\n");
+        preview.search("main", false);
+        assert_eq!(preview.match_lines, vec![0, 2, 4]);
+        assert_eq!(preview.search_ranges(2, "Main loop"), vec![0..4]);
+        assert!(preview.search_ranges(1, "let x").is_empty());
+
+        // Smart case: an uppercase query matches exactly
+        preview.search("Main", false);
+        assert_eq!(preview.match_lines, vec![2]);
+        preview.search("main", true);
+        assert_eq!(preview.match_lines, vec![0, 4]);
+
+        preview.search("main", false);
+        preview.scroll = 2;
+        assert_eq!(preview.match_from_scroll(true, true), Some(1));
+        assert_eq!(preview.match_from_scroll(true, false), Some(2));
+        assert_eq!(preview.match_from_scroll(false, false), Some(0));
+        preview.scroll = 4;
+        assert_eq!(preview.match_from_scroll(true, false), Some(0));
+
+        preview.clear_search();
+        assert!(preview.search_query.is_none());
+        assert_eq!(preview.match_from_scroll(true, true), None);
+    }
+
+    #[test]
+    fn test_large_file_search_and_anchor_follow_the_index() {
+        use std::io::Write;
+        use std::time::{Duration, Instant};
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        let lines = SEARCH_STEP_LINES * 2 + 10;
+        for n in 0..lines {
+            writeln!(file, "{}", if n % 1000 == 7 { "needle" } else { "hay" }).unwrap();
+        }
+        file.flush().unwrap();
+
+        let mut preview = TextPreview::open_large(file.path()).unwrap();
+        // Anchoring past the index waits for it instead of being dropped
+        preview.anchor(lines - 1, 20);
+        preview.search("needle", false);
+        assert!(preview.match_lines.len() <= SEARCH_STEP_LINES / 1000 + 1);
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while preview.is_searching() || preview.is_anchoring() {
+            assert!(Instant::now() < deadline, "search never finished");
+            preview.poll_anchor();
+            preview.continue_search();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(preview.highlight, Some(lines - 1));
+        assert_eq!(preview.match_lines.len(), lines.div_ceil(1000));
+        assert_eq!(preview.match_lines[1], 1007);
+    }
+
+    #[test]
+    fn test_search_spans_mark_matches_within_row() {
+        let spans = search_spans("abcabcx", None, 1, 7, &[0..3, 3..6]);
+        let text: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, vec!["bc", "abc", "x"]);
+        assert_eq!(spans[1].style.bg, Some(Color::Yellow));
+        assert_eq!(spans[2].style.bg, None);
+    }
+
+    #[test]
+    fn test_load_theme_known_name() {
+        let (_, found) = load_theme("Solarized (dark)");
+        assert!(found);
+        let (_, found) = load_theme("BASE16-OCEAN.DARK");
+        assert!(found);
+    }
+
+    #[test]
+    fn test_load_theme_unknown_falls_back() {
+        let (theme, found) = load_theme("no-such-theme");
+        assert!(!found);
+        let (default, _) = load_theme(DEFAULT_SYNTAX_THEME);
+        assert_eq!(theme.name, default.name);
+    }
+
+    #[test]
+    fn test_available_syntax_themes_contains_default() {
+        assert!(available_syntax_themes()
+            .iter()
+            .any(|t| t == DEFAULT_SYNTAX_THEME));
+    }
+
+    #[test]
+    fn test_expand_tabs_aligns_to_tab_stops() {
+        assert_eq!(expand_tabs("\tx", 4), "    x");
+        assert_eq!(expand_tabs("ab\tx", 4), "ab  x");
+        assert_eq!(expand_tabs("a\n\tb", 2), "a\n  b");
+    }
+
+    #[test]
+    fn test_sanitize_truncates_long_line() {
+        let content = format!("{}\nshort\n", "x".repeat(100_000));
+        let out = sanitize_content(&content, 4, 80);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], format!("{} … [+99920 chars]", "x".repeat(80)));
+        assert_eq!(lines[1], "short");
+
+        // No limit keeps the line
+        assert_eq!(sanitize_content(&content, 4, 0).len(), content.len());
+        // Wide characters count two columns
+        assert_eq!(
+            sanitize_content("日本語テキスト", 4, 5),
+            "日本 … [+5 chars]"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_control_characters() {
+        let out = sanitize_content("a\0b\x1b[31mred\x7f\u{9b}\r\n\tx\r\n", 4, 0);
+        assert_eq!(out, "a^@b^[[31mred^?·\r\n    x\r\n");
+        assert!(!out
+            .chars()
+            .any(|c| c.is_control() && c != '\r' && c != '\n'));
+        assert_eq!(sanitize_content("lone\rreturn", 4, 0), "lone^Mreturn");
+
+        // Plain text is borrowed untouched
+        assert!(matches!(
+            sanitize_content("plain\ntext\n", 4, 80),
+            Cow::Borrowed(_)
+        ));
+        // A tab width of 0 keeps tabs
+        assert_eq!(sanitize_content("\tx", 0, 0), "\tx");
+    }
+
+    #[test]
+    fn test_wrapped_line_count() {
+        assert_eq!(wrapped_line_count("", 10), 1);
+        assert_eq!(wrapped_line_count("short", 10), 1);
+        assert_eq!(wrapped_line_count("0123456789", 10), 1);
+        assert_eq!(wrapped_line_count("0123456789a", 10), 2);
+        assert_eq!(wrapped_line_count(&"x".repeat(25), 10), 3);
+        // Wide characters take two columns
+        assert_eq!(wrapped_line_count("日本語テキスト", 6), 3);
+    }
+
+    #[test]
+    fn test_wrapped_continuations_are_indented() {
+        // 4 columns of indent leave 6 columns per continuation row
+        let line = format!("    {}", "x".repeat(12));
+        let (indent, rows) = wrap_line(&line, 10);
+        assert_eq!(indent, 4);
+        assert_eq!(rows, vec![(0, 10), (10, 16)]);
+        assert_eq!(wrapped_line_count(&line, 10), 2);
+
+        // Indent wider than half the width is ignored
+        let deep = format!("{}{}", " ".repeat(8), "x".repeat(8));
+        assert_eq!(wrap_line(&deep, 10).0, 0);
+    }
+
+    #[test]
+    fn test_gutter_width_scales_with_line_count() {
+        assert_eq!(gutter_width(0), 4);
+        assert_eq!(gutter_width(999), 4);
+        assert_eq!(gutter_width(1000), 5);
+        assert_eq!(gutter_width(123_456), 7);
+    }
+
+    #[test]
+    fn test_line_spans_split_highlighted_segments() {
+        let styled = StyledLine {
+            segments: vec![
+                StyledSegment {
+                    text: "let ".to_string(),
+                    color: Color::Red,
+                },
+                StyledSegment {
+                    text: "x = 1;\n".to_string(),
+                    color: Color::Blue,
+                },
+            ],
+        };
+        let spans = line_spans("let x = 1;", Some(&styled), 2, 7);
+        let texts: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, vec!["t ", "x ="]);
+        assert_eq!(spans[1].style.fg, Some(Color::Blue));
+    }
+
+    #[test]
+    fn test_expand_tabs_zero_width_keeps_tabs() {
+        assert_eq!(expand_tabs("\tx", 0), "\tx");
+    }
+}
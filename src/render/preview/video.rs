@@ -6,12 +6,12 @@ use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::Paragraph,
     Frame,
 };
 use ratatui_image::{FontSize, Resize, StatefulImage};
 
-use super::common::get_border_style;
+use super::common::preview_block;
 use super::image::{calculate_centered_image_area, ImagePreview};
 
 /// Video preview content
@@ -57,10 +57,7 @@ pub fn render_video_preview(
     // Build title with duration
     let full_title = format!(" {} [{}] ", title, meta.format_duration());
 
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .title(full_title)
-        .border_style(get_border_style(focused));
+    let block = preview_block(full_title, focused);
 
     let inner_area = block.inner(area);
     frame.render_widget(block, area);
@@ -0,0 +1,97 @@
+//! Recent files popup rendering.
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem},
+    Frame,
+};
+
+use crate::core::{AppState, ViewMode};
+use crate::integrate::RecentEntry;
+use crate::render::fuzzy_scores;
+
+/// Path of a recent file as listed in the popup (relative to the root if inside)
+fn recent_label(state: &AppState, entry: &RecentEntry) -> String {
+    entry
+        .path
+        .strip_prefix(&state.root)
+        .unwrap_or(&entry.path)
+        .display()
+        .to_string()
+}
+
+/// Recent files whose label fuzzy-matches `query`, newest first
+pub fn recent_matches<'a>(state: &'a AppState, query: &str) -> Vec<&'a RecentEntry> {
+    let entries = state.recent_files.entries();
+    if query.is_empty() {
+        return entries.iter().collect();
+    }
+    let labels: Vec<String> = entries.iter().map(|e| recent_label(state, e)).collect();
+    let candidates: Vec<&str> = labels.iter().map(String::as_str).collect();
+    entries
+        .iter()
+        .zip(fuzzy_scores(query, &candidates, state.case_sensitive))
+        .filter_map(|(entry, score)| score.map(|_| entry))
+        .collect()
+}
+
+/// Render recent files popup (O)
+pub fn render_recent_files_popup(frame: &mut Frame, state: &AppState) {
+    let ViewMode::RecentFiles { query, selected } = &state.mode else {
+        return;
+    };
+
+    let area = frame.area();
+    let width = area.width.saturating_sub(6).clamp(30, 80);
+    let height = area.height.saturating_sub(6).clamp(8, 20);
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+    let popup = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, popup);
+
+    // Keep the selected entry visible when the list is longer than the popup
+    let max_items = (height.saturating_sub(2) as usize).max(1);
+    let offset = selected.saturating_sub(max_items - 1);
+
+    let matches = recent_matches(state, query);
+    let mut items: Vec<ListItem> = matches
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(max_items)
+        .map(|(idx, entry)| {
+            let style = if idx == *selected {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let text = recent_label(state, entry);
+            ListItem::new(Line::from(vec![Span::styled(text, style)]))
+        })
+        .collect();
+    if items.is_empty() {
+        items.push(ListItem::new(Line::from(Span::styled(
+            "No matches",
+            Style::default().fg(Color::DarkGray),
+        ))));
+    }
+
+    let title = if query.is_empty() {
+        " Recent Files ".to_string()
+    } else {
+        format!(" Recent Files: {} ", query)
+    };
+    let widget = List::new(items).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    frame.render_widget(widget, popup);
+}
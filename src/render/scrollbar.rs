@@ -0,0 +1,106 @@
+//! Scrollbar rendering for the tree and preview panes
+
+use ratatui::{
+    layout::{Margin, Rect},
+    style::Style,
+    widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
+};
+
+use super::theme::theme;
+
+/// Compute scrollbar state for a list of `total` lines showing `visible` at a time
+///
+/// Returns None when everything fits on screen (the scrollbar is hidden).
+/// The content length is the number of distinct scroll positions, so the thumb
+/// reaches the bottom of the track when the last line is visible.
+pub fn scrollbar_state(total: usize, visible: usize, position: usize) -> Option<ScrollbarState> {
+    if visible == 0 || total <= visible {
+        return None;
+    }
+    let max_position = total - visible;
+    Some(
+        ScrollbarState::new(max_position + 1)
+            .position(position.min(max_position))
+            .viewport_content_length(visible),
+    )
+}
+
+/// Render a vertical scrollbar over the right border of a bordered pane
+pub fn render_scrollbar(
+    frame: &mut Frame,
+    area: Rect,
+    total: usize,
+    visible: usize,
+    position: usize,
+) {
+    let Some(mut scroll_state) = scrollbar_state(total, visible, position) else {
+        return;
+    };
+
+    let t = theme();
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .track_style(Style::default().fg(t.border))
+        .thumb_style(Style::default().fg(t.border_active));
+
+    frame.render_stateful_widget(
+        scrollbar,
+        area.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        &mut scroll_state,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{buffer::Buffer, widgets::StatefulWidget};
+
+    /// Render a scrollbar into a 1-column buffer and return the thumb rows
+    fn thumb_rows(total: usize, visible: usize, position: usize) -> Vec<u16> {
+        let area = Rect::new(0, 0, 1, visible as u16);
+        let mut buf = Buffer::empty(area);
+        let mut state = scrollbar_state(total, visible, position).unwrap();
+        Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .render(area, &mut buf, &mut state);
+        (0..area.height)
+            .filter(|&y| buf[(0, y)].symbol() == "█")
+            .collect()
+    }
+
+    #[test]
+    fn test_scrollbar_hidden_when_content_fits() {
+        assert!(scrollbar_state(5, 10, 0).is_none());
+        assert!(scrollbar_state(10, 10, 0).is_none());
+        assert!(scrollbar_state(10, 0, 0).is_none());
+        assert!(scrollbar_state(11, 10, 0).is_some());
+    }
+
+    #[test]
+    fn test_scrollbar_thumb_at_top() {
+        let rows = thumb_rows(100, 10, 0);
+        assert_eq!(rows.first(), Some(&0));
+        assert_eq!(rows.len(), 1);
+    }
+
+    #[test]
+    fn test_scrollbar_thumb_in_middle() {
+        let rows = thumb_rows(100, 10, 45);
+        let first = *rows.first().unwrap();
+        assert!((3..=6).contains(&first), "thumb at row {}", first);
+    }
+
+    #[test]
+    fn test_scrollbar_thumb_at_bottom() {
+        let rows = thumb_rows(100, 10, 90);
+        assert_eq!(rows.last(), Some(&9));
+        // Positions past the end are clamped
+        assert_eq!(thumb_rows(100, 10, 500), rows);
+    }
+}
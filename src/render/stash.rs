@@ -0,0 +1,68 @@
+//! Git stash popup rendering.
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem},
+    Frame,
+};
+
+use crate::core::{AppState, ViewMode};
+
+/// Render git stash popup (Z)
+pub fn render_git_stash_popup(frame: &mut Frame, state: &AppState) {
+    let ViewMode::GitStash { stashes, selected } = &state.mode else {
+        return;
+    };
+
+    let area = frame.area();
+    let width = area.width.saturating_sub(6).clamp(30, 80);
+    let height = area.height.saturating_sub(6).clamp(8, 20);
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+    let popup = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, popup);
+
+    // Keep the selected stash visible when the list is longer than the popup
+    let max_items = (height.saturating_sub(2) as usize).max(1);
+    let offset = selected.saturating_sub(max_items - 1);
+
+    let items: Vec<ListItem> = stashes
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(max_items)
+        .map(|(idx, stash)| {
+            let is_selected = idx == *selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let dim = if is_selected {
+                style
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            let mut spans = vec![Span::styled(format!("{} ", stash.name()), dim)];
+            if !stash.branch.is_empty() {
+                spans.push(Span::styled(format!("[{}] ", stash.branch), dim));
+            }
+            spans.push(Span::styled(stash.message.clone(), style));
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let widget = List::new(items).block(
+        Block::default()
+            .title(" Git Stash (Enter: apply, p: pop, d: drop) ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    frame.render_widget(widget, popup);
+}
@@ -1,8 +1,8 @@
 //! Status bar and input popup rendering
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
-use std::time::SystemTime;
+use std::time::{Instant, SystemTime};
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -17,15 +17,35 @@ use std::io::{BufRead, BufReader};
 
 use super::layout::LayoutEngine;
 use super::theme::theme;
+use crate::action::{DeleteSummary, LinkKind, PastePlan};
 use crate::core::{
-    AppState, InputPurpose, PendingAction, PreviewDisplayMode, SortMode, UiDensity, ViewMode,
+    AppState, InputPurpose, PatternSelect, PendingAction, PreviewDisplayMode, Severity, SortMode,
+    UiDensity, ViewMode,
 };
+use crate::git::Stash;
 
 /// Render the status bar with adaptive layout based on screen width
 pub fn render_status_bar(
     frame: &mut Frame,
     state: &AppState,
     focused_path: Option<&PathBuf>,
+    total_entries: usize,
+    area: Rect,
+) {
+    render_status_content(frame, state, focused_path, total_entries, area);
+    // An error just shown inverts the whole bar for a moment
+    if state.is_flashing(Instant::now()) {
+        frame
+            .buffer_mut()
+            .set_style(area, Style::default().add_modifier(Modifier::REVERSED));
+    }
+}
+
+fn render_status_content(
+    frame: &mut Frame,
+    state: &AppState,
+    focused_path: Option<&PathBuf>,
+    total_entries: usize,
     area: Rect,
 ) {
     // Check if peek mode is enabled - render peek preview instead of normal status
@@ -34,6 +54,11 @@ pub fn render_status_bar(
         return;
     }
 
+    if let Some(template) = state.status_format.as_deref() {
+        render_template_status(frame, state, template, focused_path, total_entries, area);
+        return;
+    }
+
     let density = state.ui_density_for_width(area.width);
     let layout = LayoutEngine::from_rect_with_density(area, density);
 
@@ -41,11 +66,172 @@ pub fn render_status_bar(
     match layout.density {
         UiDensity::Ultra => render_ultra_compact_status(frame, state, area),
         UiDensity::Narrow => render_compact_status(frame, state, focused_path, area),
-        UiDensity::Compact => render_narrow_status(frame, state, focused_path, area),
-        UiDensity::Full => render_full_status(frame, state, focused_path, area),
+        UiDensity::Compact => render_narrow_status(frame, state, focused_path, total_entries, area),
+        UiDensity::Full => render_full_status(frame, state, focused_path, total_entries, area),
+    }
+}
+
+/// A piece of state a status template can show as `{name}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusField {
+    /// Select or pick mode indicator
+    Mode,
+    /// Focused path relative to the root
+    Path,
+    /// Git branch, repository state and changed file count
+    Git,
+    /// Marked entries and their total size
+    Selection,
+    /// Sort indicator (empty for the default sort)
+    Sort,
+    /// Status message or the help hint
+    Message,
+    /// Cursor position in the tree
+    Position,
+    /// Size and modification time of the focused entry
+    Info,
+    /// Clipboard contents
+    Clipboard,
+}
+
+impl StatusField {
+    /// Field named by a template token
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "mode" => Some(Self::Mode),
+            "path" => Some(Self::Path),
+            "git" => Some(Self::Git),
+            "selection" => Some(Self::Selection),
+            "sort" => Some(Self::Sort),
+            "message" => Some(Self::Message),
+            "position" => Some(Self::Position),
+            "info" => Some(Self::Info),
+            "clipboard" => Some(Self::Clipboard),
+            _ => None,
+        }
+    }
+
+    fn style(self, state: &AppState) -> Style {
+        let t = theme();
+        match self {
+            Self::Mode => Style::default().fg(t.selection),
+            Self::Path => Style::default().fg(t.border_active),
+            Self::Git => Style::default().fg(t.git_staged),
+            Self::Selection => Style::default().fg(t.mark),
+            Self::Sort => Style::default().fg(t.git_conflict),
+            Self::Message => message_style(state),
+            Self::Position | Self::Info | Self::Clipboard => Style::default(),
+        }
+    }
+}
+
+/// One piece of an expanded status template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatusSegment {
+    /// Text between tokens, and unknown tokens as written
+    Literal(String),
+    /// Current text of a field (may be empty)
+    Field(StatusField, String),
+}
+
+/// Expand a status template such as `{mode} {path} {git}` for `state`
+pub fn expand_status_template(
+    template: &str,
+    state: &AppState,
+    focused_path: Option<&PathBuf>,
+    total_entries: usize,
+) -> Vec<StatusSegment> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        literal.push_str(&rest[..open]);
+        let token = &rest[open..];
+        let field = token
+            .find('}')
+            .and_then(|close| Some((StatusField::from_name(&token[1..close])?, close)));
+        match field {
+            Some((field, close)) => {
+                if !literal.is_empty() {
+                    segments.push(StatusSegment::Literal(std::mem::take(&mut literal)));
+                }
+                let text = status_field_text(field, state, focused_path, total_entries);
+                segments.push(StatusSegment::Field(field, text));
+                rest = &token[close + 1..];
+            }
+            None => {
+                literal.push('{');
+                rest = &token[1..];
+            }
+        }
+    }
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        segments.push(StatusSegment::Literal(literal));
+    }
+    segments
+}
+
+fn status_field_text(
+    field: StatusField,
+    state: &AppState,
+    focused_path: Option<&PathBuf>,
+    total_entries: usize,
+) -> String {
+    match field {
+        StatusField::Mode => mode_indicator(state).trim_end().to_string(),
+        StatusField::Path => focused_path
+            .map(|path| {
+                let relative = path.strip_prefix(&state.root).unwrap_or(path);
+                if relative.as_os_str().is_empty() {
+                    ".".to_string()
+                } else {
+                    relative.display().to_string()
+                }
+            })
+            .unwrap_or_default(),
+        StatusField::Git => {
+            let changed = state
+                .git_status
+                .as_ref()
+                .map_or(0, |g| g.changed_paths().len());
+            match branch_indicator(state) {
+                Some(branch) if changed > 0 => format!("{} ±{}", branch, changed),
+                branch => branch.unwrap_or_default(),
+            }
+        }
+        StatusField::Selection if state.selected_paths.is_empty() => String::new(),
+        StatusField::Selection => format!("Selected: {}", selection_label(state)),
+        StatusField::Sort => sort_indicator(state).unwrap_or_default(),
+        StatusField::Message => message_or(state, "? for help"),
+        StatusField::Position => position_indicator(state, total_entries)
+            .trim_end_matches(" | ")
+            .to_string(),
+        StatusField::Info => focused_file_info(state, focused_path),
+        StatusField::Clipboard => clipboard_indicator(state).unwrap_or_default(),
     }
 }
 
+/// Render the status bar from the `ui.status_format` template
+fn render_template_status(
+    frame: &mut Frame,
+    state: &AppState,
+    template: &str,
+    focused_path: Option<&PathBuf>,
+    total_entries: usize,
+    area: Rect,
+) {
+    let spans: Vec<Span> = expand_status_template(template, state, focused_path, total_entries)
+        .into_iter()
+        .map(|segment| match segment {
+            StatusSegment::Literal(text) => Span::raw(text),
+            StatusSegment::Field(field, text) => Span::styled(text, field.style(state)),
+        })
+        .collect();
+    let widget = Paragraph::new(Line::from(spans)).block(Block::default().borders(Borders::ALL));
+    frame.render_widget(widget, area);
+}
+
 /// Render peek mode status bar (shows file preview in status area)
 fn render_peek_status(
     frame: &mut Frame,
@@ -202,6 +388,25 @@ fn read_peek_lines(path: &PathBuf, max_lines: usize) -> Vec<String> {
     }
 }
 
+/// Status message, else the pending count prefix (as vim shows it), else `hint`
+fn message_or(state: &AppState, hint: &str) -> String {
+    match (state.message_text(), state.count_prefix) {
+        (Some(message), _) => message.to_string(),
+        (None, Some(count)) => count.to_string(),
+        (None, None) => hint.to_string(),
+    }
+}
+
+/// Style of the status message text by severity
+fn message_style(state: &AppState) -> Style {
+    let t = theme();
+    match state.message_severity() {
+        Some(Severity::Warning) => Style::default().fg(t.warning),
+        Some(Severity::Error) => Style::default().fg(t.error).add_modifier(Modifier::BOLD),
+        Some(Severity::Info) | None => Style::default(),
+    }
+}
+
 /// Render ultra-compact status bar for extremely narrow screens (< 25 chars)
 /// Shows minimal info: `? 3* ⎇m` (help, selection count, git branch)
 /// Optimized for 20-24 character width terminals
@@ -238,19 +443,19 @@ fn render_ultra_compact_status(frame: &mut Frame, state: &AppState, area: Rect)
         ));
     }
 
-    // Sort mode indicator (single char, only if not default)
-    if state.sort_mode != SortMode::Name {
+    // Sort mode indicator (single char plus direction, only if not default)
+    if is_custom_sort(state) {
         if !spans.is_empty() {
             spans.push(Span::raw(" "));
         }
         spans.push(Span::styled(
-            state.sort_mode.short_name(),
+            format!("{}{}", state.sort_mode.short_name(), sort_arrow(state)),
             Style::default().fg(t.git_conflict),
         ));
     }
 
-    // Filter indicator (just an icon)
-    if state.filter_pattern.is_some() {
+    // Filter indicator (just an icon, also for the git changed-only view)
+    if state.filter_pattern.is_some() || state.git_changed_only {
         if !spans.is_empty() {
             spans.push(Span::raw(" "));
         }
@@ -264,7 +469,8 @@ fn render_ultra_compact_status(frame: &mut Frame, state: &AppState, area: Rect)
     }
 
     // Message (only if there's significant space left)
-    if let Some(msg) = &state.message {
+    let msg = message_or(state, "");
+    if !msg.is_empty() {
         let used_width: usize = spans.iter().map(|s| s.width()).sum();
         let available = inner_width.saturating_sub(used_width + 1);
         if available > 3 {
@@ -272,9 +478,9 @@ fn render_ultra_compact_status(frame: &mut Frame, state: &AppState, area: Rect)
             let truncated = if msg.len() > available {
                 format!("{}…", &msg[..available.saturating_sub(1)])
             } else {
-                msg.clone()
+                msg.to_string()
             };
-            spans.push(Span::raw(truncated));
+            spans.push(Span::styled(truncated, message_style(state)));
         }
     }
 
@@ -297,8 +503,8 @@ fn render_compact_status(
     let mut spans = Vec::new();
 
     // Help or message (highest priority)
-    let message = state.message.as_deref().unwrap_or("?");
-    spans.push(Span::raw(format!(" {}", message)));
+    let message = message_or(state, "?");
+    spans.push(Span::styled(format!(" {}", message), message_style(state)));
 
     // File size only (no modification time)
     if let Some(size) = focused_path.and_then(|p| get_file_size_only(p.as_path())) {
@@ -313,6 +519,12 @@ fn render_compact_status(
             format!("\u{e0a0}{}", branch),
             Style::default().fg(t.git_staged),
         ));
+        if let Some(label) = git_state_label(state) {
+            spans.push(Span::styled(
+                format!(" {}", label),
+                Style::default().fg(t.warning),
+            ));
+        }
     }
 
     // Selection count (abbreviated)
@@ -333,6 +545,7 @@ fn render_narrow_status(
     frame: &mut Frame,
     state: &AppState,
     focused_path: Option<&PathBuf>,
+    total_entries: usize,
     area: Rect,
 ) {
     // Dynamic split: adjust based on content
@@ -353,16 +566,23 @@ fn render_narrow_status(
 
     // Git branch (abbreviated)
     if let Some(branch) = state.git_status.as_ref().and_then(|g| g.branch()) {
+        let label = git_state_label(state)
+            .map(|label| format!(" {}", label))
+            .unwrap_or_default();
         left_spans.push(Span::styled(
-            format!("\u{e0a0}{} |", branch),
+            format!("\u{e0a0}{}{} |", branch, label),
             Style::default().fg(t.git_staged),
         ));
     }
 
     // Sort mode (abbreviated, only if non-default)
-    if state.sort_mode != SortMode::Name {
+    if is_custom_sort(state) {
         left_spans.push(Span::styled(
-            format!("\u{f0dc}{}|", state.sort_mode.short_name()),
+            format!(
+                "\u{f0dc}{}{}|",
+                state.sort_mode.short_name(),
+                sort_arrow(state)
+            ),
             Style::default().fg(t.git_conflict),
         ));
     }
@@ -376,8 +596,8 @@ fn render_narrow_status(
     }
 
     // Help or message
-    let message = state.message.as_deref().unwrap_or("? help");
-    left_spans.push(Span::raw(format!(" {}", message)));
+    let message = message_or(state, "? help");
+    left_spans.push(Span::styled(format!(" {}", message), message_style(state)));
 
     let left_content = Line::from(left_spans);
     let left_widget = Paragraph::new(left_content).block(Block::default().borders(Borders::ALL));
@@ -403,10 +623,11 @@ fn render_narrow_status(
         .unwrap_or_default();
 
     let stats = format!(
-        "{}{}{}",
+        "{}{}{}{}",
+        position_indicator(state, total_entries),
         file_info,
         if selected_count > 0 {
-            format!(" | Sel:{}", selected_count)
+            format!(" | Sel:{}", selection_label(state))
         } else {
             String::new()
         },
@@ -422,6 +643,7 @@ fn render_full_status(
     frame: &mut Frame,
     state: &AppState,
     focused_path: Option<&PathBuf>,
+    total_entries: usize,
     area: Rect,
 ) {
     let chunks = Layout::default()
@@ -430,17 +652,7 @@ fn render_full_status(
         .split(area);
 
     // Left: message or help hint, with mode, git branch, watch, filter, sort, and search indicators
-    let mode_indicator = if state.select_mode {
-        if state.multi_select {
-            "\u{f05c}\u{f05c} " // Multi-select icon
-        } else {
-            "\u{f05c} " // Select icon (nf-fa-circle_o)
-        }
-    } else if state.pick_mode {
-        "\u{f046} " // Pick icon (nf-fa-check_square_o)
-    } else {
-        ""
-    };
+    let mode_indicator = mode_indicator(state);
 
     let watch_indicator = if state.watch_enabled {
         "\u{f06e} " // Eye icon (nf-fa-eye) for file watching
@@ -454,19 +666,14 @@ fn render_full_status(
         .map(|p| format!("\u{f0b0} {} |", p)) // Filter icon
         .unwrap_or_default();
 
-    let branch_info = state
-        .git_status
-        .as_ref()
-        .and_then(|g| g.branch())
-        .map(|b| format!("\u{e0a0} {} |", b)) // Git branch icon
+    let branch_info = branch_indicator(state)
+        .map(|b| format!("{} |", b))
         .unwrap_or_default();
 
     // Sort mode indicator (only show if not default)
-    let sort_indicator = if state.sort_mode != SortMode::Name {
-        format!("\u{f0dc} {} |", state.sort_mode.display_name()) // Sort icon
-    } else {
-        String::new()
-    };
+    let sort_indicator = sort_indicator(state)
+        .map(|s| format!("{} |", s))
+        .unwrap_or_default();
 
     // Search match info
     let search_indicator = state
@@ -475,7 +682,7 @@ fn render_full_status(
         .unwrap_or_default();
 
     let t = theme();
-    let message = state.message.as_deref().unwrap_or("? for help");
+    let message = message_or(state, "? for help");
     let left_content = Line::from(vec![
         Span::styled(mode_indicator, Style::default().fg(t.selection)),
         Span::styled(watch_indicator, Style::default().fg(t.info)),
@@ -483,44 +690,137 @@ fn render_full_status(
         Span::styled(branch_info, Style::default().fg(t.git_staged)),
         Span::styled(sort_indicator, Style::default().fg(t.git_conflict)),
         Span::styled(search_indicator, Style::default().fg(t.border_active)),
-        Span::raw(format!(" {}", message)),
+        Span::styled(format!(" {}", message), message_style(state)),
     ]);
     let msg_widget = Paragraph::new(left_content).block(Block::default().borders(Borders::ALL));
     frame.render_widget(msg_widget, chunks[0]);
 
-    // Right: file info + selection stats
-    let file_info = focused_path
-        .map(|p| p.as_path())
-        .and_then(get_file_info)
-        .unwrap_or_else(|| "--".to_string());
+    // Right: file info (with the MIME type from file(1) when known) + selection stats
+    let file_info = focused_file_info(state, focused_path);
 
     let selected_count = state.selected_paths.len();
-    let clipboard_info = state
-        .clipboard
+    let clipboard_info = clipboard_indicator(state)
+        .map(|c| format!(" | {}", c))
+        .unwrap_or_default();
+
+    let disk_info = state
+        .disk_usage
         .as_ref()
-        .map(|c| {
-            if c.is_cut() {
-                format!(" | Cut: {}", c.paths().len())
-            } else {
-                format!(" | Copied: {}", c.paths().len())
-            }
+        .map(|usage| {
+            format!(
+                " | {}/{} free on {}",
+                format_size(usage.free),
+                format_size(usage.total),
+                usage.mount.display()
+            )
         })
         .unwrap_or_default();
 
     let stats = format!(
-        "{}{}{}",
+        "{}{}{}{}{}",
+        position_indicator(state, total_entries),
         file_info,
         if selected_count > 0 {
-            format!(" | Selected: {}", selected_count)
+            format!(" | Selected: {}", selection_label(state))
         } else {
             String::new()
         },
-        clipboard_info
+        clipboard_info,
+        disk_info
     );
     let stats_widget = Paragraph::new(stats).block(Block::default().borders(Borders::ALL));
     frame.render_widget(stats_widget, chunks[1]);
 }
 
+/// Select or pick mode icon with a trailing space (empty when browsing)
+fn mode_indicator(state: &AppState) -> &'static str {
+    if state.select_mode {
+        if state.multi_select {
+            "\u{f05c}\u{f05c} " // Multi-select icon
+        } else {
+            "\u{f05c} " // Select icon (nf-fa-circle_o)
+        }
+    } else if state.pick_mode {
+        "\u{f046} " // Pick icon (nf-fa-check_square_o)
+    } else {
+        ""
+    }
+}
+
+/// Git branch with the repository state and changed-only filter
+fn branch_indicator(state: &AppState) -> Option<String> {
+    let branch = state.git_status.as_ref().and_then(|g| g.branch())?;
+    let changed_only = if state.git_changed_only {
+        " changed"
+    } else {
+        ""
+    };
+    let label = git_state_label(state)
+        .map(|label| format!(" {}", label))
+        .unwrap_or_default();
+    Some(format!("\u{e0a0} {}{}{}", branch, label, changed_only)) // Git branch icon
+}
+
+/// Sort mode, direction and mixing (None for the default sort)
+fn sort_indicator(state: &AppState) -> Option<String> {
+    if !is_custom_sort(state) {
+        return None;
+    }
+    let direction = if state.sort_mode.is_descending(state.sort_reverse) {
+        "desc"
+    } else {
+        "asc"
+    };
+    let mixed = if state.dirs_first { "" } else { " mixed" };
+    // Sort icon
+    Some(format!(
+        "\u{f0dc} {}{} {}{}",
+        state.sort_mode.display_name(),
+        sort_arrow(state),
+        direction,
+        mixed
+    ))
+}
+
+/// Size and age of the focused entry, with its MIME type when known
+fn focused_file_info(state: &AppState, focused_path: Option<&PathBuf>) -> String {
+    let mut file_info = focused_path
+        .map(|p| p.as_path())
+        .and_then(get_file_info)
+        .unwrap_or_else(|| "--".to_string());
+    if let Some((_, mime)) = state
+        .file_mime
+        .as_ref()
+        .filter(|(path, _)| Some(path) == focused_path)
+    {
+        file_info.push_str(&format!(" · {}", mime));
+    }
+    file_info
+}
+
+/// Clipboard item count (`Cut: N` or `Copied: N`)
+fn clipboard_indicator(state: &AppState) -> Option<String> {
+    state.clipboard.as_ref().map(|c| {
+        if c.is_cut() {
+            format!("Cut: {}", c.paths().len())
+        } else {
+            format!("Copied: {}", c.paths().len())
+        }
+    })
+}
+
+/// Cursor position in the tree as `N/M | ` (empty when the tree is empty)
+fn position_indicator(state: &AppState, total_entries: usize) -> String {
+    if total_entries == 0 {
+        return String::new();
+    }
+    format!(
+        "{}/{} | ",
+        (state.focus_index + 1).min(total_entries),
+        total_entries
+    )
+}
+
 /// Get file size and modification time as a formatted string (full display)
 fn get_file_info(path: &std::path::Path) -> Option<String> {
     let metadata = path.metadata().ok()?;
@@ -592,7 +892,7 @@ fn format_size(bytes: u64) -> String {
 }
 
 /// Format time as relative (e.g., "2h ago", "Yesterday", "Jan 30")
-fn format_relative_time(time: SystemTime) -> String {
+pub(crate) fn format_relative_time(time: SystemTime) -> String {
     let now = SystemTime::now();
     let duration = match now.duration_since(time) {
         Ok(d) => d,
@@ -780,14 +1080,62 @@ pub fn render_input_popup(frame: &mut Frame, state: &AppState) {
                 InputPurpose::CreateFile => "New File",
                 InputPurpose::CreateDir => "New Directory",
                 InputPurpose::Rename { .. } => "Rename",
+                InputPurpose::CreateLink {
+                    kind: LinkKind::Symlink { relative: true },
+                    ..
+                } => "Symlink (relative, Tab: absolute)",
+                InputPurpose::CreateLink {
+                    kind: LinkKind::Symlink { relative: false },
+                    ..
+                } => "Symlink (absolute, Tab: relative)",
+                InputPurpose::CreateLink {
+                    kind: LinkKind::Hardlink,
+                    ..
+                } => "Hard Link",
+                InputPurpose::DiffRevision { .. } => {
+                    "Diff against (Tab: complete, empty: working tree)"
+                }
+                InputPurpose::SelectPattern {
+                    action: PatternSelect::Select,
+                } => "Select matching (e.g. *.rs)",
+                InputPurpose::SelectPattern {
+                    action: PatternSelect::Deselect,
+                } => "Deselect matching (e.g. *.rs)",
+                InputPurpose::SelectPattern {
+                    action: PatternSelect::Subtree { .. },
+                } => "Select matching in subtree (e.g. *.rs)",
+                InputPurpose::ContentSearch => "Search in files",
+                InputPurpose::ForeachCommand { targets } => {
+                    let title = format!(
+                        "Run on {} file{} ({{path}} = each file)",
+                        targets.len(),
+                        if targets.len() == 1 { "" } else { "s" }
+                    );
+                    draw_input_popup(frame, &title, buffer);
+                    return;
+                }
+                InputPurpose::CommitMessage { amend } => {
+                    let title = if *amend {
+                        "Amend commit (Alt+Enter: new line)"
+                    } else {
+                        "Commit message (Alt+Enter: new line)"
+                    };
+                    draw_multiline_input_popup(frame, title, buffer);
+                    return;
+                }
             };
             draw_input_popup(frame, title, buffer);
         }
         ViewMode::Search { query } => {
-            draw_input_popup(frame, "Search", query);
+            let title = if state.case_sensitive {
+                "Search /c"
+            } else {
+                "Search /"
+            };
+            draw_input_popup(frame, title, query);
         }
         ViewMode::Confirm { action } => {
-            draw_confirm_popup(frame, action);
+            draw_confirm_popup(frame, action, state.delete_summary.as_ref());
         }
         ViewMode::BookmarkSet => {
             draw_mini_popup(frame, "Set bookmark (1-9)");
@@ -795,13 +1143,54 @@ pub fn render_input_popup(frame: &mut Frame, state: &AppState) {
         ViewMode::BookmarkJump => {
             draw_mini_popup(frame, "Jump to bookmark (1-9)");
         }
+        ViewMode::SortSelect => {
+            draw_mini_popup(
+                frame,
+                "Sort: n)ame s)ize d)ate e)xt t)ype v)natural r)everse f)olders first",
+            );
+        }
+        ViewMode::YankGit => {
+            draw_mini_popup(frame, "Copy git: d)iff b)lame");
+        }
         ViewMode::Filter { query } => {
             draw_input_popup(frame, "Filter (e.g., *.rs)", query);
         }
+        ViewMode::LiveFilter { query, .. } => {
+            draw_input_popup(frame, "Narrow (Enter focus, Esc cancel)", query);
+        }
+        ViewMode::Preview { .. } => {
+            if let Some(query) = &state.preview_search {
+                let title = if state.case_sensitive {
+                    "Search preview /c"
+                } else {
+                    "Search preview /"
+                };
+                draw_input_popup(frame, title, query);
+            }
+        }
         _ => {}
     }
 }
 
+/// In-progress git operation shown next to the branch (e.g. `REBASING`)
+fn git_state_label(state: &AppState) -> Option<&'static str> {
+    state.git_status.as_ref().and_then(|g| g.state().label())
+}
+
+/// Check if the sort differs from the default (name, ascending, directories first)
+fn is_custom_sort(state: &AppState) -> bool {
+    state.sort_mode != SortMode::Name || state.sort_reverse || !state.dirs_first
+}
+
+/// Direction arrow shown after the sort mode
+fn sort_arrow(state: &AppState) -> &'static str {
+    if state.sort_mode.is_descending(state.sort_reverse) {
+        "\u{2193}"
+    } else {
+        "\u{2191}"
+    }
+}
+
 /// Draw a simple input popup
 fn draw_input_popup(frame: &mut Frame, title: &str, content: &str) {
     let t = theme();
@@ -815,6 +1204,20 @@ fn draw_input_popup(frame: &mut Frame, title: &str, content: &str) {
     frame.render_widget(input, area);
 }
 
+/// Draw a text input popup that grows with the lines of its content
+fn draw_multiline_input_popup(frame: &mut Frame, title: &str, content: &str) {
+    let t = theme();
+    let lines = content.split('\n').count().clamp(3, 12) as u16;
+    let area = centered_rect(72, lines + 2, frame.area());
+
+    let input = Paragraph::new(content)
+        .style(Style::default().fg(t.warning))
+        .block(Block::default().borders(Borders::ALL).title(title));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(input, area);
+}
+
 /// Draw a small notification popup (for bookmark modes, etc.)
 fn draw_mini_popup(frame: &mut Frame, message: &str) {
     let t = theme();
@@ -830,30 +1233,284 @@ fn draw_mini_popup(frame: &mut Frame, message: &str) {
 }
 
 /// Draw confirmation popup
-fn draw_confirm_popup(frame: &mut Frame, action: &PendingAction) {
+fn draw_confirm_popup(frame: &mut Frame, action: &PendingAction, summary: Option<&DeleteSummary>) {
     match action {
-        PendingAction::Delete { targets } => {
-            draw_delete_confirm_popup(frame, targets);
+        PendingAction::Delete { targets, permanent } => {
+            draw_delete_confirm_popup(frame, targets, summary, *permanent);
+        }
+        PendingAction::ResolvePasteConflict { plan, .. } => {
+            draw_paste_conflict_popup(frame, plan);
+        }
+        PendingAction::ResolveRenameConflict { original, target } => {
+            draw_rename_conflict_popup(frame, original, target);
+        }
+        PendingAction::DropStash { stash } => {
+            draw_stash_drop_popup(frame, stash);
+        }
+        PendingAction::GitDiscard { targets } => {
+            draw_git_discard_popup(frame, targets);
         }
     }
 }
 
+/// Draw the discard changes confirmation
+fn draw_git_discard_popup(frame: &mut Frame, paths: &[std::path::PathBuf]) {
+    let max_items_to_show = 8;
+    let items_count = paths.len().min(max_items_to_show);
+    let more_line = usize::from(paths.len() > max_items_to_show);
+    let area = centered_rect(60, (items_count + more_line + 6) as u16, frame.area());
+
+    let mut content = vec![
+        Line::from(vec![Span::styled(
+            format!("Discard changes to {} item(s)?", paths.len()),
+            Style::default().add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![Span::styled(
+            "Working tree edits are lost; staged changes are kept",
+            Style::default().fg(Color::Yellow),
+        )]),
+    ];
+    for path in paths.iter().take(max_items_to_show) {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        content.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(name, Style::default().fg(Color::White)),
+        ]));
+    }
+    if more_line > 0 {
+        content.push(Line::from(vec![Span::styled(
+            format!("  ... and {} more", paths.len() - max_items_to_show),
+            Style::default().fg(Color::DarkGray),
+        )]));
+    }
+    content.push(Line::from(""));
+    content.push(Line::from(vec![
+        Span::styled(
+            "y",
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" to discard, "),
+        Span::styled(
+            "n",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" to cancel"),
+    ]));
+
+    let popup = Paragraph::new(content).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red))
+            .title(" Discard Changes "),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+/// Draw the drop stash confirmation
+fn draw_stash_drop_popup(frame: &mut Frame, stash: &Stash) {
+    let area = centered_rect(60, 6, frame.area());
+
+    let content = vec![
+        Line::from(vec![Span::styled(
+            format!("Drop {}?", stash.name()),
+            Style::default().add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![Span::styled(
+            format!("  {}", stash.message),
+            Style::default().fg(Color::White),
+        )]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                "y",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" to drop, "),
+            Span::styled(
+                "n",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" to go back"),
+        ]),
+    ];
+
+    let popup = Paragraph::new(content).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red))
+            .title(" Drop Stash "),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+/// Draw paste conflict popup for the next undecided item
+fn draw_paste_conflict_popup(frame: &mut Frame, plan: &PastePlan) {
+    let Some(index) = plan.next_conflict() else {
+        return;
+    };
+    let item = &plan.items[index];
+    let name = item
+        .dest
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| item.dest.display().to_string());
+    let decided = plan
+        .items
+        .iter()
+        .filter(|i| i.conflict && i.resolution.is_some())
+        .count();
+
+    let key = |k: &'static str| {
+        Span::styled(
+            k,
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+
+    let mut content = vec![
+        Line::from(vec![Span::styled(
+            format!("'{}' already exists", name),
+            Style::default().add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![Span::styled(
+            format!(
+                "Conflict {} of {} ({})",
+                decided + 1,
+                plan.conflict_count(),
+                if plan.is_cut { "move" } else { "copy" }
+            ),
+            Style::default().fg(Color::DarkGray),
+        )]),
+    ];
+    if item.src.is_dir() && item.dest.is_dir() {
+        content.push(Line::from(vec![Span::styled(
+            "Overwrite merges the directories",
+            Style::default().fg(Color::Yellow),
+        )]));
+    }
+    content.push(Line::from(""));
+    content.push(Line::from(vec![
+        key("o"),
+        Span::raw(" overwrite  "),
+        key("s"),
+        Span::raw(" skip  "),
+        key("r"),
+        Span::raw(" rename"),
+    ]));
+    content.push(Line::from(vec![
+        key("O"),
+        Span::raw(" overwrite all  "),
+        key("S"),
+        Span::raw(" skip all  "),
+        key("Esc"),
+        Span::raw(" cancel"),
+    ]));
+
+    let height = content.len() as u16 + 2;
+    let area = centered_rect(60, height, frame.area());
+    let popup = Paragraph::new(content).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(" Paste Conflict "),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+/// Draw the prompt for a rename onto an existing name
+fn draw_rename_conflict_popup(frame: &mut Frame, original: &Path, target: &Path) {
+    let name = |path: &Path| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string())
+    };
+    let key = |k: &'static str| {
+        Span::styled(
+            k,
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+
+    let content = vec![
+        Line::from(vec![Span::styled(
+            format!("'{}' already exists", name(target)),
+            Style::default().add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![Span::styled(
+            format!("Renaming '{}' would replace it", name(original)),
+            Style::default().fg(Color::DarkGray),
+        )]),
+        Line::from(""),
+        Line::from(vec![
+            key("o"),
+            Span::raw(" overwrite  "),
+            key("u"),
+            Span::raw(" unique name  "),
+            key("Esc"),
+            Span::raw(" cancel"),
+        ]),
+    ];
+
+    let height = content.len() as u16 + 2;
+    let area = centered_rect(60, height, frame.area());
+    let popup = Paragraph::new(content).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(" Rename Conflict "),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
 /// Draw delete confirmation popup
-fn draw_delete_confirm_popup(frame: &mut Frame, paths: &[std::path::PathBuf]) {
+fn draw_delete_confirm_popup(
+    frame: &mut Frame,
+    paths: &[std::path::PathBuf],
+    summary: Option<&DeleteSummary>,
+    permanent: bool,
+) {
     let max_items_to_show = 8;
     let items_count = paths.len().min(max_items_to_show);
     let has_more = paths.len() > max_items_to_show;
     let has_directories = paths.iter().any(|p| p.is_dir());
 
-    let warning_lines = if has_directories { 2 } else { 0 };
+    let warning_lines = if has_directories || permanent { 2 } else { 0 };
     let more_line = if has_more { 1 } else { 0 };
-    let height = (3 + warning_lines + items_count + more_line + 2) as u16;
+    let height = (3 + warning_lines + 1 + items_count + more_line + 2) as u16;
 
     let area = centered_rect(60, height, frame.area());
 
     let mut content = Vec::new();
 
-    if has_directories {
+    if permanent {
+        content.push(Line::from(vec![Span::styled(
+            "!! WARNING: PERMANENT DELETE !!",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )]));
+        content.push(Line::from(vec![Span::styled(
+            "Items skip the trash and cannot be restored",
+            Style::default().fg(Color::Yellow),
+        )]));
+        content.push(Line::from(""));
+    } else if has_directories {
         content.push(Line::from(vec![Span::styled(
             "!! WARNING: FOLDER MOVE !!",
             Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
@@ -865,11 +1522,28 @@ fn draw_delete_confirm_popup(frame: &mut Frame, paths: &[std::path::PathBuf]) {
         content.push(Line::from(""));
     }
 
+    let heading = if permanent {
+        format!("Delete {} item(s) forever:", paths.len())
+    } else {
+        format!("Move {} item(s) to trash:", paths.len())
+    };
     content.push(Line::from(vec![Span::styled(
-        format!("Move {} item(s) to trash:", paths.len()),
+        heading,
         Style::default().add_modifier(Modifier::BOLD),
     )]));
 
+    // Totals are counted in the background
+    let style = if has_directories {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let totals = summary.map_or_else(|| "counting…".to_string(), format_delete_summary);
+    content.push(Line::from(vec![Span::styled(
+        format!("  {}", totals),
+        style,
+    )]));
+
     for path in paths.iter().take(max_items_to_show) {
         let name = path
             .file_name()
@@ -908,13 +1582,15 @@ fn draw_delete_confirm_popup(frame: &mut Frame, paths: &[std::path::PathBuf]) {
         Span::raw(" to cancel"),
     ]));
 
-    let title = if has_directories {
+    let title = if permanent {
+        " !! DELETE FOREVER !! "
+    } else if has_directories {
         " !! MOVE FOLDERS TO TRASH !! "
     } else {
         " Move to Trash "
     };
 
-    let title_style = if has_directories {
+    let title_style = if has_directories || permanent {
         Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
     } else {
         Style::default()
@@ -923,7 +1599,7 @@ fn draw_delete_confirm_popup(frame: &mut Frame, paths: &[std::path::PathBuf]) {
     let popup = Paragraph::new(content).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(if has_directories {
+            .border_style(if has_directories || permanent {
                 Style::default().fg(Color::Red)
             } else {
                 Style::default()
@@ -935,6 +1611,56 @@ fn draw_delete_confirm_popup(frame: &mut Frame, paths: &[std::path::PathBuf]) {
     frame.render_widget(popup, area);
 }
 
+/// Selection count with combined size, e.g. "3 (2.1 MB)"
+///
+/// Sizes include directory contents; a truncated total gets a `+` suffix.
+fn selection_label(state: &AppState) -> String {
+    let count = state.selected_paths.len();
+    match state.selection_summary {
+        Some(summary) => format!(
+            "{} ({}{})",
+            count,
+            format_size(summary.bytes),
+            if summary.truncated { "+" } else { "" }
+        ),
+        None => count.to_string(),
+    }
+}
+
+/// Format delete totals, e.g. "3 items, 1,204 files, 2.1 GB"
+///
+/// Truncated totals are lower bounds and get a `+` suffix.
+fn format_delete_summary(summary: &DeleteSummary) -> String {
+    let more = if summary.truncated { "+" } else { "" };
+    format!(
+        "{} item{}, {}{} file{}, {}{}",
+        summary.items,
+        if summary.items == 1 { "" } else { "s" },
+        format_count(summary.files),
+        more,
+        if summary.files == 1 && !summary.truncated {
+            ""
+        } else {
+            "s"
+        },
+        format_size(summary.bytes),
+        more
+    )
+}
+
+/// Format a count with thousands separators (1204 -> "1,204")
+pub(crate) fn format_count(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
 /// Create a centered rectangle
 fn centered_rect(percent_x: u16, height: u16, area: Rect) -> Rect {
     let popup_layout = Layout::default()
@@ -1046,11 +1772,27 @@ fn build_narrow_help() -> Vec<Line<'static>> {
             help_key(" Enter "),
             help_desc(" Toggle"),
         ]),
+        Line::from(vec![
+            help_key(" > "),
+            help_desc(" Root "),
+            help_key(" < "),
+            help_desc(" Up"),
+        ]),
         Line::from(""),
         help_section("Selection"),
-        Line::from(vec![help_key(" Space "), help_desc(" Mark")]),
+        Line::from(vec![
+            help_key(" Space "),
+            help_desc(" Mark "),
+            help_key(" M "),
+            help_desc(" Marks"),
+        ]),
         Line::from(vec![help_key(" ^G "), help_desc(" Git changed")]),
         Line::from(vec![help_key(" ^T "), help_desc(" Test pair")]),
+        Line::from(vec![
+            help_key(" + "),
+            help_key(" - "),
+            help_desc(" By pattern"),
+        ]),
         Line::from(""),
         help_section("File"),
         Line::from(vec![
@@ -1059,16 +1801,30 @@ fn build_narrow_help() -> Vec<Line<'static>> {
             help_key(" A "),
             help_desc(" Dir"),
         ]),
-        Line::from(vec![help_key(" r "), help_desc(" Rename")]),
+        Line::from(vec![
+            help_key(" r "),
+            help_desc(" Rename "),
+            help_key(" E "),
+            help_desc(" $EDITOR"),
+        ]),
         Line::from(vec![
             help_key(" y "),
             help_desc(" Cp "),
             help_key(" d "),
             help_desc(" Cut "),
             help_key(" p "),
-            help_desc(" Paste"),
+            help_desc(" Paste "),
+            help_key(" M-P "),
+            help_desc(" As copy"),
+        ]),
+        Line::from(vec![
+            help_key(" D "),
+            help_desc(" Delete "),
+            help_key(" M-l "),
+            help_desc(" Link "),
+            help_key(" M-L "),
+            help_desc(" Hard"),
         ]),
-        Line::from(vec![help_key(" D "), help_desc(" Delete")]),
         Line::from(""),
         help_section("Clipboard"),
         Line::from(vec![
@@ -1077,21 +1833,38 @@ fn build_narrow_help() -> Vec<Line<'static>> {
             help_key(" C "),
             help_desc(" Name"),
         ]),
+        Line::from(vec![help_key(" M-c "), help_desc(" Relative path")]),
         Line::from(vec![help_key(" Y "), help_desc(" Content")]),
         Line::from(vec![help_key(" ^Y "), help_desc(" Claude fmt")]),
+        Line::from(vec![help_key(" M-Y "), help_desc(" Concat dir")]),
         Line::from(""),
         help_section("Search"),
-        Line::from(vec![help_key(" / "), help_desc(" Search")]),
+        Line::from(vec![
+            help_key(" / "),
+            help_desc(" Search "),
+            help_key(" ^F "),
+            help_desc(" In files"),
+        ]),
         Line::from(vec![
             help_key(" n "),
             help_desc(" Next "),
             help_key(" N "),
             help_desc(" Prev"),
         ]),
-        Line::from(vec![help_key(" ^P "), help_desc(" Fuzzy")]),
+        Line::from(vec![
+            help_key(" ^P "),
+            help_desc(" Fuzzy "),
+            help_key(" O "),
+            help_desc(" Recent "),
+            help_key(" U "),
+            help_desc(" History"),
+        ]),
+        Line::from(vec![help_key(" ^Z "), help_desc(" Undo rename/move/trash")]),
         Line::from(vec![
             help_key(" F "),
             help_desc(" Filter "),
+            help_key(" i "),
+            help_desc(" Narrow "),
             help_key(" S "),
             help_desc(" Sort"),
         ]),
@@ -1103,20 +1876,47 @@ fn build_narrow_help() -> Vec<Line<'static>> {
             help_key(" P "),
             help_desc(" Quick"),
         ]),
+        Line::from(vec![
+            help_key(" A-= "),
+            help_key(" A-- "),
+            help_desc(" Resize"),
+        ]),
         Line::from(vec![help_key(" b "), help_key(" f "), help_desc(" Scroll")]),
         Line::from(vec![
             help_key(" [ "),
             help_key(" ] "),
-            help_desc(" PDF page"),
+            help_desc(" PDF page "),
+            help_key(" t "),
+            help_desc(" Text"),
+        ]),
+        Line::from(vec![
+            help_key(" w "),
+            help_desc(" Wrap "),
+            help_key(" # "),
+            help_desc(" Lines"),
         ]),
+        Line::from(vec![help_key(" x/X "), help_desc(" Conflicts")]),
         Line::from(""),
         help_section("Git"),
         Line::from(vec![
             help_key(" s "),
             help_desc(" Stage "),
             help_key(" u "),
-            help_desc(" Unstage"),
+            help_desc(" Unstage "),
+            help_key(" Z "),
+            help_desc(" Stash"),
         ]),
+        Line::from(vec![help_key(" X "), help_desc(" Discard changes")]),
+        Line::from(vec![help_key(" Alt+G "), help_desc(" Changed only")]),
+        Line::from(vec![help_key(" Alt+d "), help_desc(" Diff vs rev")]),
+        Line::from(vec![
+            help_key(" Alt+m "),
+            help_desc(" Commit "),
+            help_key(" Alt+M "),
+            help_desc(" Amend"),
+        ]),
+        Line::from(vec![help_key(" Alt+D "), help_desc(" Diff 2 marked")]),
+        Line::from(vec![help_key(" Alt+e "), help_desc("+d/b Copy diff/blame")]),
         Line::from(""),
         help_section("Bookmarks"),
         Line::from(vec![help_key(" m "), help_desc("+1-9 Set")]),
@@ -1143,6 +1943,9 @@ fn build_narrow_help() -> Vec<Line<'static>> {
             help_key(" F5 "),
             help_desc(" Refresh"),
         ]),
+        Line::from(vec![help_key(" ! "), help_desc(" Run on marked")]),
+        Line::from(vec![help_key(" Alt+n "), help_desc(" Relative numbers")]),
+        Line::from(vec![help_key(" Alt+k "), help_desc(" Reload keymap")]),
         Line::from(vec![
             help_key(" q "),
             help_desc(" Quit "),
@@ -1203,6 +2006,12 @@ fn build_wide_help() -> Vec<Line<'static>> {
             help_key(" Enter "),
             help_desc(" Toggle/Pick"),
         ]),
+        Line::from(vec![
+            help_key(" > "),
+            help_desc(" Set focused dir as root   "),
+            help_key(" < "),
+            help_desc(" Root to parent"),
+        ]),
         Line::from(""),
         help_section("Selection"),
         Line::from(vec![
@@ -1211,7 +2020,17 @@ fn build_wide_help() -> Vec<Line<'static>> {
             help_key(" Ctrl+G "),
             help_desc(" Git changed   "),
             help_key(" Ctrl+T "),
-            help_desc(" Test pair"),
+            help_desc(" Test pair   "),
+            help_key(" M "),
+            help_desc(" Marks overview"),
+        ]),
+        Line::from(vec![
+            help_key(" + "),
+            help_desc(" Mark matching   "),
+            help_key(" - "),
+            help_desc(" Unmark matching   "),
+            help_key(" Alt++ "),
+            help_desc(" Mark in subtree"),
         ]),
         Line::from(""),
         help_section("File Operations"),
@@ -1223,7 +2042,9 @@ fn build_wide_help() -> Vec<Line<'static>> {
             help_key(" r "),
             help_desc(" Rename   "),
             help_key(" R "),
-            help_desc(" Bulk rename"),
+            help_desc(" Bulk rename   "),
+            help_key(" E "),
+            help_desc(" Rename in $EDITOR"),
         ]),
         Line::from(vec![
             help_key(" y "),
@@ -1232,22 +2053,32 @@ fn build_wide_help() -> Vec<Line<'static>> {
             help_desc(" Cut   "),
             help_key(" p "),
             help_desc(" Paste   "),
+            help_key(" M-P "),
+            help_desc(" Paste as copy   "),
             help_key(" D "),
             help_desc("/"),
             help_key(" Del "),
-            help_desc(" Delete"),
+            help_desc(" Delete   "),
+            help_key(" M-l "),
+            help_desc(" Symlink   "),
+            help_key(" M-L "),
+            help_desc(" Hard link"),
         ]),
         Line::from(""),
         help_section("Clipboard"),
         Line::from(vec![
             help_key(" c "),
             help_desc(" Path   "),
+            help_key(" Alt+c "),
+            help_desc(" Relative path   "),
             help_key(" C "),
             help_desc(" Filename   "),
             help_key(" Y "),
             help_desc(" Content   "),
             help_key(" Ctrl+Y "),
-            help_desc(" Claude format"),
+            help_desc(" Claude format   "),
+            help_key(" Alt+Y "),
+            help_desc(" Concat dir"),
         ]),
         Line::from(""),
         help_section("Search & Filter"),
@@ -1259,13 +2090,23 @@ fn build_wide_help() -> Vec<Line<'static>> {
             help_key(" N "),
             help_desc(" Prev   "),
             help_key(" Ctrl+P "),
-            help_desc(" Fuzzy finder"),
+            help_desc(" Fuzzy finder   "),
+            help_key(" O "),
+            help_desc(" Recent files   "),
+            help_key(" U "),
+            help_desc(" Operation history   "),
+            help_key(" Ctrl+Z "),
+            help_desc(" Undo"),
         ]),
         Line::from(vec![
             help_key(" F "),
             help_desc(" Filter   "),
+            help_key(" i "),
+            help_desc(" Type to narrow   "),
             help_key(" S "),
-            help_desc(" Sort mode"),
+            help_desc(" Sort menu   "),
+            help_key(" Ctrl+F "),
+            help_desc(" Search in files"),
         ]),
         Line::from(""),
         help_section("Preview"),
@@ -1275,6 +2116,13 @@ fn build_wide_help() -> Vec<Line<'static>> {
             help_key(" P "),
             help_desc(" Quick preview"),
         ]),
+        Line::from(vec![
+            help_key(" Alt+= "),
+            help_desc("/"),
+            help_key(" Alt+- "),
+            help_desc(" Grow / shrink preview"),
+        ]),
+        Line::from(vec![help_key(" Alt+b "), help_desc(" Preview borders")]),
         Line::from(vec![
             help_key(" b "),
             help_desc("/"),
@@ -1286,7 +2134,19 @@ fn build_wide_help() -> Vec<Line<'static>> {
             help_desc(" Down   "),
             help_key(" [ "),
             help_key(" ] "),
-            help_desc(" PDF pages"),
+            help_desc(" PDF pages   "),
+            help_key(" t "),
+            help_desc(" PDF/SVG text/image"),
+        ]),
+        Line::from(vec![
+            help_key(" w "),
+            help_desc(" Wrap lines   "),
+            help_key(" # "),
+            help_desc(" Line numbers"),
+        ]),
+        Line::from(vec![
+            help_key(" x/X "),
+            help_desc(" Next/previous merge conflict"),
         ]),
         Line::from(""),
         help_section("Git"),
@@ -1294,7 +2154,35 @@ fn build_wide_help() -> Vec<Line<'static>> {
             help_key(" s "),
             help_desc(" Stage   "),
             help_key(" u "),
-            help_desc(" Unstage"),
+            help_desc(" Unstage   "),
+            help_key(" Z "),
+            help_desc(" Stash list   "),
+            help_key(" X "),
+            help_desc(" Discard changes"),
+        ]),
+        Line::from(vec![
+            help_key(" Alt+G "),
+            help_desc(" Show only changed files   "),
+            help_key(" Alt+d "),
+            help_desc(" Diff against revision"),
+        ]),
+        Line::from(vec![
+            help_key(" Alt+m "),
+            help_desc(" Commit staged changes   "),
+            help_key(" Alt+M "),
+            help_desc(" Amend last commit"),
+        ]),
+        Line::from(vec![
+            help_key(" Alt+D "),
+            help_desc(" Diff the two marked files"),
+        ]),
+        Line::from(vec![
+            help_key(" Alt+e "),
+            help_desc("+"),
+            help_key(" d "),
+            help_desc("/"),
+            help_key(" b "),
+            help_desc(" Copy the file's diff / blame"),
         ]),
         Line::from(""),
         help_section("Bookmarks"),
@@ -1327,6 +2215,8 @@ fn build_wide_help() -> Vec<Line<'static>> {
             help_desc(" Hidden   "),
             help_key(" F5 "),
             help_desc(" Refresh   "),
+            help_key(" ! "),
+            help_desc(" Run on marked   "),
             help_key(" ? "),
             help_desc(" Help   "),
             help_key(" q "),
@@ -1334,6 +2224,12 @@ fn build_wide_help() -> Vec<Line<'static>> {
             help_key(" Q "),
             help_desc(" Quit+cd"),
         ]),
+        Line::from(vec![
+            help_key(" Alt+n "),
+            help_desc(" Relative numbers   "),
+            help_key(" Alt+k "),
+            help_desc(" Reload keymap"),
+        ]),
         Line::from(""),
         Line::from(Span::styled(
             "  Press ? or Esc to close",
@@ -1382,3 +2278,64 @@ pub fn render_help_popup(frame: &mut Frame, state: &AppState) {
 
     frame.render_widget(paragraph, overlay_area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_indicator_shows_direction_arrow() {
+        let mut state = AppState::new(PathBuf::from("/project"));
+        assert_eq!(sort_indicator(&state), None);
+
+        state.sort_reverse = true;
+        assert_eq!(
+            sort_indicator(&state).as_deref(),
+            Some("\u{f0dc} name\u{2193} desc")
+        );
+        // Size is descending by default, so reversing makes it ascending
+        state.sort_mode = SortMode::Size;
+        state.dirs_first = false;
+        assert_eq!(
+            sort_indicator(&state).as_deref(),
+            Some("\u{f0dc} size\u{2191} asc mixed")
+        );
+    }
+
+    #[test]
+    fn test_expand_status_template() {
+        let root = PathBuf::from("/project");
+        let mut state = AppState::new(root.clone());
+        state.selected_paths.insert(root.join("a.txt"));
+        state.sort_mode = SortMode::Size;
+        let focused = root.join("src").join("main.rs");
+
+        let segments = expand_status_template(
+            "{path} | {selection}{sort} {nope} {message}{",
+            &state,
+            Some(&focused),
+            0,
+        );
+        let sort = sort_indicator(&state).unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                StatusSegment::Field(StatusField::Path, "src/main.rs".to_string()),
+                StatusSegment::Literal(" | ".to_string()),
+                StatusSegment::Field(StatusField::Selection, "Selected: 1".to_string()),
+                StatusSegment::Field(StatusField::Sort, sort),
+                // Unknown and unclosed tokens stay as written
+                StatusSegment::Literal(" {nope} ".to_string()),
+                StatusSegment::Field(StatusField::Message, "? for help".to_string()),
+                StatusSegment::Literal("{".to_string()),
+            ]
+        );
+
+        // Fields without a value expand to nothing
+        state.selected_paths.clear();
+        let segments = expand_status_template("{mode}{git}{selection}", &state, None, 0);
+        assert!(segments
+            .iter()
+            .all(|s| matches!(s, StatusSegment::Field(_, text) if text.is_empty())));
+    }
+}
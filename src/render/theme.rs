@@ -5,17 +5,38 @@
 use ratatui::style::Color;
 use serde::Deserialize;
 use std::fs;
-use std::path::PathBuf;
-use std::sync::OnceLock;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
 
 use crate::app::ConfigFile;
 
-/// Global theme instance
-static THEME: OnceLock<Theme> = OnceLock::new();
+/// Global theme instance (replaced on live reload)
+static THEME: RwLock<Option<Arc<Theme>>> = RwLock::new(None);
 
 /// Get the global theme instance
-pub fn theme() -> &'static Theme {
-    THEME.get_or_init(Theme::load)
+pub fn theme() -> Arc<Theme> {
+    if let Some(theme) = THEME.read().ok().and_then(|t| t.clone()) {
+        return theme;
+    }
+    match THEME.write() {
+        Ok(mut slot) => Arc::clone(slot.get_or_insert_with(|| Arc::new(Theme::load()))),
+        Err(_) => Arc::new(Theme::load()),
+    }
+}
+
+/// Reload the global theme from the theme file
+///
+/// A missing file resets to the default theme. On a parse error the current
+/// theme is kept and the error is returned.
+pub fn reload_theme() -> anyhow::Result<()> {
+    let file = match ThemeFile::theme_path() {
+        Some(path) if path.exists() => ThemeFile::load_from(&path)?,
+        _ => ThemeFile::default(),
+    };
+    if let Ok(mut slot) = THEME.write() {
+        *slot = Some(Arc::new(Theme::from_file(&file)));
+    }
+    Ok(())
 }
 
 /// Theme configuration file structure
@@ -175,6 +196,12 @@ impl ThemeFile {
             .and_then(|content| toml::from_str(&content).ok())
             .unwrap_or_default()
     }
+
+    /// Load theme from a specific path, reporting parse errors
+    pub fn load_from(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(toml::from_str(&content)?)
+    }
 }
 
 /// Parsed theme with ratatui Color values
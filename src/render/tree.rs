@@ -2,19 +2,25 @@
 
 use ratatui::{
     layout::Rect,
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem},
     Frame,
 };
 
 use super::layout::LayoutEngine;
+use super::scrollbar::render_scrollbar;
 use super::theme::theme;
-use crate::core::{AppState, FocusTarget, UiDensity};
+use crate::core::{
+    match_ranges, AppState, FocusTarget, IconSet, NameTruncation, TreeGuideStyle, UiDensity,
+    ViewMode,
+};
 use crate::git::FileStatus;
 use crate::render::icons;
 use crate::tree::TreeEntry;
 
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
 /// Render the file tree widget
 pub fn render_tree(frame: &mut Frame, state: &AppState, entries: &[&TreeEntry], area: Rect) {
     let visible_height = area.height.saturating_sub(2) as usize;
@@ -22,6 +28,20 @@ pub fn render_tree(frame: &mut Frame, state: &AppState, entries: &[&TreeEntry],
     let layout = LayoutEngine::from_rect_with_density(area, density);
     let tree_cols = layout.tree_columns(area);
 
+    // Guides need sibling boundaries, so they are computed over the full list
+    let guides = match state.tree_guides {
+        TreeGuideStyle::Spaces => None,
+        style => {
+            let depths: Vec<usize> = entries.iter().map(|e| e.depth).collect();
+            let unit = indent_unit(density);
+            Some(build_guides(&depths, style, unit))
+        }
+    };
+
+    let number_width = state
+        .relative_numbers
+        .then(|| number_width(visible_height, state.focus_index));
+
     let items: Vec<ListItem> = entries
         .iter()
         .skip(state.viewport_top)
@@ -29,7 +49,28 @@ pub fn render_tree(frame: &mut Frame, state: &AppState, entries: &[&TreeEntry],
         .enumerate()
         .map(|(i, entry)| {
             let absolute_index = state.viewport_top + i;
-            render_entry(state, entry, absolute_index, &layout, &tree_cols)
+            let indent = match &guides {
+                Some(g) => g[absolute_index].clone(),
+                None => " ".repeat(entry.depth * indent_unit(density)),
+            };
+            // The focused row shows its position left-aligned, like vim
+            let number = number_width.map(|width| {
+                let n = relative_number(absolute_index, state.focus_index);
+                if absolute_index == state.focus_index {
+                    format!("{:<width$} ", n)
+                } else {
+                    format!("{:>width$} ", n)
+                }
+            });
+            render_entry(
+                state,
+                entry,
+                absolute_index,
+                &layout,
+                &tree_cols,
+                indent,
+                number,
+            )
         })
         .collect();
 
@@ -54,6 +95,13 @@ pub fn render_tree(frame: &mut Frame, state: &AppState, entries: &[&TreeEntry],
     );
 
     frame.render_widget(list, area);
+    render_scrollbar(
+        frame,
+        area,
+        entries.len(),
+        visible_height,
+        state.viewport_top,
+    );
 }
 
 /// Render a single tree entry as a ListItem
@@ -63,25 +111,27 @@ fn render_entry(
     index: usize,
     layout: &LayoutEngine,
     tree_cols: &super::layout::TreeColumns,
+    indent_str: String,
+    number: Option<String>,
 ) -> ListItem<'static> {
     let t = theme();
     let density = layout.density;
 
-    // Adjust indent based on density
-    let indent_str = match density {
-        UiDensity::Ultra | UiDensity::Narrow => " ".repeat(entry.depth),
-        UiDensity::Compact | UiDensity::Full => "  ".repeat(entry.depth),
-    };
-
     // Icon selection based on density and settings
-    let icon = if tree_cols.show_icons && state.icons_enabled {
-        icons::get_icon(&entry.path, entry.is_dir, entry.is_expanded())
+    let icon = if tree_cols.show_icons && state.icons_enabled && state.icon_set != IconSet::None {
+        icons::get_icon(
+            &entry.path,
+            entry.is_dir,
+            entry.is_expanded(),
+            state.icon_set,
+        )
     } else if entry.is_dir {
         // Use compact indicators in narrow modes
-        if entry.is_expanded() {
-            "▾"
-        } else {
-            "▸"
+        match (entry.is_expanded(), state.icon_set == IconSet::Ascii) {
+            (true, false) => "▾",
+            (false, false) => "▸",
+            (true, true) => "-",
+            (false, true) => "+",
         }
     } else {
         ""
@@ -98,51 +148,72 @@ fn render_entry(
     let mark_indicator = if is_selected { "*" } else { " " };
 
     // Get git status color and staging info
-    let git_status = state
-        .git_status
-        .as_ref()
+    let git = state.git_status_for(&entry.path);
+    let git_status = git
         .map(|g| g.get_status(&entry.path))
         .unwrap_or(FileStatus::Clean);
 
-    let is_staged = state
-        .git_status
-        .as_ref()
-        .is_some_and(|g| g.is_staged(&entry.path));
+    let is_staged = git.is_some_and(|g| g.is_staged(&entry.path));
 
     let mut style = Style::default();
 
     // Apply git status color first (using theme colors)
-    style = match git_status {
-        FileStatus::Modified => style.fg(t.git_modified),
-        FileStatus::Added | FileStatus::Untracked => style.fg(t.git_untracked),
-        FileStatus::Deleted => style.fg(t.git_deleted),
-        FileStatus::Renamed => style.fg(t.git_renamed),
-        FileStatus::Ignored => style.fg(t.git_ignored),
-        FileStatus::Conflict => style.fg(t.git_conflict),
-        FileStatus::Clean => {
-            if entry.is_dir {
-                style.fg(t.directory)
-            } else {
-                style
-            }
-        }
+    style = match status_color(git_status) {
+        Some(color) => style.fg(color),
+        None if entry.is_dir => style.fg(t.directory),
+        None => style,
     };
 
+    // Collapsed directories summarize their changed descendants
+    let dir_marker = if entry.is_dir && !entry.is_expanded() {
+        git.and_then(|g| g.dir_status(&entry.path)).and_then(|dir| {
+            let color = status_color(dir.status)?;
+            let text = match density {
+                UiDensity::Ultra => " ●".to_string(),
+                _ => format!(" ●{}", dir.changed),
+            };
+            Some(Span::styled(text, Style::default().fg(color)))
+        })
+    } else {
+        None
+    };
+
+    // Directories that could not be listed
+    let denied_marker = entry.is_denied().then(|| {
+        let text = if state.icon_set == IconSet::Ascii {
+            " [denied]"
+        } else {
+            " 🔒"
+        };
+        Span::styled(text, Style::default().fg(t.error))
+    });
+
     // Override with cut style if applicable
     if is_cut {
         style = style.fg(t.git_ignored);
     }
 
+    // Guides keep the entry style unless dimming is enabled
+    let mut guide_style = if state.dim_tree_guides && state.tree_guides != TreeGuideStyle::Spaces {
+        Style::default().add_modifier(Modifier::DIM)
+    } else {
+        style
+    };
+
     // Apply focus style
     if is_focused {
         style = style.bg(t.selection).add_modifier(Modifier::BOLD);
+        guide_style = guide_style.bg(t.selection);
     }
+    let indent = Span::styled(indent_str, guide_style);
 
     // Stage indicator: compact in ultra mode
     let stage_indicator = match density {
         UiDensity::Ultra => {
             // In ultra mode, combine mark and stage into one char
-            if is_staged {
+            if git_status == FileStatus::Conflict {
+                Span::styled("!", Style::default().fg(t.git_conflict))
+            } else if is_staged {
                 Span::styled("✓", Style::default().fg(t.git_staged))
             } else if git_status == FileStatus::Modified {
                 Span::styled("M", Style::default().fg(t.git_modified))
@@ -151,7 +222,9 @@ fn render_entry(
             }
         }
         _ => {
-            if is_staged {
+            if git_status == FileStatus::Conflict {
+                Span::styled("!", Style::default().fg(t.git_conflict))
+            } else if is_staged {
                 Span::styled("+", Style::default().fg(t.git_staged))
             } else if git_status == FileStatus::Modified {
                 Span::styled("~", Style::default().fg(t.git_modified))
@@ -161,41 +234,67 @@ fn render_entry(
         }
     };
 
-    // Truncate filename if needed for narrow modes
-    let max_name_width = tree_cols.filename_width_at_depth(entry.depth) as usize;
-    let display_name = if entry.name.len() > max_name_width && max_name_width > 3 {
-        format!("{}…", &entry.name[..max_name_width - 1])
-    } else {
-        entry.name.clone()
-    };
+    let number = number.map(|n| {
+        let number_style = if is_focused {
+            Style::default()
+                .bg(t.selection)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().add_modifier(Modifier::DIM)
+        };
+        Span::styled(n, number_style)
+    });
+
+    // Truncate filename if needed, leaving room for the gutter and markers
+    let marker_width: usize = denied_marker
+        .iter()
+        .chain(dir_marker.iter())
+        .chain(number.iter())
+        .map(Span::width)
+        .sum();
+    let max_name_width =
+        (tree_cols.filename_width_at_depth(entry.depth) as usize).saturating_sub(marker_width);
+    let display_name = truncate_name(&entry.name, max_name_width, state.name_truncation);
+
+    let name_spans = highlight_name(state, &display_name, style);
 
     // Build the line based on density
-    let line = match density {
+    let mut line = match density {
         UiDensity::Ultra => {
             // Ultra compact: mark + indent + icon + name + stage (at end)
-            let entry_text = if icon.is_empty() {
-                format!("{}{}", indent_str, display_name)
+            let icon_with_space = if icon.is_empty() {
+                String::new()
             } else {
-                format!("{}{} {}", indent_str, icon, display_name)
+                format!("{} ", icon)
             };
-            Line::from(vec![
+            let mut spans = vec![
                 Span::styled(mark_indicator, Style::default().fg(t.mark)),
-                Span::styled(entry_text, style),
-                stage_indicator,
-            ])
+                indent,
+                Span::styled(icon_with_space, style),
+            ];
+            spans.extend(name_spans);
+            spans.extend(denied_marker);
+            spans.extend(dir_marker);
+            spans.push(stage_indicator);
+            Line::from(spans)
         }
         UiDensity::Narrow => {
             // Narrow: mark + stage + indent + icon + name
-            let entry_text = if icon.is_empty() {
-                format!("{}{}", indent_str, display_name)
+            let icon_with_space = if icon.is_empty() {
+                String::new()
             } else {
-                format!("{}{} {}", indent_str, icon, display_name)
+                format!("{} ", icon)
             };
-            Line::from(vec![
+            let mut spans = vec![
                 Span::styled(mark_indicator, Style::default().fg(t.mark)),
                 stage_indicator,
-                Span::styled(entry_text, style),
-            ])
+                indent,
+                Span::styled(icon_with_space, style),
+            ];
+            spans.extend(name_spans);
+            spans.extend(denied_marker);
+            spans.extend(dir_marker);
+            Line::from(spans)
         }
         _ => {
             // Full/Compact: standard layout with space after icon
@@ -204,20 +303,203 @@ fn render_entry(
             } else {
                 format!("{} ", icon)
             };
-            Line::from(vec![
+            let mut spans = vec![
                 Span::styled(mark_indicator, Style::default().fg(t.mark)),
                 stage_indicator,
-                Span::styled(
-                    format!("{}{}{}", indent_str, icon_with_space, display_name),
-                    style,
-                ),
-            ])
+                indent,
+                Span::styled(icon_with_space, style),
+            ];
+            spans.extend(name_spans);
+            spans.extend(denied_marker);
+            spans.extend(dir_marker);
+            Line::from(spans)
         }
     };
+    if let Some(number) = number {
+        line.spans.insert(0, number);
+    }
 
     ListItem::new(line)
 }
 
+/// Gutter number of the row at `index`: its distance from the focused row,
+/// or the 1-based position of the focused row itself
+fn relative_number(index: usize, focus: usize) -> usize {
+    if index == focus {
+        focus + 1
+    } else {
+        index.abs_diff(focus)
+    }
+}
+
+/// Digits of the gutter: enough for the farthest row of a `height`-row
+/// viewport and for the position of the focused row
+fn number_width(height: usize, focus: usize) -> usize {
+    height.max(focus + 1).max(1).ilog10() as usize + 1
+}
+
+/// Fit `name` into `width` columns, cutting it where `mode` says
+///
+/// Names that fit, and widths of 3 columns or less, are left alone.
+fn truncate_name(name: &str, width: usize, mode: NameTruncation) -> String {
+    if name.width() <= width || width <= 3 {
+        return name.to_string();
+    }
+    // Room left for the name once the ellipsis is placed
+    let budget = width - 1;
+    match mode {
+        NameTruncation::Tail => format!("{}…", take_width(name.chars(), budget)),
+        NameTruncation::Middle => {
+            let head = take_width(name.chars(), budget.div_ceil(2));
+            let tail = take_width_rev(name, budget - head.width());
+            format!("{}…{}", head, tail)
+        }
+        NameTruncation::Smart => match name.rfind('.').filter(|&i| i > 0) {
+            // Keep at least one character of the stem in front of the ellipsis
+            Some(dot) if name[dot..].width() < budget => {
+                let ext = &name[dot..];
+                let stem = take_width(name[..dot].chars(), budget - ext.width());
+                format!("{}…{}", stem, ext)
+            }
+            _ => truncate_name(name, width, NameTruncation::Tail),
+        },
+    }
+}
+
+/// Leading characters of `chars` fitting in `width` columns
+fn take_width(chars: impl Iterator<Item = char>, width: usize) -> String {
+    let mut used = 0;
+    chars
+        .take_while(|c| {
+            used += c.width().unwrap_or(0);
+            used <= width
+        })
+        .collect()
+}
+
+/// Trailing characters of `text` fitting in `width` columns
+fn take_width_rev(text: &str, width: usize) -> String {
+    let mut tail: Vec<char> = take_width(text.chars().rev(), width).chars().collect();
+    tail.reverse();
+    tail.into_iter().collect()
+}
+
+/// Name spans with the active search query's matches highlighted
+fn highlight_name(state: &AppState, name: &str, style: Style) -> Vec<Span<'static>> {
+    let ranges = match &state.mode {
+        ViewMode::Search { query } => match_ranges(name, query, state.case_sensitive),
+        _ => Vec::new(),
+    };
+    if ranges.is_empty() {
+        return vec![Span::styled(name.to_string(), style)];
+    }
+
+    let match_style = style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for range in ranges {
+        if range.start > last {
+            spans.push(Span::styled(name[last..range.start].to_string(), style));
+        }
+        spans.push(Span::styled(name[range.clone()].to_string(), match_style));
+        last = range.end;
+    }
+    if last < name.len() {
+        spans.push(Span::styled(name[last..].to_string(), style));
+    }
+    spans
+}
+
+/// Theme color for a git status (None for clean entries)
+fn status_color(status: FileStatus) -> Option<Color> {
+    let t = theme();
+    match status {
+        FileStatus::Modified => Some(t.git_modified),
+        FileStatus::Added | FileStatus::Untracked => Some(t.git_untracked),
+        FileStatus::Deleted => Some(t.git_deleted),
+        FileStatus::Renamed => Some(t.git_renamed),
+        FileStatus::Ignored => Some(t.git_ignored),
+        FileStatus::Conflict => Some(t.git_conflict),
+        FileStatus::Clean => None,
+    }
+}
+
+/// Indentation width per depth level
+fn indent_unit(density: UiDensity) -> usize {
+    match density {
+        UiDensity::Ultra | UiDensity::Narrow => 1,
+        UiDensity::Compact | UiDensity::Full => 2,
+    }
+}
+
+/// Determine for each entry whether it is the last child of its parent
+///
+/// Works on the flat visible list: an entry is the last child if no later
+/// entry at the same depth appears before the list returns to a shallower depth.
+pub fn last_child_flags(depths: &[usize]) -> Vec<bool> {
+    let mut flags = vec![false; depths.len()];
+    // has_later_sibling[d]: an entry at depth d follows in the current parent
+    let mut has_later_sibling: Vec<bool> = Vec::new();
+
+    for (i, &depth) in depths.iter().enumerate().rev() {
+        has_later_sibling.resize(depth + 1, false);
+        flags[i] = !has_later_sibling[depth];
+        has_later_sibling[depth] = true;
+    }
+
+    flags
+}
+
+/// Build the guide prefix for each entry (each level is `unit` columns wide)
+fn build_guides(depths: &[usize], style: TreeGuideStyle, unit: usize) -> Vec<String> {
+    let (vertical, branch, last) = match style {
+        TreeGuideStyle::Unicode => ('│', '├', '└'),
+        _ => ('|', '|', '`'),
+    };
+    let horizontal = if style == TreeGuideStyle::Unicode {
+        '─'
+    } else {
+        '-'
+    };
+    // ASCII shares '|' between branches and continuations, so the caller
+    // says which one it draws instead of comparing glyphs
+    let pad = |first: char, joint: bool| -> String {
+        let mut s = first.to_string();
+        let fill = if joint { horizontal } else { ' ' };
+        for _ in 1..unit {
+            s.push(fill);
+        }
+        s
+    };
+
+    let last_flags = last_child_flags(depths);
+    // ancestor_last[d]: whether the most recent entry at depth d was a last child
+    let mut ancestor_last: Vec<bool> = Vec::new();
+
+    depths
+        .iter()
+        .zip(&last_flags)
+        .map(|(&depth, &is_last)| {
+            ancestor_last.resize(depth + 1, false);
+            ancestor_last[depth] = is_last;
+
+            let mut prefix = String::new();
+            // Depth 0 is the root; levels 1..depth are drawn
+            for &ancestor_is_last in ancestor_last.iter().take(depth).skip(1) {
+                if ancestor_is_last {
+                    prefix.push_str(&" ".repeat(unit));
+                } else {
+                    prefix.push_str(&pad(vertical, false));
+                }
+            }
+            if depth > 0 {
+                prefix.push_str(&pad(if is_last { last } else { branch }, true));
+            }
+            prefix
+        })
+        .collect()
+}
+
 /// Abbreviate a path to fit within max_width
 /// Adaptive abbreviation based on available width:
 /// - max_width < 20: filename only, truncated if needed
@@ -298,3 +580,130 @@ fn abbreviate_path(path: &std::path::Path, max_width: usize) -> String {
 pub fn visible_height(area: Rect) -> usize {
     area.height.saturating_sub(2) as usize
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_numbers_around_focus() {
+        let numbers: Vec<usize> = (0..7).map(|i| relative_number(i, 3)).collect();
+        assert_eq!(numbers, vec![3, 2, 1, 4, 1, 2, 3]);
+        assert_eq!(relative_number(0, 0), 1);
+
+        // The gutter widens with the viewport and the focused position
+        assert_eq!(number_width(9, 0), 1);
+        assert_eq!(number_width(40, 0), 2);
+        assert_eq!(number_width(40, 1200), 4);
+    }
+
+    #[test]
+    fn test_last_child_flags() {
+        // root
+        // ├─ a
+        // │  ├─ a1
+        // │  └─ a2
+        // └─ b
+        let depths = [0, 1, 2, 2, 1];
+        assert_eq!(
+            last_child_flags(&depths),
+            vec![true, false, false, true, true]
+        );
+    }
+
+    #[test]
+    fn test_last_child_flags_nested_last() {
+        // A deeper entry followed by a shallower one closes the branch
+        let depths = [0, 1, 1, 2, 3, 1];
+        assert_eq!(
+            last_child_flags(&depths),
+            vec![true, false, false, true, true, true]
+        );
+    }
+
+    #[test]
+    fn test_last_child_flags_empty() {
+        assert!(last_child_flags(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_build_guides_unicode() {
+        let depths = [0, 1, 2, 2, 1, 2];
+        let guides = build_guides(&depths, TreeGuideStyle::Unicode, 2);
+        assert_eq!(guides, vec!["", "├─", "│ ├─", "│ └─", "└─", "  └─"]);
+    }
+
+    #[test]
+    fn test_build_guides_ascii_narrow() {
+        let depths = [0, 1, 2, 1];
+        let guides = build_guides(&depths, TreeGuideStyle::Ascii, 1);
+        assert_eq!(guides, vec!["", "|", "|`", "`"]);
+    }
+
+    #[test]
+    fn test_build_guides_ascii_continuations() {
+        // Continuations are "| ", levels below a last child are blank
+        let depths = [0, 1, 2, 3, 1, 2];
+        let guides = build_guides(&depths, TreeGuideStyle::Ascii, 2);
+        assert_eq!(guides, vec!["", "|-", "| `-", "|   `-", "`-", "  `-"]);
+    }
+
+    #[test]
+    fn test_truncate_name_tail() {
+        let name = "report_2024_final.pdf";
+        assert_eq!(
+            truncate_name(name, 12, NameTruncation::Tail),
+            "report_2024…"
+        );
+        assert_eq!(truncate_name(name, 30, NameTruncation::Tail), name);
+        // Too narrow to truncate meaningfully
+        assert_eq!(truncate_name(name, 3, NameTruncation::Tail), name);
+        // Wide characters count as two columns and are never split
+        assert_eq!(
+            truncate_name("日本語のファイル.txt", 6, NameTruncation::Tail),
+            "日本…"
+        );
+    }
+
+    #[test]
+    fn test_truncate_name_middle() {
+        let name = "report_2024_final.pdf";
+        let cut = truncate_name(name, 12, NameTruncation::Middle);
+        assert_eq!(cut, "report…l.pdf");
+        assert_eq!(cut.width(), 12);
+    }
+
+    #[test]
+    fn test_truncate_name_smart_keeps_extension() {
+        let name = "report_2024_final.pdf";
+        let cut = truncate_name(name, 12, NameTruncation::Smart);
+        assert_eq!(cut, "report_….pdf");
+        assert!(cut.ends_with(".pdf"));
+        assert_eq!(cut.width(), 12);
+
+        // No extension, or one too long to keep, falls back to tail
+        assert_eq!(
+            truncate_name("a_very_long_directory", 8, NameTruncation::Smart),
+            "a_very_…"
+        );
+        assert_eq!(
+            truncate_name("archive.backup-original", 8, NameTruncation::Smart),
+            "archive…"
+        );
+        // Dotfiles have no extension
+        assert_eq!(
+            truncate_name(".config_backup_old", 8, NameTruncation::Smart),
+            ".config…"
+        );
+    }
+
+    #[test]
+    fn test_tree_guide_style_resolve_ascii_only() {
+        assert_eq!(TreeGuideStyle::Unicode.resolve(true), TreeGuideStyle::Ascii);
+        assert_eq!(
+            TreeGuideStyle::Unicode.resolve(false),
+            TreeGuideStyle::Unicode
+        );
+        assert_eq!(TreeGuideStyle::Spaces.resolve(true), TreeGuideStyle::Spaces);
+    }
+}
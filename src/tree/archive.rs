@@ -0,0 +1,403 @@
+//! Archives browsed as virtual directories
+//!
+//! A mounted zip or tar archive shows its members as a subtree below the
+//! archive entry. Member paths continue the archive's own path
+//! (`dist.zip/docs/guide.md`), so lookups by path prefix work unchanged; the
+//! members exist only in the tree. A member is previewed from a copy
+//! extracted on demand, in the background, to the user's cache directory
+//! (`~/.cache/fileview/archive`), and nothing inside an archive can be
+//! written.
+
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::TreeEntry;
+use crate::core::{cache_dir, cache_key};
+use crate::render::{is_archive_file, is_tar_gz_file};
+
+/// Members listed from one archive at most
+pub const MAX_ARCHIVE_MEMBERS: usize = 10_000;
+
+/// Largest member extracted for a preview
+pub const MAX_MEMBER_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Extracted members kept in the cache
+const MEMBER_CACHE_ENTRIES: usize = 64;
+
+/// One file or directory inside an archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveMember {
+    /// Path inside the archive
+    pub path: PathBuf,
+    pub is_dir: bool,
+}
+
+/// Whether `path` is an archive file that can be mounted
+pub fn is_browsable_archive(path: &Path) -> bool {
+    is_archive_file(path) && path.is_file()
+}
+
+/// Split a path inside a mounted archive into the archive and the member
+///
+/// Returns None for paths that exist on disk or lie in no archive.
+pub fn split_archive_path(path: &Path) -> Option<(PathBuf, PathBuf)> {
+    if path.exists() {
+        return None;
+    }
+    let archive = path.ancestors().skip(1).find(|a| a.exists())?;
+    if !is_browsable_archive(archive) {
+        return None;
+    }
+    let member = path.strip_prefix(archive).ok()?.to_path_buf();
+    Some((archive.to_path_buf(), member))
+}
+
+/// Relative path without `..`, root or prefix components (None if unsafe)
+fn safe_member_path(name: &str) -> Option<PathBuf> {
+    let path = Path::new(name);
+    let mut clean = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => clean.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!clean.as_os_str().is_empty()).then_some(clean)
+}
+
+/// Members of a zip or tar.gz archive, in archive order
+///
+/// Members with unsafe paths are skipped; listing stops after
+/// [`MAX_ARCHIVE_MEMBERS`].
+pub fn list_members(archive: &Path) -> anyhow::Result<Vec<ArchiveMember>> {
+    let mut members = Vec::new();
+    if is_tar_gz_file(archive) {
+        let file = File::open(archive)?;
+        let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(file));
+        for entry in tar.entries()?.take(MAX_ARCHIVE_MEMBERS) {
+            let entry = entry?;
+            let is_dir = entry.header().entry_type().is_dir();
+            if let Some(path) = safe_member_path(&entry.path()?.to_string_lossy()) {
+                members.push(ArchiveMember { path, is_dir });
+            }
+        }
+    } else {
+        let mut zip = zip::ZipArchive::new(File::open(archive)?)?;
+        for i in 0..zip.len().min(MAX_ARCHIVE_MEMBERS) {
+            let entry = zip.by_index(i)?;
+            let is_dir = entry.is_dir();
+            if let Some(path) = safe_member_path(entry.name()) {
+                members.push(ArchiveMember { path, is_dir });
+            }
+        }
+    }
+    Ok(members)
+}
+
+/// Replace the children of the archive `entry` with its members
+///
+/// The entry becomes a directory of the tree; members get `entry.archive`
+/// set and parent directories missing from the archive are filled in.
+pub fn mount(entry: &mut TreeEntry) -> anyhow::Result<()> {
+    let members = list_members(&entry.path)?;
+    let archive = entry.path.clone();
+    entry.children_mut().clear();
+    entry.is_dir = true;
+    entry.archive = Some(archive.clone());
+    for member in members {
+        insert_member(entry, &archive, &member);
+    }
+    Ok(())
+}
+
+fn insert_member(root: &mut TreeEntry, archive: &Path, member: &ArchiveMember) {
+    let parts: Vec<_> = member.path.components().collect();
+    let mut current = root;
+    for (i, part) in parts.iter().enumerate() {
+        let is_dir = member.is_dir || i + 1 < parts.len();
+        let path = current.path.join(part);
+        let idx = match current.children().iter().position(|c| c.path == path) {
+            Some(idx) => idx,
+            None => {
+                let mut child = TreeEntry::new_with_type(path, current.depth + 1, is_dir);
+                child.archive = Some(archive.to_path_buf());
+                current.children_mut().push(child);
+                current.children().len() - 1
+            }
+        };
+        current = &mut current.children_mut()[idx];
+        // A file and a directory of the same name: the directory wins
+        current.is_dir |= is_dir;
+    }
+}
+
+/// Directory below the user cache that holds extracted members
+pub fn member_cache_dir() -> Option<PathBuf> {
+    cache_dir().map(|p| p.join("archive"))
+}
+
+/// Where `member` of `archive` is extracted below `cache`
+fn member_target(archive: &Path, member: &Path, cache: &Path) -> anyhow::Result<Option<PathBuf>> {
+    let Some(file_name) = member.file_name() else {
+        return Ok(None);
+    };
+    let modified = fs::metadata(archive)?.modified().unwrap_or(UNIX_EPOCH);
+    let key = cache_key(&archive.join(member), modified);
+    Ok(Some(cache.join(key).join(file_name)))
+}
+
+/// Copy of `member` already extracted below `cache`, if any
+pub fn cached_member(archive: &Path, member: &Path, cache: &Path) -> Option<PathBuf> {
+    let target = member_target(archive, member, cache).ok()??;
+    // Only a regular file written by us is reused, never a planted symlink
+    let is_file = fs::symlink_metadata(&target).is_ok_and(|meta| meta.is_file());
+    is_file.then_some(target)
+}
+
+/// Extract `member` of `archive` below `cache` for a preview
+///
+/// `cache` is a per-user directory (see [`member_cache_dir`]). The copy keeps
+/// the member's file name so its type is detected as usual, and is reused
+/// while the archive is unchanged. Returns None when no file member has that
+/// path (directories included).
+pub fn extract_member(
+    archive: &Path,
+    member: &Path,
+    cache: &Path,
+) -> anyhow::Result<Option<PathBuf>> {
+    let Some(target) = member_target(archive, member, cache)? else {
+        return Ok(None);
+    };
+    if let Some(cached) = cached_member(archive, member, cache) {
+        return Ok(Some(cached));
+    }
+
+    let mut reader: Box<dyn Read> = if is_tar_gz_file(archive) {
+        let file = File::open(archive)?;
+        let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(file));
+        let mut found = None;
+        for entry in tar.entries()? {
+            let entry = entry?;
+            let is_file = entry.header().entry_type().is_file();
+            let path = safe_member_path(&entry.path()?.to_string_lossy());
+            if is_file && path.as_deref() == Some(member) {
+                found = Some(read_capped(entry, member)?);
+                break;
+            }
+        }
+        let Some(bytes) = found else {
+            return Ok(None);
+        };
+        Box::new(io::Cursor::new(bytes))
+    } else {
+        let mut zip = zip::ZipArchive::new(File::open(archive)?)?;
+        let index = (0..zip.len()).find(|&i| {
+            zip.by_index(i).is_ok_and(|entry| {
+                !entry.is_dir() && safe_member_path(entry.name()).as_deref() == Some(member)
+            })
+        });
+        let Some(index) = index else {
+            return Ok(None);
+        };
+        let entry = zip.by_index(index)?;
+        Box::new(io::Cursor::new(read_capped(entry, member)?))
+    };
+
+    let dir = target.parent().unwrap_or(cache);
+    create_private_dir(cache)?;
+    fs::create_dir_all(dir)?;
+    // Written to a freshly created temp file and renamed, so a half-written
+    // copy is never reused and concurrent extractions do not collide
+    let mut partial = tempfile::NamedTempFile::new_in(dir)?;
+    io::copy(&mut reader, &mut partial)?;
+    partial.persist(&target)?;
+    evict_members(cache);
+    Ok(Some(target))
+}
+
+/// Create `dir` readable by the current user only
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder.create(dir)
+}
+
+/// Drop the least recently extracted members past [`MEMBER_CACHE_ENTRIES`]
+fn evict_members(cache: &Path) {
+    let Ok(read_dir) = fs::read_dir(cache) else {
+        return;
+    };
+    let mut dirs: Vec<(SystemTime, PathBuf)> = read_dir
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|entry| {
+            let modified = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(UNIX_EPOCH);
+            (modified, entry.path())
+        })
+        .collect();
+    if dirs.len() <= MEMBER_CACHE_ENTRIES {
+        return;
+    }
+    dirs.sort();
+    let excess = dirs.len() - MEMBER_CACHE_ENTRIES;
+    for (_, dir) in dirs.into_iter().take(excess) {
+        let _ = fs::remove_dir_all(dir);
+    }
+}
+
+/// Extraction of an archive member running on a worker thread
+pub struct MemberExtraction {
+    /// Tree path of the member (`dist.zip/docs/guide.md`)
+    pub path: PathBuf,
+    result: Receiver<anyhow::Result<Option<PathBuf>>>,
+}
+
+impl MemberExtraction {
+    /// Start extracting `member` of `archive` below `cache`
+    pub fn start(path: &Path, archive: PathBuf, member: PathBuf, cache: PathBuf) -> Self {
+        let (tx, result) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(extract_member(&archive, &member, &cache));
+        });
+        Self {
+            path: path.to_path_buf(),
+            result,
+        }
+    }
+
+    /// The extracted copy once the worker is done
+    pub fn poll(&self) -> Option<anyhow::Result<Option<PathBuf>>> {
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(anyhow::anyhow!("extraction stopped"))),
+        }
+    }
+}
+
+/// Read a member, refusing ones past [`MAX_MEMBER_BYTES`]
+fn read_capped(reader: impl Read, member: &Path) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader.take(MAX_MEMBER_BYTES + 1).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > MAX_MEMBER_BYTES {
+        anyhow::bail!("{} is too large to preview", member.display());
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn write_zip(path: &Path) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        zip.add_directory("docs/", options).unwrap();
+        zip.start_file("docs/guide.md", options).unwrap();
+        zip.write_all(b"# Guide\n").unwrap();
+        // No entry for `src/`: its directory is implied
+        zip.start_file("src/main.rs", options).unwrap();
+        zip.write_all(b"fn main() {}\n").unwrap();
+        zip.start_file("../escape.txt", options).unwrap();
+        zip.write_all(b"nope").unwrap();
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_mount_builds_member_tree() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("bundle.zip");
+        write_zip(&path);
+
+        let mut entry = TreeEntry::new(path.clone(), 1);
+        assert!(!entry.is_dir);
+        mount(&mut entry).unwrap();
+
+        assert!(entry.is_dir);
+        let names: Vec<&str> = entry.children().iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["docs", "src"]);
+        let src = &entry.children()[1];
+        assert!(src.is_dir);
+        assert_eq!(src.depth, 2);
+        assert_eq!(src.children()[0].path, path.join("src/main.rs"));
+        assert_eq!(src.children()[0].archive.as_deref(), Some(path.as_path()));
+
+        assert_eq!(
+            split_archive_path(&path.join("src/main.rs")),
+            Some((path.clone(), PathBuf::from("src/main.rs")))
+        );
+        assert_eq!(split_archive_path(&path), None);
+        assert_eq!(split_archive_path(&temp.path().join("missing/x")), None);
+    }
+
+    #[test]
+    fn test_extract_member_for_preview() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("bundle.zip");
+        write_zip(&path);
+
+        let cache = temp.path().join("cache");
+
+        let main = Path::new("src/main.rs");
+        assert_eq!(cached_member(&path, main, &cache), None);
+        let extracted = extract_member(&path, main, &cache).unwrap().unwrap();
+        assert!(extracted.starts_with(&cache));
+        assert_eq!(extracted.file_name().unwrap(), "main.rs");
+        assert_eq!(fs::read_to_string(&extracted).unwrap(), "fn main() {}\n");
+        assert_eq!(cached_member(&path, main, &cache), Some(extracted));
+        assert_eq!(
+            extract_member(&path, Path::new("docs"), &cache).unwrap(),
+            None
+        );
+        assert_eq!(
+            extract_member(&path, Path::new("nope.txt"), &cache).unwrap(),
+            None
+        );
+
+        let tar_path = temp.path().join("bundle.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            File::create(&tar_path).unwrap(),
+            flate2::Compression::default(),
+        );
+        let mut tar = tar::Builder::new(encoder);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(6);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tar.append_data(&mut header, "notes/todo.txt", &b"ship!\n"[..])
+            .unwrap();
+        tar.into_inner().unwrap().finish().unwrap();
+
+        let mut entry = TreeEntry::new(tar_path.clone(), 0);
+        mount(&mut entry).unwrap();
+        assert_eq!(entry.children()[0].children()[0].name, "todo.txt");
+        let job = MemberExtraction::start(
+            &tar_path.join("notes/todo.txt"),
+            tar_path.clone(),
+            PathBuf::from("notes/todo.txt"),
+            cache.clone(),
+        );
+        let extracted = loop {
+            if let Some(result) = job.poll() {
+                break result.unwrap().unwrap();
+            }
+            thread::sleep(std::time::Duration::from_millis(5));
+        };
+        assert_eq!(fs::read_to_string(extracted).unwrap(), "ship!\n");
+    }
+}
@@ -0,0 +1,118 @@
+//! `.fvignore` rules: entries hidden from the tree and the fuzzy finder
+//!
+//! Rules use gitignore syntax and apply with or without git: the global
+//! `~/.config/fileview/fvignore` first, then `.fvignore` in the root, so a
+//! project can re-include (`!pattern`) what the global file hides. Showing
+//! hidden files reveals ignored entries as well.
+
+use std::fs;
+use std::path::Path;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+/// Name of the per-project ignore file
+pub const FVIGNORE_FILENAME: &str = ".fvignore";
+
+/// Name of the global ignore file in the config directory
+const GLOBAL_FVIGNORE_FILENAME: &str = "fvignore";
+
+/// Ignore rules rooted at a directory
+#[derive(Debug, Clone, Default)]
+pub struct FvIgnore {
+    /// Compiled rules (None without any)
+    rules: Option<Gitignore>,
+}
+
+impl FvIgnore {
+    /// Rules for `root`: the global file, then `root/.fvignore`
+    pub fn load(root: &Path) -> Self {
+        let global =
+            dirs::config_dir().map(|dir| dir.join("fileview").join(GLOBAL_FVIGNORE_FILENAME));
+        let mut text = global
+            .and_then(|path| fs::read_to_string(path).ok())
+            .unwrap_or_default();
+        text.push('\n');
+        text.push_str(&fs::read_to_string(root.join(FVIGNORE_FILENAME)).unwrap_or_default());
+        Self::parse(root, &text)
+    }
+
+    /// Parse gitignore-style `text` with patterns relative to `base`
+    ///
+    /// Lines that are not valid globs are skipped.
+    pub fn parse(base: &Path, text: &str) -> Self {
+        let mut builder = GitignoreBuilder::new(base);
+        for line in text.lines() {
+            let _ = builder.add_line(None, line);
+        }
+        let rules = builder.build().ok().filter(|rules| !rules.is_empty());
+        Self { rules }
+    }
+
+    /// Whether there are no rules
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_none()
+    }
+
+    /// Whether `path` (a directory if `is_dir`) is ignored
+    ///
+    /// Paths outside the base never are. Only the path itself is checked; a
+    /// caller walking the tree skips the contents of an ignored directory.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Some(ref rules) = self.rules else {
+            return false;
+        };
+        match path.strip_prefix(rules.path()) {
+            Ok(relative) if !relative.as_os_str().is_empty() => {
+                rules.matched(relative, is_dir).is_ignore()
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ignored(rules: &str, path: &str, is_dir: bool) -> bool {
+        FvIgnore::parse(Path::new("/p"), rules).is_ignored(&Path::new("/p").join(path), is_dir)
+    }
+
+    #[test]
+    fn test_name_patterns_match_at_any_depth() {
+        let rules = "# build output\n*.log\n\ntmp/\n";
+        assert!(ignored(rules, "debug.log", false));
+        assert!(ignored(rules, "a/b/debug.log", false));
+        assert!(!ignored(rules, "debug.txt", false));
+        assert!(ignored(rules, "src/tmp", true));
+        // Directory-only patterns skip files
+        assert!(!ignored(rules, "tmp", false));
+        assert!(!FvIgnore::parse(Path::new("/p"), rules).is_ignored(Path::new("/q/x.log"), false));
+        assert!(FvIgnore::parse(Path::new("/p"), "# nothing\n\n").is_empty());
+    }
+
+    #[test]
+    fn test_anchored_patterns_and_globstar() {
+        let rules = "/notes.md\ndocs/*.pdf\nassets/**/raw\nvendor/**\n";
+        assert!(ignored(rules, "notes.md", false));
+        assert!(!ignored(rules, "sub/notes.md", false));
+        assert!(ignored(rules, "docs/spec.pdf", false));
+        assert!(!ignored(rules, "docs/old/spec.pdf", false));
+        assert!(ignored(rules, "assets/raw", true));
+        assert!(ignored(rules, "assets/img/2024/raw", true));
+        assert!(ignored(rules, "vendor/lib.rs", false));
+        assert!(!ignored(rules, "vendor", true));
+    }
+
+    #[test]
+    fn test_negation_and_classes() {
+        let rules = "*.csv\n!keep.csv\ndata[0-9].bin\n";
+        assert!(ignored(rules, "a.csv", false));
+        assert!(!ignored(rules, "keep.csv", false));
+        assert!(ignored(rules, "data7.bin", false));
+        assert!(!ignored(rules, "datax.bin", false));
+        assert!(ignored("file?.txt\n[!a]b", "file1.txt", false));
+        assert!(ignored("[!a]b", "cb", false));
+        assert!(!ignored("[!a]b", "ab", false));
+    }
+}
@@ -1,7 +1,14 @@
 //! Tree module - File tree data structure and navigation
 
+pub mod archive;
+pub mod fvignore;
 pub mod navigator;
 pub mod node;
 
-pub use navigator::TreeNavigator;
+pub use archive::{
+    cached_member, extract_member, is_browsable_archive, member_cache_dir, split_archive_path,
+    MemberExtraction,
+};
+pub use fvignore::{FvIgnore, FVIGNORE_FILENAME};
+pub use navigator::{TreeNavigator, STARTUP_EXPAND_ENTRY_LIMIT};
 pub use node::TreeEntry;
@@ -1,22 +1,43 @@
 //! Tree navigator - handles tree traversal and flattening
 
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+use super::archive::{self, is_browsable_archive};
+use super::fvignore::FvIgnore;
 use super::node::sort_entries;
 use super::TreeEntry;
 use crate::core::SortMode;
+use crate::core::EXCLUDED_DIRS;
+
+/// Stop startup expansion once this many entries are shown
+pub const STARTUP_EXPAND_ENTRY_LIMIT: usize = 1000;
 
 /// Manages file tree navigation
+///
+/// A workspace launched with several directories has extra roots, shown as
+/// top-level sections after the main root. Every root expands, collapses and
+/// reloads on its own.
 #[derive(Clone)]
 pub struct TreeNavigator {
     /// Root entry
     root: TreeEntry,
+    /// Further top-level roots of a multi-root workspace
+    extra_roots: Vec<TreeEntry>,
     /// Whether to show hidden files
     show_hidden: bool,
+    /// Directories showing their hidden entries while `show_hidden` is off
+    hidden_dirs: HashSet<PathBuf>,
     /// Whether in stdin mode (read-only, no filesystem operations)
     stdin_mode: bool,
     /// Current sort mode
     sort_mode: SortMode,
+    /// Reverse the sort direction
+    sort_reverse: bool,
+    /// Keep directories above files
+    dirs_first: bool,
+    /// `.fvignore` rules of each root (hidden unless `show_hidden`)
+    ignores: Vec<FvIgnore>,
 }
 
 impl TreeNavigator {
@@ -28,9 +49,14 @@ impl TreeNavigator {
 
         Ok(Self {
             root,
+            extra_roots: Vec::new(),
             show_hidden,
+            hidden_dirs: HashSet::new(),
             stdin_mode: false,
             sort_mode: SortMode::default(),
+            sort_reverse: false,
+            dirs_first: true,
+            ignores: vec![FvIgnore::load(root_path)],
         })
     }
 
@@ -56,9 +82,14 @@ impl TreeNavigator {
 
         Ok(Self {
             root,
+            extra_roots: Vec::new(),
             show_hidden,
+            hidden_dirs: HashSet::new(),
             stdin_mode: true,
             sort_mode: SortMode::default(),
+            sort_reverse: false,
+            dirs_first: true,
+            ignores: Vec::new(),
         })
     }
 
@@ -70,7 +101,9 @@ impl TreeNavigator {
     /// Collect all paths in the tree (for fuzzy finder in stdin mode)
     pub fn collect_all_paths(&self) -> Vec<PathBuf> {
         let mut paths = Vec::new();
-        collect_paths_recursive(&self.root, &mut paths);
+        for root in self.roots() {
+            collect_paths_recursive(root, &mut paths);
+        }
         paths
     }
 
@@ -79,10 +112,51 @@ impl TreeNavigator {
         &self.root
     }
 
+    /// Add a top-level root section for another directory
+    ///
+    /// Fails if `path` is already a root or lies inside or around one.
+    pub fn add_root(&mut self, path: &Path) -> anyhow::Result<()> {
+        if let Some(existing) = self
+            .roots()
+            .find(|r| path.starts_with(&r.path) || r.path.starts_with(path))
+        {
+            anyhow::bail!(
+                "{} overlaps the root {}",
+                path.display(),
+                existing.path.display()
+            );
+        }
+        let mut root = TreeEntry::new(path.to_path_buf(), 0);
+        root.load_children_with_sort(
+            self.shows_hidden_in(path),
+            self.sort_mode,
+            self.sort_reverse,
+            self.dirs_first,
+        )?;
+        root.set_expanded(true);
+        self.extra_roots.push(root);
+        self.ignores.push(FvIgnore::load(path));
+        Ok(())
+    }
+
+    /// Top-level roots in display order (the main root first)
+    pub fn roots(&self) -> impl Iterator<Item = &TreeEntry> {
+        std::iter::once(&self.root).chain(&self.extra_roots)
+    }
+
+    /// Root containing `path` (None if it is outside every root)
+    pub fn root_containing(&self, path: &Path) -> Option<&Path> {
+        self.roots()
+            .map(|r| r.path.as_path())
+            .find(|root| path.starts_with(root))
+    }
+
     /// Flatten the tree into a list of visible entries
     pub fn visible_entries(&self) -> Vec<&TreeEntry> {
         let mut entries = Vec::new();
-        self.collect_visible(&self.root, &mut entries);
+        for root in self.roots() {
+            self.collect_visible(root, &mut entries);
+        }
         entries
     }
 
@@ -91,9 +165,41 @@ impl TreeNavigator {
         out.push(entry);
         if entry.is_expanded() {
             for child in entry.children() {
-                self.collect_visible(child, out);
+                if !self.is_ignored(child) {
+                    self.collect_visible(child, out);
+                }
+            }
+        }
+    }
+
+    /// Whether `.fvignore` hides `entry` (never while showing hidden files)
+    fn is_ignored(&self, entry: &TreeEntry) -> bool {
+        !entry
+            .path
+            .parent()
+            .is_some_and(|dir| self.shows_hidden_in(dir))
+            && self
+                .ignores
+                .iter()
+                .any(|ignore| ignore.is_ignored(&entry.path, entry.is_dir))
+    }
+
+    /// Flatten the tree, keeping only entries accepted by `keep`
+    ///
+    /// Roots are always included. A rejected directory hides its whole
+    /// subtree, so `keep` must accept the ancestors of every kept entry.
+    pub fn visible_entries_where<F>(&self, keep: F) -> Vec<&TreeEntry>
+    where
+        F: Fn(&TreeEntry) -> bool,
+    {
+        let mut entries = Vec::new();
+        for root in self.roots() {
+            entries.push(root);
+            for child in root.children() {
+                collect_visible_where(child, &|e| !self.is_ignored(e) && keep(e), &mut entries);
             }
         }
+        entries
     }
 
     /// Get total count of visible entries
@@ -103,11 +209,16 @@ impl TreeNavigator {
 
     /// Toggle expand/collapse for entry at path
     pub fn toggle_expand(&mut self, path: &Path) -> anyhow::Result<()> {
-        let show_hidden = self.show_hidden;
+        let show_hidden = self.shows_hidden_in(path);
         let sort_mode = self.sort_mode;
+        let reverse = self.sort_reverse;
+        let dirs_first = self.dirs_first;
         if let Some(entry) = self.find_entry_mut(path) {
+            if !entry.is_expanded() {
+                mount_archive(entry, sort_mode, reverse, dirs_first)?;
+            }
             if entry.is_dir && !entry.is_expanded() && entry.children().is_empty() {
-                entry.load_children_with_sort(show_hidden, sort_mode)?;
+                entry.load_children_with_sort(show_hidden, sort_mode, reverse, dirs_first)?;
             }
             entry.toggle_expanded();
         }
@@ -116,17 +227,28 @@ impl TreeNavigator {
 
     /// Expand entry at path
     pub fn expand(&mut self, path: &Path) -> anyhow::Result<()> {
-        let show_hidden = self.show_hidden;
+        let show_hidden = self.shows_hidden_in(path);
         let sort_mode = self.sort_mode;
+        let reverse = self.sort_reverse;
+        let dirs_first = self.dirs_first;
         if let Some(entry) = self.find_entry_mut(path) {
+            mount_archive(entry, sort_mode, reverse, dirs_first)?;
             if entry.is_dir && entry.children().is_empty() {
-                entry.load_children_with_sort(show_hidden, sort_mode)?;
+                entry.load_children_with_sort(show_hidden, sort_mode, reverse, dirs_first)?;
             }
             entry.set_expanded(true);
         }
         Ok(())
     }
 
+    /// Whether the entry at `path` is a directory, a mounted archive or an
+    /// archive that expanding would mount
+    pub fn is_expandable(&self, path: &Path) -> bool {
+        self.find_entry(path).is_some_and(|entry| {
+            entry.is_dir || (entry.archive.is_none() && is_browsable_archive(&entry.path))
+        })
+    }
+
     /// Collapse entry at path
     pub fn collapse(&mut self, path: &Path) {
         if let Some(entry) = self.find_entry_mut(path) {
@@ -137,33 +259,106 @@ impl TreeNavigator {
     /// Reload tree from filesystem
     pub fn reload(&mut self) -> anyhow::Result<()> {
         let expanded_paths = self.expanded_paths();
-        self.root
-            .load_children_with_sort(self.show_hidden, self.sort_mode)?;
+        if !self.stdin_mode {
+            self.ignores = self
+                .roots()
+                .map(|root| FvIgnore::load(&root.path))
+                .collect();
+        }
+        for root in std::iter::once(&mut self.root).chain(&mut self.extra_roots) {
+            let show_hidden = self.show_hidden || self.hidden_dirs.contains(&root.path);
+            root.load_children_with_sort(
+                show_hidden,
+                self.sort_mode,
+                self.sort_reverse,
+                self.dirs_first,
+            )?;
+        }
         self.restore_expanded(&expanded_paths)?;
         Ok(())
     }
 
+    /// Reread the children of `dir` alone, keeping its expanded subdirectories
+    /// expanded
+    ///
+    /// Directories not in the tree, or never loaded, are left alone.
+    pub fn reload_subtree(&mut self, dir: &Path) -> anyhow::Result<()> {
+        let expanded: Vec<PathBuf> = self
+            .expanded_paths()
+            .into_iter()
+            .filter(|path| path != dir && path.starts_with(dir))
+            .collect();
+        let show_hidden = self.shows_hidden_in(dir);
+        let (sort_mode, reverse, dirs_first) = (self.sort_mode, self.sort_reverse, self.dirs_first);
+        let Some(entry) = self.find_entry_mut(dir) else {
+            return Ok(());
+        };
+        if !entry.is_dir || (!entry.is_expanded() && entry.children().is_empty()) {
+            return Ok(());
+        }
+        entry.load_children_with_sort(show_hidden, sort_mode, reverse, dirs_first)?;
+        self.restore_expanded(&expanded)
+    }
+
     /// Set show_hidden and reload
+    ///
+    /// Drops the directories toggled with [`toggle_hidden_in`](Self::toggle_hidden_in).
     pub fn set_show_hidden(&mut self, show: bool) -> anyhow::Result<()> {
         self.show_hidden = show;
+        self.hidden_dirs.clear();
         self.reload()
     }
 
+    /// Whether hidden entries directly inside `dir` are shown
+    pub fn shows_hidden_in(&self, dir: &Path) -> bool {
+        self.show_hidden || self.hidden_dirs.contains(dir)
+    }
+
+    /// Show or hide the hidden entries of `dir` alone, then reload
+    ///
+    /// Subdirectories keep hiding theirs. Returns whether they are now shown.
+    pub fn toggle_hidden_in(&mut self, dir: &Path) -> anyhow::Result<bool> {
+        if self.show_hidden {
+            return Ok(true);
+        }
+        if !self.hidden_dirs.remove(dir) {
+            self.hidden_dirs.insert(dir.to_path_buf());
+        }
+        self.reload()?;
+        Ok(self.hidden_dirs.contains(dir))
+    }
+
     /// Set sort mode and re-sort all loaded children
     pub fn set_sort_mode(&mut self, mode: SortMode) -> anyhow::Result<()> {
+        self.set_sort(mode, self.sort_reverse)
+    }
+
+    /// Set sort mode and direction, and re-sort all loaded children
+    pub fn set_sort(&mut self, mode: SortMode, reverse: bool) -> anyhow::Result<()> {
         self.sort_mode = mode;
+        self.sort_reverse = reverse;
         // Re-sort all loaded children recursively
-        resort_entry_children(&mut self.root, mode);
+        for root in std::iter::once(&mut self.root).chain(&mut self.extra_roots) {
+            resort_entry_children(root, mode, reverse, self.dirs_first);
+        }
         Ok(())
     }
 
+    /// Keep directories above files (or intermix them) and re-sort all loaded children
+    pub fn set_dirs_first(&mut self, dirs_first: bool) -> anyhow::Result<()> {
+        self.dirs_first = dirs_first;
+        self.set_sort(self.sort_mode, self.sort_reverse)
+    }
+
     /// Reveal a path by expanding all parent directories
     ///
     /// This makes the target path visible in the tree by expanding
     /// all ancestor directories from the root to the target.
     pub fn reveal_path(&mut self, target: &Path) -> anyhow::Result<()> {
-        // Collect ancestors from root to target
-        let root_path = self.root.path.clone();
+        // Collect ancestors from the containing root to target
+        let Some(root_path) = self.root_containing(target).map(Path::to_path_buf) else {
+            return Ok(());
+        };
         let mut ancestors = Vec::new();
 
         // Build list of ancestors that need to be expanded
@@ -186,9 +381,74 @@ impl TreeNavigator {
         Ok(())
     }
 
+    /// Expand directories level by level until `depth` levels are shown
+    ///
+    /// Depth 1 shows only the root's children. Directories in
+    /// [`EXCLUDED_DIRS`] stay collapsed, and expansion stops once more than
+    /// `max_entries` entries are visible.
+    pub fn expand_to_depth(&mut self, depth: usize, max_entries: usize) -> anyhow::Result<()> {
+        let mut shown = self.visible_count();
+        for level in 1..depth {
+            let dirs: Vec<&TreeEntry> = self
+                .visible_entries()
+                .into_iter()
+                .filter(|e| e.is_dir && e.depth == level)
+                .filter(|e| !EXCLUDED_DIRS.contains(&e.name.as_str()))
+                .collect();
+            if dirs.is_empty() {
+                break;
+            }
+            let collapsed: Vec<PathBuf> = dirs
+                .into_iter()
+                .filter(|e| !e.is_expanded())
+                .map(|e| e.path.clone())
+                .collect();
+            for dir in collapsed {
+                if shown > max_entries {
+                    return Ok(());
+                }
+                // Unreadable directories stay collapsed
+                if self.expand(&dir).is_err() {
+                    continue;
+                }
+                shown += self
+                    .find_entry_mut(&dir)
+                    .map_or(0, |entry| entry.children().len());
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether listing the loaded directory at `path` was refused
+    pub fn is_denied(&self, path: &Path) -> bool {
+        self.find_entry(path).is_some_and(TreeEntry::is_denied)
+    }
+
+    /// Whether the directory at `path` is expanded in the tree
+    pub fn is_expanded(&self, path: &Path) -> bool {
+        self.find_entry(path).is_some_and(TreeEntry::is_expanded)
+    }
+
+    /// Find entry by path
+    fn find_entry(&self, path: &Path) -> Option<&TreeEntry> {
+        fn find<'a>(entry: &'a TreeEntry, path: &Path) -> Option<&'a TreeEntry> {
+            if entry.path == path {
+                return Some(entry);
+            }
+            entry
+                .children()
+                .iter()
+                .filter(|child| path.starts_with(&child.path))
+                .find_map(|child| find(child, path))
+        }
+        self.roots().find_map(|root| find(root, path))
+    }
+
     /// Find entry by path (mutable)
     fn find_entry_mut(&mut self, path: &Path) -> Option<&mut TreeEntry> {
-        Self::find_in_entry_mut(&mut self.root, path)
+        std::iter::once(&mut self.root)
+            .chain(&mut self.extra_roots)
+            .find_map(|root| Self::find_in_entry_mut(root, path))
     }
 
     fn find_in_entry_mut<'a>(entry: &'a mut TreeEntry, path: &Path) -> Option<&'a mut TreeEntry> {
@@ -208,10 +468,17 @@ impl TreeNavigator {
     /// Used for syncing file watcher with expanded directories.
     pub fn expanded_paths(&self) -> Vec<PathBuf> {
         let mut paths = Vec::new();
-        self.collect_expanded_in(&self.root, &mut paths);
+        for root in self.roots() {
+            self.collect_expanded_in(root, &mut paths);
+        }
         paths
     }
 
+    /// Check if a directory's contents are currently shown (root or expanded)
+    pub fn shows_dir(&self, dir: &Path) -> bool {
+        self.roots().any(|r| r.path == dir) || self.expanded_paths().iter().any(|p| p == dir)
+    }
+
     fn collect_expanded_in(&self, entry: &TreeEntry, paths: &mut Vec<PathBuf>) {
         if entry.is_expanded() {
             paths.push(entry.path.clone());
@@ -230,6 +497,37 @@ impl TreeNavigator {
     }
 }
 
+/// Mount `entry` if it is an archive file not mounted yet
+fn mount_archive(
+    entry: &mut TreeEntry,
+    mode: SortMode,
+    reverse: bool,
+    dirs_first: bool,
+) -> anyhow::Result<()> {
+    if entry.is_dir || entry.archive.is_some() || !is_browsable_archive(&entry.path) {
+        return Ok(());
+    }
+    archive::mount(entry)?;
+    resort_entry_children(entry, mode, reverse, dirs_first);
+    Ok(())
+}
+
+/// Recursively collect visible entries accepted by `keep`
+fn collect_visible_where<'a, F>(entry: &'a TreeEntry, keep: &F, out: &mut Vec<&'a TreeEntry>)
+where
+    F: Fn(&TreeEntry) -> bool,
+{
+    if !keep(entry) {
+        return;
+    }
+    out.push(entry);
+    if entry.is_expanded() {
+        for child in entry.children() {
+            collect_visible_where(child, keep, out);
+        }
+    }
+}
+
 /// Insert a path into the tree, creating intermediate directories as needed
 fn insert_path_into_tree(root: &mut TreeEntry, path: &Path, root_path: &Path) {
     // Get relative path from root
@@ -281,7 +579,7 @@ fn insert_path_into_tree(root: &mut TreeEntry, path: &Path, root_path: &Path) {
 
 /// Recursively sort children in the tree (directories first, then alphabetically)
 fn sort_tree_children(entry: &mut TreeEntry) {
-    sort_entries(entry.children_mut(), SortMode::Name);
+    sort_entries(entry.children_mut(), SortMode::Name, false, true);
 
     for child in entry.children_mut() {
         sort_tree_children(child);
@@ -289,11 +587,11 @@ fn sort_tree_children(entry: &mut TreeEntry) {
 }
 
 /// Recursively re-sort children in an entry with the given sort mode
-fn resort_entry_children(entry: &mut TreeEntry, mode: SortMode) {
-    sort_entries(entry.children_mut(), mode);
+fn resort_entry_children(entry: &mut TreeEntry, mode: SortMode, reverse: bool, dirs_first: bool) {
+    sort_entries(entry.children_mut(), mode, reverse, dirs_first);
     for child in entry.children_mut() {
         if child.is_dir && !child.children().is_empty() {
-            resort_entry_children(child, mode);
+            resort_entry_children(child, mode, reverse, dirs_first);
         }
     }
 }
@@ -335,6 +633,47 @@ mod tests {
         assert_eq!(nav.root().children().len(), 3);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_unreadable_dir_listed_but_denied() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = setup_test_dir();
+        let locked = temp.path().join("dir_b");
+        fs::write(locked.join("secret.txt"), "x").unwrap();
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+        // Permissions don't apply to root; nothing to check then
+        if fs::read_dir(&locked).is_ok() {
+            fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+            return;
+        }
+
+        let mut nav = TreeNavigator::new(temp.path(), false).unwrap();
+        assert!(nav.visible_entries().iter().any(|e| e.path == locked));
+        // Listing the parent does not open every child directory
+        assert!(!nav.is_denied(&locked));
+
+        // Expanding does not fail; the directory is marked and stays collapsed
+        nav.expand(&locked).unwrap();
+        nav.toggle_expand(&locked).unwrap();
+        assert!(nav.is_denied(&locked));
+        let entry = nav
+            .visible_entries()
+            .into_iter()
+            .find(|e| e.path == locked)
+            .unwrap();
+        assert!(!entry.is_expanded());
+        assert!(entry.children().is_empty());
+        // A reload keeps the rest of the tree
+        nav.reload().unwrap();
+        assert_eq!(nav.root().children().len(), 3);
+
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+        nav.expand(&locked).unwrap();
+        assert!(!nav.is_denied(&locked));
+        assert_eq!(nav.visible_count(), 5);
+    }
+
     #[test]
     fn test_visible_entries() {
         let temp = setup_test_dir();
@@ -398,6 +737,79 @@ mod tests {
         assert!(count_with_hidden > count_without_hidden);
     }
 
+    #[test]
+    fn test_toggle_hidden_in_one_directory() {
+        let temp = setup_test_dir();
+        let dir_a = temp.path().join("dir_a");
+        fs::write(temp.path().join(".root_hidden"), "").unwrap();
+        fs::write(dir_a.join(".a_hidden"), "").unwrap();
+        fs::create_dir(dir_a.join(".a_hidden_dir")).unwrap();
+        fs::write(dir_a.join(".a_hidden_dir").join(".nested"), "").unwrap();
+
+        let mut nav = TreeNavigator::new(temp.path(), false).unwrap();
+        nav.expand(&dir_a).unwrap();
+        let names = |nav: &TreeNavigator| -> Vec<String> {
+            nav.visible_entries()
+                .iter()
+                .filter(|e| e.depth > 0 && e.name.starts_with('.'))
+                .map(|e| e.name.clone())
+                .collect()
+        };
+        assert!(names(&nav).is_empty());
+
+        assert!(nav.toggle_hidden_in(&dir_a).unwrap());
+        assert!(nav.shows_hidden_in(&dir_a));
+        assert!(!nav.shows_hidden_in(temp.path()));
+        // Only dir_a's hidden entries appear, and expansion survives the reload
+        assert_eq!(names(&nav), vec![".a_hidden_dir", ".a_hidden"]);
+        assert!(nav.is_expanded(&dir_a));
+
+        // Subdirectories keep hiding their own hidden entries
+        nav.expand(&dir_a.join(".a_hidden_dir")).unwrap();
+        assert_eq!(names(&nav), vec![".a_hidden_dir", ".a_hidden"]);
+
+        assert!(!nav.toggle_hidden_in(&dir_a).unwrap());
+        assert!(names(&nav).is_empty());
+
+        // The global toggle overrides and clears the scoped ones
+        nav.toggle_hidden_in(&dir_a).unwrap();
+        nav.set_show_hidden(true).unwrap();
+        nav.set_show_hidden(false).unwrap();
+        assert!(!nav.shows_hidden_in(&dir_a));
+    }
+
+    #[test]
+    fn test_fvignore_hides_entries_until_show_hidden() {
+        let temp = setup_test_dir();
+        fs::write(temp.path().join("notes.log"), "log").unwrap();
+        fs::write(
+            temp.path().join(crate::tree::FVIGNORE_FILENAME),
+            "*.log\ndir_b/\n",
+        )
+        .unwrap();
+
+        let mut nav = TreeNavigator::new(temp.path(), false).unwrap();
+        let names = |nav: &TreeNavigator| -> Vec<String> {
+            nav.visible_entries()
+                .iter()
+                .map(|e| e.name.clone())
+                .collect()
+        };
+        let hidden = names(&nav);
+        assert!(!hidden.contains(&"notes.log".to_string()));
+        assert!(!hidden.contains(&"dir_b".to_string()));
+        assert!(hidden.contains(&"dir_a".to_string()));
+        assert!(!nav
+            .visible_entries_where(|_| true)
+            .iter()
+            .any(|e| e.name == "notes.log"));
+
+        nav.set_show_hidden(true).unwrap();
+        let shown = names(&nav);
+        assert!(shown.contains(&"notes.log".to_string()));
+        assert!(shown.contains(&"dir_b".to_string()));
+    }
+
     #[test]
     fn test_reload() {
         let temp = setup_test_dir();
@@ -534,4 +946,187 @@ mod tests {
         // Should be the same
         assert_eq!(count1, count2);
     }
+
+    #[test]
+    fn test_multiple_roots_are_top_level_sections() {
+        let a = setup_test_dir();
+        let b = TempDir::new().unwrap();
+        fs::create_dir(b.path().join("lib")).unwrap();
+        fs::write(b.path().join("lib/mod.rs"), "").unwrap();
+
+        let mut nav = TreeNavigator::new(a.path(), false).unwrap();
+        nav.add_root(b.path()).unwrap();
+
+        let entries = nav.visible_entries();
+        let roots: Vec<&TreeEntry> = entries.iter().copied().filter(|e| e.depth == 0).collect();
+        assert_eq!(roots.len(), 2);
+        assert_eq!(roots[0].path, a.path());
+        assert_eq!(roots[1].path, b.path());
+        assert!(entries.iter().any(|e| e.path == a.path().join("file.txt")));
+        assert!(entries.iter().any(|e| e.path == b.path().join("lib")));
+
+        let nested = b.path().join("lib/mod.rs");
+        assert_eq!(nav.root_containing(&nested), Some(b.path()));
+        assert_eq!(nav.root_containing(Path::new("/elsewhere")), None);
+
+        // Overlapping roots are refused
+        assert!(nav.add_root(b.path()).is_err());
+        assert!(nav.add_root(&a.path().join("dir_a")).is_err());
+    }
+
+    #[test]
+    fn test_roots_expand_independently() {
+        let a = setup_test_dir();
+        let b = setup_test_dir();
+        let mut nav = TreeNavigator::new(a.path(), false).unwrap();
+        nav.add_root(b.path()).unwrap();
+        let count = nav.visible_count();
+
+        nav.expand(&b.path().join("dir_a")).unwrap();
+        let entries = nav.visible_entries();
+        assert!(entries
+            .iter()
+            .any(|e| e.path == b.path().join("dir_a/nested.txt")));
+        assert!(!entries
+            .iter()
+            .any(|e| e.path == a.path().join("dir_a/nested.txt")));
+        assert_eq!(nav.visible_count(), count + 2);
+
+        // Collapsing one root leaves the other as it was
+        nav.collapse(a.path());
+        let entries = nav.visible_entries();
+        assert!(!entries.iter().any(|e| e.path == a.path().join("file.txt")));
+        assert!(entries
+            .iter()
+            .any(|e| e.path == b.path().join("dir_a/nested.txt")));
+
+        // Reloading keeps each root's expansion
+        nav.reload().unwrap();
+        assert_eq!(
+            nav.expanded_paths(),
+            vec![b.path().to_path_buf(), b.path().join("dir_a")]
+        );
+
+        // Revealing expands within the containing root
+        nav.expand(a.path()).unwrap();
+        nav.reveal_path(&a.path().join("dir_a/subdir/x")).unwrap();
+        let expanded = nav.expanded_paths();
+        assert!(expanded.contains(&a.path().join("dir_a/subdir")));
+        assert!(!expanded.contains(&b.path().join("dir_a/subdir")));
+    }
+
+    fn visible_names(nav: &TreeNavigator) -> Vec<String> {
+        nav.visible_entries()
+            .iter()
+            .skip(1)
+            .map(|e| e.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_expand_to_depth_two_levels() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("a/b/c")).unwrap();
+        fs::write(temp.path().join("a/b/c/deep.txt"), "").unwrap();
+        fs::create_dir_all(temp.path().join("target/debug")).unwrap();
+
+        let mut nav = TreeNavigator::new(temp.path(), false).unwrap();
+        nav.expand_to_depth(1, STARTUP_EXPAND_ENTRY_LIMIT).unwrap();
+        assert_eq!(visible_names(&nav), vec!["a", "target"]);
+
+        nav.expand_to_depth(2, STARTUP_EXPAND_ENTRY_LIMIT).unwrap();
+        // `a` is expanded, `a/b` is not, and `target` is never auto-expanded
+        assert_eq!(visible_names(&nav), vec!["a", "b", "target"]);
+
+        nav.expand_to_depth(3, STARTUP_EXPAND_ENTRY_LIMIT).unwrap();
+        assert_eq!(visible_names(&nav), vec!["a", "b", "c", "target"]);
+    }
+
+    #[test]
+    fn test_expand_to_depth_stops_at_entry_limit() {
+        let temp = TempDir::new().unwrap();
+        for dir in ["one", "two"] {
+            fs::create_dir(temp.path().join(dir)).unwrap();
+            for i in 0..5 {
+                fs::write(temp.path().join(dir).join(format!("{}.txt", i)), "").unwrap();
+            }
+        }
+
+        let mut nav = TreeNavigator::new(temp.path(), false).unwrap();
+        nav.expand_to_depth(2, 4).unwrap();
+        // `one` pushes the count past the limit, so `two` stays collapsed
+        assert_eq!(nav.visible_count(), 3 + 5);
+    }
+
+    #[test]
+    fn test_expanding_archive_lists_members() {
+        use std::io::Write;
+
+        let temp = TempDir::new().unwrap();
+        let archive = temp.path().join("bundle.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("src/main.rs", options).unwrap();
+        zip.write_all(b"fn main() {}\n").unwrap();
+        zip.start_file("README.md", options).unwrap();
+        zip.write_all(b"# Bundle\n").unwrap();
+        zip.finish().unwrap();
+
+        let mut nav = TreeNavigator::new(temp.path(), false).unwrap();
+        assert!(nav.is_expandable(&archive));
+        nav.toggle_expand(&archive).unwrap();
+        assert_eq!(visible_names(&nav), vec!["bundle.zip", "src", "README.md"]);
+
+        let src = archive.join("src");
+        assert!(nav.is_expandable(&src));
+        nav.expand(&src).unwrap();
+        assert_eq!(
+            visible_names(&nav),
+            vec!["bundle.zip", "src", "main.rs", "README.md"]
+        );
+
+        // A reload mounts the archive again and keeps its members expanded
+        nav.reload().unwrap();
+        assert_eq!(
+            visible_names(&nav),
+            vec!["bundle.zip", "src", "main.rs", "README.md"]
+        );
+        nav.toggle_expand(&archive).unwrap();
+        assert_eq!(visible_names(&nav), vec!["bundle.zip"]);
+    }
+
+    #[test]
+    fn test_reload_subtree_rereads_one_directory() {
+        let temp = setup_test_dir();
+        let root = temp.path();
+        let dir_a = root.join("dir_a");
+        let mut nav = TreeNavigator::new(root, false).unwrap();
+        nav.expand(&dir_a).unwrap();
+        nav.expand(&dir_a.join("subdir")).unwrap();
+        nav.expand(&root.join("dir_b")).unwrap();
+
+        fs::write(dir_a.join("new.txt"), "").unwrap();
+        fs::write(dir_a.join("subdir/deep.txt"), "").unwrap();
+        fs::write(root.join("dir_b/other.txt"), "").unwrap();
+        nav.reload_subtree(&dir_a).unwrap();
+
+        // dir_a and its expanded subdir are reread; dir_b is untouched
+        assert_eq!(
+            visible_names(&nav),
+            vec![
+                "dir_a",
+                "subdir",
+                "deep.txt",
+                "nested.txt",
+                "new.txt",
+                "dir_b",
+                "file.txt"
+            ]
+        );
+
+        // Unknown and never loaded directories are ignored
+        nav.reload_subtree(&root.join("missing")).unwrap();
+        nav.collapse(&root.join("dir_b"));
+        nav.reload_subtree(&root.join("file.txt")).unwrap();
+    }
 }
@@ -1,6 +1,9 @@
 //! Tree entry (node) definition
 
+use std::cmp::Ordering;
+use std::iter::Peekable;
 use std::path::PathBuf;
+use std::str::Chars;
 
 use crate::core::SortMode;
 
@@ -17,6 +20,11 @@ pub struct TreeEntry {
     pub depth: usize,
     /// Whether directory is expanded
     pub expanded: bool,
+    /// Whether listing the directory failed with permission denied
+    pub denied: bool,
+    /// Archive this entry belongs to: its own path once a mounted archive,
+    /// the containing archive for a member
+    pub archive: Option<PathBuf>,
     /// Child entries (directories only)
     children: Vec<TreeEntry>,
 }
@@ -44,6 +52,8 @@ impl TreeEntry {
             is_dir,
             depth,
             expanded: false,
+            denied: false,
+            archive: None,
             children: Vec::new(),
         }
     }
@@ -53,6 +63,11 @@ impl TreeEntry {
         self.expanded
     }
 
+    /// Check if listing this directory was refused
+    pub fn is_denied(&self) -> bool {
+        self.denied
+    }
+
     /// Get children (immutable)
     pub fn children(&self) -> &[TreeEntry] {
         &self.children
@@ -65,14 +80,12 @@ impl TreeEntry {
 
     /// Toggle expanded state
     pub fn toggle_expanded(&mut self) {
-        if self.is_dir {
-            self.expanded = !self.expanded;
-        }
+        self.set_expanded(!self.expanded);
     }
 
-    /// Set expanded state
+    /// Set expanded state (unreadable directories stay collapsed)
     pub fn set_expanded(&mut self, expanded: bool) {
-        if self.is_dir {
+        if self.is_dir && !(expanded && self.denied) {
             self.expanded = expanded;
         }
     }
@@ -82,24 +95,40 @@ impl TreeEntry {
     /// Uses `DirEntry::file_type()` to avoid extra stat() calls for better performance.
     /// For symlinks, falls back to `path.is_dir()` to follow the link.
     pub fn load_children(&mut self, show_hidden: bool) -> anyhow::Result<()> {
-        self.load_children_with_sort(show_hidden, SortMode::Name)
+        self.load_children_with_sort(show_hidden, SortMode::Name, false, true)
     }
 
     /// Load children from filesystem with specified sort mode
     ///
     /// Uses `DirEntry::file_type()` to avoid extra stat() calls for better performance.
     /// For symlinks, falls back to `path.is_dir()` to follow the link.
+    /// A directory that can't be listed for lack of permission is marked
+    /// [`denied`](Self::denied) and left empty instead of failing; entries
+    /// that can't be read individually are skipped.
     pub fn load_children_with_sort(
         &mut self,
         show_hidden: bool,
         sort_mode: SortMode,
+        reverse: bool,
+        dirs_first: bool,
     ) -> anyhow::Result<()> {
-        if !self.is_dir {
+        // Archive members are listed once, when the archive is mounted
+        if !self.is_dir || self.archive.is_some() {
             return Ok(());
         }
 
         self.children.clear();
-        let mut entries: Vec<_> = std::fs::read_dir(&self.path)?
+        let read_dir = match std::fs::read_dir(&self.path) {
+            Ok(read_dir) => read_dir,
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                self.denied = true;
+                self.expanded = false;
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+        self.denied = false;
+        let mut entries: Vec<_> = read_dir
             .filter_map(|e| e.ok())
             .filter(|e| {
                 if show_hidden {
@@ -126,44 +155,137 @@ impl TreeEntry {
             })
             .collect();
 
-        // Sort: directories first, then by sort mode
-        sort_entries(&mut entries, sort_mode);
+        // Sort: directories first (unless mixed), then by sort mode
+        sort_entries(&mut entries, sort_mode, reverse, dirs_first);
 
         self.children = entries;
         Ok(())
     }
 }
 
-/// Sort entries with directories first, then by sort mode
-pub fn sort_entries(entries: &mut [TreeEntry], sort_mode: SortMode) {
+/// Sort entries by sort mode, with directories first if `dirs_first`
+///
+/// `reverse` flips the order within directories and files, but with
+/// `dirs_first` directories always stay on top. Without it directories and
+/// files are intermixed by the sort key alone; directories have no size, so
+/// `SortMode::Size` still lists them after the files (by name).
+pub fn sort_entries(
+    entries: &mut [TreeEntry],
+    sort_mode: SortMode,
+    reverse: bool,
+    dirs_first: bool,
+) {
     entries.sort_by(|a, b| {
-        // Directories always come first
-        match (a.is_dir, b.is_dir) {
-            (true, false) => return std::cmp::Ordering::Less,
-            (false, true) => return std::cmp::Ordering::Greater,
-            _ => {}
+        if dirs_first {
+            match (a.is_dir, b.is_dir) {
+                (true, false) => return Ordering::Less,
+                (false, true) => return Ordering::Greater,
+                _ => {}
+            }
         }
 
-        match sort_mode {
-            SortMode::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-            SortMode::Size => {
+        let by_name = || a.name.to_lowercase().cmp(&b.name.to_lowercase());
+        let ordering = match sort_mode {
+            SortMode::Name => by_name(),
+            SortMode::Size => match (a.is_dir, b.is_dir) {
                 // For directories, sort by name (size doesn't make sense)
-                if a.is_dir {
-                    return a.name.to_lowercase().cmp(&b.name.to_lowercase());
+                (true, true) => by_name(),
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => {
+                    let a_size = a.path.metadata().map(|m| m.len()).unwrap_or(0);
+                    let b_size = b.path.metadata().map(|m| m.len()).unwrap_or(0);
+                    b_size.cmp(&a_size) // Descending (largest first)
                 }
-                let a_size = a.path.metadata().map(|m| m.len()).unwrap_or(0);
-                let b_size = b.path.metadata().map(|m| m.len()).unwrap_or(0);
-                b_size.cmp(&a_size) // Descending (largest first)
-            }
+            },
             SortMode::Date => {
                 let a_time = a.path.metadata().and_then(|m| m.modified()).ok();
                 let b_time = b.path.metadata().and_then(|m| m.modified()).ok();
                 b_time.cmp(&a_time) // Descending (newest first)
             }
+            SortMode::Extension => extension_key(a).cmp(&extension_key(b)).then_with(by_name),
+            SortMode::Type => type_rank(a).cmp(&type_rank(b)).then_with(by_name),
+            SortMode::Natural => natural_cmp(&a.name, &b.name).then_with(by_name),
+        };
+
+        if reverse {
+            ordering.reverse()
+        } else {
+            ordering
         }
     });
 }
 
+/// Compare names in natural order, as `SortMode::Natural` does
+///
+/// Runs of ASCII digits compare by value (`img2` < `img10`), everything else
+/// case-insensitively. Leading zeros are ignored (`007` = `7`).
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+    loop {
+        let ordering = match (a.peek(), b.peek()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let x = digit_run(&mut a);
+                let y = digit_run(&mut b);
+                let (x, y) = (x.trim_start_matches('0'), y.trim_start_matches('0'));
+                // Without leading zeros, the longer run is the larger number
+                x.len().cmp(&y.len()).then_with(|| x.cmp(y))
+            }
+            (Some(_), Some(_)) => {
+                let x = a.next().into_iter().flat_map(char::to_lowercase);
+                let y = b.next().into_iter().flat_map(char::to_lowercase);
+                x.cmp(y)
+            }
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// Consume the run of ASCII digits at the front of `chars`
+fn digit_run(chars: &mut Peekable<Chars>) -> String {
+    let mut run = String::new();
+    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        run.push(c);
+    }
+    run
+}
+
+/// Lowercase extension used by `SortMode::Extension` (empty for none)
+fn extension_key(entry: &TreeEntry) -> String {
+    entry
+        .path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Rank used by `SortMode::Type`: regular < executable < symlink < other
+fn type_rank(entry: &TreeEntry) -> u8 {
+    let Ok(meta) = entry.path.symlink_metadata() else {
+        return 3;
+    };
+    let file_type = meta.file_type();
+    if file_type.is_symlink() {
+        return 2;
+    }
+    if !file_type.is_file() {
+        return if file_type.is_dir() { 0 } else { 3 };
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if meta.permissions().mode() & 0o111 != 0 {
+            return 1;
+        }
+    }
+    0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -267,4 +389,167 @@ mod tests {
         entry.set_expanded(false);
         assert!(!entry.is_expanded());
     }
+
+    fn sorted_names(dir: &std::path::Path, mode: SortMode, reverse: bool) -> Vec<String> {
+        sorted_names_with(dir, mode, reverse, true)
+    }
+
+    fn sorted_names_with(
+        dir: &std::path::Path,
+        mode: SortMode,
+        reverse: bool,
+        dirs_first: bool,
+    ) -> Vec<String> {
+        let mut entry = TreeEntry::new(dir.to_path_buf(), 0);
+        entry
+            .load_children_with_sort(false, mode, reverse, dirs_first)
+            .unwrap();
+        entry.children().iter().map(|e| e.name.clone()).collect()
+    }
+
+    #[test]
+    fn test_dirs_mixed_with_files() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join("zeta")).unwrap();
+        fs::write(temp.path().join("alpha.txt"), "").unwrap();
+        fs::write(temp.path().join("omega.txt"), "").unwrap();
+
+        assert_eq!(
+            sorted_names_with(temp.path(), SortMode::Name, false, true),
+            vec!["zeta", "alpha.txt", "omega.txt"]
+        );
+        assert_eq!(
+            sorted_names_with(temp.path(), SortMode::Name, false, false),
+            vec!["alpha.txt", "omega.txt", "zeta"]
+        );
+        assert_eq!(
+            sorted_names_with(temp.path(), SortMode::Name, true, false),
+            vec!["zeta", "omega.txt", "alpha.txt"]
+        );
+        assert_eq!(
+            sorted_names_with(temp.path(), SortMode::Name, true, true),
+            vec!["zeta", "omega.txt", "alpha.txt"]
+        );
+    }
+
+    #[test]
+    fn test_dirs_mixed_by_extension() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join("src")).unwrap();
+        fs::write(temp.path().join("b.rs"), "").unwrap();
+        fs::write(temp.path().join("a"), "").unwrap();
+
+        // No extension sorts first, so the directory lands between the files
+        assert_eq!(
+            sorted_names_with(temp.path(), SortMode::Extension, false, false),
+            vec!["a", "src", "b.rs"]
+        );
+        assert_eq!(
+            sorted_names_with(temp.path(), SortMode::Extension, false, true),
+            vec!["src", "a", "b.rs"]
+        );
+    }
+
+    #[test]
+    fn test_sort_reverse_flips_order_keeps_dirs_first() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join("a_dir")).unwrap();
+        fs::create_dir(temp.path().join("b_dir")).unwrap();
+        fs::write(temp.path().join("a.txt"), "").unwrap();
+        fs::write(temp.path().join("b.txt"), "").unwrap();
+
+        assert_eq!(
+            sorted_names(temp.path(), SortMode::Name, false),
+            vec!["a_dir", "b_dir", "a.txt", "b.txt"]
+        );
+        assert_eq!(
+            sorted_names(temp.path(), SortMode::Name, true),
+            vec!["b_dir", "a_dir", "b.txt", "a.txt"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_size_reverse_is_smallest_first() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("big.txt"), "0123456789").unwrap();
+        fs::write(temp.path().join("small.txt"), "0").unwrap();
+
+        assert_eq!(
+            sorted_names(temp.path(), SortMode::Size, false),
+            vec!["big.txt", "small.txt"]
+        );
+        assert_eq!(
+            sorted_names(temp.path(), SortMode::Size, true),
+            vec!["small.txt", "big.txt"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_extension() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("a.rs"), "").unwrap();
+        fs::write(temp.path().join("b.md"), "").unwrap();
+        fs::write(temp.path().join("Makefile"), "").unwrap();
+
+        assert_eq!(
+            sorted_names(temp.path(), SortMode::Extension, false),
+            vec!["Makefile", "b.md", "a.rs"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sort_by_type() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("a_script"), "").unwrap();
+        fs::set_permissions(
+            temp.path().join("a_script"),
+            fs::Permissions::from_mode(0o755),
+        )
+        .unwrap();
+        fs::write(temp.path().join("b.txt"), "").unwrap();
+        std::os::unix::fs::symlink("b.txt", temp.path().join("a_link")).unwrap();
+
+        assert_eq!(
+            sorted_names(temp.path(), SortMode::Type, false),
+            vec!["b.txt", "a_script", "a_link"]
+        );
+    }
+
+    #[test]
+    fn test_natural_sort_compares_numbers_by_value() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join("v10")).unwrap();
+        fs::create_dir(temp.path().join("v9")).unwrap();
+        for name in ["img10.png", "IMG2.png", "img1.png", "img02b.png"] {
+            fs::write(temp.path().join(name), "").unwrap();
+        }
+
+        assert_eq!(
+            sorted_names(temp.path(), SortMode::Natural, false),
+            vec![
+                "v9",
+                "v10",
+                "img1.png",
+                "IMG2.png",
+                "img02b.png",
+                "img10.png"
+            ]
+        );
+        assert_eq!(
+            sorted_names(temp.path(), SortMode::Natural, true),
+            vec![
+                "v10",
+                "v9",
+                "img10.png",
+                "img02b.png",
+                "IMG2.png",
+                "img1.png"
+            ]
+        );
+        assert_eq!(natural_cmp("a007", "a7"), Ordering::Equal);
+        assert_eq!(natural_cmp("a", "a1"), Ordering::Less);
+    }
 }
@@ -1,47 +1,85 @@
 //! File system watcher for real-time updates
 
-use notify_debouncer_mini::{new_debouncer, Debouncer};
-use std::collections::HashSet;
+use notify::{Event, EventHandler, Watcher};
+use notify_debouncer_mini::{new_debouncer_opt, Config, DebouncedEvent, Debouncer};
+use std::cell::Cell;
+use std::collections::{BTreeSet, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::{channel, Receiver};
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
 use std::time::Duration;
 
-/// Directories to exclude from watching (common large/generated directories)
-const EXCLUDED_DIRS: &[&str] = &[
-    ".git",
-    "target",
-    "node_modules",
-    ".venv",
-    "venv",
-    "__pycache__",
-    ".cache",
-    "dist",
-    "build",
-    ".next",
-    ".nuxt",
-    "vendor",
-];
+use crate::core::EXCLUDED_DIRS;
+
+/// Debounced events as delivered by the watcher thread
+type EventResult = Result<Vec<DebouncedEvent>, notify::Error>;
+
+/// Outcome of draining the watcher's pending events
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchPoll {
+    /// Nothing happened
+    Idle,
+    /// These paths changed (sorted, without duplicates)
+    Changed(Vec<PathBuf>),
+    /// The watcher reported an error (e.g. its event queue overflowed), so
+    /// changes may have been missed and the tree should be fully reloaded
+    Lost,
+}
 
 /// File watcher with debouncing for real-time file system monitoring
+///
+/// By default only the root and the directories passed to
+/// [`sync_with_expanded`](Self::sync_with_expanded) are watched, so changes
+/// inside collapsed directories go unnoticed until they are expanded. A
+/// [`new_recursive`](Self::new_recursive) watcher sees the whole tree
+/// instead, at a cost: on Linux every directory below the root takes an
+/// inotify watch (a kernel descriptor slot and roughly 1 KiB of kernel
+/// memory each, capped by `fs.inotify.max_user_watches`), and the excluded
+/// directories are watched too, their events only dropped on arrival.
 pub struct FileWatcher {
-    debouncer: Debouncer<notify::RecommendedWatcher>,
-    rx: Receiver<Result<Vec<notify_debouncer_mini::DebouncedEvent>, notify::Error>>,
+    debouncer: Debouncer<RescanWatcher>,
+    rx: Receiver<EventResult>,
     watched_paths: HashSet<PathBuf>,
+    /// Root of a recursive watch (events in excluded directories are dropped)
+    recursive_root: Option<PathBuf>,
+    /// The watcher thread hung up (reported once)
+    disconnected: Cell<bool>,
 }
 
 impl FileWatcher {
     /// Create a new file watcher (initially watches only root)
     pub fn new(root: &Path) -> anyhow::Result<Self> {
+        Self::with_debounce(root, Duration::from_millis(500))
+    }
+
+    /// Create a watcher that reports bursts of events once `debounce` has passed
+    pub fn with_debounce(root: &Path, debounce: Duration) -> anyhow::Result<Self> {
+        Self::watch_root(root, debounce, notify::RecursiveMode::NonRecursive)
+    }
+
+    /// Create a watcher for everything below `root`, expanded or not
+    ///
+    /// Changes inside [`EXCLUDED_DIRS`] are not reported.
+    pub fn new_recursive(root: &Path) -> anyhow::Result<Self> {
+        Self::watch_root(
+            root,
+            Duration::from_millis(500),
+            notify::RecursiveMode::Recursive,
+        )
+    }
+
+    fn watch_root(
+        root: &Path,
+        debounce: Duration,
+        mode: notify::RecursiveMode,
+    ) -> anyhow::Result<Self> {
         let (tx, rx) = channel();
 
-        let mut debouncer = new_debouncer(Duration::from_millis(500), move |res| {
+        let config = Config::default().with_timeout(debounce);
+        let mut debouncer = new_debouncer_opt::<_, RescanWatcher>(config, move |res| {
             let _ = tx.send(res);
         })?;
 
-        // Watch root directory only (non-recursive)
-        debouncer
-            .watcher()
-            .watch(root, notify::RecursiveMode::NonRecursive)?;
+        debouncer.watcher().watch(root, mode)?;
 
         let mut watched_paths = HashSet::new();
         watched_paths.insert(root.to_path_buf());
@@ -50,18 +88,27 @@ impl FileWatcher {
             debouncer,
             rx,
             watched_paths,
+            recursive_root: (mode == notify::RecursiveMode::Recursive).then(|| root.to_path_buf()),
+            disconnected: Cell::new(false),
         })
     }
 
     /// Sync watched directories with expanded paths
     ///
-    /// Adds watches for newly expanded directories and removes watches for collapsed ones.
+    /// Adds watches for newly expanded directories and removes watches for
+    /// collapsed ones. A recursive watcher already covers those under its
+    /// root and only watches the ones outside it (e.g. reached via symlink).
     pub fn sync_with_expanded(&mut self, expanded_paths: &[PathBuf]) {
-        let new_set: HashSet<PathBuf> = expanded_paths
+        let root = self.recursive_root.as_deref();
+        let mut new_set: HashSet<PathBuf> = expanded_paths
             .iter()
             .filter(|p| !Self::is_excluded(p))
+            .filter(|p| root.is_none_or(|root| !p.starts_with(root)))
             .cloned()
             .collect();
+        if let Some(root) = root {
+            new_set.insert(root.to_path_buf());
+        }
 
         // Remove watches for collapsed directories
         for path in self.watched_paths.difference(&new_set) {
@@ -89,14 +136,257 @@ impl FileWatcher {
 
     /// Check for pending file change events (non-blocking)
     ///
-    /// Drains all pending events from the channel and returns true if any were found.
-    /// This prevents event buildup that could cause repeated expensive reloads.
-    pub fn poll(&self) -> bool {
-        let mut has_events = false;
-        // Drain all pending events to avoid buildup
-        while let Ok(Ok(_)) = self.rx.try_recv() {
-            has_events = true;
+    /// Drains all pending events from the channel so they cannot build up and
+    /// cause repeated expensive reloads. Watcher errors, and the watcher
+    /// thread going away, are reported as [`WatchPoll::Lost`].
+    pub fn poll(&self) -> WatchPoll {
+        let (poll, disconnected) = drain(&self.rx, self.recursive_root.as_deref());
+        if disconnected && !self.disconnected.replace(true) {
+            return WatchPoll::Lost;
+        }
+        poll
+    }
+
+    /// Wait up to `timeout` for file change events, then drain them
+    ///
+    /// Returns the changed paths (empty if nothing happened), or None if the
+    /// watcher reported an error and changes may have been missed.
+    pub fn wait_paths(&self, timeout: Duration) -> Option<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        let mut lost = false;
+        let mut next = self.rx.recv_timeout(timeout).ok();
+        while let Some(result) = next {
+            match result {
+                Ok(events) => paths.extend(self.relevant(events)),
+                Err(_) => lost = true,
+            }
+            next = self.rx.try_recv().ok();
+        }
+        (!lost).then_some(paths)
+    }
+
+    /// Drain pending events and return the changed paths (non-blocking)
+    ///
+    /// Each path is listed once. Unlike [`poll`](Self::poll), lost events
+    /// are not reported.
+    pub fn poll_paths(&self) -> Vec<PathBuf> {
+        match drain(&self.rx, self.recursive_root.as_deref()).0 {
+            WatchPoll::Changed(paths) => paths,
+            WatchPoll::Idle | WatchPoll::Lost => Vec::new(),
+        }
+    }
+
+    /// Paths of `events`, without those a recursive watch ignores
+    fn relevant(&self, events: Vec<DebouncedEvent>) -> impl Iterator<Item = PathBuf> + '_ {
+        events
+            .into_iter()
+            .map(|e| e.path)
+            .filter(|path| !in_excluded_dir(self.recursive_root.as_deref(), path))
+    }
+}
+
+/// The recommended watcher, reporting rescan notices as errors
+///
+/// The debouncer keeps only the paths of raw events, so a rescan notice (sent
+/// e.g. when the inotify queue overflows) would arrive as nothing at all.
+/// As an error it reaches [`drain`] and becomes [`WatchPoll::Lost`].
+struct RescanWatcher(notify::RecommendedWatcher);
+
+impl Watcher for RescanWatcher {
+    fn new<F: EventHandler>(mut event_handler: F, config: notify::Config) -> notify::Result<Self> {
+        let handler = move |res| event_handler.handle_event(rescan_as_error(res));
+        notify::RecommendedWatcher::new(handler, config).map(Self)
+    }
+
+    fn watch(&mut self, path: &Path, recursive_mode: notify::RecursiveMode) -> notify::Result<()> {
+        self.0.watch(path, recursive_mode)
+    }
+
+    fn unwatch(&mut self, path: &Path) -> notify::Result<()> {
+        self.0.unwatch(path)
+    }
+
+    fn configure(&mut self, option: notify::Config) -> notify::Result<bool> {
+        self.0.configure(option)
+    }
+
+    fn kind() -> notify::WatcherKind {
+        notify::RecommendedWatcher::kind()
+    }
+}
+
+/// Turn an event flagged for rescan into an error
+fn rescan_as_error(res: notify::Result<Event>) -> notify::Result<Event> {
+    match res {
+        Ok(event) if event.need_rescan() => Err(notify::Error::generic("events were missed")),
+        other => other,
+    }
+}
+
+/// Whether `path` lies in an excluded directory below the recursive `root`
+fn in_excluded_dir(root: Option<&Path>, path: &Path) -> bool {
+    let Some(relative) = root.and_then(|root| path.strip_prefix(root).ok()) else {
+        return false;
+    };
+    relative.components().any(|c| {
+        c.as_os_str()
+            .to_str()
+            .is_some_and(|name| EXCLUDED_DIRS.contains(&name))
+    })
+}
+
+/// Drain `rx`, returning what was seen and whether the sender hung up
+///
+/// Under a recursive `root`, events in excluded directories do not count.
+fn drain(rx: &Receiver<EventResult>, root: Option<&Path>) -> (WatchPoll, bool) {
+    let mut paths = BTreeSet::new();
+    let mut lost = false;
+    let disconnected = loop {
+        match rx.try_recv() {
+            Ok(Ok(events)) => paths.extend(
+                events
+                    .into_iter()
+                    .map(|e| e.path)
+                    .filter(|path| !in_excluded_dir(root, path)),
+            ),
+            Ok(Err(_)) => lost = true,
+            Err(TryRecvError::Empty) => break false,
+            Err(TryRecvError::Disconnected) => break true,
         }
-        has_events
+    };
+    let poll = if lost {
+        WatchPoll::Lost
+    } else if paths.is_empty() {
+        WatchPoll::Idle
+    } else {
+        WatchPoll::Changed(paths.into_iter().collect())
+    };
+    (poll, disconnected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify_debouncer_mini::DebouncedEventKind;
+
+    fn event(path: &str) -> EventResult {
+        Ok(vec![DebouncedEvent::new(
+            PathBuf::from(path),
+            DebouncedEventKind::Any,
+        )])
+    }
+
+    #[test]
+    fn test_drain_reports_changes() {
+        let (tx, rx) = channel();
+        assert_eq!(drain(&rx, None), (WatchPoll::Idle, false));
+
+        tx.send(event("/b")).unwrap();
+        tx.send(event("/a")).unwrap();
+        tx.send(event("/b")).unwrap();
+        assert_eq!(
+            drain(&rx, None),
+            (
+                WatchPoll::Changed(vec![PathBuf::from("/a"), PathBuf::from("/b")]),
+                false
+            )
+        );
+        assert_eq!(drain(&rx, None), (WatchPoll::Idle, false));
+    }
+
+    #[test]
+    fn test_drain_error_requests_recovery() {
+        let (tx, rx) = channel();
+        tx.send(event("/a")).unwrap();
+        tx.send(Err(notify::Error::generic("event queue overflow")))
+            .unwrap();
+        tx.send(event("/b")).unwrap();
+        // The error does not stop the drain, and wins over plain changes
+        assert_eq!(drain(&rx, None), (WatchPoll::Lost, false));
+        assert_eq!(drain(&rx, None), (WatchPoll::Idle, false));
+
+        drop(tx);
+        assert_eq!(drain(&rx, None), (WatchPoll::Idle, true));
+    }
+
+    #[test]
+    fn test_rescan_notice_requests_recovery() {
+        let change = Event::new(notify::EventKind::Any).add_path(PathBuf::from("/a"));
+        assert_eq!(rescan_as_error(Ok(change.clone())).unwrap(), change);
+
+        // The overflow notice carries no paths, only the flag
+        let rescan = Event::new(notify::EventKind::Other).set_flag(notify::event::Flag::Rescan);
+        let (tx, rx) = channel();
+        tx.send(event("/a")).unwrap();
+        tx.send(rescan_as_error(Ok(rescan)).map(|_| Vec::new()))
+            .unwrap();
+        assert_eq!(drain(&rx, None), (WatchPoll::Lost, false));
+    }
+
+    #[test]
+    fn test_recursive_drain_skips_excluded_dirs() {
+        let root = Path::new("/project");
+        let (tx, rx) = channel();
+        tx.send(event("/project/target/debug/app")).unwrap();
+        tx.send(event("/project/web/node_modules/x/index.js"))
+            .unwrap();
+        assert_eq!(drain(&rx, Some(root)), (WatchPoll::Idle, false));
+
+        tx.send(event("/project/target/debug/app")).unwrap();
+        tx.send(event("/project/src/deep/lib.rs")).unwrap();
+        assert_eq!(
+            drain(&rx, Some(root)),
+            (
+                WatchPoll::Changed(vec![PathBuf::from("/project/src/deep/lib.rs")]),
+                false
+            )
+        );
+
+        // Only names below the root count: a root inside `build/` still works
+        assert!(!in_excluded_dir(
+            Some(Path::new("/build/app")),
+            Path::new("/build/app/src/main.rs")
+        ));
+        assert!(!in_excluded_dir(None, Path::new("/project/target/x")));
+    }
+
+    #[test]
+    fn test_recursive_watcher_sees_collapsed_dirs() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("a/b")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        let watcher = FileWatcher::new_recursive(&root).unwrap();
+
+        std::fs::write(root.join("target/out.o"), "").unwrap();
+        std::fs::write(root.join("a/b/deep.txt"), "").unwrap();
+        let mut seen = Vec::new();
+        let deadline = std::time::Instant::now() + Duration::from_secs(10);
+        while !seen.iter().any(|p: &PathBuf| p.ends_with("deep.txt")) {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "no event for a/b/deep.txt"
+            );
+            seen.extend(watcher.wait_paths(Duration::from_millis(100)).unwrap());
+        }
+        assert!(seen.iter().all(|p| !p.starts_with(root.join("target"))));
+    }
+
+    #[test]
+    fn test_recursive_watcher_watches_dirs_outside_root() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().join("root");
+        let outside = temp.path().join("outside");
+        std::fs::create_dir_all(root.join("a")).unwrap();
+        std::fs::create_dir(&outside).unwrap();
+        let mut watcher = FileWatcher::new_recursive(&root).unwrap();
+
+        watcher.sync_with_expanded(&[root.clone(), root.join("a"), outside.clone()]);
+        let expected: HashSet<PathBuf> = [root.clone(), outside].into_iter().collect();
+        assert_eq!(watcher.watched_paths, expected);
+
+        // Collapsing the outside directory keeps the recursive root
+        watcher.sync_with_expanded(std::slice::from_ref(&root));
+        assert_eq!(watcher.watched_paths, HashSet::from([root]));
     }
 }